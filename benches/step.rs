@@ -0,0 +1,77 @@
+use std::hint::black_box;
+
+use criterion::{BatchSize, BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use plant_growth_simulation::{config, map, types};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+/// The number of steps taken in each measured iteration
+const STEPS: usize = 10;
+
+/// The map sizes to measure
+const SIZES: [types::ISize; 3] = [
+    types::ISize { w: 100, h: 50 },
+    types::ISize { w: 200, h: 100 },
+    types::ISize { w: 400, h: 200 },
+];
+
+/// The fractions of tiles starting with a plant
+const DENSITIES: [f64; 3] = [0.0, 0.1, 0.5];
+
+/// The sun intensity of the default settings
+type Intensity =
+    map::sun::IntensityYearDay<map::sun::IntensityYearPlanet, map::sun::IntensityDayPlanet>;
+
+/// Constructs a map with the default settings where a fraction of the tiles
+/// start with a placed plant, the same size and density always give the same
+/// map
+///
+/// # Parameters
+///
+/// size: The size of the map
+///
+/// density: The fraction of tiles to place a plant on
+fn new_populated_map(size: types::ISize, density: f64) -> map::Map<Intensity> {
+    let settings = config::map::Settings::new();
+    let mut map = map::Map::new(size, settings.settings, settings.sun.get_intensity());
+
+    let mut rng = StdRng::seed_from_u64(0);
+    for y in 0..size.h as isize {
+        for x in 0..size.w as isize {
+            if rng.random_bool(density) {
+                let bulk = map::EditBulk::from_id(rng.random_range(0..map::EditBulk::COUNT));
+                map.place_plant(&types::Index::new(x, y), bulk);
+            }
+        }
+    }
+
+    return map;
+}
+
+/// Measures the number of steps per second for all map sizes and densities
+fn bench_step(c: &mut Criterion) {
+    let mut group = c.benchmark_group("step");
+    group.throughput(Throughput::Elements(STEPS as u64));
+    for size in SIZES {
+        for density in DENSITIES {
+            let map = new_populated_map(size, density);
+            group.bench_with_input(
+                BenchmarkId::new(format!("{}x{}", size.w, size.h), density),
+                &map,
+                |b, map| {
+                    b.iter_batched(
+                        || map.clone(),
+                        |mut map| {
+                            map.step_n(STEPS);
+                            black_box(map)
+                        },
+                        BatchSize::LargeInput,
+                    );
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_step);
+criterion_main!(benches);
@@ -0,0 +1,77 @@
+use thiserror::Error;
+
+use crate::render;
+
+/// The errors which may occur while running the viewer
+#[derive(Error, Debug)]
+pub enum ApplicationError {
+    /// The event loop could not be created
+    #[error("Unable to create event loop: {:?}", .0)]
+    CreateEventLoop(winit::error::EventLoopError),
+    /// The event loop stopped because of an error
+    #[error("An error occured in the main loop: {:?}", .0)]
+    RunEventLoop(winit::error::EventLoopError),
+    /// A window could not be opened
+    #[error("Unable to create window: {:?}", .0)]
+    CreateWindow(winit::error::OsError),
+    /// The render state of a window could not be created
+    #[error("Unable to add render state: {}", .0)]
+    RenderState(render::NewRenderStateError),
+    /// The texture to render the next frame of a window to could not be
+    /// retrieved
+    #[error("Unable to get texture: {}", .0)]
+    Surface(wgpu::SurfaceError),
+}
+
+impl ApplicationError {
+    /// Checks if the viewer can keep running after the error by skipping the
+    /// frame, the gpu running out of memory is not recoverable
+    pub fn is_recoverable(&self) -> bool {
+        return matches!(
+            self,
+            Self::Surface(
+                wgpu::SurfaceError::Lost
+                    | wgpu::SurfaceError::Outdated
+                    | wgpu::SurfaceError::Timeout
+                    | wgpu::SurfaceError::Other
+            )
+        );
+    }
+
+    /// Checks if the frame should be tried again right away, this is the case
+    /// when the surface has been configured again after being lost or
+    /// outdated
+    pub fn is_retried(&self) -> bool {
+        return matches!(
+            self,
+            Self::Surface(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated)
+        );
+    }
+
+    /// Checks if the error is expected to pass by itself, like the surface
+    /// being outdated while the window is resized
+    pub fn is_transient(&self) -> bool {
+        return matches!(
+            self,
+            Self::Surface(wgpu::SurfaceError::Outdated | wgpu::SurfaceError::Timeout)
+        );
+    }
+}
+
+impl From<winit::error::OsError> for ApplicationError {
+    fn from(value: winit::error::OsError) -> Self {
+        return Self::CreateWindow(value);
+    }
+}
+
+impl From<render::NewRenderStateError> for ApplicationError {
+    fn from(value: render::NewRenderStateError) -> Self {
+        return Self::RenderState(value);
+    }
+}
+
+impl From<wgpu::SurfaceError> for ApplicationError {
+    fn from(value: wgpu::SurfaceError) -> Self {
+        return Self::Surface(value);
+    }
+}
@@ -0,0 +1,119 @@
+use std::path::{Path, PathBuf};
+
+use crate::{map, report, runner, server};
+
+/// Runs the simulation without opening a window until one of the stop
+/// conditions is met, and writes the summary report and the ancestry tree of
+/// the surviving lineages. There is no one to
+/// continue a paused run so the run always ends
+///
+/// # Parameters
+///
+/// runner: The runner of the map to simulate
+///
+/// export_directory: The directory to save the report and the ancestry tree
+/// to
+///
+/// phylogeny_format: The format to write the ancestry tree in
+///
+/// stats_exporter: The exporter to stream the statistics of every step to if
+/// any
+///
+/// map_server: The server to stream the map of every step to web viewers
+/// with if any
+pub fn run_headless<S: map::sun::Intensity>(
+    runner: &mut runner::Runner<S>,
+    export_directory: &Path,
+    phylogeny_format: report::TreeFormat,
+    mut stats_exporter: Option<report::StatsExporter>,
+    map_server: Option<server::MapServer>,
+) -> report::EndReason {
+    export_statistics(&mut stats_exporter, &runner.get_map().get_statistics());
+    if let Some(map_server) = &map_server {
+        map_server.send(runner.get_map());
+    }
+
+    let reason = loop {
+        if let Some(reason) = runner.step() {
+            break reason;
+        }
+
+        export_statistics(&mut stats_exporter, &runner.get_map().get_statistics());
+        if let Some(map_server) = &map_server {
+            map_server.send(runner.get_map());
+        }
+    };
+    let map = runner.get_map();
+    println!("Run ended at step {}: {}", map.get_time(), reason);
+
+    if let Some(Err(error)) = stats_exporter.as_mut().map(|exporter| exporter.flush()) {
+        eprintln!("Unable to export statistics: {:?}", error);
+    }
+
+    let summary = runner.get_report(reason);
+    let path = export_directory.join(format!("report_step_{}.md", map.get_time()));
+    match summary.save(&path) {
+        Ok(()) => println!("Saved run report to {}", path.display()),
+        Err(error) => eprintln!("Unable to save run report: {:?}", error),
+    }
+    save_phylogeny(map, export_directory, phylogeny_format);
+
+    return reason;
+}
+
+/// Writes the ancestry tree of the surviving lineages to a file named after
+/// the current time step, returns the path of the file or None if it could
+/// not be saved
+///
+/// # Parameters
+///
+/// map: The map to get the lineages from
+///
+/// export_directory: The directory to save the tree to
+///
+/// format: The format to write the tree in
+pub(super) fn save_phylogeny<S: map::sun::Intensity>(
+    map: &map::Map<S>,
+    export_directory: &Path,
+    format: report::TreeFormat,
+) -> Option<PathBuf> {
+    let path = export_directory.join(format!(
+        "phylogeny_step_{}.{}",
+        map.get_time(),
+        format.get_extension()
+    ));
+    return match report::Phylogeny::new(map).save(&path, format) {
+        Ok(()) => {
+            println!("Saved ancestry tree to {}", path.display());
+            Some(path)
+        }
+        Err(error) => {
+            eprintln!("Unable to save ancestry tree: {:?}", error);
+            None
+        }
+    };
+}
+
+/// Writes the statistics of a step to the exporter, the export is stopped if
+/// the file can no longer be written to
+///
+/// # Parameters
+///
+/// stats_exporter: The exporter to write to if any
+///
+/// statistics: The statistics to write
+pub(super) fn export_statistics(
+    stats_exporter: &mut Option<report::StatsExporter>,
+    statistics: &map::Statistics,
+) {
+    if let Some(Err(error)) = stats_exporter
+        .as_mut()
+        .map(|exporter| exporter.write(statistics))
+    {
+        eprintln!(
+            "Unable to export statistics, stopping the export: {:?}",
+            error
+        );
+        *stats_exporter = None;
+    }
+}
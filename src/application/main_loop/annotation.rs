@@ -0,0 +1,113 @@
+use std::path::PathBuf;
+
+use crate::{map, types};
+
+use super::{AnnotationMarker, MainLoop, get_timestamp};
+
+impl<S: map::sun::Intensity> MainLoop<S> {
+    /// Finds the position in pixels of every note on the map, a note is
+    /// placed at the copy of its position closest to the center of the view
+    /// if the map wraps
+    pub(super) fn get_annotation_markers(&self) -> Vec<AnnotationMarker> {
+        let size = self.settings_window.size;
+        if size.width == 0 || size.height == 0 {
+            return Vec::new();
+        }
+
+        let view_center = self.camera.get_transform().inv() * types::Point::new(0.0, 0.0);
+        let map_width = self.camera.get_settings().map_width;
+
+        return self
+            .map
+            .get_annotations()
+            .get_all()
+            .iter()
+            .map(|annotation| {
+                let center = self
+                    .map
+                    .get_annotation_center(annotation, &*self.settings_shader.grid_layout);
+                let copy = ((view_center.x - center.x) / map_width).round();
+                let center = types::Point::new(center.x + copy * map_width, center.y);
+
+                return AnnotationMarker {
+                    annotation: annotation.clone(),
+                    pixel: self.world_to_pixel(&center),
+                };
+            })
+            .collect();
+    }
+
+    /// Converts a point in world coordinates to a position in pixels from the
+    /// top left corner of the window, the inverse of pixel_to_world
+    ///
+    /// # Parameters
+    ///
+    /// point: The point in world coordinates
+    fn world_to_pixel(&self, point: &types::Point) -> types::Point {
+        let size = self.settings_window.size;
+        let screen = self.camera.get_transform() * point;
+
+        return types::Point::new(
+            0.5 * (screen.x + 1.0) * size.width as f64,
+            0.5 * (1.0 - screen.y) * size.height as f64,
+        );
+    }
+
+    /// Retrieves the notes pinned to the selected tile or to the organism
+    /// growing on it
+    pub(super) fn get_selected_annotations(&self) -> Vec<map::Annotation> {
+        let Some(pos) = self.state.selected_tile else {
+            return Vec::new();
+        };
+        let lineage = self.map.get_tile(&pos).and_then(|tile| tile.get_lineage());
+
+        return self
+            .map
+            .get_annotations()
+            .get_all()
+            .iter()
+            .filter(|annotation| {
+                annotation.pos == pos
+                    || (annotation.lineage.is_some() && annotation.lineage == lineage)
+            })
+            .cloned()
+            .collect();
+    }
+
+    /// Pins a new note to the selected tile or the organism growing on it
+    ///
+    /// # Parameters
+    ///
+    /// text: The text of the note
+    ///
+    /// pin_organism: True if the note should follow the organism on the tile
+    pub(super) fn add_selected_annotation(&mut self, text: &str, pin_organism: bool) {
+        let Some(pos) = self.state.selected_tile else {
+            return;
+        };
+        if let Some(id) = self.map.add_annotation(pos, text.to_string(), pin_organism) {
+            println!("Added note {} at tile ({}, {})", id, pos.x, pos.y);
+        }
+    }
+
+    /// Saves a snapshot of every tile and all notes on the map as a TOML file
+    /// named after the current time, returns the path of the file or None if
+    /// it could not be saved
+    pub(super) fn save_snapshot(&self) -> Option<PathBuf> {
+        let path = self.settings_viewer.export_directory.join(format!(
+            "snapshot_{}_step_{}.toml",
+            get_timestamp(),
+            self.map.get_time()
+        ));
+        return match self.map.get_snapshot().save(&path) {
+            Ok(()) => {
+                println!("Saved snapshot to {}", path.display());
+                Some(path)
+            }
+            Err(error) => {
+                eprintln!("Unable to save snapshot: {}", error);
+                None
+            }
+        };
+    }
+}
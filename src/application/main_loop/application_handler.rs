@@ -1,28 +1,45 @@
-use std::time::Instant;
-
+use web_time::Instant;
 use winit::{application::ApplicationHandler, event::StartCause, event_loop::ActiveEventLoop};
 
 use crate::map;
 
-use super::{MainLoop, OptionalRenderedWindow};
+use super::{MainLoop, OptionalRenderedWindow, RenderedWindow, UserEvent};
 
-impl<S: map::sun::Intensity> ApplicationHandler for MainLoop<S> {
+impl<S: map::sun::Intensity> ApplicationHandler<UserEvent> for MainLoop<S> {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         self.new_window(event_loop);
     }
 
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: UserEvent) {
+        match event {
+            UserEvent::RenderState(window, render_state) => {
+                let rendered_window = render_state.map(|render_state| {
+                    RenderedWindow::from_render_state(
+                        window,
+                        render_state,
+                        self.settings_window.graphics_settings.clone(),
+                        &self.map,
+                    )
+                });
+                self.open_window(event_loop, rendered_window);
+                if self.window.is_open() {
+                    self.request_redraw();
+                }
+            }
+        }
+    }
+
     fn window_event(
         &mut self,
         event_loop: &ActiveEventLoop,
         window_id: winit::window::WindowId,
         event: winit::event::WindowEvent,
     ) {
-        // Get the window
-        let window = self.window.get();
-
         // Find the correct window and handle event correspondingly
-        if window_id == window.window.id() {
+        if Some(window_id) == self.window.get_id() {
             self.main_window_event(event_loop, event);
+        } else if Some(window_id) == self.stats_window.get_id() {
+            self.stats_window_event(event);
         }
     }
 
@@ -38,12 +55,14 @@ impl<S: map::sun::Intensity> ApplicationHandler for MainLoop<S> {
     }
 
     fn suspended(&mut self, _event_loop: &ActiveEventLoop) {
-        // Close the window
+        // Close the windows
+        self.stats_window = OptionalRenderedWindow::empty();
         self.window = OptionalRenderedWindow::empty();
     }
 
     fn exiting(&mut self, _event_loop: &ActiveEventLoop) {
-        // Close the window
+        // Close the windows
+        self.stats_window = OptionalRenderedWindow::empty();
         self.window = OptionalRenderedWindow::empty();
     }
 }
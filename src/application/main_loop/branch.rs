@@ -0,0 +1,76 @@
+use std::mem;
+
+use crate::map;
+
+use super::{History, MainLoop};
+
+/// The runs a user can switch between after forking the simulation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Branch {
+    /// The run started with the application, only this run is recorded in
+    /// the statistics and the report
+    Original,
+    /// A copy of the original run made while it was paused
+    Fork,
+}
+
+impl Branch {
+    /// Gets the name of the branch for displaying
+    pub fn get_name(&self) -> &'static str {
+        return match self {
+            Self::Original => "Original",
+            Self::Fork => "Fork",
+        };
+    }
+
+    /// Gets the other branch
+    pub fn other(&self) -> Self {
+        return match self {
+            Self::Original => Self::Fork,
+            Self::Fork => Self::Original,
+        };
+    }
+}
+
+/// The run which is not currently shown
+#[derive(Clone, Debug)]
+pub struct InactiveRun<S: map::sun::Intensity> {
+    /// The map of the run
+    map: map::Map<S>,
+    /// The recent states of the run for stepping backwards
+    history: History<S>,
+}
+
+impl<S: map::sun::Intensity> MainLoop<S> {
+    /// Copies the original run into the fork, replacing any earlier fork, and
+    /// switches to the copy with the simulation paused
+    pub(super) fn fork(&mut self) {
+        if self.branch != Branch::Original {
+            println!("Switch to the original run to fork it");
+            return;
+        }
+
+        self.inactive_run = Some(InactiveRun {
+            map: self.map.clone(),
+            history: self.history.clone(),
+        });
+        println!("Forked the run at step {}", self.map.get_time());
+        self.switch_branch();
+    }
+
+    /// Switches to the other run and pauses the simulation, returns true if
+    /// there was another run to switch to
+    pub(super) fn switch_branch(&mut self) -> bool {
+        let Some(inactive_run) = self.inactive_run.as_mut() else {
+            println!("Press F to fork the simulation before switching runs");
+            return false;
+        };
+        mem::swap(&mut self.map, &mut inactive_run.map);
+        mem::swap(&mut self.history, &mut inactive_run.history);
+        self.branch = self.branch.other();
+        self.state.flags.run_simulation = false;
+        self.reload_map();
+
+        return true;
+    }
+}
@@ -0,0 +1,119 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use web_time::{Duration, Instant};
+
+use crate::{config, constants, map};
+
+use super::MainLoop;
+
+/// Checks a config file for changes at a fixed interval such that the
+/// simulation settings can be edited while the simulation is running
+#[derive(Clone, Debug)]
+pub(super) struct ConfigWatcher {
+    /// The path of the config file
+    path: PathBuf,
+    /// The time the config file was last modified when it was last checked
+    modified: Option<SystemTime>,
+    /// The time to check the config file next
+    next_check: Instant,
+}
+
+impl ConfigWatcher {
+    /// Starts watching a config file, the current content is seen as loaded
+    ///
+    /// # Parameters
+    ///
+    /// path: The path of the config file
+    pub(super) fn new(path: &Path) -> Self {
+        return Self {
+            path: path.to_path_buf(),
+            modified: get_modified(path),
+            next_check: Instant::now(),
+        };
+    }
+
+    /// Loads the config file if it has been modified since the last time it
+    /// was checked, None if it is too early to check or it has not changed
+    ///
+    /// # Parameters
+    ///
+    /// now: The current time
+    fn poll(&mut self, now: Instant) -> Option<Result<config::Config, config::ConfigError>> {
+        if now < self.next_check {
+            return None;
+        }
+        self.next_check = now + Duration::from_secs_f64(constants::CONFIG_POLL_INTERVAL);
+
+        let modified = get_modified(&self.path);
+        if modified == self.modified {
+            return None;
+        }
+        self.modified = modified;
+
+        return Some(config::Config::load(&self.path));
+    }
+}
+
+impl<S: map::sun::Intensity> MainLoop<S> {
+    /// Applies the simulation settings of the watched config file to the
+    /// shown run if the file has been saved since it was last checked, the
+    /// seed of the run is kept
+    ///
+    /// # Parameters
+    ///
+    /// now: The current time
+    pub(super) fn update_config(&mut self, now: Instant) {
+        let Some(result) = self
+            .config_watcher
+            .as_mut()
+            .and_then(|watcher| watcher.poll(now))
+        else {
+            return;
+        };
+
+        match result {
+            Ok(config) => {
+                // A preset named in the config replaces its settings like
+                // when the run was started
+                let settings = match config
+                    .map
+                    .preset
+                    .as_deref()
+                    .map(|name| self.settings_viewer.presets.load(name))
+                {
+                    Some(Ok(settings)) => settings,
+                    Some(Err(error)) => {
+                        eprintln!("Unable to load preset, using config settings: {}", error);
+                        config.map.settings
+                    }
+                    None => config.map.settings,
+                };
+                let seed = self.map.get_settings().seed;
+                self.map.set_settings(settings.with_seed(seed));
+                println!(
+                    "Reloaded the simulation settings into the {} run",
+                    self.branch.get_name().to_lowercase()
+                );
+            }
+            Err(error) => eprintln!(
+                "Unable to reload the config, keeping the settings: {}",
+                error
+            ),
+        }
+    }
+}
+
+/// Retrieves the time a file was last modified, None if it could not be read
+///
+/// # Parameters
+///
+/// path: The path of the file
+fn get_modified(path: &Path) -> Option<SystemTime> {
+    return fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok();
+}
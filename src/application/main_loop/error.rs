@@ -0,0 +1,20 @@
+use crate::map;
+
+use super::{ApplicationError, MainLoop};
+
+impl<S: map::sun::Intensity> MainLoop<S> {
+    /// Reports an error of the viewer, recoverable errors which do not pass
+    /// by themselves are kept to be shown in the overlay until they are
+    /// dismissed
+    ///
+    /// # Parameters
+    ///
+    /// error: The error to report
+    pub(super) fn report_error(&mut self, error: ApplicationError) {
+        eprintln!("{}", error);
+
+        if error.is_recoverable() && !error.is_transient() {
+            self.error = Some(error);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use winit::dpi::PhysicalPosition;
+
+use crate::{map, types};
+
+use super::MainLoop;
+
+impl<S: map::sun::Intensity> MainLoop<S> {
+    /// Zooms the view towards the cursor when pinching on a touchpad
+    ///
+    /// # Parameters
+    ///
+    /// delta: The change in magnification, positive values zoom in
+    pub(super) fn main_window_pinch_gesture(&mut self, delta: f64) {
+        if !delta.is_finite() {
+            return;
+        }
+
+        // Zoom towards the center of the screen if the cursor is not known
+        let pivot = self
+            .get_cursor_world()
+            .unwrap_or_else(|| self.camera.get_transform().inv() * types::Point::new(0.0, 0.0));
+
+        if self.camera.apply_zoom(1.0 + delta, &pivot) {
+            self.window.get().window.request_redraw();
+        }
+    }
+
+    /// Moves the view along with the fingers when panning on a touchpad
+    ///
+    /// # Parameters
+    ///
+    /// delta: The distance moved in pixels, positive values move the map
+    /// right and down
+    pub(super) fn main_window_pan_gesture(&mut self, delta: PhysicalPosition<f64>) {
+        let size = self.settings_window.size;
+        if size.width == 0 || size.height == 0 {
+            return;
+        }
+
+        // The conversion is affine so any start position gives the same offset
+        let start = types::Point::new(0.0, 0.0);
+        let offset =
+            self.pixel_to_world(&types::Point::new(delta.x, delta.y)) - self.pixel_to_world(&start);
+        self.camera.apply_drag(&offset);
+        self.window.get().window.request_redraw();
+    }
+}
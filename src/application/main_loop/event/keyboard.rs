@@ -4,7 +4,7 @@ use winit::{
     keyboard::{KeyCode, PhysicalKey},
 };
 
-use crate::map;
+use crate::{map, report};
 
 use super::{ChangeMode, MainLoop};
 
@@ -69,8 +69,8 @@ impl<S: map::sun::Intensity> MainLoop<S> {
             PhysicalKey::Unidentified(_) => (),
             PhysicalKey::Code(code) => match code {
                 KeyCode::Escape => {
-                    // Close the application
-                    event_loop.exit();
+                    // End the run and close the application
+                    self.end_run(event_loop, report::EndReason::UserQuit);
                 }
                 KeyCode::KeyH => {
                     // Return to home view
@@ -80,6 +80,7 @@ impl<S: map::sun::Intensity> MainLoop<S> {
                 KeyCode::Space => {
                     // Toggle the simulation
                     self.state.flags.run_simulation = !self.state.flags.run_simulation;
+                    update = true;
                 }
                 KeyCode::Tab => {
                     // Change the speed of the simulation
@@ -88,50 +89,180 @@ impl<S: map::sun::Intensity> MainLoop<S> {
                     } else {
                         self.settings_viewer.sim_rate *= self.settings_viewer.sim_rate_mod;
                     }
+                    update = true;
                 }
                 KeyCode::ShiftLeft => {
                     // Toggle the shift key
                     self.state.flags.left_shift_active = true;
                 }
+                KeyCode::ControlLeft => {
+                    // Toggle the control key
+                    self.state.flags.left_ctrl_active = true;
+                }
+                KeyCode::AltLeft => {
+                    // Toggle the alt key
+                    self.state.flags.left_alt_active = true;
+                }
+                KeyCode::KeyP => {
+                    // Export the current map settings as a new preset
+                    self.export_preset();
+                }
+                KeyCode::KeyT => {
+                    // Save a thumbnail of the map
+                    _ = self.save_thumbnail();
+                }
+                KeyCode::KeyV => {
+                    // Save a snapshot of the map with all notes
+                    _ = self.save_snapshot();
+                }
+                KeyCode::F12 => {
+                    // Save a screenshot of the current frame
+                    _ = self.save_screenshot();
+                }
+                KeyCode::F1 => {
+                    // Show or hide the overlay
+                    self.state.flags.show_overlay = !self.state.flags.show_overlay;
+                    update = true;
+                }
+                KeyCode::F2 => {
+                    // Enter or leave the edit mode
+                    self.state.flags.edit_mode = !self.state.flags.edit_mode;
+                    update = true;
+                }
+                KeyCode::F3 => {
+                    // Show or hide the profiler
+                    self.state.flags.show_profiler = !self.state.flags.show_profiler;
+                    update = true;
+                }
+                KeyCode::F4 => {
+                    // Show or hide the column profile
+                    self.state.flags.show_columns = !self.state.flags.show_columns;
+                    update = true;
+                }
+                KeyCode::KeyB => {
+                    // Change the bulk type placed in the edit mode
+                    self.state.edit_bulk = if self.state.flags.left_shift_active {
+                        self.state.edit_bulk.prev()
+                    } else {
+                        self.state.edit_bulk.next()
+                    };
+                    update = true;
+                }
+                KeyCode::KeyG => {
+                    // Show or hide the energy transfer arrows or the
+                    // outlines of the tiles
+                    if self.state.flags.left_shift_active {
+                        self.toggle_transfers();
+                    } else {
+                        self.toggle_borders();
+                    }
+                }
+                KeyCode::KeyX => {
+                    // Export the genome of the selected plant, simplified if
+                    // ctrl is held, or place a plant with the exported genome
+                    // on the selected tile
+                    if self.state.flags.left_shift_active {
+                        update |= self.inject_genome();
+                    } else {
+                        self.export_genome(self.state.flags.left_ctrl_active);
+                    }
+                }
+                KeyCode::KeyY => {
+                    // Export the ancestry tree of the surviving lineages
+                    self.export_phylogeny();
+                }
+                KeyCode::KeyM => {
+                    // Change the plant display mode
+                    if self.state.flags.left_shift_active {
+                        self.change_mode_plant(&ChangeMode::Prev);
+                    } else {
+                        self.change_mode_plant(&ChangeMode::Next);
+                    }
+                }
+                KeyCode::KeyC => {
+                    // Change the scaling of the background color map
+                    if self.state.flags.left_shift_active {
+                        self.change_scaling_background(&ChangeMode::Prev);
+                    } else {
+                        self.change_scaling_background(&ChangeMode::Next);
+                    }
+                }
+                KeyCode::KeyR => {
+                    // Start or stop recording frames
+                    self.toggle_recording();
+                }
+                KeyCode::KeyF => {
+                    // Switch between the original and the forked run or fork
+                    // the original run
+                    if self.state.flags.left_shift_active {
+                        self.switch_branch();
+                    } else {
+                        self.fork();
+                    }
+                    update = true;
+                }
+                KeyCode::KeyI => {
+                    // Open or close the statistics window
+                    self.toggle_stats_window(event_loop);
+                }
+                KeyCode::KeyO => {
+                    // Follow the lineage of the selected plant with the camera
+                    self.toggle_follow();
+                    update = true;
+                }
+                KeyCode::KeyL => {
+                    // Load the next settings preset into the shown run
+                    self.load_next_preset();
+                }
                 KeyCode::Digit1 => {
-                    // Go to background display mode 0
-                    self.change_mode_background(&ChangeMode::Id(0));
+                    // Go to background display mode 0 or save or recall
+                    // view bookmark 0
+                    update |= self.main_window_digit_pressed(0);
                 }
                 KeyCode::Digit2 => {
-                    // Go to background display mode 1
-                    self.change_mode_background(&ChangeMode::Id(1));
+                    // Go to background display mode 1 or save or recall
+                    // view bookmark 1
+                    update |= self.main_window_digit_pressed(1);
                 }
                 KeyCode::Digit3 => {
-                    // Go to background display mode 2
-                    self.change_mode_background(&ChangeMode::Id(2));
+                    // Go to background display mode 2 or save or recall
+                    // view bookmark 2
+                    update |= self.main_window_digit_pressed(2);
                 }
                 KeyCode::Digit4 => {
-                    // Go to background display mode 3
-                    self.change_mode_background(&ChangeMode::Id(3));
+                    // Go to background display mode 3 or save or recall
+                    // view bookmark 3
+                    update |= self.main_window_digit_pressed(3);
                 }
                 KeyCode::Digit5 => {
-                    // Go to background display mode 4
-                    self.change_mode_background(&ChangeMode::Id(4));
+                    // Go to background display mode 4 or save or recall
+                    // view bookmark 4
+                    update |= self.main_window_digit_pressed(4);
                 }
                 KeyCode::Digit6 => {
-                    // Go to background display mode 5
-                    self.change_mode_background(&ChangeMode::Id(5));
+                    // Go to background display mode 5 or save or recall
+                    // view bookmark 5
+                    update |= self.main_window_digit_pressed(5);
                 }
                 KeyCode::Digit7 => {
-                    // Go to background display mode 6
-                    self.change_mode_background(&ChangeMode::Id(6));
+                    // Go to background display mode 6 or save or recall
+                    // view bookmark 6
+                    update |= self.main_window_digit_pressed(6);
                 }
                 KeyCode::Digit8 => {
-                    // Go to background display mode 7
-                    self.change_mode_background(&ChangeMode::Id(7));
+                    // Go to background display mode 7 or save or recall
+                    // view bookmark 7
+                    update |= self.main_window_digit_pressed(7);
                 }
                 KeyCode::Digit9 => {
-                    // Go to background display mode 8
-                    self.change_mode_background(&ChangeMode::Id(8));
+                    // Go to background display mode 8 or save or recall
+                    // view bookmark 8
+                    update |= self.main_window_digit_pressed(8);
                 }
                 KeyCode::Digit0 => {
-                    // Go to background display mode 9
-                    self.change_mode_background(&ChangeMode::Id(9));
+                    // Go to background display mode 9 or save or recall
+                    // view bookmark 9
+                    update |= self.main_window_digit_pressed(9);
                 }
                 _ => (),
             },
@@ -140,6 +271,27 @@ impl<S: map::sun::Intensity> MainLoop<S> {
         return update;
     }
 
+    /// Handles a pressed digit key, returns true if an update is needed. With
+    /// control held the current view is saved as a bookmark, with alt held
+    /// the camera moves to the bookmark and otherwise the background display
+    /// mode is changed
+    ///
+    /// # Parameters
+    ///
+    /// id: The index of the digit key, the 1 key is 0 and the 0 key is 9
+    fn main_window_digit_pressed(&mut self, id: usize) -> bool {
+        if self.state.flags.left_ctrl_active {
+            self.save_bookmark(id);
+            return false;
+        }
+        if self.state.flags.left_alt_active {
+            return self.recall_bookmark(id);
+        }
+
+        self.change_mode_background(&ChangeMode::Id(id));
+        return false;
+    }
+
     /// Handles all keys release, returns true if an update is needed
     ///
     /// # Parameters
@@ -159,6 +311,14 @@ impl<S: map::sun::Intensity> MainLoop<S> {
                     // Toggle the shift key
                     self.state.flags.left_shift_active = false;
                 }
+                KeyCode::ControlLeft => {
+                    // Toggle the control key
+                    self.state.flags.left_ctrl_active = false;
+                }
+                KeyCode::AltLeft => {
+                    // Toggle the alt key
+                    self.state.flags.left_alt_active = false;
+                }
                 _ => (),
             },
         };
@@ -178,6 +338,7 @@ impl<S: map::sun::Intensity> MainLoop<S> {
         _event_loop: &ActiveEventLoop,
         key: PhysicalKey,
     ) -> bool {
+        let mut update = false;
         match key {
             PhysicalKey::Unidentified(_) => (),
             PhysicalKey::Code(code) => match code {
@@ -185,6 +346,10 @@ impl<S: map::sun::Intensity> MainLoop<S> {
                     // Forward the simulation once
                     self.state.flags.iterate_simulation = true;
                 }
+                KeyCode::Backspace => {
+                    // Go back to the previous saved state of the simulation
+                    update = self.rewind();
+                }
                 KeyCode::ArrowRight => {
                     // Go to the next background display mode
                     self.change_mode_background(&ChangeMode::Next);
@@ -197,6 +362,6 @@ impl<S: map::sun::Intensity> MainLoop<S> {
             },
         };
 
-        return false;
+        return update;
     }
 }
@@ -1,4 +1,8 @@
-use winit::{event::WindowEvent, event_loop::ActiveEventLoop};
+use winit::{
+    dpi::PhysicalPosition,
+    event::{ElementState, WindowEvent},
+    event_loop::ActiveEventLoop,
+};
 
 use crate::map;
 
@@ -8,6 +12,10 @@ mod resize;
 
 mod keyboard;
 
+mod mouse;
+
+mod gesture;
+
 impl<S: map::sun::Intensity> MainLoop<S> {
     /// Handles a window event for the main window
     ///
@@ -17,16 +25,50 @@ impl<S: map::sun::Intensity> MainLoop<S> {
     ///
     /// event: The event to be handled
     pub(super) fn main_window_event(&mut self, event_loop: &ActiveEventLoop, event: WindowEvent) {
+        // Let the overlay handle the event first, releasing keys and buttons
+        // is always passed on such that nothing stays held down
+        if self.state.flags.show_overlay
+            || self.state.flags.show_profiler
+            || self.state.flags.show_columns
+            || self.error.is_some()
+        {
+            let window = self.window.get_mut();
+            let consumed = window.overlay.on_window_event(&window.window, &event);
+            let released = matches!(
+                event,
+                WindowEvent::KeyboardInput { ref event, .. } if event.state == ElementState::Released
+            ) || matches!(
+                event,
+                WindowEvent::MouseInput {
+                    state: ElementState::Released,
+                    ..
+                }
+            );
+            if consumed && !released {
+                return;
+            }
+        }
+
         // Find the correct event
         match event {
             WindowEvent::CloseRequested => self.main_window_close_request(event_loop),
-            WindowEvent::RedrawRequested => self.main_window_redraw_requested(),
+            WindowEvent::RedrawRequested => self.main_window_redraw_requested(event_loop),
             WindowEvent::Resized(size) => self.main_window_resized(size),
             WindowEvent::KeyboardInput {
                 device_id,
                 event,
                 is_synthetic,
             } => self.main_window_keyboard_input(event_loop, device_id, event, is_synthetic),
+            WindowEvent::CursorMoved { position, .. } => self.main_window_cursor_moved(position),
+            WindowEvent::CursorLeft { .. } => self.main_window_cursor_left(),
+            WindowEvent::MouseInput { state, button, .. } => {
+                self.main_window_mouse_input(state, button)
+            }
+            WindowEvent::MouseWheel { delta, .. } => self.main_window_mouse_wheel(delta),
+            WindowEvent::PinchGesture { delta, .. } => self.main_window_pinch_gesture(delta),
+            WindowEvent::PanGesture { delta, .. } => {
+                self.main_window_pan_gesture(PhysicalPosition::new(delta.x as f64, delta.y as f64))
+            }
             _ => (),
         }
     }
@@ -0,0 +1,181 @@
+use winit::{
+    dpi::PhysicalPosition,
+    event::{ElementState, MouseButton, MouseScrollDelta},
+};
+
+use crate::{constants, map, types};
+
+use super::MainLoop;
+
+impl<S: map::sun::Intensity> MainLoop<S> {
+    /// Run when the cursor has moved inside the window
+    ///
+    /// # Parameters
+    ///
+    /// position: The new position of the cursor in pixels from the top left
+    /// corner of the window
+    pub(super) fn main_window_cursor_moved(&mut self, position: PhysicalPosition<f64>) {
+        let cursor = types::Point::new(position.x, position.y);
+        self.state.cursor = Some(cursor);
+
+        // Move the view along with the cursor while dragging
+        if let Some(last) = self.state.drag {
+            self.state.drag_distance += (cursor - last).norm();
+            self.state.drag = Some(cursor);
+            let size = self.settings_window.size;
+            if self.state.drag_distance >= constants::CAMERA_DRAG_CLICK_DISTANCE
+                && size.width > 0
+                && size.height > 0
+            {
+                let offset = self.pixel_to_world(&cursor) - self.pixel_to_world(&last);
+                self.camera.apply_drag(&offset);
+                self.window.get().window.request_redraw();
+            }
+        }
+
+        // Highlight the tile below the cursor
+        self.update_hovered_tile();
+    }
+
+    /// Run when the cursor has left the window
+    pub(super) fn main_window_cursor_left(&mut self) {
+        self.state.cursor = None;
+        self.state.drag = None;
+        self.update_hovered_tile();
+    }
+
+    /// Finds the tile below the cursor and updates the highlight if it has
+    /// changed
+    fn update_hovered_tile(&mut self) {
+        let hovered_tile = self.get_cursor_tile();
+        if hovered_tile != self.state.hovered_tile {
+            self.state.hovered_tile = hovered_tile;
+            self.update_highlight();
+        }
+    }
+
+    /// Handles any mouse button input, the left button drags the view and
+    /// inspects or edits the tile below the cursor if it is released without
+    /// moving
+    ///
+    /// # Parameters
+    ///
+    /// state: Whether the button was pressed or released
+    ///
+    /// button: The button which changed
+    pub(super) fn main_window_mouse_input(&mut self, state: ElementState, button: MouseButton) {
+        if button != MouseButton::Left {
+            return;
+        }
+
+        match state {
+            ElementState::Pressed => {
+                self.state.drag = self.state.cursor;
+                self.state.drag_distance = 0.0;
+            }
+            ElementState::Released => {
+                if self.state.drag.is_some()
+                    && self.state.drag_distance < constants::CAMERA_DRAG_CLICK_DISTANCE
+                {
+                    if self.state.flags.edit_mode {
+                        self.edit_cursor_tile();
+                    } else {
+                        self.inspect_cursor_tile();
+                    }
+                }
+                self.state.drag = None;
+            }
+        }
+    }
+
+    /// Zooms the view towards the cursor when scrolling a mouse wheel, two
+    /// finger scrolling on a touchpad moves the view unless control is held
+    ///
+    /// # Parameters
+    ///
+    /// delta: The amount scrolled
+    pub(super) fn main_window_mouse_wheel(&mut self, delta: MouseScrollDelta) {
+        let lines = match delta {
+            MouseScrollDelta::LineDelta(_, y) => y as f64,
+            MouseScrollDelta::PixelDelta(position) => {
+                if !self.state.flags.left_ctrl_active {
+                    self.main_window_pan_gesture(position);
+                    return;
+                }
+                position.y / constants::CAMERA_SCROLL_PIXELS_PER_LINE
+            }
+        };
+
+        // Zoom towards the center of the screen if the cursor is not known
+        let pivot = self
+            .get_cursor_world()
+            .unwrap_or_else(|| self.camera.get_transform().inv() * types::Point::new(0.0, 0.0));
+
+        if self.camera.apply_scroll(lines, &pivot) {
+            self.window.get().window.request_redraw();
+        }
+    }
+
+    /// Selects the tile below the cursor to show in the overlay and prints
+    /// its state to the console
+    fn inspect_cursor_tile(&mut self) {
+        let Some(pos) = self.get_cursor_tile() else {
+            return;
+        };
+        if let Some(info) = self.map.inspect_tile(&pos) {
+            println!("{}", info);
+            self.state.selected_tile = Some(pos);
+            self.update_highlight();
+        }
+    }
+
+    /// Places a plant of the chosen bulk type on the tile below the cursor if
+    /// it is free and otherwise removes the plant on it
+    fn edit_cursor_tile(&mut self) {
+        let Some(pos) = self.get_cursor_tile() else {
+            return;
+        };
+        if self.map.place_plant(&pos, self.state.edit_bulk) || self.map.remove_plant(&pos) {
+            self.state.flags.map_changed = true;
+            self.request_redraw();
+        }
+    }
+
+    /// Gets the position of the tile below the cursor, None if the cursor is
+    /// outside the window or not above the map
+    fn get_cursor_tile(&self) -> Option<types::Index> {
+        return self.map.pick_tile(
+            &*self.settings_shader.grid_layout,
+            &self.get_cursor_world()?,
+        );
+    }
+
+    /// Gets the world position of the cursor, None if the cursor is outside
+    /// the window
+    pub(super) fn get_cursor_world(&self) -> Option<types::Point> {
+        let cursor = self.state.cursor?;
+        let size = self.settings_window.size;
+        if size.width == 0 || size.height == 0 {
+            return None;
+        }
+
+        return Some(self.pixel_to_world(&cursor));
+    }
+
+    /// Converts a position in pixels from the top left corner of the window
+    /// to world coordinates, the window must not have a size of 0
+    ///
+    /// # Parameters
+    ///
+    /// pixel: The position in pixels
+    pub(super) fn pixel_to_world(&self, pixel: &types::Point) -> types::Point {
+        // Convert from pixels to screen coordinates and then to world coordinates
+        let size = self.settings_window.size;
+        let screen = types::Point::new(
+            2.0 * pixel.x / size.width as f64 - 1.0,
+            1.0 - 2.0 * pixel.y / size.height as f64,
+        );
+
+        return self.camera.get_transform().inv() * screen;
+    }
+}
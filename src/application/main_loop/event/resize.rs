@@ -23,6 +23,6 @@ impl<S: map::sun::Intensity> MainLoop<S> {
         self.window.get_mut().render_state.resize(size);
 
         // Update the camera
-        self.camera.resize(&size);
+        self.camera.resize(size.width, size.height);
     }
 }
@@ -0,0 +1,48 @@
+use crate::{constants, map};
+
+use super::MainLoop;
+
+impl<S: map::sun::Intensity> MainLoop<S> {
+    /// Starts following the lineage of the plant on the selected tile with
+    /// the camera or stops following if a lineage is already followed
+    pub(super) fn toggle_follow(&mut self) {
+        if let Some(lineage) = self.state.follow_lineage.take() {
+            println!("Stopped following lineage {}", lineage);
+            return;
+        }
+
+        let lineage = self
+            .state
+            .selected_tile
+            .and_then(|pos| self.map.inspect_tile(&pos))
+            .and_then(|info| info.plant)
+            .filter(|plant| plant.alive)
+            .and_then(|plant| plant.lineage);
+        match lineage {
+            Some(lineage) => {
+                println!("Following lineage {}", lineage);
+                self.state.follow_lineage = Some(lineage);
+            }
+            None => println!("Select a living plant to follow its lineage"),
+        }
+    }
+
+    /// Moves the camera towards the center of the followed lineage, should
+    /// be run once per frame. Stops following if the lineage has died out.
+    /// Returns true if the camera moved
+    pub(super) fn update_follow(&mut self) -> bool {
+        let Some(lineage) = self.state.follow_lineage else {
+            return false;
+        };
+        let Some(center) = self
+            .map
+            .get_lineage_center(lineage, &*self.settings_shader.grid_layout)
+        else {
+            println!("Lineage {} has died out", lineage);
+            self.state.follow_lineage = None;
+            return false;
+        };
+
+        return self.camera.follow(&center, constants::CAMERA_FOLLOW_RATE);
+    }
+}
@@ -0,0 +1,73 @@
+use std::fs;
+
+use crate::{constants, map};
+
+use super::MainLoop;
+
+impl<S: map::sun::Intensity> MainLoop<S> {
+    /// Writes the genome of the plant on the selected tile to the genome file
+    /// in the export directory and reports any index of the genome which is
+    /// out of range
+    ///
+    /// # Parameters
+    ///
+    /// simplify: True if operators which can never be evaluated are left out
+    pub(super) fn export_genome(&self, simplify: bool) {
+        let Some((pos, genome)) = self
+            .state
+            .selected_tile
+            .and_then(|pos| Some((pos, self.map.get_genome(&pos, simplify)?)))
+        else {
+            eprintln!("Select a tile with a plant to export its genome");
+            return;
+        };
+        if let Some(Err(error)) = self.map.validate_genome(&pos) {
+            println!("{}", error);
+        }
+
+        let directory = &self.settings_viewer.export_directory;
+        let path = directory.join(constants::GENOME_FILE);
+        match fs::create_dir_all(directory).and_then(|_| fs::write(&path, genome)) {
+            Ok(()) => println!("Exported genome to {}", path.display()),
+            Err(error) => eprintln!("Unable to export genome: {:?}", error),
+        }
+    }
+
+    /// Places a plant with the genome from the genome file in the export
+    /// directory on the selected tile, using the bulk type of the edit mode,
+    /// returns true if a plant was placed
+    pub(super) fn inject_genome(&mut self) -> bool {
+        let Some(pos) = self.state.selected_tile else {
+            eprintln!("Select a free tile to place the genome on");
+            return false;
+        };
+
+        let path = self
+            .settings_viewer
+            .export_directory
+            .join(constants::GENOME_FILE);
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(error) => {
+                eprintln!("Unable to read genome from {}: {:?}", path.display(), error);
+                return false;
+            }
+        };
+
+        return match self.map.place_genome(&pos, &text, self.state.edit_bulk) {
+            Ok(true) => {
+                println!("Placed genome from {}", path.display());
+                self.state.flags.map_changed = true;
+                true
+            }
+            Ok(false) => {
+                eprintln!("Select a free tile to place the genome on");
+                false
+            }
+            Err(error) => {
+                eprintln!("Unable to read genome: {}", error);
+                false
+            }
+        };
+    }
+}
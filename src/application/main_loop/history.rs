@@ -0,0 +1,86 @@
+use std::collections::VecDeque;
+
+use crate::map;
+
+use super::MainLoop;
+
+/// The most recent states of the map for stepping backwards through the run,
+/// the oldest state is dropped when it is full. The statistics and the report
+/// of the run keep the steps which have been rewound
+#[derive(Clone, Debug)]
+pub struct History<S: map::sun::Intensity> {
+    /// The saved maps from the oldest to the newest
+    snapshots: VecDeque<map::Map<S>>,
+    /// The maximum number of saved maps
+    capacity: usize,
+}
+
+impl<S: map::sun::Intensity> History<S> {
+    /// Constructs a new empty history
+    ///
+    /// # Parameters
+    ///
+    /// capacity: The maximum number of saved maps, 0 disables the history
+    pub fn new(capacity: usize) -> Self {
+        return Self {
+            snapshots: VecDeque::with_capacity(capacity),
+            capacity,
+        };
+    }
+
+    /// Saves a copy of a map as the newest state, the oldest state is dropped
+    /// if the history is full
+    ///
+    /// # Parameters
+    ///
+    /// map: The map to save
+    pub fn push(&mut self, map: &map::Map<S>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.snapshots.len() >= self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(map.clone());
+    }
+
+    /// Removes and returns the newest state, None if the history is empty
+    pub fn pop(&mut self) -> Option<map::Map<S>> {
+        return self.snapshots.pop_back();
+    }
+
+    /// Retrieves the number of saved states
+    pub fn len(&self) -> usize {
+        return self.snapshots.len();
+    }
+}
+
+impl<S: map::sun::Intensity> MainLoop<S> {
+    /// Saves the current map to the history before it is stepped if the
+    /// current step is a multiple of the rewind interval
+    pub(super) fn save_history(&mut self) {
+        if self
+            .map
+            .get_time()
+            .is_multiple_of(self.settings_viewer.rewind_interval)
+        {
+            self.history.push(&self.map);
+        }
+    }
+
+    /// Replaces the map by the newest state in the history and pauses the
+    /// simulation, returns true if there was a state to go back to
+    pub(super) fn rewind(&mut self) -> bool {
+        let Some(map) = self.history.pop() else {
+            println!("No earlier state to rewind to");
+            return false;
+        };
+        self.map = map;
+        self.state.flags.run_simulation = false;
+
+        // The gpu holds the tiles of the newer state so every tile is uploaded
+        self.reload_map();
+
+        return true;
+    }
+}
@@ -1,10 +1,9 @@
-use std::time::{Duration, Instant};
-
+use web_time::{Duration, Instant};
 use winit::event_loop::{ActiveEventLoop, ControlFlow};
 
-use crate::map;
+use crate::{map, report};
 
-use super::MainLoop;
+use super::{Branch, MainLoop, export_statistics};
 
 impl<S: map::sun::Intensity> MainLoop<S> {
     /// Handles the initialization of the game loop
@@ -17,7 +16,13 @@ impl<S: map::sun::Intensity> MainLoop<S> {
         event_loop.set_control_flow(ControlFlow::Poll);
 
         // Set the size of the camera
-        self.camera.resize(&self.settings_window.size);
+        self.camera.resize(
+            self.settings_window.size.width,
+            self.settings_window.size.height,
+        );
+
+        // Start counting for the window title
+        self.state.next_title_time = Instant::now() + self.settings_window.title_interval;
 
         // Home the view
         self.home();
@@ -28,9 +33,9 @@ impl<S: map::sun::Intensity> MainLoop<S> {
     /// # Parameters
     ///
     /// event_loop: The event loop currently running
-    pub(super) fn main_window_close_request(&self, event_loop: &ActiveEventLoop) {
-        // Stop the application
-        event_loop.exit();
+    pub(super) fn main_window_close_request(&mut self, event_loop: &ActiveEventLoop) {
+        // End the run and stop the application
+        self.end_run(event_loop, report::EndReason::UserQuit);
     }
 
     /// Handles the iteration of the game loop
@@ -45,6 +50,12 @@ impl<S: map::sun::Intensity> MainLoop<S> {
         event_loop: &ActiveEventLoop,
         requested_resume: Instant,
     ) {
+        // In a web page the window is opened in the background after the
+        // event loop has started
+        if !self.window.is_open() {
+            return;
+        }
+
         // Update the time, make sure we do not get a backlog by skipping if we should wait until before now
         let now_time = Instant::now();
 
@@ -68,16 +79,25 @@ impl<S: map::sun::Intensity> MainLoop<S> {
         self.state.next_frame_time = new_time_frame;
         self.state.next_sim_time = new_time_sim;
 
-        event_loop.set_control_flow(ControlFlow::WaitUntil(new_time_frame.min(new_time_sim)));
+        event_loop.set_control_flow(ControlFlow::WaitUntil(
+            new_time_frame
+                .min(new_time_sim)
+                .min(self.state.next_title_time),
+        ));
 
-        // Get the window
-        let window = self.window.get();
+        // Show the live statistics in the window title
+        if now_time >= self.state.next_title_time {
+            self.update_title(now_time);
+        }
 
         // Handle frame iteration
         if forward_frame {
+            // Apply the settings of the config file if it has been saved
+            self.update_config(now_time);
+
             // Update the camera
-            if self.camera.update_transform() {
-                window.window.request_redraw();
+            if self.camera.update_transform() | self.update_follow() {
+                self.window.get().window.request_redraw();
             }
         }
 
@@ -86,13 +106,55 @@ impl<S: map::sun::Intensity> MainLoop<S> {
             self.state.flags.iterate_simulation = false;
             self.state.flags.map_changed = true;
             self.state.flags.redraw_simulation = true;
+            self.save_history();
+            let step_start = Instant::now();
             self.map.step();
+            self.profiler.record_step(step_start.elapsed());
+            self.record_step();
+            self.state.title_steps += 1;
+
+            // Only the original run is recorded and can end the run, a fork
+            // is only for comparing
+            if self.branch == Branch::Original && self.record_original(event_loop) {
+                return;
+            }
         }
 
         // Request a redraw because of the simulation
         if forward_frame && self.state.flags.redraw_simulation {
             self.state.flags.redraw_simulation = false;
-            window.window.request_redraw();
+            self.request_redraw();
+        }
+    }
+
+    /// Records the statistics of the original run after a step and ends or
+    /// pauses the run if it has met one of its stop conditions, returns true
+    /// if the run has ended
+    ///
+    /// # Parameters
+    ///
+    /// event_loop: The event loop currently running
+    fn record_original(&mut self, event_loop: &ActiveEventLoop) -> bool {
+        self.recorder.record(self.map.get_statistics());
+        export_statistics(&mut self.stats_exporter, &self.map.get_statistics());
+
+        let Some(reason) = self
+            .settings_viewer
+            .stop_conditions
+            .check(&self.recorder, &self.map.get_statistics())
+        else {
+            return false;
+        };
+
+        match self.settings_viewer.stop_action {
+            report::StopAction::End => {
+                self.end_run(event_loop, reason);
+                return true;
+            }
+            report::StopAction::Pause => {
+                self.pause_run(reason);
+                return false;
+            }
         }
     }
 }
@@ -115,7 +177,7 @@ fn get_new_time(
     framerate: f64,
 ) -> (Instant, bool) {
     return if now_time < next_time {
-        (next_time.clone(), false)
+        (*next_time, false)
     } else {
         let duration = Duration::from_micros((1e6 / framerate).floor() as u64);
         let new_time = *requested_resume + duration;
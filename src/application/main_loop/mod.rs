@@ -1,8 +1,19 @@
-use crate::{camera, constants::MATH_SQRT_3, map, types};
+use std::path::Path;
+
+#[cfg(target_arch = "wasm32")]
+use winit::event_loop::EventLoopProxy;
+
+use crate::{
+    camera,
+    constants::{self, MATH_SQRT_3},
+    map, report, types,
+};
 
 use super::{
-    OptionalRenderedWindow, RenderedWindow, ShaderSettings, ShaderSettingsInput, State,
-    ViewerSettings, ViewerSettingsInput, WindowSettings, WindowSettingsInput,
+    AnnotationMarker, ApplicationError, OptionalRenderedWindow, OverlayActions, OverlayInfo,
+    ProfileInfo, RenderedWindow, ShaderSettings, ShaderSettingsInput, State, StatsInfo, TimingInfo,
+    UserEvent, ViewerSettings, ViewerSettingsInput, WindowSettings, WindowSettingsInput,
+    export_statistics, save_phylogeny,
 };
 
 mod state;
@@ -10,18 +21,62 @@ use state::ChangeMode;
 
 mod view;
 
+mod follow;
+
+mod preset;
+
+mod config_watch;
+use config_watch::ConfigWatcher;
+
+mod thumbnail;
+
+mod genome;
+
+mod phylogeny;
+
+mod screenshot;
+use screenshot::get_timestamp;
+
+mod annotation;
+
+mod recording;
+use recording::Recording;
+
+mod history;
+use history::History;
+
+mod profiler;
+use profiler::Profiler;
+
+mod branch;
+use branch::{Branch, InactiveRun};
+
+mod overlay;
+
+mod error;
+
+mod stats_window;
+
+mod summary;
+
 mod redraw;
 
+mod title;
+
 mod lifecycle;
 
 mod event;
 
 mod application_handler;
 
+mod setup;
+
 /// Controls the main game loop of the application
 pub struct MainLoop<S: map::sun::Intensity> {
     /// The currently opened window of the application
     window: OptionalRenderedWindow,
+    /// The secondary window showing the statistics of the run if it is open
+    stats_window: OptionalRenderedWindow,
     /// The map of tiles
     map: map::Map<S>,
     /// The camera for controlling what is displayed
@@ -34,6 +89,33 @@ pub struct MainLoop<S: map::sun::Intensity> {
     settings_viewer: ViewerSettings,
     /// The state of the viewer
     state: State,
+    /// Records the key events of the run for the summary report
+    recorder: report::RunRecorder,
+    /// Streams the statistics of every step to a file if set
+    stats_exporter: Option<report::StatsExporter>,
+    /// Reloads the simulation settings when the config file is saved if set
+    config_watcher: Option<ConfigWatcher>,
+    /// The active recording of frames if any
+    recording: Option<Recording>,
+    /// The recent states of the map for stepping backwards
+    history: History<S>,
+    /// The run which is currently shown
+    branch: Branch,
+    /// The other run if the simulation has been forked
+    inactive_run: Option<InactiveRun<S>>,
+    /// The last recoverable error, shown in the overlay until it is
+    /// dismissed
+    error: Option<ApplicationError>,
+    /// The durations of the recent frames and simulation steps
+    profiler: Profiler,
+    /// Sends the render state created in the background back to the event
+    /// loop, set when the event loop is created
+    #[cfg(target_arch = "wasm32")]
+    proxy: Option<EventLoopProxy<UserEvent>>,
+    /// The canvas of the web page to draw onto, None appends a new canvas to
+    /// the page
+    #[cfg(target_arch = "wasm32")]
+    canvas: Option<web_sys::HtmlCanvasElement>,
 }
 
 impl<S: map::sun::Intensity> MainLoop<S> {
@@ -57,17 +139,21 @@ impl<S: map::sun::Intensity> MainLoop<S> {
         settings_shader: ShaderSettingsInput,
         settings_viewer: ViewerSettingsInput,
     ) -> Self {
-        // Set the width of the map in the camera
-        let camera_settings = camera
-            .get_settings()
-            .with_map_width(map.get_size().w as f64);
+        // Set the width of the map in the camera, a map which does not wrap in
+        // x is never wrapped by the camera
+        let map_width = if map.get_settings().wrap.horizontal() {
+            map.get_size().w as f64
+        } else {
+            f64::MAX
+        };
+        let camera_settings = camera.get_settings().with_map_width(map_width);
         camera.set_settings(camera_settings);
 
         // Create the window settings
         let settings_window = WindowSettings::new(settings_window);
 
         // Create the shader settings
-        let settings_shader = ShaderSettings::new(settings_shader, map.get_grid_layout());
+        let settings_shader = ShaderSettings::new(settings_shader, map.get_size().w);
 
         // Create the viewer settings
         let home_view = types::View::new(
@@ -82,14 +168,87 @@ impl<S: map::sun::Intensity> MainLoop<S> {
         );
         let settings_viewer = ViewerSettings::new(settings_viewer, home_view);
 
+        // Start recording the run
+        let recorder = report::RunRecorder::new(map.get_statistics());
+
+        // Keep the recent states for stepping backwards
+        let history = History::new(settings_viewer.rewind_capacity);
+
+        let mut state = State::new();
+        state.flags.show_overlay = settings_viewer.show_overlay;
+
         return Self {
             window: OptionalRenderedWindow::empty(),
+            stats_window: OptionalRenderedWindow::empty(),
             map,
             camera,
             settings_window,
             settings_shader,
             settings_viewer,
-            state: State::new(),
+            state,
+            recorder,
+            stats_exporter: None,
+            config_watcher: None,
+            recording: None,
+            history,
+            branch: Branch::Original,
+            inactive_run: None,
+            error: None,
+            profiler: Profiler::new(constants::PROFILER_SAMPLES),
+            #[cfg(target_arch = "wasm32")]
+            proxy: None,
+            #[cfg(target_arch = "wasm32")]
+            canvas: None,
         };
     }
+
+    /// Streams the statistics of every step to a file, starting with the
+    /// current state of the map, and returns the updated main loop
+    ///
+    /// # Parameters
+    ///
+    /// stats_exporter: The exporter to write the statistics to
+    pub fn with_stats_exporter(mut self, stats_exporter: report::StatsExporter) -> Self {
+        self.stats_exporter = Some(stats_exporter);
+        export_statistics(&mut self.stats_exporter, &self.map.get_statistics());
+
+        return self;
+    }
+
+    /// Loads the simulation settings of a config file into the shown run
+    /// whenever the file is saved and returns the updated main loop
+    ///
+    /// # Parameters
+    ///
+    /// path: The path of the config file to watch
+    pub fn with_config_watcher(mut self, path: &Path) -> Self {
+        self.config_watcher = Some(ConfigWatcher::new(path));
+
+        return self;
+    }
+
+    /// Draws onto a canvas of the web page and returns the updated main loop
+    ///
+    /// # Parameters
+    ///
+    /// canvas: The canvas to draw onto
+    #[cfg(target_arch = "wasm32")]
+    pub fn with_canvas(mut self, canvas: web_sys::HtmlCanvasElement) -> Self {
+        self.canvas = Some(canvas);
+
+        return self;
+    }
+
+    /// Sets the proxy sending events to the event loop and returns the
+    /// updated main loop
+    ///
+    /// # Parameters
+    ///
+    /// proxy: The proxy of the event loop running the main loop
+    #[cfg(target_arch = "wasm32")]
+    pub(super) fn with_proxy(mut self, proxy: EventLoopProxy<UserEvent>) -> Self {
+        self.proxy = Some(proxy);
+
+        return self;
+    }
 }
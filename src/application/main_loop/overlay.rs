@@ -0,0 +1,95 @@
+use crate::{graphics, map};
+
+use super::{ChangeMode, MainLoop, OverlayActions, OverlayInfo, ProfileInfo};
+
+impl<S: map::sun::Intensity> MainLoop<S> {
+    /// Collects the state of the simulation to show in the overlay
+    pub(super) fn get_overlay_info(&self) -> OverlayInfo {
+        return OverlayInfo {
+            running: self.state.flags.run_simulation,
+            sim_rate: self.settings_viewer.sim_rate,
+            history_len: self.history.len(),
+            branch: self.inactive_run.as_ref().map(|_| self.branch.get_name()),
+            follow_lineage: self.state.follow_lineage,
+            mode_background: self.settings_window.graphics_settings.mode_background,
+            scaling_background: self.settings_window.graphics_settings.get_color_scaling(
+                &graphics::InstanceMode::GridBackground(
+                    self.settings_window.graphics_settings.mode_background,
+                ),
+            ),
+            mode_plant: self.settings_window.graphics_settings.mode_plant,
+            statistics: self.map.get_statistics(),
+            time_of_day: self.map.get_time_of_day(),
+            time_of_year: self.map.get_time_of_year(),
+            lineage_count: self.map.get_genealogy().len(),
+            selected_tile: self
+                .state
+                .selected_tile
+                .and_then(|pos| self.map.inspect_tile(&pos)),
+            selected_annotations: self.get_selected_annotations(),
+            annotations: self.get_annotation_markers(),
+            edit_bulk: if self.state.flags.edit_mode {
+                Some(self.state.edit_bulk)
+            } else {
+                None
+            },
+            show_panels: self.state.flags.show_overlay,
+            error: self.error.as_ref().map(|error| error.to_string()),
+            profile: if self.state.flags.show_profiler {
+                Some(ProfileInfo {
+                    frame: self.profiler.get_frame_info(),
+                    step: self.profiler.get_step_info(),
+                })
+            } else {
+                None
+            },
+            columns: if self.state.flags.show_columns {
+                Some(self.map.get_column_profile())
+            } else {
+                None
+            },
+        };
+    }
+
+    /// Applies all actions requested through the overlay
+    ///
+    /// # Parameters
+    ///
+    /// actions: The actions to apply
+    pub(super) fn apply_overlay_actions(&mut self, actions: &OverlayActions) {
+        if actions.toggle_simulation {
+            self.state.flags.run_simulation = !self.state.flags.run_simulation;
+        }
+        if actions.step {
+            self.state.flags.iterate_simulation = true;
+        }
+        if actions.rewind {
+            self.rewind();
+        }
+        if let Some(sim_rate) = actions.sim_rate {
+            self.settings_viewer.sim_rate = sim_rate;
+        }
+        if let Some(mode) = actions.mode_background {
+            self.change_mode_background(&ChangeMode::Id(mode.id()));
+        }
+        if let Some(scaling) = actions.scaling_background {
+            self.change_scaling_background(&ChangeMode::Id(scaling.id()));
+        }
+        if let Some(mode) = actions.mode_plant {
+            self.change_mode_plant(&ChangeMode::Id(mode.id()));
+        }
+        if actions.deselect {
+            self.state.selected_tile = None;
+            self.update_highlight();
+        }
+        if let Some((text, pin_organism)) = &actions.add_annotation {
+            self.add_selected_annotation(text, *pin_organism);
+        }
+        if let Some(id) = actions.remove_annotation {
+            self.map.remove_annotation(id);
+        }
+        if actions.dismiss_error {
+            self.error = None;
+        }
+    }
+}
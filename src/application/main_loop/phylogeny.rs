@@ -0,0 +1,15 @@
+use crate::map;
+
+use super::{MainLoop, save_phylogeny};
+
+impl<S: map::sun::Intensity> MainLoop<S> {
+    /// Writes the ancestry tree of the lineages surviving in the shown run to
+    /// the export directory
+    pub(super) fn export_phylogeny(&self) {
+        save_phylogeny(
+            &self.map,
+            &self.settings_viewer.export_directory,
+            self.settings_viewer.phylogeny_format,
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use crate::map;
+
+use super::MainLoop;
+
+impl<S: map::sun::Intensity> MainLoop<S> {
+    /// Exports the current map settings as a new preset named after the first
+    /// unused export number
+    pub(super) fn export_preset(&self) {
+        let presets = &self.settings_viewer.presets;
+        let existing = match presets.list() {
+            Ok(names) => names,
+            Err(error) => {
+                eprintln!("Unable to list presets: {:?}", error);
+                return;
+            }
+        };
+        let name = (0..)
+            .map(|index| format!("export_{index}"))
+            .find(|name| !existing.contains(name))
+            .unwrap();
+
+        match presets.save(&name, self.map.get_settings()) {
+            Ok(path) => println!("Exported settings to {}", path.display()),
+            Err(error) => eprintln!("Unable to export preset: {}", error),
+        }
+    }
+
+    /// Replaces the settings of the shown run by the next preset in the
+    /// library, the seed of the run is kept such that a fork can be compared
+    /// with the original run
+    pub(super) fn load_next_preset(&mut self) {
+        let presets = &self.settings_viewer.presets;
+        let names = match presets.list() {
+            Ok(names) if !names.is_empty() => names,
+            Ok(_) => {
+                eprintln!("There are no presets to load");
+                return;
+            }
+            Err(error) => {
+                eprintln!("Unable to list presets: {:?}", error);
+                return;
+            }
+        };
+        let name = &names[self.state.preset_index % names.len()];
+        self.state.preset_index = (self.state.preset_index + 1) % names.len();
+
+        match presets.load(name) {
+            Ok(settings) => {
+                let seed = self.map.get_settings().seed;
+                self.map.set_settings(settings.with_seed(seed));
+                println!(
+                    "Loaded preset {} into the {} run",
+                    name,
+                    self.branch.get_name().to_lowercase()
+                );
+            }
+            Err(error) => eprintln!("Unable to load preset: {}", error),
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::{collections::VecDeque, time::Duration};
+
+use super::TimingInfo;
+
+/// The durations of the most recent frames and simulation steps, used to see
+/// whether rendering or the simulation limits the speed of the viewer
+#[derive(Clone, Debug)]
+pub struct Profiler {
+    /// The time spent drawing each of the most recent frames
+    frames: Timings,
+    /// The time spent on each of the most recent simulation steps
+    steps: Timings,
+}
+
+impl Profiler {
+    /// Constructs a new profiler without any measurements
+    ///
+    /// # Parameters
+    ///
+    /// capacity: The number of frames and steps to keep the durations of
+    pub fn new(capacity: usize) -> Self {
+        return Self {
+            frames: Timings::new(capacity),
+            steps: Timings::new(capacity),
+        };
+    }
+
+    /// Records the time spent drawing a frame
+    ///
+    /// # Parameters
+    ///
+    /// duration: The time spent
+    pub fn record_frame(&mut self, duration: Duration) {
+        self.frames.push(duration);
+    }
+
+    /// Records the time spent on a simulation step
+    ///
+    /// # Parameters
+    ///
+    /// duration: The time spent
+    pub fn record_step(&mut self, duration: Duration) {
+        self.steps.push(duration);
+    }
+
+    /// Summarizes the durations of the recent frames
+    pub fn get_frame_info(&self) -> TimingInfo {
+        return self.frames.get_info();
+    }
+
+    /// Summarizes the durations of the recent simulation steps
+    pub fn get_step_info(&self) -> TimingInfo {
+        return self.steps.get_info();
+    }
+}
+
+/// A rolling window of durations, the oldest duration is dropped when it is
+/// full
+#[derive(Clone, Debug)]
+struct Timings {
+    /// The durations in milliseconds from the oldest to the newest
+    samples: VecDeque<f64>,
+    /// The maximum number of durations
+    capacity: usize,
+}
+
+impl Timings {
+    /// Constructs a new empty window of durations
+    ///
+    /// # Parameters
+    ///
+    /// capacity: The maximum number of durations
+    fn new(capacity: usize) -> Self {
+        return Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        };
+    }
+
+    /// Adds a duration as the newest, the oldest is dropped if the window is
+    /// full
+    ///
+    /// # Parameters
+    ///
+    /// duration: The duration to add
+    fn push(&mut self, duration: Duration) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.samples.len() >= self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(duration.as_secs_f64() * 1000.0);
+    }
+
+    /// Computes the average and 95th percentile of the durations
+    fn get_info(&self) -> TimingInfo {
+        if self.samples.is_empty() {
+            return TimingInfo {
+                average: 0.0,
+                percentile_95: 0.0,
+                samples: Vec::new(),
+            };
+        }
+
+        let average = self.samples.iter().sum::<f64>() / self.samples.len() as f64;
+        let mut sorted = self.samples.iter().copied().collect::<Vec<_>>();
+        sorted.sort_by(f64::total_cmp);
+        let index = ((sorted.len() as f64 * 0.95).ceil() as usize).clamp(1, sorted.len()) - 1;
+
+        return TimingInfo {
+            average,
+            percentile_95: sorted[index],
+            samples: self.samples.iter().copied().collect(),
+        };
+    }
+}
@@ -0,0 +1,93 @@
+use std::path::PathBuf;
+
+use crate::map;
+
+use super::{MainLoop, get_timestamp};
+
+/// An active recording saving frames as numbered PNG files for making
+/// time-lapse videos
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Recording {
+    /// The directory all frames are saved to
+    directory: PathBuf,
+    /// The number of frames saved so far
+    frame_count: usize,
+}
+
+impl Recording {
+    /// Constructs a new recording with no frames
+    ///
+    /// # Parameters
+    ///
+    /// directory: The directory to save all frames to
+    pub fn new(directory: PathBuf) -> Self {
+        return Self {
+            directory,
+            frame_count: 0,
+        };
+    }
+
+    /// Retrieves the path of the next frame and counts it as saved
+    fn next_frame_path(&mut self) -> PathBuf {
+        let path = self
+            .directory
+            .join(format!("frame_{:06}.png", self.frame_count));
+        self.frame_count += 1;
+
+        return path;
+    }
+}
+
+impl<S: map::sun::Intensity> MainLoop<S> {
+    /// Starts a new recording into its own directory or stops the current
+    /// recording
+    pub(super) fn toggle_recording(&mut self) {
+        match self.recording.take() {
+            Some(recording) => println!(
+                "Stopped recording after {} frames in {}",
+                recording.frame_count,
+                recording.directory.display()
+            ),
+            None => {
+                let directory = self
+                    .settings_viewer
+                    .export_directory
+                    .join(format!("recording_{}", get_timestamp()));
+                println!("Started recording to {}", directory.display());
+                self.recording = Some(Recording::new(directory));
+                self.record_frame();
+            }
+        }
+    }
+
+    /// Saves the current frame if recording and the current step is a
+    /// multiple of the record interval, the recording is stopped if a frame
+    /// could not be saved
+    pub(super) fn record_step(&mut self) {
+        if self.recording.is_some()
+            && self
+                .map
+                .get_time()
+                .is_multiple_of(self.settings_viewer.record_interval)
+        {
+            self.record_frame();
+        }
+    }
+
+    /// Saves the current frame as the next frame of the recording, the
+    /// recording is stopped if the frame could not be saved
+    fn record_frame(&mut self) {
+        let Some(frame) = self.capture_frame() else {
+            self.recording = None;
+            return;
+        };
+        let Some(recording) = self.recording.as_mut() else {
+            return;
+        };
+
+        if let Err(error) = frame.save_png(&recording.next_frame_path()) {
+            eprintln!("Unable to save frame, stopping the recording: {}", error);
+            self.recording = None;
+        }
+    }
+}
@@ -1,6 +1,9 @@
-use crate::{graphics, map, types};
+use web_time::Instant;
+use winit::event_loop::ActiveEventLoop;
 
-use super::MainLoop;
+use crate::{map, report};
+
+use super::{ApplicationError, MainLoop};
 
 impl<S: map::sun::Intensity> MainLoop<S> {
     /// Requests a redraw to the system
@@ -8,24 +11,68 @@ impl<S: map::sun::Intensity> MainLoop<S> {
         self.window.get().window.request_redraw();
     }
 
+    /// Uploads every tile of the map to the gpu, used when the map has been
+    /// replaced by another state
+    pub(super) fn reload_map(&mut self) {
+        let window = self.window.get_mut();
+        window
+            .graphics_state
+            .update_map(&window.render_state, &self.map);
+        self.map.clear_dirty();
+        self.state.flags.map_changed = false;
+        self.request_redraw();
+    }
+
     /// Run when the main window must be redrawn
-    pub(super) fn main_window_redraw_requested(&mut self) {
+    ///
+    /// # Parameters
+    ///
+    /// event_loop: The event loop currently running
+    #[tracing::instrument(skip_all)]
+    pub(super) fn main_window_redraw_requested(&mut self, event_loop: &ActiveEventLoop) {
+        let frame_start = Instant::now();
+
+        // Collect what to show in the overlay before borrowing the window, an
+        // error and the profiler are shown even if the rest of the overlay is
+        // hidden
+        let overlay_info = if self.state.flags.show_overlay
+            || self.state.flags.show_profiler
+            || self.state.flags.show_columns
+            || self.error.is_some()
+        {
+            Some(self.get_overlay_info())
+        } else {
+            None
+        };
+
         // Get the window
-        let window = self.window.get();
+        let window = self.window.get_mut();
 
         // Update the map data
         if self.state.flags.map_changed {
             self.state.flags.map_changed = false;
             window
                 .graphics_state
-                .update_map(&window.render_state, &mut self.map);
+                .update_map_dirty(&window.render_state, &self.map);
+            self.map.clear_dirty();
         }
 
         // Get the current texture view
-        let output_texture = match window.render_state.get_surface().get_current_texture() {
+        let output_texture = match window.render_state.get_current_texture() {
             Ok(value) => value,
             Err(error) => {
-                eprintln!("Unable to get texture: {:?}", error);
+                // A lost or outdated surface has been configured again so the
+                // frame is tried again, after a timeout the frame is skipped
+                // and running out of memory ends the run
+                let error = ApplicationError::from(error);
+                if error.is_retried() {
+                    window.window.request_redraw();
+                }
+                let recoverable = error.is_recoverable();
+                self.report_error(error);
+                if !recoverable {
+                    self.end_run(event_loop, report::EndReason::RenderError);
+                }
                 return;
             }
         };
@@ -33,63 +80,51 @@ impl<S: map::sun::Intensity> MainLoop<S> {
             .texture
             .create_view(&wgpu::TextureViewDescriptor::default());
 
-        // Get the transforms for all repeats of the map
-        let transform = self.camera.get_transform();
-        let transform_pos = transform
-            * types::Transform2D::translate(&types::Point {
-                x: self.camera.get_settings().map_width,
-                y: 0.0,
-            });
-        let transform_neg = transform
-            * types::Transform2D::translate(&types::Point {
-                x: -self.camera.get_settings().map_width,
-                y: 0.0,
-            });
-
-        // Clear the screen
-        window.graphics_state.clear(&window.render_state, &view);
-
-        // Render the sun
-        window.graphics_state.render(
-            &window.render_state,
-            &view,
-            &transform_neg,
-            &graphics::InstanceType::Sun,
-        );
-        window.graphics_state.render(
-            &window.render_state,
-            &view,
-            &transform_pos,
-            &graphics::InstanceType::Sun,
-        );
-        window.graphics_state.render(
-            &window.render_state,
-            &view,
-            &transform,
-            &graphics::InstanceType::Sun,
-        );
+        // Record the full frame into a single encoder
+        let mut encoder = window.graphics_state.create_encoder(&window.render_state);
 
-        // Render the background of the tiles
-        window.graphics_state.render(
+        // Render the map
+        window.graphics_state.render_map(
             &window.render_state,
+            &mut encoder,
             &view,
-            &transform_neg,
-            &graphics::InstanceType::GridBackground,
-        );
-        window.graphics_state.render(
-            &window.render_state,
-            &view,
-            &transform_pos,
-            &graphics::InstanceType::GridBackground,
-        );
-        window.graphics_state.render(
-            &window.render_state,
-            &view,
-            &transform,
-            &graphics::InstanceType::GridBackground,
+            window.render_state.get_msaa_view(),
+            &self.camera.get_transform(),
+            self.camera.get_settings().map_width,
         );
 
+        // Render the overlay on top of the map
+        let mut command_buffers = Vec::new();
+        let overlay_actions = overlay_info.map(|info| {
+            let (actions, output) = window.overlay.run(&window.window, &info);
+            command_buffers = window.graphics_state.render_overlay(
+                &window.render_state,
+                &mut encoder,
+                &view,
+                &output.primitives,
+                &output.textures_delta,
+                output.pixels_per_point,
+            );
+            actions
+        });
+
+        // Submit everything at once, the overlay shapes must be uploaded
+        // before they are drawn
+        let submit_span = tracing::debug_span!("submit").entered();
+        command_buffers.push(encoder.finish());
+        window.render_state.get_queue().submit(command_buffers);
+
         // Show to screen
         output_texture.present();
+        submit_span.exit();
+        self.state.title_frames += 1;
+        self.profiler.record_frame(frame_start.elapsed());
+
+        if let Some(actions) = overlay_actions {
+            self.apply_overlay_actions(&actions);
+        }
+
+        // Keep the statistics window in step with the map
+        self.request_stats_redraw();
     }
 }
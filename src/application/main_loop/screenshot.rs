@@ -0,0 +1,68 @@
+use std::path::PathBuf;
+
+use web_time::{SystemTime, UNIX_EPOCH};
+
+use crate::{graphics, map};
+
+use super::MainLoop;
+
+impl<S: map::sun::Intensity> MainLoop<S> {
+    /// Renders the current frame as shown in the window without the overlay
+    /// and saves it as a PNG file named after the current time, returns the
+    /// path of the file or None if it could not be saved
+    pub(super) fn save_screenshot(&mut self) -> Option<PathBuf> {
+        let screenshot = self.capture_frame()?;
+
+        let path = self.settings_viewer.export_directory.join(format!(
+            "screenshot_{}_step_{}.png",
+            get_timestamp(),
+            self.map.get_time()
+        ));
+        return match screenshot.save_png(&path) {
+            Ok(()) => {
+                println!("Saved screenshot to {}", path.display());
+                Some(path)
+            }
+            Err(error) => {
+                eprintln!("Unable to save screenshot: {}", error);
+                None
+            }
+        };
+    }
+
+    /// Renders the current frame as shown in the window without the overlay,
+    /// returns None if it could not be rendered
+    pub(super) fn capture_frame(&mut self) -> Option<graphics::Thumbnail> {
+        let window = self.window.get();
+
+        // Make sure the frame shows the current map
+        if self.state.flags.map_changed {
+            self.state.flags.map_changed = false;
+            window
+                .graphics_state
+                .update_map_dirty(&window.render_state, &self.map);
+            self.map.clear_dirty();
+        }
+
+        return match window.graphics_state.render_screenshot(
+            &window.render_state,
+            &self.camera.get_transform(),
+            self.camera.get_settings().map_width,
+        ) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                eprintln!("Unable to render frame: {}", error);
+                None
+            }
+        };
+    }
+}
+
+/// Retrieves the number of milliseconds since the unix epoch for naming files,
+/// a clock set before the epoch only affects the names so it gives 0
+pub(super) fn get_timestamp() -> u128 {
+    return SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or(0);
+}
@@ -0,0 +1,163 @@
+use winit::dpi::PhysicalSize;
+
+use crate::{camera, config, graphics, map, types};
+
+use super::{MainLoop, ShaderSettingsInput, ViewerSettingsInput, WindowSettingsInput};
+
+impl<S: map::sun::Intensity> MainLoop<S> {
+    /// Creates a new main loop showing a map with the camera, colors and
+    /// viewer settings of a config
+    ///
+    /// # Parameters
+    ///
+    /// map: The full map
+    ///
+    /// config: The config to take the settings from
+    ///
+    /// presets: The settings presets which can be loaded while running
+    pub fn from_config(
+        map: map::Map<S>,
+        config: &config::Config,
+        presets: map::settings::preset::PresetLibrary,
+    ) -> Self {
+        // Setup the camera
+        let camera_transform = types::Transform2D::scale(&types::Point::new(1.0, 1.0));
+        let camera_settings = camera::CameraSettings::default()
+            .with_framerate(config.viewer.framerate)
+            .with_speed_move(config.camera.speed_move)
+            .with_speed_zoom(config.camera.speed_zoom)
+            .with_speed_scroll(config.camera.speed_scroll)
+            .with_boost_factor(config.camera.boost_factor)
+            .with_zoom_limits(config.camera.zoom_limits);
+        let camera = camera::Camera::new(camera_settings, camera_transform);
+
+        // Setup the shader settings
+        let color_map_sun: Box<dyn types::ColorMap> = Box::new(config.colors.light);
+        let color_map_background_transparency: Box<dyn types::ColorMap> =
+            Box::new(config.colors.transparency);
+        let color_map_background_light: Box<dyn types::ColorMap> = Box::new(config.colors.light);
+        let color_map_background_water: Box<dyn types::ColorMap> = Box::new(config.colors.water);
+        let color_map_background_nutrients: Box<dyn types::ColorMap> =
+            Box::new(config.colors.nutrients);
+        let color_map_background_chunk_cost: Box<dyn types::ColorMap> =
+            Box::new(config.colors.chunk_cost.clone());
+        let color_map_background_clouds: Box<dyn types::ColorMap> = Box::new(config.colors.clouds);
+        let color_map_background_temperature: Box<dyn types::ColorMap> =
+            Box::new(config.colors.temperature);
+        let color_map_background_detritus: Box<dyn types::ColorMap> =
+            Box::new(config.colors.detritus);
+        let color_map_background_fertility: Box<dyn types::ColorMap> =
+            Box::new(config.colors.fertility);
+        let color_map_background_foreign_shade: Box<dyn types::ColorMap> =
+            Box::new(config.colors.foreign_shade);
+        let color_map_background_diffuse: Box<dyn types::ColorMap> =
+            Box::new(config.colors.diffuse);
+        let color_map_background_terrain: Box<dyn types::ColorMap> =
+            Box::new(config.colors.terrain);
+        let color_maps_background = map::DataModeBackground::new_color_map_collection(
+            color_map_background_light,
+            color_map_background_transparency,
+            color_map_background_water,
+            color_map_background_nutrients,
+            color_map_background_chunk_cost,
+            color_map_background_clouds,
+            color_map_background_temperature,
+            color_map_background_detritus,
+            color_map_background_fertility,
+            color_map_background_foreign_shade,
+            color_map_background_diffuse,
+            color_map_background_terrain,
+        );
+
+        let color_map_plant_bulk: Box<dyn types::ColorMap> =
+            Box::new(config.colors.plant.get_color_map());
+        let color_map_plant_age: Box<dyn types::ColorMap> =
+            Box::new(config.colors.plant.get_age_color_map());
+        let color_map_plant_cum_age: Box<dyn types::ColorMap> =
+            Box::new(config.colors.plant.get_age_color_map());
+        let color_map_plant_species: Box<dyn types::ColorMap> =
+            Box::new(config.colors.plant.get_species_color_map());
+        let color_maps_plant = map::DataModePlant::new_color_map_collection(
+            color_map_plant_bulk,
+            color_map_plant_age,
+            color_map_plant_cum_age,
+            color_map_plant_species,
+        );
+        let color_map_bridge: Box<dyn types::ColorMap> =
+            Box::new(config.colors.plant.get_bridge_color_map());
+        let color_map_border: Box<dyn types::ColorMap> =
+            Box::new(config.colors.get_border_color_map());
+        let color_map_transfer: Box<dyn types::ColorMap> = Box::new(config.colors.transfer);
+
+        // Set window settings
+        let name = format!("{} v{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+        let size = PhysicalSize::new(config.viewer.window_width, config.viewer.window_height);
+        let color_background = config.colors.background;
+        let mode_background = config.colors.mode_background;
+        let mode_plant = config.colors.mode_plant;
+        let active_color_maps = graphics::InstanceType::new_color_map_collection(
+            color_map_sun,
+            color_maps_background,
+            color_maps_plant,
+            color_map_bridge,
+            color_map_border,
+            color_map_transfer,
+        );
+        let color_scalings =
+            graphics::InstanceType::new_color_scaling_collection(config.colors.scaling);
+        let graphics_settings = graphics::Settings {
+            color_clear: color_background,
+            mode_background,
+            mode_plant,
+            color_maps: active_color_maps,
+            color_scalings,
+            scaling_exponent: config.colors.scaling_exponent,
+            color_map_highlight: Box::new(config.colors.get_highlight_color_map()),
+            sample_count: config.viewer.msaa_samples,
+            show_borders: false,
+            show_transfers: false,
+        };
+        let settings_window = WindowSettingsInput {
+            name,
+            size,
+            icon: config.viewer.window_icon.clone(),
+            graphics_settings,
+        };
+
+        // Setup shader settings
+        let settings_shader = ShaderSettingsInput {
+            column_stretch: config.viewer.column_stretch,
+        };
+
+        // Setup the conditions for ending the run
+        let stop_conditions = config.viewer.get_stop_conditions();
+
+        // Setup the viewer settings
+        let framerate = config.viewer.framerate;
+        let sim_rate = config.viewer.sim_rate;
+        let sim_rate_mod = config.viewer.sim_rate_modifier;
+        let settings_viewer = ViewerSettingsInput {
+            framerate,
+            sim_rate,
+            sim_rate_mod,
+            presets: presets.clone(),
+            export_directory: config.viewer.export_directory.clone(),
+            thumbnail_width: config.viewer.thumbnail_width,
+            phylogeny_format: config.viewer.phylogeny_format,
+            stop_conditions,
+            stop_action: config.viewer.stop_action,
+            record_interval: config.viewer.record_interval,
+            rewind_capacity: config.viewer.rewind_capacity,
+            rewind_interval: config.viewer.rewind_interval,
+            show_overlay: config.viewer.show_overlay,
+        };
+
+        return Self::new(
+            map,
+            camera,
+            settings_window,
+            settings_shader,
+            settings_viewer,
+        );
+    }
+}
@@ -1,7 +1,12 @@
+#[cfg(target_arch = "wasm32")]
+use std::sync::Arc;
+
 use winit::{event_loop::ActiveEventLoop, window::Window};
 
-use crate::{graphics, map};
+use crate::{graphics, map, render, types};
 
+#[cfg(target_arch = "wasm32")]
+use super::UserEvent;
 use super::{MainLoop, OptionalRenderedWindow, RenderedWindow};
 
 impl<S: map::sun::Intensity> MainLoop<S> {
@@ -14,26 +19,82 @@ impl<S: map::sun::Intensity> MainLoop<S> {
         // Open a new window
         let window_attributes = Window::default_attributes()
             .with_title(&self.settings_window.name)
+            .with_window_icon(self.settings_window.icon.clone())
             .with_inner_size(self.settings_window.size);
 
+        // In a web page the window is a canvas
+        #[cfg(target_arch = "wasm32")]
+        let window_attributes = {
+            use winit::platform::web::WindowAttributesExtWebSys;
+
+            window_attributes
+                .with_canvas(self.canvas.clone())
+                .with_append(self.canvas.is_none())
+        };
+
         let window = match event_loop.create_window(window_attributes) {
             Ok(window) => window,
             Err(error) => {
-                eprintln!("Unable to create window: {:?}", error);
+                self.report_error(error.into());
                 event_loop.exit();
                 return;
             }
         };
 
-        // Add a render state
-        self.window = match pollster::block_on(RenderedWindow::new(
-            window,
-            self.settings_window.graphics_settings.clone(),
-            &mut self.map,
-        )) {
+        // Add a render state, the gpu can not be waited for in a web page so
+        // there it is created in the background and the window is opened
+        // when it is sent back to the event loop
+        #[cfg(target_arch = "wasm32")]
+        self.spawn_render_state(window);
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let rendered_window = pollster::block_on(RenderedWindow::new(
+                window,
+                self.settings_window.graphics_settings.clone(),
+                &self.map,
+            ));
+            self.open_window(event_loop, rendered_window);
+        }
+    }
+
+    /// Creates the render state of a window in the background and sends it to
+    /// the event loop when it is ready
+    ///
+    /// # Parameters
+    ///
+    /// window: The window to create the render state for
+    #[cfg(target_arch = "wasm32")]
+    fn spawn_render_state(&self, window: Window) {
+        let Some(proxy) = self.proxy.clone() else {
+            return;
+        };
+        let window = Arc::new(window);
+        let sample_count = self.settings_window.graphics_settings.sample_count;
+
+        wasm_bindgen_futures::spawn_local(async move {
+            let render_state = render::RenderState::new(&window, sample_count).await;
+            // The event loop is gone if the page has been closed
+            _ = proxy.send_event(UserEvent::RenderState(window, render_state));
+        });
+    }
+
+    /// Opens the main window once its render state has been created, the
+    /// application stops if it could not be created
+    ///
+    /// # Parameters
+    ///
+    /// event_loop: The event loop running the application
+    ///
+    /// rendered_window: The window with its render state
+    pub(super) fn open_window(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        rendered_window: Result<RenderedWindow, render::NewRenderStateError>,
+    ) {
+        self.window = match rendered_window {
             Ok(value) => OptionalRenderedWindow::new(value),
             Err(error) => {
-                eprintln!("Unable to add render state: {:?}", error);
+                self.report_error(error.into());
                 event_loop.exit();
                 return;
             }
@@ -46,9 +107,10 @@ impl<S: map::sun::Intensity> MainLoop<S> {
             &window.render_state,
             self.settings_window.graphics_settings.clone(),
         );
-        window
-            .graphics_state
-            .set_grid_layout(&window.render_state, &self.settings_shader.grid_layout);
+        window.graphics_state.set_grid_layout(
+            &window.render_state,
+            self.settings_shader.grid_layout.clone(),
+        );
     }
 
     /// Sets the graphics settings
@@ -69,6 +131,65 @@ impl<S: map::sun::Intensity> MainLoop<S> {
         window.window.request_redraw();
     }
 
+    /// Sends the selected and hovered tiles to the graphics to be highlighted
+    /// and redraws the window
+    pub(super) fn update_highlight(&mut self) {
+        let tiles = [
+            self.state
+                .selected_tile
+                .map(|pos| (pos, graphics::HighlightType::Selected)),
+            self.state
+                .hovered_tile
+                .map(|pos| (pos, graphics::HighlightType::Hovered)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        let window = self.window.get_mut();
+        window
+            .graphics_state
+            .set_highlight(&window.render_state, &tiles);
+        self.request_redraw();
+    }
+
+    /// Shows or hides the outlines of the tiles
+    pub(super) fn toggle_borders(&mut self) {
+        let graphics_settings = self.settings_window.graphics_settings.clone();
+        let show_borders = !graphics_settings.show_borders;
+        self.set_graphics_settings(graphics_settings.with_show_borders(show_borders));
+    }
+
+    /// Shows or hides the arrows showing the energy transfer through the
+    /// bridges
+    pub(super) fn toggle_transfers(&mut self) {
+        let graphics_settings = self.settings_window.graphics_settings.clone();
+        let show_transfers = !graphics_settings.show_transfers;
+        self.set_graphics_settings(graphics_settings.with_show_transfers(show_transfers));
+    }
+
+    /// Changes the scaling of the color map of the current background
+    /// display mode
+    ///
+    /// # Parameters
+    ///
+    /// mode: The way to change the scaling
+    pub(super) fn change_scaling_background(&mut self, mode: &ChangeMode) {
+        let old_graphics_settings = &self.settings_window.graphics_settings;
+        let instance =
+            graphics::InstanceMode::GridBackground(old_graphics_settings.mode_background);
+        let scaling = old_graphics_settings.get_color_scaling(&instance);
+        let graphics_settings = old_graphics_settings.clone().with_color_scaling(
+            match mode {
+                ChangeMode::Next => scaling.next(),
+                ChangeMode::Prev => scaling.prev(),
+                ChangeMode::Id(id) => types::ColorScaling::from_id(*id),
+            },
+            &instance,
+        );
+        self.set_graphics_settings(graphics_settings);
+    }
+
     /// Changes the display mode for the background
     ///
     /// # Parameters
@@ -95,6 +216,31 @@ impl<S: map::sun::Intensity> MainLoop<S> {
 
         self.request_redraw();
     }
+
+    /// Changes the display mode for the plants
+    ///
+    /// # Parameters
+    ///
+    /// mode: The way to change the display mode
+    pub(super) fn change_mode_plant(&mut self, mode: &ChangeMode) {
+        // Set the display mode
+        let old_graphics_settings = &self.settings_window.graphics_settings;
+        let graphics_settings = old_graphics_settings.clone().with_mode_plant(match mode {
+            ChangeMode::Next => old_graphics_settings.mode_plant.next(),
+            ChangeMode::Prev => old_graphics_settings.mode_plant.prev(),
+            ChangeMode::Id(id) => map::DataModePlant::from_id(*id),
+        });
+        self.set_graphics_settings(graphics_settings);
+
+        // Update the map
+        let window = self.window.get_mut();
+
+        window
+            .graphics_state
+            .update_map(&window.render_state, &self.map);
+
+        self.request_redraw();
+    }
 }
 
 /// Describes how to change the display mode
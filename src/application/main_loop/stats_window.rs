@@ -0,0 +1,149 @@
+use winit::{dpi::PhysicalSize, event::WindowEvent, event_loop::ActiveEventLoop, window::Window};
+
+use crate::map;
+
+use super::{ApplicationError, MainLoop, OptionalRenderedWindow, RenderedWindow, StatsInfo};
+
+/// The number of recent events shown in the statistics window
+const STATS_WINDOW_EVENT_COUNT: usize = 50;
+
+impl<S: map::sun::Intensity> MainLoop<S> {
+    /// Opens the statistics window next to the main window or closes it if
+    /// it is already open
+    ///
+    /// # Parameters
+    ///
+    /// event_loop: The event loop running the application
+    pub(super) fn toggle_stats_window(&mut self, event_loop: &ActiveEventLoop) {
+        // A web page only has the canvas of the main window
+        if self.stats_window.is_open() || cfg!(target_arch = "wasm32") {
+            self.stats_window = OptionalRenderedWindow::empty();
+            return;
+        }
+
+        // Open a new window
+        let window_attributes = Window::default_attributes()
+            .with_title(format!("{} - Statistics", self.settings_window.name))
+            .with_window_icon(self.settings_window.icon.clone())
+            .with_inner_size(PhysicalSize::new(360, 480));
+
+        let window = match event_loop.create_window(window_attributes) {
+            Ok(window) => window,
+            Err(error) => {
+                self.report_error(error.into());
+                return;
+            }
+        };
+
+        // Add a render state, only its overlay is used
+        self.stats_window = match pollster::block_on(RenderedWindow::new(
+            window,
+            self.settings_window.graphics_settings.clone(),
+            &self.map,
+        )) {
+            Ok(value) => OptionalRenderedWindow::new(value),
+            Err(error) => {
+                self.report_error(error.into());
+                return;
+            }
+        };
+    }
+
+    /// Handles a window event for the statistics window
+    ///
+    /// # Parameters
+    ///
+    /// event: The event to be handled
+    pub(super) fn stats_window_event(&mut self, event: WindowEvent) {
+        // The window only holds the overlay so it gets every event
+        let window = self.stats_window.get_mut();
+        _ = window.overlay.on_window_event(&window.window, &event);
+
+        match event {
+            WindowEvent::CloseRequested => {
+                self.stats_window = OptionalRenderedWindow::empty();
+            }
+            WindowEvent::RedrawRequested => self.stats_window_redraw_requested(),
+            WindowEvent::Resized(size) if size.width != 0 && size.height != 0 => {
+                self.stats_window.get_mut().render_state.resize(size);
+            }
+            _ => (),
+        }
+    }
+
+    /// Requests a redraw of the statistics window if it is open
+    pub(super) fn request_stats_redraw(&self) {
+        if self.stats_window.is_open() {
+            self.stats_window.get().window.request_redraw();
+        }
+    }
+
+    /// Collects the state of the simulation to show in the statistics window
+    fn get_stats_info(&self) -> StatsInfo {
+        return StatsInfo {
+            branch: self.inactive_run.as_ref().map(|_| self.branch.get_name()),
+            statistics: self.map.get_statistics(),
+            lineage_count: self.map.get_genealogy().len(),
+            peak: *self.recorder.get_peak(),
+            events: self
+                .recorder
+                .get_events()
+                .iter()
+                .rev()
+                .take(STATS_WINDOW_EVENT_COUNT)
+                .cloned()
+                .collect(),
+            selected_tile: self
+                .state
+                .selected_tile
+                .and_then(|pos| self.map.inspect_tile(&pos)),
+        };
+    }
+
+    /// Run when the statistics window must be redrawn
+    fn stats_window_redraw_requested(&mut self) {
+        let info = self.get_stats_info();
+
+        // Get the window
+        let window = self.stats_window.get_mut();
+
+        // Get the current texture view
+        let output_texture = match window.render_state.get_current_texture() {
+            Ok(value) => value,
+            Err(error) => {
+                // The statistics window is closed if it can no longer be
+                // rendered
+                let error = ApplicationError::from(error);
+                if error.is_retried() {
+                    window.window.request_redraw();
+                }
+                if !error.is_recoverable() {
+                    self.stats_window = OptionalRenderedWindow::empty();
+                }
+                self.report_error(error);
+                return;
+            }
+        };
+        let view = output_texture
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        // The panel covers the entire window so nothing is drawn below it
+        let mut encoder = window.graphics_state.create_encoder(&window.render_state);
+        let output = window.overlay.run_stats(&window.window, &info);
+        let mut command_buffers = window.graphics_state.render_overlay(
+            &window.render_state,
+            &mut encoder,
+            &view,
+            &output.primitives,
+            &output.textures_delta,
+            output.pixels_per_point,
+        );
+
+        command_buffers.push(encoder.finish());
+        window.render_state.get_queue().submit(command_buffers);
+
+        // Show to screen
+        output_texture.present();
+    }
+}
@@ -0,0 +1,84 @@
+use winit::event_loop::ActiveEventLoop;
+
+use crate::{map, report};
+
+use super::{Branch, MainLoop};
+
+impl<S: map::sun::Intensity> MainLoop<S> {
+    /// Ends the run by writing the summary report with a thumbnail of the
+    /// final map and the ancestry tree of the surviving lineages next to it
+    /// and closing the application
+    ///
+    /// # Parameters
+    ///
+    /// event_loop: The event loop currently running
+    ///
+    /// reason: The reason the run ended
+    pub(super) fn end_run(&mut self, event_loop: &ActiveEventLoop, reason: report::EndReason) {
+        // Only write a single report even if several end conditions are met
+        if !self.state.flags.run_ended {
+            self.state.flags.run_ended = true;
+
+            // The report always describes the original run
+            if self.branch != Branch::Original {
+                self.switch_branch();
+            }
+
+            self.write_report(reason, "The final map");
+            self.export_phylogeny();
+        }
+
+        // Stop the application
+        event_loop.exit();
+    }
+
+    /// Pauses the original run because it has met a stop condition and
+    /// writes the summary report so far, the condition is not checked again
+    /// such that the run can be continued
+    ///
+    /// # Parameters
+    ///
+    /// reason: The stop condition which was met
+    pub(super) fn pause_run(&mut self, reason: report::EndReason) {
+        println!("Paused the run at step {}: {}", self.map.get_time(), reason);
+        self.state.flags.run_simulation = false;
+        self.settings_viewer.stop_conditions.disarm(reason);
+        self.write_report(reason, "The map when the run was paused");
+    }
+
+    /// Writes the summary report of the shown run with a thumbnail of the
+    /// current map next to it
+    ///
+    /// # Parameters
+    ///
+    /// reason: The reason the run was stopped
+    ///
+    /// caption: The caption of the thumbnail
+    fn write_report(&mut self, reason: report::EndReason, caption: &str) {
+        if let Some(Err(error)) = self
+            .stats_exporter
+            .as_mut()
+            .map(|exporter| exporter.flush())
+        {
+            eprintln!("Unable to export statistics: {:?}", error);
+        }
+
+        let mut summary = report::Report::new(reason, &self.recorder, &self.map);
+        if let Some(file_name) = self
+            .save_thumbnail()
+            .as_ref()
+            .and_then(|path| path.file_name())
+        {
+            summary = summary.with_thumbnail(caption, file_name.as_ref());
+        }
+
+        let path = self
+            .settings_viewer
+            .export_directory
+            .join(format!("report_step_{}.md", self.map.get_time()));
+        match summary.save(&path) {
+            Ok(()) => println!("Saved run report to {}", path.display()),
+            Err(error) => eprintln!("Unable to save run report: {:?}", error),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use std::path::PathBuf;
+
+use crate::map;
+
+use super::MainLoop;
+
+impl<S: map::sun::Intensity> MainLoop<S> {
+    /// Renders the full map into a thumbnail and saves it as a PNG file named
+    /// after the current time step, returns the path of the file or None if it
+    /// could not be saved
+    pub(super) fn save_thumbnail(&mut self) -> Option<PathBuf> {
+        let window = self.window.get();
+
+        // Make sure the thumbnail shows the current map
+        if self.state.flags.map_changed {
+            self.state.flags.map_changed = false;
+            window
+                .graphics_state
+                .update_map_dirty(&window.render_state, &self.map);
+            self.map.clear_dirty();
+        }
+
+        let thumbnail = match window.graphics_state.render_thumbnail(
+            &window.render_state,
+            &self.settings_viewer.home_view,
+            self.camera.get_settings().map_width,
+            self.settings_viewer.thumbnail_width,
+        ) {
+            Ok(value) => value,
+            Err(error) => {
+                eprintln!("Unable to render thumbnail: {}", error);
+                return None;
+            }
+        };
+
+        let path = self
+            .settings_viewer
+            .export_directory
+            .join(format!("step_{}.png", self.map.get_time()));
+        return match thumbnail.save_png(&path) {
+            Ok(()) => {
+                println!("Saved thumbnail to {}", path.display());
+                Some(path)
+            }
+            Err(error) => {
+                eprintln!("Unable to save thumbnail: {}", error);
+                None
+            }
+        };
+    }
+}
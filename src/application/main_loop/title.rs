@@ -0,0 +1,37 @@
+use web_time::Instant;
+
+use crate::map;
+
+use super::MainLoop;
+
+impl<S: map::sun::Intensity> MainLoop<S> {
+    /// Updates the title of the main window with the current step, the
+    /// measured simulation rate and the framerate since the last update, and
+    /// restarts the counting
+    ///
+    /// # Parameters
+    ///
+    /// now: The current time
+    pub(super) fn update_title(&mut self, now: Instant) {
+        // Measure over the full time since the last update in case the loop
+        // was late
+        let interval = self.settings_window.title_interval;
+        let elapsed = (now - self.state.next_title_time + interval).as_secs_f64();
+        let sim_rate = self.state.title_steps as f64 / elapsed;
+        let framerate = self.state.title_frames as f64 / elapsed;
+
+        self.state.title_steps = 0;
+        self.state.title_frames = 0;
+        self.state.next_title_time = now + interval;
+
+        if self.window.is_open() {
+            self.window.get().window.set_title(&format!(
+                "{} - Step {} - {:.1} steps/s - {:.1} fps",
+                self.settings_window.name,
+                self.map.get_time(),
+                sim_rate,
+                framerate,
+            ));
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{map, types};
+use crate::{constants, map, types};
 
 use super::MainLoop;
 
@@ -35,4 +35,33 @@ impl<S: map::sun::Intensity> MainLoop<S> {
         self.camera.reset_keys();
         self.camera.set_transform(transform);
     }
+
+    /// Saves the current view as a bookmark
+    ///
+    /// # Parameters
+    ///
+    /// id: The index of the bookmark
+    pub(super) fn save_bookmark(&mut self, id: usize) {
+        self.settings_viewer.bookmarks[id] = Some(self.camera.get_view());
+        println!("Saved the view as bookmark {}", (id + 1) % 10);
+    }
+
+    /// Moves the camera smoothly to a bookmarked view and stops following
+    /// any lineage, returns true if the bookmark exists
+    ///
+    /// # Parameters
+    ///
+    /// id: The index of the bookmark
+    pub(super) fn recall_bookmark(&mut self, id: usize) -> bool {
+        let Some(view) = self.settings_viewer.bookmarks[id] else {
+            println!("No view saved as bookmark {}", (id + 1) % 10);
+            return false;
+        };
+        self.state.follow_lineage = None;
+        self.camera.reset_keys();
+        self.camera
+            .animate_to(&view, constants::CAMERA_ANIMATION_DURATION);
+
+        return true;
+    }
 }
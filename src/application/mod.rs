@@ -2,7 +2,11 @@ use winit::event_loop::EventLoop;
 
 use crate::map;
 
+mod error;
+pub use error::ApplicationError;
+
 mod window;
+pub use window::UserEvent;
 use window::{OptionalRenderedWindow, RenderedWindow};
 
 mod settings;
@@ -12,25 +16,57 @@ pub use settings::{ShaderSettingsInput, ViewerSettingsInput, WindowSettingsInput
 mod state;
 use state::State;
 
+mod overlay;
+use overlay::{
+    AnnotationMarker, Overlay, OverlayActions, OverlayInfo, ProfileInfo, StatsInfo, TimingInfo,
+};
+
 mod main_loop;
 pub use main_loop::MainLoop;
 
+mod headless;
+pub use headless::run_headless;
+use headless::{export_statistics, save_phylogeny};
+
 /// Runs the application
 pub fn run<S: map::sun::Intensity>(main_loop: &mut MainLoop<S>) {
-    // Setup logging
-    env_logger::init();
-
     // Create the event loop
-    let event_loop = match EventLoop::new() {
+    let event_loop = match EventLoop::with_user_event().build() {
         Ok(value) => value,
         Err(error) => {
-            eprintln!("Unable to create event loop: {:?}", error);
+            eprintln!("{}", ApplicationError::CreateEventLoop(error));
             return;
         }
     };
 
     if let Err(error) = event_loop.run_app(main_loop) {
-        eprintln!("An error occured in the main loop: {:?}", error);
+        eprintln!("{}", ApplicationError::RunEventLoop(error));
         return;
     }
 }
+
+/// Runs the application in a web page, the event loop is handed over to the
+/// browser so this returns right away while the application keeps running
+///
+/// # Parameters
+///
+/// main_loop: The main loop to run
+///
+/// # Errors
+///
+/// Returns ApplicationError::CreateEventLoop if the event loop could not be
+/// created
+#[cfg(target_arch = "wasm32")]
+pub fn run_web<S: map::sun::Intensity + 'static>(
+    main_loop: MainLoop<S>,
+) -> Result<(), ApplicationError> {
+    use winit::platform::web::EventLoopExtWebSys;
+
+    let event_loop = EventLoop::with_user_event()
+        .build()
+        .map_err(ApplicationError::CreateEventLoop)?;
+    let main_loop = main_loop.with_proxy(event_loop.create_proxy());
+    event_loop.spawn_app(main_loop);
+
+    return Ok(());
+}
@@ -0,0 +1,872 @@
+use winit::{event::WindowEvent, window::Window};
+
+use crate::{constants, map, report, types};
+
+/// The egui overlay showing the state of the simulation and giving access to
+/// the most common controls
+pub struct Overlay {
+    /// The egui context holding the state of all widgets
+    context: egui::Context,
+    /// Converts window events into egui input
+    input: egui_winit::State,
+}
+
+impl Overlay {
+    /// Constructs a new overlay for a window
+    ///
+    /// # Parameters
+    ///
+    /// window: The window the overlay is shown in
+    pub fn new(window: &Window) -> Self {
+        let context = egui::Context::default();
+        let input = egui_winit::State::new(
+            context.clone(),
+            egui::ViewportId::ROOT,
+            window,
+            Some(window.scale_factor() as f32),
+            window.theme(),
+            None,
+        );
+
+        return Self { context, input };
+    }
+
+    /// Passes a window event to the overlay, returns true if the overlay used
+    /// the event such that it should not be handled by the viewer
+    ///
+    /// # Parameters
+    ///
+    /// window: The window the event happened in
+    ///
+    /// event: The event to pass on
+    pub fn on_window_event(&mut self, window: &Window, event: &WindowEvent) -> bool {
+        let response = self.input.on_window_event(window, event);
+        if response.repaint {
+            window.request_redraw();
+        }
+
+        return response.consumed;
+    }
+
+    /// Lays out the overlay for this frame, returns the actions requested by
+    /// the user and the output to render
+    ///
+    /// # Parameters
+    ///
+    /// window: The window the overlay is shown in
+    ///
+    /// info: The state of the simulation to show
+    pub fn run(&mut self, window: &Window, info: &OverlayInfo) -> (OverlayActions, OverlayOutput) {
+        let mut actions = OverlayActions::new();
+        let output = self.run_ui(window, |context| {
+            show(context, info, &mut actions);
+        });
+
+        return (actions, output);
+    }
+
+    /// Lays out the statistics window for this frame and returns the output
+    /// to render, it fills the entire window
+    ///
+    /// # Parameters
+    ///
+    /// window: The statistics window
+    ///
+    /// info: The state of the run to show
+    pub fn run_stats(&mut self, window: &Window, info: &StatsInfo) -> OverlayOutput {
+        return self.run_ui(window, |context| {
+            show_stats(context, info);
+        });
+    }
+
+    /// Lays out a frame of the overlay and returns the output to render
+    ///
+    /// # Parameters
+    ///
+    /// window: The window the overlay is shown in
+    ///
+    /// ui: Lays out all widgets of the frame
+    fn run_ui(&mut self, window: &Window, ui: impl FnMut(&egui::Context)) -> OverlayOutput {
+        let raw_input = self.input.take_egui_input(window);
+        let output = self.context.run(raw_input, ui);
+        self.input
+            .handle_platform_output(window, output.platform_output);
+
+        let primitives = self
+            .context
+            .tessellate(output.shapes, output.pixels_per_point);
+
+        return OverlayOutput {
+            primitives,
+            textures_delta: output.textures_delta,
+            pixels_per_point: output.pixels_per_point,
+        };
+    }
+}
+
+/// A snapshot of the state of the simulation to show in the overlay
+#[derive(Clone, Debug)]
+pub struct OverlayInfo {
+    /// True if the simulation is running
+    pub running: bool,
+    /// The number of simulation steps per second
+    pub sim_rate: f64,
+    /// The number of saved states which can be stepped back to
+    pub history_len: usize,
+    /// The name of the shown run if the simulation has been forked
+    pub branch: Option<&'static str>,
+    /// The lineage followed by the camera if any
+    pub follow_lineage: Option<usize>,
+    /// The current display mode of the background
+    pub mode_background: map::DataModeBackground,
+    /// The scaling of the color map of the current background display mode
+    pub scaling_background: types::ColorScaling,
+    /// The current display mode of the plants
+    pub mode_plant: map::DataModePlant,
+    /// The statistics of the map
+    pub statistics: map::Statistics,
+    /// The fraction of the day which has passed from noon if the sun follows
+    /// a day
+    pub time_of_day: Option<f64>,
+    /// The fraction of the year which has passed from midsummer if the sun
+    /// follows a year
+    pub time_of_year: Option<f64>,
+    /// The number of lineages which have existed on the map
+    pub lineage_count: usize,
+    /// The state of the selected tile if any
+    pub selected_tile: Option<map::TileInfo>,
+    /// The notes pinned to the selected tile or to the organism growing on
+    /// it
+    pub selected_annotations: Vec<map::Annotation>,
+    /// Every note on the map with the position of its marker
+    pub annotations: Vec<AnnotationMarker>,
+    /// The bulk type placed by clicking if the edit mode is active
+    pub edit_bulk: Option<map::EditBulk>,
+    /// True if the panels of the overlay are shown, otherwise only the error
+    /// is shown
+    pub show_panels: bool,
+    /// The message of the last recoverable error if it has not been
+    /// dismissed
+    pub error: Option<String>,
+    /// The durations of the recent frames and simulation steps if the
+    /// profiler is shown
+    pub profile: Option<ProfileInfo>,
+    /// The light reaching the ground and the plant biomass of every column if
+    /// the column profile is shown
+    pub columns: Option<map::ColumnProfile>,
+}
+
+/// A note shown as a marker on the map
+#[derive(Clone, Debug)]
+pub struct AnnotationMarker {
+    /// The note
+    pub annotation: map::Annotation,
+    /// The position of the marker in pixels from the top left corner of the
+    /// window
+    pub pixel: types::Point,
+}
+
+/// The durations of the recent frames and simulation steps
+#[derive(Clone, Debug)]
+pub struct ProfileInfo {
+    /// The time spent drawing frames
+    pub frame: TimingInfo,
+    /// The time spent on simulation steps
+    pub step: TimingInfo,
+}
+
+/// A summary of the recent durations of a repeated task
+#[derive(Clone, Debug)]
+pub struct TimingInfo {
+    /// The average duration in milliseconds
+    pub average: f64,
+    /// The duration in milliseconds which 95% of the durations do not exceed
+    pub percentile_95: f64,
+    /// The durations in milliseconds from the oldest to the newest
+    pub samples: Vec<f64>,
+}
+
+/// A snapshot of the run to show in the statistics window
+#[derive(Clone, Debug)]
+pub struct StatsInfo {
+    /// The name of the shown run if the simulation has been forked
+    pub branch: Option<&'static str>,
+    /// The statistics of the shown run
+    pub statistics: map::Statistics,
+    /// The number of lineages which have existed in the shown run
+    pub lineage_count: usize,
+    /// The statistics at the largest population of the recorded run
+    pub peak: map::Statistics,
+    /// The noteworthy events of the recorded run from the newest to the
+    /// oldest
+    pub events: Vec<report::Event>,
+    /// The state of the selected tile if any
+    pub selected_tile: Option<map::TileInfo>,
+}
+
+/// All actions requested by the user through the overlay in a single frame
+#[derive(Clone, Debug, PartialEq)]
+pub struct OverlayActions {
+    /// True if the simulation should be paused or resumed
+    pub toggle_simulation: bool,
+    /// True if the simulation should take a single step
+    pub step: bool,
+    /// True if the simulation should go back to the previous saved state
+    pub rewind: bool,
+    /// The new number of simulation steps per second if it was changed
+    pub sim_rate: Option<f64>,
+    /// The new display mode of the background if it was changed
+    pub mode_background: Option<map::DataModeBackground>,
+    /// The new scaling of the background color map if it was changed
+    pub scaling_background: Option<types::ColorScaling>,
+    /// The new display mode of the plants if it was changed
+    pub mode_plant: Option<map::DataModePlant>,
+    /// True if the selected tile should be deselected
+    pub deselect: bool,
+    /// The text of a note to pin to the selected tile and whether it should
+    /// follow the organism on it
+    pub add_annotation: Option<(String, bool)>,
+    /// The id of the note to remove if any
+    pub remove_annotation: Option<usize>,
+    /// True if the shown error should be dismissed
+    pub dismiss_error: bool,
+}
+
+impl OverlayActions {
+    /// Constructs a new set of actions where nothing is requested
+    pub fn new() -> Self {
+        return Self {
+            toggle_simulation: false,
+            step: false,
+            rewind: false,
+            sim_rate: None,
+            mode_background: None,
+            scaling_background: None,
+            mode_plant: None,
+            deselect: false,
+            add_annotation: None,
+            remove_annotation: None,
+            dismiss_error: false,
+        };
+    }
+}
+
+/// Everything needed to render the overlay for a single frame
+pub struct OverlayOutput {
+    /// The tessellated shapes of the overlay
+    pub primitives: Vec<egui::ClippedPrimitive>,
+    /// The changes to the textures used by the overlay
+    pub textures_delta: egui::TexturesDelta,
+    /// The number of pixels for each egui point
+    pub pixels_per_point: f32,
+}
+
+/// The smallest number of simulation steps per second which can be entered
+const SIM_RATE_MIN: f64 = 0.01;
+
+/// The size of the graph of the profiler in points
+const PROFILER_WIDTH: f32 = 240.0;
+const PROFILER_HEIGHT: f32 = 60.0;
+
+/// The radius of the clock and season dials in points
+const DIAL_RADIUS: f32 = 18.0;
+
+/// The names of the seasons starting at midsummer
+const SEASON_NAMES: [&str; 4] = ["Summer", "Autumn", "Winter", "Spring"];
+
+/// The size of the graph of the column profile in points
+const COLUMNS_WIDTH: f32 = 360.0;
+const COLUMNS_HEIGHT: f32 = 80.0;
+
+/// The radius of the markers of notes in points
+const ANNOTATION_RADIUS: f32 = 6.0;
+
+/// Lays out all panels of the overlay
+///
+/// # Parameters
+///
+/// context: The egui context to lay out in
+///
+/// info: The state of the simulation to show
+///
+/// actions: The actions to fill in with the requests of the user
+fn show(context: &egui::Context, info: &OverlayInfo, actions: &mut OverlayActions) {
+    if let Some(error) = &info.error {
+        show_error(context, error, actions);
+    }
+    if let Some(profile) = &info.profile {
+        show_profiler(context, profile);
+    }
+    if let Some(columns) = &info.columns {
+        show_columns(context, columns);
+    }
+    if !info.show_panels {
+        return;
+    }
+
+    show_hud(context, info);
+    show_annotations(context, &info.annotations);
+
+    egui::Window::new("Simulation")
+        .default_pos([10.0, 10.0])
+        .resizable(false)
+        .show(context, |ui| {
+            // Running the simulation
+            ui.horizontal(|ui| {
+                if ui
+                    .button(if info.running { "Pause" } else { "Resume" })
+                    .clicked()
+                {
+                    actions.toggle_simulation = true;
+                }
+                if ui
+                    .add_enabled(!info.running, egui::Button::new("Step"))
+                    .clicked()
+                {
+                    actions.step = true;
+                }
+                if ui
+                    .add_enabled(
+                        !info.running && info.history_len > 0,
+                        egui::Button::new("Back"),
+                    )
+                    .clicked()
+                {
+                    actions.rewind = true;
+                }
+            });
+            let mut sim_rate = info.sim_rate;
+            let slider = ui.add(
+                egui::Slider::new(&mut sim_rate, 0.1..=10000.0)
+                    .logarithmic(true)
+                    .text("Steps per second"),
+            );
+            let exact = ui
+                .horizontal(|ui| {
+                    ui.label("Exact rate");
+                    return ui.add(
+                        egui::DragValue::new(&mut sim_rate)
+                            .range(SIM_RATE_MIN..=f64::MAX)
+                            .speed(0.1)
+                            .suffix(" steps/s"),
+                    );
+                })
+                .inner;
+            if slider.changed() || exact.changed() {
+                actions.sim_rate = Some(sim_rate);
+            }
+
+            // The display mode
+            let mut mode_background = info.mode_background;
+            egui::ComboBox::from_label("Background")
+                .selected_text(mode_background.get_name())
+                .show_ui(ui, |ui| {
+                    for id in 0..map::DataModeBackground::COUNT {
+                        let mode = map::DataModeBackground::from_id(id);
+                        ui.selectable_value(&mut mode_background, mode, mode.get_name());
+                    }
+                });
+            if mode_background != info.mode_background {
+                actions.mode_background = Some(mode_background);
+            }
+            let mut scaling_background = info.scaling_background;
+            egui::ComboBox::from_label("Scaling")
+                .selected_text(scaling_background.get_name())
+                .show_ui(ui, |ui| {
+                    for id in 0..types::ColorScaling::COUNT {
+                        let scaling = types::ColorScaling::from_id(id);
+                        ui.selectable_value(&mut scaling_background, scaling, scaling.get_name());
+                    }
+                });
+            if scaling_background != info.scaling_background {
+                actions.scaling_background = Some(scaling_background);
+            }
+            let mut mode_plant = info.mode_plant;
+            egui::ComboBox::from_label("Plants")
+                .selected_text(mode_plant.get_name())
+                .show_ui(ui, |ui| {
+                    for id in 0..map::DataModePlant::COUNT {
+                        let mode = map::DataModePlant::from_id(id);
+                        ui.selectable_value(&mut mode_plant, mode, mode.get_name());
+                    }
+                });
+            if mode_plant != info.mode_plant {
+                actions.mode_plant = Some(mode_plant);
+            }
+
+            // The statistics of the map
+            ui.separator();
+            show_statistics(ui, &info.statistics, info.lineage_count);
+        });
+
+    // The details of the selected tile
+    if let Some(tile) = &info.selected_tile {
+        let mut open = true;
+        egui::Window::new("Tile")
+            .default_pos([10.0, 250.0])
+            .open(&mut open)
+            .show(context, |ui| {
+                ui.monospace(tile.to_string());
+                ui.separator();
+                show_annotation_editor(ui, tile, &info.selected_annotations, actions);
+            });
+        if !open {
+            actions.deselect = true;
+        }
+    }
+}
+
+/// Draws a marker for every note on the map and shows the notes below the
+/// cursor in a tooltip, notes following an organism are filled
+///
+/// # Parameters
+///
+/// context: The egui context to lay out in
+///
+/// annotations: The notes to show
+fn show_annotations(context: &egui::Context, annotations: &[AnnotationMarker]) {
+    let painter = context.layer_painter(egui::LayerId::background());
+    let pixels_per_point = context.pixels_per_point() as f64;
+    let pointer = context
+        .pointer_hover_pos()
+        .filter(|_| !context.is_pointer_over_area());
+
+    let mut hovered = Vec::new();
+    for marker in annotations.iter() {
+        let center = egui::pos2(
+            (marker.pixel.x / pixels_per_point) as f32,
+            (marker.pixel.y / pixels_per_point) as f32,
+        );
+        let fill = if marker.annotation.lineage.is_some() {
+            egui::Color32::YELLOW
+        } else {
+            egui::Color32::TRANSPARENT
+        };
+        painter.circle(
+            center,
+            ANNOTATION_RADIUS,
+            fill,
+            egui::Stroke::new(2.0, egui::Color32::YELLOW),
+        );
+
+        if pointer.is_some_and(|pointer| pointer.distance(center) <= ANNOTATION_RADIUS) {
+            hovered.push(&marker.annotation);
+        }
+    }
+
+    if hovered.is_empty() {
+        return;
+    }
+    egui::Tooltip::always_open(
+        context.clone(),
+        egui::LayerId::background(),
+        egui::Id::new("Annotations"),
+        egui::PopupAnchor::Pointer,
+    )
+    .show(|ui| {
+        for annotation in hovered {
+            ui.label(format!("Step {}: {}", annotation.time, annotation.text));
+        }
+    });
+}
+
+/// Lists the notes of the selected tile and lets the user add a new note to
+/// it, the text of the new note is kept in the memory of egui between frames
+///
+/// # Parameters
+///
+/// ui: The ui to lay out in
+///
+/// tile: The selected tile
+///
+/// annotations: The notes pinned to the tile or its organism
+///
+/// actions: The actions to fill in with the requests of the user
+fn show_annotation_editor(
+    ui: &mut egui::Ui,
+    tile: &map::TileInfo,
+    annotations: &[map::Annotation],
+    actions: &mut OverlayActions,
+) {
+    for annotation in annotations.iter() {
+        ui.horizontal(|ui| {
+            if ui.small_button("Remove").clicked() {
+                actions.remove_annotation = Some(annotation.id);
+            }
+            let pin = match annotation.lineage {
+                Some(lineage) => format!(" (lineage {})", lineage),
+                None => String::new(),
+            };
+            ui.label(format!(
+                "Step {}{}: {}",
+                annotation.time, pin, annotation.text
+            ));
+        });
+    }
+
+    let text_id = egui::Id::new("Annotation text");
+    let pin_id = egui::Id::new("Annotation pin");
+    let mut text = ui.data(|data| data.get_temp::<String>(text_id).unwrap_or_default());
+    let mut pin_organism = ui.data(|data| data.get_temp::<bool>(pin_id).unwrap_or(false));
+
+    let has_organism = tile
+        .plant
+        .as_ref()
+        .is_some_and(|plant| plant.alive && plant.lineage.is_some());
+    ui.horizontal(|ui| {
+        ui.text_edit_singleline(&mut text);
+        if ui
+            .add_enabled(!text.trim().is_empty(), egui::Button::new("Add note"))
+            .clicked()
+        {
+            actions.add_annotation = Some((text.trim().to_string(), pin_organism && has_organism));
+            text.clear();
+        }
+    });
+    ui.add_enabled(
+        has_organism,
+        egui::Checkbox::new(&mut pin_organism, "Follow the organism"),
+    );
+
+    ui.data_mut(|data| {
+        data.insert_temp(text_id, text);
+        data.insert_temp(pin_id, pin_organism);
+    });
+}
+
+/// Lays out the statistics window, it is meant to be kept open next to the
+/// map for following the run while the map is zoomed in
+///
+/// # Parameters
+///
+/// context: The egui context to lay out in
+///
+/// info: The state of the run to show
+fn show_stats(context: &egui::Context, info: &StatsInfo) {
+    egui::CentralPanel::default().show(context, |ui| {
+        ui.heading(match info.branch {
+            Some(branch) => format!("{} run", branch),
+            None => "Run".to_string(),
+        });
+        show_statistics(ui, &info.statistics, info.lineage_count);
+
+        // The summary of the recorded run
+        ui.separator();
+        ui.heading("Recorded run");
+        ui.label(format!(
+            "Peak population {} at step {}",
+            info.peak.population, info.peak.time
+        ));
+        egui::ScrollArea::vertical()
+            .max_height(150.0)
+            .show(ui, |ui| {
+                for event in info.events.iter() {
+                    ui.label(format!("Step {}: {}", event.time, event.description));
+                }
+            });
+
+        // The details of the selected tile
+        ui.separator();
+        ui.heading("Selected tile");
+        match &info.selected_tile {
+            Some(tile) => ui.monospace(tile.to_string()),
+            None => ui.label("Click a tile on the map to inspect it"),
+        };
+    });
+}
+
+/// Shows the statistics of a map in a grid
+///
+/// # Parameters
+///
+/// ui: The ui to lay out in
+///
+/// statistics: The statistics of the map
+///
+/// lineage_count: The number of lineages which have existed on the map
+fn show_statistics(ui: &mut egui::Ui, statistics: &map::Statistics, lineage_count: usize) {
+    egui::Grid::new("Statistics").show(ui, |ui| {
+        ui.label("Step");
+        ui.label(statistics.time.to_string());
+        ui.end_row();
+        ui.label("Population");
+        ui.label(statistics.population.to_string());
+        ui.end_row();
+        ui.label("Energy");
+        ui.label(format!("{:.3}", statistics.energy));
+        ui.end_row();
+        ui.label("Bridge transfer");
+        ui.label(format!("{:.3}", statistics.transfer));
+        ui.end_row();
+        ui.label("Max bridge throughput");
+        ui.label(format!("{:.3}", statistics.max_throughput));
+        ui.end_row();
+        ui.label("Lineages");
+        ui.label(lineage_count.to_string());
+        ui.end_row();
+        ui.label("Species");
+        ui.label(statistics.species.to_string());
+        ui.end_row();
+    });
+}
+
+/// Shows the last recoverable error at the top of the screen until it is
+/// dismissed
+///
+/// # Parameters
+///
+/// context: The egui context to lay out in
+///
+/// error: The message of the error
+///
+/// actions: The actions to fill in with the requests of the user
+fn show_error(context: &egui::Context, error: &str, actions: &mut OverlayActions) {
+    egui::Area::new(egui::Id::new("Error"))
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 10.0])
+        .show(context, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                    if ui.button("Dismiss").clicked() {
+                        actions.dismiss_error = true;
+                    }
+                });
+            });
+        });
+}
+
+/// Shows the speed and progress of the simulation in the corner of the screen
+///
+/// # Parameters
+///
+/// context: The egui context to lay out in
+///
+/// info: The state of the simulation to show
+fn show_hud(context: &egui::Context, info: &OverlayInfo) {
+    egui::Area::new(egui::Id::new("HUD"))
+        .anchor(egui::Align2::RIGHT_TOP, [-10.0, 10.0])
+        .interactable(false)
+        .show(context, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.monospace(format!(
+                    "{}  {:.2} steps/s  step {}",
+                    if info.running { "Running" } else { "Paused" },
+                    info.sim_rate,
+                    info.statistics.time
+                ));
+                if let Some(branch) = info.branch {
+                    ui.monospace(format!("Run: {}", branch));
+                }
+                if let Some(lineage) = info.follow_lineage {
+                    ui.monospace(format!("Following lineage {}", lineage));
+                }
+                if let Some(bulk) = info.edit_bulk {
+                    ui.monospace(format!("Editing: {}", bulk.get_name()));
+                }
+                if info.time_of_day.is_some() || info.time_of_year.is_some() {
+                    ui.horizontal(|ui| {
+                        if let Some(time_of_day) = info.time_of_day {
+                            // Noon is at the top of the clock
+                            let minutes = ((time_of_day + 0.5).fract() * 24.0 * 60.0) as usize;
+                            show_dial(
+                                ui,
+                                time_of_day,
+                                egui::Color32::from_rgb(255, 220, 80),
+                                &format!("{:02}:{:02}", minutes / 60, minutes % 60),
+                            );
+                        }
+                        if let Some(time_of_year) = info.time_of_year {
+                            // The seasons are centered on the solstices and
+                            // equinoxes
+                            let season = (time_of_year * 4.0).round() as usize % 4;
+                            show_dial(
+                                ui,
+                                time_of_year,
+                                egui::Color32::from_rgb(120, 200, 120),
+                                SEASON_NAMES[season],
+                            );
+                        }
+                    });
+                }
+            });
+        });
+}
+
+/// Shows a round dial with a hand going clockwise from the top and a label
+/// below it
+///
+/// # Parameters
+///
+/// ui: The ui to lay out in
+///
+/// fraction: The fraction of a full turn the hand points at
+///
+/// color: The color of the hand
+///
+/// label: The text below the dial
+fn show_dial(ui: &mut egui::Ui, fraction: f64, color: egui::Color32, label: &str) {
+    ui.vertical(|ui| {
+        let (rect, _) = ui.allocate_exact_size(
+            egui::vec2(2.0 * DIAL_RADIUS, 2.0 * DIAL_RADIUS),
+            egui::Sense::hover(),
+        );
+        let painter = ui.painter_at(rect);
+        let center = rect.center();
+        painter.circle_stroke(
+            center,
+            DIAL_RADIUS - 1.0,
+            ui.visuals().widgets.noninteractive.fg_stroke,
+        );
+        let angle = (fraction * 2.0 * constants::MATH_PI) as f32;
+        let hand = egui::vec2(angle.sin(), -angle.cos()) * (DIAL_RADIUS - 4.0);
+        painter.line_segment([center, center + hand], egui::Stroke::new(2.0, color));
+        ui.monospace(label);
+    });
+}
+
+/// Shows the durations of the recent frames and simulation steps as a graph
+/// in the corner of the screen
+///
+/// # Parameters
+///
+/// context: The egui context to lay out in
+///
+/// profile: The durations to show
+fn show_profiler(context: &egui::Context, profile: &ProfileInfo) {
+    let frame_color = egui::Color32::from_rgb(80, 160, 255);
+    let step_color = egui::Color32::from_rgb(255, 160, 60);
+
+    egui::Area::new(egui::Id::new("Profiler"))
+        .anchor(egui::Align2::RIGHT_BOTTOM, [-10.0, -10.0])
+        .interactable(false)
+        .show(context, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                for (name, timing, color) in [
+                    ("Frame", &profile.frame, frame_color),
+                    ("Step ", &profile.step, step_color),
+                ] {
+                    ui.colored_label(
+                        color,
+                        egui::RichText::new(format!(
+                            "{} avg {:7.2} ms  p95 {:7.2} ms",
+                            name, timing.average, timing.percentile_95
+                        ))
+                        .monospace(),
+                    );
+                }
+
+                // Both durations share the vertical scale such that they can
+                // be compared
+                let (rect, _) = ui.allocate_exact_size(
+                    egui::vec2(PROFILER_WIDTH, PROFILER_HEIGHT),
+                    egui::Sense::hover(),
+                );
+                let painter = ui.painter_at(rect);
+                painter.rect_stroke(
+                    rect,
+                    0.0,
+                    ui.visuals().widgets.noninteractive.bg_stroke,
+                    egui::StrokeKind::Inside,
+                );
+                let max = profile
+                    .frame
+                    .samples
+                    .iter()
+                    .chain(profile.step.samples.iter())
+                    .copied()
+                    .fold(0.0, f64::max);
+                if max <= 0.0 {
+                    return;
+                }
+                for (timing, color) in [(&profile.frame, frame_color), (&profile.step, step_color)]
+                {
+                    let count = timing.samples.len().max(2) - 1;
+                    let points = timing
+                        .samples
+                        .iter()
+                        .enumerate()
+                        .map(|(index, duration)| {
+                            egui::pos2(
+                                rect.left() + rect.width() * index as f32 / count as f32,
+                                rect.bottom() - rect.height() * (duration / max) as f32,
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    painter.add(egui::Shape::line(points, egui::Stroke::new(1.0, color)));
+                }
+                ui.label(format!("Scale: {:.2} ms", max));
+            });
+        });
+}
+
+/// Shows the light reaching the ground and the plant biomass of every column
+/// as a graph at the bottom of the screen
+///
+/// # Parameters
+///
+/// context: The egui context to lay out in
+///
+/// columns: The profile of the columns to show
+fn show_columns(context: &egui::Context, columns: &map::ColumnProfile) {
+    let light_color = egui::Color32::from_rgb(255, 220, 80);
+    let biomass_color = egui::Color32::from_rgb(80, 200, 100);
+
+    let biomass = columns
+        .biomass
+        .iter()
+        .map(|&count| count as f64)
+        .collect::<Vec<_>>();
+
+    egui::Area::new(egui::Id::new("Columns"))
+        .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -10.0])
+        .interactable(false)
+        .show(context, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                // The two values have different units so each is scaled by its
+                // own largest value which is printed in the legend
+                let light_max = columns.light.iter().copied().fold(0.0, f64::max);
+                let biomass_max = biomass.iter().copied().fold(0.0, f64::max);
+                for (name, max, color) in [
+                    ("Ground light", light_max, light_color),
+                    ("Biomass     ", biomass_max, biomass_color),
+                ] {
+                    ui.colored_label(
+                        color,
+                        egui::RichText::new(format!("{} max {:9.3}", name, max)).monospace(),
+                    );
+                }
+
+                let (rect, _) = ui.allocate_exact_size(
+                    egui::vec2(COLUMNS_WIDTH, COLUMNS_HEIGHT),
+                    egui::Sense::hover(),
+                );
+                let painter = ui.painter_at(rect);
+                painter.rect_stroke(
+                    rect,
+                    0.0,
+                    ui.visuals().widgets.noninteractive.bg_stroke,
+                    egui::StrokeKind::Inside,
+                );
+                for (values, max, color) in [
+                    (&columns.light, light_max, light_color),
+                    (&biomass, biomass_max, biomass_color),
+                ] {
+                    if max <= 0.0 {
+                        continue;
+                    }
+                    let count = values.len().max(2) - 1;
+                    let points = values
+                        .iter()
+                        .enumerate()
+                        .map(|(index, value)| {
+                            egui::pos2(
+                                rect.left() + rect.width() * index as f32 / count as f32,
+                                rect.bottom() - rect.height() * (value / max) as f32,
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    painter.add(egui::Shape::line(points, egui::Stroke::new(1.0, color)));
+                }
+            });
+        });
+}
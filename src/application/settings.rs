@@ -1,6 +1,12 @@
-use winit::dpi::PhysicalSize;
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
-use crate::{graphics, map, types};
+use thiserror::Error;
+use winit::{dpi::PhysicalSize, window::Icon};
+
+use crate::{constants, graphics, map, report, types};
 
 /// All input settings for how to open and display a window
 #[derive(Clone, Debug)]
@@ -9,6 +15,9 @@ pub struct WindowSettingsInput {
     pub name: String,
     /// The size of the application window
     pub size: PhysicalSize<u32>,
+    /// The png image to use as the icon of the window, None to use the
+    /// default icon
+    pub icon: Option<PathBuf>,
     /// The settings for rendering
     pub graphics_settings: graphics::Settings,
 }
@@ -20,6 +29,11 @@ pub struct WindowSettings {
     pub name: String,
     /// The size of the application window
     pub size: PhysicalSize<u32>,
+    /// The icon of the window, None to use the default icon
+    pub icon: Option<Icon>,
+    /// The time between each update of the window title with the live
+    /// statistics
+    pub title_interval: Duration,
     /// The settings for rendering
     pub graphics_settings: graphics::Settings,
 }
@@ -31,23 +45,73 @@ impl WindowSettings {
     ///
     /// input: The user input settings
     pub fn new(input: WindowSettingsInput) -> Self {
+        // The default icon is used if the icon cannot be loaded
+        let icon = input.icon.and_then(|path| match load_icon(&path) {
+            Ok(icon) => Some(icon),
+            Err(error) => {
+                eprintln!("Unable to load window icon {:?}: {}", path, error);
+                None
+            }
+        });
+
         return Self {
             name: input.name,
             size: input.size,
+            icon,
+            title_interval: Duration::from_secs_f64(constants::TITLE_INTERVAL),
             graphics_settings: input.graphics_settings,
         };
     }
 }
 
+/// Loads a window icon from an image file
+///
+/// # Parameters
+///
+/// path: The path of the image
+fn load_icon(path: &Path) -> Result<Icon, IconError> {
+    let image = image::open(path)?.into_rgba8();
+    let (width, height) = image.dimensions();
+
+    return Ok(Icon::from_rgba(image.into_raw(), width, height)?);
+}
+
+/// The errors which may occur when loading a window icon
+#[derive(Error, Debug)]
+pub enum IconError {
+    /// The image could not be read or decoded
+    #[error("Unable to read image: {}", .0)]
+    Image(image::ImageError),
+    /// The image could not be used as an icon
+    #[error("Unable to create icon: {}", .0)]
+    Icon(winit::window::BadIcon),
+}
+
+impl From<image::ImageError> for IconError {
+    fn from(value: image::ImageError) -> Self {
+        return Self::Image(value);
+    }
+}
+
+impl From<winit::window::BadIcon> for IconError {
+    fn from(value: winit::window::BadIcon) -> Self {
+        return Self::Icon(value);
+    }
+}
+
 /// All input settings for the shader
 #[derive(Clone, Debug)]
-pub struct ShaderSettingsInput {}
+pub struct ShaderSettingsInput {
+    /// How much wider the columns at the edges of the map are drawn than the
+    /// average column, 0 draws all columns equally wide
+    pub column_stretch: f64,
+}
 
 /// All settings for the shader
 #[derive(Clone, Debug)]
 pub struct ShaderSettings {
     /// The layout of the grid for displaying
-    pub grid_layout: map::GridLayout,
+    pub grid_layout: Box<dyn map::GridLayout>,
 }
 
 impl ShaderSettings {
@@ -57,8 +121,17 @@ impl ShaderSettings {
     ///
     /// input: The user input settings
     ///
-    /// grid_layout: The layout of the grid for displaying
-    pub fn new(_input: ShaderSettingsInput, grid_layout: map::GridLayout) -> Self {
+    /// n_columns: The number of columns in the grid
+    pub fn new(input: ShaderSettingsInput, n_columns: usize) -> Self {
+        let grid_layout: Box<dyn map::GridLayout> = if input.column_stretch == 0.0 {
+            Box::new(map::EvenColumns { n_columns })
+        } else {
+            Box::new(map::StretchedColumns {
+                n_columns,
+                stretch: input.column_stretch,
+            })
+        };
+
         return Self { grid_layout };
     }
 }
@@ -72,6 +145,30 @@ pub struct ViewerSettingsInput {
     pub sim_rate: f64,
     /// The multiplier when speeding up or slowing down the simulation
     pub sim_rate_mod: f64,
+    /// The library to export the current map settings to as presets
+    pub presets: map::settings::preset::PresetLibrary,
+    /// The directory to save thumbnails and reports of the run to
+    pub export_directory: PathBuf,
+    /// The width of thumbnails in pixels
+    pub thumbnail_width: u32,
+    /// The format of the ancestry tree of the surviving lineages
+    pub phylogeny_format: report::TreeFormat,
+    /// The conditions for stopping the run
+    pub stop_conditions: report::StopConditions,
+    /// What happens when one of the stop conditions is met
+    pub stop_action: report::StopAction,
+    /// The number of simulation steps between each frame saved while
+    /// recording
+    pub record_interval: usize,
+    /// The number of past states of the map kept for stepping backwards, 0
+    /// disables rewinding
+    pub rewind_capacity: usize,
+    /// The number of simulation steps between each state kept for stepping
+    /// backwards
+    pub rewind_interval: usize,
+    /// If true then the overlay with controls and statistics is shown at
+    /// startup
+    pub show_overlay: bool,
 }
 
 /// All settings how to view the app
@@ -83,8 +180,34 @@ pub struct ViewerSettings {
     pub sim_rate: f64,
     /// The multiplier when speeding up or slowing down the simulation
     pub sim_rate_mod: f64,
+    /// The library to export the current map settings to as presets
+    pub presets: map::settings::preset::PresetLibrary,
+    /// The directory to save thumbnails and reports of the run to
+    pub export_directory: PathBuf,
+    /// The width of thumbnails in pixels
+    pub thumbnail_width: u32,
+    /// The format of the ancestry tree of the surviving lineages
+    pub phylogeny_format: report::TreeFormat,
+    /// The conditions for stopping the run
+    pub stop_conditions: report::StopConditions,
+    /// What happens when one of the stop conditions is met
+    pub stop_action: report::StopAction,
+    /// The number of simulation steps between each frame saved while
+    /// recording
+    pub record_interval: usize,
+    /// The number of past states of the map kept for stepping backwards, 0
+    /// disables rewinding
+    pub rewind_capacity: usize,
+    /// The number of simulation steps between each state kept for stepping
+    /// backwards
+    pub rewind_interval: usize,
+    /// If true then the overlay with controls and statistics is shown at
+    /// startup
+    pub show_overlay: bool,
     /// The home view for the camera
     pub home_view: types::View,
+    /// The views saved by the user to jump back to, one for each digit key
+    pub bookmarks: [Option<types::View>; constants::VIEW_BOOKMARK_COUNT],
 }
 
 impl ViewerSettings {
@@ -100,7 +223,18 @@ impl ViewerSettings {
             framerate: input.framerate,
             sim_rate: input.sim_rate,
             sim_rate_mod: input.sim_rate_mod,
+            presets: input.presets,
+            export_directory: input.export_directory,
+            thumbnail_width: input.thumbnail_width,
+            phylogeny_format: input.phylogeny_format,
+            stop_conditions: input.stop_conditions,
+            stop_action: input.stop_action,
+            record_interval: input.record_interval,
+            rewind_capacity: input.rewind_capacity,
+            rewind_interval: input.rewind_interval,
+            show_overlay: input.show_overlay,
             home_view,
+            bookmarks: [None; constants::VIEW_BOOKMARK_COUNT],
         };
     }
 }
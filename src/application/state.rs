@@ -1,4 +1,6 @@
-use std::time::Instant;
+use web_time::Instant;
+
+use crate::{map, types};
 
 /// All values related to the running state of the application
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -9,6 +11,33 @@ pub struct State {
     pub next_frame_time: Instant,
     /// The next time the simulation must step
     pub next_sim_time: Instant,
+    /// The next time the window title is updated with the live statistics
+    pub next_title_time: Instant,
+    /// The number of frames drawn since the window title was last updated
+    pub title_frames: usize,
+    /// The number of simulation steps since the window title was last
+    /// updated
+    pub title_steps: usize,
+    /// The position of the cursor in pixels from the top left corner of the
+    /// window, None if the cursor is outside the window
+    pub cursor: Option<types::Point>,
+    /// The position of the cursor in pixels at the last drag update while the
+    /// left mouse button is held, None if not dragging
+    pub drag: Option<types::Point>,
+    /// The total distance in pixels the cursor has moved since the left mouse
+    /// button was pressed
+    pub drag_distance: f64,
+    /// The position of the tile last clicked, None if no tile is selected
+    pub selected_tile: Option<types::Index>,
+    /// The position of the tile below the cursor, None if the cursor is not
+    /// above the map
+    pub hovered_tile: Option<types::Index>,
+    /// The bulk type placed when clicking a free tile in the edit mode
+    pub edit_bulk: map::EditBulk,
+    /// The index of the next preset to load into the shown run
+    pub preset_index: usize,
+    /// The lineage the camera keeps centered, None if the camera is free
+    pub follow_lineage: Option<usize>,
 }
 
 impl State {
@@ -18,6 +47,17 @@ impl State {
             flags: Flags::new(),
             next_frame_time: Instant::now(),
             next_sim_time: Instant::now(),
+            next_title_time: Instant::now(),
+            title_frames: 0,
+            title_steps: 0,
+            cursor: None,
+            drag: None,
+            drag_distance: 0.0,
+            selected_tile: None,
+            hovered_tile: None,
+            edit_bulk: map::EditBulk::SugarBulb,
+            preset_index: 0,
+            follow_lineage: None,
         };
     }
 }
@@ -35,6 +75,23 @@ pub struct Flags {
     pub redraw_simulation: bool,
     /// True if left shift is pressed down
     pub left_shift_active: bool,
+    /// True if left control is pressed down
+    pub left_ctrl_active: bool,
+    /// True if left alt is pressed down
+    pub left_alt_active: bool,
+    /// True if the run has ended and the report has been written
+    pub run_ended: bool,
+    /// True if the overlay with controls and statistics is shown
+    pub show_overlay: bool,
+    /// True if the durations of the recent frames and simulation steps are
+    /// shown
+    pub show_profiler: bool,
+    /// True if the light reaching the ground and the plant biomass of every
+    /// column are shown
+    pub show_columns: bool,
+    /// True if clicking a tile places or removes a plant instead of
+    /// inspecting it
+    pub edit_mode: bool,
 }
 
 impl Flags {
@@ -46,6 +103,13 @@ impl Flags {
             run_simulation: false,
             redraw_simulation: false,
             left_shift_active: false,
+            left_ctrl_active: false,
+            left_alt_active: false,
+            run_ended: false,
+            show_overlay: false,
+            show_profiler: false,
+            show_columns: false,
+            edit_mode: false,
         };
     }
 }
@@ -1,9 +1,11 @@
 use std::sync::Arc;
 
-use winit::window::Window;
+use winit::window::{Window, WindowId};
 
 use crate::{graphics, map, render};
 
+use super::Overlay;
+
 /// A window with an assosciated render state
 pub struct RenderedWindow {
     /// The window, it must be in an Arc because it is shared with the render state
@@ -12,6 +14,8 @@ pub struct RenderedWindow {
     pub render_state: render::RenderState,
     /// The graphics state used for rendering
     pub graphics_state: graphics::State,
+    /// The overlay with controls and statistics
+    pub overlay: Overlay,
 }
 
 impl RenderedWindow {
@@ -30,25 +34,66 @@ impl RenderedWindow {
         map: &map::Map<S>,
     ) -> Result<Self, render::NewRenderStateError> {
         let window = Arc::new(window);
-        let render_state = render::RenderState::new(&window).await?;
+        let render_state =
+            render::RenderState::new(&window, graphics_settings.sample_count).await?;
+
+        return Ok(Self::from_render_state(
+            window,
+            render_state,
+            graphics_settings,
+            map,
+        ));
+    }
+
+    /// Constructs a new rendered window from a render state which has already
+    /// been created for it
+    ///
+    /// # Parameters
+    ///
+    /// window: The window the render state was created for
+    ///
+    /// render_state: The render state of the window
+    ///
+    /// graphics_settings: The settings for the graphics
+    ///
+    /// map: The map to render
+    pub fn from_render_state<S: map::sun::Intensity>(
+        window: Arc<Window>,
+        render_state: render::RenderState,
+        graphics_settings: graphics::Settings,
+        map: &map::Map<S>,
+    ) -> Self {
         let graphics_state = graphics::State::new(&render_state, graphics_settings, map);
+        let overlay = Overlay::new(&window);
 
-        return Ok(Self {
+        return Self {
             window,
             render_state,
             graphics_state,
-        });
+            overlay,
+        };
     }
 }
 
+/// The events sent to the event loop from outside of it
+pub enum UserEvent {
+    /// The render state of the main window has been created in the
+    /// background, this is how the window is opened in a browser where the
+    /// gpu can not be waited for
+    RenderState(
+        Arc<Window>,
+        Result<render::RenderState, render::NewRenderStateError>,
+    ),
+}
+
 /// An optional rendered window with some utility
 pub struct OptionalRenderedWindow(Option<RenderedWindow>);
 
 impl OptionalRenderedWindow {
     /// Constructs a new window
-    /// 
+    ///
     /// # Parameters
-    /// 
+    ///
     /// window: The window to set
     pub fn new(window: RenderedWindow) -> Self {
         return Self(Some(window));
@@ -59,6 +104,16 @@ impl OptionalRenderedWindow {
         return Self(None);
     }
 
+    /// Checks if the window is open
+    pub fn is_open(&self) -> bool {
+        return self.0.is_some();
+    }
+
+    /// Retrieves the id of the window, None if it is not open
+    pub fn get_id(&self) -> Option<WindowId> {
+        return self.0.as_ref().map(|window| window.window.id());
+    }
+
     /// Retrieves a reference to the rendered window of the application
     ///
     /// # Parameters
@@ -1,5 +1,5 @@
+#[cfg(feature = "viewer")]
 use winit::{
-    dpi::PhysicalSize,
     event::{ElementState, KeyEvent},
     keyboard::{KeyCode, PhysicalKey},
 };
@@ -25,6 +25,9 @@ pub struct Camera {
     transform_aspect: types::Transform2D,
     /// The transform to apply to the current transform every frame
     transform_update: types::Transform2D,
+    /// The transition to another view currently running, None if the camera
+    /// is not animating
+    animation: Option<Animation>,
 }
 
 impl Camera {
@@ -43,11 +46,9 @@ impl Camera {
             active: false,
             settings,
             transform,
-            transform_aspect: Self::size_to_aspect(&PhysicalSize {
-                width: 1,
-                height: 1,
-            }),
+            transform_aspect: Self::size_to_aspect(1, 1),
             transform_update: types::Transform2D::identity(),
+            animation: None,
         }
     }
 
@@ -72,6 +73,7 @@ impl Camera {
     /// # Parameters
     ///
     /// event: The key event to handle
+    #[cfg(feature = "viewer")]
     pub fn apply_key(&mut self, event: &KeyEvent) -> bool {
         // Stop if it is a repeat press
         if event.repeat {
@@ -83,6 +85,11 @@ impl Camera {
             ElementState::Released => false,
         };
 
+        // Moving the camera by hand takes over from any animation
+        if active {
+            self.animation = None;
+        }
+
         match event.physical_key {
             PhysicalKey::Unidentified(_) => return false,
             PhysicalKey::Code(code) => match code {
@@ -103,6 +110,103 @@ impl Camera {
         return true;
     }
 
+    /// Moves the view such that the world moves by the given offset on the
+    /// screen, used for dragging the view
+    ///
+    /// # Parameters
+    ///
+    /// offset: The offset in world coordinates
+    pub fn apply_drag(&mut self, offset: &types::Point) {
+        self.animation = None;
+        self.transform = self.transform * types::Transform2D::translate(offset);
+
+        // Correct the position
+        self.enforce_limits();
+    }
+
+    /// Zooms in or out keeping the given world position fixed on the screen,
+    /// returns true if the transform changed
+    ///
+    /// # Parameters
+    ///
+    /// delta: The number of scroll lines, positive values zoom in
+    ///
+    /// cursor_world_pos: The world position to zoom towards
+    pub fn apply_scroll(&mut self, delta: f64, cursor_world_pos: &types::Point) -> bool {
+        let steps = delta
+            * if self.boost {
+                self.settings.boost_factor
+            } else {
+                1.0
+            };
+
+        return self.apply_zoom(self.settings.speed_scroll.powf(steps), cursor_world_pos);
+    }
+
+    /// Zooms by a factor keeping the given world position fixed on the
+    /// screen, used for pinch gestures, returns true if the transform changed
+    ///
+    /// # Parameters
+    ///
+    /// factor: The factor to scale the zoom level by, values above 1 zoom in
+    ///
+    /// cursor_world_pos: The world position to zoom towards
+    pub fn apply_zoom(&mut self, factor: f64, cursor_world_pos: &types::Point) -> bool {
+        self.animation = None;
+
+        // Limit the zoom factor such that the zoom limits are held
+        let zoom_level = self.transform.get_scaling_x();
+        if !zoom_level.is_normal() || !factor.is_normal() {
+            return false;
+        }
+        let zoom_new =
+            (zoom_level * factor).clamp(self.settings.zoom_limits.0, self.settings.zoom_limits.1);
+        let zoom_factor = zoom_new / zoom_level;
+        if zoom_factor == 1.0 || !zoom_factor.is_normal() {
+            return false;
+        }
+
+        // Scale around the position of the cursor
+        let pivot = self.transform * cursor_world_pos;
+        self.transform = types::Transform2D::scale(&types::Point::new(zoom_factor, zoom_factor))
+            .transform_at(&pivot)
+            * self.transform;
+
+        // Correct the position
+        self.enforce_limits();
+
+        return true;
+    }
+
+    /// Moves the view part of the way towards having a point in the center of
+    /// the screen, used to smoothly follow a moving target once per frame.
+    /// Returns true if the transform changed
+    ///
+    /// # Parameters
+    ///
+    /// target: The point to center in world coordinates
+    ///
+    /// rate: How fast the view closes in on the target, the remaining
+    /// distance shrinks by a factor of e every 1 / rate seconds
+    pub fn follow(&mut self, target: &types::Point, rate: f64) -> bool {
+        let current = self.transform.inv() * types::Point::new(0.0, 0.0);
+        let mut offset = current - target;
+
+        // Take the shortest way around the map
+        if self.settings.map_width < f64::MAX {
+            let width = self.settings.map_width;
+            offset.x = (offset.x + 0.5 * width).rem_euclid(width) - 0.5 * width;
+        }
+        if offset.norm() < 1e-6 {
+            return false;
+        }
+
+        let fraction = 1.0 - (-rate / self.settings.framerate).exp();
+        self.apply_drag(&(offset * fraction));
+
+        return true;
+    }
+
     /// Reset all of the input such that all of it is turned off
     pub fn reset_keys(&mut self) {
         self.active_move.iter_mut().for_each(|val| *val = false);
@@ -115,9 +219,62 @@ impl Camera {
     ///
     /// # Parameters
     ///
-    /// size: THe new size of the window
-    pub fn resize(&mut self, size: &winit::dpi::PhysicalSize<u32>) {
-        self.transform_aspect = Self::size_to_aspect(size);
+    /// width: The new width of the window in pixels
+    ///
+    /// height: The new height of the window in pixels
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.transform_aspect = Self::size_to_aspect(width, height);
+    }
+
+    /// Gets the part of the world currently shown on the screen
+    pub fn get_view(&self) -> types::View {
+        let zoom = self.transform.get_scaling_x();
+        let ratio = self.transform_aspect.get_scaling_y();
+
+        return types::View::new(
+            self.transform.inv() * types::Point::new(0.0, 0.0),
+            types::Size::new(2.0 / zoom, 2.0 / (zoom * ratio)),
+        );
+    }
+
+    /// Starts a smooth transition from the current view to a new view, the
+    /// new view is fitted to the screen keeping the aspect ratio of the
+    /// screen
+    ///
+    /// # Parameters
+    ///
+    /// view: The view to end at
+    ///
+    /// duration: The length of the transition in seconds, 0 jumps there
+    /// right away
+    pub fn animate_to(&mut self, view: &types::View, duration: f64) {
+        let zoom_end = self.get_view_zoom(view);
+        if !zoom_end.is_normal() {
+            return;
+        }
+        if duration <= 0.0 {
+            self.set_transform(Self::center_zoom_to_transform(view.get_center(), zoom_end));
+            return;
+        }
+
+        // Move the shortest way around the map
+        let center_start = self.transform.inv() * types::Point::new(0.0, 0.0);
+        let mut center_end = *view.get_center();
+        if self.settings.map_width < f64::MAX {
+            let width = self.settings.map_width;
+            center_end.x = center_start.x
+                + (center_end.x - center_start.x + 0.5 * width).rem_euclid(width)
+                - 0.5 * width;
+        }
+
+        self.animation = Some(Animation {
+            center_start,
+            zoom_start: self.transform.get_scaling_x(),
+            center_end,
+            zoom_end,
+            elapsed: 0.0,
+            duration,
+        });
     }
 
     /// Retrieves the transform
@@ -129,12 +286,13 @@ impl Camera {
         return &self.transform;
     }
 
-    /// Sets a new transform
+    /// Sets a new transform, stopping any running animation
     ///
     /// # Parameters
     ///
     /// transform: The new transform to set
     pub fn set_transform(&mut self, transform: types::Transform2D) {
+        self.animation = None;
         self.transform = transform;
 
         // Correct zoom level
@@ -145,6 +303,21 @@ impl Camera {
     ///
     /// Returns true if the transform has updated
     pub fn update_transform(&mut self) -> bool {
+        if let Some(animation) = &mut self.animation {
+            animation.elapsed += 1.0 / self.settings.framerate;
+            let progress = (animation.elapsed / animation.duration).min(1.0);
+            let (center, zoom) = animation.get(progress);
+            if progress >= 1.0 {
+                self.animation = None;
+            }
+            self.transform = Self::center_zoom_to_transform(&center, zoom);
+
+            // Correct zoom level
+            self.enforce_limits();
+
+            return true;
+        }
+
         if !self.active {
             return false;
         }
@@ -226,28 +399,98 @@ impl Camera {
         }
 
         // Wrap position
-        self.transform.center.x %= self.settings.map_width * zoom_clamped;
-        if self.transform.center.x > 0.0 {
-            self.transform.center.x -= self.settings.map_width * zoom_clamped;
+        if self.settings.map_width < f64::MAX {
+            self.transform.center.x %= self.settings.map_width * zoom_clamped;
+            if self.transform.center.x > 0.0 {
+                self.transform.center.x -= self.settings.map_width * zoom_clamped;
+            }
         }
     }
 
+    /// Gets the zoom level at which a view exactly fits on the screen
+    ///
+    /// # Parameters
+    ///
+    /// view: The view to fit
+    fn get_view_zoom(&self, view: &types::View) -> f64 {
+        let ratio = self.transform_aspect.get_scaling_y();
+        let scale_x = if view.get_size().get_w() == 0.0 {
+            f64::INFINITY
+        } else {
+            1.0 / view.get_size().get_w()
+        };
+        let scale_y = if view.get_size().get_h() == 0.0 || ratio == 0.0 {
+            f64::INFINITY
+        } else {
+            1.0 / (ratio * view.get_size().get_h())
+        };
+
+        return 2.0 * scale_x.min(scale_y);
+    }
+
+    /// Constructs the transform showing a point in the center of the screen
+    ///
+    /// # Parameters
+    ///
+    /// center: The point in world coordinates
+    ///
+    /// zoom: The zoom level
+    fn center_zoom_to_transform(center: &types::Point, zoom: f64) -> types::Transform2D {
+        return types::Transform2D::scale(&types::Point::new(zoom, zoom))
+            * types::Transform2D::translate(&(-center));
+    }
+
     /// Converts a size to an aspect transform
     ///
     /// # Parameters
     ///
-    /// size: The size of the window
-    fn size_to_aspect(size: &PhysicalSize<u32>) -> types::Transform2D {
-        let ratio = if size.height == 0 {
+    /// width: The width of the window in pixels
+    ///
+    /// height: The height of the window in pixels
+    fn size_to_aspect(width: u32, height: u32) -> types::Transform2D {
+        let ratio = if height == 0 {
             1.0
         } else {
-            (size.width as f64) / (size.height as f64)
+            (width as f64) / (height as f64)
         };
 
         return types::Transform2D::scale(&types::Point::new(1.0, ratio));
     }
 }
 
+/// A smooth transition of the camera from one view to another
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Animation {
+    /// The center of the screen in world coordinates at the start
+    center_start: types::Point,
+    /// The zoom level at the start
+    zoom_start: f64,
+    /// The center of the screen in world coordinates at the end
+    center_end: types::Point,
+    /// The zoom level at the end
+    zoom_end: f64,
+    /// The time since the start in seconds
+    elapsed: f64,
+    /// The length of the transition in seconds
+    duration: f64,
+}
+
+impl Animation {
+    /// Gets the center and zoom level at some point of the transition, it
+    /// eases in and out and zooms at a constant rate
+    ///
+    /// # Parameters
+    ///
+    /// progress: How far the transition has come from 0 to 1
+    fn get(&self, progress: f64) -> (types::Point, f64) {
+        let eased = progress * progress * (3.0 - 2.0 * progress);
+        let center = self.center_start + (self.center_end - self.center_start) * eased;
+        let zoom = self.zoom_start * (self.zoom_end / self.zoom_start).powf(eased);
+
+        return (center, zoom);
+    }
+}
+
 /// All settings for a camera
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct CameraSettings {
@@ -255,13 +498,16 @@ pub struct CameraSettings {
     pub speed_move: f64,
     /// The speed of zooming
     pub speed_zoom: f64,
+    /// The zoom factor for each line scrolled
+    pub speed_scroll: f64,
     /// The speed factor when boosting
     pub boost_factor: f64,
     /// The framerate of the program, this is how many times a second the transform should be updated
     pub framerate: f64,
     /// The minimum and maximum allowed zoom
     pub zoom_limits: (f64, f64),
-    /// The width of the map used for wrapping
+    /// The width of the map used for wrapping, f64::MAX if the map does not
+    /// wrap in x
     pub map_width: f64,
 }
 
@@ -271,6 +517,7 @@ impl CameraSettings {
         return Self {
             speed_move: 4.0,
             speed_zoom: 1.2,
+            speed_scroll: 1.1,
             boost_factor: 2.0,
             framerate: 60.0,
             zoom_limits: (0.0, f64::INFINITY),
@@ -298,6 +545,16 @@ impl CameraSettings {
         return self;
     }
 
+    /// Changes the scroll zoom factor and returns the updated object
+    ///
+    /// # Parameters
+    ///
+    /// speed: The new zoom factor for each line scrolled
+    pub fn with_speed_scroll(mut self, speed: f64) -> Self {
+        self.speed_scroll = speed;
+        return self;
+    }
+
     /// Changes the boost factor and returns the updated object
     ///
     /// # Parameters
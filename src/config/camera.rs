@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+use crate::constants;
+
+/// All settings for moving the camera
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The speed of movement with the keyboard
+    pub speed_move: f64,
+    /// The speed of zooming with the keyboard
+    pub speed_zoom: f64,
+    /// The zoom factor for each line scrolled
+    pub speed_scroll: f64,
+    /// The speed factor when boosting
+    pub boost_factor: f64,
+    /// The minimum and maximum allowed zoom
+    pub zoom_limits: (f64, f64),
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            speed_move: constants::CAMERA_MOVE_SPEED,
+            speed_zoom: constants::CAMERA_ZOOM_SPEED,
+            speed_scroll: constants::CAMERA_SCROLL_SPEED,
+            boost_factor: constants::CAMERA_BOOST_FACTOR,
+            zoom_limits: constants::CAMERA_ZOOM_LIMITS,
+        };
+    }
+}
@@ -0,0 +1,209 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants, map,
+    types::{self, ColorMap},
+};
+
+/// All colors and color maps used for rendering
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The color to clear the screen with
+    pub background: types::Color,
+    /// The initial display mode for the background of the tiles
+    pub mode_background: map::DataModeBackground,
+    /// The initial display mode for the plants
+    pub mode_plant: map::DataModePlant,
+    /// The initial scaling of the values of all continuous color maps
+    pub scaling: types::ColorScaling,
+    /// The exponent used by the exponent scaling
+    pub scaling_exponent: f64,
+    /// The color map for the sun and the light of the tiles
+    pub light: types::ColorMapLinearRGBA,
+    /// The color map for the transparency of the tiles
+    pub transparency: types::ColorMapLinearRGBA,
+    /// The color map for the water level of the tiles
+    pub water: types::ColorMapLinearRGBA,
+    /// The color map for the nutrients of the ground
+    pub nutrients: types::ColorMapLinearRGBA,
+    /// The color map for the simulation cost of the chunks
+    pub chunk_cost: types::ColorMapGradient,
+    /// The color map for the cloud coverage
+    pub clouds: types::ColorMapLinearRGBA,
+    /// The color map for the temperature from the coldest to the warmest tile
+    pub temperature: types::ColorMapLinearRGBA,
+    /// The color map for the detritus left behind by dead plants
+    pub detritus: types::ColorMapLinearRGBA,
+    /// The color map for the fertility from barren to fertile tiles
+    pub fertility: types::ColorMapLinearRGBA,
+    /// The color map for the fraction of the light blocked by plants of other
+    /// lineages
+    pub foreign_shade: types::ColorMapLinearRGBA,
+    /// The color map for the diffuse light scattered by the sky
+    pub diffuse: types::ColorMapLinearRGBA,
+    /// The color map for the terrain from open tiles to barren terrain
+    pub terrain: types::ColorMapLinearRGBA,
+    /// The colors of the plants
+    pub plant: PlantColors,
+    /// The color of the outlines of the tiles
+    pub border: types::Color,
+    /// The color map for the energy transfer arrows from the smallest to the
+    /// largest transfer
+    pub transfer: types::ColorMapLinearRGBA,
+    /// The tint of the tile selected for inspection
+    pub selected: types::Color,
+    /// The tint of the tile below the cursor
+    pub hovered: types::Color,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            background: constants::COLOR_BACKGROUND,
+            mode_background: constants::COLOR_MODE_BACKGROUND,
+            mode_plant: constants::COLOR_MODE_PLANT,
+            scaling: constants::COLOR_SCALING,
+            scaling_exponent: constants::COLOR_SCALING_EXPONENT,
+            light: constants::COLOR_MAP_LIGHT,
+            transparency: constants::COLOR_MAP_TRANSPARENCY,
+            water: constants::COLOR_MAP_WATER,
+            nutrients: constants::COLOR_MAP_NUTRIENTS,
+            chunk_cost: types::ColorMapGradient::new(constants::COLOR_MAP_CHUNK_COST.to_vec()),
+            clouds: constants::COLOR_MAP_CLOUDS,
+            temperature: constants::COLOR_MAP_TEMPERATURE,
+            detritus: constants::COLOR_MAP_DETRITUS,
+            fertility: constants::COLOR_MAP_FERTILITY,
+            foreign_shade: constants::COLOR_MAP_FOREIGN_SHADE,
+            diffuse: constants::COLOR_MAP_DIFFUSE,
+            terrain: constants::COLOR_MAP_TERRAIN,
+            plant: PlantColors::new(),
+            border: constants::COLOR_BORDER,
+            transfer: constants::COLOR_MAP_TRANSFER,
+            selected: constants::COLOR_SELECTED,
+            hovered: constants::COLOR_HOVERED,
+        };
+    }
+
+    /// Constructs the color map for the outlines of the tiles, they all use
+    /// the first color
+    pub fn get_border_color_map(&self) -> types::ColorMapDiscrete {
+        return types::ColorMapDiscrete::new(vec![self.border], self.border);
+    }
+
+    /// Constructs the discrete color map for the highlighted tiles, the order
+    /// follows the ids of the highlight types
+    pub fn get_highlight_color_map(&self) -> types::ColorMapDiscrete {
+        return types::ColorMapDiscrete::new(vec![self.selected, self.hovered], self.hovered);
+    }
+}
+
+/// The color of every bulk type of a plant
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "PlantColors::new")]
+pub struct PlantColors {
+    /// The color of tiles without a living plant
+    pub none: types::Color,
+    /// The color of a log
+    pub log: types::Color,
+    /// The color of a sugar bulb
+    pub sugar_bulb: types::Color,
+    /// The color of a leaf
+    pub leaf: types::Color,
+    /// The color of a seed
+    pub seed: types::Color,
+    /// The color of a ripe seed
+    pub ripe_seed: types::Color,
+    /// The color of a root
+    pub root: types::Color,
+    /// The color of a flower
+    pub flower: types::Color,
+    /// The color of a thorn
+    pub thorn: types::Color,
+    /// The color map for the age of the plant tiles and plants, from the
+    /// youngest to the oldest
+    pub age: types::ColorMapLinearRGBA,
+    /// The color of a log bridge
+    pub bridge_log: types::Color,
+    /// The color of a branch bridge
+    pub bridge_branch: types::Color,
+}
+
+impl PlantColors {
+    /// Constructs the default plant colors
+    pub fn new() -> Self {
+        return Self {
+            none: constants::COLOR_PLANT_NONE,
+            log: constants::COLOR_PLANT_LOG,
+            sugar_bulb: constants::COLOR_PLANT_SUGAR_BULB,
+            leaf: constants::COLOR_PLANT_LEAF,
+            seed: constants::COLOR_PLANT_SEED,
+            ripe_seed: constants::COLOR_PLANT_RIPE_SEED,
+            root: constants::COLOR_PLANT_ROOT,
+            flower: constants::COLOR_PLANT_FLOWER,
+            thorn: constants::COLOR_PLANT_THORN,
+            age: constants::COLOR_MAP_PLANT_AGE,
+            bridge_log: constants::COLOR_BRIDGE_LOG,
+            bridge_branch: constants::COLOR_BRIDGE_BRANCH,
+        };
+    }
+
+    /// Constructs the discrete color map for the plant layer, the order
+    /// follows the ids of the bulk types
+    pub fn get_color_map(&self) -> types::ColorMapDiscrete {
+        return types::ColorMapDiscrete::new(
+            vec![
+                self.none,
+                self.log,
+                self.sugar_bulb,
+                self.leaf,
+                self.seed,
+                self.ripe_seed,
+                self.root,
+                self.flower,
+                self.thorn,
+            ],
+            self.none,
+        );
+    }
+
+    /// Constructs the discrete color map for the bridges, the order follows
+    /// the ids of the bridge types
+    pub fn get_bridge_color_map(&self) -> types::ColorMapDiscrete {
+        return types::ColorMapDiscrete::new(
+            vec![self.none, self.bridge_log, self.bridge_branch],
+            self.none,
+        );
+    }
+
+    /// Constructs the discrete color map for the age modes of the plant
+    /// layer, index 0 is used for tiles without a living plant and the
+    /// remaining indices follow the age color map
+    pub fn get_age_color_map(&self) -> types::ColorMapDiscrete {
+        let age = self.age.get_colors();
+
+        return types::ColorMapDiscrete::new(
+            std::iter::once(self.none)
+                .chain((1..256).map(|index| age[(index - 1) * 255 / 254]))
+                .collect(),
+            self.none,
+        );
+    }
+
+    /// Constructs the discrete color map for the species mode of the plant
+    /// layer, index 0 is used for tiles without a living plant and the
+    /// remaining indices get hues spread by the golden angle such that
+    /// species with close ids get distinct colors
+    pub fn get_species_color_map(&self) -> types::ColorMapDiscrete {
+        return types::ColorMapDiscrete::new(
+            std::iter::once(self.none)
+                .chain((1..256).map(|index| {
+                    let hue = (index as f64 * constants::COLOR_SPECIES_HUE_STEP).fract();
+                    types::Color::from_hsv(hue, 0.7, 0.9)
+                }))
+                .collect(),
+            self.none,
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{constants, map, types};
+
+/// All settings for constructing the map
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The number of tiles in each row
+    pub width: usize,
+    /// The number of rows of tiles
+    pub height: usize,
+    /// The name of a preset to load the simulation settings from, if given
+    /// then the simulation settings below are ignored
+    pub preset: Option<String>,
+    /// The settings for the sun
+    pub sun: SunSettings,
+    /// The settings for the simulation including all energy costs, any
+    /// value left out uses the default of the simulation
+    pub settings: map::settings::Settings,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        let transparency =
+            map::settings::transparency::Settings::new().with_base(constants::MAP_TRANSPARENCY);
+        let update = map::settings::update::Settings::new().with_order(constants::MAP_UPDATE_ORDER);
+
+        return Self {
+            width: constants::MAP_SIZE.w,
+            height: constants::MAP_SIZE.h,
+            preset: constants::MAP_PRESET.map(|name| name.to_string()),
+            sun: SunSettings::new(),
+            settings: map::settings::Settings::new()
+                .with_transparency(transparency)
+                .with_update(update)
+                .with_seed(constants::MAP_SEED),
+        };
+    }
+
+    /// Retrieves the size of the map
+    pub fn get_size(&self) -> types::ISize {
+        return types::ISize {
+            w: self.width,
+            h: self.height,
+        };
+    }
+}
+
+/// All settings for the sun
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "SunSettings::new")]
+pub struct SunSettings {
+    /// The tilt of the planet axis in degrees
+    pub tilt: f64,
+    /// The latitude of the map in degrees, of the leftmost column if the
+    /// latitude changes across the map
+    pub latitude: f64,
+    /// The latitude of the rightmost column in degrees such that the
+    /// latitude changes across the map, None keeps it the same everywhere
+    pub latitude_end: Option<f64>,
+    /// The number of steps in a year
+    pub year: f64,
+    /// The number of steps in a day
+    pub day: f64,
+    /// The intensity of the sun
+    pub intensity: f64,
+    /// The second sun of a binary star if any, it lights the map from the
+    /// same sky with its own cycles
+    pub companion: Option<CompanionSettings>,
+}
+
+impl SunSettings {
+    /// Constructs the default sun settings
+    pub fn new() -> Self {
+        return Self {
+            tilt: constants::MAP_SUN_TILT_DEGREES,
+            latitude: constants::MAP_SUN_LATITUDE_DEGREES,
+            latitude_end: constants::MAP_SUN_LATITUDE_END_DEGREES,
+            year: constants::MAP_SUN_YEAR,
+            day: constants::MAP_SUN_DAY,
+            intensity: constants::MAP_SUN_INTENSITY,
+            companion: None,
+        };
+    }
+
+    /// Constructs the sun intensity with both a yearly and daily cycle,
+    /// leaving out the companion
+    pub fn get_intensity(&self) -> map::presets::IntensityPlanet {
+        return self.get_planet(self.year, self.day, self.intensity);
+    }
+
+    /// Constructs the intensity of the sun together with its companion if
+    /// it has one
+    pub fn get_world_intensity(&self) -> map::presets::WorldIntensity {
+        return match &self.companion {
+            Some(companion) => {
+                map::presets::WorldIntensity::TwinSuns(map::sun::IntensityBinaryStar::new(
+                    self.get_intensity(),
+                    self.get_planet(companion.year, companion.day, companion.intensity),
+                ))
+            }
+            None => map::presets::WorldIntensity::Planet(self.get_intensity()),
+        };
+    }
+
+    /// Constructs the intensity of a sun seen from the map with both a
+    /// yearly and daily cycle
+    ///
+    /// # Parameters
+    ///
+    /// year: The number of steps in a year of the sun
+    ///
+    /// day: The number of steps in a day of the sun
+    ///
+    /// intensity: The intensity of the sun
+    fn get_planet(&self, year: f64, day: f64, intensity: f64) -> map::presets::IntensityPlanet {
+        let sun_year = map::sun::IntensityYearPlanet::new(
+            self.tilt * constants::MATH_PI / 180.0,
+            self.latitude * constants::MATH_PI / 180.0,
+            year,
+            intensity,
+        );
+        let sun_year = match self.latitude_end {
+            Some(latitude_end) => {
+                sun_year.with_latitude_end(latitude_end * constants::MATH_PI / 180.0)
+            }
+            None => sun_year,
+        };
+        let sun_day = map::sun::IntensityDayPlanet::new(day);
+
+        return map::sun::IntensityYearDay::new(sun_year, sun_day);
+    }
+}
+
+/// All settings for the companion of a binary star
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "CompanionSettings::new")]
+pub struct CompanionSettings {
+    /// The number of steps in a year of the companion
+    pub year: f64,
+    /// The number of steps in a day of the companion
+    pub day: f64,
+    /// The intensity of the companion
+    pub intensity: f64,
+}
+
+impl CompanionSettings {
+    /// Constructs the default companion settings
+    pub fn new() -> Self {
+        return Self {
+            year: constants::MAP_COMPANION_YEAR,
+            day: constants::MAP_COMPANION_DAY,
+            intensity: constants::MAP_COMPANION_INTENSITY,
+        };
+    }
+}
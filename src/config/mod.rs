@@ -0,0 +1,441 @@
+use std::{fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+pub mod camera;
+pub mod colors;
+pub mod map;
+pub mod viewer;
+
+/// All settings of the application which can be tuned without recompiling,
+/// loaded from a TOML file where any settings left out keep their default
+/// value
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Config::new")]
+pub struct Config {
+    /// The settings for the window and for running the viewer
+    pub viewer: viewer::Settings,
+    /// The settings for moving the camera
+    pub camera: camera::Settings,
+    /// The colors used for rendering
+    pub colors: colors::Settings,
+    /// The settings for constructing the map
+    pub map: map::Settings,
+}
+
+impl Config {
+    /// Constructs a new default config
+    pub fn new() -> Self {
+        return Self {
+            viewer: viewer::Settings::new(),
+            camera: camera::Settings::new(),
+            colors: colors::Settings::new(),
+            map: map::Settings::new(),
+        };
+    }
+
+    /// Parses a config from a TOML text and validates it
+    ///
+    /// # Parameters
+    ///
+    /// text: The TOML text
+    ///
+    /// # Errors
+    ///
+    /// See ConfigError for a description of the different errors which may occur
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        let config: Self = toml::from_str(text)?;
+        config.validate()?;
+
+        return Ok(config);
+    }
+
+    /// Loads a config from a TOML file and validates it
+    ///
+    /// # Parameters
+    ///
+    /// path: The path of the file
+    ///
+    /// # Errors
+    ///
+    /// See ConfigError for a description of the different errors which may occur
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let text = fs::read_to_string(path)?;
+
+        return Self::parse(&text);
+    }
+
+    /// Writes the config as TOML
+    ///
+    /// # Errors
+    ///
+    /// Returns the serialization error if the config could not be converted
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        return toml::to_string_pretty(self);
+    }
+
+    /// Makes sure all values are within their allowed ranges
+    ///
+    /// # Errors
+    ///
+    /// Returns ConfigError::Invalid describing the first invalid value
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        // Viewer
+        if !is_larger(self.viewer.framerate, 0.0) {
+            return Err(ConfigError::Invalid(format!(
+                "viewer.framerate must be larger than 0 but received {}",
+                self.viewer.framerate
+            )));
+        }
+        if !is_larger(self.viewer.sim_rate, 0.0) {
+            return Err(ConfigError::Invalid(format!(
+                "viewer.sim_rate must be larger than 0 but received {}",
+                self.viewer.sim_rate
+            )));
+        }
+        if !is_larger(self.viewer.sim_rate_modifier, 1.0) {
+            return Err(ConfigError::Invalid(format!(
+                "viewer.sim_rate_modifier must be larger than 1 but received {}",
+                self.viewer.sim_rate_modifier
+            )));
+        }
+        if self.viewer.window_width == 0 || self.viewer.window_height == 0 {
+            return Err(ConfigError::Invalid(format!(
+                "The window size must be larger than 0 but received {}x{}",
+                self.viewer.window_width, self.viewer.window_height
+            )));
+        }
+        if self.viewer.thumbnail_width == 0 {
+            return Err(ConfigError::Invalid(String::from(
+                "viewer.thumbnail_width must be larger than 0",
+            )));
+        }
+        if self.viewer.stats_flush_interval == 0 {
+            return Err(ConfigError::Invalid(String::from(
+                "viewer.stats_flush_interval must be larger than 0",
+            )));
+        }
+        if self.viewer.record_interval == 0 {
+            return Err(ConfigError::Invalid(String::from(
+                "viewer.record_interval must be larger than 0",
+            )));
+        }
+        if self.viewer.rewind_interval == 0 {
+            return Err(ConfigError::Invalid(String::from(
+                "viewer.rewind_interval must be larger than 0",
+            )));
+        }
+        if !(self.viewer.column_stretch > -1.0 && self.viewer.column_stretch < 1.0) {
+            return Err(ConfigError::Invalid(format!(
+                "viewer.column_stretch must be between -1 and 1 but received {}",
+                self.viewer.column_stretch
+            )));
+        }
+
+        // Camera
+        if !is_larger(self.camera.speed_move, 0.0)
+            || !is_larger(self.camera.speed_zoom, 0.0)
+            || !is_larger(self.camera.boost_factor, 0.0)
+        {
+            return Err(ConfigError::Invalid(String::from(
+                "The camera speeds and boost factor must be larger than 0",
+            )));
+        }
+        if !is_larger(self.camera.speed_scroll, 1.0) {
+            return Err(ConfigError::Invalid(format!(
+                "camera.speed_scroll must be larger than 1 but received {}",
+                self.camera.speed_scroll
+            )));
+        }
+        let (zoom_min, zoom_max) = self.camera.zoom_limits;
+        if !(is_larger(zoom_min, 0.0) && zoom_min <= zoom_max) {
+            return Err(ConfigError::Invalid(format!(
+                "camera.zoom_limits must be positive and ordered but received ({}, {})",
+                zoom_min, zoom_max
+            )));
+        }
+
+        // Map
+        if self.map.width == 0 || self.map.height == 0 {
+            return Err(ConfigError::Invalid(format!(
+                "The map size must be larger than 0 but received {}x{}",
+                self.map.width, self.map.height
+            )));
+        }
+        if self.map.settings.wrap.vertical() && !self.map.height.is_multiple_of(2) {
+            return Err(ConfigError::Invalid(format!(
+                "The map height must be even when map.settings.wrap is Both but received {}",
+                self.map.height
+            )));
+        }
+        if !is_larger(self.map.sun.year, 0.0) || !is_larger(self.map.sun.day, 0.0) {
+            return Err(ConfigError::Invalid(String::from(
+                "The length of the sun year and day must be larger than 0",
+            )));
+        }
+        if self.map.sun.intensity.is_nan() || self.map.sun.intensity < 0.0 {
+            return Err(ConfigError::Invalid(format!(
+                "map.sun.intensity must not be negative but received {}",
+                self.map.sun.intensity
+            )));
+        }
+        if let Some(companion) = &self.map.sun.companion {
+            if !is_larger(companion.year, 0.0) || !is_larger(companion.day, 0.0) {
+                return Err(ConfigError::Invalid(String::from(
+                    "The length of the year and day of map.sun.companion must be larger than 0",
+                )));
+            }
+            if companion.intensity.is_nan() || companion.intensity < 0.0 {
+                return Err(ConfigError::Invalid(format!(
+                    "map.sun.companion.intensity must not be negative but received {}",
+                    companion.intensity
+                )));
+            }
+        }
+        if !(-90.0..=90.0).contains(&self.map.sun.latitude) {
+            return Err(ConfigError::Invalid(format!(
+                "map.sun.latitude must be between -90 and 90 degrees but received {}",
+                self.map.sun.latitude
+            )));
+        }
+        if let Some(latitude_end) = self.map.sun.latitude_end {
+            if !(-90.0..=90.0).contains(&latitude_end) {
+                return Err(ConfigError::Invalid(format!(
+                    "map.sun.latitude_end must be between -90 and 90 degrees but received {}",
+                    latitude_end
+                )));
+            }
+        }
+
+        // Weather
+        let weather = &self.map.settings.weather;
+        if !(0.0..=1.0).contains(&weather.density) || !(0.0..=1.0).contains(&weather.opacity) {
+            return Err(ConfigError::Invalid(format!(
+                "map.settings.weather.density and opacity must be between 0 and 1 but received {} and {}",
+                weather.density, weather.opacity
+            )));
+        }
+        if !is_larger(weather.scale, 0.0) || !weather.speed.is_finite() {
+            return Err(ConfigError::Invalid(format!(
+                "map.settings.weather.scale must be larger than 0 and speed must be finite but received {} and {}",
+                weather.scale, weather.speed
+            )));
+        }
+        if !weather.wind.is_finite()
+            || !weather.gust.is_finite()
+            || !is_larger(weather.gust_period, 0.0)
+        {
+            return Err(ConfigError::Invalid(format!(
+                "map.settings.weather.wind and gust must be finite and gust_period must be larger than 0 but received {}, {} and {}",
+                weather.wind, weather.gust, weather.gust_period
+            )));
+        }
+
+        // Transparency
+        let albedo = self.map.settings.transparency.albedo;
+        if !(0.0..=1.0).contains(&albedo) {
+            return Err(ConfigError::Invalid(format!(
+                "map.settings.transparency.albedo must be between 0 and 1 but received {}",
+                albedo
+            )));
+        }
+
+        // Sky
+        let sky = &self.map.settings.sky;
+        if !(0.0..=1.0).contains(&sky.intensity) || sky.spread.is_nan() || sky.spread < 0.0 {
+            return Err(ConfigError::Invalid(format!(
+                "map.settings.sky.intensity must be between 0 and 1 and spread must not be negative but received {} and {}",
+                sky.intensity, sky.spread
+            )));
+        }
+
+        // Temperature
+        let temperature = &self.map.settings.temperature;
+        if !(0.0..=1.0).contains(&temperature.relaxation)
+            || !(0.0..=1.0).contains(&temperature.diffusion)
+        {
+            return Err(ConfigError::Invalid(format!(
+                "map.settings.temperature.relaxation and diffusion must be between 0 and 1 but received {} and {}",
+                temperature.relaxation, temperature.diffusion
+            )));
+        }
+        if !is_larger(temperature.tolerance, 0.0)
+            || temperature.sensitivity.is_nan()
+            || temperature.sensitivity < 0.0
+        {
+            return Err(ConfigError::Invalid(format!(
+                "map.settings.temperature.tolerance must be larger than 0 and sensitivity must not be negative but received {} and {}",
+                temperature.tolerance, temperature.sensitivity
+            )));
+        }
+
+        // Genesis
+        let genesis = &self.map.settings.genesis;
+        if !(0.0..=1.0).contains(&genesis.probability) {
+            return Err(ConfigError::Invalid(format!(
+                "map.settings.genesis.probability must be between 0 and 1 but received {}",
+                genesis.probability
+            )));
+        }
+        if genesis.program_length == 0 || !is_larger(genesis.energy_capacity, 0.0) {
+            return Err(ConfigError::Invalid(format!(
+                "map.settings.genesis.program_length and energy_capacity must be larger than 0 but received {} and {}",
+                genesis.program_length, genesis.energy_capacity
+            )));
+        }
+
+        // Pollen
+        let pollen = &self.map.settings.pollen;
+        if !(0.0..=1.0).contains(&pollen.emission) {
+            return Err(ConfigError::Invalid(format!(
+                "map.settings.pollen.emission must be between 0 and 1 but received {}",
+                pollen.emission
+            )));
+        }
+        if !pollen.wind.is_finite() || !pollen.turbulence.is_finite() || pollen.turbulence < 0.0 {
+            return Err(ConfigError::Invalid(format!(
+                "map.settings.pollen.wind must be finite and turbulence must not be negative but received {} and {}",
+                pollen.wind, pollen.turbulence
+            )));
+        }
+
+        // Herbivory
+        let herbivory = &self.map.settings.herbivory;
+        if !(0.0..=1.0).contains(&herbivory.pressure) || !(0.0..=1.0).contains(&herbivory.damage) {
+            return Err(ConfigError::Invalid(format!(
+                "map.settings.herbivory.pressure and damage must be between 0 and 1 but received {} and {}",
+                herbivory.pressure, herbivory.damage
+            )));
+        }
+
+        // Detritus
+        let detritus = &self.map.settings.detritus;
+        if !(0.0..=1.0).contains(&detritus.opacity) || !(0.0..=1.0).contains(&detritus.decay) {
+            return Err(ConfigError::Invalid(format!(
+                "map.settings.detritus.opacity and decay must be between 0 and 1 but received {} and {}",
+                detritus.opacity, detritus.decay
+            )));
+        }
+
+        // Fertility
+        let fertility = &self.map.settings.fertility;
+        if !(0.0..=1.0).contains(&fertility.strength) || !is_larger(fertility.scale, 0.0) {
+            return Err(ConfigError::Invalid(format!(
+                "map.settings.fertility.strength must be between 0 and 1 and scale must be larger than 0 but received {} and {}",
+                fertility.strength, fertility.scale
+            )));
+        }
+
+        // Terrain
+        let terrain = &self.map.settings.terrain;
+        if !is_larger(terrain.scale, 0.0) || terrain.slope.is_nan() || terrain.slope < 0.0 {
+            return Err(ConfigError::Invalid(format!(
+                "map.settings.terrain.scale must be larger than 0 and slope must not be negative but received {} and {}",
+                terrain.scale, terrain.slope
+            )));
+        }
+
+        // Remodel
+        let remodel = &self.map.settings.remodel;
+        if remodel.capacity_growth.is_nan()
+            || remodel.capacity_growth < 0.0
+            || !is_larger(remodel.capacity_max, 0.0)
+            || remodel.reserve_cost.is_nan()
+            || remodel.reserve_cost < 0.0
+        {
+            return Err(ConfigError::Invalid(format!(
+                "map.settings.remodel.capacity_growth and reserve_cost must not be negative and capacity_max must be larger than 0 but received {}, {} and {}",
+                remodel.capacity_growth, remodel.reserve_cost, remodel.capacity_max
+            )));
+        }
+
+        // Dormancy
+        let dormancy = &self.map.settings.dormancy;
+        if dormancy.running.is_nan() || dormancy.running < 0.0 {
+            return Err(ConfigError::Invalid(format!(
+                "map.settings.dormancy.running must not be negative but received {}",
+                dormancy.running
+            )));
+        }
+
+        // Species
+        let species = &self.map.settings.species;
+        if !(0.0..=1.0).contains(&species.threshold) {
+            return Err(ConfigError::Invalid(format!(
+                "map.settings.species.threshold must be between 0 and 1 but received {}",
+                species.threshold
+            )));
+        }
+
+        // Catastrophes
+        let events = &self.map.settings.events;
+        for (name, probability) in [
+            ("eclipse", events.eclipse.schedule.probability),
+            ("wildfire", events.wildfire.schedule.probability),
+            ("frost", events.frost.schedule.probability),
+        ] {
+            if !(0.0..=1.0).contains(&probability) {
+                return Err(ConfigError::Invalid(format!(
+                    "map.settings.events.{}.schedule.probability must be between 0 and 1 but received {}",
+                    name, probability
+                )));
+            }
+        }
+        if !(0.0..=1.0).contains(&events.eclipse.intensity)
+            || !(0.0..=1.0).contains(&events.frost.damage)
+        {
+            return Err(ConfigError::Invalid(format!(
+                "map.settings.events.eclipse.intensity and frost.damage must be between 0 and 1 but received {} and {}",
+                events.eclipse.intensity, events.frost.damage
+            )));
+        }
+
+        // Audit
+        let audit = &self.map.settings.audit;
+        if audit.tolerance.is_nan() || audit.tolerance < 0.0 {
+            return Err(ConfigError::Invalid(format!(
+                "map.settings.audit.tolerance must not be negative but received {}",
+                audit.tolerance
+            )));
+        }
+
+        return Ok(());
+    }
+}
+
+/// Checks if a value is larger than a bound, NaN is never larger
+/// # Parameters
+///
+/// value: The value to check
+///
+/// bound: The bound to compare with
+fn is_larger(value: f64, bound: f64) -> bool {
+    return value > bound;
+}
+
+/// The error types for when loading a config
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    /// The file could not be read
+    #[error("Unable to read config file: {:?}", .0)]
+    Io(io::Error),
+    /// The content of the file is not valid TOML or has the wrong types
+    #[error("Unable to parse config: {}", .0)]
+    Parse(toml::de::Error),
+    /// A value is outside of its allowed range
+    #[error("Invalid config: {}", .0)]
+    Invalid(String),
+}
+
+impl From<io::Error> for ConfigError {
+    fn from(value: io::Error) -> Self {
+        return Self::Io(value);
+    }
+}
+
+impl From<toml::de::Error> for ConfigError {
+    fn from(value: toml::de::Error) -> Self {
+        return Self::Parse(value);
+    }
+}
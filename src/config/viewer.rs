@@ -0,0 +1,113 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{constants, report};
+
+/// All settings for the window and for running the viewer
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The number of frames per second
+    pub framerate: f64,
+    /// The number of simulation steps per second
+    pub sim_rate: f64,
+    /// The multiplier when speeding up or slowing down the simulation
+    pub sim_rate_modifier: f64,
+    /// The initial width of the window in pixels
+    pub window_width: u32,
+    /// The initial height of the window in pixels
+    pub window_height: u32,
+    /// The png image to use as the icon of the window, left out to use the
+    /// default icon
+    pub window_icon: Option<PathBuf>,
+    /// The number of steps after which the run ends, left out for no limit
+    pub step_limit: Option<usize>,
+    /// If true then the run ends when all plants have died out
+    pub end_on_extinction: bool,
+    /// The population above which the run ends, left out for no limit
+    pub population_limit: Option<usize>,
+    /// What happens when the step limit, extinction or population limit is
+    /// reached, the run either ends or is paused with the report written
+    pub stop_action: report::StopAction,
+    /// The directory containing the settings presets
+    pub preset_directory: PathBuf,
+    /// The directory to save thumbnails and reports of the run to
+    pub export_directory: PathBuf,
+    /// The width of thumbnails in pixels
+    pub thumbnail_width: u32,
+    /// The file to stream the statistics of every step to, CSV unless the
+    /// extension is .json or .jsonl, left out to not export statistics
+    pub stats_out: Option<PathBuf>,
+    /// The format of the ancestry tree of the surviving lineages written at
+    /// the end of the run and with Y, either "Newick" or "Dot"
+    pub phylogeny_format: report::TreeFormat,
+    /// The number of steps between each time the statistics file is flushed
+    pub stats_flush_interval: usize,
+    /// The number of simulation steps between each frame saved while
+    /// recording
+    pub record_interval: usize,
+    /// The number of past states of the map kept for stepping backwards, 0
+    /// disables rewinding
+    pub rewind_capacity: usize,
+    /// The number of simulation steps between each state kept for stepping
+    /// backwards
+    pub rewind_interval: usize,
+    /// If true then the overlay with controls and statistics is shown at
+    /// startup
+    pub show_overlay: bool,
+    /// If true then the light is computed on the gpu, meant for very large
+    /// maps, runs are not identical to runs computing the light on the cpu
+    pub gpu_light: bool,
+    /// If true then the simulation settings are loaded again whenever the
+    /// config file is saved, such that the environment can be changed in the
+    /// middle of a run
+    pub watch_config: bool,
+    /// The number of samples per pixel for anti-aliasing, 1 disables it
+    pub msaa_samples: u32,
+    /// How much wider the columns at the left and right edge of the map are
+    /// drawn than the average column relative to its width, the columns in
+    /// the middle are narrower by the same amount, 0 draws all columns
+    /// equally wide
+    pub column_stretch: f64,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            framerate: constants::FRAMERATE,
+            sim_rate: constants::SIM_RATE,
+            sim_rate_modifier: constants::SIM_RATE_MODIFIER,
+            window_width: constants::WINDOW_SIZE.w as u32,
+            window_height: constants::WINDOW_SIZE.h as u32,
+            window_icon: constants::WINDOW_ICON.map(PathBuf::from),
+            step_limit: constants::STEP_LIMIT,
+            end_on_extinction: constants::END_ON_EXTINCTION,
+            population_limit: constants::POPULATION_LIMIT,
+            stop_action: constants::STOP_ACTION,
+            preset_directory: PathBuf::from(constants::PRESET_DIRECTORY),
+            export_directory: PathBuf::from(constants::EXPORT_DIRECTORY),
+            thumbnail_width: constants::THUMBNAIL_WIDTH,
+            stats_out: constants::STATS_OUT.map(PathBuf::from),
+            phylogeny_format: constants::PHYLOGENY_FORMAT,
+            stats_flush_interval: constants::STATS_FLUSH_INTERVAL,
+            record_interval: constants::RECORD_INTERVAL,
+            rewind_capacity: constants::REWIND_CAPACITY,
+            rewind_interval: constants::REWIND_INTERVAL,
+            show_overlay: constants::SHOW_OVERLAY,
+            gpu_light: constants::GPU_LIGHT,
+            watch_config: constants::WATCH_CONFIG,
+            msaa_samples: constants::MSAA_SAMPLES,
+            column_stretch: constants::COLUMN_STRETCH,
+        };
+    }
+
+    /// Constructs the conditions ending the run
+    pub fn get_stop_conditions(&self) -> report::StopConditions {
+        return report::StopConditions::new()
+            .with_step_limit(self.step_limit)
+            .with_extinction(self.end_on_extinction)
+            .with_population_limit(self.population_limit);
+    }
+}
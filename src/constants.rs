@@ -1,10 +1,17 @@
-use crate::{map, types};
+use crate::{map, report, types};
 
 pub const FRAMERATE: f64 = 60.0;
+pub const WINDOW_SIZE: types::ISize = types::ISize { w: 500, h: 500 };
 pub const CAMERA_MOVE_SPEED: f64 = 1.0;
 pub const CAMERA_ZOOM_SPEED: f64 = 1.0;
+pub const CAMERA_SCROLL_SPEED: f64 = 1.1;
+pub const CAMERA_SCROLL_PIXELS_PER_LINE: f64 = 40.0;
+pub const CAMERA_DRAG_CLICK_DISTANCE: f64 = 4.0;
 pub const CAMERA_BOOST_FACTOR: f64 = 2.0;
 pub const CAMERA_ZOOM_LIMITS: (f64, f64) = (0.01, 1.0);
+pub const CAMERA_FOLLOW_RATE: f64 = 3.0;
+pub const CAMERA_ANIMATION_DURATION: f64 = 0.6;
+pub const VIEW_BOOKMARK_COUNT: usize = 10;
 pub const COLOR_BACKGROUND: types::Color = types::Color::new(0.0, 0.0, 0.0, 1.0);
 pub const COLOR_MAP_LIGHT: types::ColorMapLinearRGBA = types::ColorMapLinearRGBA {
     empty: types::Color::new(0.0, 0.0, 0.0, 1.0),
@@ -14,19 +21,121 @@ pub const COLOR_MAP_TRANSPARENCY: types::ColorMapLinearRGBA = types::ColorMapLin
     empty: types::Color::new(0.5, 0.5, 1.0, 1.0),
     saturated: types::Color::new(0.0, 0.0, 1.0, 1.0),
 };
+pub const COLOR_MAP_WATER: types::ColorMapLinearRGBA = types::ColorMapLinearRGBA {
+    empty: types::Color::new(0.0, 0.0, 0.0, 1.0),
+    saturated: types::Color::new(0.0, 0.6, 1.0, 1.0),
+};
+pub const COLOR_MAP_NUTRIENTS: types::ColorMapLinearRGBA = types::ColorMapLinearRGBA {
+    empty: types::Color::new(0.0, 0.0, 0.0, 1.0),
+    saturated: types::Color::new(0.6, 0.4, 0.1, 1.0),
+};
+pub const COLOR_MAP_CHUNK_COST: [types::ColorStop; 5] = [
+    types::ColorStop::new(0.0, types::Color::from_hex(0x440154)),
+    types::ColorStop::new(0.25, types::Color::from_hex(0x3b528b)),
+    types::ColorStop::new(0.5, types::Color::from_hex(0x21918c)),
+    types::ColorStop::new(0.75, types::Color::from_hex(0x5ec962)),
+    types::ColorStop::new(1.0, types::Color::from_hex(0xfde725)),
+];
+pub const COLOR_MAP_CLOUDS: types::ColorMapLinearRGBA = types::ColorMapLinearRGBA {
+    empty: types::Color::new(0.2, 0.4, 0.8, 1.0),
+    saturated: types::Color::new(0.9, 0.9, 0.9, 1.0),
+};
+pub const COLOR_MAP_TEMPERATURE: types::ColorMapLinearRGBA = types::ColorMapLinearRGBA {
+    empty: types::Color::new(0.0, 0.2, 1.0, 1.0),
+    saturated: types::Color::new(1.0, 0.2, 0.0, 1.0),
+};
+pub const COLOR_MAP_DETRITUS: types::ColorMapLinearRGBA = types::ColorMapLinearRGBA {
+    empty: types::Color::new(0.0, 0.0, 0.0, 1.0),
+    saturated: types::Color::new(0.45, 0.35, 0.25, 1.0),
+};
+pub const COLOR_MAP_FERTILITY: types::ColorMapLinearRGBA = types::ColorMapLinearRGBA {
+    empty: types::Color::new(0.55, 0.45, 0.3, 1.0),
+    saturated: types::Color::new(0.1, 0.5, 0.1, 1.0),
+};
+pub const COLOR_MAP_FOREIGN_SHADE: types::ColorMapLinearRGBA = types::ColorMapLinearRGBA {
+    empty: types::Color::new(0.1, 0.1, 0.1, 1.0),
+    saturated: types::Color::new(0.9, 0.1, 0.6, 1.0),
+};
+pub const COLOR_MAP_DIFFUSE: types::ColorMapLinearRGBA = types::ColorMapLinearRGBA {
+    empty: types::Color::new(0.0, 0.0, 0.0, 1.0),
+    saturated: types::Color::new(0.4, 0.7, 1.0, 1.0),
+};
+pub const COLOR_MAP_TERRAIN: types::ColorMapLinearRGBA = types::ColorMapLinearRGBA {
+    empty: types::Color::new(0.15, 0.2, 0.3, 1.0),
+    saturated: types::Color::new(0.75, 0.6, 0.45, 1.0),
+};
+pub const COLOR_MAP_PLANT_AGE: types::ColorMapLinearRGBA = types::ColorMapLinearRGBA {
+    empty: types::Color::new(0.6, 1.0, 0.2, 1.0),
+    saturated: types::Color::new(0.3, 0.1, 0.0, 1.0),
+};
+pub const COLOR_MAP_TRANSFER: types::ColorMapLinearRGBA = types::ColorMapLinearRGBA {
+    empty: types::Color::new(1.0, 0.9, 0.4, 0.6),
+    saturated: types::Color::new(1.0, 0.3, 0.0, 1.0),
+};
+pub const COLOR_SPECIES_HUE_STEP: f64 = 0.618033988749895;
+pub const COLOR_PLANT_NONE: types::Color = types::Color::new(0.0, 0.0, 0.0, 0.0);
+pub const COLOR_PLANT_LOG: types::Color = types::Color::from_hex(0x52361e);
+pub const COLOR_PLANT_SUGAR_BULB: types::Color = types::Color::from_hex(0x93b5ae);
+pub const COLOR_PLANT_LEAF: types::Color = types::Color::from_hex(0x1b6623);
+pub const COLOR_PLANT_SEED: types::Color = types::Color::from_hex(0xf2bb07);
+pub const COLOR_PLANT_RIPE_SEED: types::Color = types::Color::from_hex(0xb30c1a);
+pub const COLOR_PLANT_ROOT: types::Color = types::Color::from_hex(0xc8a46e);
+pub const COLOR_PLANT_FLOWER: types::Color = types::Color::from_hex(0xe85fa8);
+pub const COLOR_PLANT_THORN: types::Color = types::Color::from_hex(0x6b4f7a);
+pub const COLOR_BRIDGE_LOG: types::Color = types::Color::from_hex(0x52361e);
+pub const COLOR_BRIDGE_BRANCH: types::Color = types::Color::from_hex(0x78583c);
+pub const COLOR_BORDER: types::Color = types::Color::new(0.0, 0.0, 0.0, 0.5);
+pub const COLOR_SELECTED: types::Color = types::Color::new(1.0, 1.0, 1.0, 0.5);
+pub const COLOR_HOVERED: types::Color = types::Color::new(1.0, 1.0, 1.0, 0.2);
 pub const COLOR_MODE_BACKGROUND: map::DataModeBackground = map::DataModeBackground::Light;
+pub const COLOR_MODE_PLANT: map::DataModePlant = map::DataModePlant::Bulk;
+pub const COLOR_SCALING: types::ColorScaling = types::ColorScaling::Linear;
+pub const COLOR_SCALING_EXPONENT: f64 = 0.5;
 
 pub const MAP_SIZE: types::ISize = types::ISize { w: 200, h: 50 };
 pub const MAP_TRANSPARENCY: f64 = 0.999;
 pub const MAP_SUN_YEAR: f64 = 6000.0;
 pub const MAP_SUN_DAY: f64 = 500.0;
-pub const MAP_SUN_TILT: f64 = MATH_PI * 23.5 / 180.0;
-pub const MAP_SUN_LATITUDE: f64 = MATH_PI * 55.7 / 180.0;
+pub const MAP_SUN_TILT_DEGREES: f64 = 23.5;
+pub const MAP_SUN_LATITUDE_DEGREES: f64 = 55.7;
+pub const MAP_SUN_LATITUDE_END_DEGREES: Option<f64> = None;
 pub const MAP_SUN_INTENSITY: f64 = 1.0;
+pub const MAP_COMPANION_YEAR: f64 = 4000.0;
+pub const MAP_COMPANION_DAY: f64 = 350.0;
+pub const MAP_COMPANION_INTENSITY: f64 = 0.5;
+pub const MAP_UPDATE_ORDER: map::settings::update::Order = map::settings::update::Order::Direction;
+pub const MAP_SEED: u64 = 0;
+pub const MAP_PRESET: Option<&str> = None;
+
+pub const PRESET_DIRECTORY: &str = "presets";
+pub const EXPORT_DIRECTORY: &str = "exports";
+pub const THUMBNAIL_WIDTH: u32 = 256;
+pub const GENOME_FILE: &str = "genome.txt";
+pub const STATS_OUT: Option<&str> = None;
+pub const WINDOW_ICON: Option<&str> = None;
+pub const TITLE_INTERVAL: f64 = 1.0;
+pub const PROFILER_SAMPLES: usize = 120;
+pub const PHYLOGENY_FORMAT: report::TreeFormat = report::TreeFormat::Newick;
+pub const STATS_FLUSH_INTERVAL: usize = 100;
+pub const RECORD_INTERVAL: usize = 10;
+pub const REWIND_CAPACITY: usize = 100;
+pub const REWIND_INTERVAL: usize = 1;
+pub const SHOW_OVERLAY: bool = true;
+pub const GPU_LIGHT: bool = false;
+pub const WATCH_CONFIG: bool = false;
+pub const CONFIG_POLL_INTERVAL: f64 = 1.0;
+pub const MSAA_SAMPLES: u32 = 4;
+pub const COLUMN_STRETCH: f64 = 0.0;
 
 pub const SIM_RATE: f64 = 100.0;
 pub const SIM_RATE_MODIFIER: f64 = 1.5;
+pub const STEP_LIMIT: Option<usize> = None;
+pub const END_ON_EXTINCTION: bool = true;
+pub const POPULATION_LIMIT: Option<usize> = None;
+pub const STOP_ACTION: report::StopAction = report::StopAction::End;
 
+pub const MATH_SQRT_2: f64 =
+    1.41421356237309504880168872420969807856967187537694807317667973799073247846;
 pub const MATH_SQRT_3: f64 =
     1.73205080756887729352744634150587236694280525381038062805580697945193301690;
 pub const MATH_PI: f64 =
@@ -0,0 +1,331 @@
+use std::sync::mpsc;
+
+use thiserror::Error;
+use wgpu::{BackendOptions, util::DeviceExt};
+
+use crate::{map, types};
+
+/// Computes the light of every tile on the gpu with a compute shader, the
+/// light is read back to the map every step. The gpu computes in single
+/// precision so runs are not identical to runs computing the light on the
+/// cpu
+#[derive(Clone, Debug)]
+pub struct LightCompute {
+    /// The logical device connected to the gpu
+    device: wgpu::Device,
+    /// The command queue for sending info to the gpu
+    queue: wgpu::Queue,
+    /// The compute pipeline running the light shader
+    pipeline: wgpu::ComputePipeline,
+    /// The buffers for the size of the map last computed, None before the
+    /// first step
+    buffers: Option<LightBuffers>,
+}
+
+impl LightCompute {
+    /// The number of tiles computed by each workgroup, must match the shader
+    const WORKGROUP_SIZE: u32 = 64;
+
+    /// Constructs a new light compute on an existing device
+    ///
+    /// # Parameters
+    ///
+    /// device: The logical device connected to the gpu
+    ///
+    /// queue: The command queue for sending info to the gpu
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
+        let shader = device.create_shader_module(wgpu::include_wgsl!("../shaders/light.wgsl"));
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Compute Pipeline: Light"),
+            layout: None,
+            module: &shader,
+            entry_point: Some("cs_main"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            cache: None,
+        });
+
+        return Self {
+            device: device.clone(),
+            queue: queue.clone(),
+            pipeline,
+            buffers: None,
+        };
+    }
+
+    /// Constructs a new light compute on a device of its own, such that it
+    /// can be used without a window
+    ///
+    /// # Errors
+    ///
+    /// See LightComputeError for a description of the different errors which may occur
+    pub fn new_headless() -> Result<Self, LightComputeError> {
+        return pollster::block_on(async {
+            let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+                backends: wgpu::Backends::all(),
+                flags: wgpu::InstanceFlags::VALIDATION,
+                backend_options: BackendOptions::from_env_or_default(),
+                memory_budget_thresholds: wgpu::MemoryBudgetThresholds::default(),
+            });
+            let adapter = instance
+                .request_adapter(&wgpu::RequestAdapterOptions {
+                    power_preference: wgpu::PowerPreference::HighPerformance,
+                    compatible_surface: None,
+                    force_fallback_adapter: false,
+                })
+                .await?;
+            let (device, queue) = adapter
+                .request_device(&wgpu::DeviceDescriptor {
+                    label: Some("Request Device: Light"),
+                    required_features: wgpu::Features::empty(),
+                    required_limits: wgpu::Limits::default(),
+                    memory_hints: wgpu::MemoryHints::Performance,
+                    trace: wgpu::Trace::Off,
+                    experimental_features: wgpu::ExperimentalFeatures::default(),
+                })
+                .await?;
+
+            return Ok(Self::new(&device, &queue));
+        });
+    }
+
+    /// Computes the light of every tile for the next step and reads it back
+    ///
+    /// # Parameters
+    ///
+    /// size: The size of the map
+    ///
+    /// light: The light of every tile
+    ///
+    /// transparency: The transparency of every tile
+    ///
+    /// sun: The intensity of the sun above every column
+    ///
+    /// # Errors
+    ///
+    /// See LightComputeError for a description of the different errors which may occur
+    fn compute(
+        &mut self,
+        size: &types::ISize,
+        light: &[f32],
+        transparency: &[f32],
+        sun: &[f32],
+    ) -> Result<Vec<f32>, LightComputeError> {
+        let tile_count = size.w * size.h;
+        if tile_count == 0 {
+            return Ok(Vec::new());
+        }
+        if light.len() != tile_count || transparency.len() != tile_count || sun.len() != size.w {
+            return Err(LightComputeError::InvalidLength);
+        }
+
+        // Make sure the buffers fit the map
+        if self
+            .buffers
+            .as_ref()
+            .is_none_or(|buffers| buffers.size != *size)
+        {
+            self.buffers = Some(LightBuffers::new(&self.device, &self.pipeline, size));
+        }
+        let buffers = self.buffers.as_ref().unwrap();
+
+        // Upload the current state and run the shader
+        self.queue
+            .write_buffer(&buffers.light, 0, bytemuck::cast_slice(light));
+        self.queue
+            .write_buffer(&buffers.transparency, 0, bytemuck::cast_slice(transparency));
+        self.queue
+            .write_buffer(&buffers.sun, 0, bytemuck::cast_slice(sun));
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Command Encoder: Light"),
+            });
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Compute Pass: Light"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.pipeline);
+            compute_pass.set_bind_group(0, &buffers.bind_group, &[]);
+            compute_pass.dispatch_workgroups(
+                (tile_count as u32).div_ceil(Self::WORKGROUP_SIZE),
+                1,
+                1,
+            );
+        }
+        encoder.copy_buffer_to_buffer(
+            &buffers.light_next,
+            0,
+            &buffers.readback,
+            0,
+            buffers.readback.size(),
+        );
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        // Wait for the result to be readable
+        let slice = buffers.readback.slice(..);
+        let (sender, receiver) = mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            _ = sender.send(result);
+        });
+        self.device.poll(wgpu::PollType::wait_indefinitely())?;
+        receiver.recv().unwrap_or(Err(wgpu::BufferAsyncError))?;
+
+        let light_next = bytemuck::cast_slice::<u8, f32>(&slice.get_mapped_range()).to_vec();
+        buffers.readback.unmap();
+
+        return Ok(light_next);
+    }
+}
+
+impl map::LightSolver for LightCompute {
+    fn solve(
+        &mut self,
+        size: &types::ISize,
+        light: &[f32],
+        transparency: &[f32],
+        sun: &[f32],
+    ) -> Option<Vec<f32>> {
+        return match self.compute(size, light, transparency, sun) {
+            Ok(light) => Some(light),
+            Err(error) => {
+                eprintln!("Unable to compute light on the gpu: {}", error);
+                None
+            }
+        };
+    }
+}
+
+/// All buffers used for computing the light of a map of a single size
+#[derive(Clone, Debug)]
+struct LightBuffers {
+    /// The size of the map the buffers fit
+    size: types::ISize,
+    /// The light of every tile in the current step
+    light: wgpu::Buffer,
+    /// The transparency of every tile in the current step
+    transparency: wgpu::Buffer,
+    /// The intensity of the sun above every column
+    sun: wgpu::Buffer,
+    /// The light of every tile in the next step
+    light_next: wgpu::Buffer,
+    /// The buffer the next light is copied to for reading it back
+    readback: wgpu::Buffer,
+    /// The bind group binding all buffers to the shader
+    bind_group: wgpu::BindGroup,
+}
+
+impl LightBuffers {
+    /// Constructs new buffers for a map, the size must not be 0
+    ///
+    /// # Parameters
+    ///
+    /// device: The logical device connected to the gpu
+    ///
+    /// pipeline: The light pipeline to bind the buffers to
+    ///
+    /// size: The size of the map
+    fn new(device: &wgpu::Device, pipeline: &wgpu::ComputePipeline, size: &types::ISize) -> Self {
+        let tile_bytes = (size.w * size.h * size_of::<f32>()) as wgpu::BufferAddress;
+        let new_buffer = |label: &str, bytes: wgpu::BufferAddress, usage: wgpu::BufferUsages| {
+            return device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size: bytes,
+                usage,
+                mapped_at_creation: false,
+            });
+        };
+        let input = wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST;
+
+        let uniforms = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Buffer: Light Uniforms"),
+            contents: bytemuck::cast_slice(&[size.w as u32, size.h as u32, 0, 0]),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let light = new_buffer("Buffer: Light", tile_bytes, input);
+        let transparency = new_buffer("Buffer: Light Transparency", tile_bytes, input);
+        let sun = new_buffer(
+            "Buffer: Light Sun",
+            (size.w * size_of::<f32>()) as wgpu::BufferAddress,
+            input,
+        );
+        let light_next = new_buffer(
+            "Buffer: Light Next",
+            tile_bytes,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        );
+        let readback = new_buffer(
+            "Buffer: Light Readback",
+            tile_bytes,
+            wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        );
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Bind Group: Light"),
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[&uniforms, &light, &transparency, &sun, &light_next]
+                .iter()
+                .enumerate()
+                .map(|(binding, buffer)| wgpu::BindGroupEntry {
+                    binding: binding as u32,
+                    resource: buffer.as_entire_binding(),
+                })
+                .collect::<Vec<_>>(),
+        });
+
+        return Self {
+            size: *size,
+            light,
+            transparency,
+            sun,
+            light_next,
+            readback,
+            bind_group,
+        };
+    }
+}
+
+/// The error types for when computing the light on the gpu
+#[derive(Error, Debug)]
+pub enum LightComputeError {
+    /// The gpu adapter could not be created
+    #[error("Unable to get adapter for gpu: {:?}", .0)]
+    GetAdapter(wgpu::RequestAdapterError),
+    /// The device and queue could not be created
+    #[error("Unable to retrieve logical device: {:?}", .0)]
+    RequestDevice(wgpu::RequestDeviceError),
+    /// The lists of light, transparency and sun do not match the size of the
+    /// map
+    #[error("The light, transparency and sun lists do not match the size of the map")]
+    InvalidLength,
+    /// The gpu could not finish computing
+    #[error("Unable to wait for the gpu: {:?}", .0)]
+    Poll(wgpu::PollError),
+    /// The computed light could not be read back from the gpu
+    #[error("Unable to read the computed light: {:?}", .0)]
+    Readback(wgpu::BufferAsyncError),
+}
+
+impl From<wgpu::RequestAdapterError> for LightComputeError {
+    fn from(value: wgpu::RequestAdapterError) -> Self {
+        return Self::GetAdapter(value);
+    }
+}
+
+impl From<wgpu::RequestDeviceError> for LightComputeError {
+    fn from(value: wgpu::RequestDeviceError) -> Self {
+        return Self::RequestDevice(value);
+    }
+}
+
+impl From<wgpu::PollError> for LightComputeError {
+    fn from(value: wgpu::PollError) -> Self {
+        return Self::Poll(value);
+    }
+}
+
+impl From<wgpu::BufferAsyncError> for LightComputeError {
+    fn from(value: wgpu::BufferAsyncError) -> Self {
+        return Self::Readback(value);
+    }
+}
@@ -0,0 +1,165 @@
+use std::ops::Range;
+
+use crate::{constants::MATH_SQRT_3, map, types};
+
+/// The distance in world coordinates a tile center may be outside the view
+/// and still be drawn, large enough to include the full hexagon and the
+/// bridges reaching towards the neighbors
+const MARGIN: f64 = 1.0;
+
+/// The largest number of rows drawn with a draw call each when only part of
+/// the width of the map is visible, with more rows all rows are drawn in one
+/// range including the hidden tiles in between
+const MAX_ROW_RANGES: usize = 64;
+
+/// Checks if the view is narrow enough for a single copy of the map wrapped
+/// around the center of the view to cover it
+///
+/// # Parameters
+///
+/// transform: The transform from world coordinates to screen coordinates
+///
+/// grid_layout: The layout of the grid
+pub(super) fn fits_in_width(
+    transform: &types::Transform2D,
+    grid_layout: &dyn map::GridLayout,
+) -> bool {
+    let (x_min, x_max, _, _) = get_view_bounds(transform, grid_layout);
+
+    // Every other row is shifted half a tile
+    return x_max - x_min + 2.0 * MARGIN + 0.5 <= grid_layout.get_n_columns() as f64;
+}
+
+/// Finds the ranges of tile indices which are visible through a transform,
+/// the tiles are in row first order such that all visible rows form a single
+/// range if the entire width of the map is visible and one or two ranges for
+/// each row otherwise, neighboring ranges are merged. The result is empty if
+/// the map is outside the view
+///
+/// # Parameters
+///
+/// transform: The transform from world coordinates to screen coordinates
+///
+/// grid_layout: The layout of the grid
+///
+/// n_tiles: The total number of tiles in the grid
+///
+/// wrap: True if the tiles are wrapped around the center of the view, see
+/// fits_in_width
+pub(super) fn get_visible_tiles(
+    transform: &types::Transform2D,
+    grid_layout: &dyn map::GridLayout,
+    n_tiles: usize,
+    wrap: bool,
+) -> Vec<Range<u32>> {
+    let n_columns = grid_layout.get_n_columns();
+    if n_columns == 0 || n_tiles == 0 {
+        return Vec::new();
+    }
+    let n_rows = n_tiles / n_columns;
+    let (x_min, x_max, y_min, y_max) = get_view_bounds(transform, grid_layout);
+
+    // Rows go downwards and every other row is shifted half a tile right
+    let row_height = 0.5 * MATH_SQRT_3;
+    let row_start = (-(y_max + MARGIN) / row_height).ceil().max(0.0);
+    let row_end = (-(y_min - MARGIN) / row_height).floor();
+    if row_start > row_end || row_start >= n_rows as f64 {
+        return Vec::new();
+    }
+    let row_start = row_start as usize;
+    let row_end = (row_end as usize).min(n_rows - 1);
+
+    // Wrapped columns may lie outside the map and are moved into it, such
+    // that the visible columns of a row may be split in two, the columns are
+    // given as the first column and the column after the last
+    let column_start = (x_min - MARGIN - 0.5).ceil() as isize;
+    let column_end = (x_max + MARGIN).floor() as isize;
+    let n = n_columns as isize;
+    let columns = if wrap {
+        if column_end - column_start + 1 >= n {
+            vec![(0, n_columns)]
+        } else {
+            let start = column_start.rem_euclid(n) as usize;
+            let end = column_end.rem_euclid(n) as usize + 1;
+            if start < end {
+                vec![(start, end)]
+            } else {
+                vec![(0, end), (start, n_columns)]
+            }
+        }
+    } else {
+        let start = column_start.max(0);
+        let end = column_end.min(n - 1) + 1;
+        if start >= end {
+            return Vec::new();
+        }
+        vec![(start as usize, end as usize)]
+    };
+
+    // Draw everything in between if there are too many rows for a draw call
+    // each
+    let row_count = row_end - row_start + 1;
+    let to_index = |row: usize, column: usize| (row * n_columns + column) as u32;
+    if row_count > MAX_ROW_RANGES {
+        let start = to_index(row_start, columns[0].0);
+        let end = to_index(row_end, columns[columns.len() - 1].1);
+        let range = start..end;
+        return vec![range];
+    }
+
+    let mut ranges: Vec<Range<u32>> = Vec::new();
+    for row in row_start..=row_end {
+        for (start, end) in columns.iter() {
+            let range = to_index(row, *start)..to_index(row, *end);
+            match ranges.last_mut() {
+                Some(last) if last.end == range.start => last.end = range.end,
+                _ => ranges.push(range),
+            }
+        }
+    }
+
+    return ranges;
+}
+
+/// Gets the bounding box of the screen in the grid, where every column is one
+/// wide, as the minimum and maximum x and y
+///
+/// # Parameters
+///
+/// transform: The transform from world coordinates to screen coordinates
+///
+/// grid_layout: The layout of the grid
+fn get_view_bounds(
+    transform: &types::Transform2D,
+    grid_layout: &dyn map::GridLayout,
+) -> (f64, f64, f64, f64) {
+    let inverse = transform.inv();
+
+    let (x_min, x_max, y_min, y_max) = [(-1.0, -1.0), (-1.0, 1.0), (1.0, -1.0), (1.0, 1.0)]
+        .map(|(x, y)| &inverse * types::Point::new(x, y))
+        .iter()
+        .fold(
+            (
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+            ),
+            |(x_min, x_max, y_min, y_max), corner| {
+                (
+                    x_min.min(corner.x),
+                    x_max.max(corner.x),
+                    y_min.min(corner.y),
+                    y_max.max(corner.y),
+                )
+            },
+        );
+
+    // The conversion to the grid is increasing so the bounds stay in order
+    return (
+        grid_layout.to_grid_x(x_min),
+        grid_layout.to_grid_x(x_max),
+        y_min,
+        y_max,
+    );
+}
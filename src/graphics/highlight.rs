@@ -0,0 +1,194 @@
+use std::mem;
+
+use crate::{map, render, types};
+
+use super::UniformsInstance;
+
+/// The ways a single tile can be highlighted, the id is the index of the
+/// color in the highlight color map
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HighlightType {
+    /// The tile selected for inspection
+    Selected,
+    /// The tile below the cursor
+    Hovered,
+}
+
+impl HighlightType {
+    /// The number of different highlight types
+    pub const COUNT: usize = 2;
+
+    /// The id of the highlight type
+    pub fn id(&self) -> usize {
+        return match self {
+            Self::Selected => 0,
+            Self::Hovered => 1,
+        };
+    }
+}
+
+/// All data for instancing the highlight of a single tile
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub(super) struct InstanceHighlight {
+    /// The index of the highlighted tile
+    tile: u32,
+    /// The value to draw the highlight with, the id of the highlight type
+    /// scaled to the color map
+    color_value: f32,
+}
+
+impl InstanceHighlight {
+    /// Creates the vertex buffer description for the highlight instance
+    pub(super) fn desc() -> wgpu::VertexBufferLayout<'static> {
+        return wgpu::VertexBufferLayout {
+            array_stride: mem::size_of::<InstanceHighlight>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 1,
+                    format: wgpu::VertexFormat::Uint32,
+                },
+                wgpu::VertexAttribute {
+                    offset: mem::size_of::<u32>() as wgpu::BufferAddress,
+                    shader_location: 2,
+                    format: wgpu::VertexFormat::Float32,
+                },
+            ],
+        };
+    }
+}
+
+/// A small instance layer tinting the highlighted tiles, it holds at most
+/// one instance for each highlight type
+#[derive(Debug)]
+pub(super) struct Highlight {
+    /// The buffer holding the instances of all highlighted tiles
+    buffer: wgpu::Buffer,
+    /// The number of highlighted tiles
+    count: u32,
+    /// The uniforms used when drawing the highlights
+    uniforms: UniformsInstance,
+}
+
+impl Highlight {
+    /// Creates a new highlight layer without any highlighted tiles
+    ///
+    /// # Parameters
+    ///
+    /// render_state: The render state to use for rendering
+    pub(super) fn new(render_state: &render::RenderState) -> Self {
+        let buffer = render_state
+            .get_device()
+            .create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Instance Buffer: Highlight"),
+                size: (HighlightType::COUNT * mem::size_of::<InstanceHighlight>()) as u64,
+                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+
+        return Self {
+            buffer,
+            count: 0,
+            uniforms: UniformsInstance::new(render_state),
+        };
+    }
+
+    /// Sets the highlighted tiles, at most one tile may be given for each
+    /// highlight type
+    ///
+    /// # Parameters
+    ///
+    /// render_state: The render state to use for rendering
+    ///
+    /// tiles: The index of every highlighted tile along with the type of the
+    /// highlight
+    pub(super) fn write(
+        &mut self,
+        render_state: &render::RenderState,
+        tiles: &[(usize, HighlightType)],
+    ) {
+        let instances = tiles
+            .iter()
+            .take(HighlightType::COUNT)
+            .map(|(tile, highlight)| InstanceHighlight {
+                tile: *tile as u32,
+                color_value: highlight.id() as f32 / 255.0,
+            })
+            .collect::<Vec<_>>();
+
+        if !instances.is_empty() {
+            render_state.get_queue().write_buffer(
+                &self.buffer,
+                0,
+                bytemuck::cast_slice(&instances),
+            );
+        }
+        self.count = instances.len() as u32;
+    }
+
+    /// Update the transform of a slot
+    ///
+    /// # Parameters
+    ///
+    /// render_state: The render state to use for rendering
+    ///
+    /// transform: The transform to apply to all vertices going from world coordinates to screen coordinates
+    ///
+    /// slot: The transform slot to write to
+    pub(super) fn write_transform(
+        &self,
+        render_state: &render::RenderState,
+        transform: &types::Transform2D,
+        slot: usize,
+    ) {
+        self.uniforms.write_transform(render_state, transform, slot);
+    }
+
+    /// Update the color map, this must be run once before the first rendering as it is not initialized
+    ///
+    /// # Parameters
+    ///
+    /// render_state: The render state to use for rendering
+    ///
+    /// color_map: The color map with a color for each highlight type
+    pub(super) fn write_color_map(
+        &self,
+        render_state: &render::RenderState,
+        color_map: &dyn types::ColorMap,
+    ) {
+        self.uniforms
+            .write_color_map(render_state, color_map, types::ColorScaling::Linear, 1.0);
+    }
+
+    /// Update the grid layout, this must be run once before the first rendering as it is not initialized
+    ///
+    /// # Parameters
+    ///
+    /// render_state: The render state to use for rendering
+    ///
+    /// grid_layout: The grid layout to write
+    pub(super) fn write_grid_layout(
+        &self,
+        render_state: &render::RenderState,
+        grid_layout: &map::UniformGridLayout,
+    ) {
+        self.uniforms.write_grid_layout(render_state, grid_layout);
+    }
+
+    /// Sets the highlight instances and uniforms, returns the number of
+    /// highlighted tiles
+    ///
+    /// # Parameters
+    ///
+    /// render_pass: The render pass to draw to
+    ///
+    /// slot: The transform slot to bind
+    pub(super) fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>, slot: usize) -> u32 {
+        self.uniforms.set(render_pass, slot);
+        render_pass.set_vertex_buffer(1, self.buffer.slice(..));
+
+        return self.count;
+    }
+}
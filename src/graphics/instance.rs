@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use wgpu::util::DeviceExt;
 
 use crate::{map, render, types};
@@ -11,17 +13,30 @@ pub enum InstanceMode {
     Sun,
     /// Instances for the background of the grid
     GridBackground(map::DataModeBackground),
+    /// Instances for the plants in the grid
+    Plant(map::DataModePlant),
+    /// Instances for the bridges between the plant tiles
+    Bridges,
+    /// Instances for the outlines of the tiles
+    Border,
+    /// Instances for the arrows showing the energy transfer through the
+    /// bridges
+    Transfers,
 }
 
 impl InstanceMode {
     /// The number of different instance modes
-    pub const COUNT: usize = 2;
+    pub const COUNT: usize = 6;
 
     /// The id for the mode of the instance
     pub fn mode_id(&self) -> usize {
         return match self {
             Self::Sun => 0,
             Self::GridBackground(mode) => mode.id(),
+            Self::Plant(mode) => mode.id(),
+            Self::Bridges => 0,
+            Self::Border => 0,
+            Self::Transfers => 0,
         };
     }
 
@@ -30,6 +45,10 @@ impl InstanceMode {
         return match self {
             Self::Sun => InstanceType::Sun,
             Self::GridBackground(_) => InstanceType::GridBackground,
+            Self::Plant(_) => InstanceType::Plant,
+            Self::Bridges => InstanceType::Bridges,
+            Self::Border => InstanceType::Border,
+            Self::Transfers => InstanceType::Transfers,
         };
     }
 
@@ -43,14 +62,40 @@ impl InstanceMode {
     /// # Parameters
     ///
     /// mode_background: The display mode for the grid background
-    pub const fn all_instances(mode_background: map::DataModeBackground) -> [Self; Self::COUNT] {
-        return [Self::Sun, Self::GridBackground(mode_background)];
+    ///
+    /// mode_plant: The display mode for the plants
+    pub const fn all_instances(
+        mode_background: map::DataModeBackground,
+        mode_plant: map::DataModePlant,
+    ) -> [Self; Self::COUNT] {
+        return [
+            Self::Sun,
+            Self::GridBackground(mode_background),
+            Self::Plant(mode_plant),
+            Self::Bridges,
+            Self::Border,
+            Self::Transfers,
+        ];
+    }
+
+    /// Checks if the data of a tile only depends on the tile itself such that
+    /// only changed tiles have to be uploaded
+    pub fn is_local(&self) -> bool {
+        return match self {
+            Self::GridBackground(mode) => mode.is_local(),
+            Self::Plant(mode) => mode.is_local(),
+            Self::Border => true,
+            Self::Sun | Self::Bridges | Self::Transfers => false,
+        };
     }
 
     /// Gets the pipeline used for this primitive
     pub(super) fn pipeline(&self) -> PipelineType {
         return match self {
-            Self::Sun | Self::GridBackground(_) => PipelineType::Unicolor,
+            Self::Sun | Self::GridBackground(_) | Self::Plant(_) => PipelineType::Unicolor,
+            Self::Bridges => PipelineType::Bridge,
+            Self::Border => PipelineType::Border,
+            Self::Transfers => PipelineType::Transfer,
         };
     }
 
@@ -59,10 +104,14 @@ impl InstanceMode {
     /// # Parameters
     ///
     /// map: The map used to get data from
-    pub(super) fn data<S: map::sun::Intensity>(&self, map: &map::Map<S>) -> Vec<map::InstanceTile> {
+    pub(super) fn data<S: map::sun::Intensity>(&self, map: &map::Map<S>) -> InstanceData {
         return match self {
-            Self::GridBackground(mode) => map.get_tile_data_background(&mode),
-            Self::Sun => map.get_sun_data(),
+            Self::GridBackground(mode) => InstanceData::Tile(map.get_tile_data_background(mode)),
+            Self::Sun => InstanceData::Tile(map.get_sun_data()),
+            Self::Plant(mode) => InstanceData::Tile(map.get_tile_data_plant(mode)),
+            Self::Bridges => InstanceData::Bridge(map.get_bridge_data()),
+            Self::Border => InstanceData::Tile(map.get_border_data()),
+            Self::Transfers => InstanceData::Transfer(map.get_transfer_data()),
         };
     }
 
@@ -93,12 +142,15 @@ impl InstanceMode {
     /// map: The map to use for initialization of the data
     ///
     /// mode_background: The display mode for the background of the tiles
+    ///
+    /// mode_plant: The display mode for the plants
     pub(super) fn new_collection<S: map::sun::Intensity>(
         render_state: &render::RenderState,
         map: &map::Map<S>,
         mode_background: map::DataModeBackground,
+        mode_plant: map::DataModePlant,
     ) -> [(BufferInstance, UniformsInstance); Self::COUNT] {
-        return Self::all_instances(mode_background)
+        return Self::all_instances(mode_background, mode_plant)
             .iter()
             .map(|instance| {
                 return instance.new(render_state, map);
@@ -139,17 +191,79 @@ impl InstanceMode {
     /// map: The map to use for data
     ///
     /// mode_background: The display mode for the background of the tiles
+    ///
+    /// mode_plant: The display mode for the plants
     pub(super) fn update_collection<S: map::sun::Intensity>(
         collection: &[(BufferInstance, UniformsInstance); Self::COUNT],
         render_state: &render::RenderState,
         map: &map::Map<S>,
         mode_background: map::DataModeBackground,
+        mode_plant: map::DataModePlant,
     ) {
-        for instance in Self::all_instances(mode_background).iter() {
+        for instance in Self::all_instances(mode_background, mode_plant).iter() {
             instance.update(collection, render_state, map);
         }
     }
 
+    /// Updates only the tiles in the given ranges of a instance buffer matching
+    /// the instance type if the data of a tile only depends on the tile
+    /// itself, otherwise the entire buffer is updated
+    ///
+    /// # Parameters
+    ///
+    /// collection: The full collection of instances
+    ///
+    /// render_state: The render state to use for rendering
+    ///
+    /// map: The map to use for data
+    ///
+    /// ranges: The ranges of tile indices which have changed
+    pub(super) fn update_ranges<S: map::sun::Intensity>(
+        &self,
+        collection: &[(BufferInstance, UniformsInstance); Self::COUNT],
+        render_state: &render::RenderState,
+        map: &map::Map<S>,
+        ranges: &[Range<usize>],
+    ) {
+        if !self.is_local() {
+            self.update(collection, render_state, map);
+            return;
+        }
+
+        collection[self.id()]
+            .0
+            .update_ranges(render_state, &self.data(map), ranges);
+    }
+
+    /// Updates the changed tiles of the instance buffers for all the
+    /// different instance types
+    ///
+    /// # Parameters
+    ///
+    /// collection: The full collection of instances
+    ///
+    /// render_state: The render state to use for rendering
+    ///
+    /// map: The map to use for data
+    ///
+    /// ranges: The ranges of tile indices which have changed
+    ///
+    /// mode_background: The display mode for the background of the tiles
+    ///
+    /// mode_plant: The display mode for the plants
+    pub(super) fn update_ranges_collection<S: map::sun::Intensity>(
+        collection: &[(BufferInstance, UniformsInstance); Self::COUNT],
+        render_state: &render::RenderState,
+        map: &map::Map<S>,
+        ranges: &[Range<usize>],
+        mode_background: map::DataModeBackground,
+        mode_plant: map::DataModePlant,
+    ) {
+        for instance in Self::all_instances(mode_background, mode_plant).iter() {
+            instance.update_ranges(collection, render_state, map, ranges);
+        }
+    }
+
     /// Update the color map, this must be run once before the first rendering as it is not initialized
     ///
     /// # Parameters
@@ -159,15 +273,24 @@ impl InstanceMode {
     /// render_state: The render state to use for rendering
     ///
     /// color_maps: The color maps for all modes
+    ///
+    /// scalings: The scalings of the values for all modes
+    ///
+    /// exponent: The exponent used by the exponent scaling
     pub(super) fn write_color_map(
         &self,
         collection: &[(BufferInstance, UniformsInstance); Self::COUNT],
         render_state: &render::RenderState,
         color_maps: &[Box<dyn types::ColorMap>],
+        scalings: &[types::ColorScaling],
+        exponent: f64,
     ) {
-        collection[self.id()]
-            .1
-            .write_color_map(render_state, color_maps[self.mode_id()].as_ref());
+        collection[self.id()].1.write_color_map(
+            render_state,
+            color_maps[self.mode_id()].as_ref(),
+            scalings[self.mode_id()],
+            exponent,
+        );
     }
 
     /// Update the color maps for the entire collection of instances, this must be run once before the first rendering as it is not initialized
@@ -180,15 +303,30 @@ impl InstanceMode {
     ///
     /// color_maps: The color maps for all the instance types
     ///
+    /// scalings: The scalings of the values for all the instance types
+    ///
+    /// exponent: The exponent used by the exponent scaling
+    ///
     /// mode_background: The display mode for the background of the tiles
+    ///
+    /// mode_plant: The display mode for the plants
     pub(super) fn write_color_map_collection(
         collection: &[(BufferInstance, UniformsInstance); Self::COUNT],
         render_state: &render::RenderState,
         color_maps: &[Vec<Box<dyn types::ColorMap>>; Self::COUNT],
+        scalings: &[Vec<types::ColorScaling>; Self::COUNT],
+        exponent: f64,
         mode_background: map::DataModeBackground,
+        mode_plant: map::DataModePlant,
     ) {
-        for instance in Self::all_instances(mode_background).iter() {
-            instance.write_color_map(collection, render_state, &color_maps[instance.id()]);
+        for instance in Self::all_instances(mode_background, mode_plant).iter() {
+            instance.write_color_map(
+                collection,
+                render_state,
+                &color_maps[instance.id()],
+                &scalings[instance.id()],
+                exponent,
+            );
         }
     }
 }
@@ -200,23 +338,43 @@ pub enum InstanceType {
     Sun,
     /// Instances for the background of the grid
     GridBackground,
+    /// Instances for the plants in the grid
+    Plant,
+    /// Instances for the bridges between the plant tiles
+    Bridges,
+    /// Instances for the outlines of the tiles
+    Border,
+    /// Instances for the arrows showing the energy transfer through the
+    /// bridges
+    Transfers,
 }
 
 impl InstanceType {
     /// The number of different instance types
-    pub const COUNT: usize = 2;
+    pub const COUNT: usize = 6;
 
     /// The id to find the instance type in the instance list
     pub fn id(&self) -> usize {
         return match self {
             Self::Sun => 0,
             Self::GridBackground => 1,
+            Self::Plant => 2,
+            Self::Bridges => 3,
+            Self::Border => 4,
+            Self::Transfers => 5,
         };
     }
 
     /// Gets a list of all the different instances
     pub const fn all_instances() -> &'static [Self; Self::COUNT] {
-        return &[Self::Sun, Self::GridBackground];
+        return &[
+            Self::Sun,
+            Self::GridBackground,
+            Self::Plant,
+            Self::Bridges,
+            Self::Border,
+            Self::Transfers,
+        ];
     }
 
     /// Constructs a list of the color maps for all the instance types
@@ -226,18 +384,59 @@ impl InstanceType {
     /// sun: The color map for the sun
     ///
     /// background: The color map for all modes of the background of the grid
+    ///
+    /// plant: The color map for all modes of the plants
+    ///
+    /// bridge: The color map for the bridges
+    ///
+    /// border: The color map for the outlines of the tiles
+    ///
+    /// transfer: The color map for the energy transfer arrows
     pub fn new_color_map_collection(
         sun: Box<dyn types::ColorMap>,
         background: [Box<dyn types::ColorMap>; map::DataModeBackground::COUNT],
+        plant: [Box<dyn types::ColorMap>; map::DataModePlant::COUNT],
+        bridge: Box<dyn types::ColorMap>,
+        border: Box<dyn types::ColorMap>,
+        transfer: Box<dyn types::ColorMap>,
     ) -> [Vec<Box<dyn types::ColorMap>>; Self::COUNT] {
-        return [vec![sun], background.into()];
+        return [
+            vec![sun],
+            background.into(),
+            plant.into(),
+            vec![bridge],
+            vec![border],
+            vec![transfer],
+        ];
+    }
+
+    /// Constructs a list of the color scalings for all modes of all the
+    /// instance types where every mode uses the same scaling
+    ///
+    /// # Parameters
+    ///
+    /// scaling: The scaling to use for all modes
+    pub fn new_color_scaling_collection(
+        scaling: types::ColorScaling,
+    ) -> [Vec<types::ColorScaling>; Self::COUNT] {
+        return [
+            vec![scaling],
+            vec![scaling; map::DataModeBackground::COUNT],
+            vec![scaling; map::DataModePlant::COUNT],
+            vec![scaling],
+            vec![scaling],
+            vec![scaling],
+        ];
     }
 
     /// Gets the primitive type used for this instance
     pub(super) fn primitive(&self) -> PrimitiveType {
         return match self {
             Self::Sun => PrimitiveType::Rectangle,
-            Self::GridBackground => PrimitiveType::Hexagon,
+            Self::GridBackground | Self::Plant => PrimitiveType::Hexagon,
+            Self::Bridges => PrimitiveType::BridgeSegment,
+            Self::Border => PrimitiveType::HexagonBorder,
+            Self::Transfers => PrimitiveType::Arrow,
         };
     }
 
@@ -250,15 +449,18 @@ impl InstanceType {
     /// render_state: The render state to use for rendering
     ///
     /// transform: The transform to apply to all vertices going from world coordinates to screen coordinates
+    ///
+    /// slot: The transform slot to write to, see UniformsInstance::TRANSFORM_SLOTS
     pub(super) fn write_transform(
         &self,
         collection: &[(BufferInstance, UniformsInstance); Self::COUNT],
         render_state: &render::RenderState,
         transform: &types::Transform2D,
+        slot: usize,
     ) {
         collection[self.id()]
             .1
-            .write_transform(render_state, transform);
+            .write_transform(render_state, transform, slot);
     }
 
     /// Update the grid layout, this must be run once before the first rendering as it is not initialized
@@ -274,7 +476,7 @@ impl InstanceType {
         &self,
         collection: &[(BufferInstance, UniformsInstance); Self::COUNT],
         render_state: &render::RenderState,
-        grid_layout: &map::GridLayout,
+        grid_layout: &map::UniformGridLayout,
     ) {
         collection[self.id()]
             .1
@@ -293,13 +495,25 @@ impl InstanceType {
     pub(super) fn write_grid_layout_collection(
         collection: &[(BufferInstance, UniformsInstance); Self::COUNT],
         render_state: &render::RenderState,
-        grid_layout: &map::GridLayout,
+        grid_layout: &map::UniformGridLayout,
     ) {
         for instance in Self::all_instances().iter() {
             instance.write_grid_layout(collection, render_state, grid_layout);
         }
     }
 
+    /// Gets the number of instances in the buffer of this instance type
+    ///
+    /// # Parameters
+    ///
+    /// collection: The full collection of instances
+    pub(super) fn get_count(
+        &self,
+        collection: &[(BufferInstance, UniformsInstance); Self::COUNT],
+    ) -> u32 {
+        return collection[self.id()].0.count;
+    }
+
     /// Sets the correct instance from the collection, returns the number of instance elements set
     ///
     /// # Parameters
@@ -307,16 +521,50 @@ impl InstanceType {
     /// collection: The full collection of instances
     ///
     /// render_pass: The render pass to draw to
+    ///
+    /// slot: The transform slot to bind
     pub(super) fn set<'a>(
         &self,
         collection: &'a [(BufferInstance, UniformsInstance); Self::COUNT],
         render_pass: &mut wgpu::RenderPass<'a>,
+        slot: usize,
     ) -> u32 {
-        collection[self.id()].1.set(render_pass);
+        collection[self.id()].1.set(render_pass, slot);
         return collection[self.id()].0.set(render_pass);
     }
 }
 
+/// The data for a single instance buffer
+#[derive(Clone, Debug)]
+pub(super) enum InstanceData {
+    /// Data with one instance per tile
+    Tile(Vec<map::InstanceTile>),
+    /// Data with one instance per direction of every tile
+    Bridge(Vec<map::InstanceBridge>),
+    /// Data with one instance per direction of every tile
+    Transfer(Vec<map::InstanceTransfer>),
+}
+
+impl InstanceData {
+    /// Gets the raw bytes of the data
+    fn get_bytes(&self) -> &[u8] {
+        return match self {
+            Self::Tile(data) => bytemuck::cast_slice(data),
+            Self::Bridge(data) => bytemuck::cast_slice(data),
+            Self::Transfer(data) => bytemuck::cast_slice(data),
+        };
+    }
+
+    /// Gets the number of instances
+    fn len(&self) -> usize {
+        return match self {
+            Self::Tile(data) => data.len(),
+            Self::Bridge(data) => data.len(),
+            Self::Transfer(data) => data.len(),
+        };
+    }
+}
+
 /// Holds GPU buffers for one type of instance data
 #[derive(Debug)]
 pub(super) struct BufferInstance {
@@ -334,14 +582,14 @@ impl BufferInstance {
     /// render_state: The render state to use for rendering
     ///
     /// data: The data to initialize the buffer with which also defines the length
-    fn new(render_state: &render::RenderState, data: &[map::InstanceTile]) -> Self {
+    fn new(render_state: &render::RenderState, data: &InstanceData) -> Self {
         // Create the instance buffer
         let buffer =
             render_state
                 .get_device()
                 .create_buffer_init(&wgpu::util::BufferInitDescriptor {
                     label: Some("Instance Buffer"),
-                    contents: bytemuck::cast_slice(data),
+                    contents: data.get_bytes(),
                     usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
                 });
 
@@ -358,10 +606,57 @@ impl BufferInstance {
     /// render_state: The render state to use for rendering
     ///
     /// data: The data to set
-    fn update(&self, render_state: &render::RenderState, data: &[map::InstanceTile]) {
+    fn update(&self, render_state: &render::RenderState, data: &InstanceData) {
         render_state
             .get_queue()
-            .write_buffer(&self.buffer, 0, bytemuck::cast_slice(data));
+            .write_buffer(&self.buffer, 0, data.get_bytes());
+    }
+
+    /// Updates the instances in the given ranges of the buffer
+    ///
+    /// # Parameters
+    ///
+    /// render_state: The render state to use for rendering
+    ///
+    /// data: The data for all instances
+    ///
+    /// ranges: The ranges of instances to update
+    fn update_ranges(
+        &self,
+        render_state: &render::RenderState,
+        data: &InstanceData,
+        ranges: &[Range<usize>],
+    ) {
+        for range in ranges.iter() {
+            self.update_range(render_state, data, range);
+        }
+    }
+
+    /// Updates the instances in a single range of the buffer
+    ///
+    /// # Parameters
+    ///
+    /// render_state: The render state to use for rendering
+    ///
+    /// data: The data for all instances
+    ///
+    /// range: The range of instances to update
+    fn update_range(
+        &self,
+        render_state: &render::RenderState,
+        data: &InstanceData,
+        range: &Range<usize>,
+    ) {
+        if data.len() == 0 {
+            return;
+        }
+        let bytes = data.get_bytes();
+        let stride = bytes.len() / data.len();
+        render_state.get_queue().write_buffer(
+            &self.buffer,
+            (range.start * stride) as wgpu::BufferAddress,
+            &bytes[range.start * stride..range.end * stride],
+        );
     }
 
     /// Sets the tile instance information for the given render pass
@@ -382,8 +677,11 @@ impl BufferInstance {
 /// Holds all of the uniforms for a single instance type
 #[derive(Debug)]
 pub(super) struct UniformsInstance {
-    /// The buffer for the world to screen coordinates transform
+    /// The buffer for the world to screen coordinates transforms, one for
+    /// each slot
     transform: wgpu::Buffer,
+    /// The number of bytes between two transform slots
+    transform_stride: u32,
     /// The buffer for the color map data
     color_map: wgpu::Buffer,
     /// The buffer for the grid layout data
@@ -393,18 +691,30 @@ pub(super) struct UniformsInstance {
 }
 
 impl UniformsInstance {
+    /// The number of transforms which can be used in a single frame, one
+    /// for each repeat of the map
+    pub(super) const TRANSFORM_SLOTS: usize = 3;
+
     /// Creates a new set of uniforms for the gpu
     ///
     /// # Parameters
     ///
     /// render_state: The render state to use for rendering
-    fn new(render_state: &render::RenderState) -> Self {
-        // Create transform buffer
+    pub(super) fn new(render_state: &render::RenderState) -> Self {
+        // Create transform buffer, the slots must be aligned for dynamic
+        // offsets
+        let transform_size = std::mem::size_of::<types::UniformTransform2D>() as u32;
+        let transform_stride = transform_size.next_multiple_of(
+            render_state
+                .get_device()
+                .limits()
+                .min_uniform_buffer_offset_alignment,
+        );
         let transform = render_state
             .get_device()
             .create_buffer(&wgpu::BufferDescriptor {
                 label: Some("Transform Uniform"),
-                size: std::mem::size_of::<types::UniformTransform2D>() as u64,
+                size: (transform_stride as usize * Self::TRANSFORM_SLOTS) as u64,
                 usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
                 mapped_at_creation: false,
             });
@@ -438,7 +748,11 @@ impl UniformsInstance {
                 entries: &[
                     wgpu::BindGroupEntry {
                         binding: 0,
-                        resource: transform.as_entire_binding(),
+                        resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                            buffer: &transform,
+                            offset: 0,
+                            size: wgpu::BufferSize::new(transform_size as u64),
+                        }),
                     },
                     wgpu::BindGroupEntry {
                         binding: 1,
@@ -453,6 +767,7 @@ impl UniformsInstance {
 
         return Self {
             transform,
+            transform_stride,
             color_map,
             grid_layout,
             bind_group,
@@ -466,10 +781,17 @@ impl UniformsInstance {
     /// render_state: The render state to use for rendering
     ///
     /// transform: The transform to apply to all vertices going from world coordinates to screen coordinates
-    fn write_transform(&self, render_state: &render::RenderState, transform: &types::Transform2D) {
+    ///
+    /// slot: The transform slot to write to
+    pub(super) fn write_transform(
+        &self,
+        render_state: &render::RenderState,
+        transform: &types::Transform2D,
+        slot: usize,
+    ) {
         render_state.get_queue().write_buffer(
             &self.transform,
-            0,
+            (slot * self.transform_stride as usize) as u64,
             bytemuck::cast_slice(&[transform.get_data()]),
         );
     }
@@ -481,11 +803,22 @@ impl UniformsInstance {
     /// render_state: The render state to use for rendering
     ///
     /// color_map: The data for the color map
-    fn write_color_map(&self, render_state: &render::RenderState, color_map: &dyn types::ColorMap) {
+    ///
+    /// scaling: The scaling applied to the values before the color is looked
+    /// up, only used by continuous color maps
+    ///
+    /// exponent: The exponent used by the exponent scaling
+    pub(super) fn write_color_map(
+        &self,
+        render_state: &render::RenderState,
+        color_map: &dyn types::ColorMap,
+        scaling: types::ColorScaling,
+        exponent: f64,
+    ) {
         render_state.get_queue().write_buffer(
             &self.color_map,
             0,
-            bytemuck::cast_slice(&[color_map.get_data()]),
+            bytemuck::cast_slice(&[color_map.get_data().with_scaling(scaling, exponent)]),
         );
     }
 
@@ -496,11 +829,15 @@ impl UniformsInstance {
     /// render_state: The render state to use for rendering
     ///
     /// grid_layout: The grid layout to write
-    fn write_grid_layout(&self, render_state: &render::RenderState, grid_layout: &map::GridLayout) {
+    pub(super) fn write_grid_layout(
+        &self,
+        render_state: &render::RenderState,
+        grid_layout: &map::UniformGridLayout,
+    ) {
         render_state.get_queue().write_buffer(
             &self.grid_layout,
             0,
-            bytemuck::cast_slice(&[grid_layout.get_data()]),
+            bytemuck::cast_slice(&[*grid_layout]),
         );
     }
 
@@ -509,8 +846,10 @@ impl UniformsInstance {
     /// # Parameters
     ///
     /// render_pass: The render pass to draw to
-    fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
-        render_pass.set_bind_group(0, &self.bind_group, &[]);
+    ///
+    /// slot: The transform slot to bind
+    pub(super) fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>, slot: usize) {
+        render_pass.set_bind_group(0, &self.bind_group, &[slot as u32 * self.transform_stride]);
     }
 
     /// Creates the bind group layout for a set of uniforms
@@ -528,7 +867,7 @@ impl UniformsInstance {
                         visibility: wgpu::ShaderStages::VERTEX,
                         ty: wgpu::BindingType::Buffer {
                             ty: wgpu::BufferBindingType::Uniform,
-                            has_dynamic_offset: false,
+                            has_dynamic_offset: true,
                             min_binding_size: None,
                         },
                         count: None,
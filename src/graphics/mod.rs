@@ -6,8 +6,11 @@ pub use settings::Settings;
 mod state;
 pub use state::State;
 
+mod thumbnail;
+pub use thumbnail::{Thumbnail, ThumbnailError};
+
 mod pipeline;
-use pipeline::{PipelineType, Pipeline};
+use pipeline::{Pipeline, PipelineType};
 
 mod primitive;
 use primitive::{BufferVertices, PrimitiveType};
@@ -16,6 +19,14 @@ mod instance;
 use instance::{BufferInstance, UniformsInstance};
 pub use instance::{InstanceMode, InstanceType};
 
+mod culling;
+
+mod highlight;
+use highlight::{Highlight, InstanceHighlight};
+pub use highlight::HighlightType;
+
+pub mod compute;
+
 /// Describes a single vertex in the gpu
 #[repr(C)]
 #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
@@ -27,8 +38,12 @@ pub struct Vertex {
 impl Vertex {
     const _COUNT_VERTEX_HEXAGON: usize = 6;
     const _COUNT_VERTEX_RECTANGLE: usize = 4;
+    const _COUNT_VERTEX_BRIDGE_SEGMENT: usize = 4;
+    const _COUNT_VERTEX_ARROW: usize = 7;
     const _COUNT_INDEX_BULK_HEXAGON: usize = 12;
     const _COUNT_INDEX_BULK_RECTANGLE: usize = 6;
+    const _COUNT_INDEX_BORDER_HEXAGON: usize = 12;
+    const _COUNT_INDEX_BULK_ARROW: usize = 9;
 
     /// Gets the memory description of a tile vertex
     fn desc() -> wgpu::VertexBufferLayout<'static> {
@@ -85,6 +100,54 @@ impl Vertex {
         ];
     }
 
+    /// Generates the vertices for a bridge segment, a unit width segment
+    /// pointing to the right from the center of a tile to the edge of the tile
+    const fn vertices_bridge_segment() -> &'static [Self] {
+        return &[
+            Self {
+                position: [0.5, 0.5],
+            },
+            Self {
+                position: [0.0, 0.5],
+            },
+            Self {
+                position: [0.0, -0.5],
+            },
+            Self {
+                position: [0.5, -0.5],
+            },
+        ];
+    }
+
+    /// Generates the vertices for an arrow inside a bridge segment pointing
+    /// to the right from near the center of a tile towards the edge of the
+    /// tile, its center is halfway between the center and the edge
+    const fn vertices_arrow() -> &'static [Self] {
+        return &[
+            Self {
+                position: [0.45, 0.0],
+            },
+            Self {
+                position: [0.3, 0.12],
+            },
+            Self {
+                position: [0.3, 0.04],
+            },
+            Self {
+                position: [0.05, 0.04],
+            },
+            Self {
+                position: [0.05, -0.04],
+            },
+            Self {
+                position: [0.3, -0.04],
+            },
+            Self {
+                position: [0.3, -0.12],
+            },
+        ];
+    }
+
     /// Generates indices for the vertices for the bulk of a hexagon
     const fn indices_bulk_hexagon() -> &'static [u16] {
         return &[0, 1, 2, 2, 3, 0, 3, 5, 0, 3, 4, 5];
@@ -94,4 +157,15 @@ impl Vertex {
     const fn indices_bulk_rectangle() -> &'static [u16] {
         return &[0, 1, 2, 2, 3, 0];
     }
+
+    /// Generates indices for the vertices for the bulk of an arrow
+    const fn indices_bulk_arrow() -> &'static [u16] {
+        return &[0, 1, 6, 2, 3, 4, 4, 5, 2];
+    }
+
+    /// Generates indices for the vertices for the border of a hexagon as a
+    /// list of lines
+    const fn indices_border_hexagon() -> &'static [u16] {
+        return &[0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 0];
+    }
 }
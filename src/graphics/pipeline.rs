@@ -1,28 +1,48 @@
 use crate::{map, render};
 
-use super::{UniformsInstance, Vertex};
+use super::{InstanceHighlight, UniformsInstance, Vertex};
 
 /// Describes which pipeline to use
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(super) enum PipelineType {
     /// All object are rendered with a uniform color from a color map
     Unicolor,
+    /// Bridges between tiles rendered with a uniform color from a color map
+    Bridge,
+    /// Outlines of the tiles rendered as lines with a uniform color from a
+    /// color map
+    Border,
+    /// Highlighted tiles tinted with a uniform color from a color map
+    Highlight,
+    /// Arrows along the bridges between tiles rendered with a uniform color
+    /// from a color map
+    Transfer,
 }
 
 impl PipelineType {
     /// The number of different pipelines
-    pub(super) const COUNT: usize = 1;
+    pub(super) const COUNT: usize = 5;
 
     /// The id to find the pipeline in the pipeline list
     pub(super) fn id(&self) -> usize {
         return match self {
             Self::Unicolor => 0,
+            Self::Bridge => 1,
+            Self::Border => 2,
+            Self::Highlight => 3,
+            Self::Transfer => 4,
         };
     }
 
     /// Gets a list of all the different pipelines
     pub(super) const fn all_pipelines() -> &'static [Self; Self::COUNT] {
-        return &[Self::Unicolor];
+        return &[
+            Self::Unicolor,
+            Self::Bridge,
+            Self::Border,
+            Self::Highlight,
+            Self::Transfer,
+        ];
     }
 
     /// Constructs a new pipeline matching the pipeline type
@@ -31,11 +51,35 @@ impl PipelineType {
     ///
     /// render_state: The render state to use for rendering
     pub(super) fn new(&self, render_state: &render::RenderState) -> Pipeline {
-        let shader = match self {
-            Self::Unicolor => wgpu::include_wgsl!("../shaders/unicolor.wgsl"),
+        let (shader, instance, topology) = match self {
+            Self::Unicolor => (
+                wgpu::include_wgsl!("../shaders/unicolor.wgsl"),
+                map::InstanceTile::desc(),
+                wgpu::PrimitiveTopology::TriangleList,
+            ),
+            Self::Bridge => (
+                wgpu::include_wgsl!("../shaders/bridge.wgsl"),
+                map::InstanceBridge::desc(),
+                wgpu::PrimitiveTopology::TriangleList,
+            ),
+            Self::Border => (
+                wgpu::include_wgsl!("../shaders/unicolor.wgsl"),
+                map::InstanceTile::desc(),
+                wgpu::PrimitiveTopology::LineList,
+            ),
+            Self::Highlight => (
+                wgpu::include_wgsl!("../shaders/highlight.wgsl"),
+                InstanceHighlight::desc(),
+                wgpu::PrimitiveTopology::TriangleList,
+            ),
+            Self::Transfer => (
+                wgpu::include_wgsl!("../shaders/transfer.wgsl"),
+                map::InstanceTransfer::desc(),
+                wgpu::PrimitiveTopology::TriangleList,
+            ),
         };
 
-        return Pipeline::new(render_state, shader);
+        return Pipeline::new(render_state, shader, instance, topology);
     }
 
     /// Constructs the pipelines for all the different pipeline type
@@ -83,7 +127,16 @@ impl Pipeline {
     /// render_state: The render state to use for rendering
     ///
     /// shader: The shader descriptor
-    fn new(render_state: &render::RenderState, shader: wgpu::ShaderModuleDescriptor) -> Self {
+    ///
+    /// instance: The memory description of the instance data
+    ///
+    /// topology: The way the vertices are assembled into primitives
+    fn new(
+        render_state: &render::RenderState,
+        shader: wgpu::ShaderModuleDescriptor,
+        instance: wgpu::VertexBufferLayout,
+        topology: wgpu::PrimitiveTopology,
+    ) -> Self {
         // Create the shader
         let shader = render_state.get_device().create_shader_module(shader);
 
@@ -108,7 +161,7 @@ impl Pipeline {
                         module: &shader,
                         entry_point: Some("vs_main"),
                         compilation_options: wgpu::PipelineCompilationOptions::default(),
-                        buffers: &[Vertex::desc(), map::InstanceTile::desc()],
+                        buffers: &[Vertex::desc(), instance],
                     },
                     fragment: Some(wgpu::FragmentState {
                         module: &shader,
@@ -116,12 +169,12 @@ impl Pipeline {
                         compilation_options: wgpu::PipelineCompilationOptions::default(),
                         targets: &[Some(wgpu::ColorTargetState {
                             format: render_state.get_config().format,
-                            blend: Some(wgpu::BlendState::REPLACE),
+                            blend: Some(wgpu::BlendState::ALPHA_BLENDING),
                             write_mask: wgpu::ColorWrites::ALL,
                         })],
                     }),
                     primitive: wgpu::PrimitiveState {
-                        topology: wgpu::PrimitiveTopology::TriangleList,
+                        topology,
                         strip_index_format: None,
                         front_face: wgpu::FrontFace::Ccw,
                         cull_mode: Some(wgpu::Face::Back),
@@ -131,7 +184,7 @@ impl Pipeline {
                     },
                     depth_stencil: None,
                     multisample: wgpu::MultisampleState {
-                        count: 1,
+                        count: render_state.get_sample_count(),
                         mask: !0,
                         alpha_to_coverage_enabled: false,
                     },
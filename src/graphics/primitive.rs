@@ -11,23 +11,39 @@ pub(super) enum PrimitiveType {
     Hexagon,
     /// Draw rectangles
     Rectangle,
+    /// Draw thin segments from the center of a hexagon to its edge
+    BridgeSegment,
+    /// Draw the outline of hexagons as lines
+    HexagonBorder,
+    /// Draw arrows inside the segments from the center of a hexagon to its
+    /// edge
+    Arrow,
 }
 
 impl PrimitiveType {
     /// The number of different primitives
-    pub(super) const COUNT: usize = 2;
+    pub(super) const COUNT: usize = 5;
 
     /// The id to find the primitive in the buffer list
     pub(super) fn id(&self) -> usize {
         return match self {
             Self::Hexagon => 0,
             Self::Rectangle => 1,
+            Self::BridgeSegment => 2,
+            Self::HexagonBorder => 3,
+            Self::Arrow => 4,
         };
     }
 
     /// Gets a list of all the different primitives
     pub(super) const fn all_primitives() -> &'static [Self; Self::COUNT] {
-        return &[Self::Hexagon, Self::Rectangle];
+        return &[
+            Self::Hexagon,
+            Self::Rectangle,
+            Self::BridgeSegment,
+            Self::HexagonBorder,
+            Self::Arrow,
+        ];
     }
 
     /// Constructs a new pipeline matching the pipeline type
@@ -42,6 +58,12 @@ impl PrimitiveType {
                 Vertex::vertices_rectangle(),
                 Vertex::indices_bulk_rectangle(),
             ),
+            Self::BridgeSegment => (
+                Vertex::vertices_bridge_segment(),
+                Vertex::indices_bulk_rectangle(),
+            ),
+            Self::HexagonBorder => (Vertex::vertices_hexagon(), Vertex::indices_border_hexagon()),
+            Self::Arrow => (Vertex::vertices_arrow(), Vertex::indices_bulk_arrow()),
         };
 
         return BufferVertices::new(render_state, vertices, bulk_indices);
@@ -1,4 +1,4 @@
-use super::InstanceType;
+use super::{InstanceMode, InstanceType};
 use crate::{map, types};
 
 /// All non-gpu settings for rendering
@@ -8,8 +8,27 @@ pub struct Settings {
     pub color_clear: types::Color,
     /// The color maps for all the instance types
     pub color_maps: [Vec<Box<dyn types::ColorMap>>; InstanceType::COUNT],
+    /// The scalings applied to the values of continuous color maps for all
+    /// modes of all the instance types
+    pub color_scalings: [Vec<types::ColorScaling>; InstanceType::COUNT],
+    /// The exponent used by the exponent scaling
+    pub scaling_exponent: f64,
+    /// The color map for the highlighted tiles with a color for each
+    /// highlight type
+    pub color_map_highlight: Box<dyn types::ColorMap>,
     /// The display mode for the background
     pub mode_background: map::DataModeBackground,
+    /// The display mode for the plants
+    pub mode_plant: map::DataModePlant,
+    /// The number of samples per pixel for anti-aliasing, 1 disables it. It
+    /// is only used when the window is created and is lowered to the nearest
+    /// count supported by the gpu
+    pub sample_count: u32,
+    /// If true then the outlines of all tiles are drawn on top of the map
+    pub show_borders: bool,
+    /// If true then arrows showing the energy transfer through the bridges
+    /// during the last step are drawn on top of the bridges
+    pub show_transfers: bool,
 }
 
 impl Settings {
@@ -35,6 +54,51 @@ impl Settings {
         return self;
     }
 
+    /// Sets the plant display mode of the settings and returns it
+    ///
+    /// # Parameters
+    ///
+    /// mode: The mode to set
+    pub fn with_mode_plant(mut self, mode: map::DataModePlant) -> Self {
+        self.mode_plant = mode;
+
+        return self;
+    }
+
+    /// Sets the number of samples per pixel for anti-aliasing of the
+    /// settings and returns it
+    ///
+    /// # Parameters
+    ///
+    /// sample_count: The number of samples to set
+    pub fn with_sample_count(mut self, sample_count: u32) -> Self {
+        self.sample_count = sample_count;
+
+        return self;
+    }
+
+    /// Sets if the outlines of the tiles are shown and returns the settings
+    ///
+    /// # Parameters
+    ///
+    /// show_borders: True if the outlines should be shown
+    pub fn with_show_borders(mut self, show_borders: bool) -> Self {
+        self.show_borders = show_borders;
+
+        return self;
+    }
+
+    /// Sets if the energy transfer arrows are shown and returns the settings
+    ///
+    /// # Parameters
+    ///
+    /// show_transfers: True if the arrows should be shown
+    pub fn with_show_transfers(mut self, show_transfers: bool) -> Self {
+        self.show_transfers = show_transfers;
+
+        return self;
+    }
+
     /// Sets one of the color maps of the settings and returns it
     ///
     /// # Parameters
@@ -51,4 +115,43 @@ impl Settings {
 
         return self;
     }
+
+    /// Sets the color scaling of a single instance mode of the settings and
+    /// returns it
+    ///
+    /// # Parameters
+    ///
+    /// scaling: The scaling to set
+    ///
+    /// instance: The instance mode to set the scaling for
+    pub fn with_color_scaling(
+        mut self,
+        scaling: types::ColorScaling,
+        instance: &InstanceMode,
+    ) -> Self {
+        self.color_scalings[instance.id()][instance.mode_id()] = scaling;
+
+        return self;
+    }
+
+    /// Sets the exponent used by the exponent scaling and returns the
+    /// settings
+    ///
+    /// # Parameters
+    ///
+    /// exponent: The exponent to set
+    pub fn with_scaling_exponent(mut self, exponent: f64) -> Self {
+        self.scaling_exponent = exponent;
+
+        return self;
+    }
+
+    /// Retrieves the color scaling of a single instance mode
+    ///
+    /// # Parameters
+    ///
+    /// instance: The instance mode to get the scaling for
+    pub fn get_color_scaling(&self, instance: &InstanceMode) -> types::ColorScaling {
+        return self.color_scalings[instance.id()][instance.mode_id()];
+    }
 }
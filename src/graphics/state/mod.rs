@@ -1,11 +1,17 @@
 use super::{
-    BufferInstance, BufferVertices, InstanceMode, InstanceType, Pipeline, PipelineType,
-    PrimitiveType, Settings, UniformsInstance,
+    BufferInstance, BufferVertices, Highlight, HighlightType, InstanceMode, InstanceType, Pipeline,
+    PipelineType, PrimitiveType, Settings, Thumbnail, ThumbnailError, UniformsInstance,
 };
-use crate::{map, render};
+use crate::{map, render, types};
 
 mod state_render;
 
+mod state_thumbnail;
+
+mod state_capture;
+
+mod state_overlay;
+
 /// A complete state for rendering
 pub struct State {
     /// All of the settings for rendering
@@ -16,6 +22,12 @@ pub struct State {
     primitives: [BufferVertices; PrimitiveType::COUNT],
     /// All instance data both buffers and uniforms
     instances: [(BufferInstance, UniformsInstance); InstanceType::COUNT],
+    /// The layer tinting the highlighted tiles
+    highlight: Highlight,
+    /// The renderer for the egui overlay
+    overlay: egui_wgpu::Renderer,
+    /// The layout of the grid, used for finding the visible tiles
+    grid_layout: Box<dyn map::GridLayout>,
 }
 
 impl State {
@@ -40,13 +52,31 @@ impl State {
         let primitives = PrimitiveType::new_collection(render_state);
 
         // Create the instance buffers and uniforms
-        let instances = InstanceMode::new_collection(render_state, map, settings.mode_background);
+        let instances = InstanceMode::new_collection(
+            render_state,
+            map,
+            settings.mode_background,
+            settings.mode_plant,
+        );
+
+        // Create the highlight layer without any highlighted tiles
+        let highlight = Highlight::new(render_state);
+
+        // Create the overlay renderer drawing directly onto the surface
+        let overlay = egui_wgpu::Renderer::new(
+            render_state.get_device(),
+            render_state.get_config().format,
+            egui_wgpu::RendererOptions::default(),
+        );
 
         let mut object = Self {
             settings,
             pipelines,
             primitives,
             instances,
+            highlight,
+            overlay,
+            grid_layout: Box::new(map.get_grid_layout()),
         };
         object.settings_changed(render_state);
 
@@ -80,8 +110,13 @@ impl State {
             &self.instances,
             render_state,
             &self.settings.color_maps,
+            &self.settings.color_scalings,
+            self.settings.scaling_exponent,
             self.settings.mode_background,
+            self.settings.mode_plant,
         );
+        self.highlight
+            .write_color_map(render_state, self.settings.color_map_highlight.as_ref());
     }
 
     /// Sets the grid layout
@@ -92,11 +127,73 @@ impl State {
     ///
     /// grid_layout: The grid layout to set
     pub fn set_grid_layout(
+        &mut self,
+        render_state: &render::RenderState,
+        grid_layout: Box<dyn map::GridLayout>,
+    ) {
+        InstanceType::write_grid_layout_collection(
+            &self.instances,
+            render_state,
+            &grid_layout.get_data(),
+        );
+        self.highlight
+            .write_grid_layout(render_state, &grid_layout.get_data());
+        self.grid_layout = grid_layout;
+    }
+
+    /// Sets the highlighted tiles, at most one tile may be given for each
+    /// highlight type and tiles outside the grid are ignored
+    ///
+    /// # Parameters
+    ///
+    /// render_state: The render state to use for rendering
+    ///
+    /// tiles: The position of every highlighted tile along with the type of
+    /// the highlight
+    pub fn set_highlight(
+        &mut self,
+        render_state: &render::RenderState,
+        tiles: &[(types::Index, HighlightType)],
+    ) {
+        let n_columns = self.grid_layout.get_n_columns() as isize;
+        let n_tiles = InstanceType::GridBackground.get_count(&self.instances) as isize;
+        let tiles = tiles
+            .iter()
+            .filter_map(|(pos, highlight)| {
+                let index = pos.y * n_columns + pos.x;
+                if pos.x < 0 || pos.x >= n_columns || index < 0 || index >= n_tiles {
+                    return None;
+                }
+                return Some((index as usize, *highlight));
+            })
+            .collect::<Vec<_>>();
+
+        self.highlight.write(render_state, &tiles);
+    }
+
+    /// Updates the map data of all tiles which have changed since the dirty
+    /// tiles of the map were last cleared, the caller must clear them
+    /// afterwards
+    ///
+    /// # Parameters
+    ///
+    /// render_state: The render state to use for rendering
+    ///
+    /// map: The map to use for the update
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub fn update_map_dirty<S: map::sun::Intensity>(
         &self,
         render_state: &render::RenderState,
-        grid_layout: &map::GridLayout,
+        map: &map::Map<S>,
     ) {
-        InstanceType::write_grid_layout_collection(&self.instances, render_state, grid_layout);
+        InstanceMode::update_ranges_collection(
+            &self.instances,
+            render_state,
+            map,
+            &map.get_dirty_ranges(),
+            self.settings.mode_background,
+            self.settings.mode_plant,
+        );
     }
 
     /// Updates the map data
@@ -106,6 +203,7 @@ impl State {
     /// render_state: The render state to use for rendering
     ///
     /// map: The map to use for the update
+    #[tracing::instrument(level = "debug", skip_all)]
     pub fn update_map<S: map::sun::Intensity>(
         &self,
         render_state: &render::RenderState,
@@ -116,6 +214,7 @@ impl State {
             render_state,
             map,
             self.settings.mode_background,
+            self.settings.mode_plant,
         );
     }
 }
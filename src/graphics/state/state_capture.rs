@@ -0,0 +1,148 @@
+use std::sync::mpsc;
+
+use crate::{render, types};
+
+use super::{State, Thumbnail, ThumbnailError};
+
+impl State {
+    /// Renders the map into an offscreen texture and reads it back as an
+    /// image
+    ///
+    /// # Parameters
+    ///
+    /// render_state: The render state to use for rendering
+    ///
+    /// transform: The transform to go from world to image coordinates
+    ///
+    /// map_width: The width of the map in world coordinates
+    ///
+    /// width: The width of the image in pixels
+    ///
+    /// height: The height of the image in pixels
+    ///
+    /// # Errors
+    ///
+    /// See ThumbnailError for a description of the different errors which may occur
+    pub fn render_capture(
+        &self,
+        render_state: &render::RenderState,
+        transform: &types::Transform2D,
+        map_width: f64,
+        width: u32,
+        height: u32,
+    ) -> Result<Thumbnail, ThumbnailError> {
+        if width == 0 || height == 0 {
+            return Err(ThumbnailError::InvalidSize(width, height));
+        }
+
+        // Only 8 bit color formats can be read back directly
+        let format = render_state.get_config().format;
+        let swap_red_blue = match format {
+            wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => false,
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => true,
+            _ => return Err(ThumbnailError::UnsupportedFormat(format)),
+        };
+
+        // Render to a texture compatible with the pipelines
+        let texture = render_state.create_capture_texture(width, height);
+        let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let msaa_view = render_state.create_msaa_view(width, height);
+        let mut encoder = self.create_encoder(render_state);
+        self.render_map(
+            render_state,
+            &mut encoder,
+            &texture_view,
+            msaa_view.as_ref(),
+            transform,
+            map_width,
+        );
+
+        // Copy the texture into a buffer, rows must be aligned
+        let bytes_per_row = (4 * width).div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+            * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let buffer = render_state
+            .get_device()
+            .create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Buffer: Capture"),
+                size: (bytes_per_row * height) as wgpu::BufferAddress,
+                usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+                mapped_at_creation: false,
+            });
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            texture.size(),
+        );
+        render_state
+            .get_queue()
+            .submit(std::iter::once(encoder.finish()));
+
+        // Wait for the buffer to be readable
+        let slice = buffer.slice(..);
+        let (sender, receiver) = mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            _ = sender.send(result);
+        });
+        render_state
+            .get_device()
+            .poll(wgpu::PollType::wait_indefinitely())?;
+        receiver.recv().unwrap_or(Err(wgpu::BufferAsyncError))?;
+
+        // Remove the row padding and convert to RGBA
+        let pixels = {
+            let data = slice.get_mapped_range();
+            data.chunks(bytes_per_row as usize)
+                .flat_map(|row| row[..4 * width as usize].chunks(4))
+                .flat_map(|pixel| {
+                    if swap_red_blue {
+                        [pixel[2], pixel[1], pixel[0], pixel[3]]
+                    } else {
+                        [pixel[0], pixel[1], pixel[2], pixel[3]]
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+        buffer.unmap();
+
+        return Ok(Thumbnail::new(width, height, pixels));
+    }
+
+    /// Renders the map as it is shown in the window, without the overlay, and
+    /// reads it back as an image with the size of the window
+    ///
+    /// # Parameters
+    ///
+    /// render_state: The render state to use for rendering
+    ///
+    /// transform: The transform of the camera to go from world to screen
+    /// coordinates
+    ///
+    /// map_width: The width of the map in world coordinates
+    ///
+    /// # Errors
+    ///
+    /// See ThumbnailError for a description of the different errors which may occur
+    pub fn render_screenshot(
+        &self,
+        render_state: &render::RenderState,
+        transform: &types::Transform2D,
+        map_width: f64,
+    ) -> Result<Thumbnail, ThumbnailError> {
+        let config = render_state.get_config();
+
+        return self.render_capture(
+            render_state,
+            transform,
+            map_width,
+            config.width,
+            config.height,
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use crate::render;
+
+use super::State;
+
+impl State {
+    /// Records the rendering of the egui overlay on top of whatever has
+    /// already been recorded for the view, returns the command buffers
+    /// uploading the shapes which must be submitted before the encoder
+    ///
+    /// # Parameters
+    ///
+    /// render_state: The render state to use for rendering
+    ///
+    /// encoder: The command encoder of the frame to record into
+    ///
+    /// view: The texture view to render to
+    ///
+    /// primitives: The tessellated shapes of the overlay
+    ///
+    /// textures_delta: The changes to the textures used by the overlay
+    ///
+    /// pixels_per_point: The number of pixels for each egui point
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub fn render_overlay(
+        &mut self,
+        render_state: &render::RenderState,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        primitives: &[egui::ClippedPrimitive],
+        textures_delta: &egui::TexturesDelta,
+        pixels_per_point: f32,
+    ) -> Vec<wgpu::CommandBuffer> {
+        // Upload all new textures like the font atlas
+        for (id, delta) in textures_delta.set.iter() {
+            self.overlay.update_texture(
+                render_state.get_device(),
+                render_state.get_queue(),
+                *id,
+                delta,
+            );
+        }
+
+        // Upload the shapes
+        let screen_descriptor = egui_wgpu::ScreenDescriptor {
+            size_in_pixels: [
+                render_state.get_config().width,
+                render_state.get_config().height,
+            ],
+            pixels_per_point,
+        };
+        let command_buffers = self.overlay.update_buffers(
+            render_state.get_device(),
+            render_state.get_queue(),
+            encoder,
+            primitives,
+            &screen_descriptor,
+        );
+
+        // Render on top of the map without clearing it
+        {
+            let mut render_pass = encoder
+                .begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Render Pass: Overlay"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view,
+                        resolve_target: None,
+                        depth_slice: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                })
+                .forget_lifetime();
+            self.overlay
+                .render(&mut render_pass, primitives, &screen_descriptor);
+        }
+
+        // Remove all textures no longer in use
+        for id in textures_delta.free.iter() {
+            self.overlay.free_texture(id);
+        }
+
+        return command_buffers;
+    }
+}
@@ -1,46 +1,209 @@
+use std::ops::Range;
+
 use crate::{render, types};
 
-use super::{InstanceMode, InstanceType, State};
+use super::{super::culling, InstanceMode, InstanceType, PipelineType, PrimitiveType, State};
 
 impl State {
-    /// Renders an instance onto the screen
+    /// Creates a new command encoder to record all rendering of a single frame
+    /// into, it must be submitted once everything has been recorded
     ///
     /// # Parameters
     ///
     /// render_state: The render state to use for rendering
+    pub fn create_encoder(&self, render_state: &render::RenderState) -> wgpu::CommandEncoder {
+        return render_state
+            .get_device()
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Command Encoder: Frame"),
+            });
+    }
+
+    /// Clears the view and records the rendering of the full map including
+    /// the sun into a single render pass. If the view is narrower than the map
+    /// the tiles are wrapped around the center of the view in the shader such
+    /// that the map is drawn once, otherwise the map is repeated once on
+    /// either side to show the wrapping in x. Only the tiles inside the view
+    /// are drawn
+    ///
+    /// # Parameters
+    ///
+    /// render_state: The render state to use for rendering
+    ///
+    /// encoder: The command encoder of the frame to record into
     ///
     /// view: The texture view to render to
     ///
+    /// msaa_view: The multisampled color target with the size of the view to
+    /// render into and resolve to the view, None if anti-aliasing is disabled
+    ///
     /// transform: The transform to go from world to screen coordinates
     ///
-    /// instance: The instance to render
-    pub fn render(
+    /// map_width: The width of the map in world coordinates, f64::MAX if the
+    /// map does not wrap in x
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub fn render_map(
         &self,
         render_state: &render::RenderState,
+        encoder: &mut wgpu::CommandEncoder,
         view: &wgpu::TextureView,
+        msaa_view: Option<&wgpu::TextureView>,
         transform: &types::Transform2D,
+        map_width: f64,
+    ) {
+        // Get the transforms for all repeats of the map
+        let transform_pos = transform
+            * types::Transform2D::translate(&types::Point {
+                x: map_width,
+                y: 0.0,
+            });
+        let transform_neg = transform
+            * types::Transform2D::translate(&types::Point {
+                x: -map_width,
+                y: 0.0,
+            });
+
+        // Find out if a single wrapped copy of the map covers the view, a map
+        // which does not wrap in x is drawn once without wrapping
+        let repeat = map_width < f64::MAX;
+        let wrap = repeat && culling::fits_in_width(transform, &*self.grid_layout);
+        let (grid_layout, transforms) = if !repeat {
+            (self.grid_layout.get_data(), vec![*transform])
+        } else if wrap {
+            let center = transform.inv() * types::Point::new(0.0, 0.0);
+            (
+                self.grid_layout.get_data_wrapped(center.x),
+                vec![*transform],
+            )
+        } else {
+            (
+                self.grid_layout.get_data(),
+                vec![transform_neg, transform_pos, *transform],
+            )
+        };
+        InstanceType::write_grid_layout_collection(&self.instances, render_state, &grid_layout);
+        self.highlight.write_grid_layout(render_state, &grid_layout);
+
+        // Clear the view at the start of the pass, with anti-aliasing the
+        // samples are only needed until they are resolved to the view
+        let (target, resolve_target, store) = match msaa_view {
+            Some(msaa_view) => (msaa_view, Some(view), wgpu::StoreOp::Discard),
+            None => (view, None, wgpu::StoreOp::Store),
+        };
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Render Pass: Map"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target,
+                depth_slice: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(self.settings.color_clear.get_wgpu()),
+                    store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        // Render the sun, then the background of the tiles, then the plants,
+        // then the bridges connecting them, then the outlines of the tiles and
+        // then the energy transfer arrows if they are shown, every repeat of
+        // the map uses its own transform slot
+        let instances = [
+            InstanceType::Sun,
+            InstanceType::GridBackground,
+            InstanceType::Plant,
+            InstanceType::Bridges,
+        ]
+        .into_iter()
+        .chain(self.settings.show_borders.then_some(InstanceType::Border))
+        .chain(
+            self.settings
+                .show_transfers
+                .then_some(InstanceType::Transfers),
+        );
+        for instance in instances {
+            for (slot, transform) in transforms.iter().enumerate() {
+                self.render(
+                    render_state,
+                    &mut render_pass,
+                    transform,
+                    slot,
+                    &instance,
+                    wrap,
+                );
+            }
+        }
+
+        // Finally tint the highlighted tiles
+        for (slot, transform) in transforms.iter().enumerate() {
+            self.render_highlight(render_state, &mut render_pass, transform, slot);
+        }
+    }
+
+    /// Records the rendering of an instance
+    ///
+    /// # Parameters
+    ///
+    /// render_state: The render state to use for rendering
+    ///
+    /// render_pass: The render pass to record into
+    ///
+    /// transform: The transform to go from world to screen coordinates
+    ///
+    /// slot: The transform slot to use, each slot may only be used once for
+    /// every instance type in a frame
+    ///
+    /// instance: The instance to render
+    ///
+    /// wrap: True if the tiles are wrapped around the center of the view
+    pub fn render<'a>(
+        &'a self,
+        render_state: &render::RenderState,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        transform: &types::Transform2D,
+        slot: usize,
         instance: &InstanceType,
+        wrap: bool,
     ) {
         match instance {
-            InstanceType::Sun => self.render_sun(render_state, view, transform),
-            InstanceType::GridBackground => self.render_background(render_state, view, transform),
+            InstanceType::Sun => self.render_sun(render_state, render_pass, transform, slot),
+            InstanceType::GridBackground => {
+                self.render_background(render_state, render_pass, transform, slot, wrap)
+            }
+            InstanceType::Plant => {
+                self.render_plant(render_state, render_pass, transform, slot, wrap)
+            }
+            InstanceType::Bridges => {
+                self.render_bridges(render_state, render_pass, transform, slot, wrap)
+            }
+            InstanceType::Border => {
+                self.render_borders(render_state, render_pass, transform, slot, wrap)
+            }
+            InstanceType::Transfers => {
+                self.render_transfers(render_state, render_pass, transform, slot, wrap)
+            }
         };
     }
 
-    /// Renders the sun onto the given view
+    /// Records the rendering of the sun into the given render pass
     ///
     /// # Parameters
     ///
     /// render_state: The render state to use for rendering
     ///
-    /// view: The texture view to render to
+    /// render_pass: The render pass to record into
     ///
     /// transform: The transform to go from world to screen coordinates
-    fn render_sun(
-        &self,
+    ///
+    /// slot: The transform slot to use
+    fn render_sun<'a>(
+        &'a self,
         render_state: &render::RenderState,
-        view: &wgpu::TextureView,
+        render_pass: &mut wgpu::RenderPass<'a>,
         transform: &types::Transform2D,
+        slot: usize,
     ) {
         // Get the transform for the sun rectangles
         let sun_scaling = (1.0 - transform.center.y) / transform.get_scaling_y();
@@ -51,137 +214,267 @@ impl State {
             })
             * types::Transform2D::translate(&types::Point { x: 0.5, y: 0.5 });
 
-        // Render the sun rays
+        // Record the sun rays
         let instance = InstanceMode::Sun;
         instance
             .get_type()
-            .write_transform(&self.instances, render_state, &sun_transform);
-        self.render_instance(render_state, view, &instance);
+            .write_transform(&self.instances, render_state, &sun_transform, slot);
+        let all = 0..instance.get_type().get_count(&self.instances);
+        self.render_instance(render_pass, &instance, slot, &[all]);
     }
 
-    /// Renders the background onto the given view
+    /// Records the rendering of the background into the given render pass
     ///
     /// # Parameters
     ///
     /// render_state: The render state to use for rendering
     ///
-    /// view: The texture view to render to
+    /// render_pass: The render pass to record into
     ///
     /// transform: The transform to go from world to screen coordinates
-    fn render_background(
-        &self,
+    ///
+    /// slot: The transform slot to use
+    ///
+    /// wrap: True if the tiles are wrapped around the center of the view
+    fn render_background<'a>(
+        &'a self,
         render_state: &render::RenderState,
-        view: &wgpu::TextureView,
+        render_pass: &mut wgpu::RenderPass<'a>,
         transform: &types::Transform2D,
+        slot: usize,
+        wrap: bool,
     ) {
         let instance = InstanceMode::GridBackground(self.settings.mode_background);
         instance
             .get_type()
-            .write_transform(&self.instances, render_state, transform);
-        self.render_instance(render_state, view, &instance);
+            .write_transform(&self.instances, render_state, transform, slot);
+        let ranges = self.get_visible_instances(transform, &instance, wrap);
+        self.render_instance(render_pass, &instance, slot, &ranges);
     }
 
-    /// Renders A single set of buffers
+    /// Records the rendering of the plants into the given render pass
     ///
     /// # Parameters
     ///
     /// render_state: The render state to use for rendering
     ///
-    /// view: The texture view to render to
+    /// render_pass: The render pass to record into
     ///
-    /// instance: The instance to render
-    fn render_instance(
-        &self,
+    /// transform: The transform to go from world to screen coordinates
+    ///
+    /// slot: The transform slot to use
+    ///
+    /// wrap: True if the tiles are wrapped around the center of the view
+    fn render_plant<'a>(
+        &'a self,
         render_state: &render::RenderState,
-        view: &wgpu::TextureView,
-        instance: &InstanceMode,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        transform: &types::Transform2D,
+        slot: usize,
+        wrap: bool,
     ) {
-        // Create the encoder
-        let mut encoder =
-            render_state
-                .get_device()
-                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                    label: Some("Command Encoder: Fill"),
-                });
-
-        // Initialize the render pass
-        {
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Render Pass: Fill"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &view,
-                    resolve_target: None,
-                    depth_slice: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Load,
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
+        let instance = InstanceMode::Plant(self.settings.mode_plant);
+        instance
+            .get_type()
+            .write_transform(&self.instances, render_state, transform, slot);
+        let ranges = self.get_visible_instances(transform, &instance, wrap);
+        self.render_instance(render_pass, &instance, slot, &ranges);
+    }
+
+    /// Records the rendering of the bridges between the plant tiles into the
+    /// given render pass
+    ///
+    /// # Parameters
+    ///
+    /// render_state: The render state to use for rendering
+    ///
+    /// render_pass: The render pass to record into
+    ///
+    /// transform: The transform to go from world to screen coordinates
+    ///
+    /// slot: The transform slot to use
+    ///
+    /// wrap: True if the tiles are wrapped around the center of the view
+    fn render_bridges<'a>(
+        &'a self,
+        render_state: &render::RenderState,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        transform: &types::Transform2D,
+        slot: usize,
+        wrap: bool,
+    ) {
+        let instance = InstanceMode::Bridges;
+        instance
+            .get_type()
+            .write_transform(&self.instances, render_state, transform, slot);
+        let ranges = self.get_visible_instances(transform, &instance, wrap);
+        self.render_instance(render_pass, &instance, slot, &ranges);
+    }
 
-            // Set the pipeline for fill
-            instance.pipeline().set(&self.pipelines, &mut render_pass);
+    /// Records the rendering of the outlines of the tiles into the given
+    /// render pass
+    ///
+    /// # Parameters
+    ///
+    /// render_state: The render state to use for rendering
+    ///
+    /// render_pass: The render pass to record into
+    ///
+    /// transform: The transform to go from world to screen coordinates
+    ///
+    /// slot: The transform slot to use
+    ///
+    /// wrap: True if the tiles are wrapped around the center of the view
+    fn render_borders<'a>(
+        &'a self,
+        render_state: &render::RenderState,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        transform: &types::Transform2D,
+        slot: usize,
+        wrap: bool,
+    ) {
+        let instance = InstanceMode::Border;
+        instance
+            .get_type()
+            .write_transform(&self.instances, render_state, transform, slot);
+        let ranges = self.get_visible_instances(transform, &instance, wrap);
+        self.render_instance(render_pass, &instance, slot, &ranges);
+    }
 
-            // Set vertices for the primitive
-            let index_count = instance
-                .get_type()
-                .primitive()
-                .set(&self.primitives, &mut render_pass);
+    /// Records the rendering of the energy transfer arrows along the bridges
+    /// into the given render pass
+    ///
+    /// # Parameters
+    ///
+    /// render_state: The render state to use for rendering
+    ///
+    /// render_pass: The render pass to record into
+    ///
+    /// transform: The transform to go from world to screen coordinates
+    ///
+    /// slot: The transform slot to use
+    ///
+    /// wrap: True if the tiles are wrapped around the center of the view
+    fn render_transfers<'a>(
+        &'a self,
+        render_state: &render::RenderState,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        transform: &types::Transform2D,
+        slot: usize,
+        wrap: bool,
+    ) {
+        let instance = InstanceMode::Transfers;
+        instance
+            .get_type()
+            .write_transform(&self.instances, render_state, transform, slot);
+        let ranges = self.get_visible_instances(transform, &instance, wrap);
+        self.render_instance(render_pass, &instance, slot, &ranges);
+    }
 
-            // Set the tile instances
-            let instance_count = instance.get_type().set(&self.instances, &mut render_pass);
+    /// Records the rendering of the highlighted tiles into the given render
+    /// pass, there are only a few so they are always drawn
+    ///
+    /// # Parameters
+    ///
+    /// render_state: The render state to use for rendering
+    ///
+    /// render_pass: The render pass to record into
+    ///
+    /// transform: The transform to go from world to screen coordinates
+    ///
+    /// slot: The transform slot to use
+    fn render_highlight<'a>(
+        &'a self,
+        render_state: &render::RenderState,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        transform: &types::Transform2D,
+        slot: usize,
+    ) {
+        self.highlight
+            .write_transform(render_state, transform, slot);
 
-            // Draw
-            render_pass.draw_indexed(0..index_count, 0, 0..instance_count);
+        // Set the pipeline and the hexagon vertices
+        PipelineType::Highlight.set(&self.pipelines, render_pass);
+        let index_count = PrimitiveType::Hexagon.set(&self.primitives, render_pass);
+
+        // Set the highlight instances and draw
+        let count = self.highlight.set(render_pass, slot);
+        if count > 0 {
+            render_pass.draw_indexed(0..index_count, 0, 0..count);
         }
+    }
 
-        // Submit
-        render_state
-            .get_queue()
-            .submit(std::iter::once(encoder.finish()));
+    /// Gets the ranges of instances of an instance type with one or more
+    /// instances per tile which are inside the view
+    ///
+    /// # Parameters
+    ///
+    /// transform: The transform to go from world to screen coordinates
+    ///
+    /// instance: The instance to find the visible instances of
+    ///
+    /// wrap: True if the tiles are wrapped around the center of the view
+    fn get_visible_instances(
+        &self,
+        transform: &types::Transform2D,
+        instance: &InstanceMode,
+        wrap: bool,
+    ) -> Vec<Range<u32>> {
+        let tile_count = InstanceType::GridBackground.get_count(&self.instances);
+        if tile_count == 0 {
+            return Vec::new();
+        }
+        let per_tile = instance.get_type().get_count(&self.instances) / tile_count;
+
+        return culling::get_visible_tiles(
+            transform,
+            &*self.grid_layout,
+            tile_count as usize,
+            wrap,
+        )
+        .into_iter()
+        .map(|range| range.start * per_tile..range.end * per_tile)
+        .collect();
     }
 
-    /// Clears the screen
+    /// Records the drawing of A single set of buffers, nothing is drawn if
+    /// there are no ranges
     ///
     /// # Parameters
     ///
-    /// render_state: The render state to use for rendering
+    /// render_pass: The render pass to record into
     ///
-    /// view: The texture view to render to
-    pub fn clear(&self, render_state: &render::RenderState, view: &wgpu::TextureView) {
-        // Create the encoder
-        let mut encoder =
-            render_state
-                .get_device()
-                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                    label: Some("Command Encoder: Fill"),
-                });
-
-        // Initialize the render pass
-        {
-            let mut _render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Render Pass: Fill"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &view,
-                    resolve_target: None,
-                    depth_slice: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(self.settings.color_clear.get_wgpu()),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
+    /// instance: The instance to render
+    ///
+    /// slot: The transform slot to use
+    ///
+    /// ranges: The ranges of instances to draw
+    fn render_instance<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        instance: &InstanceMode,
+        slot: usize,
+        ranges: &[Range<u32>],
+    ) {
+        if ranges.is_empty() {
+            return;
         }
 
-        // Submit
-        render_state
-            .get_queue()
-            .submit(std::iter::once(encoder.finish()));
+        // Set the pipeline for fill
+        instance.pipeline().set(&self.pipelines, render_pass);
+
+        // Set vertices for the primitive
+        let index_count = instance
+            .get_type()
+            .primitive()
+            .set(&self.primitives, render_pass);
+
+        // Set the tile instances
+        instance.get_type().set(&self.instances, render_pass, slot);
+
+        // Draw
+        for range in ranges.iter() {
+            render_pass.draw_indexed(0..index_count, 0, range.clone());
+        }
     }
 }
@@ -0,0 +1,47 @@
+use crate::{render, types};
+
+use super::{State, Thumbnail, ThumbnailError};
+
+impl State {
+    /// Renders the map into an offscreen texture and reads it back as a
+    /// thumbnail, the height is chosen to keep the aspect ratio of the view
+    ///
+    /// # Parameters
+    ///
+    /// render_state: The render state to use for rendering
+    ///
+    /// view: The part of the world to render
+    ///
+    /// map_width: The width of the map in world coordinates
+    ///
+    /// width: The width of the thumbnail in pixels
+    ///
+    /// # Errors
+    ///
+    /// See ThumbnailError for a description of the different errors which may occur
+    pub fn render_thumbnail(
+        &self,
+        render_state: &render::RenderState,
+        view: &types::View,
+        map_width: f64,
+        width: u32,
+    ) -> Result<Thumbnail, ThumbnailError> {
+        // Find the size keeping the aspect ratio of the view
+        let height = if view.get_size().get_w() > 0.0 {
+            (width as f64 * view.get_size().get_h() / view.get_size().get_w()).round() as u32
+        } else {
+            0
+        };
+        if width == 0 || height == 0 {
+            return Err(ThumbnailError::InvalidSize(width, height));
+        }
+
+        // Render the view such that it fills the entire texture
+        let transform = types::Transform2D::scale(&types::Point::new(
+            2.0 / view.get_size().get_w(),
+            2.0 / view.get_size().get_h(),
+        )) * types::Transform2D::translate(&(-view.get_center()));
+
+        return self.render_capture(render_state, &transform, map_width, width, height);
+    }
+}
@@ -0,0 +1,135 @@
+use std::{fs, io, path::Path};
+
+use thiserror::Error;
+
+/// An image rendered offscreen, stored as tightly packed 8 bit RGBA
+/// pixels row by row from the top
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Thumbnail {
+    /// The width in pixels
+    width: u32,
+    /// The height in pixels
+    height: u32,
+    /// The RGBA values of all pixels
+    pixels: Vec<u8>,
+}
+
+impl Thumbnail {
+    /// Constructs a new thumbnail
+    ///
+    /// # Parameters
+    ///
+    /// width: The width in pixels
+    ///
+    /// height: The height in pixels
+    ///
+    /// pixels: The RGBA values of all pixels, must have a length of 4 * width * height
+    pub fn new(width: u32, height: u32, pixels: Vec<u8>) -> Self {
+        assert_eq!(pixels.len(), 4 * width as usize * height as usize);
+
+        return Self {
+            width,
+            height,
+            pixels,
+        };
+    }
+
+    /// Retrieves the width in pixels
+    pub fn get_width(&self) -> u32 {
+        return self.width;
+    }
+
+    /// Retrieves the height in pixels
+    pub fn get_height(&self) -> u32 {
+        return self.height;
+    }
+
+    /// Retrieves the RGBA values of all pixels
+    pub fn get_pixels(&self) -> &[u8] {
+        return &self.pixels;
+    }
+
+    /// Encodes the thumbnail as a PNG image
+    ///
+    /// # Errors
+    ///
+    /// See ThumbnailError for a description of the different errors which may occur
+    pub fn encode_png(&self) -> Result<Vec<u8>, ThumbnailError> {
+        let mut data = Vec::new();
+        image::write_buffer_with_format(
+            &mut io::Cursor::new(&mut data),
+            &self.pixels,
+            self.width,
+            self.height,
+            image::ExtendedColorType::Rgba8,
+            image::ImageFormat::Png,
+        )?;
+
+        return Ok(data);
+    }
+
+    /// Saves the thumbnail as a PNG file
+    ///
+    /// # Parameters
+    ///
+    /// path: The path of the file
+    ///
+    /// # Errors
+    ///
+    /// See ThumbnailError for a description of the different errors which may occur
+    pub fn save_png(&self, path: &Path) -> Result<(), ThumbnailError> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, self.encode_png()?)?;
+
+        return Ok(());
+    }
+}
+
+/// The error types for when rendering or saving a thumbnail
+#[derive(Error, Debug)]
+pub enum ThumbnailError {
+    /// The width and height must both be larger than 0
+    #[error("The size of a thumbnail must be larger than 0 but received {}x{}", .0, .1)]
+    InvalidSize(u32, u32),
+    /// The pixels of the texture format cannot be converted to RGBA
+    #[error("Unable to read pixels of texture format {:?}", .0)]
+    UnsupportedFormat(wgpu::TextureFormat),
+    /// The gpu could not finish rendering
+    #[error("Unable to wait for the gpu: {:?}", .0)]
+    Poll(wgpu::PollError),
+    /// The rendered texture could not be read back from the gpu
+    #[error("Unable to read the rendered texture: {:?}", .0)]
+    Readback(wgpu::BufferAsyncError),
+    /// The image could not be encoded
+    #[error("Unable to encode thumbnail: {}", .0)]
+    Encode(image::ImageError),
+    /// The file could not be written
+    #[error("Unable to write thumbnail file: {:?}", .0)]
+    Io(io::Error),
+}
+
+impl From<wgpu::PollError> for ThumbnailError {
+    fn from(value: wgpu::PollError) -> Self {
+        return Self::Poll(value);
+    }
+}
+
+impl From<wgpu::BufferAsyncError> for ThumbnailError {
+    fn from(value: wgpu::BufferAsyncError) -> Self {
+        return Self::Readback(value);
+    }
+}
+
+impl From<image::ImageError> for ThumbnailError {
+    fn from(value: image::ImageError) -> Self {
+        return Self::Encode(value);
+    }
+}
+
+impl From<io::Error> for ThumbnailError {
+    fn from(value: io::Error) -> Self {
+        return Self::Io(value);
+    }
+}
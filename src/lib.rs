@@ -0,0 +1,25 @@
+//! Simulates the evolution of plants on a hexagonal grid, the simulation is
+//! a library such that it can be benchmarked and tested apart from the viewer
+//! and embedded in other crates. The window showing the simulation is only
+//! built with the viewer feature, without it there is no windowing stack
+
+#[cfg(feature = "viewer")]
+pub mod application;
+pub mod camera;
+pub mod config;
+pub mod constants;
+#[cfg(feature = "viewer")]
+pub mod graphics;
+pub mod map;
+#[cfg(feature = "viewer")]
+pub mod render;
+pub mod report;
+pub mod runner;
+pub mod scenario;
+pub mod script;
+pub mod server;
+pub mod sweep;
+pub mod trace;
+pub mod types;
+#[cfg(all(target_arch = "wasm32", feature = "viewer"))]
+pub mod web;
@@ -1,95 +1,319 @@
-use std::env;
+use std::{env, path::PathBuf, process};
 
-use winit::dpi::PhysicalSize;
+use clap::Parser;
+use plant_growth_simulation::{
+    application, config, graphics, map, report, runner, scenario, script, server, sweep, trace,
+};
+use tracing_subscriber::filter::LevelFilter;
 
-pub mod application;
-pub mod camera;
-pub mod constants;
-pub mod graphics;
-pub mod map;
-pub mod render;
-pub mod types;
+/// Simulates the evolution of plants on a hexagonal grid
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Cli {
+    /// The TOML config file to load the settings from
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+    /// The number of tiles in each row of the map
+    #[arg(long)]
+    width: Option<usize>,
+    /// The number of rows of tiles in the map
+    #[arg(long)]
+    height: Option<usize>,
+    /// The ready made world to explore, it replaces the map size, simulation
+    /// settings and sun of the config
+    #[arg(long, value_parser = parse_world, conflicts_with_all = ["width", "height"])]
+    world: Option<map::presets::WorldPreset>,
+    /// The name of the settings preset in the preset directory to run, it
+    /// replaces the simulation settings of the config
+    #[arg(long, conflicts_with = "world")]
+    preset: Option<String>,
+    /// The seed of the simulation
+    #[arg(short, long)]
+    seed: Option<u64>,
+    /// Run the simulation without opening a window
+    #[arg(long, requires = "steps")]
+    headless: bool,
+    /// The number of steps after which the run ends
+    #[arg(short = 'n', long)]
+    steps: Option<usize>,
+    /// The initial width of the window in pixels
+    #[arg(long)]
+    window_width: Option<u32>,
+    /// The initial height of the window in pixels
+    #[arg(long)]
+    window_height: Option<u32>,
+    /// The initial number of simulation steps per second
+    #[arg(long)]
+    sim_rate: Option<f64>,
+    /// The file to stream the statistics of every step to, CSV unless the
+    /// extension is .json or .jsonl
+    #[arg(long)]
+    stats_out: Option<PathBuf>,
+    /// The address to stream the map of a headless run to web viewers on,
+    /// like 0.0.0.0:9001, requires the server feature
+    #[arg(long, requires = "headless")]
+    serve: Option<String>,
+    /// The Rhai script to run on the map in place of opening a window
+    #[arg(long, conflicts_with = "headless")]
+    script: Option<PathBuf>,
+    /// The sweep file describing the settings to vary over many headless
+    /// runs in place of opening a window
+    #[arg(long, conflicts_with_all = ["headless", "script", "world"])]
+    sweep: Option<PathBuf>,
+    /// The scenario file to run on the map in place of opening a window, the
+    /// program exits with an error if any of its assertions fail
+    #[arg(long, conflicts_with_all = ["headless", "script", "sweep"])]
+    scenario: Option<PathBuf>,
+    /// The file to record the time spent in every phase of the simulation
+    /// and the rendering to, it can be opened in chrome://tracing or Perfetto
+    #[arg(long)]
+    trace_out: Option<PathBuf>,
+    /// The most detailed level of spans to record to the trace file, the
+    /// spans for every tile are only recorded at the trace level
+    #[arg(long, default_value = "debug", requires = "trace_out")]
+    trace_level: LevelFilter,
+}
+
+impl Cli {
+    /// Loads the config file, or the default config if no file is given, and
+    /// overrides it with all options given on the command line
+    ///
+    /// # Errors
+    ///
+    /// See ConfigError for a description of the different errors which may occur
+    fn get_config(&self) -> Result<config::Config, config::ConfigError> {
+        let mut config = match &self.config {
+            Some(path) => config::Config::load(path)?,
+            None => config::Config::new(),
+        };
+
+        if let Some(width) = self.width {
+            config.map.width = width;
+        }
+        if let Some(height) = self.height {
+            config.map.height = height;
+        }
+        if let Some(preset) = &self.preset {
+            config.map.preset = Some(preset.clone());
+        }
+        if let Some(seed) = self.seed {
+            config.map.settings.seed = seed;
+        }
+        if let Some(steps) = self.steps {
+            config.viewer.step_limit = Some(steps);
+        }
+        if let Some(window_width) = self.window_width {
+            config.viewer.window_width = window_width;
+        }
+        if let Some(window_height) = self.window_height {
+            config.viewer.window_height = window_height;
+        }
+        if let Some(sim_rate) = self.sim_rate {
+            config.viewer.sim_rate = sim_rate;
+        }
+        if let Some(stats_out) = &self.stats_out {
+            config.viewer.stats_out = Some(stats_out.clone());
+        }
+        config.validate()?;
+
+        return Ok(config);
+    }
+}
+
+/// Finds the world with the name given on the command line
+///
+/// # Parameters
+///
+/// name: The name of the world
+fn parse_world(name: &str) -> Result<map::presets::WorldPreset, String> {
+    return map::presets::WorldPreset::from_name(name).ok_or_else(|| {
+        let names = (0..map::presets::WorldPreset::COUNT)
+            .map(|id| map::presets::WorldPreset::from_id(id).get_name())
+            .collect::<Vec<_>>();
+        format!("Unknown world, available worlds: {}", names.join(", "))
+    });
+}
 
 fn main() {
     unsafe { env::set_var("RUST_BACKTRACE", "1") };
 
-    // Get crate data
-    let crate_name = env!("CARGO_PKG_NAME");
-    let crate_version = env!("CARGO_PKG_VERSION");
-
-    // Setup the camera
-    let camera_transform = types::Transform2D::scale(&types::Point::new(1.0, 1.0));
-    let camera_settings = camera::CameraSettings::default()
-        .with_framerate(constants::FRAMERATE)
-        .with_speed_move(constants::CAMERA_MOVE_SPEED)
-        .with_speed_zoom(constants::CAMERA_ZOOM_SPEED)
-        .with_boost_factor(constants::CAMERA_BOOST_FACTOR)
-        .with_zoom_limits(constants::CAMERA_ZOOM_LIMITS);
-    let camera = camera::Camera::new(camera_settings, camera_transform);
-
-    // Setup the shader settings
-    let color_map_sun: Box<dyn types::ColorMap> = Box::new(constants::COLOR_MAP_LIGHT);
-    let color_map_background_transparency: Box<dyn types::ColorMap> =
-        Box::new(constants::COLOR_MAP_TRANSPARENCY);
-    let color_map_background_light: Box<dyn types::ColorMap> = Box::new(constants::COLOR_MAP_LIGHT);
-    let color_maps_background = map::DataModeBackground::new_color_map_collection(
-        color_map_background_light,
-        color_map_background_transparency,
-    );
-
-    // Set window settings
-    let name = format!("{crate_name} v{crate_version}");
-    let size = PhysicalSize::new(500, 500);
-    let color_background = constants::COLOR_BACKGROUND;
-    let mode_background = constants::COLOR_MODE_BACKGROUND;
-    let active_color_maps =
-        graphics::InstanceType::new_color_map_collection(color_map_sun, color_maps_background);
-    let graphics_settings = graphics::Settings {
-        color_clear: color_background,
-        mode_background,
-        color_maps: active_color_maps,
-    };
-    let settings_window = application::WindowSettingsInput {
-        name,
-        size,
-        graphics_settings,
-    };
+    // Load the config and apply the command line options
+    let cli = Cli::parse();
 
-    // Setup shader settings
-    let settings_shader = application::ShaderSettingsInput {};
-
-    // Setup the viewer settings
-    let framerate = constants::FRAMERATE;
-    let sim_rate = constants::SIM_RATE;
-    let sim_rate_mod = constants::SIM_RATE_MODIFIER;
-    let settings_viewer = application::ViewerSettingsInput {
-        framerate,
-        sim_rate,
-        sim_rate_mod,
+    // Setup logging and tracing, the guard must be kept until the end
+    let _trace_guard = trace::init(cli.trace_out.as_deref(), cli.trace_level);
+    let config = match cli.get_config() {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
     };
 
+    // Run a sweep over the settings of the config
+    if let Some(path) = &cli.sweep {
+        let result = sweep::Sweep::load(path).and_then(|sweep| {
+            let count = sweep.run(&config)?;
+            println!(
+                "Finished {} runs, saved the results to {}",
+                count,
+                sweep.output.display()
+            );
+            return Ok(());
+        });
+        if let Err(error) = result {
+            eprintln!("Unable to run sweep {}: {}", path.display(), error);
+            process::exit(1);
+        }
+        return;
+    }
+
+    // Find the settings presets, a preset given on the command line must
+    // exist
+    let presets = map::settings::preset::PresetLibrary::new(&config.viewer.preset_directory);
+    if let Some(name) = &cli.preset {
+        match presets.list() {
+            Ok(names) if names.contains(name) => (),
+            Ok(names) => {
+                eprintln!(
+                    "Unknown preset {}, available presets: {}",
+                    name,
+                    names.join(", ")
+                );
+                process::exit(1);
+            }
+            Err(error) => {
+                eprintln!(
+                    "Unable to list presets in {}: {:?}",
+                    config.viewer.preset_directory.display(),
+                    error
+                );
+                process::exit(1);
+            }
+        }
+    }
+
+    // Setup the conditions for ending the run
+    let stop_conditions = config.viewer.get_stop_conditions();
+
     // Construct the map
-    let map_transparency_settings =
-        map::settings::transparency::Settings::new().with_base(constants::MAP_TRANSPARENCY);
-    let map_settings = map::settings::Settings::new().with_transparency(map_transparency_settings);
-    let sun_year = map::sun::IntensityYearPlanet::new(
-        constants::MAP_SUN_TILT,
-        constants::MAP_SUN_LATITUDE,
-        constants::MAP_SUN_YEAR,
-        constants::MAP_SUN_INTENSITY,
-    );
-    let sun_day = map::sun::IntensityDayPlanet::new(constants::MAP_SUN_DAY);
-    let sun = map::sun::IntensityYearDay::new(sun_year, sun_day);
-    let map = map::Map::new(constants::MAP_SIZE, map_settings, sun);
+    let map_settings = match config.map.preset.as_deref().map(|name| presets.load(name)) {
+        Some(Ok(settings)) => settings,
+        Some(Err(error)) if cli.preset.is_some() => {
+            eprintln!("Unable to load preset: {}", error);
+            process::exit(1);
+        }
+        Some(Err(error)) => {
+            eprintln!("Unable to load preset, using config settings: {}", error);
+            config.map.settings
+        }
+        None => config.map.settings,
+    };
+    // The seed given on the command line also applies to presets
+    let map_settings = match cli.seed {
+        Some(seed) => map_settings.with_seed(seed),
+        None => map_settings,
+    };
+    let mut map = match cli.world {
+        Some(world) => world.new_map(map_settings.seed),
+        None => {
+            let sun = config.map.sun.get_world_intensity();
+            map::Map::new(config.map.get_size(), map_settings, sun)
+        }
+    };
+
+    // Compute the light on the gpu, the cpu is used if no gpu is available
+    if config.viewer.gpu_light {
+        match graphics::compute::LightCompute::new_headless() {
+            Ok(light_compute) => map.set_light_solver(Some(Box::new(light_compute))),
+            Err(error) => eprintln!(
+                "Unable to compute light on the gpu, using the cpu: {}",
+                error
+            ),
+        }
+    }
+
+    // Let a script drive the run without a window
+    if let Some(path) = &cli.script {
+        let result = script::Script::load(path).and_then(|script| script.run(map));
+        if let Err(error) = result {
+            eprintln!("Unable to run script {}: {}", path.display(), error);
+            process::exit(1);
+        }
+        return;
+    }
+
+    // Run a scenario without a window and fail if any assertion does not hold
+    if let Some(path) = &cli.scenario {
+        let result = scenario::Scenario::load(path).map_err(|error| error.to_string());
+        let result = result.and_then(|scenario| {
+            scenario.run(&mut map).map_err(|error| error.to_string())?;
+            println!(
+                "All {} assertion(s) held after {} steps",
+                scenario.assertions.len(),
+                scenario.steps
+            );
+            return Ok(());
+        });
+        if let Err(error) = result {
+            eprintln!("Scenario {} failed: {}", path.display(), error);
+            process::exit(1);
+        }
+        return;
+    }
+
+    // Open the statistics file, the run continues without it if it fails
+    let stats_exporter = config.viewer.stats_out.as_ref().and_then(|path| {
+        match report::StatsExporter::create(path, config.viewer.stats_flush_interval) {
+            Ok(exporter) => Some(exporter),
+            Err(error) => {
+                eprintln!(
+                    "Unable to create statistics file {}: {:?}",
+                    path.display(),
+                    error
+                );
+                None
+            }
+        }
+    });
+
+    // Run without a window, the step limit is always given in headless mode
+    if cli.headless {
+        // The run continues without viewers if the server can not be started
+        let map_server = cli.serve.as_ref().and_then(|address| {
+            match server::MapServer::bind(address.as_str()) {
+                Ok(map_server) => Some(map_server),
+                Err(error) => {
+                    eprintln!("Unable to stream the map on {}: {}", address, error);
+                    None
+                }
+            }
+        });
+        let mut runner = runner::Runner::new(map, stop_conditions);
+        application::run_headless(
+            &mut runner,
+            &config.viewer.export_directory,
+            config.viewer.phylogeny_format,
+            stats_exporter,
+            map_server,
+        );
+        return;
+    }
 
     // Setup the main loop
-    let mut main_loop = application::MainLoop::new(
-        map,
-        camera,
-        settings_window,
-        settings_shader,
-        settings_viewer,
-    );
+    let mut main_loop = application::MainLoop::from_config(map, &config, presets);
+    if let Some(stats_exporter) = stats_exporter {
+        main_loop = main_loop.with_stats_exporter(stats_exporter);
+    }
+    // A world replaces the settings of the config so there is nothing to
+    // reload
+    if config.viewer.watch_config {
+        match (&cli.config, cli.world) {
+            (Some(path), None) => main_loop = main_loop.with_config_watcher(path),
+            (None, _) => eprintln!("There is no config file to watch"),
+            (Some(_), Some(_)) => eprintln!("The config is not watched when exploring a world"),
+        }
+    }
 
     // Run the application
     application::run(&mut main_loop);
@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types;
+
+/// A user note pinned to a single tile of the map or to the organism growing
+/// on it
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    /// The unique id of the annotation
+    pub id: usize,
+    /// The position of the tile the note was pinned to
+    pub pos: types::Index,
+    /// The lineage of the organism the note is pinned to, the note follows
+    /// the organism while it lives and stays on its tile afterwards
+    pub lineage: Option<usize>,
+    /// The simulation step at which the note was created
+    pub time: usize,
+    /// The text of the note
+    pub text: String,
+}
+
+/// All annotations placed on a map
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnnotationSet {
+    /// All annotations in the order they were created
+    annotations: Vec<Annotation>,
+    /// The id to give the next annotation
+    next_id: usize,
+}
+
+impl AnnotationSet {
+    /// Constructs a new empty set of annotations
+    pub fn new() -> Self {
+        return Self {
+            annotations: Vec::new(),
+            next_id: 0,
+        };
+    }
+
+    /// Adds a new annotation and returns its id
+    ///
+    /// # Parameters
+    ///
+    /// pos: The position of the tile to pin the note to
+    ///
+    /// lineage: The lineage of the organism to pin the note to if any
+    ///
+    /// time: The simulation step at which the note was created
+    ///
+    /// text: The text of the note
+    pub fn add(
+        &mut self,
+        pos: types::Index,
+        lineage: Option<usize>,
+        time: usize,
+        text: String,
+    ) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.annotations.push(Annotation {
+            id,
+            pos,
+            lineage,
+            time,
+            text,
+        });
+
+        return id;
+    }
+
+    /// Removes an annotation, returns the removed annotation or None if it did
+    /// not exist
+    ///
+    /// # Parameters
+    ///
+    /// id: The id of the annotation to remove
+    pub fn remove(&mut self, id: usize) -> Option<Annotation> {
+        return self
+            .annotations
+            .iter()
+            .position(|annotation| annotation.id == id)
+            .map(|index| self.annotations.remove(index));
+    }
+
+    /// Retrieves all annotations in the order they were created
+    pub fn get_all(&self) -> &[Annotation] {
+        return &self.annotations;
+    }
+
+    /// Iterates through all annotations pinned to a specific tile
+    ///
+    /// # Parameters
+    ///
+    /// pos: The position of the tile
+    pub fn iter_at(&self, pos: types::Index) -> impl Iterator<Item = &Annotation> {
+        return self
+            .annotations
+            .iter()
+            .filter(move |annotation| annotation.pos == pos);
+    }
+}
+
+#[cfg(test)]
+mod tests;
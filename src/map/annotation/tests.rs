@@ -0,0 +1,111 @@
+use crate::map::{
+    EditBulk, GridLayout,
+    diff::Snapshot,
+    settings,
+    testing::{get_plant_pos, new_map},
+};
+
+use super::*;
+
+#[test]
+fn add_and_remove() {
+    let mut annotations = AnnotationSet::new();
+    let first = annotations.add(types::Index::new(1, 1), None, 3, "First".to_string());
+    let second = annotations.add(types::Index::new(1, 1), Some(4), 5, "Second".to_string());
+    annotations.add(types::Index::new(2, 1), None, 5, "Third".to_string());
+
+    assert_eq!(annotations.iter_at(types::Index::new(1, 1)).count(), 2);
+    assert_eq!(
+        annotations.remove(first).map(|annotation| annotation.text),
+        Some("First".to_string())
+    );
+    assert_eq!(annotations.remove(first), None);
+    assert_eq!(
+        annotations
+            .get_all()
+            .iter()
+            .map(|annotation| annotation.id)
+            .collect::<Vec<_>>(),
+        vec![second, 2]
+    );
+}
+
+#[test]
+fn pin_organism() {
+    let mut map = new_map(settings::Settings::new());
+    map.step();
+    let leaf = get_plant_pos(EditBulk::Leaf);
+    let empty = map.add_annotation(types::Index::new(1, 1), "Empty".to_string(), true);
+    let organism = map.add_annotation(leaf, "Leaf".to_string(), true);
+    let tile = map.add_annotation(leaf, "Tile".to_string(), false);
+    assert_eq!(
+        map.add_annotation(types::Index::new(12, 0), "Outside".to_string(), false),
+        None
+    );
+
+    // Only a tile with an organism pins the note to its lineage
+    let annotations = map.get_annotations().get_all();
+    assert_eq!(
+        annotations
+            .iter()
+            .map(|annotation| Some(annotation.id))
+            .collect::<Vec<_>>(),
+        vec![empty, organism, tile]
+    );
+    assert_eq!(annotations[0].lineage, None);
+    assert_eq!(
+        annotations[1].lineage,
+        map.get_tile(&leaf).and_then(|tile| tile.get_lineage())
+    );
+    assert!(annotations[1].lineage.is_some());
+    assert_eq!(annotations[2].lineage, None);
+}
+
+#[test]
+fn follow_organism() {
+    let mut map = new_map(settings::Settings::new());
+    map.step();
+    let leaf = get_plant_pos(EditBulk::Leaf);
+    map.add_annotation(leaf, "Leaf".to_string(), true);
+    let annotation = map.get_annotations().get_all()[0].clone();
+    let layout = map.get_grid_layout();
+    let lineage = annotation.lineage.unwrap();
+
+    assert_eq!(
+        Some(map.get_annotation_center(&annotation, &layout)),
+        map.get_lineage_center(lineage, &layout)
+    );
+
+    // The note stays on its tile once the organism is gone
+    map.remove_plant(&leaf);
+    assert_eq!(
+        map.get_annotation_center(&annotation, &layout),
+        layout.get_center(&leaf)
+    );
+}
+
+#[test]
+fn saved_in_snapshot() {
+    let mut map = new_map(settings::Settings::new());
+    map.step();
+    map.add_annotation(get_plant_pos(EditBulk::Leaf), "Leaf".to_string(), true);
+    map.add_annotation(types::Index::new(1, 1), "Empty".to_string(), false);
+
+    let snapshot = map.get_snapshot();
+    let text = toml::to_string(&snapshot).unwrap();
+    let loaded: Snapshot = toml::from_str(&text).unwrap();
+    assert_eq!(loaded, snapshot);
+    assert_eq!(loaded.annotations, map.get_annotations().get_all());
+}
+
+#[test]
+fn not_saved_if_empty() {
+    let map = new_map(settings::Settings::new());
+    let text = toml::to_string(&map.get_snapshot()).unwrap();
+
+    assert!(!text.contains("annotations"));
+    assert_eq!(
+        toml::from_str::<Snapshot>(&text).map(|snapshot| snapshot.annotations),
+        Ok(Vec::new())
+    );
+}
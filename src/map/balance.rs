@@ -0,0 +1,43 @@
+/// The energy entering and leaving the plants of a map during a single step,
+/// the change of the stored energy should equal the net balance. Energy moved
+/// between plant tiles through bridges should sum to zero and is only kept
+/// for finding the cause of a violation
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EnergyBalance {
+    /// The energy gained from light and nutrients
+    pub gained: f64,
+    /// The energy spent on running and building plant tiles
+    pub spent: f64,
+    /// The energy lost because plant tiles were full or a plant tile could
+    /// not be built
+    pub wasted: f64,
+    /// The energy left in dead plant tiles which were removed
+    pub died: f64,
+    /// The energy eaten by herbivores
+    pub grazed: f64,
+    /// The energy of plants which appeared spontaneously
+    pub spawned: f64,
+    /// The sum of the energy transferred into plant tiles through bridges
+    pub transferred: f64,
+}
+
+impl EnergyBalance {
+    /// Constructs a new balance where nothing has happened
+    pub fn new() -> Self {
+        return Self {
+            gained: 0.0,
+            spent: 0.0,
+            wasted: 0.0,
+            died: 0.0,
+            grazed: 0.0,
+            spawned: 0.0,
+            transferred: 0.0,
+        };
+    }
+
+    /// Gets the expected change of the stored energy, the transferred energy
+    /// is not included since it should only move energy around
+    pub fn get_net(&self) -> f64 {
+        return self.gained + self.spawned - self.spent - self.wasted - self.died - self.grazed;
+    }
+}
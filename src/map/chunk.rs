@@ -0,0 +1,259 @@
+use crate::types;
+
+use super::{Tile, TilePos, settings::wrap::Wrap};
+
+/// Divides the map into square chunks of tiles and keeps track of which chunks
+/// are static such that their plant updates can be skipped, and which chunks
+/// have changed such that unchanged chunks can skip the step entirely
+#[derive(Clone, Debug)]
+pub struct ChunkSet {
+    /// The side length of a chunk in tiles
+    chunk_size: usize,
+    /// The number of chunks in each direction
+    count: types::ISize,
+    /// The state of all chunks in row first, left to right, top to bottom order
+    chunks: Vec<Chunk>,
+}
+
+impl ChunkSet {
+    /// The simulation cost of fully updating an empty tile
+    pub const COST_EMPTY: usize = 1;
+    /// The simulation cost of fully updating a tile with a plant
+    pub const COST_PLANT: usize = 2;
+
+    /// Constructs a new set of chunks where all chunks are awake
+    ///
+    /// # Parameters
+    ///
+    /// size: The size of the map in tiles
+    ///
+    /// chunk_size: The side length of a chunk in tiles
+    pub fn new(size: &types::ISize, chunk_size: usize) -> Self {
+        let chunk_size = chunk_size.max(1);
+        let count = types::ISize {
+            w: size.w.div_ceil(chunk_size),
+            h: size.h.div_ceil(chunk_size),
+        };
+        let chunks = (0..count.size()).map(|_| Chunk::new()).collect();
+
+        return Self {
+            chunk_size,
+            count,
+            chunks,
+        };
+    }
+
+    /// Retrieves the total number of chunks
+    pub fn get_chunk_count(&self) -> usize {
+        return self.chunks.len();
+    }
+
+    /// Gets the index of the chunk containing a tile
+    ///
+    /// # Parameters
+    ///
+    /// pos: The position of the tile
+    pub fn get_chunk_index(&self, pos: &TilePos) -> usize {
+        let x = pos.pos.x as usize / self.chunk_size;
+        let y = pos.pos.y as usize / self.chunk_size;
+
+        return y * self.count.w + x;
+    }
+
+    /// Checks if a chunk must be fully simulated in the next step
+    ///
+    /// # Parameters
+    ///
+    /// index: The index of the chunk
+    ///
+    /// sleep_delay: The number of idle steps before a chunk falls asleep
+    pub fn is_awake(&self, index: usize, sleep_delay: usize) -> bool {
+        return self.chunks[index].idle_steps < sleep_delay;
+    }
+
+    /// Wakes a chunk up such that it is fully simulated in the next step and
+    /// marks it as changed
+    ///
+    /// # Parameters
+    ///
+    /// index: The index of the chunk
+    pub fn wake(&mut self, index: usize) {
+        let chunk = &mut self.chunks[index];
+        chunk.idle_steps = 0;
+        chunk.changed = true;
+    }
+
+    /// Wakes all chunks up and marks them as changed, used when something
+    /// outside of the tiles changes how they are simulated
+    pub fn wake_all(&mut self) {
+        for index in 0..self.chunks.len() {
+            self.wake(index);
+        }
+    }
+
+    /// Finds all chunks which can skip the next step entirely since stepping
+    /// them would not change any of their tiles. This is the case when a chunk
+    /// is asleep and neither it nor any of its neighbor chunks changed in the
+    /// last step, chunks in the top row also need the sun above them to be
+    /// unchanged
+    ///
+    /// # Parameters
+    ///
+    /// sleep_delay: The number of idle steps before a chunk falls asleep
+    ///
+    /// size: The size of the map in tiles
+    ///
+    /// wrap: The directions in which the map wraps around
+    ///
+    /// sun_changed: For every column, true if the intensity of the sun has
+    /// changed since the last step
+    pub fn get_static(
+        &self,
+        sleep_delay: usize,
+        size: &types::ISize,
+        wrap: &Wrap,
+        sun_changed: &[bool],
+    ) -> Vec<bool> {
+        return (0..self.chunks.len())
+            .map(|index| {
+                if self.is_awake(index, sleep_delay) {
+                    return false;
+                }
+
+                // The sun acts as a row of chunks above the map, the columns
+                // next to the chunk are included since tiles see diagonally
+                if index < self.count.w {
+                    let x = (index * self.chunk_size) as isize;
+                    let end = ((index + 1) * self.chunk_size).min(size.w) as isize;
+                    if (x - 1..=end)
+                        .any(|column| sun_changed[column.rem_euclid(size.w as isize) as usize])
+                    {
+                        return false;
+                    }
+                }
+
+                return !self.any_around(index, wrap, |neighbor| self.chunks[neighbor].changed);
+            })
+            .collect();
+    }
+
+    /// Gets the simulation cost of a chunk in the last step relative to the
+    /// cost of a fully occupied chunk, in the range 0 to 1
+    ///
+    /// # Parameters
+    ///
+    /// index: The index of the chunk
+    pub fn get_cost_fraction(&self, index: usize) -> f64 {
+        return self.chunks[index].cost as f64
+            / (Self::COST_PLANT * self.chunk_size * self.chunk_size) as f64;
+    }
+
+    /// Updates the activity of all chunks after a step, a chunk is idle if
+    /// neither it nor any of its neighbor chunks contain a plant. A chunk has
+    /// changed if it was fully simulated or any of its tiles differ from
+    /// before the step
+    ///
+    /// # Parameters
+    ///
+    /// tiles: All the tiles of the map after the step
+    ///
+    /// previous: All the tiles of the map before the step
+    ///
+    /// size: The size of the map in tiles
+    ///
+    /// costs: The simulation cost of each chunk in the step
+    ///
+    /// sleep_delay: The number of idle steps before a chunk falls asleep
+    ///
+    /// wrap: The directions in which the map wraps around
+    pub fn update(
+        &mut self,
+        tiles: &[Tile],
+        previous: &[Tile],
+        size: &types::ISize,
+        costs: &[usize],
+        sleep_delay: usize,
+        wrap: &Wrap,
+    ) {
+        // Find all chunks containing plants and all changed chunks, only
+        // sleeping chunks need their tiles compared
+        let mut occupied = vec![false; self.chunks.len()];
+        let mut changed = (0..self.chunks.len())
+            .map(|index| self.is_awake(index, sleep_delay))
+            .collect::<Vec<_>>();
+        for (index, (tile, previous)) in tiles.iter().zip(previous.iter()).enumerate() {
+            let chunk = self.get_chunk_index(&TilePos::from_index(index, size));
+            occupied[chunk] |= tile.has_plant();
+            if !changed[chunk] && tile.has_changed(previous) {
+                changed[chunk] = true;
+            }
+        }
+
+        // Update the idle counters
+        let active = (0..self.chunks.len())
+            .map(|index| self.any_around(index, wrap, |neighbor| occupied[neighbor]))
+            .collect::<Vec<_>>();
+        for (index, chunk) in self.chunks.iter_mut().enumerate() {
+            chunk.idle_steps = if active[index] {
+                0
+            } else {
+                chunk.idle_steps.saturating_add(1)
+            };
+            chunk.cost = costs[index];
+            chunk.changed = changed[index];
+        }
+    }
+
+    /// Checks if a condition holds for any chunk in the 3x3 block of chunks
+    /// centered on a chunk, always wrapping in x and wrapping in y like the
+    /// map. Wrapping in x when the map does not only includes extra chunks
+    ///
+    /// # Parameters
+    ///
+    /// index: The index of the center chunk
+    ///
+    /// wrap: The directions in which the map wraps around
+    ///
+    /// condition: The condition to check for the index of each chunk
+    fn any_around(&self, index: usize, wrap: &Wrap, condition: impl Fn(usize) -> bool) -> bool {
+        let w = self.count.w as isize;
+        let h = self.count.h as isize;
+        let x = (index % self.count.w) as isize;
+        let y = (index / self.count.w) as isize;
+
+        return (-1..=1).any(|dy| {
+            let ny = if wrap.vertical() {
+                (y + dy).rem_euclid(h)
+            } else {
+                y + dy
+            };
+            return ny >= 0
+                && ny < h
+                && (-1..=1).any(|dx| condition((ny * w + (x + dx).rem_euclid(w)) as usize));
+        });
+    }
+}
+
+/// The activity state of a single chunk
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Chunk {
+    /// The number of consecutive steps without any plant in or next to this
+    /// chunk
+    idle_steps: usize,
+    /// The simulation cost of this chunk in the last step
+    cost: usize,
+    /// True if any tile in this chunk changed in the last step or was edited
+    /// since
+    changed: bool,
+}
+
+impl Chunk {
+    /// Constructs a new awake chunk
+    fn new() -> Self {
+        return Self {
+            idle_steps: 0,
+            cost: 0,
+            changed: true,
+        };
+    }
+}
@@ -1,22 +1,58 @@
+use serde::{Deserialize, Serialize};
+
 use crate::types;
 
 /// The display mode for the background of a tile
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DataModeBackground {
     /// Display the light level of the tile
     Light,
     /// Display the transparency value of the tile
     Transparency,
+    /// Display the water level of the tile
+    Water,
+    /// Display the nutrient concentration of the ground
+    Nutrients,
+    /// Display the detritus left behind by dead plants
+    Detritus,
+    /// Display the fertility of the tiles, from 0 for barren tiles to 1 for
+    /// twice the average fertility
+    Fertility,
+    /// Display the simulation cost of the chunk containing the tile
+    ChunkCost,
+    /// Display the cloud coverage above the column of the tile
+    Clouds,
+    /// Display the temperature of the tile relative to the coldest and
+    /// warmest tiles
+    Temperature,
+    /// Display the fraction of the light reaching the plant on the tile which
+    /// is blocked by plants of other lineages
+    ForeignShade,
+    /// Display the diffuse light scattered by the sky reaching the tile
+    Diffuse,
+    /// Display the terrain raised above the ground, steep terrain where no
+    /// plant can grow is shown at full strength
+    Terrain,
 }
 
 impl DataModeBackground {
-    pub const COUNT: usize = 2;
+    pub const COUNT: usize = 12;
 
     /// The id to the mode in a list of all modes
     pub fn id(&self) -> usize {
         return match self {
             Self::Light => 0,
             Self::Transparency => 1,
+            Self::Water => 2,
+            Self::Nutrients => 3,
+            Self::ChunkCost => 4,
+            Self::Clouds => 5,
+            Self::Temperature => 6,
+            Self::Detritus => 7,
+            Self::Fertility => 8,
+            Self::ForeignShade => 9,
+            Self::Diffuse => 10,
+            Self::Terrain => 11,
         };
     }
 
@@ -29,10 +65,38 @@ impl DataModeBackground {
         return match id.clamp(0, Self::COUNT - 1) {
             0 => Self::Light,
             1 => Self::Transparency,
+            2 => Self::Water,
+            3 => Self::Nutrients,
+            4 => Self::ChunkCost,
+            5 => Self::Clouds,
+            6 => Self::Temperature,
+            7 => Self::Detritus,
+            8 => Self::Fertility,
+            9 => Self::ForeignShade,
+            10 => Self::Diffuse,
+            11 => Self::Terrain,
             _ => panic!("DataModeBackground::from_id has not been updated"),
         };
     }
 
+    /// Gets the name of the mode for displaying
+    pub fn get_name(&self) -> &'static str {
+        return match self {
+            Self::Light => "Light",
+            Self::Transparency => "Transparency",
+            Self::Water => "Water",
+            Self::Nutrients => "Nutrients",
+            Self::ChunkCost => "Chunk cost",
+            Self::Clouds => "Clouds",
+            Self::Temperature => "Temperature",
+            Self::Detritus => "Detritus",
+            Self::Fertility => "Fertility",
+            Self::ForeignShade => "Foreign shade",
+            Self::Diffuse => "Diffuse light",
+            Self::Terrain => "Terrain",
+        };
+    }
+
     /// Gets the next mode
     pub fn next(&self) -> Self {
         return Self::from_id((self.id() + 1) % Self::COUNT);
@@ -43,17 +107,173 @@ impl DataModeBackground {
         return Self::from_id((self.id() + (Self::COUNT - 1)) % Self::COUNT);
     }
 
+    /// Checks if the color of a tile only depends on the tile itself such that
+    /// only changed tiles have to be uploaded to the gpu
+    pub fn is_local(&self) -> bool {
+        return match self {
+            Self::Light
+            | Self::Transparency
+            | Self::Water
+            | Self::Nutrients
+            | Self::Detritus
+            | Self::Fertility
+            | Self::Diffuse
+            | Self::Terrain => true,
+            Self::ChunkCost | Self::Clouds | Self::Temperature | Self::ForeignShade => false,
+        };
+    }
+
     /// Constructs a new list of the color maps for all modes
     ///
     /// # Parameters
     ///
+    /// light: The color map for light mode
+    ///
     /// transparency: The color map for transparency mode
     ///
-    /// light: The color map for light mode
+    /// water: The color map for water mode
+    ///
+    /// nutrients: The color map for nutrients mode
+    ///
+    /// chunk_cost: The color map for chunk cost mode
+    ///
+    /// clouds: The color map for cloud mode
+    ///
+    /// temperature: The color map for temperature mode
+    ///
+    /// detritus: The color map for detritus mode
+    ///
+    /// fertility: The color map for fertility mode
+    ///
+    /// foreign_shade: The color map for foreign shade mode
+    ///
+    /// diffuse: The color map for diffuse light mode
+    ///
+    /// terrain: The color map for terrain mode
+    #[allow(clippy::too_many_arguments)]
     pub fn new_color_map_collection(
         light: Box<dyn types::ColorMap>,
         transparency: Box<dyn types::ColorMap>,
+        water: Box<dyn types::ColorMap>,
+        nutrients: Box<dyn types::ColorMap>,
+        chunk_cost: Box<dyn types::ColorMap>,
+        clouds: Box<dyn types::ColorMap>,
+        temperature: Box<dyn types::ColorMap>,
+        detritus: Box<dyn types::ColorMap>,
+        fertility: Box<dyn types::ColorMap>,
+        foreign_shade: Box<dyn types::ColorMap>,
+        diffuse: Box<dyn types::ColorMap>,
+        terrain: Box<dyn types::ColorMap>,
+    ) -> [Box<dyn types::ColorMap>; Self::COUNT] {
+        return [
+            light,
+            transparency,
+            water,
+            nutrients,
+            chunk_cost,
+            clouds,
+            temperature,
+            detritus,
+            fertility,
+            foreign_shade,
+            diffuse,
+            terrain,
+        ];
+    }
+}
+
+/// The display mode for the plants on the tiles
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DataModePlant {
+    /// Display the bulk type of the plant
+    Bulk,
+    /// Display the age of the plant tile relative to the oldest plant tile
+    Age,
+    /// Display the age of the entire plant relative to the oldest plant
+    CumAge,
+    /// Display the species of the plant found by the last clustering
+    Species,
+}
+
+impl DataModePlant {
+    pub const COUNT: usize = 4;
+
+    /// The id to the mode in a list of all modes
+    pub fn id(&self) -> usize {
+        return match self {
+            Self::Bulk => 0,
+            Self::Age => 1,
+            Self::CumAge => 2,
+            Self::Species => 3,
+        };
+    }
+
+    /// Constructs a new data mode from an id
+    ///
+    /// # Parameters
+    ///
+    /// id: The id to construct from
+    pub fn from_id(id: usize) -> Self {
+        return match id.clamp(0, Self::COUNT - 1) {
+            0 => Self::Bulk,
+            1 => Self::Age,
+            2 => Self::CumAge,
+            3 => Self::Species,
+            _ => panic!("DataModePlant::from_id has not been updated"),
+        };
+    }
+
+    /// Gets the name of the mode for displaying
+    pub fn get_name(&self) -> &'static str {
+        return match self {
+            Self::Bulk => "Bulk",
+            Self::Age => "Tile age",
+            Self::CumAge => "Plant age",
+            Self::Species => "Species",
+        };
+    }
+
+    /// Gets the next mode
+    pub fn next(&self) -> Self {
+        return Self::from_id((self.id() + 1) % Self::COUNT);
+    }
+
+    /// Gets the previous mode
+    pub fn prev(&self) -> Self {
+        return Self::from_id((self.id() + (Self::COUNT - 1)) % Self::COUNT);
+    }
+
+    /// Checks if the color of a tile only depends on the tile itself such that
+    /// only changed tiles have to be uploaded to the gpu
+    pub fn is_local(&self) -> bool {
+        return match self {
+            Self::Bulk => true,
+            Self::Age | Self::CumAge | Self::Species => false,
+        };
+    }
+
+    /// Constructs a new list of the color maps for all modes, all color maps
+    /// are discrete where index 0 is used for tiles without a living plant
+    ///
+    /// # Parameters
+    ///
+    /// bulk: The color map for bulk mode, index 1 + Bulk::id() is used for
+    /// each bulk type
+    ///
+    /// age: The color map for tile age mode, index 1 is used for the youngest
+    /// and 255 for the oldest tiles
+    ///
+    /// cum_age: The color map for plant age mode, index 1 is used for the
+    /// youngest and 255 for the oldest plants
+    ///
+    /// species: The color map for species mode, index 1 + id % 255 is used
+    /// for each species
+    pub fn new_color_map_collection(
+        bulk: Box<dyn types::ColorMap>,
+        age: Box<dyn types::ColorMap>,
+        cum_age: Box<dyn types::ColorMap>,
+        species: Box<dyn types::ColorMap>,
     ) -> [Box<dyn types::ColorMap>; Self::COUNT] {
-        return [light, transparency];
+        return [bulk, age, cum_age, species];
     }
 }
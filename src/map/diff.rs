@@ -0,0 +1,414 @@
+use std::{fmt, fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::{Annotation, TileSummary};
+use crate::types;
+
+/// The state of every tile of a map at a single time step together with the
+/// notes placed on it, which can be saved to a TOML file and compared with
+/// another snapshot later on
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// The time step the snapshot was taken at
+    pub time: usize,
+    /// The number of columns of the map
+    pub width: usize,
+    /// The number of rows of the map
+    pub height: usize,
+    /// The summary of every tile, row by row starting from the top
+    pub tiles: Vec<TileSummary>,
+    /// All user notes placed on the map, they are not compared
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<Annotation>,
+}
+
+impl Snapshot {
+    /// Loads a snapshot from a TOML file
+    ///
+    /// # Parameters
+    ///
+    /// path: The path of the file
+    ///
+    /// # Errors
+    ///
+    /// See DiffError for a description of the different errors which may occur
+    pub fn load(path: &Path) -> Result<Self, DiffError> {
+        let text = fs::read_to_string(path)?;
+        let snapshot: Self = toml::from_str(&text)?;
+        if snapshot.tiles.len() != snapshot.width * snapshot.height {
+            return Err(DiffError::TileCount(
+                snapshot.tiles.len(),
+                snapshot.width * snapshot.height,
+            ));
+        }
+
+        return Ok(snapshot);
+    }
+
+    /// Writes the snapshot to a TOML file
+    ///
+    /// # Parameters
+    ///
+    /// path: The path of the file
+    ///
+    /// # Errors
+    ///
+    /// See DiffError for a description of the different errors which may occur
+    pub fn save(&self, path: &Path) -> Result<(), DiffError> {
+        let text = toml::to_string(self)?;
+        fs::write(path, text)?;
+
+        return Ok(());
+    }
+
+    /// Compares this snapshot with a later snapshot of the same map and
+    /// finds every tile which differs between them
+    ///
+    /// # Parameters
+    ///
+    /// after: The later snapshot
+    ///
+    /// tolerance: The largest change in energy of a plant which is not
+    /// reported, 0 reports any change
+    ///
+    /// # Errors
+    ///
+    /// Returns DiffError::Size if the two snapshots are of differently sized
+    /// maps
+    pub fn diff(&self, after: &Snapshot, tolerance: f64) -> Result<MapDiff, DiffError> {
+        if self.width != after.width || self.height != after.height {
+            return Err(DiffError::Size(
+                self.width,
+                self.height,
+                after.width,
+                after.height,
+            ));
+        }
+
+        let mut diff = MapDiff {
+            time_before: self.time,
+            time_after: after.time,
+            tiles: Vec::new(),
+        };
+        for (index, (before, after)) in self.tiles.iter().zip(after.tiles.iter()).enumerate() {
+            let Some(change) = TileChange::new(before, after, tolerance) else {
+                continue;
+            };
+            diff.tiles.push(TileDiff {
+                pos: types::Index::new(
+                    (index % self.width) as isize,
+                    (index / self.width) as isize,
+                ),
+                change,
+            });
+        }
+
+        return Ok(diff);
+    }
+}
+
+/// All differences between two snapshots of the same map
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapDiff {
+    /// The time step of the earlier snapshot
+    pub time_before: usize,
+    /// The time step of the later snapshot
+    pub time_after: usize,
+    /// Every tile which differs, row by row starting from the top
+    pub tiles: Vec<TileDiff>,
+}
+
+impl MapDiff {
+    /// Checks if the two snapshots are the same
+    pub fn is_empty(&self) -> bool {
+        return self.tiles.is_empty();
+    }
+
+    /// Sums up the differences by their kind
+    pub fn get_summary(&self) -> DiffSummary {
+        let mut summary = DiffSummary::new();
+        for tile in self.tiles.iter() {
+            match tile.change {
+                TileChange::Appeared { energy, .. } => {
+                    summary.appeared += 1;
+                    summary.energy_delta += energy;
+                }
+                TileChange::Died { energy, .. } => {
+                    summary.died += 1;
+                    summary.energy_delta -= energy;
+                }
+                TileChange::Replaced { before, after } => {
+                    summary.replaced += 1;
+                    summary.energy_delta += after.1 - before.1;
+                }
+                TileChange::Energy { before, after, .. } => {
+                    summary.changed += 1;
+                    summary.energy_delta += after - before;
+                    summary.max_energy_delta = summary.max_energy_delta.max((after - before).abs());
+                }
+                TileChange::Ground => summary.ground += 1,
+            }
+        }
+
+        return summary;
+    }
+
+    /// Writes a human readable report listing the summary and the first
+    /// tiles which differ
+    ///
+    /// # Parameters
+    ///
+    /// max_tiles: The largest number of tiles to list
+    pub fn report(&self, max_tiles: usize) -> String {
+        let mut report = format!(
+            "Steps {} to {}\n{}",
+            self.time_before,
+            self.time_after,
+            self.get_summary()
+        );
+        for tile in self.tiles.iter().take(max_tiles) {
+            report.push_str(&format!("\n  {}", tile));
+        }
+        if self.tiles.len() > max_tiles {
+            report.push_str(&format!(
+                "\n  ... and {} more tiles",
+                self.tiles.len() - max_tiles
+            ));
+        }
+
+        return report;
+    }
+}
+
+/// The number of differences between two snapshots by their kind
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DiffSummary {
+    /// The number of tiles where a living plant appeared
+    pub appeared: usize,
+    /// The number of tiles where a living plant died or disappeared
+    pub died: usize,
+    /// The number of tiles where the living plant is of another lineage
+    pub replaced: usize,
+    /// The number of tiles where only the energy of the plant changed
+    pub changed: usize,
+    /// The number of tiles which became or stopped being ground
+    pub ground: usize,
+    /// The change in the total energy of all differing living plants
+    pub energy_delta: f64,
+    /// The largest change in energy of a single plant tile which kept its
+    /// lineage
+    pub max_energy_delta: f64,
+}
+
+impl DiffSummary {
+    /// Constructs a new summary without any differences
+    fn new() -> Self {
+        return Self {
+            appeared: 0,
+            died: 0,
+            replaced: 0,
+            changed: 0,
+            ground: 0,
+            energy_delta: 0.0,
+            max_energy_delta: 0.0,
+        };
+    }
+}
+
+impl fmt::Display for DiffSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  Plants appeared: {}", self.appeared)?;
+        writeln!(f, "  Plants died: {}", self.died)?;
+        writeln!(f, "  Plants replaced: {}", self.replaced)?;
+        writeln!(f, "  Energy changed: {}", self.changed)?;
+        if self.ground > 0 {
+            writeln!(f, "  Ground changed: {}", self.ground)?;
+        }
+        write!(
+            f,
+            "  Energy delta: {:.4} (largest {:.4})",
+            self.energy_delta, self.max_energy_delta
+        )?;
+
+        return Ok(());
+    }
+}
+
+/// A single tile which differs between two snapshots
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TileDiff {
+    /// The position of the tile
+    pub pos: types::Index,
+    /// How the tile differs
+    pub change: TileChange,
+}
+
+impl fmt::Display for TileDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "({}, {}): {}", self.pos.x, self.pos.y, self.change);
+    }
+}
+
+/// How a single tile differs between two snapshots, only living plants are
+/// compared as the lineage and energy are only known for those
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TileChange {
+    /// A living plant is on the tile which was not there before
+    Appeared {
+        /// The lineage of the new plant
+        lineage: usize,
+        /// The energy of the new plant
+        energy: f64,
+    },
+    /// The living plant on the tile has died or disappeared
+    Died {
+        /// The lineage of the plant
+        lineage: usize,
+        /// The energy the plant had before
+        energy: f64,
+    },
+    /// The living plant on the tile is of another lineage, the lineage and
+    /// energy before and after
+    Replaced {
+        /// The lineage and energy of the plant before
+        before: (usize, f64),
+        /// The lineage and energy of the plant after
+        after: (usize, f64),
+    },
+    /// The living plant is of the same lineage but its energy has changed
+    Energy {
+        /// The lineage of the plant
+        lineage: usize,
+        /// The energy before
+        before: f64,
+        /// The energy after
+        after: f64,
+    },
+    /// The tile became or stopped being ground
+    Ground,
+}
+
+impl TileChange {
+    /// Finds how a tile differs between two snapshots, None if it does not
+    ///
+    /// # Parameters
+    ///
+    /// before: The tile in the earlier snapshot
+    ///
+    /// after: The tile in the later snapshot
+    ///
+    /// tolerance: The largest change in energy which is not reported
+    fn new(before: &TileSummary, after: &TileSummary, tolerance: f64) -> Option<Self> {
+        if before.ground != after.ground {
+            return Some(Self::Ground);
+        }
+
+        let before = get_plant(before);
+        let after = get_plant(after);
+        return match (before, after) {
+            (None, None) => None,
+            (None, Some((lineage, energy))) => Some(Self::Appeared { lineage, energy }),
+            (Some((lineage, energy)), None) => Some(Self::Died { lineage, energy }),
+            (Some(before), Some(after)) => {
+                if before.0 != after.0 {
+                    Some(Self::Replaced { before, after })
+                } else if (after.1 - before.1).abs() > tolerance {
+                    Some(Self::Energy {
+                        lineage: before.0,
+                        before: before.1,
+                        after: after.1,
+                    })
+                } else {
+                    None
+                }
+            }
+        };
+    }
+}
+
+impl fmt::Display for TileChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Self::Appeared { lineage, energy } => write!(
+                f,
+                "Plant of lineage {} appeared with energy {:.4}",
+                lineage, energy
+            ),
+            Self::Died { lineage, energy } => write!(
+                f,
+                "Plant of lineage {} died with energy {:.4}",
+                lineage, energy
+            ),
+            Self::Replaced { before, after } => write!(
+                f,
+                "Plant of lineage {} replaced by lineage {}",
+                before.0, after.0
+            ),
+            Self::Energy {
+                lineage,
+                before,
+                after,
+            } => write!(
+                f,
+                "Plant of lineage {} changed energy from {:.4} to {:.4}",
+                lineage, before, after
+            ),
+            Self::Ground => write!(f, "Ground changed"),
+        };
+    }
+}
+
+/// Retrieves the lineage and energy of the living plant on a tile, None if
+/// there is no fully grown living plant
+///
+/// # Parameters
+///
+/// tile: The summary of the tile
+fn get_plant(tile: &TileSummary) -> Option<(usize, f64)> {
+    return tile
+        .lineage
+        .map(|lineage| (lineage, tile.energy.unwrap_or(0.0)));
+}
+
+/// The error types for when loading, saving or comparing snapshots
+#[derive(Error, Debug)]
+pub enum DiffError {
+    /// The file could not be read or written
+    #[error("Unable to access snapshot file: {:?}", .0)]
+    Io(io::Error),
+    /// The content of the file is invalid
+    #[error("Unable to parse snapshot: {}", .0)]
+    Parse(toml::de::Error),
+    /// The snapshot could not be converted to TOML
+    #[error("Unable to serialize snapshot: {}", .0)]
+    Serialize(toml::ser::Error),
+    /// The number of tiles does not fit the size of the map
+    #[error("The snapshot has {} tiles but the map has {} tiles", .0, .1)]
+    TileCount(usize, usize),
+    /// The snapshots are of maps of different sizes
+    #[error("Unable to compare a {}x{} map with a {}x{} map", .0, .1, .2, .3)]
+    Size(usize, usize, usize, usize),
+}
+
+impl From<io::Error> for DiffError {
+    fn from(value: io::Error) -> Self {
+        return Self::Io(value);
+    }
+}
+
+impl From<toml::de::Error> for DiffError {
+    fn from(value: toml::de::Error) -> Self {
+        return Self::Parse(value);
+    }
+}
+
+impl From<toml::ser::Error> for DiffError {
+    fn from(value: toml::ser::Error) -> Self {
+        return Self::Serialize(value);
+    }
+}
+
+#[cfg(test)]
+mod tests;
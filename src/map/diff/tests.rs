@@ -0,0 +1,202 @@
+use std::{env, process};
+
+use super::*;
+
+/// Constructs the summary of a tile
+///
+/// # Parameters
+///
+/// ground: True if the tile is part of the ground
+///
+/// plant: The lineage and energy of the living plant on the tile if any
+fn tile(ground: bool, plant: Option<(usize, f64)>) -> TileSummary {
+    return TileSummary {
+        light: 0.5,
+        ground,
+        occupied: plant.is_some(),
+        energy: plant.map(|(_, energy)| energy),
+        lineage: plant.map(|(lineage, _)| lineage),
+    };
+}
+
+/// Constructs a snapshot of a map with a single row of tiles
+///
+/// # Parameters
+///
+/// time: The time step of the snapshot
+///
+/// tiles: The summary of every tile
+fn snapshot(time: usize, tiles: Vec<TileSummary>) -> Snapshot {
+    return Snapshot {
+        time,
+        width: tiles.len(),
+        height: 1,
+        tiles,
+        annotations: Vec::new(),
+    };
+}
+
+/// Constructs two snapshots where each tile after the first changes in
+/// another way, the energy of the sixth tile only changes within a tolerance
+/// of 0.1
+fn snapshots() -> (Snapshot, Snapshot) {
+    let before = snapshot(
+        10,
+        vec![
+            tile(false, Some((1, 2.0))),
+            tile(false, None),
+            tile(false, Some((2, 3.0))),
+            tile(false, Some((3, 1.0))),
+            tile(false, Some((4, 5.0))),
+            tile(false, Some((5, 5.0))),
+            tile(false, None),
+        ],
+    );
+    let after = snapshot(
+        20,
+        vec![
+            tile(false, Some((1, 2.0))),
+            tile(false, Some((6, 1.5))),
+            tile(false, None),
+            tile(false, Some((7, 4.0))),
+            tile(false, Some((4, 4.0))),
+            tile(false, Some((5, 5.05))),
+            tile(true, None),
+        ],
+    );
+
+    return (before, after);
+}
+
+#[test]
+fn diff_changes() {
+    let (before, after) = snapshots();
+    let diff = before.diff(&after, 0.1).unwrap();
+
+    assert_eq!(diff.time_before, 10);
+    assert_eq!(diff.time_after, 20);
+    assert_eq!(
+        diff.tiles,
+        vec![
+            TileDiff {
+                pos: types::Index::new(1, 0),
+                change: TileChange::Appeared {
+                    lineage: 6,
+                    energy: 1.5
+                },
+            },
+            TileDiff {
+                pos: types::Index::new(2, 0),
+                change: TileChange::Died {
+                    lineage: 2,
+                    energy: 3.0
+                },
+            },
+            TileDiff {
+                pos: types::Index::new(3, 0),
+                change: TileChange::Replaced {
+                    before: (3, 1.0),
+                    after: (7, 4.0)
+                },
+            },
+            TileDiff {
+                pos: types::Index::new(4, 0),
+                change: TileChange::Energy {
+                    lineage: 4,
+                    before: 5.0,
+                    after: 4.0
+                },
+            },
+            TileDiff {
+                pos: types::Index::new(6, 0),
+                change: TileChange::Ground,
+            },
+        ]
+    );
+}
+
+#[test]
+fn diff_tolerance() {
+    let (before, after) = snapshots();
+
+    // The change in energy within the tolerance is reported without one
+    let diff = before.diff(&after, 0.0).unwrap();
+    assert_eq!(diff.tiles.len(), 6);
+    assert!(matches!(
+        diff.tiles[4].change,
+        TileChange::Energy { lineage: 5, .. }
+    ));
+
+    // Nothing is reported when comparing a snapshot with itself
+    assert!(before.diff(&before, 0.0).unwrap().is_empty());
+}
+
+#[test]
+fn diff_summary() {
+    let (before, after) = snapshots();
+    let summary = before.diff(&after, 0.1).unwrap().get_summary();
+
+    assert_eq!(
+        (
+            summary.appeared,
+            summary.died,
+            summary.replaced,
+            summary.changed,
+            summary.ground
+        ),
+        (1, 1, 1, 1, 1)
+    );
+    assert!((summary.energy_delta - 0.5).abs() < 1e-12);
+    assert!((summary.max_energy_delta - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn diff_report() {
+    let (before, after) = snapshots();
+    let report = before.diff(&after, 0.1).unwrap().report(2);
+
+    assert!(report.starts_with("Steps 10 to 20\n"));
+    assert!(report.contains("\n  Ground changed: 1\n"));
+    assert!(report.contains("\n  (1, 0): Plant of lineage 6 appeared with energy 1.5000"));
+    assert!(report.contains("\n  (2, 0): Plant of lineage 2 died with energy 3.0000"));
+    assert!(!report.contains("(3, 0)"));
+    assert!(report.ends_with("\n  ... and 3 more tiles"));
+}
+
+#[test]
+fn diff_size() {
+    let (before, _) = snapshots();
+    let other = snapshot(20, vec![tile(false, None); 3]);
+
+    assert!(matches!(
+        before.diff(&other, 0.0),
+        Err(DiffError::Size(7, 1, 3, 1))
+    ));
+}
+
+#[test]
+fn save_and_load() {
+    let directory = env::temp_dir().join(format!("snapshot_test_{}", process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let (before, _) = snapshots();
+
+    let path = directory.join("snapshot.toml");
+    before.save(&path).unwrap();
+    assert_eq!(Snapshot::load(&path).unwrap(), before);
+
+    // A snapshot with too few tiles for its size is rejected
+    let mut broken = before.clone();
+    broken.height = 2;
+    let path = directory.join("broken.toml");
+    broken.save(&path).unwrap();
+    assert!(matches!(
+        Snapshot::load(&path),
+        Err(DiffError::TileCount(7, 14))
+    ));
+
+    assert!(matches!(
+        Snapshot::load(&directory.join("missing.toml")),
+        Err(DiffError::Io(_))
+    ));
+    fs::remove_dir_all(&directory).unwrap();
+}
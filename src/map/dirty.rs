@@ -0,0 +1,67 @@
+use std::ops::Range;
+
+/// Keeps track of which tiles have changed since their data was last
+/// retrieved such that only changed tiles have to be uploaded to the gpu
+#[derive(Clone, Debug)]
+pub struct DirtyTiles {
+    /// For every tile, true if it has changed
+    flags: Vec<bool>,
+    /// The indices of all changed tiles in the order they were marked
+    indices: Vec<usize>,
+}
+
+impl DirtyTiles {
+    /// The largest number of unchanged tiles between two changed tiles for
+    /// them to be written in a single range, fewer but larger writes are
+    /// cheaper than many tiny ones
+    const MERGE_GAP: usize = 16;
+
+    /// Constructs a new tracker where all tiles are changed such that the
+    /// first retrieval covers the entire map
+    ///
+    /// # Parameters
+    ///
+    /// count: The number of tiles
+    pub fn new(count: usize) -> Self {
+        return Self {
+            flags: vec![true; count],
+            indices: (0..count).collect(),
+        };
+    }
+
+    /// Marks a tile as changed
+    ///
+    /// # Parameters
+    ///
+    /// index: The index of the tile
+    pub fn mark(&mut self, index: usize) {
+        if !self.flags[index] {
+            self.flags[index] = true;
+            self.indices.push(index);
+        }
+    }
+
+    /// Gets the ranges of indices covering all changed tiles in increasing
+    /// order, changed tiles close to each other are merged into one range
+    pub fn get_ranges(&self) -> Vec<Range<usize>> {
+        let mut indices = self.indices.clone();
+        indices.sort_unstable();
+
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for index in indices {
+            match ranges.last_mut() {
+                Some(last) if index <= last.end + Self::MERGE_GAP => last.end = index + 1,
+                _ => ranges.push(index..index + 1),
+            }
+        }
+
+        return ranges;
+    }
+
+    /// Marks all tiles as unchanged
+    pub fn clear(&mut self) {
+        for index in self.indices.drain(..) {
+            self.flags[index] = false;
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use crate::types;
+
+use super::{GenomeError, Map, TilePos, sun};
+
+/// The energy capacity of plants placed by hand, they start filled up
+const PLACED_ENERGY_CAPACITY: f64 = 16.0;
+
+/// The bulk types which can be placed by hand in the edit mode, non-ripe
+/// seeds are left out since they die without a mother plant and roots can
+/// only be placed in the ground
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditBulk {
+    /// A sugar bulb storing energy
+    SugarBulb,
+    /// A leaf converting light into energy
+    Leaf,
+    /// A log forming the skeleton of a plant
+    Log,
+    /// A ripe seed which falls until it lands
+    RipeSeed,
+    /// A root drawing water and nutrients from the ground
+    Root,
+    /// A flower emitting pollen
+    Flower,
+    /// A thorn protecting against grazing
+    Thorn,
+}
+
+impl EditBulk {
+    pub const COUNT: usize = 7;
+
+    /// The id to the bulk type in a list of all placeable bulk types
+    pub fn id(&self) -> usize {
+        return match self {
+            Self::SugarBulb => 0,
+            Self::Leaf => 1,
+            Self::Log => 2,
+            Self::RipeSeed => 3,
+            Self::Root => 4,
+            Self::Flower => 5,
+            Self::Thorn => 6,
+        };
+    }
+
+    /// Constructs a new placeable bulk type from an id
+    ///
+    /// # Parameters
+    ///
+    /// id: The id to construct from
+    pub fn from_id(id: usize) -> Self {
+        return match id.clamp(0, Self::COUNT - 1) {
+            0 => Self::SugarBulb,
+            1 => Self::Leaf,
+            2 => Self::Log,
+            3 => Self::RipeSeed,
+            4 => Self::Root,
+            5 => Self::Flower,
+            6 => Self::Thorn,
+            _ => panic!("EditBulk::from_id has not been updated"),
+        };
+    }
+
+    /// Gets the name of the bulk type for displaying
+    pub fn get_name(&self) -> &'static str {
+        return match self {
+            Self::SugarBulb => "Sugar bulb",
+            Self::Leaf => "Leaf",
+            Self::Log => "Log",
+            Self::RipeSeed => "Ripe seed",
+            Self::Root => "Root",
+            Self::Flower => "Flower",
+            Self::Thorn => "Thorn",
+        };
+    }
+
+    /// Gets the next bulk type
+    pub fn next(&self) -> Self {
+        return Self::from_id((self.id() + 1) % Self::COUNT);
+    }
+
+    /// Gets the previous bulk type
+    pub fn prev(&self) -> Self {
+        return Self::from_id((self.id() + (Self::COUNT - 1)) % Self::COUNT);
+    }
+
+    /// Gets the id of the bulk type used by the plants
+    fn get_bulk_id(&self) -> usize {
+        return match self {
+            Self::Log => 0,
+            Self::SugarBulb => 1,
+            Self::Leaf => 2,
+            Self::RipeSeed => 4,
+            Self::Root => 5,
+            Self::Flower => 6,
+            Self::Thorn => 7,
+        };
+    }
+}
+
+impl<S: sun::Intensity> Map<S> {
+    /// Places a new plant with the starter genome on a free tile, it starts a
+    /// lineage of its own right away, returns false if the position is
+    /// outside the map, the tile is not free or a root is placed outside the
+    /// ground
+    ///
+    /// # Parameters
+    ///
+    /// pos: The position of the tile to place the plant on
+    ///
+    /// bulk: The bulk type of the plant
+    pub fn place_plant(&mut self, pos: &types::Index, bulk: EditBulk) -> bool {
+        let Some(index) = self.get_edit_index(pos) else {
+            return false;
+        };
+        if !self.tiles[index].place_plant(bulk.get_bulk_id(), PLACED_ENERGY_CAPACITY) {
+            return false;
+        }
+        self.finish_placing(index);
+
+        return true;
+    }
+
+    /// Places a new plant with a genome read from the genome text format on a
+    /// free tile, it starts a lineage of its own right away, returns false if
+    /// the position is outside the map, the tile is not free or a root is
+    /// placed outside the ground
+    ///
+    /// # Parameters
+    ///
+    /// pos: The position of the tile to place the plant on
+    ///
+    /// text: The genome text
+    ///
+    /// bulk: The bulk type of the plant
+    ///
+    /// # Errors
+    ///
+    /// See GenomeError for a description of the different errors which may occur
+    pub fn place_genome(
+        &mut self,
+        pos: &types::Index,
+        text: &str,
+        bulk: EditBulk,
+    ) -> Result<bool, GenomeError> {
+        let Some(index) = self.get_edit_index(pos) else {
+            return Ok(false);
+        };
+        if !self.tiles[index].place_genome(text, bulk.get_bulk_id(), PLACED_ENERGY_CAPACITY)? {
+            return Ok(false);
+        }
+        self.finish_placing(index);
+
+        return Ok(true);
+    }
+
+    /// Writes the genome of the plant on a tile in the genome text format,
+    /// None if the position is outside the map or there is no fully grown
+    /// plant
+    ///
+    /// # Parameters
+    ///
+    /// pos: The position of the tile
+    ///
+    /// simplify: True if operators which can never be evaluated are left out
+    pub fn get_genome(&self, pos: &types::Index, simplify: bool) -> Option<String> {
+        return self.tiles[self.get_edit_index(pos)?].get_plant_genome(simplify);
+    }
+
+    /// Checks if all indices of the genome of the plant on a tile are in
+    /// range, None if the position is outside the map or there is no fully
+    /// grown plant
+    ///
+    /// # Parameters
+    ///
+    /// pos: The position of the tile
+    pub fn validate_genome(&self, pos: &types::Index) -> Option<Result<(), GenomeError>> {
+        return self.tiles[self.get_edit_index(pos)?].validate_plant_genome();
+    }
+
+    /// Removes the plant on a tile, returns false if the position is outside
+    /// the map or there was no plant
+    ///
+    /// # Parameters
+    ///
+    /// pos: The position of the tile to remove the plant from
+    pub fn remove_plant(&mut self, pos: &types::Index) -> bool {
+        let Some(index) = self.get_edit_index(pos) else {
+            return false;
+        };
+        if !self.tiles[index].remove_plant() {
+            return false;
+        }
+        self.mark_edited(index);
+
+        return true;
+    }
+
+    /// Registers the lineage of a newly placed plant and makes sure it is
+    /// simulated even if it was placed in a sleeping chunk
+    ///
+    /// # Parameters
+    ///
+    /// index: The index of the tile the plant was placed on
+    fn finish_placing(&mut self, index: usize) {
+        self.tiles[index].register_lineage(&mut self.genealogy, self.time);
+        self.mark_edited(index);
+    }
+
+    /// Wakes the chunk containing an edited tile and marks the tile as
+    /// changed such that the edit is simulated and shown
+    ///
+    /// # Parameters
+    ///
+    /// index: The index of the edited tile
+    fn mark_edited(&mut self, index: usize) {
+        let chunk = self
+            .chunks
+            .get_chunk_index(&TilePos::from_index(index, &self.size));
+        self.chunks.wake(chunk);
+        self.dirty.mark(index);
+    }
+
+    /// Gets the index of the tile at a position, None if the position is
+    /// outside the map
+    ///
+    /// # Parameters
+    ///
+    /// pos: The position of the tile
+    fn get_edit_index(&self, pos: &types::Index) -> Option<usize> {
+        if pos.x < 0 || pos.y < 0 || pos.x >= self.size.w as isize || pos.y >= self.size.h as isize
+        {
+            return None;
+        }
+
+        return Some(TilePos { pos: *pos }.to_index(&self.size));
+    }
+}
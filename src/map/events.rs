@@ -0,0 +1,169 @@
+use std::fmt;
+
+use rand::Rng;
+
+use super::{
+    SimRng,
+    settings::events::{Schedule, Settings},
+    sun,
+};
+
+/// A catastrophe striking the map
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Catastrophe {
+    /// The sun is darkened for a number of steps
+    Eclipse {
+        /// The number of steps the eclipse lasts
+        duration: usize,
+    },
+    /// Every plant tile above the ground in a range of columns is killed, the
+    /// range wraps around the right edge of the map
+    Wildfire {
+        /// The leftmost burned column
+        column: usize,
+        /// The number of burned columns
+        width: usize,
+    },
+    /// Leaves all over the map are killed
+    Frost,
+}
+
+impl fmt::Display for Catastrophe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Self::Eclipse { duration } => {
+                write!(f, "An eclipse darkened the sun for {} steps", duration)
+            }
+            Self::Wildfire { column, width } => write!(
+                f,
+                "A wildfire burned {} columns starting at column {}",
+                width, column
+            ),
+            Self::Frost => write!(f, "A frost killed leaves all over the map"),
+        };
+    }
+}
+
+/// Decides when catastrophes strike and remembers all catastrophes which have
+/// struck, an eclipse lasts for several steps while the other catastrophes
+/// only happen in the step they strike
+#[derive(Clone, Debug, PartialEq)]
+pub struct Catastrophes {
+    /// The first step which is no longer eclipsed
+    eclipse_end: usize,
+    /// All catastrophes which have struck together with the step they struck
+    /// in, from the oldest to the newest
+    history: Vec<(usize, Catastrophe)>,
+}
+
+impl Catastrophes {
+    /// Constructs a new record where nothing has struck yet
+    pub fn new() -> Self {
+        return Self {
+            eclipse_end: 0,
+            history: Vec::new(),
+        };
+    }
+
+    /// Finds the catastrophes striking at a time step and records them, an
+    /// eclipse striking during another eclipse extends it
+    ///
+    /// # Parameters
+    ///
+    /// settings: The catastrophe settings
+    ///
+    /// time: The time step
+    ///
+    /// width: The number of columns of the map
+    ///
+    /// rng: The random number generator of the simulation
+    pub fn strike(
+        &mut self,
+        settings: &Settings,
+        time: usize,
+        width: usize,
+        rng: &SimRng,
+    ) -> Vec<Catastrophe> {
+        let mut struck = Vec::new();
+
+        let mut rng_eclipse = rng.get(time, SimRng::STREAM_CATASTROPHE, 0);
+        if is_due(&settings.eclipse.schedule, time, &mut rng_eclipse) {
+            self.eclipse_end = self.eclipse_end.max(time + settings.eclipse.duration);
+            struck.push(Catastrophe::Eclipse {
+                duration: settings.eclipse.duration,
+            });
+        }
+
+        let mut rng_wildfire = rng.get(time, SimRng::STREAM_CATASTROPHE, 1);
+        if width > 0 && is_due(&settings.wildfire.schedule, time, &mut rng_wildfire) {
+            struck.push(Catastrophe::Wildfire {
+                column: rng_wildfire.random_range(0..width),
+                width: settings.wildfire.width.min(width),
+            });
+        }
+
+        let mut rng_frost = rng.get(time, SimRng::STREAM_CATASTROPHE, 2);
+        if is_due(&settings.frost.schedule, time, &mut rng_frost) {
+            struck.push(Catastrophe::Frost);
+        }
+
+        self.history
+            .extend(struck.iter().map(|catastrophe| (time, *catastrophe)));
+
+        return struck;
+    }
+
+    /// Darkens the sun if it is eclipsed at a time step
+    ///
+    /// # Parameters
+    ///
+    /// settings: The catastrophe settings
+    ///
+    /// time: The time step
+    ///
+    /// sun_tiles: The sun tiles for every column to darken
+    pub fn shade(&self, settings: &Settings, time: usize, sun_tiles: &mut [sun::Tile]) {
+        if !self.is_eclipsed(time) {
+            return;
+        }
+
+        let intensity = settings.eclipse.intensity.clamp(0.0, 1.0);
+        for tile in sun_tiles.iter_mut() {
+            tile.darken(intensity);
+        }
+    }
+
+    /// Checks if the sun is eclipsed at a time step
+    ///
+    /// # Parameters
+    ///
+    /// time: The time step
+    pub fn is_eclipsed(&self, time: usize) -> bool {
+        return time < self.eclipse_end;
+    }
+
+    /// Retrieves all catastrophes which have struck together with the step
+    /// they struck in, from the oldest to the newest
+    pub fn get_history(&self) -> &[(usize, Catastrophe)] {
+        return &self.history;
+    }
+}
+
+/// Checks if a catastrophe strikes at a time step, either because it is on
+/// the schedule or at random
+///
+/// # Parameters
+///
+/// schedule: When the catastrophe happens
+///
+/// time: The time step
+///
+/// rng: The random number generator to draw the random strike from
+fn is_due(schedule: &Schedule, time: usize, rng: &mut impl Rng) -> bool {
+    let scheduled = schedule.interval > 0
+        && time >= schedule.start
+        && (time - schedule.start).is_multiple_of(schedule.interval);
+    let probability = schedule.probability.clamp(0.0, 1.0);
+
+    return scheduled || (probability > 0.0 && rng.random_bool(probability));
+}
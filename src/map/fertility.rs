@@ -0,0 +1,137 @@
+use rand::Rng;
+
+use crate::{constants, types};
+
+use super::{SimRng, TilePos, settings::fertility::Settings};
+
+/// Generates the fertility of every tile as a sum of octaves of 2D Perlin
+/// noise, the noise is periodic in x such that the fertile patches wrap
+/// around like the map does
+///
+/// # Parameters
+///
+/// settings: The fertility settings
+///
+/// size: The size of the map
+///
+/// rng: The random number generator of the simulation
+pub fn new_fertility_field(settings: &Settings, size: &types::ISize, rng: &SimRng) -> Vec<f64> {
+    if settings.strength <= 0.0 || settings.octaves == 0 {
+        return vec![1.0; size.w * size.h];
+    }
+
+    let octaves = (0..settings.octaves)
+        .map(|octave| Octave::new(settings.scale, size, octave, rng))
+        .collect::<Vec<_>>();
+    let amplitude = octaves.iter().map(|octave| octave.amplitude).sum::<f64>();
+
+    return (0..size.w * size.h)
+        .map(|index| {
+            let pos = TilePos::from_index(index, size).pos;
+            let noise = octaves
+                .iter()
+                .map(|octave| octave.amplitude * octave.get(pos.x as f64, pos.y as f64))
+                .sum::<f64>()
+                / amplitude;
+
+            // 2D Perlin noise lies between -1 / sqrt(2) and 1 / sqrt(2)
+            let noise = (noise * constants::MATH_SQRT_2).clamp(-1.0, 1.0);
+            return 1.0 + settings.strength * noise;
+        })
+        .collect();
+}
+
+/// A single layer of 2D Perlin noise
+struct Octave {
+    /// The size of a lattice cell in tiles
+    cell_size: f64,
+    /// The number of lattice cells in x, the lattice wraps around in x
+    cells_x: usize,
+    /// The number of lattice points in y
+    points_y: usize,
+    /// The gradient at every lattice point in a row first order
+    gradients: Vec<(f64, f64)>,
+    /// The weight of this layer in the sum
+    amplitude: f64,
+}
+
+impl Octave {
+    /// Constructs a new layer of noise with random gradients
+    ///
+    /// # Parameters
+    ///
+    /// scale: The size of the lattice cells of the first octave in tiles
+    ///
+    /// size: The size of the map
+    ///
+    /// octave: The index of this layer, each layer halves the cell size
+    ///
+    /// rng: The random number generator of the simulation
+    fn new(scale: f64, size: &types::ISize, octave: usize, rng: &SimRng) -> Self {
+        let factor = 0.5_f64.powi(octave as i32);
+        let cells_x = (size.w as f64 / (scale * factor)).round().max(1.0) as usize;
+        let cell_size = size.w as f64 / cells_x as f64;
+        let points_y = (size.h as f64 / cell_size).ceil() as usize + 1;
+
+        let mut rng = rng.get(0, SimRng::STREAM_FERTILITY, octave);
+        let gradients = (0..cells_x * points_y)
+            .map(|_| {
+                let angle = rng.random_range(0.0..2.0 * constants::MATH_PI);
+                (angle.cos(), angle.sin())
+            })
+            .collect();
+
+        return Self {
+            cell_size,
+            cells_x,
+            points_y,
+            gradients,
+            amplitude: factor,
+        };
+    }
+
+    /// Evaluates the noise at a position in tiles
+    ///
+    /// # Parameters
+    ///
+    /// x: The x-coordinate
+    ///
+    /// y: The y-coordinate
+    fn get(&self, x: f64, y: f64) -> f64 {
+        let x = x / self.cell_size;
+        let y = y / self.cell_size;
+        let cell_x = x.floor();
+        let cell_y = y.floor();
+        let fraction_x = x - cell_x;
+        let fraction_y = y - cell_y;
+
+        // The contribution of a lattice point is its gradient dotted with the
+        // offset from the point
+        let index_x = cell_x as usize % self.cells_x;
+        let index_y = (cell_y as usize).min(self.points_y - 2);
+        let dot = |offset_x: usize, offset_y: usize| {
+            let point_x = (index_x + offset_x) % self.cells_x;
+            let point_y = index_y + offset_y;
+            let gradient = self.gradients[point_y * self.cells_x + point_x];
+            return gradient.0 * (fraction_x - offset_x as f64)
+                + gradient.1 * (fraction_y - offset_y as f64);
+        };
+
+        // Blend the contributions of the four surrounding points smoothly
+        let fade_x = fade(fraction_x);
+        let fade_y = fade(fraction_y);
+        let bottom = dot(0, 0) + fade_x * (dot(1, 0) - dot(0, 0));
+        let top = dot(0, 1) + fade_x * (dot(1, 1) - dot(0, 1));
+
+        return bottom + fade_y * (top - bottom);
+    }
+}
+
+/// The smooth step used to blend the lattice points
+///
+/// # Parameters
+///
+/// t: The fraction of the cell to blend at
+pub(super) fn fade(t: f64) -> f64 {
+    return t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+}
@@ -0,0 +1,121 @@
+/// The record of a single lineage, a lineage is started by every seed which
+/// takes root and is shared by all plant tiles grown from it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Lineage {
+    /// The unique id of the lineage
+    pub id: usize,
+    /// The id of the lineage of the mother plant, None for plants which were
+    /// placed directly on the map
+    pub parent: Option<usize>,
+    /// The simulation step at which the first tile of the lineage was built
+    pub birth_time: usize,
+    /// A hash of the genome of the lineage, equal genomes have equal hashes
+    pub genome_hash: u64,
+}
+
+/// The registry of all lineages which have existed on a map
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Genealogy {
+    /// All lineages where the id of a lineage is its index
+    lineages: Vec<Lineage>,
+}
+
+impl Genealogy {
+    /// Constructs a new empty registry
+    pub fn new() -> Self {
+        return Self {
+            lineages: Vec::new(),
+        };
+    }
+
+    /// Registers a new lineage and returns its id
+    ///
+    /// # Parameters
+    ///
+    /// parent: The id of the lineage of the mother plant if there is any
+    ///
+    /// birth_time: The simulation step at which the lineage started
+    ///
+    /// genome_hash: The hash of the genome of the lineage
+    pub fn register(
+        &mut self,
+        parent: Option<usize>,
+        birth_time: usize,
+        genome_hash: u64,
+    ) -> usize {
+        let id = self.lineages.len();
+        self.lineages.push(Lineage {
+            id,
+            parent,
+            birth_time,
+            genome_hash,
+        });
+
+        return id;
+    }
+
+    /// Retrieves the number of lineages registered
+    pub fn len(&self) -> usize {
+        return self.lineages.len();
+    }
+
+    /// Checks if no lineages have been registered
+    pub fn is_empty(&self) -> bool {
+        return self.lineages.is_empty();
+    }
+
+    /// Retrieves a single lineage, None if it does not exist
+    ///
+    /// # Parameters
+    ///
+    /// id: The id of the lineage
+    pub fn get(&self, id: usize) -> Option<&Lineage> {
+        return self.lineages.get(id);
+    }
+
+    /// Retrieves all lineages in the order they were registered
+    pub fn get_all(&self) -> &[Lineage] {
+        return &self.lineages;
+    }
+
+    /// Retrieves the ancestry of a lineage starting with the lineage itself
+    /// and ending with the founder it descends from, empty if the lineage
+    /// does not exist
+    ///
+    /// # Parameters
+    ///
+    /// id: The id of the lineage
+    pub fn get_ancestry(&self, id: usize) -> Vec<&Lineage> {
+        let mut ancestry = Vec::new();
+        let mut next = self.get(id);
+        while let Some(lineage) = next {
+            ancestry.push(lineage);
+            next = lineage.parent.and_then(|parent| self.get(parent));
+        }
+
+        return ancestry;
+    }
+
+    /// Retrieves the founder a lineage descends from, None if the lineage does
+    /// not exist
+    ///
+    /// # Parameters
+    ///
+    /// id: The id of the lineage
+    pub fn get_founder(&self, id: usize) -> Option<&Lineage> {
+        return self.get_ancestry(id).last().copied();
+    }
+
+    /// Retrieves all lineages started by seeds of a lineage
+    ///
+    /// # Parameters
+    ///
+    /// id: The id of the parent lineage
+    pub fn get_children(&self, id: usize) -> Vec<&Lineage> {
+        return self
+            .lineages
+            .iter()
+            .filter(|lineage| lineage.parent == Some(id))
+            .collect();
+    }
+}
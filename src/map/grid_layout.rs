@@ -1,23 +1,211 @@
-/// All data for the layout of the grid
+use std::fmt::Debug;
+
+use dyn_clone::DynClone;
+
+use crate::{
+    constants::{MATH_PI, MATH_SQRT_3},
+    types,
+};
+
+/// The layout of the tiles of the grid in world coordinates. The rows are
+/// evenly spaced while the columns may be drawn with different widths, the
+/// map must keep a width of one per column such that it wraps in the same
+/// way for every layout. The positioning must match the shader
+pub trait GridLayout: DynClone + Debug {
+    /// Gets the number of columns in the grid
+    fn get_n_columns(&self) -> usize;
+
+    /// Converts an x-coordinate in the grid, where every column is one wide,
+    /// to world coordinates, it must be increasing and moving by the map
+    /// width in the grid must move by the map width in world coordinates
+    ///
+    /// # Parameters
+    ///
+    /// x: The x-coordinate in the grid
+    fn to_world_x(&self, x: f64) -> f64;
+
+    /// Converts an x-coordinate in world coordinates to the grid, the inverse
+    /// of to_world_x
+    ///
+    /// # Parameters
+    ///
+    /// x: The x-coordinate in world coordinates
+    fn to_grid_x(&self, x: f64) -> f64;
+
+    /// Constructs the shader compatible version of the grid layout
+    fn get_data(&self) -> UniformGridLayout;
+
+    /// Gets the center of a tile in world coordinates
+    ///
+    /// # Parameters
+    ///
+    /// index: The position of the tile in the grid
+    fn get_center(&self, index: &types::Index) -> types::Point {
+        let center = get_grid_center(index);
+
+        return types::Point::new(self.to_world_x(center.x), center.y);
+    }
+
+    /// Gets the position of the tile containing a point in world coordinates,
+    /// the x-index is wrapped into the grid while the y-index is not bounded
+    ///
+    /// # Parameters
+    ///
+    /// point: The point in world coordinates
+    fn get_index(&self, point: &types::Point) -> types::Index {
+        // The containing hexagon is the one with the closest center in the
+        // grid, it must be among the tiles around the nearest row and column
+        let point = types::Point::new(self.to_grid_x(point.x), point.y);
+        let row = (-point.y / (0.5 * MATH_SQRT_3)).round() as isize;
+        let index = (row - 1..=row + 1)
+            .flat_map(|y| {
+                let column = (point.x - 0.5 * y.rem_euclid(2) as f64).round() as isize;
+                return (column - 1..=column + 1).map(move |x| types::Index::new(x, y));
+            })
+            .min_by(|lhs, rhs| {
+                let lhs_distance = (get_grid_center(lhs) - point).norm_squared();
+                let rhs_distance = (get_grid_center(rhs) - point).norm_squared();
+                return lhs_distance.total_cmp(&rhs_distance);
+            })
+            .unwrap();
+
+        return types::Index::new(index.x.rem_euclid(self.get_n_columns() as isize), index.y);
+    }
+
+    /// Constructs the shader compatible version of the grid layout where
+    /// every tile is moved by a multiple of the map width to be as close as
+    /// possible to a center, such that the map only has to be drawn once when
+    /// the view is narrower than the map
+    ///
+    /// # Parameters
+    ///
+    /// center: The x-coordinate in world coordinates to wrap around
+    fn get_data_wrapped(&self, center: f64) -> UniformGridLayout {
+        return UniformGridLayout {
+            wrap: 1,
+            wrap_center: self.to_grid_x(center) as f32,
+            ..self.get_data()
+        };
+    }
+}
+
+dyn_clone::clone_trait_object!(GridLayout);
+
+/// A grid where all columns are equally wide
 #[derive(Copy, Clone, Debug)]
-pub struct GridLayout {
+pub struct EvenColumns {
     /// The number of columns in the grid
     pub n_columns: usize,
 }
 
-impl GridLayout {
-    /// Constructs the shader compatible version off a grid layout
-    pub fn get_data(&self) -> UniformGridLayout {
+impl GridLayout for EvenColumns {
+    fn get_n_columns(&self) -> usize {
+        return self.n_columns;
+    }
+
+    fn to_world_x(&self, x: f64) -> f64 {
+        return x;
+    }
+
+    fn to_grid_x(&self, x: f64) -> f64 {
+        return x;
+    }
+
+    fn get_data(&self) -> UniformGridLayout {
         return UniformGridLayout {
             n_columns: self.n_columns as u32,
+            wrap: 0,
+            wrap_center: 0.0,
+            stretch: 0.0,
         };
     }
 }
 
+/// A grid where the columns are widest at the left and right edge of the map
+/// and narrowest in the middle, the width changes smoothly along a cosine
+/// such that the map still wraps seamlessly
+#[derive(Copy, Clone, Debug)]
+pub struct StretchedColumns {
+    /// The number of columns in the grid
+    pub n_columns: usize,
+    /// How much wider the columns at the edges are than the average column
+    /// relative to the average width, the columns in the middle are narrower
+    /// by the same amount, must be between -1 and 1
+    pub stretch: f64,
+}
+
+impl StretchedColumns {
+    /// Gets the largest distance between an x-coordinate in the grid and in
+    /// world coordinates
+    fn get_amplitude(&self) -> f64 {
+        return self.stretch.abs() * self.n_columns as f64 / (2.0 * MATH_PI);
+    }
+}
+
+impl GridLayout for StretchedColumns {
+    fn get_n_columns(&self) -> usize {
+        return self.n_columns;
+    }
+
+    fn to_world_x(&self, x: f64) -> f64 {
+        // The edges of the map are half a column left of the first column
+        let width = self.n_columns as f64;
+        let phase = 2.0 * MATH_PI * (x + 0.5) / width;
+
+        return x + self.stretch * width / (2.0 * MATH_PI) * phase.sin();
+    }
+
+    fn to_grid_x(&self, x: f64) -> f64 {
+        // The conversion is increasing and moves by at most the amplitude so
+        // the inverse is found by bisection
+        let amplitude = self.get_amplitude();
+        let mut low = x - amplitude;
+        let mut high = x + amplitude;
+        for _ in 0..64 {
+            let middle = 0.5 * (low + high);
+            if self.to_world_x(middle) < x {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+
+        return 0.5 * (low + high);
+    }
+
+    fn get_data(&self) -> UniformGridLayout {
+        return UniformGridLayout {
+            n_columns: self.n_columns as u32,
+            wrap: 0,
+            wrap_center: 0.0,
+            stretch: self.stretch as f32,
+        };
+    }
+}
+
+/// Gets the center of a tile in the grid where every column is one wide
+///
+/// # Parameters
+///
+/// index: The position of the tile in the grid
+fn get_grid_center(index: &types::Index) -> types::Point {
+    return types::Point::new(
+        index.x as f64 + 0.5 * index.y.rem_euclid(2) as f64,
+        -0.5 * MATH_SQRT_3 * index.y as f64,
+    );
+}
+
 /// All data for the layout of the grid
 #[repr(C)]
 #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct UniformGridLayout {
-    // The base color to scale
+    /// The number of columns in the grid
     pub n_columns: u32,
+    /// If not 0 then the tiles are wrapped around the wrap center
+    pub wrap: u32,
+    /// The x-coordinate in the grid to wrap around
+    pub wrap_center: f32,
+    /// How much wider the columns at the edges are than the average column,
+    /// 0 for equally wide columns
+    pub stretch: f32,
 }
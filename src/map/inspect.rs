@@ -0,0 +1,188 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types;
+
+/// A snapshot of the state of a single tile for inspection
+#[derive(Clone, Debug, PartialEq)]
+pub struct TileInfo {
+    /// The position of the tile
+    pub pos: types::Index,
+    /// The light level of the tile
+    pub light: f64,
+    /// The part of the light level which is diffuse light scattered by the
+    /// sky
+    pub diffuse: f64,
+    /// The light transparency of the tile
+    pub transparency: f64,
+    /// The water level of the tile
+    pub water: f64,
+    /// True if the tile is part of the ground
+    pub ground: bool,
+    /// True if the tile is part of the terrain raised above the ground
+    pub terrain: bool,
+    /// True if the tile is too steep for plants to grow on
+    pub barren: bool,
+    /// The nutrient concentration of the tile
+    pub nutrients: f64,
+    /// The temperature of the tile
+    pub temperature: f64,
+    /// The amount of detritus left behind by a dead plant
+    pub detritus: f64,
+    /// The factor scaling the energy gained by leaves on the tile
+    pub fertility: f64,
+    /// The plant on the tile if there is any
+    pub plant: Option<PlantInfo>,
+}
+
+impl fmt::Display for TileInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Tile ({}, {})", self.pos.x, self.pos.y)?;
+        writeln!(f, "  Light: {:.4}", self.light)?;
+        if self.diffuse > 0.0 {
+            writeln!(f, "  Diffuse light: {:.4}", self.diffuse)?;
+        }
+        writeln!(f, "  Transparency: {:.4}", self.transparency)?;
+        writeln!(f, "  Water: {:.4}", self.water)?;
+        write!(f, "  Temperature: {:.2}", self.temperature)?;
+        if self.ground {
+            write!(f, "\n  Nutrients: {:.4}", self.nutrients)?;
+        }
+        if self.barren {
+            write!(f, "\n  Terrain: Too steep for plants")?;
+        } else if self.terrain {
+            write!(f, "\n  Terrain: Raised ground")?;
+        }
+        if self.fertility != 1.0 {
+            write!(f, "\n  Fertility: {:.4}", self.fertility)?;
+        }
+        if self.detritus > 0.0 {
+            write!(f, "\n  Detritus: {:.4}", self.detritus)?;
+        }
+        if let Some(plant) = &self.plant {
+            write!(f, "\n{}", plant)?;
+        } else {
+            write!(f, "\n  Plant: None")?;
+        }
+
+        return Ok(());
+    }
+}
+
+/// The few values of a tile needed to draw it, cheap enough to collect for
+/// every tile at every step
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TileSummary {
+    /// The light level of the tile
+    pub light: f64,
+    /// True if the tile is part of the ground
+    pub ground: bool,
+    /// True if there is a plant on the tile, even one still being built
+    pub occupied: bool,
+    /// The energy of the plant on the tile, None if there is no fully grown
+    /// plant
+    pub energy: Option<f64>,
+    /// The lineage of the plant on the tile, None if there is no fully grown
+    /// living plant
+    pub lineage: Option<usize>,
+}
+
+/// A snapshot of the state of a single plant tile for inspection
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlantInfo {
+    /// The name of the bulk type
+    pub bulk: &'static str,
+    /// True if the plant is still being built and not yet occupying the tile
+    pub building: bool,
+    /// True if the plant is alive
+    pub alive: bool,
+    /// True if the plant tile is the root of its plant
+    pub root: bool,
+    /// The energy in the plant tile
+    pub energy: f64,
+    /// The maximum amount of energy allowed
+    pub energy_capacity: f64,
+    /// The age of the plant tile in simulation steps
+    pub age: usize,
+    /// The cumulative age of the entire plant in simulation steps
+    pub cum_age: usize,
+    /// The id of the lineage of the plant, None if not registered yet
+    pub lineage: Option<usize>,
+    /// The id of the species of the lineage, None if it has not been
+    /// clustered yet
+    pub species: Option<usize>,
+    /// All bridges connecting the plant tile to its neighbors
+    pub bridges: Vec<BridgeInfo>,
+}
+
+impl fmt::Display for PlantInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "  Plant: {} ({}, {}{})",
+            self.bulk,
+            if self.building {
+                "building"
+            } else {
+                "occupied"
+            },
+            if self.alive { "alive" } else { "dead" },
+            if self.root { ", root" } else { "" }
+        )?;
+        writeln!(
+            f,
+            "  Energy: {:.4} / {:.4}",
+            self.energy, self.energy_capacity
+        )?;
+        writeln!(f, "  Age: {} (plant age {})", self.age, self.cum_age)?;
+        match self.lineage {
+            Some(lineage) => writeln!(f, "  Lineage: {}", lineage)?,
+            None => writeln!(f, "  Lineage: Unregistered")?,
+        }
+        if let Some(species) = self.species {
+            writeln!(f, "  Species: {}", species)?;
+        }
+        write!(f, "  Bridges:")?;
+        if self.bridges.is_empty() {
+            write!(f, " None")?;
+        }
+        for bridge in self.bridges.iter() {
+            write!(f, "\n    {}", bridge)?;
+        }
+
+        return Ok(());
+    }
+}
+
+/// A snapshot of a single bridge for inspection
+#[derive(Clone, Debug, PartialEq)]
+pub struct BridgeInfo {
+    /// The name of the direction of the bridge
+    pub direction: &'static str,
+    /// The name of the bridge type
+    pub bridge: &'static str,
+    /// True if this plant tile is the mother of the neighbor
+    pub exiting: bool,
+    /// The maximum amount of energy able to transfer per step
+    pub energy_capacity: f64,
+    /// The maximum amount of water able to transfer per step
+    pub water_capacity: f64,
+    /// The total energy moved through the bridge since it was built
+    pub throughput: f64,
+}
+
+impl fmt::Display for BridgeInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(
+            f,
+            "{}: {} to {}, capacity {:.4}, water capacity {:.4}, throughput {:.4}",
+            self.direction,
+            self.bridge,
+            if self.exiting { "child" } else { "mother" },
+            self.energy_capacity,
+            self.water_capacity,
+            self.throughput
+        );
+    }
+}
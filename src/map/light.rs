@@ -0,0 +1,34 @@
+use std::fmt::Debug;
+
+use dyn_clone::DynClone;
+
+use crate::types;
+
+/// Computes the light of every tile for the next step in place of the tiles
+/// themselves, such that the light pass can be moved to other hardware like
+/// the gpu
+pub trait LightSolver: DynClone + Debug {
+    /// Computes the light of every tile for the next step from the light and
+    /// transparency of the current step, all lists are in the order of the
+    /// tiles of the map, returns None if the light could not be computed and
+    /// the tiles must compute it themselves
+    ///
+    /// # Parameters
+    ///
+    /// size: The size of the map
+    ///
+    /// light: The light of every tile
+    ///
+    /// transparency: The transparency of every tile
+    ///
+    /// sun: The intensity of the sun above every column
+    fn solve(
+        &mut self,
+        size: &types::ISize,
+        light: &[f32],
+        transparency: &[f32],
+        sun: &[f32],
+    ) -> Option<Vec<f32>>;
+}
+
+dyn_clone::clone_trait_object!(LightSolver);
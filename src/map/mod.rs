@@ -1,34 +1,133 @@
-use crate::types;
+use std::{collections::BTreeMap, mem, ops::Range};
+
+use rand::{Rng, seq::SliceRandom};
+
+use crate::{constants, types};
 
 pub mod sun;
 
 mod data_mode;
-pub use data_mode::DataModeBackground;
+pub use data_mode::{DataModeBackground, DataModePlant};
 
 mod tile;
-pub use tile::InstanceTile;
-use tile::{Tile, TileNeighbors, TilePos};
+pub use tile::{GenomeError, InstanceBridge, InstanceTile, InstanceTransfer};
+use tile::{NeighborDirection, Plant, Tile, TileNeighbors, TilePos, TilePosNeighbor};
 
 pub mod settings;
 
+pub mod presets;
+
 mod grid_layout;
-pub use grid_layout::{GridLayout, UniformGridLayout};
+pub use grid_layout::{EvenColumns, GridLayout, StretchedColumns, UniformGridLayout};
+
+mod annotation;
+pub use annotation::{Annotation, AnnotationSet};
+
+mod genealogy;
+pub use genealogy::{Genealogy, Lineage};
+
+mod statistics;
+pub use statistics::{ColumnProfile, SpeciesStatistics, Statistics};
+
+mod balance;
+pub use balance::EnergyBalance;
+
+mod chunk;
+use chunk::ChunkSet;
+
+mod dirty;
+use dirty::DirtyTiles;
+
+mod rng;
+pub use rng::SimRng;
+
+mod inspect;
+pub use inspect::{BridgeInfo, PlantInfo, TileInfo, TileSummary};
+
+mod view;
+pub use view::TileView;
+
+pub mod diff;
+
+mod weather;
+use weather::Weather;
+
+mod pollen;
+use pollen::PollenField;
+
+mod fertility;
+use fertility::new_fertility_field;
+
+mod terrain;
+use terrain::{new_relief, new_steep_columns};
+
+mod shading;
+use shading::new_foreign_shade_field;
+
+mod species;
+use species::SpeciesSet;
+
+pub mod events;
+use events::{Catastrophe, Catastrophes};
+
+mod edit;
+pub use edit::EditBulk;
+
+mod light;
+pub use light::LightSolver;
+
+mod observer;
+pub use observer::{MapObserver, PlantEvent};
+
+#[cfg(test)]
+pub(crate) mod testing;
 
 /// Describes the entire map
 #[derive(Clone, Debug)]
 pub struct Map<S: sun::Intensity> {
     /// All the tiles in a row first, left to right, bottom to top order
     tiles: Vec<Tile>,
+    /// The buffer the next state of the tiles is written into during a step,
+    /// it is swapped with the tiles afterwards such that no new list of tiles
+    /// is allocated every step
+    tiles_back: Vec<Tile>,
     /// The intensity of the sun at each column in the range 0 to 1
     sun_tiles: Vec<sun::Tile>,
     /// The state of the sun
     sun: sun::State<S>,
+    /// The clouds shading the sun
+    weather: Weather,
+    /// The pollen drifting between flowers
+    pollen: PollenField,
     /// The size of the grid
     size: types::ISize,
     /// The simulation settings of the map
     settings: settings::Settings,
     /// The current iteration time step
     time: usize,
+    /// The energy entering and leaving the plants during the last step
+    balance: EnergyBalance,
+    /// All user notes pinned to tiles
+    annotations: AnnotationSet,
+    /// The registry of all lineages which have existed on the map
+    genealogy: Genealogy,
+    /// The species the living lineages were clustered into at the last
+    /// clustering
+    species: SpeciesSet,
+    /// The catastrophes which have struck the map
+    catastrophes: Catastrophes,
+    /// The activity state of all chunks of tiles
+    chunks: ChunkSet,
+    /// The tiles which have changed since their data was last uploaded to the
+    /// gpu
+    dirty: DirtyTiles,
+    /// The source of all randomness in the simulation
+    rng: SimRng,
+    /// Computes the light of the tiles in place of the tiles themselves if
+    /// set
+    light_solver: Option<Box<dyn LightSolver>>,
+    /// All observers receiving the events of the simulation
+    observers: Vec<Box<dyn MapObserver>>,
 }
 
 impl<S: sun::Intensity> Map<S> {
@@ -45,50 +144,686 @@ impl<S: sun::Intensity> Map<S> {
         // Set the map size for the sun intensities
         sun_intensity.set_size(size.w);
 
-        let tiles = (0..size.w * size.h).map(|_| Tile::new()).collect();
-        let sun_tiles = (0..size.w).map(|_| sun::Tile::new(0.0)).collect();
+        // The bottom rows are ground and start with full nutrients, rows are
+        // counted downwards from the sun, the terrain raises the ground above
+        // them. The fertility and terrain are drawn from the seed once and
+        // never change
+        let rng = SimRng::new(settings.seed);
+        let base = size.h.saturating_sub(settings.nutrients.depth);
+        let fertility = new_fertility_field(&settings.fertility, &size, &rng);
+        let relief = new_relief(&settings.terrain, size.w, &rng);
+        let steep = new_steep_columns(&settings.terrain, &relief, &settings.wrap);
+        let tiles: Vec<Tile> = (0..size.w * size.h)
+            .map(|index| {
+                let pos = TilePos::from_index(index, &size).pos;
+                let (row, column) = (pos.y as usize, pos.x as usize);
+                let surface = base.saturating_sub(relief[column]);
+                let tile = if row >= base {
+                    Tile::new_ground(settings.nutrients.capacity)
+                } else if row >= surface {
+                    Tile::new_ground(settings.nutrients.capacity).with_terrain(steep[column])
+                } else {
+                    Tile::new()
+                };
+                tile.with_altitude(surface.saturating_sub(row), &settings)
+                    .with_fertility(fertility[index])
+            })
+            .collect();
+        let sun_tiles = (0..size.w).map(|_| sun::Tile::new(0.0, 0.0)).collect();
         let sun = sun::State::new(sun_intensity);
+        let weather = Weather::new(size.w);
+        let chunks = ChunkSet::new(&size, settings.chunk.size);
+        let dirty = DirtyTiles::new(tiles.len());
 
         return Self {
+            tiles_back: tiles.clone(),
             tiles,
             sun_tiles,
             sun,
+            weather,
+            pollen: PollenField::new(),
             size,
             settings,
             time: 0,
+            balance: EnergyBalance::new(),
+            annotations: AnnotationSet::new(),
+            genealogy: Genealogy::new(),
+            species: SpeciesSet::new(),
+            catastrophes: Catastrophes::new(),
+            chunks,
+            dirty,
+            rng,
+            light_solver: None,
+            observers: Vec::new(),
         };
     }
 
     /// Steps the simulation once
+    #[tracing::instrument(skip_all, fields(time = self.time))]
     pub fn step(&mut self) {
-        // Set the new sun tile values
-        self.sun_tiles = self.sun.get_tiles(self.time);
+        // Remember the stored energy to check the energy balance against
+        let stored = if self.settings.audit.enabled {
+            self.get_stored_energy()
+        } else {
+            0.0
+        };
 
-        // Update the grid
-        self.tiles = self
+        // Set the new sun tile values and let the clouds shade them
+        let sun_previous = mem::replace(&mut self.sun_tiles, self.sun.get_tiles(self.time));
+        self.weather
+            .update(&self.settings.weather, self.time, &self.rng);
+        self.weather
+            .shade(&self.settings.weather, &mut self.sun_tiles);
+
+        // Find the catastrophes striking in this step, an eclipse darkens the
+        // sun while the other catastrophes strike after the tiles are updated
+        let struck =
+            self.catastrophes
+                .strike(&self.settings.events, self.time, self.size.w, &self.rng);
+        self.catastrophes
+            .shade(&self.settings.events, self.time, &mut self.sun_tiles);
+        let sun_changed = self
+            .sun_tiles
+            .iter()
+            .zip(sun_previous.iter())
+            .map(|(tile, previous)| {
+                tile.intensity != previous.intensity
+                    || (self.settings.sky.angled && tile.angle != previous.angle)
+            })
+            .collect::<Vec<_>>();
+
+        // Update the grid, sleeping chunks only need their light updated and
+        // static chunks are skipped since their tiles in the back buffer are
+        // already identical to the current tiles
+        let sleep_delay = if self.settings.chunk.sleep {
+            self.settings.chunk.sleep_delay
+        } else {
+            usize::MAX
+        };
+        let static_chunks =
+            self.chunks
+                .get_static(sleep_delay, &self.size, &self.settings.wrap, &sun_changed);
+        let priorities = self.get_update_priorities();
+        let light = self.solve_light();
+        let mut costs = vec![0; self.chunks.get_chunk_count()];
+        let mut balance = EnergyBalance::new();
+        let mut room = self.get_plant_room();
+        let tiles_span = tracing::debug_span!("tiles").entered();
+        for (index, (tile, next)) in self
             .tiles
             .iter()
+            .zip(self.tiles_back.iter_mut())
             .enumerate()
-            .map(|(index, tile)| {
-                tile.forward(
-                    &self.settings,
-                    &TileNeighbors::new(
-                        &self.tiles,
-                        &self.sun_tiles,
-                        &self.size,
-                        &TilePos::from_index(index, &self.size),
-                    ),
+        {
+            let pos = TilePos::from_index(index, &self.size);
+            let chunk = self.chunks.get_chunk_index(&pos);
+            if static_chunks[chunk] {
+                continue;
+            }
+            let neighbors = tracing::trace_span!("neighbors").in_scope(|| {
+                TileNeighbors::new(
+                    &self.tiles,
+                    &self.sun_tiles,
+                    &self.size,
+                    &self.settings.wrap,
+                    &pos,
+                    &priorities,
                 )
-            })
-            .collect();
+            });
+            let light = light.as_ref().map(|light| light[index] as f64);
+            *next = if self.chunks.is_awake(chunk, sleep_delay) {
+                costs[chunk] += if tile.has_plant() {
+                    ChunkSet::COST_PLANT
+                } else {
+                    ChunkSet::COST_EMPTY
+                };
+                let mut rng = self.rng.get(self.time, SimRng::STREAM_TILE, index);
+                tile.forward(&self.settings, &neighbors, light, &mut rng, &mut balance)
+            } else {
+                tile.forward_light_only(&self.settings, &neighbors, light)
+            };
+
+            // New plant tiles are only started while the plant cap is not
+            // reached, the mother gets the energy back when the spread fails
+            if next.has_plant() && !tile.has_plant() {
+                if room == 0 {
+                    next.remove_plant();
+                } else {
+                    room -= 1;
+                }
+            }
+        }
+        tiles_span.exit();
+        mem::swap(&mut self.tiles, &mut self.tiles_back);
+        self.balance = balance;
+
+        // Find the changes of the plants before seeds start falling such that
+        // ripened seeds are found where they ripened
+        let mut events = if self.observers.is_empty() {
+            Vec::new()
+        } else {
+            self.tiles
+                .iter()
+                .zip(self.tiles_back.iter())
+                .enumerate()
+                .filter_map(|(index, (tile, previous))| {
+                    tile.get_plant_event(previous).map(|event| (index, event))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Move all falling seeds now that every tile has been updated
+        self.resolve_falling(&priorities);
+
+        // Let the pollen drift and fertilize flowers before new pollen is
+        // emitted
+        self.update_pollen();
+
+        // Let herbivores eat from the plants
+        self.graze();
+
+        // Kill the plants struck by catastrophes, they are removed in the next
+        // step like any other dead plant
+        let killed = self.apply_catastrophes(&struck);
+        if !self.observers.is_empty() {
+            events.extend(killed.into_iter().map(|index| (index, PlantEvent::Died)));
+        }
+
+        // Let fresh plants appear next to the ground
+        let spawned = self.spawn_plants();
+        if !self.observers.is_empty() {
+            events.extend(spawned.into_iter().map(|index| (index, PlantEvent::Born)));
+            events.sort_by_key(|(index, _)| *index);
+        }
+
+        // Give all newly built seeds and placed plants their own lineage, in
+        // index order such that ids are deterministic
+        let time = self.time;
+        self.tiles
+            .iter_mut()
+            .for_each(|tile| tile.register_lineage(&mut self.genealogy, time));
+
+        // Find all tiles which are shown differently after the step
+        for (index, (tile, previous)) in self.tiles.iter().zip(self.tiles_back.iter()).enumerate() {
+            if tile.has_changed_appearance(previous) {
+                self.dirty.mark(index);
+            }
+        }
+
+        // Update which chunks are active and which have changed
+        let _chunks_span = tracing::debug_span!("chunks").entered();
+        self.chunks.update(
+            &self.tiles,
+            &self.tiles_back,
+            &self.size,
+            &costs,
+            sleep_delay,
+            &self.settings.wrap,
+        );
 
         // Update the time
         self.time += 1;
+
+        // Cluster the living lineages into species every interval
+        let interval = self.settings.species.interval;
+        if interval > 0 && self.time.is_multiple_of(interval) {
+            self.update_species();
+        }
+
+        // Make sure no energy appeared or vanished during the step
+        if self.settings.audit.enabled {
+            self.audit_energy(stored);
+        }
+
+        // Tell all observers what happened during the step
+        self.notify_observers(&events);
+    }
+
+    /// Steps the simulation a number of times
+    ///
+    /// # Parameters
+    ///
+    /// n: The number of steps to take
+    pub fn step_n(&mut self, n: usize) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+
+    /// Sets the solver computing the light of the tiles in place of the tiles
+    /// themselves, None to let the tiles compute it
+    ///
+    /// # Parameters
+    ///
+    /// light_solver: The new light solver
+    pub fn set_light_solver(&mut self, light_solver: Option<Box<dyn LightSolver>>) {
+        self.light_solver = light_solver;
+        self.chunks.wake_all();
+    }
+
+    /// Replaces the simulation settings in the middle of a run, settings only
+    /// used when constructing the map like the ground depth, the chunk size or
+    /// the fertility keep their original effect and the wrapping of the map
+    /// is kept
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new simulation settings
+    pub fn set_settings(&mut self, settings: settings::Settings) {
+        self.rng = SimRng::new(settings.seed);
+        self.settings = settings.with_wrap(self.settings.wrap);
+        self.chunks.wake_all();
+    }
+
+    /// Adds an observer receiving the events of the simulation after every
+    /// step
+    ///
+    /// # Parameters
+    ///
+    /// observer: The observer to add
+    pub fn add_observer(&mut self, observer: Box<dyn MapObserver>) {
+        self.observers.push(observer);
+    }
+
+    /// Removes all observers
+    pub fn clear_observers(&mut self) {
+        self.observers.clear();
     }
 
-    /// Retrieves the grid layout of the map
-    pub fn get_grid_layout(&self) -> GridLayout {
-        return GridLayout {
+    /// Sends the events of the last step to all observers followed by the
+    /// completion of the step
+    ///
+    /// # Parameters
+    ///
+    /// events: The index of the tile and the event for every change of a
+    /// plant during the step in the order of the tiles
+    fn notify_observers(&mut self, events: &[(usize, PlantEvent)]) {
+        let time = self.time;
+        for observer in self.observers.iter_mut() {
+            for (index, event) in events {
+                let pos = TilePos::from_index(*index, &self.size).pos;
+                match event {
+                    PlantEvent::Born => observer.on_plant_born(&pos, time - 1),
+                    PlantEvent::Died => observer.on_plant_died(&pos, time - 1),
+                    PlantEvent::SeedRipened => observer.on_seed_ripened(&pos, time - 1),
+                }
+            }
+            observer.on_step(time);
+        }
+    }
+
+    /// Gets the ranges of indices covering all tiles which have changed since
+    /// the dirty tiles were last cleared, the tile data of all other tiles is
+    /// the same as when it was last retrieved
+    pub fn get_dirty_ranges(&self) -> Vec<Range<usize>> {
+        return self.dirty.get_ranges();
+    }
+
+    /// Marks all tiles as unchanged, run after the tile data of all dirty
+    /// tiles has been retrieved
+    pub fn clear_dirty(&mut self) {
+        self.dirty.clear();
+    }
+
+    /// Computes the light of every tile for the next step with the light
+    /// solver, None if there is no light solver, it failed or the map does not
+    /// wrap in x
+    #[tracing::instrument(level = "debug", skip_all)]
+    fn solve_light(&mut self) -> Option<Vec<f32>> {
+        // The light solvers assume the map wraps in x and the light falls
+        // straight down
+        if !self.settings.wrap.horizontal() || self.settings.sky.angled {
+            return None;
+        }
+
+        // The solvers only compute the direct light, the diffuse light is
+        // still gathered by the tiles
+        let solver = self.light_solver.as_mut()?;
+        let light = self
+            .tiles
+            .iter()
+            .map(|tile| (tile.get_light() - tile.get_diffuse()) as f32)
+            .collect::<Vec<_>>();
+        let transparency = self
+            .tiles
+            .iter()
+            .map(|tile| tile.get_transparency() as f32)
+            .collect::<Vec<_>>();
+        let direct = self.settings.sky.get_direct();
+        let sun = self
+            .sun_tiles
+            .iter()
+            .map(|tile| (direct * tile.intensity) as f32)
+            .collect::<Vec<_>>();
+
+        return solver
+            .solve(&self.size, &light, &transparency, &sun)
+            .filter(|light| light.len() == self.tiles.len());
+    }
+
+    /// Clusters all living lineages into species by the similarity of their
+    /// genomes
+    #[tracing::instrument(level = "debug", skip_all)]
+    fn update_species(&mut self) {
+        let genealogy = &self.genealogy;
+        let genomes = self.tiles.iter().filter_map(|tile| {
+            let plant = tile.get_living_plant()?;
+            let lineage = genealogy.get(plant.get_lineage()?)?;
+            return Some((lineage.id, lineage.genome_hash, plant));
+        });
+        self.species.update(&self.settings.species, genomes);
+    }
+
+    /// Moves every falling seed one tile down into a free tile, this is the
+    /// second phase of a step such that movement does not depend on the order
+    /// tiles are updated in. If both tiles below are free one is chosen at
+    /// random, seeds act in the order of their update priority and a seed
+    /// whose target has been taken waits for the next step. The wind may
+    /// instead blow a seed one tile sideways if that tile is free
+    ///
+    /// # Parameters
+    ///
+    /// priorities: The update priority of every tile for this step
+    #[tracing::instrument(level = "debug", skip_all)]
+    fn resolve_falling(&mut self, priorities: &[usize]) {
+        let mut falling = (0..self.tiles.len())
+            .filter(|&index| self.tiles[index].is_falling())
+            .collect::<Vec<_>>();
+        falling.sort_by_key(|&index| (priorities[index], index));
+
+        // Only tiles which were free after the first phase can be moved into
+        let mut free = self
+            .tiles
+            .iter()
+            .map(|tile| tile.is_free())
+            .collect::<Vec<_>>();
+        let wind = self.settings.weather.get_wind(self.time);
+        let gust_probability = wind.abs().min(1.0);
+        for index in falling {
+            let pos = TilePos::from_index(index, &self.size);
+
+            // The wind may blow the seed one column sideways in place of
+            // falling
+            let blown = if gust_probability > 0.0
+                && self
+                    .rng
+                    .get(self.time, SimRng::STREAM_WIND, index)
+                    .random_bool(gust_probability)
+            {
+                let target = if wind > 0.0 {
+                    pos.right(&self.size, &self.settings.wrap)
+                } else {
+                    pos.left(&self.size, &self.settings.wrap)
+                };
+                match target {
+                    TilePosNeighbor::Valid(target) => Some(target.to_index(&self.size)),
+                    TilePosNeighbor::Invalid(_) => None,
+                }
+                .filter(|&target| free[target])
+            } else {
+                None
+            };
+
+            let targets = [
+                pos.down_left(&self.size, &self.settings.wrap),
+                pos.down_right(&self.size, &self.settings.wrap),
+            ]
+            .into_iter()
+            .filter_map(|target| match target {
+                TilePosNeighbor::Valid(target) => Some(target.to_index(&self.size)),
+                TilePosNeighbor::Invalid(_) => None,
+            })
+            .filter(|&target| free[target])
+            .collect::<Vec<_>>();
+            let target = if let Some(target) = blown {
+                target
+            } else if targets.is_empty() {
+                continue;
+            } else if targets.len() == 1 {
+                targets[0]
+            } else {
+                let mut rng = self.rng.get(self.time, SimRng::STREAM_FALL, index);
+                targets[rng.random_range(0..targets.len())]
+            };
+
+            free[target] = false;
+            let (source, target) = if index < target {
+                let (first, second) = self.tiles.split_at_mut(target);
+                (&mut first[index], &mut second[0])
+            } else {
+                let (first, second) = self.tiles.split_at_mut(index);
+                (&mut second[0], &mut first[target])
+            };
+            source.move_plant(target);
+        }
+    }
+
+    /// Moves all pollen with the wind, lets every grain fertilize the flower
+    /// it lands on and lets every flower emit new pollen with the emission
+    /// probability. Grains act in the order they were emitted such that the
+    /// oldest grain fertilizes a flower when several land on it
+    #[tracing::instrument(level = "debug", skip_all)]
+    fn update_pollen(&mut self) {
+        self.pollen
+            .drift(&self.settings.pollen, self.size.w, self.time, &self.rng);
+
+        let tiles = &mut self.tiles;
+        let (settings, size, time, rng) = (&self.settings, &self.size, self.time, &self.rng);
+        self.pollen.land(size.w, |pos, pollen| {
+            let index = pos.to_index(size);
+            let mut rng = rng.get(time, SimRng::STREAM_POLLEN, index);
+
+            return tiles[index].fertilize(pollen, settings, &mut rng);
+        });
+
+        let probability = self.settings.pollen.emission.clamp(0.0, 1.0);
+        if probability == 0.0 {
+            return;
+        }
+
+        for (index, tile) in self.tiles.iter().enumerate() {
+            if let Some(pollen) = tile.get_pollen() {
+                let mut rng = self.rng.get(self.time, SimRng::STREAM_POLLEN, index);
+                if rng.random_bool(probability) {
+                    self.pollen
+                        .emit(pollen, &TilePos::from_index(index, &self.size));
+                }
+            }
+        }
+    }
+
+    /// Lets herbivores graze every column with the grazing pressure, the
+    /// living plant tile with the most energy in a grazed column loses a
+    /// fraction of its energy unless it or one of its neighbors is a thorn
+    #[tracing::instrument(level = "debug", skip_all)]
+    fn graze(&mut self) {
+        let pressure = self.settings.herbivory.pressure.clamp(0.0, 1.0);
+        if pressure == 0.0 {
+            return;
+        }
+
+        for column in 0..self.size.w {
+            let mut rng = self.rng.get(self.time, SimRng::STREAM_GRAZING, column);
+            if !rng.random_bool(pressure) {
+                continue;
+            }
+
+            let target = (0..self.size.h)
+                .map(|row| row * self.size.w + column)
+                .filter_map(|index| self.tiles[index].get_forage().map(|energy| (index, energy)))
+                .max_by(|(_, first), (_, second)| first.total_cmp(second));
+            let index = match target {
+                Some((index, _)) => index,
+                None => continue,
+            };
+
+            let pos = TilePos::from_index(index, &self.size);
+            let protected = self.tiles[index].has_thorn()
+                || NeighborDirection::collection().iter().any(|dir| {
+                    return match pos.direction(&self.size, &self.settings.wrap, dir) {
+                        TilePosNeighbor::Valid(neighbor) => {
+                            self.tiles[neighbor.to_index(&self.size)].has_thorn()
+                        }
+                        TilePosNeighbor::Invalid(_) => false,
+                    };
+                });
+            if !protected {
+                let energy = self.tiles[index].get_stored_energy();
+                self.tiles[index].graze(self.settings.herbivory.damage);
+                self.balance.grazed += energy - self.tiles[index].get_stored_energy();
+            }
+        }
+    }
+
+    /// Kills the plants struck by wildfires and frosts, returns the indices
+    /// of the tiles whose plant was killed
+    ///
+    /// # Parameters
+    ///
+    /// struck: The catastrophes striking in this step
+    #[tracing::instrument(level = "debug", skip_all)]
+    fn apply_catastrophes(&mut self, struck: &[Catastrophe]) -> Vec<usize> {
+        let mut killed = Vec::new();
+        for catastrophe in struck.iter() {
+            match catastrophe {
+                Catastrophe::Eclipse { .. } => (),
+                Catastrophe::Wildfire { column, width } => {
+                    for offset in 0..*width {
+                        let column = (column + offset) % self.size.w;
+                        for row in 0..self.size.h {
+                            let index = row * self.size.w + column;
+                            if !self.tiles[index].is_ground() && self.tiles[index].kill_plant() {
+                                killed.push(index);
+                            }
+                        }
+                    }
+                }
+                Catastrophe::Frost => {
+                    let damage = self.settings.events.frost.damage.clamp(0.0, 1.0);
+                    for index in 0..self.tiles.len() {
+                        if !self.tiles[index].has_leaf() {
+                            continue;
+                        }
+                        let mut rng = self.rng.get(self.time, SimRng::STREAM_FROST, index);
+                        if rng.random_bool(damage) && self.tiles[index].kill_plant() {
+                            killed.push(index);
+                        }
+                    }
+                }
+            }
+        }
+
+        return killed;
+    }
+
+    /// Spawns a plant with a random genome on every free tile next to the
+    /// ground with the genesis probability, such that an empty map can
+    /// bootstrap life. No plants are spawned once the plant cap is reached.
+    /// Returns the indices of the tiles which got a plant
+    #[tracing::instrument(level = "debug", skip_all)]
+    fn spawn_plants(&mut self) -> Vec<usize> {
+        let probability = self.settings.genesis.probability.clamp(0.0, 1.0);
+        if probability == 0.0 {
+            return Vec::new();
+        }
+
+        let mut spawned = Vec::new();
+        let mut room = self.get_plant_room();
+
+        for index in 0..self.tiles.len() {
+            if room == 0 {
+                break;
+            }
+            if !self.tiles[index].is_free() || self.tiles[index].is_ground() {
+                continue;
+            }
+            let pos = TilePos::from_index(index, &self.size);
+            let next_to_ground = NeighborDirection::collection().iter().any(|dir| {
+                return match pos.direction(&self.size, &self.settings.wrap, dir) {
+                    TilePosNeighbor::Valid(neighbor) => {
+                        self.tiles[neighbor.to_index(&self.size)].is_ground()
+                    }
+                    TilePosNeighbor::Invalid(_) => false,
+                };
+            });
+            if !next_to_ground {
+                continue;
+            }
+
+            let mut rng = self.rng.get(self.time, SimRng::STREAM_GENESIS, index);
+            if rng.random_bool(probability)
+                && self.tiles[index].spawn_plant(&self.settings, &mut rng)
+            {
+                self.balance.spawned += self.tiles[index].get_stored_energy();
+                room -= 1;
+                spawned.push(index);
+            }
+        }
+
+        return spawned;
+    }
+
+    /// Gets the number of new plant tiles which fit below the plant cap,
+    /// usize::MAX if there is no cap
+    fn get_plant_room(&self) -> usize {
+        return match self.settings.population.cap {
+            Some(cap) => {
+                cap.saturating_sub(self.tiles.iter().filter(|tile| tile.has_plant()).count())
+            }
+            None => usize::MAX,
+        };
+    }
+
+    /// Compares the change of the stored energy during the last step with
+    /// the energy balance of the step and logs or panics depending on the
+    /// audit settings if they differ by more than the tolerance
+    ///
+    /// # Parameters
+    ///
+    /// stored: The energy stored in the plants before the step
+    fn audit_energy(&self, stored: f64) {
+        let change = self.get_stored_energy() - stored;
+        let net = self.balance.get_net();
+        if (change - net).abs() <= self.settings.audit.tolerance {
+            return;
+        }
+
+        let message = format!(
+            "Energy is not conserved in step {}: the stored energy changed by {} but the balance is {}, {:?}",
+            self.time, change, net, self.balance
+        );
+        match self.settings.audit.action {
+            settings::audit::Action::Log => eprintln!("{}", message),
+            settings::audit::Action::Panic => panic!("{}", message),
+        }
+    }
+
+    /// Gets the update priority of every tile for the current step from the
+    /// update order setting, lower values act first
+    fn get_update_priorities(&self) -> Vec<usize> {
+        return match self.settings.update.order {
+            settings::update::Order::Direction => vec![0; self.tiles.len()],
+            settings::update::Order::Shuffled => {
+                let mut order = (0..self.tiles.len()).collect::<Vec<_>>();
+                order.shuffle(&mut self.rng.get(self.time, SimRng::STREAM_UPDATE_ORDER, 0));
+                let mut priorities = vec![0; self.tiles.len()];
+                for (priority, index) in order.into_iter().enumerate() {
+                    priorities[index] = priority;
+                }
+                priorities
+            }
+            settings::update::Order::Checkerboard => (0..self.tiles.len())
+                .map(|index| {
+                    let pos = TilePos::from_index(index, &self.size);
+                    ((pos.pos.x + pos.pos.y) as usize + self.time) % 2
+                })
+                .collect(),
+        };
+    }
+
+    /// Retrieves the grid layout of the map with equally wide columns
+    pub fn get_grid_layout(&self) -> EvenColumns {
+        return EvenColumns {
             n_columns: self.size.w,
         };
     }
@@ -103,16 +838,475 @@ impl<S: sun::Intensity> Map<S> {
         return &self.settings;
     }
 
+    /// Retrieves the current iteration time step
+    pub fn get_time(&self) -> usize {
+        return self.time;
+    }
+
+    /// Finds the tile at a point in world coordinates, the map repeats in x so
+    /// any x-coordinate is valid, returns None if the point is above or below
+    /// the map
+    ///
+    /// # Parameters
+    ///
+    /// grid_layout: The layout the map is displayed with
+    ///
+    /// point: The point in world coordinates
+    pub fn pick_tile(
+        &self,
+        grid_layout: &dyn GridLayout,
+        point: &types::Point,
+    ) -> Option<types::Index> {
+        let index = grid_layout.get_index(point);
+
+        return if index.y >= 0 && index.y < self.size.h as isize {
+            Some(index)
+        } else {
+            None
+        };
+    }
+
+    /// Gets the fraction of the day which has passed at the leftmost column
+    /// from 0 at noon to 1 at the next noon, None if the sun does not follow
+    /// a day
+    pub fn get_time_of_day(&self) -> Option<f64> {
+        return self.sun.get_time_of_day(self.time);
+    }
+
+    /// Gets the fraction of the year which has passed from 0 at midsummer to
+    /// 1 at the next midsummer, None if the sun does not follow a year
+    pub fn get_time_of_year(&self) -> Option<f64> {
+        return self.sun.get_time_of_year(self.time);
+    }
+
+    /// Gets a read-only view of a tile, None if the position is outside the
+    /// map
+    ///
+    /// # Parameters
+    ///
+    /// pos: The position of the tile
+    pub fn get_tile(&self, pos: &types::Index) -> Option<TileView<'_>> {
+        if pos.x < 0 || pos.x >= self.size.w as isize || pos.y < 0 || pos.y >= self.size.h as isize
+        {
+            return None;
+        }
+        let tile = &self.tiles[TilePos { pos: *pos }.to_index(&self.size)];
+
+        return Some(TileView::new(*pos, tile, &self.species));
+    }
+
+    /// Gets a snapshot of the state of a tile for inspection, None if the
+    /// position is outside the map
+    ///
+    /// # Parameters
+    ///
+    /// pos: The position of the tile
+    pub fn inspect_tile(&self, pos: &types::Index) -> Option<TileInfo> {
+        return self.get_tile(pos).map(|view| view.get_info());
+    }
+
+    /// Gets the summary of every tile in the same order as the tiles, row by
+    /// row starting from the top
+    pub fn get_tile_summaries(&self) -> Vec<TileSummary> {
+        return self
+            .tiles
+            .iter()
+            .map(|tile| TileSummary {
+                light: tile.get_light(),
+                ground: tile.is_ground(),
+                occupied: !tile.is_free(),
+                energy: tile.get_plant_energy(),
+                lineage: tile.get_plant_lineage(),
+            })
+            .collect();
+    }
+
+    /// Takes a snapshot of every tile which can be saved and compared with
+    /// another snapshot of the map
+    pub fn get_snapshot(&self) -> diff::Snapshot {
+        return diff::Snapshot {
+            time: self.time,
+            width: self.size.w,
+            height: self.size.h,
+            tiles: self.get_tile_summaries(),
+            annotations: self.annotations.get_all().to_vec(),
+        };
+    }
+
+    /// Finds the center of all living plant tiles of a lineage in world
+    /// coordinates, None if the lineage has no living tiles. The map wraps
+    /// around in x so the x-coordinate is averaged as an angle around the map
+    ///
+    /// # Parameters
+    ///
+    /// lineage: The id of the lineage
+    ///
+    /// grid_layout: The layout the map is displayed with
+    pub fn get_lineage_center(
+        &self,
+        lineage: usize,
+        grid_layout: &dyn GridLayout,
+    ) -> Option<types::Point> {
+        let width = self.size.w as f64;
+        let mut count = 0;
+        let mut sum_cos = 0.0;
+        let mut sum_sin = 0.0;
+        let mut sum_y = 0.0;
+        self.tiles
+            .iter()
+            .enumerate()
+            .filter(|(_, tile)| tile.get_plant_lineage() == Some(lineage))
+            .for_each(|(index, _)| {
+                let center = grid_layout.get_center(&TilePos::from_index(index, &self.size).pos);
+                let angle = 2.0 * constants::MATH_PI * center.x / width;
+                count += 1;
+                sum_cos += angle.cos();
+                sum_sin += angle.sin();
+                sum_y += center.y;
+            });
+
+        if count == 0 {
+            return None;
+        }
+
+        let x = sum_sin.atan2(sum_cos) * width / (2.0 * constants::MATH_PI);
+        return Some(types::Point::new(x.rem_euclid(width), sum_y / count as f64));
+    }
+
+    /// Collects aggregate statistics for the current state of the map
+    pub fn get_statistics(&self) -> Statistics {
+        let mut statistics = Statistics::new(self.time);
+        statistics.species = self.species.len();
+        self.tiles
+            .iter()
+            .filter_map(|tile| tile.get_plant_energy())
+            .for_each(|energy| statistics.add_plant(energy));
+        self.tiles.iter().for_each(|tile| {
+            tile.get_plant_transfers()
+                .into_iter()
+                .zip(tile.get_plant_throughputs())
+                .filter_map(|(transfer, throughput)| Some((transfer, throughput?)))
+                .for_each(|(transfer, throughput)| statistics.add_bridge(transfer, throughput));
+        });
+
+        return statistics;
+    }
+
+    /// Checks if a species has appeared on the map and died out, the species
+    /// are only updated at every clustering of the lineages
+    ///
+    /// # Parameters
+    ///
+    /// species: The id of the species
+    pub fn is_species_extinct(&self, species: usize) -> bool {
+        return self.species.is_extinct(species);
+    }
+
+    /// Collects the statistics of every species with living plants ordered by
+    /// their id, the plants without a species are collected last
+    pub fn get_species_statistics(&self) -> Vec<SpeciesStatistics> {
+        let mut species = BTreeMap::new();
+        let mut unclustered = SpeciesStatistics::new(None);
+        for tile in self.tiles.iter() {
+            let Some(energy) = tile.get_plant_energy() else {
+                continue;
+            };
+            match tile
+                .get_plant_lineage()
+                .and_then(|lineage| self.species.get(lineage))
+            {
+                Some(id) => species
+                    .entry(id)
+                    .or_insert_with(|| SpeciesStatistics::new(Some(id)))
+                    .add_plant(energy),
+                None => unclustered.add_plant(energy),
+            }
+        }
+
+        let mut species = species.into_values().collect::<Vec<_>>();
+        if unclustered.population > 0 {
+            species.push(unclustered);
+        }
+
+        return species;
+    }
+
+    /// Collects the light reaching the ground and the plant biomass of every
+    /// column, ordered from the left to the right
+    pub fn get_column_profile(&self) -> ColumnProfile {
+        let mut light = vec![None; self.size.w];
+        let mut biomass = vec![0; self.size.w];
+        for (index, tile) in self.tiles.iter().enumerate() {
+            let column = index % self.size.w;
+            if tile.get_plant_energy().is_some() {
+                biomass[column] += 1;
+            }
+            if light[column].is_none() && tile.is_ground() {
+                light[column] = Some(tile.get_light());
+            }
+        }
+
+        // Without ground the light leaving the bottom of the map is used
+        let bottom = self.size.w * self.size.h.saturating_sub(1);
+        let light = light
+            .into_iter()
+            .enumerate()
+            .map(|(column, light)| {
+                light.unwrap_or_else(|| {
+                    self.tiles
+                        .get(bottom + column)
+                        .map_or(0.0, |tile| tile.get_light())
+                })
+            })
+            .collect();
+
+        return ColumnProfile { light, biomass };
+    }
+
+    /// Retrieves the energy entering and leaving the plants during the last
+    /// step
+    pub fn get_energy_balance(&self) -> &EnergyBalance {
+        return &self.balance;
+    }
+
+    /// Gets the total energy stored in all plants including the energy set
+    /// aside for spreading
+    pub fn get_stored_energy(&self) -> f64 {
+        return self.tiles.iter().map(|tile| tile.get_stored_energy()).sum();
+    }
+
+    /// Retrieves all user notes pinned to tiles
+    pub fn get_annotations(&self) -> &AnnotationSet {
+        return &self.annotations;
+    }
+
+    /// Pins a new note to a tile at the current time step, returns the id of
+    /// the note or None if the position is outside the map
+    ///
+    /// # Parameters
+    ///
+    /// pos: The position of the tile to pin the note to
+    ///
+    /// text: The text of the note
+    ///
+    /// pin_organism: True if the note should follow the organism growing on
+    /// the tile, it is pinned to the tile if there is no organism
+    pub fn add_annotation(
+        &mut self,
+        pos: types::Index,
+        text: String,
+        pin_organism: bool,
+    ) -> Option<usize> {
+        let tile = self.get_tile(&pos)?;
+        let lineage = if pin_organism {
+            tile.get_lineage()
+        } else {
+            None
+        };
+
+        return Some(self.annotations.add(pos, lineage, self.time, text));
+    }
+
+    /// Finds the position of a note in world coordinates, a note pinned to an
+    /// organism is placed at the center of its living tiles and otherwise at
+    /// the center of its tile
+    ///
+    /// # Parameters
+    ///
+    /// annotation: The note to find the position of
+    ///
+    /// grid_layout: The layout the map is displayed with
+    pub fn get_annotation_center(
+        &self,
+        annotation: &Annotation,
+        grid_layout: &dyn GridLayout,
+    ) -> types::Point {
+        return annotation
+            .lineage
+            .and_then(|lineage| self.get_lineage_center(lineage, grid_layout))
+            .unwrap_or_else(|| grid_layout.get_center(&annotation.pos));
+    }
+
+    /// Removes a note, returns the removed note or None if it did not exist
+    ///
+    /// # Parameters
+    ///
+    /// id: The id of the note to remove
+    pub fn remove_annotation(&mut self, id: usize) -> Option<Annotation> {
+        return self.annotations.remove(id);
+    }
+
+    /// Retrieves the catastrophes which have struck the map
+    pub fn get_catastrophes(&self) -> &Catastrophes {
+        return &self.catastrophes;
+    }
+
+    /// Retrieves the registry of all lineages which have existed on the map
+    pub fn get_genealogy(&self) -> &Genealogy {
+        return &self.genealogy;
+    }
+
+    /// Retrieves the ids of all lineages with living plant tiles in increasing
+    /// order
+    pub fn get_living_lineages(&self) -> Vec<usize> {
+        let mut lineages = self
+            .tiles
+            .iter()
+            .filter_map(|tile| tile.get_plant_lineage())
+            .collect::<Vec<_>>();
+        lineages.sort_unstable();
+        lineages.dedup();
+
+        return lineages;
+    }
+
+    /// Retrieves the ancestry of a lineage starting with the lineage itself
+    /// and ending with the founder it descends from, empty if the lineage does
+    /// not exist
+    ///
+    /// # Parameters
+    ///
+    /// id: The id of the lineage
+    pub fn get_lineage(&self, id: usize) -> Vec<&Lineage> {
+        return self.genealogy.get_ancestry(id);
+    }
+
     /// Converts all tiles to shader compatible data
     ///
     /// # Parameters
     ///
     /// mode: The mode for displaying the background
     pub fn get_tile_data_background(&self, mode: &DataModeBackground) -> Vec<InstanceTile> {
+        // Temperatures are shown relative to the coldest and warmest tiles
+        let (min_temperature, max_temperature) = if *mode == DataModeBackground::Temperature {
+            self.tiles
+                .iter()
+                .map(|tile| tile.get_temperature())
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                    (min.min(value), max.max(value))
+                })
+        } else {
+            (0.0, 0.0)
+        };
+        let foreign_shade = if *mode == DataModeBackground::ForeignShade {
+            new_foreign_shade_field(&self.tiles, &self.sun_tiles, &self.size, &self.settings)
+        } else {
+            Vec::new()
+        };
+
         return self
             .tiles
             .iter()
-            .map(|tile| tile.get_data_background(mode))
+            .enumerate()
+            .map(|(index, tile)| match mode {
+                DataModeBackground::ChunkCost => InstanceTile::new(
+                    self.chunks.get_cost_fraction(
+                        self.chunks
+                            .get_chunk_index(&TilePos::from_index(index, &self.size)),
+                    ) as f32,
+                ),
+                DataModeBackground::Temperature => {
+                    InstanceTile::new(if max_temperature > min_temperature {
+                        ((tile.get_temperature() - min_temperature)
+                            / (max_temperature - min_temperature)) as f32
+                    } else {
+                        0.5
+                    })
+                }
+                DataModeBackground::ForeignShade => InstanceTile::new(foreign_shade[index] as f32),
+                DataModeBackground::Clouds => InstanceTile::new(
+                    self.weather
+                        .get_coverage(TilePos::from_index(index, &self.size).pos.x as usize)
+                        as f32,
+                ),
+                _ => tile.get_data_background(mode),
+            })
+            .collect();
+    }
+
+    /// Converts the plants of all tiles to shader compatible data for a
+    /// discrete color map, the color value is 0 for tiles without a living
+    /// plant
+    ///
+    /// # Parameters
+    ///
+    /// mode: The mode for displaying the plants
+    pub fn get_tile_data_plant(&self, mode: &DataModePlant) -> Vec<InstanceTile> {
+        // Ages are shown relative to the oldest plant such that the colors
+        // keep their contrast as the plants grow older
+        let max_age = self
+            .tiles
+            .iter()
+            .filter_map(|tile| tile.get_plant_age())
+            .fold((0, 0), |(max_age, max_cum_age), (age, cum_age)| {
+                (max_age.max(age), max_cum_age.max(cum_age))
+            });
+
+        return self
+            .tiles
+            .iter()
+            .map(|tile| match mode {
+                DataModePlant::Bulk => tile.get_data_plant(),
+                DataModePlant::Age => {
+                    InstanceTile::new_age(tile.get_plant_age().map(|(age, _)| age), max_age.0)
+                }
+                DataModePlant::CumAge => InstanceTile::new_age(
+                    tile.get_plant_age().map(|(_, cum_age)| cum_age),
+                    max_age.1,
+                ),
+                DataModePlant::Species => InstanceTile::new_species(
+                    tile.get_plant_lineage()
+                        .and_then(|lineage| self.species.get(lineage)),
+                ),
+            })
+            .collect();
+    }
+
+    /// Converts the bridges of all plants to shader compatible data, every
+    /// tile has one instance for each direction in the order of
+    /// NeighborDirection::collection()
+    pub fn get_bridge_data(&self) -> Vec<InstanceBridge> {
+        let bridges = self
+            .tiles
+            .iter()
+            .flat_map(|tile| tile.get_plant_bridges())
+            .collect::<Vec<_>>();
+
+        // Widths are relative to the largest bridge such that the difference
+        // between logs and branches stays visible
+        let max_capacity = bridges
+            .iter()
+            .flatten()
+            .fold(0.0, |max_capacity: f64, (_, capacity)| {
+                max_capacity.max(*capacity)
+            });
+
+        return bridges
+            .into_iter()
+            .map(|bridge| InstanceBridge::new(bridge, max_capacity))
+            .collect();
+    }
+
+    /// Converts the energy transferred through the bridges of all plants
+    /// during the last step to shader compatible data, every tile has one
+    /// instance for each direction in the order of
+    /// NeighborDirection::collection()
+    pub fn get_transfer_data(&self) -> Vec<InstanceTransfer> {
+        let transfers = self
+            .tiles
+            .iter()
+            .flat_map(|tile| tile.get_plant_transfers())
+            .collect::<Vec<_>>();
+
+        // Sizes are relative to the largest transfer such that the main
+        // routes of energy stand out
+        let max_transfer = transfers.iter().fold(0.0, |max_transfer: f64, transfer| {
+            max_transfer.max(transfer.abs())
+        });
+
+        return transfers
+            .into_iter()
+            .map(|transfer| InstanceTransfer::new(transfer, max_transfer))
             .collect();
     }
 
@@ -120,4 +1314,10 @@ impl<S: sun::Intensity> Map<S> {
     pub fn get_sun_data(&self) -> Vec<InstanceTile> {
         return self.sun_tiles.iter().map(|tile| tile.get_data()).collect();
     }
+
+    /// Gets the shader compatible data for the outlines of all tiles, they
+    /// all use the first color of the color map
+    pub fn get_border_data(&self) -> Vec<InstanceTile> {
+        return self.tiles.iter().map(|_| InstanceTile::new(0.0)).collect();
+    }
 }
@@ -0,0 +1,60 @@
+use std::fmt::Debug;
+
+use dyn_clone::DynClone;
+
+use crate::types;
+
+/// A change of the plant on a single tile during a step
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlantEvent {
+    /// A new plant tile has been built or has appeared spontaneously
+    Born,
+    /// A plant tile has died, it is removed in the next step
+    Died,
+    /// A seed has been filled up with energy and has detached from its mother
+    SeedRipened,
+}
+
+/// Receives the events of the simulation such that tools like statistics,
+/// logging or scripted interventions can follow a run without changing the
+/// simulation itself, all methods do nothing by default. The events of a step
+/// are sent after the step has finished in the order of the tiles
+pub trait MapObserver: DynClone + Debug {
+    /// Called after every step
+    ///
+    /// # Parameters
+    ///
+    /// time: The number of steps taken including this one
+    fn on_step(&mut self, _time: usize) {}
+
+    /// Called when a new plant tile has been built or has appeared
+    /// spontaneously
+    ///
+    /// # Parameters
+    ///
+    /// pos: The position of the plant tile
+    ///
+    /// time: The time step the plant tile was born in
+    fn on_plant_born(&mut self, _pos: &types::Index, _time: usize) {}
+
+    /// Called when a plant tile has died
+    ///
+    /// # Parameters
+    ///
+    /// pos: The position of the plant tile
+    ///
+    /// time: The time step the plant tile died in
+    fn on_plant_died(&mut self, _pos: &types::Index, _time: usize) {}
+
+    /// Called when a seed has ripened, the position is the one before it
+    /// starts falling
+    ///
+    /// # Parameters
+    ///
+    /// pos: The position of the seed
+    ///
+    /// time: The time step the seed ripened in
+    fn on_seed_ripened(&mut self, _pos: &types::Index, _time: usize) {}
+}
+
+dyn_clone::clone_trait_object!(MapObserver);
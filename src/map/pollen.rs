@@ -0,0 +1,100 @@
+use rand::Rng;
+
+use crate::types;
+
+use super::{SimRng, TilePos, settings::pollen::Settings, tile::Pollen};
+
+/// A single grain of pollen drifting in the air
+#[derive(Clone, Debug)]
+struct Grain {
+    /// The pollen carried by the grain
+    pollen: Pollen,
+    /// The continuous column position of the grain, it wraps around the map
+    /// like the tiles do
+    column: f64,
+    /// The row of the grain, pollen drifts sideways only
+    row: usize,
+    /// The number of steps the grain has been drifting
+    age: usize,
+}
+
+/// All pollen drifting across the map, grains are emitted by flowers, drift
+/// sideways with the wind and are removed when they fertilize a flower or
+/// decay
+#[derive(Clone, Debug)]
+pub struct PollenField {
+    /// All grains in the order they were emitted
+    grains: Vec<Grain>,
+}
+
+impl PollenField {
+    /// Constructs a new field without any pollen
+    pub fn new() -> Self {
+        return Self { grains: Vec::new() };
+    }
+
+    /// Adds a grain of pollen at the position of the flower emitting it
+    ///
+    /// # Parameters
+    ///
+    /// pollen: The pollen to add
+    ///
+    /// pos: The position of the tile emitting the pollen
+    pub fn emit(&mut self, pollen: Pollen, pos: &TilePos) {
+        self.grains.push(Grain {
+            pollen,
+            column: pos.pos.x as f64,
+            row: pos.pos.y as usize,
+            age: 0,
+        });
+    }
+
+    /// Moves all grains by the wind with a random deviation and removes the
+    /// grains which have decayed
+    ///
+    /// # Parameters
+    ///
+    /// settings: The pollen settings
+    ///
+    /// width: The number of columns of the map
+    ///
+    /// time: The current time step
+    ///
+    /// rng: The random number generator of the simulation
+    pub fn drift(&mut self, settings: &Settings, width: usize, time: usize, rng: &SimRng) {
+        self.grains.retain(|grain| grain.age < settings.lifetime);
+
+        let mut rng = rng.get(time, SimRng::STREAM_DRIFT, 0);
+        for grain in self.grains.iter_mut() {
+            let deviation = if settings.turbulence > 0.0 {
+                rng.random_range(-settings.turbulence..=settings.turbulence)
+            } else {
+                0.0
+            };
+            grain.column = (grain.column + settings.wind + deviation).rem_euclid(width as f64);
+            grain.age += 1;
+        }
+    }
+
+    /// Lets every grain land on the tile it is above, the grains for which
+    /// the callback returns true are consumed
+    ///
+    /// # Parameters
+    ///
+    /// width: The number of columns of the map
+    ///
+    /// land: Called with the position of the tile and the pollen of every
+    /// grain, returns true if the pollen was taken
+    pub fn land<F: FnMut(&TilePos, &Pollen) -> bool>(&mut self, width: usize, mut land: F) {
+        self.grains.retain(|grain| {
+            let pos = TilePos {
+                pos: types::Index {
+                    x: (grain.column.round() as usize % width) as isize,
+                    y: grain.row as isize,
+                },
+            };
+
+            return !land(&pos, &grain.pollen);
+        });
+    }
+}
@@ -0,0 +1,260 @@
+use crate::{constants, types};
+
+use super::{Map, settings, sun};
+
+/// The sun of a planet with a yearly and a daily cycle
+pub type IntensityPlanet = sun::IntensityYearDay<sun::IntensityYearPlanet, sun::IntensityDayPlanet>;
+
+/// A ready made world with its own map size, simulation settings and sun to
+/// explore different environments without writing a config
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorldPreset {
+    /// A wide and low map close to the equator with little water and high
+    /// temperatures
+    FlatDesert,
+    /// A tall map where the middle columns lie at the bottom of a canyon and
+    /// only receive light when the sun is high
+    TallCanyon,
+    /// A map lit by two suns with different day lengths such that they drift
+    /// in and out of phase
+    TwinSuns,
+    /// A cold map close to the pole with long summer days and dark winters
+    PolarLatitude,
+}
+
+impl WorldPreset {
+    pub const COUNT: usize = 4;
+
+    /// The id to the world in a list of all worlds
+    pub fn id(&self) -> usize {
+        return match self {
+            Self::FlatDesert => 0,
+            Self::TallCanyon => 1,
+            Self::TwinSuns => 2,
+            Self::PolarLatitude => 3,
+        };
+    }
+
+    /// Constructs a new world from an id
+    ///
+    /// # Parameters
+    ///
+    /// id: The id to construct from
+    pub fn from_id(id: usize) -> Self {
+        return match id.clamp(0, Self::COUNT - 1) {
+            0 => Self::FlatDesert,
+            1 => Self::TallCanyon,
+            2 => Self::TwinSuns,
+            3 => Self::PolarLatitude,
+            _ => panic!("WorldPreset::from_id has not been updated"),
+        };
+    }
+
+    /// Finds the world with a given name, returns None if there is no such
+    /// world
+    ///
+    /// # Parameters
+    ///
+    /// name: The name of the world as given by get_name
+    pub fn from_name(name: &str) -> Option<Self> {
+        return (0..Self::COUNT)
+            .map(Self::from_id)
+            .find(|world| world.get_name() == name);
+    }
+
+    /// Gets the name of the world used to select it
+    pub fn get_name(&self) -> &'static str {
+        return match self {
+            Self::FlatDesert => "flat_desert",
+            Self::TallCanyon => "tall_canyon",
+            Self::TwinSuns => "twin_suns",
+            Self::PolarLatitude => "polar_latitude",
+        };
+    }
+
+    /// Gets the next world
+    pub fn next(&self) -> Self {
+        return Self::from_id((self.id() + 1) % Self::COUNT);
+    }
+
+    /// Gets the previous world
+    pub fn prev(&self) -> Self {
+        return Self::from_id((self.id() + (Self::COUNT - 1)) % Self::COUNT);
+    }
+
+    /// Gets the size of the map
+    pub fn get_size(&self) -> types::ISize {
+        return match self {
+            Self::FlatDesert => types::ISize { w: 300, h: 40 },
+            Self::TallCanyon => types::ISize { w: 120, h: 120 },
+            Self::TwinSuns => constants::MAP_SIZE,
+            Self::PolarLatitude => constants::MAP_SIZE,
+        };
+    }
+
+    /// Constructs the simulation settings, they start from the defaults of
+    /// the config
+    pub fn get_settings(&self) -> settings::Settings {
+        let transparency =
+            settings::transparency::Settings::new().with_base(constants::MAP_TRANSPARENCY);
+        let update = settings::update::Settings::new().with_order(constants::MAP_UPDATE_ORDER);
+        let settings = settings::Settings::new()
+            .with_transparency(transparency)
+            .with_update(update)
+            .with_seed(constants::MAP_SEED);
+
+        return match self {
+            Self::FlatDesert => settings
+                .with_water(
+                    settings::water::Settings::new()
+                        .with_source(0.2)
+                        .with_evaporation(0.03),
+                )
+                .with_temperature(
+                    settings::temperature::Settings::new()
+                        .with_ambient(30.0)
+                        .with_heating(15.0),
+                ),
+            Self::TallCanyon => {
+                settings.with_nutrients(settings::nutrients::Settings::new().with_depth(20))
+            }
+            Self::TwinSuns => settings,
+            Self::PolarLatitude => {
+                settings.with_temperature(settings::temperature::Settings::new().with_ambient(0.0))
+            }
+        };
+    }
+
+    /// Constructs the sun intensity
+    pub fn get_intensity(&self) -> WorldIntensity {
+        return match self {
+            Self::FlatDesert => {
+                WorldIntensity::Planet(new_planet(20.0, 1.2, constants::MAP_SUN_DAY))
+            }
+            Self::TallCanyon => {
+                // The rims are fully lit while the floor only gets a fraction
+                let shade = vec![1.0, 1.0, 0.7, 0.4, 0.2, 0.1, 0.1, 0.2, 0.4, 0.7, 1.0, 1.0];
+                let sun = new_planet(
+                    constants::MAP_SUN_LATITUDE_DEGREES,
+                    constants::MAP_SUN_INTENSITY,
+                    constants::MAP_SUN_DAY,
+                );
+                WorldIntensity::Shaded(sun::IntensityShaded::new(sun, shade))
+            }
+            Self::TwinSuns => {
+                let first = new_planet(
+                    constants::MAP_SUN_LATITUDE_DEGREES,
+                    0.6,
+                    constants::MAP_SUN_DAY,
+                );
+                let second = new_planet(
+                    constants::MAP_SUN_LATITUDE_DEGREES,
+                    0.6,
+                    0.7 * constants::MAP_SUN_DAY,
+                );
+                WorldIntensity::TwinSuns(sun::IntensityBinaryStar::new(first, second))
+            }
+            Self::PolarLatitude => WorldIntensity::Planet(new_planet(
+                75.0,
+                constants::MAP_SUN_INTENSITY,
+                constants::MAP_SUN_DAY,
+            )),
+        };
+    }
+
+    /// Constructs a new empty map of the world
+    ///
+    /// # Parameters
+    ///
+    /// seed: The seed of the simulation
+    pub fn new_map(&self, seed: u64) -> Map<WorldIntensity> {
+        return Map::new(
+            self.get_size(),
+            self.get_settings().with_seed(seed),
+            self.get_intensity(),
+        );
+    }
+}
+
+/// Constructs the sun of a planet using the default tilt and year
+///
+/// # Parameters
+///
+/// latitude: The latitude of the map in degrees
+///
+/// intensity: The intensity of the sun
+///
+/// day: The number of steps in a day
+fn new_planet(latitude: f64, intensity: f64, day: f64) -> IntensityPlanet {
+    let sun_year = sun::IntensityYearPlanet::new(
+        constants::MAP_SUN_TILT_DEGREES * constants::MATH_PI / 180.0,
+        latitude * constants::MATH_PI / 180.0,
+        constants::MAP_SUN_YEAR,
+        intensity,
+    );
+    let sun_day = sun::IntensityDayPlanet::new(day);
+
+    return sun::IntensityYearDay::new(sun_year, sun_day);
+}
+
+/// The sun intensity of any world such that maps of different worlds have
+/// the same type
+#[derive(Clone, Debug)]
+pub enum WorldIntensity {
+    /// A single sun on a planet
+    Planet(IntensityPlanet),
+    /// A single sun on a planet where some columns are shaded
+    Shaded(sun::IntensityShaded<IntensityPlanet>),
+    /// Two suns on a planet adding their light
+    TwinSuns(sun::IntensityBinaryStar<IntensityPlanet, IntensityPlanet>),
+}
+
+impl sun::Intensity for WorldIntensity {
+    fn get_intensity(&self, tile: usize, t: usize) -> (f64, f64) {
+        return match self {
+            Self::Planet(intensity) => intensity.get_intensity(tile, t),
+            Self::Shaded(intensity) => intensity.get_intensity(tile, t),
+            Self::TwinSuns(intensity) => intensity.get_intensity(tile, t),
+        };
+    }
+
+    fn get_angle(&self, tile: usize, t: usize) -> Option<f64> {
+        return match self {
+            Self::Planet(intensity) => intensity.get_angle(tile, t),
+            Self::Shaded(intensity) => intensity.get_angle(tile, t),
+            Self::TwinSuns(intensity) => intensity.get_angle(tile, t),
+        };
+    }
+
+    fn get_time_of_day(&self, t: usize) -> Option<f64> {
+        return match self {
+            Self::Planet(intensity) => intensity.get_time_of_day(t),
+            Self::Shaded(intensity) => intensity.get_time_of_day(t),
+            Self::TwinSuns(intensity) => intensity.get_time_of_day(t),
+        };
+    }
+
+    fn get_time_of_year(&self, t: usize) -> Option<f64> {
+        return match self {
+            Self::Planet(intensity) => intensity.get_time_of_year(t),
+            Self::Shaded(intensity) => intensity.get_time_of_year(t),
+            Self::TwinSuns(intensity) => intensity.get_time_of_year(t),
+        };
+    }
+
+    fn get_size(&self) -> usize {
+        return match self {
+            Self::Planet(intensity) => intensity.get_size(),
+            Self::Shaded(intensity) => intensity.get_size(),
+            Self::TwinSuns(intensity) => intensity.get_size(),
+        };
+    }
+
+    fn set_size(&mut self, size: usize) {
+        match self {
+            Self::Planet(intensity) => intensity.set_size(size),
+            Self::Shaded(intensity) => intensity.set_size(size),
+            Self::TwinSuns(intensity) => intensity.set_size(size),
+        }
+    }
+}
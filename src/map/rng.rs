@@ -0,0 +1,85 @@
+use rand::{SeedableRng, rngs::StdRng};
+
+/// The random number generator of the simulation, every generator handed out
+/// is derived only from the seed, the time step, a stream id and an index such
+/// that a run is reproducible from its seed regardless of the order in which
+/// random values are drawn
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SimRng {
+    /// The seed of the simulation
+    seed: u64,
+}
+
+impl SimRng {
+    /// The stream used for randomizing the update order of tiles
+    pub const STREAM_UPDATE_ORDER: u64 = 0;
+    /// The stream used by each tile when updating, like for mutations
+    pub const STREAM_TILE: u64 = 1;
+    /// The stream used for choosing where falling seeds land
+    pub const STREAM_FALL: u64 = 2;
+    /// The stream used for shaping the clouds
+    pub const STREAM_WEATHER: u64 = 3;
+    /// The stream used for spawning plants spontaneously
+    pub const STREAM_GENESIS: u64 = 4;
+    /// The stream used for the drift of pollen
+    pub const STREAM_DRIFT: u64 = 5;
+    /// The stream used by flowers emitting and receiving pollen
+    pub const STREAM_POLLEN: u64 = 6;
+    /// The stream used for choosing the columns grazed by herbivores
+    pub const STREAM_GRAZING: u64 = 7;
+    /// The stream used for shaping the fertility of the tiles
+    pub const STREAM_FERTILITY: u64 = 8;
+    /// The stream used for blowing falling seeds sideways
+    pub const STREAM_WIND: u64 = 9;
+    /// The stream used for deciding when catastrophes strike and where
+    pub const STREAM_CATASTROPHE: u64 = 10;
+    /// The stream used for choosing the leaves killed by a frost
+    pub const STREAM_FROST: u64 = 11;
+    /// The stream used for shaping the terrain
+    pub const STREAM_TERRAIN: u64 = 12;
+
+    /// Constructs a new random number generator
+    ///
+    /// # Parameters
+    ///
+    /// seed: The seed of the simulation
+    pub fn new(seed: u64) -> Self {
+        return Self { seed };
+    }
+
+    /// Retrieves the seed of the simulation
+    pub fn get_seed(&self) -> u64 {
+        return self.seed;
+    }
+
+    /// Gets a generator for a single purpose at a single time step
+    ///
+    /// # Parameters
+    ///
+    /// time: The time step
+    ///
+    /// stream: The id of what the random values are used for
+    ///
+    /// index: The index of the object drawing the values, like a tile index
+    pub fn get(&self, time: usize, stream: u64, index: usize) -> StdRng {
+        let seed = [time as u64, stream, index as u64]
+            .iter()
+            .fold(mix(self.seed), |state, value| mix(state ^ value));
+
+        return StdRng::seed_from_u64(seed);
+    }
+}
+
+/// Scrambles the bits of a value such that similar inputs give unrelated
+/// outputs, this is the finalizer of SplitMix64
+///
+/// # Parameters
+///
+/// value: The value to scramble
+fn mix(value: u64) -> u64 {
+    let mut value = value.wrapping_add(0x9e3779b97f4a7c15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d049bb133111eb);
+
+    return value ^ (value >> 31);
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+/// All settings for checking that energy is conserved every step, this is a
+/// debugging aid which compares the change of the energy stored in the plants
+/// with the energy gained, spent and lost during the step
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// True if the energy balance is checked after every step
+    pub enabled: bool,
+    /// The largest allowed difference between the change of the stored energy
+    /// and the energy balance of a step
+    pub tolerance: f64,
+    /// What to do when the energy is not conserved
+    pub action: Action,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            enabled: false,
+            tolerance: 1e-6,
+            action: Action::Log,
+        };
+    }
+
+    /// Sets if the audit is enabled and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// enabled: True if the energy balance is checked after every step
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+
+        return self;
+    }
+
+    /// Sets the tolerance and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// tolerance: The largest allowed difference of the energy balance
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+
+        return self;
+    }
+
+    /// Sets the action on violations and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// action: What to do when the energy is not conserved
+    pub fn with_action(mut self, action: Action) -> Self {
+        self.action = action;
+
+        return self;
+    }
+}
+
+/// What to do when a step does not conserve energy
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    /// Print the violation and continue the simulation
+    Log,
+    /// Panic with the violation
+    Panic,
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+/// All settings for dividing the map into chunks
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The side length of a chunk in tiles
+    pub size: usize,
+    /// If true then chunks without any plants nearby skip their plant updates
+    pub sleep: bool,
+    /// The number of steps a chunk must be without plants in or next to it
+    /// before it falls asleep
+    pub sleep_delay: usize,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            size: 16,
+            sleep: true,
+            sleep_delay: 8,
+        };
+    }
+
+    /// Sets the side length of a chunk and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// size: The side length of a chunk in tiles
+    pub fn with_size(mut self, size: usize) -> Self {
+        self.size = size;
+
+        return self;
+    }
+
+    /// Sets whether static chunks may fall asleep and returns the updated
+    /// settings
+    ///
+    /// # Parameters
+    ///
+    /// sleep: True if static chunks may fall asleep
+    pub fn with_sleep(mut self, sleep: bool) -> Self {
+        self.sleep = sleep;
+
+        return self;
+    }
+
+    /// Sets the number of idle steps before a chunk falls asleep and returns
+    /// the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// delay: The number of idle steps
+    pub fn with_sleep_delay(mut self, delay: usize) -> Self {
+        self.sleep_delay = delay;
+
+        return self;
+    }
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// All settings for the detritus left behind by dead plants
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The fraction of the light blocked by fresh detritus, it blocks less as
+    /// it decays
+    pub opacity: f64,
+    /// The amount of detritus decaying each step, fresh detritus has an
+    /// amount of 1 such that it lasts 1 / decay steps
+    pub decay: f64,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            opacity: 0.5,
+            decay: 0.02,
+        };
+    }
+
+    /// Sets the opacity and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// opacity: The fraction of the light blocked by fresh detritus
+    pub fn with_opacity(mut self, opacity: f64) -> Self {
+        self.opacity = opacity;
+
+        return self;
+    }
+
+    /// Sets the decay rate and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// decay: The amount of detritus decaying each step
+    pub fn with_decay(mut self, decay: f64) -> Self {
+        self.decay = decay;
+
+        return self;
+    }
+}
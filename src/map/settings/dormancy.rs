@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+/// All settings for ripe seeds lying dormant after they have landed
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The largest number of steps a landed seed waits for its program to
+    /// start growing before it germinates anyway, 0 germinates on landing
+    pub duration: usize,
+    /// The factor multiplied onto the running cost of a dormant seed
+    pub running: f64,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            duration: 0,
+            running: 0.1,
+        };
+    }
+
+    /// Sets the largest number of steps a seed is dormant and returns the
+    /// updated settings
+    ///
+    /// # Parameters
+    ///
+    /// duration: The largest number of steps a landed seed waits
+    pub fn with_duration(mut self, duration: usize) -> Self {
+        self.duration = duration;
+
+        return self;
+    }
+
+    /// Sets the running cost factor of dormant seeds and returns the updated
+    /// settings
+    ///
+    /// # Parameters
+    ///
+    /// running: The factor multiplied onto the running cost
+    pub fn with_running(mut self, running: f64) -> Self {
+        self.running = running;
+
+        return self;
+    }
+}
@@ -1,5 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 /// The base energy cost of building a bridge
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
 pub struct Settings {
     /// The base cost for a log
     pub log: f64,
@@ -1,9 +1,12 @@
+use serde::{Deserialize, Serialize};
+
 pub mod bridge;
 
 pub mod bulk;
 
 /// The base energy cost of building
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
 pub struct Settings {
     /// The base cost for a bridge
     pub bridge: bridge::Settings,
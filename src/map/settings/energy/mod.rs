@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 pub mod base;
 
 pub mod production;
@@ -9,7 +11,8 @@ pub mod transfer;
 pub mod running;
 
 /// The energy cost
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
 pub struct Settings {
     /// The base cost when building
     pub base: base::Settings,
@@ -1,5 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 /// The scaling energy cost of production
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
 pub struct Settings {
     /// The scaling cost for a leaf
     pub leaf: f64,
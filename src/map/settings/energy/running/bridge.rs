@@ -1,5 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 /// The running energy cost multiplier for a bridge (cost per step is build cost multiplied by this value)
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
 pub struct Settings {
     /// The running cost multiplier for a log
     pub log: f64,
@@ -1,14 +1,21 @@
+use serde::{Deserialize, Serialize};
+
 pub mod bridge;
 
 pub mod bulk;
 
+pub mod program;
+
 /// The running energy cost multiplier (cost per step is build cost multiplied by this value)
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
 pub struct Settings {
     /// The running cost multiplier for a bridge
     pub bridge: bridge::Settings,
     /// The running cost multiplier for a bulk
     pub bulk: bulk::Settings,
+    /// The running cost of the program
+    pub program: program::Settings,
 }
 
 impl Settings {
@@ -17,6 +24,7 @@ impl Settings {
         return Self {
             bridge: bridge::Settings::new(),
             bulk: bulk::Settings::new(),
+            program: program::Settings::new(),
         };
     }
 
@@ -41,4 +49,15 @@ impl Settings {
 
         return self;
     }
+
+    /// Sets the running cost of the program and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The settings to set
+    pub fn with_program(mut self, settings: program::Settings) -> Self {
+        self.program = settings;
+
+        return self;
+    }
 }
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// The running energy cost of the program of a plant, paid every step by
+/// every tile of the plant
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The cost for every operator in the program
+    pub operator: f64,
+    /// The cost for every operator which is reachable from the entry action,
+    /// operators which can never be evaluated are not included
+    pub active_operator: f64,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            operator: 0.0,
+            active_operator: 0.0,
+        };
+    }
+
+    /// Sets the cost for every operator in the program and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// cost: The new cost
+    pub fn with_operator(mut self, cost: f64) -> Self {
+        self.operator = cost;
+
+        return self;
+    }
+
+    /// Sets the cost for every reachable operator in the program and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// cost: The new cost
+    pub fn with_active_operator(mut self, cost: f64) -> Self {
+        self.active_operator = cost;
+
+        return self;
+    }
+}
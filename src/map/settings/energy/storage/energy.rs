@@ -1,5 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 /// The energy cost of building energy storage
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
 pub struct Settings {
     /// The storage cost for a log
     pub log: f64,
@@ -9,6 +12,12 @@ pub struct Settings {
     pub leaf: f64,
     /// The storage cost for a seed
     pub seed: f64,
+    /// The storage cost for a root
+    pub root: f64,
+    /// The storage cost for a flower
+    pub flower: f64,
+    /// The storage cost for a thorn
+    pub thorn: f64,
 }
 
 impl Settings {
@@ -19,6 +28,9 @@ impl Settings {
             sugar_bulb: 1.0,
             leaf: 1.0,
             seed: 1.0,
+            root: 1.0,
+            flower: 1.0,
+            thorn: 1.0,
         };
     }
 
@@ -65,4 +77,37 @@ impl Settings {
 
         return self;
     }
+
+    /// Sets the storage cost for a root and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// cost: The new cost
+    pub fn with_root(mut self, cost: f64) -> Self {
+        self.root = cost;
+
+        return self;
+    }
+
+    /// Sets the storage cost for a flower and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// cost: The new cost
+    pub fn with_flower(mut self, cost: f64) -> Self {
+        self.flower = cost;
+
+        return self;
+    }
+
+    /// Sets the storage cost for a thorn and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// cost: The new cost
+    pub fn with_thorn(mut self, cost: f64) -> Self {
+        self.thorn = cost;
+
+        return self;
+    }
 }
@@ -1,7 +1,10 @@
+use serde::{Deserialize, Serialize};
+
 pub mod energy;
 
 /// The energy cost when building storage
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
 pub struct Settings {
     /// The cost when building energy storage
     pub energy: energy::Settings,
@@ -1,10 +1,17 @@
+use serde::{Deserialize, Serialize};
+
 pub mod energy;
 
+pub mod water;
+
 /// The scaling energy cost of building transfer
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
 pub struct Settings {
     /// The scaling cost for energy transfer
     pub energy: energy::Settings,
+    /// The scaling cost for water transfer
+    pub water: water::Settings,
 }
 
 impl Settings {
@@ -12,6 +19,7 @@ impl Settings {
     pub fn new() -> Self {
         return Self {
             energy: energy::Settings::new(),
+            water: water::Settings::new(),
         };
     }
 
@@ -25,4 +33,15 @@ impl Settings {
 
         return self;
     }
+
+    /// Sets the scaling cost for water transfer and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The settings to set
+    pub fn with_water(mut self, settings: water::Settings) -> Self {
+        self.water = settings;
+
+        return self;
+    }
 }
@@ -0,0 +1,251 @@
+use serde::{Deserialize, Serialize};
+
+/// All settings for the catastrophes testing how well the plants recover,
+/// every catastrophe happens at random with its probability and on its
+/// schedule
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The eclipses darkening the sun
+    pub eclipse: Eclipse,
+    /// The wildfires burning a range of columns
+    pub wildfire: Wildfire,
+    /// The frosts killing leaves
+    pub frost: Frost,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            eclipse: Eclipse::new(),
+            wildfire: Wildfire::new(),
+            frost: Frost::new(),
+        };
+    }
+
+    /// Sets the eclipse settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// eclipse: The new eclipse settings
+    pub fn with_eclipse(mut self, eclipse: Eclipse) -> Self {
+        self.eclipse = eclipse;
+
+        return self;
+    }
+
+    /// Sets the wildfire settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// wildfire: The new wildfire settings
+    pub fn with_wildfire(mut self, wildfire: Wildfire) -> Self {
+        self.wildfire = wildfire;
+
+        return self;
+    }
+
+    /// Sets the frost settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// frost: The new frost settings
+    pub fn with_frost(mut self, frost: Frost) -> Self {
+        self.frost = frost;
+
+        return self;
+    }
+}
+
+/// When a catastrophe happens, both at random and on a fixed schedule
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Schedule::new")]
+pub struct Schedule {
+    /// The probability each step for the catastrophe to happen, 0 disables
+    /// the random catastrophes
+    pub probability: f64,
+    /// The first step the catastrophe happens at on the schedule
+    pub start: usize,
+    /// The number of steps between each scheduled catastrophe, 0 disables
+    /// the schedule
+    pub interval: usize,
+}
+
+impl Schedule {
+    /// Constructs a new schedule where the catastrophe never happens
+    pub fn new() -> Self {
+        return Self {
+            probability: 0.0,
+            start: 0,
+            interval: 0,
+        };
+    }
+
+    /// Sets the probability each step and returns the updated schedule
+    ///
+    /// # Parameters
+    ///
+    /// probability: The probability each step for the catastrophe to happen
+    pub fn with_probability(mut self, probability: f64) -> Self {
+        self.probability = probability;
+
+        return self;
+    }
+
+    /// Sets the fixed schedule and returns the updated schedule
+    ///
+    /// # Parameters
+    ///
+    /// start: The first step the catastrophe happens at
+    ///
+    /// interval: The number of steps between each catastrophe, 0 disables
+    /// the schedule
+    pub fn with_schedule(mut self, start: usize, interval: usize) -> Self {
+        self.start = start;
+        self.interval = interval;
+
+        return self;
+    }
+}
+
+/// All settings for the eclipses darkening the sun for a number of steps
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Eclipse::new")]
+pub struct Eclipse {
+    /// When the eclipses happen
+    pub schedule: Schedule,
+    /// The number of steps an eclipse lasts
+    pub duration: usize,
+    /// The factor multiplied onto the sun intensity during an eclipse
+    pub intensity: f64,
+}
+
+impl Eclipse {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            schedule: Schedule::new(),
+            duration: 50,
+            intensity: 0.05,
+        };
+    }
+
+    /// Sets the schedule and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// schedule: When the eclipses happen
+    pub fn with_schedule(mut self, schedule: Schedule) -> Self {
+        self.schedule = schedule;
+
+        return self;
+    }
+
+    /// Sets the duration and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// duration: The number of steps an eclipse lasts
+    pub fn with_duration(mut self, duration: usize) -> Self {
+        self.duration = duration;
+
+        return self;
+    }
+
+    /// Sets the intensity and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// intensity: The factor multiplied onto the sun intensity
+    pub fn with_intensity(mut self, intensity: f64) -> Self {
+        self.intensity = intensity;
+
+        return self;
+    }
+}
+
+/// All settings for the wildfires killing every plant tile above the ground
+/// in a range of columns
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Wildfire::new")]
+pub struct Wildfire {
+    /// When the wildfires happen
+    pub schedule: Schedule,
+    /// The number of columns burned by a wildfire
+    pub width: usize,
+}
+
+impl Wildfire {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            schedule: Schedule::new(),
+            width: 10,
+        };
+    }
+
+    /// Sets the schedule and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// schedule: When the wildfires happen
+    pub fn with_schedule(mut self, schedule: Schedule) -> Self {
+        self.schedule = schedule;
+
+        return self;
+    }
+
+    /// Sets the width and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// width: The number of columns burned by a wildfire
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = width;
+
+        return self;
+    }
+}
+
+/// All settings for the frosts killing leaves all over the map
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Frost::new")]
+pub struct Frost {
+    /// When the frosts happen
+    pub schedule: Schedule,
+    /// The probability for every leaf to be killed by a frost
+    pub damage: f64,
+}
+
+impl Frost {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            schedule: Schedule::new(),
+            damage: 0.5,
+        };
+    }
+
+    /// Sets the schedule and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// schedule: When the frosts happen
+    pub fn with_schedule(mut self, schedule: Schedule) -> Self {
+        self.schedule = schedule;
+
+        return self;
+    }
+
+    /// Sets the damage and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// damage: The probability for every leaf to be killed
+    pub fn with_damage(mut self, damage: f64) -> Self {
+        self.damage = damage;
+
+        return self;
+    }
+}
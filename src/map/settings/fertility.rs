@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+/// All settings for the fertility of the tiles scaling the energy gained by
+/// leaves, the fertility is a fixed 2D noise over the map
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// How far the fertility varies around 1, the fertility lies between
+    /// 1 - strength and 1 + strength, 0 makes all tiles equally fertile
+    pub strength: f64,
+    /// The size of the largest fertile patches in tiles
+    pub scale: f64,
+    /// The number of layers of noise, each layer adds patches half the size
+    /// and half the strength of the previous layer
+    pub octaves: usize,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            strength: 0.0,
+            scale: 30.0,
+            octaves: 3,
+        };
+    }
+
+    /// Sets the strength and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// strength: How far the fertility varies around 1
+    pub fn with_strength(mut self, strength: f64) -> Self {
+        self.strength = strength;
+
+        return self;
+    }
+
+    /// Sets the scale and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// scale: The size of the largest fertile patches in tiles
+    pub fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+
+        return self;
+    }
+
+    /// Sets the number of octaves and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// octaves: The number of layers of noise
+    pub fn with_octaves(mut self, octaves: usize) -> Self {
+        self.octaves = octaves;
+
+        return self;
+    }
+}
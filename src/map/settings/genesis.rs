@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+/// All settings for fresh plants appearing spontaneously on the map
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The probability each step for a free tile next to the ground to
+    /// spawn a new plant, 0 disables genesis
+    pub probability: f64,
+    /// The number of operators of each kind in the random genome
+    pub program_length: usize,
+    /// The energy capacity of a new plant, it starts filled up
+    pub energy_capacity: f64,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            probability: 0.0,
+            program_length: 8,
+            energy_capacity: 16.0,
+        };
+    }
+
+    /// Sets the genesis probability and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// probability: The probability each step for a tile to spawn a plant
+    pub fn with_probability(mut self, probability: f64) -> Self {
+        self.probability = probability;
+
+        return self;
+    }
+
+    /// Sets the program length and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// program_length: The number of operators of each kind in the genome
+    pub fn with_program_length(mut self, program_length: usize) -> Self {
+        self.program_length = program_length;
+
+        return self;
+    }
+
+    /// Sets the energy capacity and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// energy_capacity: The energy capacity of a new plant
+    pub fn with_energy_capacity(mut self, energy_capacity: f64) -> Self {
+        self.energy_capacity = energy_capacity;
+
+        return self;
+    }
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/// All settings for the herbivores grazing on the plants
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The probability each step for every column to be grazed, 0 disables
+    /// grazing
+    pub pressure: f64,
+    /// The fraction of the energy eaten from the grazed plant tile
+    pub damage: f64,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            pressure: 0.0,
+            damage: 0.5,
+        };
+    }
+
+    /// Sets the grazing pressure and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// pressure: The probability each step for every column to be grazed
+    pub fn with_pressure(mut self, pressure: f64) -> Self {
+        self.pressure = pressure;
+
+        return self;
+    }
+
+    /// Sets the damage and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// damage: The fraction of the energy eaten from the grazed plant tile
+    pub fn with_damage(mut self, damage: f64) -> Self {
+        self.damage = damage;
+
+        return self;
+    }
+}
@@ -1,14 +1,110 @@
+use serde::{Deserialize, Serialize};
+
 pub mod transparency;
 
 pub mod energy;
 
+pub mod chunk;
+
+pub mod update;
+
+pub mod mutation;
+
+pub mod program;
+
+pub mod water;
+
+pub mod nutrients;
+
+pub mod weather;
+
+pub mod sky;
+
+pub mod temperature;
+
+pub mod genesis;
+
+pub mod pollen;
+
+pub mod herbivory;
+
+pub mod detritus;
+
+pub mod fertility;
+
+pub mod terrain;
+
+pub mod population;
+
+pub mod remodel;
+
+pub mod dormancy;
+
+pub mod species;
+
+pub mod events;
+
+pub mod audit;
+
+pub mod preset;
+
+pub mod wrap;
+
 /// All basic settings for a map
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
 pub struct Settings {
     /// All transparency settings
     pub transparency: transparency::Settings,
     /// All energy cost settings
     pub energy: energy::Settings,
+    /// All settings for dividing the map into chunks
+    pub chunk: chunk::Settings,
+    /// All settings for how tiles are updated
+    pub update: update::Settings,
+    /// All settings for mutating new plants
+    pub mutation: mutation::Settings,
+    /// All settings for running plant programs
+    pub program: program::Settings,
+    /// All settings for the water
+    pub water: water::Settings,
+    /// All settings for the nutrients in the ground
+    pub nutrients: nutrients::Settings,
+    /// All settings for the clouds shading the sun
+    pub weather: weather::Settings,
+    /// All settings for the diffuse light scattered by the sky
+    pub sky: sky::Settings,
+    /// All settings for the temperature of the tiles
+    pub temperature: temperature::Settings,
+    /// All settings for plants appearing spontaneously
+    pub genesis: genesis::Settings,
+    /// All settings for the pollen emitted by flowers
+    pub pollen: pollen::Settings,
+    /// All settings for the herbivores grazing on the plants
+    pub herbivory: herbivory::Settings,
+    /// All settings for the detritus left behind by dead plants
+    pub detritus: detritus::Settings,
+    /// All settings for the fertility of the tiles
+    pub fertility: fertility::Settings,
+    /// All settings for the terrain raising the ground
+    pub terrain: terrain::Settings,
+    /// All settings for limiting the number of plants
+    pub population: population::Settings,
+    /// All settings for plant tiles changing their structure after being
+    /// built
+    pub remodel: remodel::Settings,
+    /// All settings for landed seeds waiting to germinate
+    pub dormancy: dormancy::Settings,
+    /// All settings for clustering the lineages into species
+    pub species: species::Settings,
+    /// All settings for the catastrophes striking the map
+    pub events: events::Settings,
+    /// All settings for checking the conservation of energy
+    pub audit: audit::Settings,
+    /// The directions in which the map wraps around
+    pub wrap: wrap::Wrap,
+    /// The seed for all randomness in the simulation
+    pub seed: u64,
 }
 
 impl Settings {
@@ -17,6 +113,29 @@ impl Settings {
         return Self {
             transparency: transparency::Settings::new(),
             energy: energy::Settings::new(),
+            chunk: chunk::Settings::new(),
+            update: update::Settings::new(),
+            mutation: mutation::Settings::new(),
+            program: program::Settings::new(),
+            water: water::Settings::new(),
+            nutrients: nutrients::Settings::new(),
+            weather: weather::Settings::new(),
+            sky: sky::Settings::new(),
+            temperature: temperature::Settings::new(),
+            genesis: genesis::Settings::new(),
+            pollen: pollen::Settings::new(),
+            herbivory: herbivory::Settings::new(),
+            detritus: detritus::Settings::new(),
+            fertility: fertility::Settings::new(),
+            terrain: terrain::Settings::new(),
+            population: population::Settings::new(),
+            remodel: remodel::Settings::new(),
+            dormancy: dormancy::Settings::new(),
+            species: species::Settings::new(),
+            events: events::Settings::new(),
+            audit: audit::Settings::new(),
+            wrap: wrap::Wrap::Horizontal,
+            seed: 0,
         };
     }
 
@@ -41,4 +160,257 @@ impl Settings {
 
         return self;
     }
+
+    /// Sets the chunk settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new chunk settings
+    pub fn with_chunk(mut self, settings: chunk::Settings) -> Self {
+        self.chunk = settings;
+
+        return self;
+    }
+
+    /// Sets the update settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new update settings
+    pub fn with_update(mut self, settings: update::Settings) -> Self {
+        self.update = settings;
+
+        return self;
+    }
+
+    /// Sets the mutation settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new mutation settings
+    pub fn with_mutation(mut self, settings: mutation::Settings) -> Self {
+        self.mutation = settings;
+
+        return self;
+    }
+
+    /// Sets the program settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new program settings
+    pub fn with_program(mut self, settings: program::Settings) -> Self {
+        self.program = settings;
+
+        return self;
+    }
+
+    /// Sets the water settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new water settings
+    pub fn with_water(mut self, settings: water::Settings) -> Self {
+        self.water = settings;
+
+        return self;
+    }
+
+    /// Sets the nutrient settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new nutrient settings
+    pub fn with_nutrients(mut self, settings: nutrients::Settings) -> Self {
+        self.nutrients = settings;
+
+        return self;
+    }
+
+    /// Sets the weather settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new weather settings
+    pub fn with_weather(mut self, settings: weather::Settings) -> Self {
+        self.weather = settings;
+
+        return self;
+    }
+
+    /// Sets the sky settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new sky settings
+    pub fn with_sky(mut self, settings: sky::Settings) -> Self {
+        self.sky = settings;
+
+        return self;
+    }
+
+    /// Sets the temperature settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new temperature settings
+    pub fn with_temperature(mut self, settings: temperature::Settings) -> Self {
+        self.temperature = settings;
+
+        return self;
+    }
+
+    /// Sets the genesis settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new genesis settings
+    pub fn with_genesis(mut self, settings: genesis::Settings) -> Self {
+        self.genesis = settings;
+
+        return self;
+    }
+
+    /// Sets the pollen settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new pollen settings
+    pub fn with_pollen(mut self, settings: pollen::Settings) -> Self {
+        self.pollen = settings;
+
+        return self;
+    }
+
+    /// Sets the herbivory settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new herbivory settings
+    pub fn with_herbivory(mut self, settings: herbivory::Settings) -> Self {
+        self.herbivory = settings;
+
+        return self;
+    }
+
+    /// Sets the detritus settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new detritus settings
+    pub fn with_detritus(mut self, settings: detritus::Settings) -> Self {
+        self.detritus = settings;
+
+        return self;
+    }
+
+    /// Sets the fertility of the settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new fertility settings
+    pub fn with_fertility(mut self, settings: fertility::Settings) -> Self {
+        self.fertility = settings;
+
+        return self;
+    }
+
+    /// Sets the terrain settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new terrain settings
+    pub fn with_terrain(mut self, settings: terrain::Settings) -> Self {
+        self.terrain = settings;
+
+        return self;
+    }
+
+    /// Sets the population settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new population settings
+    pub fn with_population(mut self, settings: population::Settings) -> Self {
+        self.population = settings;
+
+        return self;
+    }
+
+    /// Sets the remodel settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new remodel settings
+    pub fn with_remodel(mut self, settings: remodel::Settings) -> Self {
+        self.remodel = settings;
+
+        return self;
+    }
+
+    /// Sets the dormancy settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new dormancy settings
+    pub fn with_dormancy(mut self, settings: dormancy::Settings) -> Self {
+        self.dormancy = settings;
+
+        return self;
+    }
+
+    /// Sets the species settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new species settings
+    pub fn with_species(mut self, settings: species::Settings) -> Self {
+        self.species = settings;
+
+        return self;
+    }
+
+    /// Sets the catastrophe settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new catastrophe settings
+    pub fn with_events(mut self, settings: events::Settings) -> Self {
+        self.events = settings;
+
+        return self;
+    }
+
+    /// Sets the audit settings and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// settings: The new audit settings
+    pub fn with_audit(mut self, settings: audit::Settings) -> Self {
+        self.audit = settings;
+
+        return self;
+    }
+
+    /// Sets the wrapping of the map and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// wrap: The directions in which the map wraps around
+    pub fn with_wrap(mut self, wrap: wrap::Wrap) -> Self {
+        self.wrap = wrap;
+
+        return self;
+    }
+
+    /// Sets the seed and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// seed: The new seed
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+
+        return self;
+    }
 }
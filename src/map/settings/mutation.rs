@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+/// All settings for mutating the genome of new plants
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The probability for each operator to have one of its indices replaced
+    /// by a random index
+    pub point_rate: f64,
+    /// The probability for each operator to be replaced by another operator
+    /// type using the same indices
+    pub swap_rate: f64,
+    /// The maximum relative change of each numeric parameter, a value of 0.1
+    /// allows changes of up to 10%
+    pub parameter_jitter: f64,
+    /// True if operators which can never be evaluated are removed from every
+    /// mutated genome, this keeps genomes small over long runs
+    pub simplify: bool,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            point_rate: 0.01,
+            swap_rate: 0.005,
+            parameter_jitter: 0.05,
+            simplify: false,
+        };
+    }
+
+    /// Sets the point mutation rate and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// rate: The probability for each operator to get a new index
+    pub fn with_point_rate(mut self, rate: f64) -> Self {
+        self.point_rate = rate;
+
+        return self;
+    }
+
+    /// Sets the operator swap rate and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// rate: The probability for each operator to change type
+    pub fn with_swap_rate(mut self, rate: f64) -> Self {
+        self.swap_rate = rate;
+
+        return self;
+    }
+
+    /// Sets the parameter jitter and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// jitter: The maximum relative change of each numeric parameter
+    pub fn with_parameter_jitter(mut self, jitter: f64) -> Self {
+        self.parameter_jitter = jitter;
+
+        return self;
+    }
+
+    /// Sets if mutated genomes are simplified and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// simplify: True if unreachable operators are removed from mutated
+    /// genomes
+    pub fn with_simplify(mut self, simplify: bool) -> Self {
+        self.simplify = simplify;
+
+        return self;
+    }
+}
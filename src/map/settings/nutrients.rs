@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+
+/// All settings for the nutrients in the ground
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The number of rows at the bottom of the map which are ground
+    pub depth: usize,
+    /// The maximum nutrient concentration of a ground tile, ground tiles
+    /// start out full
+    pub capacity: f64,
+    /// The nutrients added to every ground tile each step
+    pub replenish: f64,
+    /// The maximum nutrients a plant in a ground tile extracts each step
+    pub extraction: f64,
+    /// The maximum nutrients a root extracts each step, it replaces the
+    /// extraction rate for roots
+    pub root_extraction: f64,
+    /// The energy gained for each unit of nutrients extracted
+    pub energy: f64,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            depth: 3,
+            capacity: 1.0,
+            replenish: 0.001,
+            extraction: 0.01,
+            root_extraction: 0.05,
+            energy: 5.0,
+        };
+    }
+
+    /// Sets the depth of the ground and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// depth: The number of rows of ground
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+
+        return self;
+    }
+
+    /// Sets the nutrient capacity and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// capacity: The maximum nutrient concentration of a ground tile
+    pub fn with_capacity(mut self, capacity: f64) -> Self {
+        self.capacity = capacity;
+
+        return self;
+    }
+
+    /// Sets the replenish rate and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// replenish: The nutrients added each step
+    pub fn with_replenish(mut self, replenish: f64) -> Self {
+        self.replenish = replenish;
+
+        return self;
+    }
+
+    /// Sets the extraction rate and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// extraction: The maximum nutrients extracted by a plant each step
+    pub fn with_extraction(mut self, extraction: f64) -> Self {
+        self.extraction = extraction;
+
+        return self;
+    }
+
+    /// Sets the extraction rate of roots and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// extraction: The maximum nutrients extracted by a root each step
+    pub fn with_root_extraction(mut self, extraction: f64) -> Self {
+        self.root_extraction = extraction;
+
+        return self;
+    }
+
+    /// Sets the energy of nutrients and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// energy: The energy gained for each unit of nutrients
+    pub fn with_energy(mut self, energy: f64) -> Self {
+        self.energy = energy;
+
+        return self;
+    }
+}
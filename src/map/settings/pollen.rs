@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+/// All settings for the pollen emitted by flowers
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The probability each step for a flower to emit a grain of pollen, 0
+    /// disables pollination
+    pub emission: f64,
+    /// The number of columns every grain of pollen drifts to the right each
+    /// step, negative values drift to the left
+    pub wind: f64,
+    /// The largest random deviation in columns from the wind each step
+    pub turbulence: f64,
+    /// The number of steps a grain of pollen drifts before it decays
+    pub lifetime: usize,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            emission: 0.05,
+            wind: 0.5,
+            turbulence: 1.0,
+            lifetime: 100,
+        };
+    }
+
+    /// Sets the emission probability and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// emission: The probability each step for a flower to emit pollen
+    pub fn with_emission(mut self, emission: f64) -> Self {
+        self.emission = emission;
+
+        return self;
+    }
+
+    /// Sets the wind and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// wind: The number of columns pollen drifts each step
+    pub fn with_wind(mut self, wind: f64) -> Self {
+        self.wind = wind;
+
+        return self;
+    }
+
+    /// Sets the turbulence and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// turbulence: The largest random deviation from the wind each step
+    pub fn with_turbulence(mut self, turbulence: f64) -> Self {
+        self.turbulence = turbulence;
+
+        return self;
+    }
+
+    /// Sets the lifetime of pollen and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// lifetime: The number of steps pollen drifts before it decays
+    pub fn with_lifetime(mut self, lifetime: usize) -> Self {
+        self.lifetime = lifetime;
+
+        return self;
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// All settings for limiting the number of plants
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The largest number of tiles with a plant, including plants which are
+    /// being built, no plant spreads or appears while it is reached. None
+    /// for no limit
+    pub cap: Option<usize>,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self { cap: None };
+    }
+
+    /// Sets the plant cap and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// cap: The largest number of tiles with a plant, None for no limit
+    pub fn with_cap(mut self, cap: Option<usize>) -> Self {
+        self.cap = cap;
+
+        return self;
+    }
+}
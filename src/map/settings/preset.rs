@@ -0,0 +1,178 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use thiserror::Error;
+
+use super::Settings;
+
+/// A directory of named settings presets, each preset is a TOML file where
+/// any settings left out keep their default value
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PresetLibrary {
+    /// The directory containing the preset files
+    directory: PathBuf,
+}
+
+impl PresetLibrary {
+    /// The file extension of preset files
+    pub const EXTENSION: &'static str = "toml";
+
+    /// Constructs a new preset library
+    ///
+    /// # Parameters
+    ///
+    /// directory: The directory containing the preset files
+    pub fn new(directory: &Path) -> Self {
+        return Self {
+            directory: directory.to_path_buf(),
+        };
+    }
+
+    /// Retrieves the names of all presets in alphabetical order, an empty
+    /// list is returned if the directory does not exist
+    ///
+    /// # Errors
+    ///
+    /// Returns the io error if the directory could not be read
+    pub fn list(&self) -> Result<Vec<String>, io::Error> {
+        if !self.directory.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut names = fs::read_dir(&self.directory)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == Self::EXTENSION))
+            .filter_map(|path| {
+                path.file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+            })
+            .collect::<Vec<_>>();
+        names.sort();
+
+        return Ok(names);
+    }
+
+    /// Retrieves the path of a preset file
+    ///
+    /// # Parameters
+    ///
+    /// name: The name of the preset
+    pub fn get_path(&self, name: &str) -> PathBuf {
+        return self.directory.join(name).with_extension(Self::EXTENSION);
+    }
+
+    /// Loads a preset
+    ///
+    /// # Parameters
+    ///
+    /// name: The name of the preset
+    ///
+    /// # Errors
+    ///
+    /// See PresetError for a description of the different errors which may occur
+    pub fn load(&self, name: &str) -> Result<Settings, PresetError> {
+        return load(&self.get_path(name));
+    }
+
+    /// Saves settings as a preset, overwriting any existing preset with the
+    /// same name
+    ///
+    /// # Parameters
+    ///
+    /// name: The name of the preset
+    ///
+    /// settings: The settings to save
+    ///
+    /// # Errors
+    ///
+    /// See PresetError for a description of the different errors which may occur
+    pub fn save(&self, name: &str, settings: &Settings) -> Result<PathBuf, PresetError> {
+        fs::create_dir_all(&self.directory)?;
+        let path = self.get_path(name);
+        save(&path, settings)?;
+
+        return Ok(path);
+    }
+}
+
+/// Parses settings from a TOML fragment, any settings left out keep their
+/// default value
+///
+/// # Parameters
+///
+/// text: The TOML text
+///
+/// # Errors
+///
+/// See PresetError for a description of the different errors which may occur
+pub fn parse(text: &str) -> Result<Settings, PresetError> {
+    return Ok(toml::from_str(text)?);
+}
+
+/// Loads settings from a TOML file
+///
+/// # Parameters
+///
+/// path: The path of the file
+///
+/// # Errors
+///
+/// See PresetError for a description of the different errors which may occur
+pub fn load(path: &Path) -> Result<Settings, PresetError> {
+    let text = fs::read_to_string(path)?;
+
+    return parse(&text);
+}
+
+/// Writes all settings to a TOML file
+///
+/// # Parameters
+///
+/// path: The path of the file
+///
+/// settings: The settings to write
+///
+/// # Errors
+///
+/// See PresetError for a description of the different errors which may occur
+pub fn save(path: &Path, settings: &Settings) -> Result<(), PresetError> {
+    let text = toml::to_string_pretty(settings)?;
+    fs::write(path, text)?;
+
+    return Ok(());
+}
+
+/// The error types for when loading or saving a preset
+#[derive(Error, Debug)]
+pub enum PresetError {
+    /// The file could not be read or written
+    #[error("Unable to access preset file: {:?}", .0)]
+    Io(io::Error),
+    /// The content of the file is invalid
+    #[error("Unable to parse preset: {}", .0)]
+    Parse(toml::de::Error),
+    /// The settings could not be converted to TOML
+    #[error("Unable to serialize preset: {}", .0)]
+    Serialize(toml::ser::Error),
+}
+
+impl From<io::Error> for PresetError {
+    fn from(value: io::Error) -> Self {
+        return Self::Io(value);
+    }
+}
+
+impl From<toml::de::Error> for PresetError {
+    fn from(value: toml::de::Error) -> Self {
+        return Self::Parse(value);
+    }
+}
+
+impl From<toml::ser::Error> for PresetError {
+    fn from(value: toml::ser::Error) -> Self {
+        return Self::Serialize(value);
+    }
+}
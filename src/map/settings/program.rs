@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// All settings for running the programs of plants
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The maximum number of operators a plant may evaluate each step, any
+    /// operator beyond this returns its default value
+    pub operator_limit: usize,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            operator_limit: 256,
+        };
+    }
+
+    /// Sets the operator limit and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// limit: The maximum number of operators to evaluate each step
+    pub fn with_operator_limit(mut self, limit: usize) -> Self {
+        self.operator_limit = limit;
+
+        return self;
+    }
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+/// All settings for plant tiles changing their own structure after they have
+/// been built
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The largest amount the energy capacity of a plant tile may grow by in
+    /// a single step
+    pub capacity_growth: f64,
+    /// The largest energy capacity a plant tile may grow to
+    pub capacity_max: f64,
+    /// The energy cost for every unit the energy reserve is moved by
+    pub reserve_cost: f64,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            capacity_growth: 4.0,
+            capacity_max: 64.0,
+            reserve_cost: 0.1,
+        };
+    }
+
+    /// Sets the largest growth of the energy capacity in a step and returns
+    /// the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// capacity_growth: The largest amount the energy capacity may grow by in
+    /// a single step
+    pub fn with_capacity_growth(mut self, capacity_growth: f64) -> Self {
+        self.capacity_growth = capacity_growth;
+
+        return self;
+    }
+
+    /// Sets the largest energy capacity and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// capacity_max: The largest energy capacity a plant tile may grow to
+    pub fn with_capacity_max(mut self, capacity_max: f64) -> Self {
+        self.capacity_max = capacity_max;
+
+        return self;
+    }
+
+    /// Sets the cost of moving the energy reserve and returns the updated
+    /// settings
+    ///
+    /// # Parameters
+    ///
+    /// reserve_cost: The energy cost for every unit the energy reserve is
+    /// moved by
+    pub fn with_reserve_cost(mut self, reserve_cost: f64) -> Self {
+        self.reserve_cost = reserve_cost;
+
+        return self;
+    }
+}
@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+/// All settings for how the light of the sky reaches the tiles, the diffuse
+/// light scattered by the sky reaches a tile from the tiles above and beside
+/// it such that tiles below gaps in the canopy are lit even when the direct
+/// sun is blocked above them
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The fraction of the sun intensity scattered by the sky into diffuse
+    /// light, 0 disables the diffuse light
+    pub intensity: f64,
+    /// The weight of the light from the tiles beside a tile relative to the
+    /// light from the tiles above it, 0 lets the diffuse light only come
+    /// from above
+    pub spread: f64,
+    /// If true then the direct light falls in at the angle of the sun such
+    /// that shadows lean in the morning and evening, otherwise it always
+    /// falls straight down
+    pub angled: bool,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            intensity: 0.0,
+            spread: 0.5,
+            angled: false,
+        };
+    }
+
+    /// Sets the intensity and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// intensity: The fraction of the sun intensity scattered by the sky
+    pub fn with_intensity(mut self, intensity: f64) -> Self {
+        self.intensity = intensity;
+
+        return self;
+    }
+
+    /// Sets the spread and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// spread: The weight of the light from the tiles beside a tile
+    pub fn with_spread(mut self, spread: f64) -> Self {
+        self.spread = spread;
+
+        return self;
+    }
+
+    /// Sets if the direct light falls in at the angle of the sun and returns
+    /// the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// angled: True if the light falls in at the angle of the sun
+    pub fn with_angled(mut self, angled: bool) -> Self {
+        self.angled = angled;
+
+        return self;
+    }
+
+    /// Gets the fraction of the sun intensity which reaches the map as
+    /// direct light
+    pub fn get_direct(&self) -> f64 {
+        return 1.0 - self.intensity.clamp(0.0, 1.0);
+    }
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+/// All settings for clustering the lineages into species by the similarity
+/// of their genomes
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The number of steps between clustering the living lineages into
+    /// species, 0 disables the clustering
+    pub interval: usize,
+    /// The largest genome distance from the founder of a species for a new
+    /// lineage to join it, from 0 for identical genomes to 1 for genomes
+    /// without any operator in common
+    pub threshold: f64,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            interval: 100,
+            threshold: 0.2,
+        };
+    }
+
+    /// Sets the number of steps between clusterings and returns the updated
+    /// settings
+    ///
+    /// # Parameters
+    ///
+    /// interval: The number of steps between clusterings, 0 disables them
+    pub fn with_interval(mut self, interval: usize) -> Self {
+        self.interval = interval;
+
+        return self;
+    }
+
+    /// Sets the largest genome distance within a species and returns the
+    /// updated settings
+    ///
+    /// # Parameters
+    ///
+    /// threshold: The largest genome distance from the founder of a species
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+
+        return self;
+    }
+}
@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+
+/// All settings for the temperature of the tiles
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The temperature at the surface of the ground without any light
+    pub ambient: f64,
+    /// The drop in temperature for every row above the ground
+    pub lapse_rate: f64,
+    /// The increase of the temperature a tile settles at for each unit of
+    /// light
+    pub heating: f64,
+    /// The fraction of the difference between the temperature of a tile and
+    /// the temperature it settles at which is closed each step, lower values
+    /// make the temperature follow a longer history of the light
+    pub relaxation: f64,
+    /// The fraction of the difference between the temperature of a tile and
+    /// the average temperature of its neighbors which is closed each step
+    pub diffusion: f64,
+    /// The temperature at which the running costs of plants are lowest
+    pub optimum: f64,
+    /// The distance from the optimum temperature at which the running costs
+    /// have increased by the sensitivity
+    pub tolerance: f64,
+    /// The relative increase of the running costs of plants at a temperature
+    /// one tolerance away from the optimum, the increase grows quadratically
+    pub sensitivity: f64,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            ambient: 15.0,
+            lapse_rate: 0.1,
+            heating: 10.0,
+            relaxation: 0.05,
+            diffusion: 0.1,
+            optimum: 20.0,
+            tolerance: 10.0,
+            sensitivity: 0.0,
+        };
+    }
+
+    /// Sets the ambient temperature and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// ambient: The temperature at the surface without any light
+    pub fn with_ambient(mut self, ambient: f64) -> Self {
+        self.ambient = ambient;
+
+        return self;
+    }
+
+    /// Sets the lapse rate and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// lapse_rate: The drop in temperature for every row above the ground
+    pub fn with_lapse_rate(mut self, lapse_rate: f64) -> Self {
+        self.lapse_rate = lapse_rate;
+
+        return self;
+    }
+
+    /// Sets the heating by light and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// heating: The temperature increase for each unit of light
+    pub fn with_heating(mut self, heating: f64) -> Self {
+        self.heating = heating;
+
+        return self;
+    }
+
+    /// Sets the relaxation rate and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// relaxation: The fraction of the difference closed each step
+    pub fn with_relaxation(mut self, relaxation: f64) -> Self {
+        self.relaxation = relaxation;
+
+        return self;
+    }
+
+    /// Sets the diffusion rate and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// diffusion: The fraction of the difference to the neighbors closed each
+    /// step
+    pub fn with_diffusion(mut self, diffusion: f64) -> Self {
+        self.diffusion = diffusion;
+
+        return self;
+    }
+
+    /// Sets the optimal temperature for plants and returns the updated
+    /// settings
+    ///
+    /// # Parameters
+    ///
+    /// optimum: The temperature with the lowest running costs
+    pub fn with_optimum(mut self, optimum: f64) -> Self {
+        self.optimum = optimum;
+
+        return self;
+    }
+
+    /// Sets the temperature tolerance and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// tolerance: The distance from the optimum where the costs have
+    /// increased by the sensitivity
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+
+        return self;
+    }
+
+    /// Sets the temperature sensitivity and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// sensitivity: The relative cost increase one tolerance from the optimum
+    pub fn with_sensitivity(mut self, sensitivity: f64) -> Self {
+        self.sensitivity = sensitivity;
+
+        return self;
+    }
+
+    /// Gets the temperature of a tile without any light
+    ///
+    /// # Parameters
+    ///
+    /// altitude: The number of rows above the ground
+    pub fn get_ambient(&self, altitude: usize) -> f64 {
+        return self.ambient - self.lapse_rate * altitude as f64;
+    }
+
+    /// Gets the multiplier for the running costs of a plant at a temperature
+    ///
+    /// # Parameters
+    ///
+    /// temperature: The temperature of the tile of the plant
+    pub fn get_cost_multiplier(&self, temperature: f64) -> f64 {
+        let deviation = (temperature - self.optimum) / self.tolerance;
+
+        return 1.0 + self.sensitivity * deviation * deviation;
+    }
+}
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+/// All settings for the terrain raising the ground into hills and cliffs, the
+/// terrain is a fixed 1D noise over the columns of the map
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The number of rows the highest hills raise the ground, 0 keeps the
+    /// ground flat
+    pub height: usize,
+    /// The width of the largest hills in columns
+    pub scale: f64,
+    /// The number of layers of noise, each layer adds hills half the width
+    /// and half the height of the previous layer
+    pub octaves: usize,
+    /// The largest difference in height between neighboring columns in rows
+    /// which plants can still root in, steeper terrain is barren
+    pub slope: f64,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            height: 0,
+            scale: 40.0,
+            octaves: 3,
+            slope: 2.0,
+        };
+    }
+
+    /// Sets the height and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// height: The number of rows the highest hills raise the ground
+    pub fn with_height(mut self, height: usize) -> Self {
+        self.height = height;
+
+        return self;
+    }
+
+    /// Sets the scale and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// scale: The width of the largest hills in columns
+    pub fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+
+        return self;
+    }
+
+    /// Sets the number of octaves and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// octaves: The number of layers of noise
+    pub fn with_octaves(mut self, octaves: usize) -> Self {
+        self.octaves = octaves;
+
+        return self;
+    }
+
+    /// Sets the steepest slope and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// slope: The largest difference in height between neighboring columns
+    /// which plants can still root in
+    pub fn with_slope(mut self, slope: f64) -> Self {
+        self.slope = slope;
+
+        return self;
+    }
+}
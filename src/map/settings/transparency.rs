@@ -1,5 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 /// All transparency settings for a map
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
 pub struct Settings {
     /// The base transparency of any tile
     pub base: f64,
@@ -11,6 +14,14 @@ pub struct Settings {
     pub leaf: f64,
     /// The transparency of a seed
     pub seed: f64,
+    /// The transparency of a flower
+    pub flower: f64,
+    /// The transparency of a thorn
+    pub thorn: f64,
+    /// The fraction of the light blocked by a tile which is reflected evenly
+    /// to the two tiles below it and the two tiles beside it, 0 disables the
+    /// reflection
+    pub albedo: f64,
 }
 
 impl Settings {
@@ -22,6 +33,9 @@ impl Settings {
             sugar_bulb: 0.0,
             leaf: 1.0,
             seed: 0.0,
+            flower: 0.5,
+            thorn: 0.5,
+            albedo: 0.0,
         };
     }
 
@@ -79,4 +93,37 @@ impl Settings {
 
         return self;
     }
+
+    /// Sets the transparency of a flower and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// transparency: The new transparency to set
+    pub fn with_flower(mut self, transparency: f64) -> Self {
+        self.flower = transparency;
+
+        return self;
+    }
+
+    /// Sets the transparency of a thorn and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// transparency: The new transparency to set
+    pub fn with_thorn(mut self, transparency: f64) -> Self {
+        self.thorn = transparency;
+
+        return self;
+    }
+
+    /// Sets the albedo and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// albedo: The fraction of the blocked light which is reflected
+    pub fn with_albedo(mut self, albedo: f64) -> Self {
+        self.albedo = albedo;
+
+        return self;
+    }
 }
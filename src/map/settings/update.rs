@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/// All settings for how tiles are updated each step
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The order in which tiles get to act when they compete for a neighbor
+    pub order: Order,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            order: Order::Direction,
+        };
+    }
+
+    /// Sets the update order and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// order: The new update order
+    pub fn with_order(mut self, order: Order) -> Self {
+        self.order = order;
+
+        return self;
+    }
+}
+
+/// The order in which tiles are processed, all tiles see the state of the
+/// previous step so the order only decides which plant wins when several try
+/// to spread into the same tile
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Order {
+    /// The winner is decided by the fixed priority of the direction it spreads
+    /// from
+    Direction,
+    /// The tiles are processed in a new random order every step
+    Shuffled,
+    /// The tiles are processed in two checkerboard phases, alternating which
+    /// phase goes first every step
+    Checkerboard,
+}
@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+
+/// All settings for the water in a map
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// The water level of the ground, the bottom row of tiles is always kept
+    /// at this level and acts as the source of all water
+    pub source: f64,
+    /// The fraction of the difference in water level between two connected
+    /// plant tiles which moves through their bridge each step
+    pub diffusion: f64,
+    /// The fraction of the water in a plant tile which evaporates each step
+    pub evaporation: f64,
+    /// The amount of water a leaf uses for each unit of energy produced
+    pub leaf_usage: f64,
+    /// The amount of water a root draws from the ground each step
+    pub root_uptake: f64,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            source: 1.0,
+            diffusion: 0.1,
+            evaporation: 0.01,
+            leaf_usage: 1.0,
+            root_uptake: 0.05,
+        };
+    }
+
+    /// Sets the water level of the ground and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// source: The new water level of the ground
+    pub fn with_source(mut self, source: f64) -> Self {
+        self.source = source;
+
+        return self;
+    }
+
+    /// Sets the diffusion rate and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// diffusion: The fraction of the water difference moving each step
+    pub fn with_diffusion(mut self, diffusion: f64) -> Self {
+        self.diffusion = diffusion;
+
+        return self;
+    }
+
+    /// Sets the evaporation rate and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// evaporation: The fraction of water evaporating each step
+    pub fn with_evaporation(mut self, evaporation: f64) -> Self {
+        self.evaporation = evaporation;
+
+        return self;
+    }
+
+    /// Sets the water usage of leaves and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// usage: The water used for each unit of energy produced
+    pub fn with_leaf_usage(mut self, usage: f64) -> Self {
+        self.leaf_usage = usage;
+
+        return self;
+    }
+
+    /// Sets the water uptake of roots and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// uptake: The water drawn from the ground each step
+    pub fn with_root_uptake(mut self, uptake: f64) -> Self {
+        self.root_uptake = uptake;
+
+        return self;
+    }
+}
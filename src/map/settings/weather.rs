@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+
+use crate::constants::MATH_PI;
+
+/// All settings for the clouds drifting over the map and shading the sun
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Settings::new")]
+pub struct Settings {
+    /// How much of the sky is covered by clouds from 0 for a clear sky to 1
+    /// for full overcast
+    pub density: f64,
+    /// The number of columns the clouds move to the right each step
+    pub speed: f64,
+    /// The typical width of a cloud in columns
+    pub scale: f64,
+    /// The fraction of the sunlight blocked by a fully covering cloud
+    pub opacity: f64,
+    /// The average probability each step for a falling seed to be blown one
+    /// column to the right, negative values blow to the left, 0 disables
+    /// the wind
+    pub wind: f64,
+    /// How much the wind varies around its average over time
+    pub gust: f64,
+    /// The number of steps for the wind to vary through a full gust
+    pub gust_period: f64,
+}
+
+impl Settings {
+    /// Constructs a new default settings
+    pub fn new() -> Self {
+        return Self {
+            density: 0.0,
+            speed: 0.05,
+            scale: 20.0,
+            opacity: 0.8,
+            wind: 0.0,
+            gust: 0.0,
+            gust_period: 200.0,
+        };
+    }
+
+    /// Sets the cloud density and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// density: The fraction of the sky covered by clouds
+    pub fn with_density(mut self, density: f64) -> Self {
+        self.density = density;
+
+        return self;
+    }
+
+    /// Sets the cloud speed and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// speed: The number of columns the clouds move each step
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = speed;
+
+        return self;
+    }
+
+    /// Sets the cloud scale and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// scale: The typical width of a cloud in columns
+    pub fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+
+        return self;
+    }
+
+    /// Sets the cloud opacity and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// opacity: The fraction of the sunlight blocked by a full cloud
+    pub fn with_opacity(mut self, opacity: f64) -> Self {
+        self.opacity = opacity;
+
+        return self;
+    }
+
+    /// Sets the average wind and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// wind: The average probability for a falling seed to be blown to the
+    /// right each step
+    pub fn with_wind(mut self, wind: f64) -> Self {
+        self.wind = wind;
+
+        return self;
+    }
+
+    /// Sets the variation of the wind and returns the updated settings
+    ///
+    /// # Parameters
+    ///
+    /// gust: How much the wind varies around its average
+    ///
+    /// gust_period: The number of steps for a full gust
+    pub fn with_gust(mut self, gust: f64, gust_period: f64) -> Self {
+        self.gust = gust;
+        self.gust_period = gust_period;
+
+        return self;
+    }
+
+    /// Gets the wind at a time step, the probability for a falling seed to be
+    /// blown one column to the right, negative to the left
+    ///
+    /// # Parameters
+    ///
+    /// time: The time step
+    pub fn get_wind(&self, time: usize) -> f64 {
+        let phase = 2.0 * MATH_PI * time as f64 / self.gust_period;
+
+        return self.wind + self.gust * phase.sin();
+    }
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// The directions in which the map wraps around such that tiles on opposite
+/// edges are neighbors. The sun always shines into the top row and the water
+/// always rises from the bottom row, wrapping only connects the plants
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Wrap {
+    /// The map is bounded in both directions
+    None,
+    /// The map wraps in x and is bounded in y
+    Horizontal,
+    /// The map wraps in both x and y, the height must be even for the rows
+    /// to keep alternating across the edge
+    Both,
+}
+
+impl Wrap {
+    /// Checks if the map wraps in x
+    pub fn horizontal(&self) -> bool {
+        return matches!(self, Self::Horizontal | Self::Both);
+    }
+
+    /// Checks if the map wraps in y
+    pub fn vertical(&self) -> bool {
+        return matches!(self, Self::Both);
+    }
+}
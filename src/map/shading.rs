@@ -0,0 +1,123 @@
+use crate::types;
+
+use super::{Tile, TilePos, TilePosNeighbor, settings::Settings, sun};
+
+/// Gets the fraction of the light reaching every tile which is blocked by
+/// plants of other lineages, 0 for tiles without a living plant of a
+/// lineage. The light is propagated down from the sun in a single pass like
+/// the tiles do it step by step, once with every plant in place and once for
+/// every lineage with the plants of all other lineages letting the light
+/// through
+///
+/// # Parameters
+///
+/// tiles: All tiles of the map
+///
+/// sun: The sun tiles above the top row
+///
+/// size: The size of the map
+///
+/// map_settings: The settings for the map
+pub fn new_foreign_shade_field(
+    tiles: &[Tile],
+    sun: &[sun::Tile],
+    size: &types::ISize,
+    map_settings: &Settings,
+) -> Vec<f64> {
+    let lineages = tiles
+        .iter()
+        .map(|tile| tile.get_plant_lineage())
+        .collect::<Vec<_>>();
+    let shaded = tiles
+        .iter()
+        .map(|tile| tile.get_transparency())
+        .collect::<Vec<_>>();
+    let clear = tiles
+        .iter()
+        .map(|tile| tile.get_transparency_without_plant(map_settings))
+        .collect::<Vec<_>>();
+    let above = (0..tiles.len())
+        .map(|index| {
+            let pos = TilePos::from_index(index, size);
+            return [
+                pos.up_left(size, &map_settings.wrap),
+                pos.up_right(size, &map_settings.wrap),
+            ]
+            .map(|neighbor| match neighbor {
+                TilePosNeighbor::Valid(pos) => Some(pos.to_index(size)),
+                TilePosNeighbor::Invalid(_) => None,
+            });
+        })
+        .collect::<Vec<_>>();
+
+    let light = propagate_light(sun, size, &above, tiles.len(), |index| shaded[index]);
+
+    let mut ids = lineages.iter().flatten().copied().collect::<Vec<_>>();
+    ids.sort_unstable();
+    ids.dedup();
+
+    let mut shade = vec![0.0; tiles.len()];
+    for lineage in ids {
+        // Light below the lowest tile of the lineage is never needed
+        let end = lineages
+            .iter()
+            .rposition(|&other| other == Some(lineage))
+            .map_or(0, |index| (index / size.w + 1) * size.w);
+        let unshaded = propagate_light(sun, size, &above, end, |index| {
+            if lineages[index].is_some_and(|other| other != lineage) {
+                clear[index]
+            } else {
+                shaded[index]
+            }
+        });
+
+        (0..end)
+            .filter(|&index| lineages[index] == Some(lineage))
+            .for_each(|index| {
+                if unshaded[index] > 0.0 {
+                    shade[index] =
+                        ((unshaded[index] - light[index]) / unshaded[index]).clamp(0.0, 1.0);
+                }
+            });
+    }
+
+    return shade;
+}
+
+/// Propagates the light of the sun down through the rows of the map and
+/// returns the light of the first tiles, every tile receives half of the light
+/// let through by each of the tiles above it
+///
+/// # Parameters
+///
+/// sun: The sun tiles above the top row
+///
+/// size: The size of the map
+///
+/// above: The indices of the up-left and up-right neighbors of every tile
+///
+/// count: The number of tiles to find the light of, it must cover full rows
+///
+/// transparency: Gets the transparency of the tile at an index
+fn propagate_light(
+    sun: &[sun::Tile],
+    size: &types::ISize,
+    above: &[[Option<usize>; 2]],
+    count: usize,
+    transparency: impl Fn(usize) -> f64,
+) -> Vec<f64> {
+    let mut light = vec![0.0; count];
+    for index in 0..count {
+        light[index] = if index < size.w {
+            sun[index].intensity
+        } else {
+            0.5 * above[index]
+                .iter()
+                .flatten()
+                .map(|&neighbor| light[neighbor] * transparency(neighbor))
+                .sum::<f64>()
+        };
+    }
+
+    return light;
+}
@@ -0,0 +1,121 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{Plant, settings::species::Settings};
+
+/// The species the living lineages are clustered into, a new lineage joins
+/// the species with the founder genome closest to its own if it is within the
+/// threshold and otherwise founds a new species. A species keeps its id for
+/// as long as it has living lineages
+#[derive(Clone, Debug)]
+pub struct SpeciesSet {
+    /// The id of every living species together with the genome hash and the
+    /// plant tile of its founder, only the genome of the plant is compared
+    founders: Vec<(usize, u64, Plant)>,
+    /// The species of every lineage which was alive at the last clustering
+    lineages: HashMap<usize, usize>,
+    /// The id given to the next new species
+    next_id: usize,
+}
+
+impl SpeciesSet {
+    /// Constructs a new set without any species
+    pub fn new() -> Self {
+        return Self {
+            founders: Vec::new(),
+            lineages: HashMap::new(),
+            next_id: 0,
+        };
+    }
+
+    /// Retrieves the species of a lineage, None if the lineage was not alive
+    /// at the last clustering
+    ///
+    /// # Parameters
+    ///
+    /// lineage: The id of the lineage
+    pub fn get(&self, lineage: usize) -> Option<usize> {
+        return self.lineages.get(&lineage).copied();
+    }
+
+    /// Retrieves the number of living species
+    pub fn len(&self) -> usize {
+        return self.founders.len();
+    }
+
+    /// Checks if a species has appeared at an earlier clustering and has no
+    /// living lineages left
+    ///
+    /// # Parameters
+    ///
+    /// species: The id of the species
+    pub fn is_extinct(&self, species: usize) -> bool {
+        return species < self.next_id && self.founders.iter().all(|(id, _, _)| *id != species);
+    }
+
+    /// Clusters the living lineages, lineages keep the species they were
+    /// given at an earlier clustering and species without living lineages
+    /// go extinct
+    ///
+    /// # Parameters
+    ///
+    /// settings: The species settings
+    ///
+    /// plants: The lineage id, genome hash and a plant tile of every living
+    /// lineage, a lineage may be listed more than once
+    pub fn update<'a>(
+        &mut self,
+        settings: &Settings,
+        plants: impl Iterator<Item = (usize, u64, &'a Plant)>,
+    ) {
+        let mut lineages = HashMap::new();
+        for (lineage, hash, plant) in plants {
+            if lineages.contains_key(&lineage) {
+                continue;
+            }
+            let species = match self.lineages.get(&lineage) {
+                Some(&species) => species,
+                None => self.classify(settings, hash, plant),
+            };
+            lineages.insert(lineage, species);
+        }
+
+        let living = lineages.values().copied().collect::<HashSet<_>>();
+        self.founders.retain(|(id, _, _)| living.contains(id));
+        self.lineages = lineages;
+    }
+
+    /// Finds the species the genome of a plant belongs to, founding a new
+    /// species if no founder is close enough
+    ///
+    /// # Parameters
+    ///
+    /// settings: The species settings
+    ///
+    /// hash: The hash of the genome
+    ///
+    /// plant: The plant with the genome
+    fn classify(&mut self, settings: &Settings, hash: u64, plant: &Plant) -> usize {
+        let closest = self
+            .founders
+            .iter()
+            .map(|(id, founder_hash, founder)| {
+                let distance = if *founder_hash == hash {
+                    0.0
+                } else {
+                    founder.get_genome_distance(plant)
+                };
+                (*id, distance)
+            })
+            .filter(|(_, distance)| *distance <= settings.threshold)
+            .min_by(|(_, distance1), (_, distance2)| distance1.total_cmp(distance2));
+        if let Some((id, _)) = closest {
+            return id;
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.founders.push((id, hash, plant.clone()));
+
+        return id;
+    }
+}
@@ -0,0 +1,121 @@
+/// Aggregate values describing the state of a map at a single time step
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Statistics {
+    /// The time step the statistics were collected at
+    pub time: usize,
+    /// The number of tiles occupied by a plant
+    pub population: usize,
+    /// The total energy stored in all plants
+    pub energy: f64,
+    /// The energy moved through all bridges during the last step
+    pub transfer: f64,
+    /// The largest total energy moved through a single bridge since it was
+    /// built
+    pub max_throughput: f64,
+    /// The number of species at the last clustering of the lineages
+    pub species: usize,
+}
+
+impl Statistics {
+    /// Constructs a new set of statistics for an empty map
+    ///
+    /// # Parameters
+    ///
+    /// time: The time step the statistics are collected at
+    pub fn new(time: usize) -> Self {
+        return Self {
+            time,
+            population: 0,
+            energy: 0.0,
+            transfer: 0.0,
+            max_throughput: 0.0,
+            species: 0,
+        };
+    }
+
+    /// Adds a single plant tile to the statistics
+    ///
+    /// # Parameters
+    ///
+    /// energy: The energy stored in the plant tile
+    pub fn add_plant(&mut self, energy: f64) {
+        self.population += 1;
+        self.energy += energy;
+    }
+
+    /// Adds one end of a bridge to the statistics, both ends of a bridge see
+    /// the same transfer so each end adds half of it
+    ///
+    /// # Parameters
+    ///
+    /// transfer: The energy received through the bridge during the last
+    /// step, negative if it was sent
+    ///
+    /// throughput: The total energy moved through the bridge since it was
+    /// built
+    pub fn add_bridge(&mut self, transfer: f64, throughput: f64) {
+        self.transfer += 0.5 * transfer.abs();
+        self.max_throughput = self.max_throughput.max(throughput);
+    }
+
+    /// Retrieves the name and value of every statistic in a fixed order, used
+    /// for exporting
+    pub fn get_fields(&self) -> Vec<(&'static str, f64)> {
+        return vec![
+            ("time", self.time as f64),
+            ("population", self.population as f64),
+            ("energy", self.energy),
+            ("transfer", self.transfer),
+            ("max_throughput", self.max_throughput),
+            ("species", self.species as f64),
+        ];
+    }
+}
+
+/// Aggregate values describing the plants of a single species
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpeciesStatistics {
+    /// The id of the species, None for the plants without a species such as
+    /// dead plants and lineages born since the last clustering
+    pub species: Option<usize>,
+    /// The number of tiles occupied by a plant of the species
+    pub population: usize,
+    /// The total energy stored in the plants of the species
+    pub energy: f64,
+}
+
+impl SpeciesStatistics {
+    /// Constructs a new set of statistics for a species without plants
+    ///
+    /// # Parameters
+    ///
+    /// species: The id of the species, None for the plants without a species
+    pub fn new(species: Option<usize>) -> Self {
+        return Self {
+            species,
+            population: 0,
+            energy: 0.0,
+        };
+    }
+
+    /// Adds a single plant tile to the statistics
+    ///
+    /// # Parameters
+    ///
+    /// energy: The energy stored in the plant tile
+    pub fn add_plant(&mut self, energy: f64) {
+        self.population += 1;
+        self.energy += energy;
+    }
+}
+
+/// The light reaching the ground and the plant biomass in every column of a
+/// map
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnProfile {
+    /// The light level of the topmost ground tile of every column, the bottom
+    /// tile is used for columns without ground
+    pub light: Vec<f64>,
+    /// The number of tiles occupied by a plant in every column
+    pub biomass: Vec<usize>,
+}
@@ -0,0 +1,75 @@
+use super::Intensity;
+
+/// The light of a binary star where both stars follow their own yearly and
+/// daily cycles, the primary intensity is the light of the primary star and
+/// the secondary intensity is the light of its companion such that the light
+/// of each star can be told apart
+#[derive(Clone, Debug)]
+pub struct IntensityBinaryStar<A: Intensity, B: Intensity> {
+    /// The intensity of the primary star
+    primary: A,
+    /// The intensity of the companion star
+    secondary: B,
+}
+
+impl<A: Intensity, B: Intensity> IntensityBinaryStar<A, B> {
+    /// Constructs a new binary star intensity
+    ///
+    /// # Parameters
+    ///
+    /// primary: The intensity of the primary star
+    ///
+    /// secondary: The intensity of the companion star
+    pub fn new(primary: A, secondary: B) -> Self {
+        return Self { primary, secondary };
+    }
+
+    /// Retrieves the intensity of the primary star
+    pub fn get_primary(&self) -> &A {
+        return &self.primary;
+    }
+
+    /// Retrieves the intensity of the companion star
+    pub fn get_secondary(&self) -> &B {
+        return &self.secondary;
+    }
+}
+
+impl<A: Intensity, B: Intensity> Intensity for IntensityBinaryStar<A, B> {
+    fn get_intensity(&self, tile: usize, t: usize) -> (f64, f64) {
+        let primary = self.primary.get_intensity(tile, t);
+        let secondary = self.secondary.get_intensity(tile, t);
+        return (primary.0 + primary.1, secondary.0 + secondary.1);
+    }
+
+    fn iter(&self, t: usize) -> impl Iterator<Item = (f64, f64)> {
+        return self
+            .primary
+            .iter(t)
+            .zip(self.secondary.iter(t))
+            .map(|(primary, secondary)| {
+                return (primary.0 + primary.1, secondary.0 + secondary.1);
+            });
+    }
+
+    fn get_time_of_day(&self, t: usize) -> Option<f64> {
+        return self.primary.get_time_of_day(t);
+    }
+
+    fn get_angle(&self, tile: usize, t: usize) -> Option<f64> {
+        return self.primary.get_angle(tile, t);
+    }
+
+    fn get_time_of_year(&self, t: usize) -> Option<f64> {
+        return self.primary.get_time_of_year(t);
+    }
+
+    fn get_size(&self) -> usize {
+        return self.primary.get_size();
+    }
+
+    fn set_size(&mut self, size: usize) {
+        self.primary.set_size(size);
+        self.secondary.set_size(size);
+    }
+}
@@ -0,0 +1,127 @@
+use super::Intensity;
+
+/// Describes how the two intensities of a composite are combined
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompositeOperation {
+    /// The intensities are added together
+    Sum,
+    /// The intensities are multiplied together
+    Product,
+}
+
+impl CompositeOperation {
+    /// Combines two intensities, the primary and secondary intensities are
+    /// combined separately
+    ///
+    /// # Parameters
+    ///
+    /// first: The first intensity
+    ///
+    /// second: The second intensity
+    pub fn apply(&self, first: (f64, f64), second: (f64, f64)) -> (f64, f64) {
+        return match self {
+            Self::Sum => (first.0 + second.0, first.1 + second.1),
+            Self::Product => (first.0 * second.0, first.1 * second.1),
+        };
+    }
+}
+
+/// A sun intensity combining two other intensities, like a yearly cycle with
+/// added noise or a daily cycle multiplied by eclipses
+#[derive(Clone, Debug)]
+pub struct IntensityComposite<A: Intensity, B: Intensity> {
+    /// The first intensity
+    first: A,
+    /// The second intensity
+    second: B,
+    /// How the intensities are combined
+    operation: CompositeOperation,
+}
+
+impl<A: Intensity, B: Intensity> IntensityComposite<A, B> {
+    /// Constructs a new composite intensity
+    ///
+    /// # Parameters
+    ///
+    /// first: The first intensity
+    ///
+    /// second: The second intensity
+    ///
+    /// operation: How the intensities are combined
+    pub fn new(first: A, second: B, operation: CompositeOperation) -> Self {
+        return Self {
+            first,
+            second,
+            operation,
+        };
+    }
+
+    /// Constructs a composite adding two intensities together
+    ///
+    /// # Parameters
+    ///
+    /// first: The first intensity
+    ///
+    /// second: The second intensity
+    pub fn sum(first: A, second: B) -> Self {
+        return Self::new(first, second, CompositeOperation::Sum);
+    }
+
+    /// Constructs a composite multiplying two intensities together
+    ///
+    /// # Parameters
+    ///
+    /// first: The first intensity
+    ///
+    /// second: The second intensity
+    pub fn product(first: A, second: B) -> Self {
+        return Self::new(first, second, CompositeOperation::Product);
+    }
+}
+
+impl<A: Intensity, B: Intensity> Intensity for IntensityComposite<A, B> {
+    fn get_intensity(&self, tile: usize, t: usize) -> (f64, f64) {
+        return self.operation.apply(
+            self.first.get_intensity(tile, t),
+            self.second.get_intensity(tile, t),
+        );
+    }
+
+    fn iter(&self, t: usize) -> impl Iterator<Item = (f64, f64)> {
+        return self
+            .first
+            .iter(t)
+            .zip(self.second.iter(t))
+            .map(|(first, second)| return self.operation.apply(first, second));
+    }
+
+    fn get_angle(&self, tile: usize, t: usize) -> Option<f64> {
+        return self
+            .first
+            .get_angle(tile, t)
+            .or_else(|| self.second.get_angle(tile, t));
+    }
+
+    fn get_time_of_day(&self, t: usize) -> Option<f64> {
+        return self
+            .first
+            .get_time_of_day(t)
+            .or_else(|| self.second.get_time_of_day(t));
+    }
+
+    fn get_time_of_year(&self, t: usize) -> Option<f64> {
+        return self
+            .first
+            .get_time_of_year(t)
+            .or_else(|| self.second.get_time_of_year(t));
+    }
+
+    fn get_size(&self) -> usize {
+        return self.first.get_size();
+    }
+
+    fn set_size(&mut self, size: usize) {
+        self.first.set_size(size);
+        self.second.set_size(size);
+    }
+}
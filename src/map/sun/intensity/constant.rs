@@ -0,0 +1,35 @@
+use super::Intensity;
+
+/// A sun intensity which is the same for all tiles at all times
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct IntensityConstant {
+    /// The size of the map
+    pub size: usize,
+    /// The intensity of the sun
+    pub intensity: f64,
+}
+
+impl IntensityConstant {
+    /// Constructs a new constant intensity
+    ///
+    /// # Parameters
+    ///
+    /// intensity: The intensity of the sun
+    pub fn new(intensity: f64) -> Self {
+        return Self { size: 1, intensity };
+    }
+}
+
+impl Intensity for IntensityConstant {
+    fn get_intensity(&self, _tile: usize, _t: usize) -> (f64, f64) {
+        return (self.intensity, 0.0);
+    }
+
+    fn get_size(&self) -> usize {
+        return self.size;
+    }
+
+    fn set_size(&mut self, size: usize) {
+        self.size = size;
+    }
+}
@@ -20,16 +20,45 @@ impl IntensityDayPlanet {
     pub fn new(day: f64) -> Self {
         return Self { size: 1, day };
     }
+
+    /// Gets the time of day at a tile as an angle from 0 at noon to 2 pi at
+    /// the next noon
+    ///
+    /// # Parameters
+    ///
+    /// tile: The index of the tile
+    ///
+    /// t: The time step of the simulation
+    fn get_hour_angle(&self, tile: usize, t: usize) -> f64 {
+        return ((t as f64 / self.day + 1.0 - tile as f64 / self.size as f64) % 1.0)
+            * 2.0
+            * constants::MATH_PI;
+    }
 }
 
 impl Intensity for IntensityDayPlanet {
     fn get_intensity(&self, tile: usize, t: usize) -> (f64, f64) {
-        let time_day = ((t as f64 / self.day + 1.0 - tile as f64 / self.size as f64) % 1.0)
-            * 2.0
-            * constants::MATH_PI;
+        let time_day = self.get_hour_angle(tile, t);
         return (time_day.cos(), 1.0);
     }
 
+    fn get_angle(&self, tile: usize, t: usize) -> Option<f64> {
+        // The sun rises on the right and sets on the left, its elevation
+        // falls evenly with the time from noon such that it leans by the
+        // hour angle
+        let time_day = self.get_hour_angle(tile, t);
+        let time_day = if time_day > constants::MATH_PI {
+            time_day - 2.0 * constants::MATH_PI
+        } else {
+            time_day
+        };
+        return Some((-time_day).clamp(-0.5 * constants::MATH_PI, 0.5 * constants::MATH_PI));
+    }
+
+    fn get_time_of_day(&self, t: usize) -> Option<f64> {
+        return Some((t as f64 / self.day).rem_euclid(1.0));
+    }
+
     fn get_size(&self) -> usize {
         return self.size;
     }
@@ -38,6 +38,21 @@ impl<Y: Intensity, D: Intensity> Intensity for IntensityYearDay<Y, D> {
             .map(|(year, day)| return (year.0 * day.0, year.1 * day.1));
     }
 
+    fn get_time_of_day(&self, t: usize) -> Option<f64> {
+        return self.day.get_time_of_day(t);
+    }
+
+    fn get_angle(&self, tile: usize, t: usize) -> Option<f64> {
+        return self
+            .day
+            .get_angle(tile, t)
+            .or_else(|| self.year.get_angle(tile, t));
+    }
+
+    fn get_time_of_year(&self, t: usize) -> Option<f64> {
+        return self.year.get_time_of_year(t);
+    }
+
     fn get_size(&self) -> usize {
         return self.year.get_size();
     }
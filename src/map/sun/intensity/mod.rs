@@ -18,6 +18,21 @@ pub use day::IntensityDayPlanet;
 mod full;
 pub use full::IntensityYearDay;
 
+mod constant;
+pub use constant::IntensityConstant;
+
+mod table;
+pub use table::{IntensityTable, IntensityTableError};
+
+mod composite;
+pub use composite::{CompositeOperation, IntensityComposite};
+
+mod shaded;
+pub use shaded::IntensityShaded;
+
+mod binary;
+pub use binary::IntensityBinaryStar;
+
 /// Defines the global intensity of the sun for all tiles as a function of time
 pub trait Intensity: Clone + Debug {
     /// Gets the intesity for a single tile at a specific iteration step, returns the primary and secondary intensities
@@ -33,12 +48,47 @@ pub trait Intensity: Clone + Debug {
     fn get_size(&self) -> usize;
 
     /// Sets the size of the map
-    /// 
+    ///
     /// # Parameters
-    /// 
+    ///
     /// size: The size of the map
     fn set_size(&mut self, size: usize);
 
+    /// Gets the fraction of the day which has passed at the leftmost column
+    /// from 0 at noon to 1 at the next noon, None if the intensity does not
+    /// follow a day
+    ///
+    /// # Parameters
+    ///
+    /// t: The time step of the simulation
+    fn get_time_of_day(&self, _t: usize) -> Option<f64> {
+        return None;
+    }
+
+    /// Gets the fraction of the year which has passed from 0 at midsummer to
+    /// 1 at the next midsummer, None if the intensity does not follow a year
+    ///
+    /// # Parameters
+    ///
+    /// t: The time step of the simulation
+    fn get_time_of_year(&self, _t: usize) -> Option<f64> {
+        return None;
+    }
+
+    /// Gets the angle of the sun from straight up above a single tile in
+    /// radians from -pi/2 to pi/2 at the horizons, positive when the sun is
+    /// to the right of the tile. None if the sun has no direction and always
+    /// shines straight down
+    ///
+    /// # Parameters
+    ///
+    /// tile: The index of the tile
+    ///
+    /// t: The time step of the simulation
+    fn get_angle(&self, _tile: usize, _t: usize) -> Option<f64> {
+        return None;
+    }
+
     /// Gets an iterator over all tiles of the intensity
     ///
     /// # Parameters
@@ -0,0 +1,89 @@
+use super::Intensity;
+
+/// A sun intensity where some columns are shaded, like the floor of a canyon,
+/// the intensity of every column is scaled by a fixed factor
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntensityShaded<S: Intensity> {
+    /// The intensity before shading
+    intensity: S,
+    /// The fraction of the light reaching each column, if the number of
+    /// columns differs from the size of the map the columns are stretched to
+    /// cover the map
+    shade: Vec<f64>,
+}
+
+impl<S: Intensity> IntensityShaded<S> {
+    /// Constructs a new shaded intensity
+    ///
+    /// # Parameters
+    ///
+    /// intensity: The intensity before shading
+    ///
+    /// shade: The fraction of the light reaching each column, it must have at
+    /// least one column
+    pub fn new(intensity: S, shade: Vec<f64>) -> Self {
+        assert!(
+            !shade.is_empty(),
+            "The shade of a shaded intensity must have at least one column"
+        );
+
+        return Self { intensity, shade };
+    }
+
+    /// Retrieves the fraction of the light reaching each column
+    pub fn get_shade(&self) -> &[f64] {
+        return &self.shade;
+    }
+
+    /// Retrieves the shade factor of a single tile
+    ///
+    /// # Parameters
+    ///
+    /// tile: The index of the tile
+    fn get_factor(&self, tile: usize) -> f64 {
+        let columns = self.shade.len();
+        let column = (tile * columns / self.get_size().max(1)).min(columns - 1);
+
+        return self.shade[column];
+    }
+}
+
+impl<S: Intensity> Intensity for IntensityShaded<S> {
+    fn get_intensity(&self, tile: usize, t: usize) -> (f64, f64) {
+        let (primary, secondary) = self.intensity.get_intensity(tile, t);
+        let factor = self.get_factor(tile);
+
+        return (factor * primary, factor * secondary);
+    }
+
+    fn iter(&self, t: usize) -> impl Iterator<Item = (f64, f64)> {
+        return self
+            .intensity
+            .iter(t)
+            .enumerate()
+            .map(|(tile, (primary, secondary))| {
+                let factor = self.get_factor(tile);
+                return (factor * primary, factor * secondary);
+            });
+    }
+
+    fn get_time_of_day(&self, t: usize) -> Option<f64> {
+        return self.intensity.get_time_of_day(t);
+    }
+
+    fn get_angle(&self, tile: usize, t: usize) -> Option<f64> {
+        return self.intensity.get_angle(tile, t);
+    }
+
+    fn get_time_of_year(&self, t: usize) -> Option<f64> {
+        return self.intensity.get_time_of_year(t);
+    }
+
+    fn get_size(&self) -> usize {
+        return self.intensity.get_size();
+    }
+
+    fn set_size(&mut self, size: usize) {
+        self.intensity.set_size(size);
+    }
+}
@@ -0,0 +1,122 @@
+use thiserror::Error;
+
+use super::Intensity;
+
+/// A sun intensity given by a table of samples for every column, the samples
+/// are evenly spread out over a period and repeat afterwards, in between
+/// samples the intensity is interpolated linearly
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntensityTable {
+    /// The size of the map
+    pub size: usize,
+    /// The number of steps before the samples repeat
+    period: f64,
+    /// The samples in time order, each sample holds the intensity of all
+    /// columns, if the number of columns differs from the size of the map the
+    /// columns are stretched to cover the map
+    samples: Vec<Vec<f64>>,
+}
+
+impl IntensityTable {
+    /// Constructs a new table intensity
+    ///
+    /// # Parameters
+    ///
+    /// period: The number of steps before the samples repeat
+    ///
+    /// samples: The samples in time order, each sample holds the intensity of
+    /// all columns
+    ///
+    /// # Errors
+    ///
+    /// See IntensityTableError for a description of the different errors which may occur
+    pub fn new(period: f64, samples: Vec<Vec<f64>>) -> Result<Self, IntensityTableError> {
+        if period.is_nan() || period <= 0.0 {
+            return Err(IntensityTableError::Period(period));
+        }
+        let columns = match samples.first() {
+            Some(sample) if !sample.is_empty() => sample.len(),
+            _ => return Err(IntensityTableError::Empty),
+        };
+        if let Some((index, sample)) = samples
+            .iter()
+            .enumerate()
+            .find(|(_, sample)| sample.len() != columns)
+        {
+            return Err(IntensityTableError::Columns {
+                index,
+                expected: columns,
+                received: sample.len(),
+            });
+        }
+
+        return Ok(Self {
+            size: 1,
+            period,
+            samples,
+        });
+    }
+
+    /// Retrieves the number of steps before the samples repeat
+    pub fn get_period(&self) -> f64 {
+        return self.period;
+    }
+
+    /// Retrieves all samples
+    pub fn get_samples(&self) -> &[Vec<f64>] {
+        return &self.samples;
+    }
+}
+
+impl Intensity for IntensityTable {
+    fn get_intensity(&self, tile: usize, t: usize) -> (f64, f64) {
+        // Find the column of the table covering this tile
+        let columns = self.samples[0].len();
+        let column = (tile * columns / self.size.max(1)).min(columns - 1);
+
+        // Find the two samples surrounding the time
+        let position = (t as f64 / self.period).fract() * self.samples.len() as f64;
+        let index = (position as usize).min(self.samples.len() - 1);
+        let next = (index + 1) % self.samples.len();
+        let ratio = position - index as f64;
+
+        return (
+            (1.0 - ratio) * self.samples[index][column] + ratio * self.samples[next][column],
+            0.0,
+        );
+    }
+
+    fn get_size(&self) -> usize {
+        return self.size;
+    }
+
+    fn set_size(&mut self, size: usize) {
+        self.size = size;
+    }
+}
+
+/// The error types for when constructing a table intensity
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum IntensityTableError {
+    /// The period is not positive
+    #[error("The period must be larger than 0 but received {}", .0)]
+    Period(f64),
+    /// There are no samples or the samples have no columns
+    #[error("The table must have at least one sample with at least one column")]
+    Empty,
+    /// Not all samples have the same number of columns
+    #[error(
+        "Sample {} has {} columns but the first sample has {}",
+        .index,
+        .received,
+        .expected
+    )]
+    Columns {
+        /// The index of the offending sample
+        index: usize,
+        /// The number of columns of the first sample
+        expected: usize,
+        /// The number of columns of the offending sample
+        received: usize,
+    },
+}
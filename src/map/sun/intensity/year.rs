@@ -11,6 +11,9 @@ pub struct IntensityYearPlanet {
     pub tilt: f64,
     /// The latitude of the target location on the planet in radians, 0 is the equator
     pub latitude: f64,
+    /// The latitude of the rightmost column in radians, the latitude changes
+    /// linearly from the leftmost column which lies at the latitude
+    pub latitude_end: f64,
     /// The length of a year in usits of iteration steps
     pub year: f64,
     /// The maximum intensity when the sun is right overhead
@@ -34,23 +37,57 @@ impl IntensityYearPlanet {
             size: 1,
             tilt,
             latitude,
+            latitude_end: latitude,
             year,
             intensity,
         };
     }
+
+    /// Lets the latitude change across the columns of the map from the
+    /// latitude at the leftmost column to another latitude at the rightmost
+    /// column, like from the tropics to a pole, and returns the updated
+    /// intensity. A map wrapping in x jumps back at the edge
+    ///
+    /// # Parameters
+    ///
+    /// latitude_end: The latitude of the rightmost column in radians
+    pub fn with_latitude_end(mut self, latitude_end: f64) -> Self {
+        self.latitude_end = latitude_end;
+
+        return self;
+    }
+
+    /// Gets the latitude of a tile in radians
+    ///
+    /// # Parameters
+    ///
+    /// tile: The index of the tile
+    fn get_latitude(&self, tile: usize) -> f64 {
+        if self.size <= 1 {
+            return self.latitude;
+        }
+
+        let fraction = tile as f64 / (self.size - 1) as f64;
+        return self.latitude + (self.latitude_end - self.latitude) * fraction;
+    }
 }
 
 impl Intensity for IntensityYearPlanet {
-    fn get_intensity(&self, _tile: usize, t: usize) -> (f64, f64) {
+    fn get_intensity(&self, tile: usize, t: usize) -> (f64, f64) {
         let time_year = ((t as f64 / self.year) % 1.0) * 2.0 * constants::MATH_PI;
         let x = time_year.cos() * self.tilt.tan();
         let max_intensity = (1.0 + x * x).sqrt();
+        let latitude = self.get_latitude(tile);
         return (
-            self.intensity * self.latitude.cos() / max_intensity,
-            self.intensity * self.latitude.sin() * x / max_intensity,
+            self.intensity * latitude.cos() / max_intensity,
+            self.intensity * latitude.sin() * x / max_intensity,
         );
     }
 
+    fn get_time_of_year(&self, t: usize) -> Option<f64> {
+        return Some((t as f64 / self.year).rem_euclid(1.0));
+    }
+
     fn get_size(&self) -> usize {
         return self.size;
     }
@@ -7,4 +7,8 @@ mod tile;
 pub(super) use tile::Tile;
 
 mod intensity;
-pub use intensity::{Intensity, IntensityYearPlanet, IntensityDayPlanet, IntensityYearDay};
+pub use intensity::{
+    CompositeOperation, Intensity, IntensityBinaryStar, IntensityComposite, IntensityConstant,
+    IntensityDayPlanet, IntensityShaded, IntensityTable, IntensityTableError, IntensityYearDay,
+    IntensityYearPlanet,
+};
@@ -17,6 +17,27 @@ impl<S: Intensity> State<S> {
         return Self { intensity };
     }
 
+    /// Gets the fraction of the day which has passed at the leftmost column
+    /// from 0 at noon to 1 at the next noon, None if the sun does not follow
+    /// a day
+    ///
+    /// # Parameters
+    ///
+    /// t: The simulation step
+    pub fn get_time_of_day(&self, t: usize) -> Option<f64> {
+        return self.intensity.get_time_of_day(t);
+    }
+
+    /// Gets the fraction of the year which has passed from 0 at midsummer to
+    /// 1 at the next midsummer, None if the sun does not follow a year
+    ///
+    /// # Parameters
+    ///
+    /// t: The simulation step
+    pub fn get_time_of_year(&self, t: usize) -> Option<f64> {
+        return self.intensity.get_time_of_year(t);
+    }
+
     /// Constructs all the sun intensity tiles for the current time of the simulation
     ///
     /// # Parameters
@@ -26,8 +47,10 @@ impl<S: Intensity> State<S> {
         return self
             .intensity
             .iter(t)
-            .map(|intensity| {
-                return Tile::new(intensity.0 + intensity.1);
+            .enumerate()
+            .map(|(tile, intensity)| {
+                let angle = self.intensity.get_angle(tile, t).unwrap_or(0.0);
+                return Tile::new(intensity.0, intensity.1).with_angle(angle);
             })
             .collect();
     }
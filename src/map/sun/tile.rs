@@ -3,8 +3,15 @@ use super::InstanceTile;
 /// All data for a single sun ray
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Tile {
-    /// The intensity of the sun
+    /// The intensity of the sun, the sum of the primary and secondary
+    /// intensities
     pub intensity: f64,
+    /// The part of the intensity coming from the secondary intensity, like
+    /// the light of the companion of a binary star
+    pub secondary: f64,
+    /// The angle of the sun from straight up in radians, positive when the
+    /// sun is to the right of the column
+    pub angle: f64,
 }
 
 impl Tile {
@@ -12,15 +19,48 @@ impl Tile {
     ///
     /// # Parameters
     ///
-    /// intensity: The intensity of the tile
-    pub fn new(intensity: f64) -> Self {
-        return Self { intensity };
+    /// primary: The primary intensity of the tile
+    ///
+    /// secondary: The secondary intensity of the tile
+    pub fn new(primary: f64, secondary: f64) -> Self {
+        return Self {
+            intensity: primary + secondary,
+            secondary,
+            angle: 0.0,
+        };
+    }
+
+    /// Sets the angle of the sun and returns the updated tile
+    ///
+    /// # Parameters
+    ///
+    /// angle: The angle of the sun from straight up in radians, positive when
+    /// the sun is to the right of the column
+    pub fn with_angle(mut self, angle: f64) -> Self {
+        self.angle = angle;
+
+        return self;
     }
 
-    /// Converts the sun tile to shader compatible data
+    /// Gets the part of the intensity coming from the primary intensity
+    pub fn get_primary(&self) -> f64 {
+        return self.intensity - self.secondary;
+    }
+
+    /// Darkens both the primary and secondary intensity
+    ///
+    /// # Parameters
+    ///
+    /// factor: The factor to multiply onto the intensities
+    pub fn darken(&mut self, factor: f64) {
+        self.intensity *= factor;
+        self.secondary *= factor;
+    }
+
+    /// Converts the sun tile to shader compatible data, the full intensity is
+    /// drawn on the upper half of the tile and the secondary intensity on the
+    /// lower half
     pub fn get_data(&self) -> InstanceTile {
-        return InstanceTile {
-            color_value: self.intensity as f32,
-        };
+        return InstanceTile::new_split(self.intensity as f32, self.secondary as f32);
     }
 }
@@ -0,0 +1,136 @@
+use rand::Rng;
+
+use super::{
+    SimRng,
+    fertility::fade,
+    settings::{terrain::Settings, wrap::Wrap},
+};
+
+/// Generates the number of rows the terrain raises the ground in every
+/// column as a sum of octaves of 1D Perlin noise, the noise is periodic such
+/// that the hills wrap around like the map does
+///
+/// # Parameters
+///
+/// settings: The terrain settings
+///
+/// width: The number of columns of the map
+///
+/// rng: The random number generator of the simulation
+pub fn new_relief(settings: &Settings, width: usize, rng: &SimRng) -> Vec<usize> {
+    if settings.height == 0 || settings.octaves == 0 {
+        return vec![0; width];
+    }
+
+    let octaves = (0..settings.octaves)
+        .map(|octave| Octave::new(settings.scale, width, octave, rng))
+        .collect::<Vec<_>>();
+    let amplitude = octaves.iter().map(|octave| octave.amplitude).sum::<f64>();
+
+    return (0..width)
+        .map(|column| {
+            let noise = octaves
+                .iter()
+                .map(|octave| octave.amplitude * octave.get(column as f64))
+                .sum::<f64>()
+                / amplitude;
+
+            // 1D Perlin noise lies between -1 / 2 and 1 / 2
+            let noise = (2.0 * noise).clamp(-1.0, 1.0);
+            return (0.5 * (1.0 + noise) * settings.height as f64).round() as usize;
+        })
+        .collect();
+}
+
+/// Finds the columns where the terrain is too steep for plants to root in,
+/// the slope of a column is the largest difference in height to its
+/// neighboring columns
+///
+/// # Parameters
+///
+/// settings: The terrain settings
+///
+/// relief: The number of rows the terrain raises the ground in every column
+///
+/// wrap: The directions in which the map wraps around
+pub fn new_steep_columns(settings: &Settings, relief: &[usize], wrap: &Wrap) -> Vec<bool> {
+    let width = relief.len();
+
+    return (0..width)
+        .map(|column| {
+            let neighbors = if wrap.horizontal() {
+                [
+                    Some((column + width - 1) % width),
+                    Some((column + 1) % width),
+                ]
+            } else {
+                [
+                    column.checked_sub(1),
+                    Some(column + 1).filter(|&next| next < width),
+                ]
+            };
+
+            return neighbors
+                .into_iter()
+                .flatten()
+                .any(|neighbor| relief[column].abs_diff(relief[neighbor]) as f64 > settings.slope);
+        })
+        .collect();
+}
+
+/// A single layer of 1D Perlin noise
+struct Octave {
+    /// The size of a lattice cell in columns
+    cell_size: f64,
+    /// The gradient at every lattice point, the lattice wraps around
+    gradients: Vec<f64>,
+    /// The weight of this layer in the sum
+    amplitude: f64,
+}
+
+impl Octave {
+    /// Constructs a new layer of noise with random gradients
+    ///
+    /// # Parameters
+    ///
+    /// scale: The size of the lattice cells of the first octave in columns
+    ///
+    /// width: The number of columns of the map
+    ///
+    /// octave: The index of this layer, each layer halves the cell size
+    ///
+    /// rng: The random number generator of the simulation
+    fn new(scale: f64, width: usize, octave: usize, rng: &SimRng) -> Self {
+        let factor = 0.5_f64.powi(octave as i32);
+        let cells = (width as f64 / (scale * factor)).round().max(1.0) as usize;
+
+        let mut rng = rng.get(0, SimRng::STREAM_TERRAIN, octave);
+        let gradients = (0..cells).map(|_| rng.random_range(-1.0..1.0)).collect();
+
+        return Self {
+            cell_size: width as f64 / cells as f64,
+            gradients,
+            amplitude: factor,
+        };
+    }
+
+    /// Evaluates the noise at a column
+    ///
+    /// # Parameters
+    ///
+    /// x: The x-coordinate
+    fn get(&self, x: f64) -> f64 {
+        let x = x / self.cell_size;
+        let cell = x.floor();
+        let fraction = x - cell;
+        let index = cell as usize % self.gradients.len();
+        let next = (index + 1) % self.gradients.len();
+
+        // The contribution of a lattice point is its gradient times the
+        // offset from the point, blended smoothly between the two points
+        let left = self.gradients[index] * fraction;
+        let right = self.gradients[next] * (fraction - 1.0);
+
+        return left + fade(fraction) * (right - left);
+    }
+}
@@ -0,0 +1,30 @@
+use super::{EditBulk, Map, settings, sun};
+use crate::types;
+
+/// The size of the maps constructed for tests
+pub(crate) const SIZE: types::ISize = types::ISize { w: 12, h: 8 };
+
+/// Constructs a small map under a constant sun with a plant of every bulk
+/// type in the third row, the plant of bulk type i is placed in column 2i
+///
+/// # Parameters
+///
+/// settings: The simulation settings
+pub(crate) fn new_map(settings: settings::Settings) -> Map<sun::IntensityConstant> {
+    let mut map = Map::new(SIZE, settings, sun::IntensityConstant::new(1.0));
+    for id in 0..EditBulk::COUNT {
+        map.place_plant(&get_plant_pos(EditBulk::from_id(id)), EditBulk::from_id(id));
+    }
+
+    return map;
+}
+
+/// Gets the position of the plant of a bulk type on a map constructed by
+/// new_map
+///
+/// # Parameters
+///
+/// bulk: The bulk type of the plant
+pub(crate) fn get_plant_pos(bulk: EditBulk) -> types::Index {
+    return types::Index::new(2 * bulk.id() as isize, 2);
+}
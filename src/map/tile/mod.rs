@@ -1,12 +1,20 @@
 use std::mem;
 
-use super::{DataModeBackground, settings::Settings, sun};
+use rand::rngs::StdRng;
+
+use crate::types;
+
+use super::{
+    BridgeInfo, DataModeBackground, EnergyBalance, Genealogy, PlantEvent, PlantInfo, TileInfo,
+    settings::Settings, sun,
+};
 
 mod neighbor;
-pub(super) use neighbor::{Neighbor, NeighborDirection, TileNeighbors, TilePos};
+pub(super) use neighbor::{Neighbor, NeighborDirection, TileNeighbors, TilePos, TilePosNeighbor};
 
 mod simulation;
 use simulation::plant;
+pub use simulation::plant::{GenomeError, Plant, Pollen};
 
 /// A single tile for the map
 #[derive(Clone, Debug)]
@@ -28,6 +36,60 @@ impl Tile {
         };
     }
 
+    /// Constructs a new empty ground tile
+    ///
+    /// # Parameters
+    ///
+    /// nutrients: The initial nutrient concentration
+    pub fn new_ground(nutrients: f64) -> Self {
+        let data = TileData::new_ground(nutrients);
+
+        return Self {
+            plant: plant::State::Nothing,
+            data,
+        };
+    }
+
+    /// Sets the altitude of the tile and lets it start at the ambient
+    /// temperature of that altitude, returns the updated tile
+    ///
+    /// # Parameters
+    ///
+    /// altitude: The number of rows above the ground
+    ///
+    /// map_settings: The settings for the map
+    pub fn with_altitude(mut self, altitude: usize, map_settings: &Settings) -> Self {
+        self.data.altitude = altitude;
+        self.data.temperature = map_settings.temperature.get_ambient(altitude);
+
+        return self;
+    }
+
+    /// Makes the tile part of the terrain raised above the ground and returns
+    /// the updated tile, the terrain blocks all light
+    ///
+    /// # Parameters
+    ///
+    /// barren: True if the terrain is too steep for plants to root in
+    pub fn with_terrain(mut self, barren: bool) -> Self {
+        self.data.terrain = true;
+        self.data.barren = barren;
+        self.data.transparency = 0.0;
+
+        return self;
+    }
+
+    /// Sets the fertility of the tile and returns the updated tile
+    ///
+    /// # Parameters
+    ///
+    /// fertility: The factor scaling the energy gained by leaves on this tile
+    pub fn with_fertility(mut self, fertility: f64) -> Self {
+        self.data.fertility = fertility;
+
+        return self;
+    }
+
     /// Converts the tile to shader compatible data
     ///
     /// mode: The mode to display
@@ -35,21 +97,473 @@ impl Tile {
         let value = match mode {
             DataModeBackground::Transparency => self.data.transparency,
             DataModeBackground::Light => self.data.light,
+            DataModeBackground::Diffuse => self.data.diffuse,
+            DataModeBackground::Terrain => match (self.data.terrain, self.data.barren) {
+                (true, true) => 1.0,
+                (true, false) => 0.5,
+                (false, _) => 0.0,
+            },
+            DataModeBackground::Water => self.data.water,
+            DataModeBackground::Nutrients => self.data.nutrients,
+            DataModeBackground::Detritus => self.data.detritus,
+            DataModeBackground::Fertility => 0.5 * self.data.fertility,
+            // Not properties of the tile alone, they are filled in by the map
+            DataModeBackground::ChunkCost
+            | DataModeBackground::ForeignShade
+            | DataModeBackground::Clouds
+            | DataModeBackground::Temperature => 0.0,
+        };
+
+        return InstanceTile::new(value as f32);
+    }
+
+    /// Converts the plant of the tile to shader compatible data for a discrete
+    /// color map, 0 if there is no living plant and otherwise 1 + the id of
+    /// the bulk
+    pub fn get_data_plant(&self) -> InstanceTile {
+        let id = match &self.plant {
+            plant::State::Occupied(plant) if plant.is_alive() => plant.get_bulk_id() + 1,
+            _ => 0,
+        };
+
+        return InstanceTile::new(id as f32 / 255.0);
+    }
+
+    /// Checks if there is any plant on this tile, fully grown or building
+    pub fn has_plant(&self) -> bool {
+        return !matches!(self.plant, plant::State::Nothing);
+    }
+
+    /// Checks if the state of the tile differs from an earlier state of the
+    /// same tile, for plants only their presence is compared
+    ///
+    /// # Parameters
+    ///
+    /// previous: The earlier state of the tile
+    pub fn has_changed(&self, previous: &Tile) -> bool {
+        return self.has_plant() != previous.has_plant() || self.data != previous.data;
+    }
+
+    /// Checks if the tile would be shown differently than an earlier state of
+    /// the same tile in any display mode only depending on the tile itself
+    ///
+    /// # Parameters
+    ///
+    /// previous: The earlier state of the tile
+    pub fn has_changed_appearance(&self, previous: &Tile) -> bool {
+        return self.data != previous.data
+            || self.get_data_plant().color_value != previous.get_data_plant().color_value;
+    }
+
+    /// Gets the change of the plant on this tile since an earlier state of
+    /// the same tile, plants moving between tiles are not seen as a change
+    ///
+    /// # Parameters
+    ///
+    /// previous: The earlier state of the tile
+    pub fn get_plant_event(&self, previous: &Tile) -> Option<PlantEvent> {
+        return self.plant.get_event(&previous.plant);
+    }
+
+    /// Checks if the tile is part of the ground
+    pub fn is_ground(&self) -> bool {
+        return self.data.ground;
+    }
+
+    /// Checks if the tile is free for a plant to move into
+    pub fn is_free(&self) -> bool {
+        return matches!(self.plant, plant::State::Nothing);
+    }
+
+    /// Checks if the plant on this tile is a ripe seed which should fall to
+    /// the tile below, plants never fall out of the ground
+    pub fn is_falling(&self) -> bool {
+        return !self.data.ground && self.plant.is_falling();
+    }
+
+    /// Moves the plant of this tile to another tile, leaving this tile empty,
+    /// all other tile data stays in place
+    ///
+    /// # Parameters
+    ///
+    /// target: The tile to move the plant to
+    pub fn move_plant(&mut self, target: &mut Self) {
+        target.plant = mem::replace(&mut self.plant, plant::State::Nothing);
+    }
+
+    /// Places a new plant with the starter genome on this tile, returns false
+    /// and leaves the tile unchanged if it is not free or the plant can only
+    /// exist in the ground and this tile is not ground
+    ///
+    /// # Parameters
+    ///
+    /// bulk_id: The type id of the bulk of the plant
+    ///
+    /// energy_capacity: The energy capacity of the plant, it starts full
+    pub fn place_plant(&mut self, bulk_id: usize, energy_capacity: f64) -> bool {
+        let plant = plant::Plant::new_placed(bulk_id, energy_capacity);
+        if !self.is_free() || (plant.requires_ground() && !self.data.ground) {
+            return false;
+        }
+        self.plant = plant::State::Occupied(plant);
+
+        return true;
+    }
+
+    /// Places a new plant with a genome read from the genome text format on
+    /// this tile, returns false and leaves the tile unchanged if it is not
+    /// free or the plant can only exist in the ground and this tile is not
+    /// ground
+    ///
+    /// # Parameters
+    ///
+    /// text: The genome text
+    ///
+    /// bulk_id: The type id of the bulk of the plant
+    ///
+    /// energy_capacity: The energy capacity of the plant, it starts full
+    ///
+    /// # Errors
+    ///
+    /// See GenomeError for a description of the different errors which may occur
+    pub fn place_genome(
+        &mut self,
+        text: &str,
+        bulk_id: usize,
+        energy_capacity: f64,
+    ) -> Result<bool, GenomeError> {
+        if !self.is_free() {
+            return Ok(false);
+        }
+        let plant = plant::Plant::from_genome_str(text, bulk_id, energy_capacity)?;
+        if plant.requires_ground() && !self.data.ground {
+            return Ok(false);
+        }
+        self.plant = plant::State::Occupied(plant);
+
+        return Ok(true);
+    }
+
+    /// Spawns a new plant with a random genome on this tile, returns false and
+    /// leaves the tile unchanged if it is not free
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The settings for the map
+    ///
+    /// rng: The random number generator to draw the genome from
+    pub fn spawn_plant(&mut self, map_settings: &Settings, rng: &mut StdRng) -> bool {
+        if !self.is_free() {
+            return false;
+        }
+        self.plant = plant::State::Occupied(plant::Plant::new_spawned(map_settings, rng));
+
+        return true;
+    }
+
+    /// Removes the plant of this tile, returns false if there was no plant,
+    /// the bridges of neighbors connecting to it are removed when they update
+    pub fn remove_plant(&mut self) -> bool {
+        return !matches!(
+            mem::replace(&mut self.plant, plant::State::Nothing),
+            plant::State::Nothing
+        );
+    }
+
+    /// Gets a grain of pollen from the plant on this tile if it is a flower
+    /// able to emit pollen
+    pub fn get_pollen(&self) -> Option<Pollen> {
+        return self.plant.get_pollen();
+    }
+
+    /// Fertilizes the plant on this tile with pollen if it is a flower able to
+    /// receive it, returns false if the pollen was not taken
+    ///
+    /// # Parameters
+    ///
+    /// pollen: The pollen to fertilize with
+    ///
+    /// map_settings: The settings for the map
+    ///
+    /// rng: The random number generator to draw the genome of the offspring
+    /// from
+    pub fn fertilize(
+        &mut self,
+        pollen: &Pollen,
+        map_settings: &Settings,
+        rng: &mut StdRng,
+    ) -> bool {
+        return self.plant.fertilize(pollen, map_settings, rng);
+    }
+
+    /// Checks if the plant on this tile is a living thorn protecting it and
+    /// its neighbors from grazing
+    pub fn has_thorn(&self) -> bool {
+        return self.plant.is_thorn();
+    }
+
+    /// Checks if there is a living leaf on this tile
+    pub fn has_leaf(&self) -> bool {
+        return self.plant.is_leaf();
+    }
+
+    /// Kills the plant on this tile if it is fully grown and alive, it is
+    /// removed in the next step. Returns true if a plant was killed
+    pub fn kill_plant(&mut self) -> bool {
+        return self.plant.kill();
+    }
+
+    /// Gets the energy herbivores can graze from the plant on this tile, None
+    /// if there is no living plant
+    pub fn get_forage(&self) -> Option<f64> {
+        return self.plant.get_forage();
+    }
+
+    /// Lets herbivores eat a fraction of the energy of the plant on this tile
+    ///
+    /// # Parameters
+    ///
+    /// damage: The fraction of the energy to eat
+    pub fn graze(&mut self, damage: f64) {
+        self.plant.graze(damage);
+    }
+
+    /// Registers the plant on this tile as the start of a new lineage if it is
+    /// a newly built seed
+    ///
+    /// # Parameters
+    ///
+    /// genealogy: The genealogy of the map to register the lineage in
+    ///
+    /// time: The current simulation step
+    pub fn register_lineage(&mut self, genealogy: &mut Genealogy, time: usize) {
+        self.plant.register_lineage(genealogy, time);
+    }
+
+    /// Gets a snapshot of the state of this tile for inspection
+    ///
+    /// # Parameters
+    ///
+    /// pos: The position of this tile
+    pub fn get_info(&self, pos: &types::Index) -> TileInfo {
+        let plant = match &self.plant {
+            plant::State::Nothing => None,
+            plant::State::Building((plant, _, _)) => Some(plant.get_info(true)),
+            plant::State::Occupied(plant) => Some(plant.get_info(false)),
+        };
+
+        return TileInfo {
+            pos: *pos,
+            light: self.data.light,
+            diffuse: self.data.diffuse,
+            transparency: self.data.transparency,
+            water: self.data.water,
+            ground: self.data.ground,
+            terrain: self.data.terrain,
+            barren: self.data.barren,
+            nutrients: self.data.nutrients,
+            temperature: self.data.temperature,
+            detritus: self.data.detritus,
+            fertility: self.data.fertility,
+            plant,
+        };
+    }
+
+    /// Gets the type id and energy capacity of the bridges in all directions
+    /// of the plant on this tile, all None if there is no living plant
+    pub fn get_plant_bridges(&self) -> [Option<(usize, f64)>; 6] {
+        return match &self.plant {
+            plant::State::Occupied(plant) if plant.is_alive() => plant.get_bridge_data(),
+            _ => [None; 6],
+        };
+    }
+
+    /// Gets the total energy moved through the bridges in all directions of
+    /// the plant on this tile since they were built, all None if there is no
+    /// living plant
+    pub fn get_plant_throughputs(&self) -> [Option<f64>; 6] {
+        return match &self.plant {
+            plant::State::Occupied(plant) if plant.is_alive() => plant.get_bridge_throughputs(),
+            _ => [None; 6],
+        };
+    }
+
+    /// Gets the energy received by the plant on this tile through the bridge
+    /// in every direction during the last step in the order of
+    /// NeighborDirection::collection(), negative if energy was sent, all
+    /// transfers are 0 if there is no living plant
+    pub fn get_plant_transfers(&self) -> [f64; 6] {
+        return match &self.plant {
+            plant::State::Occupied(plant) if plant.is_alive() => plant.get_transfer_data(),
+            _ => [0.0; 6],
+        };
+    }
+
+    /// Gets a snapshot of all bridges of the plant on this tile, empty if
+    /// there is no fully grown plant
+    pub fn get_plant_bridge_info(&self) -> Vec<BridgeInfo> {
+        return match &self.plant {
+            plant::State::Occupied(plant) => plant.get_bridge_info(),
+            plant::State::Nothing | plant::State::Building(_) => Vec::new(),
+        };
+    }
+
+    /// Retrieves the name of the bulk type of the plant on this tile or None
+    /// if there is no fully grown plant
+    pub fn get_plant_bulk(&self) -> Option<&'static str> {
+        return match &self.plant {
+            plant::State::Occupied(plant) => Some(plant.get_bulk_name()),
+            plant::State::Nothing | plant::State::Building(_) => None,
+        };
+    }
+
+    /// Retrieves the water level of this tile
+    pub fn get_water(&self) -> f64 {
+        return self.data.water;
+    }
+
+    /// Retrieves the nutrient concentration of this tile, always 0 if it is
+    /// not ground
+    pub fn get_nutrients(&self) -> f64 {
+        return self.data.nutrients;
+    }
+
+    /// Retrieves the age of the plant tile and the age of the entire plant on
+    /// this tile or None if there is no living plant
+    pub fn get_plant_age(&self) -> Option<(usize, usize)> {
+        return match &self.plant {
+            plant::State::Occupied(plant) if plant.is_alive() => {
+                Some((plant.get_age(), plant.get_cum_age()))
+            }
+            _ => None,
         };
+    }
 
-        return InstanceTile {
-            color_value: value as f32,
+    /// Writes the genome of the plant on this tile in the genome text format,
+    /// None if there is no fully grown plant
+    ///
+    /// # Parameters
+    ///
+    /// simplify: True if operators which can never be evaluated are left out
+    pub fn get_plant_genome(&self, simplify: bool) -> Option<String> {
+        return match &self.plant {
+            plant::State::Occupied(plant) => Some(plant.genome_to_string(simplify)),
+            plant::State::Nothing | plant::State::Building(_) => None,
+        };
+    }
+
+    /// Checks if all indices of the genome of the plant on this tile are in
+    /// range, None if there is no fully grown plant
+    pub fn validate_plant_genome(&self) -> Option<Result<(), GenomeError>> {
+        return match &self.plant {
+            plant::State::Occupied(plant) => Some(plant.validate_genome()),
+            plant::State::Nothing | plant::State::Building(_) => None,
+        };
+    }
+
+    /// Retrieves the light level of this tile
+    pub fn get_light(&self) -> f64 {
+        return self.data.light;
+    }
+
+    /// Retrieves the part of the light level of this tile which is diffuse
+    /// light scattered by the sky
+    pub fn get_diffuse(&self) -> f64 {
+        return self.data.diffuse;
+    }
+
+    /// Retrieves the light transparency of this tile
+    pub fn get_transparency(&self) -> f64 {
+        return self.data.transparency;
+    }
+
+    /// Gets the transparency this tile would have if its plant let all light
+    /// through
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The settings for the map
+    pub fn get_transparency_without_plant(&self, map_settings: &Settings) -> f64 {
+        if self.data.terrain {
+            return 0.0;
+        }
+
+        return map_settings.transparency.base
+            * (1.0 - map_settings.detritus.opacity * self.data.detritus);
+    }
+
+    /// Retrieves the temperature of this tile
+    pub fn get_temperature(&self) -> f64 {
+        return self.data.temperature;
+    }
+
+    /// Retrieves the energy of the plant on this tile or None if there is no
+    /// fully grown plant
+    pub fn get_plant_energy(&self) -> Option<f64> {
+        return match &self.plant {
+            plant::State::Occupied(plant) => Some(plant.get_energy()),
+            plant::State::Nothing | plant::State::Building(_) => None,
+        };
+    }
+
+    /// Retrieves the energy stored in the plant on this tile including the
+    /// energy set aside for spreading, 0 if there is no fully grown plant
+    pub fn get_stored_energy(&self) -> f64 {
+        return self.plant.get_stored_energy();
+    }
+
+    /// Retrieves the lineage of the plant on this tile or None if there is no
+    /// fully grown living plant
+    pub fn get_plant_lineage(&self) -> Option<usize> {
+        return match &self.plant {
+            plant::State::Occupied(plant) if plant.is_alive() => plant.get_lineage(),
+            _ => None,
+        };
+    }
+
+    /// Retrieves the living plant on this tile, None if there is no fully
+    /// grown living plant
+    pub fn get_living_plant(&self) -> Option<&Plant> {
+        return match &self.plant {
+            plant::State::Occupied(plant) if plant.is_alive() => Some(plant),
+            _ => None,
         };
     }
 }
 
 /// All state data for the tile (no plant data)
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 struct TileData {
     /// The light transparency of this tile
     transparency: f64,
-    /// The light level of this tile
+    /// The light level of this tile, the sum of the direct, reflected and
+    /// diffuse light
     light: f64,
+    /// The part of the light level which is diffuse light scattered by the
+    /// sky
+    diffuse: f64,
+    /// The water level of this tile
+    water: f64,
+    /// If true then this tile is part of the ground
+    ground: bool,
+    /// If true then this tile is part of the terrain raised above the ground,
+    /// it blocks all light and holds ground water like the bottom row
+    terrain: bool,
+    /// If true then this tile is too steep for plants to grow on, it is only
+    /// set for terrain
+    barren: bool,
+    /// The nutrient concentration of this tile, always 0 if it is not ground
+    nutrients: f64,
+    /// The temperature of this tile
+    temperature: f64,
+    /// The number of rows this tile is above the ground, 0 for ground tiles
+    altitude: usize,
+    /// The amount of detritus left behind by a dead plant, from 1 when the
+    /// plant has just died to 0 when it has fully decayed
+    detritus: f64,
+    /// The factor scaling the energy gained by leaves on this tile, it never
+    /// changes
+    fertility: f64,
 }
 
 impl TileData {
@@ -58,6 +572,38 @@ impl TileData {
         return Self {
             transparency: 1.0,
             light: 0.0,
+            diffuse: 0.0,
+            water: 0.0,
+            ground: false,
+            terrain: false,
+            barren: false,
+            nutrients: 0.0,
+            temperature: 0.0,
+            altitude: 0,
+            detritus: 0.0,
+            fertility: 1.0,
+        };
+    }
+
+    /// Constructs a new empty ground tile
+    ///
+    /// # Parameters
+    ///
+    /// nutrients: The initial nutrient concentration
+    pub fn new_ground(nutrients: f64) -> Self {
+        return Self {
+            transparency: 1.0,
+            light: 0.0,
+            diffuse: 0.0,
+            water: 0.0,
+            ground: true,
+            terrain: false,
+            barren: false,
+            nutrients,
+            temperature: 0.0,
+            altitude: 0,
+            detritus: 0.0,
+            fertility: 1.0,
         };
     }
 }
@@ -68,19 +614,230 @@ impl TileData {
 pub struct InstanceTile {
     /// The value to draw at this tile
     pub color_value: f32,
+    /// The value to draw on the lower half of this tile, negative to draw the
+    /// full tile with the color value
+    pub secondary_value: f32,
 }
 
 impl InstanceTile {
+    /// Constructs the shader compatible data drawing a single value across
+    /// the full tile
+    ///
+    /// # Parameters
+    ///
+    /// color_value: The value to draw at the tile
+    pub fn new(color_value: f32) -> Self {
+        return Self {
+            color_value,
+            secondary_value: -1.0,
+        };
+    }
+
+    /// Constructs the shader compatible data drawing one value on the upper
+    /// half of the tile and another on the lower half
+    ///
+    /// # Parameters
+    ///
+    /// color_value: The value to draw on the upper half of the tile
+    ///
+    /// secondary_value: The value to draw on the lower half of the tile, it
+    /// is clamped to not be negative
+    pub fn new_split(color_value: f32, secondary_value: f32) -> Self {
+        return Self {
+            color_value,
+            secondary_value: secondary_value.max(0.0),
+        };
+    }
+
+    /// Converts an age to shader compatible data for a discrete color map, 0
+    /// if there is no living plant and otherwise from 1 for the youngest to
+    /// 255 for the oldest
+    ///
+    /// # Parameters
+    ///
+    /// age: The age to convert, None if there is no living plant
+    ///
+    /// max_age: The age of the oldest plant
+    pub fn new_age(age: Option<usize>, max_age: usize) -> Self {
+        let id = match age {
+            Some(age) if max_age > 0 => 1.0 + (254.0 * age as f64 / max_age as f64).round(),
+            Some(_) => 1.0,
+            None => 0.0,
+        };
+
+        return Self::new((id / 255.0) as f32);
+    }
+
+    /// Converts a species to shader compatible data for a discrete color map,
+    /// 0 if there is no living plant of a known species and otherwise from 1
+    /// to 255 where the species ids repeat every 255 species
+    ///
+    /// # Parameters
+    ///
+    /// species: The id of the species, None if there is no living plant of a
+    /// known species
+    pub fn new_species(species: Option<usize>) -> Self {
+        let id = match species {
+            Some(species) => 1 + species % 255,
+            None => 0,
+        };
+
+        return Self::new((id as f64 / 255.0) as f32);
+    }
+
     /// Creates the vertex buffer description for the tile instance
+    #[cfg(feature = "viewer")]
     pub fn desc() -> wgpu::VertexBufferLayout<'static> {
         return wgpu::VertexBufferLayout {
             array_stride: mem::size_of::<InstanceTile>() as wgpu::BufferAddress,
             step_mode: wgpu::VertexStepMode::Instance,
-            attributes: &[wgpu::VertexAttribute {
-                offset: 0,
-                shader_location: 1,
-                format: wgpu::VertexFormat::Float32,
-            }],
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 1,
+                    format: wgpu::VertexFormat::Float32,
+                },
+                wgpu::VertexAttribute {
+                    offset: mem::size_of::<f32>() as wgpu::BufferAddress,
+                    shader_location: 2,
+                    format: wgpu::VertexFormat::Float32,
+                },
+            ],
+        };
+    }
+}
+
+/// All data for instancing half of a bridge, going from the center of a tile
+/// to the edge shared with the neighbor
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct InstanceBridge {
+    /// The value to draw the bridge with, 0 if there is no bridge and
+    /// otherwise 1 + the id of the bridge type
+    pub color_value: f32,
+    /// The width of the bridge in world coordinates
+    pub width: f32,
+}
+
+impl InstanceBridge {
+    /// The width of the bridge with the smallest energy capacity
+    const WIDTH_MIN: f64 = 0.05;
+    /// The width of the bridge with the largest energy capacity
+    const WIDTH_MAX: f64 = 0.3;
+
+    /// Converts a bridge to shader compatible data, the width scales with the
+    /// energy capacity relative to the largest capacity
+    ///
+    /// # Parameters
+    ///
+    /// bridge: The type id and the energy capacity of the bridge, None if
+    /// there is no bridge
+    ///
+    /// max_capacity: The largest energy capacity of any bridge
+    pub fn new(bridge: Option<(usize, f64)>, max_capacity: f64) -> Self {
+        let (id, width) = match bridge {
+            Some((id, capacity)) if max_capacity > 0.0 => (
+                id + 1,
+                Self::WIDTH_MIN + (Self::WIDTH_MAX - Self::WIDTH_MIN) * capacity / max_capacity,
+            ),
+            Some((id, _)) => (id + 1, Self::WIDTH_MIN),
+            None => (0, 0.0),
+        };
+
+        return Self {
+            color_value: id as f32 / 255.0,
+            width: width as f32,
+        };
+    }
+
+    /// Creates the vertex buffer description for the bridge instance
+    #[cfg(feature = "viewer")]
+    pub fn desc() -> wgpu::VertexBufferLayout<'static> {
+        return wgpu::VertexBufferLayout {
+            array_stride: mem::size_of::<InstanceBridge>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 1,
+                    format: wgpu::VertexFormat::Float32,
+                },
+                wgpu::VertexAttribute {
+                    offset: mem::size_of::<f32>() as wgpu::BufferAddress,
+                    shader_location: 2,
+                    format: wgpu::VertexFormat::Float32,
+                },
+            ],
+        };
+    }
+}
+
+/// All data for instancing the arrow showing the energy transfer through half
+/// of a bridge, going from the center of a tile to the edge shared with the
+/// neighbor
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct InstanceTransfer {
+    /// The value to draw the arrow with, the size of the transfer relative to
+    /// the largest transfer
+    pub color_value: f32,
+    /// The scale of the arrow, positive if it points towards the neighbor
+    /// and negative if it points towards the center of the tile, 0 if there
+    /// is no transfer
+    pub size: f32,
+}
+
+impl InstanceTransfer {
+    /// The scale of the arrow for the smallest transfer
+    const SIZE_MIN: f64 = 0.3;
+    /// The scale of the arrow for the largest transfer
+    const SIZE_MAX: f64 = 1.0;
+
+    /// Converts the energy transfer through a bridge to shader compatible
+    /// data, the size scales with the transfer relative to the largest
+    /// transfer
+    ///
+    /// # Parameters
+    ///
+    /// transfer: The energy received through the bridge, negative if energy
+    /// was sent to the neighbor
+    ///
+    /// max_transfer: The largest absolute transfer through any bridge
+    pub fn new(transfer: f64, max_transfer: f64) -> Self {
+        if transfer == 0.0 || max_transfer <= 0.0 {
+            return Self {
+                color_value: 0.0,
+                size: 0.0,
+            };
+        }
+
+        let ratio = transfer.abs() / max_transfer;
+        let size = Self::SIZE_MIN + (Self::SIZE_MAX - Self::SIZE_MIN) * ratio;
+
+        return Self {
+            color_value: ratio as f32,
+            size: (-transfer.signum() * size) as f32,
+        };
+    }
+
+    /// Creates the vertex buffer description for the transfer instance
+    #[cfg(feature = "viewer")]
+    pub fn desc() -> wgpu::VertexBufferLayout<'static> {
+        return wgpu::VertexBufferLayout {
+            array_stride: mem::size_of::<InstanceTransfer>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 1,
+                    format: wgpu::VertexFormat::Float32,
+                },
+                wgpu::VertexAttribute {
+                    offset: mem::size_of::<f32>() as wgpu::BufferAddress,
+                    shader_location: 2,
+                    format: wgpu::VertexFormat::Float32,
+                },
+            ],
         };
     }
 }
@@ -1,4 +1,4 @@
-use crate::types;
+use crate::{map::settings::wrap::Wrap, types};
 
 use super::{Tile, sun};
 
@@ -17,6 +17,15 @@ pub struct TileNeighbors<'a> {
     pub down_left: Neighbor<'a>,
     /// The tile to the down-right
     pub down_right: Neighbor<'a>,
+    /// The sun tile shining into this tile, only set for the top row
+    pub sun: Option<&'a sun::Tile>,
+    /// The sun tile above the column of this tile
+    pub column_sun: &'a sun::Tile,
+    /// True if this tile is in the bottom row
+    pub bottom: bool,
+    /// The update priority of each neighbor in the order of
+    /// NeighborDirection::collection(), lower values act first
+    priorities: [usize; 6],
 }
 
 impl<'a> TileNeighbors<'a> {
@@ -28,38 +37,62 @@ impl<'a> TileNeighbors<'a> {
     ///
     /// size: The size of the grid
     ///
+    /// wrap: The directions in which the grid wraps around
+    ///
     /// pos: The position of the tile to get neighbors for
+    ///
+    /// priorities: The update priority of every tile in the grid, lower
+    /// values act first
     pub fn new(
         tiles: &'a [Tile],
         sun: &'a [sun::Tile],
         size: &types::ISize,
+        wrap: &Wrap,
         pos: &TilePos,
+        priorities: &[usize],
     ) -> Self {
-        let right = match pos.right(size) {
+        // Only the top row sees the sun, other neighbors outside the grid are
+        // beyond an edge which does not wrap
+        let top = pos.pos.y == 0;
+        let outside = || {
+            if top {
+                Neighbor::SunTile(&sun[pos.pos.x as usize])
+            } else {
+                Neighbor::Empty
+            }
+        };
+
+        let right = match pos.right(size, wrap) {
             TilePosNeighbor::Valid(pos) => Neighbor::Tile(&tiles[pos.to_index(size)]),
             TilePosNeighbor::Invalid(_) => Neighbor::Empty,
         };
-        let up_right = match pos.up_right(size) {
+        let up_right = match pos.up_right(size, wrap) {
             TilePosNeighbor::Valid(pos) => Neighbor::Tile(&tiles[pos.to_index(size)]),
-            TilePosNeighbor::Invalid(_) => Neighbor::SunTile(&sun[pos.pos.x as usize]),
+            TilePosNeighbor::Invalid(_) => outside(),
         };
-        let up_left = match pos.up_left(size) {
+        let up_left = match pos.up_left(size, wrap) {
             TilePosNeighbor::Valid(pos) => Neighbor::Tile(&tiles[pos.to_index(size)]),
-            TilePosNeighbor::Invalid(_) => Neighbor::SunTile(&sun[pos.pos.x as usize]),
+            TilePosNeighbor::Invalid(_) => outside(),
         };
-        let left = match pos.left(size) {
+        let left = match pos.left(size, wrap) {
             TilePosNeighbor::Valid(pos) => Neighbor::Tile(&tiles[pos.to_index(size)]),
             TilePosNeighbor::Invalid(_) => Neighbor::Empty,
         };
-        let down_left = match pos.down_left(size) {
+        let down_left = match pos.down_left(size, wrap) {
             TilePosNeighbor::Valid(pos) => Neighbor::Tile(&tiles[pos.to_index(size)]),
             TilePosNeighbor::Invalid(_) => Neighbor::Empty,
         };
-        let down_right = match pos.down_right(size) {
+        let down_right = match pos.down_right(size, wrap) {
             TilePosNeighbor::Valid(pos) => Neighbor::Tile(&tiles[pos.to_index(size)]),
             TilePosNeighbor::Invalid(_) => Neighbor::Empty,
         };
 
+        let priorities =
+            NeighborDirection::collection().map(|dir| match pos.direction(size, wrap, &dir) {
+                TilePosNeighbor::Valid(pos) => priorities[pos.to_index(size)],
+                TilePosNeighbor::Invalid(_) => usize::MAX,
+            });
+
         return Self {
             right,
             up_right,
@@ -67,6 +100,10 @@ impl<'a> TileNeighbors<'a> {
             left,
             down_left,
             down_right,
+            sun: top.then(|| &sun[pos.pos.x as usize]),
+            column_sun: &sun[pos.pos.x as usize],
+            bottom: pos.pos.y == size.h as isize - 1,
+            priorities,
         };
     }
 
@@ -86,20 +123,14 @@ impl<'a> TileNeighbors<'a> {
         };
     }
 
-    /// Gets a mutable reference to the neighbor in the given direction
+    /// Gets the update priority of the neighbor in the given direction, lower
+    /// values act first
     ///
     /// # Parameters
     ///
     /// direction: The direction of the neighbor
-    pub fn get_mut(&mut self, direction: &NeighborDirection) -> &mut Neighbor<'a> {
-        return match direction {
-            NeighborDirection::Right => &mut self.right,
-            NeighborDirection::UpRight => &mut self.up_right,
-            NeighborDirection::UpLeft => &mut self.up_left,
-            NeighborDirection::Left => &mut self.left,
-            NeighborDirection::DownLeft => &mut self.down_left,
-            NeighborDirection::DownRight => &mut self.down_right,
-        };
+    pub fn get_priority(&self, direction: &NeighborDirection) -> usize {
+        return self.priorities[direction.index()];
     }
 }
 
@@ -154,15 +185,22 @@ impl TilePos {
     ///
     /// size: The size of the tile grid
     ///
+    /// wrap: The directions in which the grid wraps around
+    ///
     /// direction: The direction of the tile
-    pub fn direction(&self, size: &types::ISize, direction: &NeighborDirection) -> TilePosNeighbor {
+    pub fn direction(
+        &self,
+        size: &types::ISize,
+        wrap: &Wrap,
+        direction: &NeighborDirection,
+    ) -> TilePosNeighbor {
         return match direction {
-            NeighborDirection::Right => self.right(size),
-            NeighborDirection::UpRight => self.up_right(size),
-            NeighborDirection::UpLeft => self.up_left(size),
-            NeighborDirection::Left => self.left(size),
-            NeighborDirection::DownLeft => self.down_left(size),
-            NeighborDirection::DownRight => self.down_right(size),
+            NeighborDirection::Right => self.right(size, wrap),
+            NeighborDirection::UpRight => self.up_right(size, wrap),
+            NeighborDirection::UpLeft => self.up_left(size, wrap),
+            NeighborDirection::Left => self.left(size, wrap),
+            NeighborDirection::DownLeft => self.down_left(size, wrap),
+            NeighborDirection::DownRight => self.down_right(size, wrap),
         };
     }
 
@@ -171,17 +209,13 @@ impl TilePos {
     /// # Parameters
     ///
     /// size: The size of the tile grid
-    pub fn right(&self, size: &types::ISize) -> TilePosNeighbor {
+    ///
+    /// wrap: The directions in which the grid wraps around
+    pub fn right(&self, size: &types::ISize, wrap: &Wrap) -> TilePosNeighbor {
         let y = self.pos.y;
-        let x = if self.pos.x == size.w as isize - 1 {
-            0
-        } else {
-            self.pos.x + 1
-        };
+        let x = self.pos.x + 1;
 
-        return TilePosNeighbor::Valid(Self {
-            pos: types::Index { x, y },
-        });
+        return Self::wrap_neighbor(types::Index { x, y }, size, wrap);
     }
 
     /// Gets the tile position up-right of this tile, None if it is outside the grid
@@ -189,24 +223,17 @@ impl TilePos {
     /// # Parameters
     ///
     /// size: The size of the tile grid
-    pub fn up_right(&self, size: &types::ISize) -> TilePosNeighbor {
+    ///
+    /// wrap: The directions in which the grid wraps around
+    pub fn up_right(&self, size: &types::ISize, wrap: &Wrap) -> TilePosNeighbor {
         let y = self.pos.y - 1;
-        let x = if self.pos.x % 2 == 0 {
+        let x = if self.pos.y % 2 == 0 {
             self.pos.x
         } else {
-            if self.pos.x == size.w as isize - 1 {
-                0
-            } else {
-                self.pos.x + 1
-            }
+            self.pos.x + 1
         };
-        let pos = types::Index { x, y };
 
-        return if y == -1 {
-            TilePosNeighbor::Invalid(Self { pos })
-        } else {
-            TilePosNeighbor::Valid(Self { pos })
-        };
+        return Self::wrap_neighbor(types::Index { x, y }, size, wrap);
     }
 
     /// Gets the tile position up-left of this tile, None if it is outside the grid
@@ -214,24 +241,17 @@ impl TilePos {
     /// # Parameters
     ///
     /// size: The size of the tile grid
-    pub fn up_left(&self, size: &types::ISize) -> TilePosNeighbor {
+    ///
+    /// wrap: The directions in which the grid wraps around
+    pub fn up_left(&self, size: &types::ISize, wrap: &Wrap) -> TilePosNeighbor {
         let y = self.pos.y - 1;
-        let x = if self.pos.x % 2 == 0 {
-            if self.pos.x == 0 {
-                size.w as isize - 1
-            } else {
-                self.pos.x - 1
-            }
+        let x = if self.pos.y % 2 == 0 {
+            self.pos.x - 1
         } else {
             self.pos.x
         };
-        let pos = types::Index { x, y };
 
-        return if y == -1 {
-            TilePosNeighbor::Invalid(Self { pos })
-        } else {
-            TilePosNeighbor::Valid(Self { pos })
-        };
+        return Self::wrap_neighbor(types::Index { x, y }, size, wrap);
     }
 
     /// Gets the tile position left of this tile, None if it is outside the grid
@@ -239,17 +259,13 @@ impl TilePos {
     /// # Parameters
     ///
     /// size: The size of the tile grid
-    pub fn left(&self, size: &types::ISize) -> TilePosNeighbor {
+    ///
+    /// wrap: The directions in which the grid wraps around
+    pub fn left(&self, size: &types::ISize, wrap: &Wrap) -> TilePosNeighbor {
         let y = self.pos.y;
-        let x = if self.pos.x == 0 {
-            size.w as isize - 1
-        } else {
-            self.pos.x - 1
-        };
+        let x = self.pos.x - 1;
 
-        return TilePosNeighbor::Valid(Self {
-            pos: types::Index { x, y },
-        });
+        return Self::wrap_neighbor(types::Index { x, y }, size, wrap);
     }
 
     /// Gets the tile position down-left of this tile, None if it is outside the grid
@@ -257,24 +273,17 @@ impl TilePos {
     /// # Parameters
     ///
     /// size: The size of the tile grid
-    pub fn down_left(&self, size: &types::ISize) -> TilePosNeighbor {
+    ///
+    /// wrap: The directions in which the grid wraps around
+    pub fn down_left(&self, size: &types::ISize, wrap: &Wrap) -> TilePosNeighbor {
         let y = self.pos.y + 1;
-        let x = if self.pos.x % 2 == 0 {
-            if self.pos.x == 0 {
-                size.w as isize - 1
-            } else {
-                self.pos.x - 1
-            }
+        let x = if self.pos.y % 2 == 0 {
+            self.pos.x - 1
         } else {
             self.pos.x
         };
-        let pos = types::Index { x, y };
 
-        return if y == size.h as isize {
-            TilePosNeighbor::Invalid(Self { pos })
-        } else {
-            TilePosNeighbor::Valid(Self { pos })
-        };
+        return Self::wrap_neighbor(types::Index { x, y }, size, wrap);
     }
 
     /// Gets the tile position down-right of this tile, None if it is outside the grid
@@ -282,20 +291,41 @@ impl TilePos {
     /// # Parameters
     ///
     /// size: The size of the tile grid
-    pub fn down_right(&self, size: &types::ISize) -> TilePosNeighbor {
+    ///
+    /// wrap: The directions in which the grid wraps around
+    pub fn down_right(&self, size: &types::ISize, wrap: &Wrap) -> TilePosNeighbor {
         let y = self.pos.y + 1;
-        let x = if self.pos.x % 2 == 0 {
+        let x = if self.pos.y % 2 == 0 {
             self.pos.x
         } else {
-            if self.pos.x == size.w as isize - 1 {
-                0
-            } else {
-                self.pos.x + 1
-            }
+            self.pos.x + 1
         };
-        let pos = types::Index { x, y };
 
-        return if y == size.h as isize {
+        return Self::wrap_neighbor(types::Index { x, y }, size, wrap);
+    }
+
+    /// Wraps the position of a neighbor around the edges of the grid which
+    /// wrap, the neighbor is invalid if it is still outside the grid
+    ///
+    /// # Parameters
+    ///
+    /// pos: The position of the neighbor, at most one tile outside the grid
+    ///
+    /// size: The size of the tile grid
+    ///
+    /// wrap: The directions in which the grid wraps around
+    fn wrap_neighbor(pos: types::Index, size: &types::ISize, wrap: &Wrap) -> TilePosNeighbor {
+        let width = size.w as isize;
+        let height = size.h as isize;
+        let mut pos = pos;
+        if wrap.horizontal() {
+            pos.x = pos.x.rem_euclid(width);
+        }
+        if wrap.vertical() {
+            pos.y = pos.y.rem_euclid(height);
+        }
+
+        return if pos.x < 0 || pos.x >= width || pos.y < 0 || pos.y >= height {
             TilePosNeighbor::Invalid(Self { pos })
         } else {
             TilePosNeighbor::Valid(Self { pos })
@@ -341,7 +371,7 @@ pub enum TilePosNeighbor {
 }
 
 /// Describes the direction of a neighbor
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum NeighborDirection {
     Right,
     UpRight,
@@ -352,6 +382,9 @@ pub enum NeighborDirection {
 }
 
 impl NeighborDirection {
+    /// The number of different directions
+    pub const COUNT: usize = 6;
+
     /// A unique id for the neighbor direction which serves as a priority for acting on a neighbor
     pub fn id(&self) -> usize {
         return match self {
@@ -364,6 +397,47 @@ impl NeighborDirection {
         };
     }
 
+    /// The index of the direction in the collection of all directions
+    pub fn index(&self) -> usize {
+        return match self {
+            Self::Right => 0,
+            Self::UpRight => 1,
+            Self::UpLeft => 2,
+            Self::Left => 3,
+            Self::DownLeft => 4,
+            Self::DownRight => 5,
+        };
+    }
+
+    /// Gets the direction at an index in the collection of all directions,
+    /// indices beyond the directions wrap around
+    ///
+    /// # Parameters
+    ///
+    /// index: The index of the direction
+    pub fn from_index(index: usize) -> Self {
+        return match index % Self::COUNT {
+            0 => Self::Right,
+            1 => Self::UpRight,
+            2 => Self::UpLeft,
+            3 => Self::Left,
+            4 => Self::DownLeft,
+            _ => Self::DownRight,
+        };
+    }
+
+    /// The name of the direction
+    pub fn get_name(&self) -> &'static str {
+        return match self {
+            Self::Right => "Right",
+            Self::UpRight => "UpRight",
+            Self::UpLeft => "UpLeft",
+            Self::Left => "Left",
+            Self::DownLeft => "DownLeft",
+            Self::DownRight => "DownRight",
+        };
+    }
+
     /// A collection of all the different directions
     pub fn collection() -> &'static [Self; 6] {
         return &[
@@ -385,6 +459,9 @@ impl NeighborDirection {
             Self::Left => Self::Right,
             Self::DownLeft => Self::UpRight,
             Self::DownRight => Self::UpLeft,
-        }
-    } 
+        };
+    }
 }
+
+#[cfg(test)]
+mod tests;
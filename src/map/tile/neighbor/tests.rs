@@ -0,0 +1,159 @@
+use proptest::prelude::*;
+
+use super::*;
+
+/// Generates a grid size together with a way to wrap it, the height is even
+/// when wrapping in y as required by the pointy top layout
+fn grid() -> impl Strategy<Value = (types::ISize, Wrap)> {
+    return (
+        1..20usize,
+        1..10usize,
+        prop_oneof![Just(Wrap::None), Just(Wrap::Horizontal), Just(Wrap::Both)],
+    )
+        .prop_map(|(w, h, wrap)| {
+            let h = if wrap.vertical() { 2 * h } else { h };
+            return (types::ISize { w, h }, wrap);
+        });
+}
+
+/// Generates a grid together with a position of a tile inside it
+fn grid_pos() -> impl Strategy<Value = (types::ISize, Wrap, TilePos)> {
+    return grid().prop_flat_map(|(size, wrap)| {
+        return (0..size.w as isize, 0..size.h as isize).prop_map(move |(x, y)| {
+            return (
+                size,
+                wrap,
+                TilePos {
+                    pos: types::Index { x, y },
+                },
+            );
+        });
+    });
+}
+
+/// Generates one of the six directions
+fn direction() -> impl Strategy<Value = NeighborDirection> {
+    return (0..6usize).prop_map(|index| NeighborDirection::collection()[index]);
+}
+
+proptest! {
+    /// Going to a neighbor and back in the opposite direction returns to the
+    /// tile
+    #[test]
+    fn opposite_round_trip((size, wrap, pos) in grid_pos(), dir in direction()) {
+        if let TilePosNeighbor::Valid(neighbor) = pos.direction(&size, &wrap, &dir) {
+            prop_assert_eq!(
+                neighbor.direction(&size, &wrap, &dir.opposite()),
+                TilePosNeighbor::Valid(pos)
+            );
+        }
+    }
+
+    /// Every valid neighbor lists the tile among its own neighbors in the
+    /// opposite direction and nowhere else unless the grid is so small that
+    /// several directions lead to the same tile
+    #[test]
+    fn neighbor_symmetry((size, wrap, pos) in grid_pos(), dir in direction()) {
+        if let TilePosNeighbor::Valid(neighbor) = pos.direction(&size, &wrap, &dir) {
+            let back = NeighborDirection::collection()
+                .iter()
+                .filter(|back| {
+                    neighbor.direction(&size, &wrap, back) == TilePosNeighbor::Valid(pos)
+                })
+                .copied()
+                .collect::<Vec<_>>();
+            prop_assert!(back.contains(&dir.opposite()));
+            if size.w >= 3 && size.h >= 3 {
+                prop_assert_eq!(back, vec![dir.opposite()]);
+            }
+        }
+    }
+
+    /// Neighbors are always in the same row or the row directly above or
+    /// below, and in a column at most one away, unless they wrap around
+    #[test]
+    fn neighbor_distance((size, wrap, pos) in grid_pos(), dir in direction()) {
+        let neighbor = match pos.direction(&size, &wrap, &dir) {
+            TilePosNeighbor::Valid(neighbor) | TilePosNeighbor::Invalid(neighbor) => neighbor,
+        };
+        let dy: isize = match dir {
+            NeighborDirection::Right | NeighborDirection::Left => 0,
+            NeighborDirection::UpRight | NeighborDirection::UpLeft => -1,
+            NeighborDirection::DownLeft | NeighborDirection::DownRight => 1,
+        };
+        if wrap.vertical() {
+            let height = size.h as isize;
+            prop_assert_eq!(
+                (neighbor.pos.y - pos.pos.y).rem_euclid(height),
+                dy.rem_euclid(height)
+            );
+        } else {
+            prop_assert_eq!(neighbor.pos.y - pos.pos.y, dy);
+        }
+
+        let width = size.w as isize;
+        let dx = (neighbor.pos.x - pos.pos.x).rem_euclid(width);
+        prop_assert!(dx == 0 || dx == 1 || dx == width - 1);
+    }
+
+    /// Wrapping in x connects the first and last columns while a bounded grid
+    /// has no neighbors beyond them, the rows are never changed by it
+    #[test]
+    fn wrap_x_edges((size, wrap) in grid(), y in 0..20isize) {
+        let y = y % size.h as isize;
+        let width = size.w as isize;
+        let first = TilePos {
+            pos: types::Index { x: 0, y },
+        };
+        let last = TilePos {
+            pos: types::Index { x: width - 1, y },
+        };
+        let right = last.right(&size, &wrap);
+        let left = first.left(&size, &wrap);
+        if wrap.horizontal() {
+            prop_assert_eq!(right, TilePosNeighbor::Valid(first));
+            prop_assert_eq!(left, TilePosNeighbor::Valid(last));
+        } else {
+            prop_assert_eq!(
+                right,
+                TilePosNeighbor::Invalid(TilePos {
+                    pos: types::Index { x: width, y },
+                })
+            );
+            prop_assert_eq!(
+                left,
+                TilePosNeighbor::Invalid(TilePos {
+                    pos: types::Index { x: -1, y },
+                })
+            );
+        }
+
+        for pos in [first, last] {
+            for dir in NeighborDirection::collection() {
+                let neighbor = match pos.direction(&size, &wrap, dir) {
+                    TilePosNeighbor::Valid(neighbor)
+                    | TilePosNeighbor::Invalid(neighbor) => neighbor,
+                };
+                if wrap.horizontal() {
+                    prop_assert!((0..width).contains(&neighbor.pos.x));
+                }
+            }
+        }
+    }
+
+    /// Converting an index to a position and back gives the same index
+    #[test]
+    fn index_round_trip((size, _wrap) in grid(), index in 0..400usize) {
+        let index = index % (size.w * size.h);
+        let pos = TilePos::from_index(index, &size);
+        prop_assert!((0..size.w as isize).contains(&pos.pos.x));
+        prop_assert!((0..size.h as isize).contains(&pos.pos.y));
+        prop_assert_eq!(pos.to_index(&size), index);
+    }
+
+    /// Converting a position to an index and back gives the same position
+    #[test]
+    fn pos_round_trip((size, _wrap, pos) in grid_pos()) {
+        prop_assert_eq!(TilePos::from_index(pos.to_index(&size), &size), pos);
+    }
+}
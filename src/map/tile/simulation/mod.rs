@@ -1,4 +1,9 @@
-use super::{Neighbor, NeighborDirection, Settings, Tile, TileData, TileNeighbors};
+use rand::rngs::StdRng;
+
+use super::{
+    BridgeInfo, EnergyBalance, Genealogy, Neighbor, NeighborDirection, PlantEvent, PlantInfo,
+    Settings, Tile, TileData, TileNeighbors,
+};
 
 pub mod plant;
 
@@ -10,12 +15,84 @@ impl Tile {
     /// map_settings: The settings for the map
     ///
     /// neighbors: References to all the neighbors of this til
-    pub fn forward(&self, map_settings: &Settings, neighbors: &TileNeighbors) -> Self {
+    ///
+    /// light: The next light level computed by a light solver, None to
+    /// compute it from the neighbors
+    ///
+    /// rng: The random number generator of this tile for this step
+    ///
+    /// balance: The energy balance of the step to add the changes of the
+    /// plant to
+    pub fn forward(
+        &self,
+        map_settings: &Settings,
+        neighbors: &TileNeighbors,
+        light: Option<f64>,
+        rng: &mut StdRng,
+        balance: &mut EnergyBalance,
+    ) -> Self {
+        let diffuse = self.forward_diffuse(map_settings, neighbors);
+
         return Self {
-            plant: self.plant.forward(map_settings, &self.data, neighbors),
+            plant: self
+                .plant
+                .forward(map_settings, &self.data, neighbors, rng, balance),
             data: TileData {
                 transparency: self.forward_transparency(map_settings, neighbors),
-                light: self.forward_light(map_settings, neighbors),
+                light: light.unwrap_or_else(|| self.forward_light(map_settings, neighbors))
+                    + self.forward_reflection(map_settings, neighbors)
+                    + diffuse,
+                diffuse,
+                water: self.forward_water(map_settings, neighbors),
+                ground: self.data.ground,
+                terrain: self.data.terrain,
+                barren: self.data.barren,
+                nutrients: self.forward_nutrients(map_settings),
+                temperature: self.forward_temperature(map_settings, neighbors),
+                altitude: self.data.altitude,
+                detritus: self.forward_detritus(map_settings),
+                fertility: self.data.fertility,
+            },
+        };
+    }
+
+    /// Calculates the next state of the tile without updating the plant, used
+    /// for tiles which are known to stay unchanged apart from the light,
+    /// water and temperature
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The settings for the map
+    ///
+    /// neighbors: References to all the neighbors of this til
+    ///
+    /// light: The next light level computed by a light solver, None to
+    /// compute it from the neighbors
+    pub fn forward_light_only(
+        &self,
+        map_settings: &Settings,
+        neighbors: &TileNeighbors,
+        light: Option<f64>,
+    ) -> Self {
+        let diffuse = self.forward_diffuse(map_settings, neighbors);
+
+        return Self {
+            plant: self.plant.clone(),
+            data: TileData {
+                transparency: self.forward_transparency(map_settings, neighbors),
+                light: light.unwrap_or_else(|| self.forward_light(map_settings, neighbors))
+                    + self.forward_reflection(map_settings, neighbors)
+                    + diffuse,
+                diffuse,
+                water: self.forward_water(map_settings, neighbors),
+                ground: self.data.ground,
+                terrain: self.data.terrain,
+                barren: self.data.barren,
+                nutrients: self.forward_nutrients(map_settings),
+                temperature: self.forward_temperature(map_settings, neighbors),
+                altitude: self.data.altitude,
+                detritus: self.forward_detritus(map_settings),
+                fertility: self.data.fertility,
             },
         };
     }
@@ -28,27 +105,239 @@ impl Tile {
     ///
     /// neighbors: References to all the neighbors of this til
     fn forward_transparency(&self, map_settings: &Settings, _neighbors: &TileNeighbors) -> f64 {
-        return map_settings.transparency.base * self.plant.get_transparency(map_settings);
+        if self.data.terrain {
+            return 0.0;
+        }
+
+        return map_settings.transparency.base
+            * self.plant.get_transparency(map_settings)
+            * (1.0 - map_settings.detritus.opacity * self.data.detritus);
     }
 
-    /// Calculates the next light level of the tile
+    /// Calculates the next direct light level of the tile, the top row is
+    /// lit directly by the part of the sun which is not scattered by the sky.
+    /// The light falls straight down from the two tiles above or, when it is
+    /// angled, in from the neighbors facing the sun above the column
     ///
     /// # Parameters
     ///
     /// map_settings: The settings for the map
     ///
     /// neighbors: References to all the neighbors of this til
-    fn forward_light(&self, _map_settings: &Settings, neighbors: &TileNeighbors) -> f64 {
-        let light_right = match neighbors.up_right {
-            Neighbor::Empty => 0.0,
-            Neighbor::Tile(tile) => tile.data.light * tile.data.transparency,
-            Neighbor::SunTile(tile) => tile.intensity,
+    fn forward_light(&self, map_settings: &Settings, neighbors: &TileNeighbors) -> f64 {
+        let direct = map_settings.sky.get_direct();
+        if let Some(sun) = neighbors.sun {
+            return direct * sun.intensity;
+        }
+
+        let get_light = |neighbor: &Neighbor| {
+            return match neighbor {
+                Neighbor::Empty => 0.0,
+                Neighbor::Tile(tile) => {
+                    (tile.data.light - tile.data.diffuse) * tile.data.transparency
+                }
+                Neighbor::SunTile(tile) => direct * tile.intensity,
+            };
         };
-        let light_left = match neighbors.up_left {
-            Neighbor::Empty => 0.0,
-            Neighbor::Tile(tile) => tile.data.light * tile.data.transparency,
-            Neighbor::SunTile(tile) => tile.intensity,
+
+        if !map_settings.sky.angled {
+            return 0.5 * (get_light(&neighbors.up_right) + get_light(&neighbors.up_left));
+        }
+        return [
+            &neighbors.up_right,
+            &neighbors.up_left,
+            &neighbors.right,
+            &neighbors.left,
+        ]
+        .into_iter()
+        .zip(get_light_weights(neighbors.column_sun.angle))
+        .map(|(neighbor, weight)| weight * get_light(neighbor))
+        .sum();
+    }
+
+    /// Calculates the light reflected into the tile by its neighbors, every
+    /// tile reflects a fraction of the light it blocks evenly to the two
+    /// tiles below it and the two tiles beside it. The reflected light passes
+    /// on like the direct light such that it reaches shaded pockets
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The settings for the map
+    ///
+    /// neighbors: References to all the neighbors of this til
+    fn forward_reflection(&self, map_settings: &Settings, neighbors: &TileNeighbors) -> f64 {
+        let albedo = map_settings.transparency.albedo.clamp(0.0, 1.0);
+        if albedo == 0.0 {
+            return 0.0;
+        }
+
+        return [
+            &neighbors.up_right,
+            &neighbors.up_left,
+            &neighbors.right,
+            &neighbors.left,
+        ]
+        .into_iter()
+        .map(|neighbor| match neighbor {
+            Neighbor::Tile(tile) => {
+                0.25 * albedo * tile.data.light * (1.0 - tile.data.transparency).max(0.0)
+            }
+            Neighbor::Empty | Neighbor::SunTile(_) => 0.0,
+        })
+        .sum();
+    }
+
+    /// Calculates the next diffuse light level of the tile, the top row is
+    /// lit by the part of the sun scattered by the sky and every other tile
+    /// gathers the diffuse light passing through the tiles above and beside
+    /// it, such that the more of these are transparent the more light it
+    /// gets. Neighbors beyond an edge which does not wrap are left out
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The settings for the map
+    ///
+    /// neighbors: References to all the neighbors of this til
+    fn forward_diffuse(&self, map_settings: &Settings, neighbors: &TileNeighbors) -> f64 {
+        let settings = &map_settings.sky;
+        let scattered = settings.intensity.clamp(0.0, 1.0);
+        if scattered == 0.0 {
+            return 0.0;
+        }
+        if let Some(sun) = neighbors.sun {
+            return scattered * sun.intensity;
+        }
+
+        let (sum, weight) = [
+            (&neighbors.up_right, 1.0),
+            (&neighbors.up_left, 1.0),
+            (&neighbors.right, settings.spread),
+            (&neighbors.left, settings.spread),
+        ]
+        .into_iter()
+        .filter_map(|(neighbor, weight)| match neighbor {
+            Neighbor::Empty => None,
+            Neighbor::Tile(tile) => {
+                Some((weight * tile.data.diffuse * tile.data.transparency, weight))
+            }
+            Neighbor::SunTile(tile) => Some((weight * scattered * tile.intensity, weight)),
+        })
+        .fold((0.0, 0.0), |(sum, total), (value, weight)| {
+            (sum + value, total + weight)
+        });
+
+        if weight <= 0.0 {
+            return 0.0;
+        }
+        return sum / weight;
+    }
+
+    /// Calculates the next water level of the tile, the bottom row and the
+    /// terrain are kept at the source level while all other tiles only hold
+    /// water inside living plants
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The settings for the map
+    ///
+    /// neighbors: References to all the neighbors of this til
+    fn forward_water(&self, map_settings: &Settings, neighbors: &TileNeighbors) -> f64 {
+        if neighbors.bottom || self.data.terrain {
+            return map_settings.water.source;
+        }
+
+        return self
+            .plant
+            .get_water_next(map_settings, &self.data, neighbors);
+    }
+
+    /// Calculates the next nutrient concentration of the tile, ground tiles
+    /// are slowly replenished while any plant on them extracts nutrients
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The settings for the map
+    fn forward_nutrients(&self, map_settings: &Settings) -> f64 {
+        if !self.data.ground {
+            return 0.0;
+        }
+
+        let extraction = self.plant.get_nutrient_extraction(map_settings, &self.data);
+
+        return (self.data.nutrients - extraction + map_settings.nutrients.replenish)
+            .clamp(0.0, map_settings.nutrients.capacity);
+    }
+
+    /// Calculates the next amount of detritus of the tile, a dead plant is
+    /// removed in the next step and leaves fresh detritus behind which then
+    /// decays
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The settings for the map
+    fn forward_detritus(&self, map_settings: &Settings) -> f64 {
+        if self.plant.is_dead() {
+            return 1.0;
+        }
+
+        return (self.data.detritus - map_settings.detritus.decay).max(0.0);
+    }
+
+    /// Calculates the next temperature of the tile, the tile is heated by the
+    /// light towards a temperature it settles at and exchanges heat with its
+    /// neighbors
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The settings for the map
+    ///
+    /// neighbors: References to all the neighbors of this til
+    fn forward_temperature(&self, map_settings: &Settings, neighbors: &TileNeighbors) -> f64 {
+        let settings = &map_settings.temperature;
+        let temperature = self.data.temperature;
+
+        // Settle towards the temperature given by the altitude and the light
+        let settled = settings.get_ambient(self.data.altitude) + settings.heating * self.data.light;
+        let relaxation = settings.relaxation * (settled - temperature);
+
+        // Exchange heat with all neighboring tiles
+        let (sum, count) = NeighborDirection::collection()
+            .iter()
+            .filter_map(|dir| match neighbors.get(dir) {
+                Neighbor::Tile(tile) => Some(tile.data.temperature),
+                Neighbor::Empty | Neighbor::SunTile(_) => None,
+            })
+            .fold((0.0, 0), |(sum, count), value| (sum + value, count + 1));
+        let diffusion = if count > 0 {
+            settings.diffusion * (sum / count as f64 - temperature)
+        } else {
+            0.0
         };
-        return 0.5 * (light_right + light_left);
+
+        return temperature + relaxation + diffusion;
     }
 }
+
+/// Gets the weights of the light falling in from the up-right, up-left,
+/// right and left neighbors for light at an angle, the upper neighbors lie 30
+/// degrees from straight up and the side neighbors 90 degrees such that the
+/// light is split between the two neighbors surrounding the angle
+///
+/// # Parameters
+///
+/// angle: The angle of the light from straight up in radians, positive when
+/// it comes from the right
+fn get_light_weights(angle: f64) -> [f64; 4] {
+    let angle = angle.to_degrees().clamp(-90.0, 90.0);
+    if angle >= 30.0 {
+        let right = (angle - 30.0) / 60.0;
+        return [1.0 - right, 0.0, right, 0.0];
+    }
+    if angle <= -30.0 {
+        let left = (-angle - 30.0) / 60.0;
+        return [0.0, 1.0 - left, 0.0, left];
+    }
+
+    let up_right = (angle + 30.0) / 60.0;
+    return [up_right, 1.0 - up_right, 0.0, 0.0];
+}
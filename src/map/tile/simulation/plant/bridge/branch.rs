@@ -10,12 +10,23 @@ impl Branch {
     /// # Parameters
     ///
     /// map_settings: The general map settings
-    /// 
+    ///
     /// capacity: The transfer capacity
     pub fn get_energy_cost_transfer_energy(&self, map_settings: &Settings, capacity: f64) -> f64 {
         return map_settings.energy.transfer.energy.branch * capacity * capacity;
     }
 
+    /// Gets the energy build cost of water transfer for a branch bridge
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The general map settings
+    ///
+    /// capacity: The transfer capacity
+    pub fn get_energy_cost_transfer_water(&self, map_settings: &Settings, capacity: f64) -> f64 {
+        return map_settings.energy.transfer.water.branch * capacity * capacity;
+    }
+
     /// Gets the energy cost factor of running a branch bridge
     ///
     /// # Parameters
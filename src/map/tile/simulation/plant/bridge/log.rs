@@ -10,12 +10,23 @@ impl Log {
     /// # Parameters
     ///
     /// map_settings: The general map settings
-    /// 
+    ///
     /// capacity: The transfer capacity
     pub fn get_energy_cost_transfer_energy(&self, map_settings: &Settings, capacity: f64) -> f64 {
         return map_settings.energy.transfer.energy.log * capacity;
     }
 
+    /// Gets the energy build cost of water transfer for a log bridge
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The general map settings
+    ///
+    /// capacity: The transfer capacity
+    pub fn get_energy_cost_transfer_water(&self, map_settings: &Settings, capacity: f64) -> f64 {
+        return map_settings.energy.transfer.water.log * capacity;
+    }
+
     /// Gets the energy cost factor of running a log bridge
     ///
     /// # Parameters
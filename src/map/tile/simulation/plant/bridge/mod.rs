@@ -1,6 +1,8 @@
 use std::iter::once;
 
-use super::{Settings, NeighborDirection};
+use rand::rngs::StdRng;
+
+use super::{NeighborDirection, Settings, apply_jitter};
 
 mod log;
 pub use log::Log;
@@ -26,6 +28,18 @@ pub struct BridgeSet {
 }
 
 impl BridgeSet {
+    /// Constructs a new set without any bridges
+    pub fn new() -> Self {
+        return Self {
+            right: None,
+            up_right: None,
+            up_left: None,
+            left: None,
+            down_left: None,
+            down_right: None,
+        };
+    }
+
     /// Iterates through all the bridges
     pub fn iter(&self) -> impl Iterator<Item = &Bridge> {
         return once(&self.right)
@@ -84,8 +98,13 @@ pub struct Bridge {
     pub exiting: bool,
     /// The maximum amount of energy able to transfer per step
     pub energy_capacity: f64,
+    /// The maximum amount of water able to transfer per step
+    pub water_capacity: f64,
     /// The transfer mode for energy
     pub energy_transfer: TransferMode,
+    /// The total energy moved through the bridge in either direction since
+    /// it was built
+    pub throughput: f64,
 }
 
 impl Bridge {
@@ -95,7 +114,27 @@ impl Bridge {
             bridge: self.bridge.clone(),
             exiting: !self.exiting,
             energy_capacity: self.energy_capacity,
+            water_capacity: self.water_capacity,
             energy_transfer: self.energy_transfer.get_opposite(),
+            throughput: 0.0,
+        };
+    }
+
+    /// Returns a copy with jittered energy and water capacities
+    ///
+    /// # Parameters
+    ///
+    /// jitter: The maximum relative change of the capacities
+    ///
+    /// rng: The random number generator to draw from
+    pub fn mutate(&self, jitter: f64, rng: &mut StdRng) -> Self {
+        return Self {
+            bridge: self.bridge.clone(),
+            exiting: self.exiting,
+            energy_capacity: apply_jitter(self.energy_capacity, jitter, rng),
+            water_capacity: apply_jitter(self.water_capacity, jitter, rng),
+            energy_transfer: self.energy_transfer,
+            throughput: self.throughput,
         };
     }
 
@@ -108,7 +147,10 @@ impl Bridge {
         return self.bridge.get_energy_cost_build_base(map_settings)
             + self
                 .bridge
-                .get_energy_cost_transfer_energy(map_settings, self.energy_capacity);
+                .get_energy_cost_transfer_energy(map_settings, self.energy_capacity)
+            + self
+                .bridge
+                .get_energy_cost_transfer_water(map_settings, self.water_capacity);
     }
 
     /// Gets the energy cost of running a bridge
@@ -173,6 +215,22 @@ pub enum BridgeType {
 }
 
 impl BridgeType {
+    /// Gets the id of the bridge type
+    pub fn id(&self) -> usize {
+        return match self {
+            Self::Log(_) => 0,
+            Self::Branch(_) => 1,
+        };
+    }
+
+    /// Gets the name of the bridge type
+    pub fn get_name(&self) -> &'static str {
+        return match self {
+            Self::Log(_) => "Log",
+            Self::Branch(_) => "Branch",
+        };
+    }
+
     /// Gets the energy build cost of energy transfer for a bridge
     ///
     /// # Parameters
@@ -187,6 +245,20 @@ impl BridgeType {
         };
     }
 
+    /// Gets the energy build cost of water transfer for a bridge
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The general map settings
+    ///
+    /// capacity: The transfer capacity
+    pub fn get_energy_cost_transfer_water(&self, map_settings: &Settings, capacity: f64) -> f64 {
+        return match self {
+            Self::Log(data) => data.get_energy_cost_transfer_water(map_settings, capacity),
+            Self::Branch(data) => data.get_energy_cost_transfer_water(map_settings, capacity),
+        };
+    }
+
     /// Gets the energy cost factor of running a bridge
     ///
     /// # Parameters
@@ -0,0 +1,90 @@
+use super::{Program, Settings, TileData, TileNeighbors};
+
+/// Detailed implementation for a flower, it emits pollen carrying the genome
+/// of its plant and turns into a seed when it is fertilized by pollen from
+/// another plant
+#[derive(Clone, Debug)]
+pub struct Flower {
+    /// The genome of the seed this flower turns into, set when the flower has
+    /// been fertilized
+    offspring: Option<Box<Program>>,
+}
+
+impl Flower {
+    /// Constructs a new unfertilized flower
+    pub fn new() -> Self {
+        return Self { offspring: None };
+    }
+
+    /// Retrieves the genome of the offspring, None if the flower has not been
+    /// fertilized
+    pub fn get_offspring(&self) -> Option<&Program> {
+        return self.offspring.as_deref();
+    }
+
+    /// Fertilizes the flower such that it turns into a seed
+    ///
+    /// # Parameters
+    ///
+    /// program: The genome of the offspring
+    pub fn fertilize(&mut self, program: Program) {
+        self.offspring = Some(Box::new(program));
+    }
+
+    /// Gets the transparency of a flower
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The settings for this map
+    pub fn get_transparency(&self, map_settings: &Settings) -> f64 {
+        return map_settings.transparency.flower;
+    }
+
+    /// Gets the energy cost of building energy storage for a flower
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The general map settings
+    ///
+    /// capacity: The storage capacity
+    pub fn get_energy_cost_storage_energy(&self, map_settings: &Settings, capacity: f64) -> f64 {
+        return map_settings.energy.storage.energy.flower * capacity;
+    }
+
+    /// Gets the energy cost factor of running a flower
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The general map settings
+    pub fn get_energy_cost_factor_run(&self, map_settings: &Settings) -> f64 {
+        return map_settings.energy.running.bulk.flower;
+    }
+
+    /// Gets the base energy cost of building a new flower
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The general map settings
+    pub fn get_energy_cost_build_base(&self, map_settings: &Settings) -> f64 {
+        return map_settings.energy.base.bulk.flower;
+    }
+
+    /// Gets the energy gained by this flower this round, a flower collects no
+    /// energy by itself
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The general map settings
+    ///
+    /// tile: The data of the tile this plant is located on
+    ///
+    /// neighbors: All neighbor tiles to this tile
+    pub fn get_energy_gain(
+        &self,
+        _map_settings: &Settings,
+        _tile: &TileData,
+        _neighbors: &TileNeighbors,
+    ) -> f64 {
+        return 0.0;
+    }
+}
@@ -1,4 +1,6 @@
-use super::{Settings, TileData, TileNeighbors};
+use rand::rngs::StdRng;
+
+use super::{Settings, TileData, TileNeighbors, apply_jitter};
 
 /// Detailed implementation for a leaf
 #[derive(Clone, Debug)]
@@ -8,6 +10,29 @@ pub struct Leaf {
 }
 
 impl Leaf {
+    /// Constructs a new leaf, the absorption is kept between 0 and 0.99
+    /// since the build cost diverges at 1
+    ///
+    /// # Parameters
+    ///
+    /// absorption: The percentage of light absorbed in photosynthesis
+    pub fn new(absorption: f64) -> Self {
+        return Self {
+            absorption: absorption.clamp(0.0, 0.99),
+        };
+    }
+
+    /// Returns a copy with a jittered absorption
+    ///
+    /// # Parameters
+    ///
+    /// jitter: The maximum relative change of the absorption
+    ///
+    /// rng: The random number generator to draw from
+    pub fn mutate(&self, jitter: f64, rng: &mut StdRng) -> Self {
+        return Self::new(apply_jitter(self.absorption, jitter, rng));
+    }
+
     /// Gets the transparency of a leaf
     ///
     /// # Parameters
@@ -47,7 +72,8 @@ impl Leaf {
             + map_settings.energy.production.leaf / (1.0 - self.absorption);
     }
 
-    /// Gets the energy gained by this leaf this round
+    /// Gets the energy gained by this leaf this round, scaled by the fertility
+    /// of the tile and limited by the water available in the tile
     ///
     /// # Parameters
     ///
@@ -58,10 +84,35 @@ impl Leaf {
     /// neighbors: All neighbor tiles to this tile
     pub fn get_energy_gain(
         &self,
-        _map_settings: &Settings,
+        map_settings: &Settings,
         tile: &TileData,
         _neighbors: &TileNeighbors,
     ) -> f64 {
-        return tile.light * self.absorption;
+        let gain = tile.light * self.absorption * tile.fertility;
+        let usage = map_settings.water.leaf_usage;
+
+        return if usage > 0.0 {
+            gain.min(tile.water / usage)
+        } else {
+            gain
+        };
+    }
+
+    /// Gets the water used by this leaf this round
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The general map settings
+    ///
+    /// tile: The data of the tile this plant is located on
+    ///
+    /// neighbors: All neighbor tiles to this tile
+    pub fn get_water_usage(
+        &self,
+        map_settings: &Settings,
+        tile: &TileData,
+        neighbors: &TileNeighbors,
+    ) -> f64 {
+        return self.get_energy_gain(map_settings, tile, neighbors) * map_settings.water.leaf_usage;
     }
 }
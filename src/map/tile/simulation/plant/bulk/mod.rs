@@ -1,4 +1,6 @@
-use super::{Settings, TileData, TileNeighbors};
+use rand::rngs::StdRng;
+
+use super::{Program, Settings, TileData, TileNeighbors, apply_jitter};
 
 mod log;
 pub use log::Log;
@@ -15,6 +17,15 @@ pub use seed::Seed;
 mod ripe_seed;
 pub use ripe_seed::RipeSeed;
 
+mod root;
+pub use root::Root;
+
+mod flower;
+pub use flower::Flower;
+
+mod thorn;
+pub use thorn::Thorn;
+
 /// The bulk of a plant tile
 #[derive(Clone, Debug)]
 pub enum Bulk {
@@ -28,9 +39,80 @@ pub enum Bulk {
     Seed(Seed),
     /// A ripe seed, able to fall in the world, will become a sugar bulb when it is ready
     RipeSeed(RipeSeed),
+    /// Grows in the ground only, draws water and nutrients instead of light
+    Root(Root),
+    /// Emits pollen and turns into a seed with a mixed genome when it is
+    /// fertilized by pollen from another plant
+    Flower(Flower),
+    /// Protects itself and its neighbors from being grazed
+    Thorn(Thorn),
 }
 
 impl Bulk {
+    /// The number of different bulk types
+    pub const COUNT: usize = 8;
+
+    /// Gets a unique id for the bulk type smaller than COUNT
+    pub fn id(&self) -> usize {
+        return match self {
+            Self::Log(_) => 0,
+            Self::SugarBulb(_) => 1,
+            Self::Leaf(_) => 2,
+            Self::Seed(_) => 3,
+            Self::RipeSeed(_) => 4,
+            Self::Root(_) => 5,
+            Self::Flower(_) => 6,
+            Self::Thorn(_) => 7,
+        };
+    }
+
+    /// Constructs a bulk from its type id, a leaf absorbs half of the light
+    ///
+    /// # Parameters
+    ///
+    /// id: The type id of the bulk, see Bulk::id
+    pub fn from_id(id: usize) -> Self {
+        return match id.clamp(0, Self::COUNT - 1) {
+            0 => Self::Log(Log {}),
+            1 => Self::SugarBulb(SugarBulb {}),
+            2 => Self::Leaf(Leaf::new(0.5)),
+            3 => Self::Seed(Seed {}),
+            4 => Self::RipeSeed(RipeSeed::new()),
+            5 => Self::Root(Root {}),
+            6 => Self::Flower(Flower::new()),
+            7 => Self::Thorn(Thorn {}),
+            _ => panic!("Bulk::from_id has not been updated"),
+        };
+    }
+
+    /// Gets the name of the bulk type
+    pub fn get_name(&self) -> &'static str {
+        return match self {
+            Self::Log(_) => "Log",
+            Self::SugarBulb(_) => "SugarBulb",
+            Self::Leaf(_) => "Leaf",
+            Self::Seed(_) => "Seed",
+            Self::RipeSeed(_) => "RipeSeed",
+            Self::Root(_) => "Root",
+            Self::Flower(_) => "Flower",
+            Self::Thorn(_) => "Thorn",
+        };
+    }
+
+    /// Returns a copy with its numeric parameters randomly jittered
+    ///
+    /// # Parameters
+    ///
+    /// jitter: The maximum relative change of each parameter
+    ///
+    /// rng: The random number generator to draw from
+    pub fn mutate(&self, jitter: f64, rng: &mut StdRng) -> Self {
+        return match self {
+            Self::Leaf(data) => Self::Leaf(data.mutate(jitter, rng)),
+            _ => self.clone(),
+        };
+    }
+
     /// Gets the transparency for this plant
     ///
     /// # Parameters
@@ -43,6 +125,9 @@ impl Bulk {
             Self::Leaf(plant) => plant.get_transparency(map_settings),
             Self::Seed(plant) => plant.get_transparency(map_settings),
             Self::RipeSeed(plant) => plant.get_transparency(map_settings),
+            Self::Root(plant) => plant.get_transparency(map_settings),
+            Self::Flower(plant) => plant.get_transparency(map_settings),
+            Self::Thorn(plant) => plant.get_transparency(map_settings),
         };
     }
 
@@ -60,6 +145,9 @@ impl Bulk {
             Self::Leaf(data) => data.get_energy_cost_storage_energy(map_settings, capacity),
             Self::Seed(data) => data.get_energy_cost_storage_energy(map_settings, capacity),
             Self::RipeSeed(data) => data.get_energy_cost_storage_energy(map_settings, capacity),
+            Self::Root(data) => data.get_energy_cost_storage_energy(map_settings, capacity),
+            Self::Flower(data) => data.get_energy_cost_storage_energy(map_settings, capacity),
+            Self::Thorn(data) => data.get_energy_cost_storage_energy(map_settings, capacity),
         };
     }
 
@@ -75,6 +163,9 @@ impl Bulk {
             Self::Leaf(data) => data.get_energy_cost_factor_run(map_settings),
             Self::Seed(data) => data.get_energy_cost_factor_run(map_settings),
             Self::RipeSeed(data) => data.get_energy_cost_factor_run(map_settings),
+            Self::Root(data) => data.get_energy_cost_factor_run(map_settings),
+            Self::Flower(data) => data.get_energy_cost_factor_run(map_settings),
+            Self::Thorn(data) => data.get_energy_cost_factor_run(map_settings),
         };
     }
 
@@ -90,6 +181,9 @@ impl Bulk {
             Self::Leaf(data) => data.get_energy_cost_build_base(map_settings),
             Self::Seed(data) => data.get_energy_cost_build_base(map_settings),
             Self::RipeSeed(data) => data.get_energy_cost_build_base(map_settings),
+            Self::Root(data) => data.get_energy_cost_build_base(map_settings),
+            Self::Flower(data) => data.get_energy_cost_build_base(map_settings),
+            Self::Thorn(data) => data.get_energy_cost_build_base(map_settings),
         };
     }
 
@@ -114,6 +208,90 @@ impl Bulk {
             Self::Leaf(data) => data.get_energy_gain(map_settings, tile, neighbors),
             Self::Seed(data) => data.get_energy_gain(map_settings, tile, neighbors),
             Self::RipeSeed(data) => data.get_energy_gain(map_settings, tile, neighbors),
+            Self::Root(data) => data.get_energy_gain(map_settings, tile, neighbors),
+            Self::Flower(data) => data.get_energy_gain(map_settings, tile, neighbors),
+            Self::Thorn(data) => data.get_energy_gain(map_settings, tile, neighbors),
+        };
+    }
+
+    /// Gets the water used by this plant this round
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The general map settings
+    ///
+    /// tile: The data of the tile this plant is located on
+    ///
+    /// neighbors: All neighbor tiles to this tile
+    pub fn get_water_usage(
+        &self,
+        map_settings: &Settings,
+        tile: &TileData,
+        neighbors: &TileNeighbors,
+    ) -> f64 {
+        return match self {
+            Self::Leaf(data) => data.get_water_usage(map_settings, tile, neighbors),
+            Self::Log(_)
+            | Self::SugarBulb(_)
+            | Self::Seed(_)
+            | Self::RipeSeed(_)
+            | Self::Root(_)
+            | Self::Flower(_)
+            | Self::Thorn(_) => 0.0,
+        };
+    }
+
+    /// Gets the water drawn from the ground by this plant this round
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The general map settings
+    ///
+    /// tile: The data of the tile this plant is located on
+    pub fn get_water_uptake(&self, map_settings: &Settings, tile: &TileData) -> f64 {
+        return match self {
+            Self::Root(data) => data.get_water_uptake(map_settings, tile),
+            Self::Log(_)
+            | Self::SugarBulb(_)
+            | Self::Leaf(_)
+            | Self::Seed(_)
+            | Self::RipeSeed(_)
+            | Self::Flower(_)
+            | Self::Thorn(_) => 0.0,
         };
     }
+
+    /// Gets the maximum nutrients this plant extracts from a ground tile each
+    /// round
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The general map settings
+    pub fn get_nutrient_extraction(&self, map_settings: &Settings) -> f64 {
+        return match self {
+            Self::Root(data) => data.get_nutrient_extraction(map_settings),
+            Self::Log(_)
+            | Self::SugarBulb(_)
+            | Self::Leaf(_)
+            | Self::Seed(_)
+            | Self::RipeSeed(_)
+            | Self::Flower(_)
+            | Self::Thorn(_) => map_settings.nutrients.extraction,
+        };
+    }
+
+    /// Checks if this bulk protects itself and its neighbors from grazing
+    pub fn is_thorn(&self) -> bool {
+        return matches!(self, Self::Thorn(_));
+    }
+
+    /// Checks if this bulk converts light into energy
+    pub fn is_leaf(&self) -> bool {
+        return matches!(self, Self::Leaf(_));
+    }
+
+    /// Checks if this bulk can only exist in ground tiles
+    pub fn requires_ground(&self) -> bool {
+        return matches!(self, Self::Root(_));
+    }
 }
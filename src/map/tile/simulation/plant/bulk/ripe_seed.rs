@@ -1,10 +1,42 @@
 use super::{Settings, TileData, TileNeighbors};
 
-/// Detailed implementation for a ripe seed
+/// Detailed implementation for a ripe seed, it falls until it lands and then
+/// lies dormant until it germinates
 #[derive(Clone, Debug)]
-pub struct RipeSeed {}
+pub struct RipeSeed {
+    /// The number of steps the seed has been dormant since it landed, None
+    /// while it is still falling
+    dormant: Option<usize>,
+}
 
 impl RipeSeed {
+    /// Constructs a new ripe seed which has not landed yet
+    pub fn new() -> Self {
+        return Self { dormant: None };
+    }
+
+    /// Checks if the seed has landed and is waiting to germinate
+    pub fn is_dormant(&self) -> bool {
+        return self.dormant.is_some();
+    }
+
+    /// Lets the seed wait one more step after it has landed, returns None if
+    /// it has waited for the full dormancy duration and germinates
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The general map settings
+    pub fn wait(&self, map_settings: &Settings) -> Option<Self> {
+        let steps = self.dormant.map_or(0, |steps| steps + 1);
+        if steps >= map_settings.dormancy.duration {
+            return None;
+        }
+
+        return Some(Self {
+            dormant: Some(steps),
+        });
+    }
+
     /// Gets the transparency of a ripe seed
     ///
     /// # Parameters
@@ -19,7 +51,7 @@ impl RipeSeed {
     /// # Parameters
     ///
     /// map_settings: The general map settings
-    /// 
+    ///
     /// capacity: The storage capacity
     pub fn get_energy_cost_storage_energy(&self, map_settings: &Settings, capacity: f64) -> f64 {
         return map_settings.energy.storage.energy.seed * capacity;
@@ -31,7 +63,13 @@ impl RipeSeed {
     ///
     /// map_settings: The general map settings
     pub fn get_energy_cost_factor_run(&self, map_settings: &Settings) -> f64 {
-        return map_settings.energy.running.bulk.seed;
+        let factor = map_settings.energy.running.bulk.seed;
+
+        return if self.is_dormant() {
+            factor * map_settings.dormancy.running
+        } else {
+            factor
+        };
     }
 
     /// Gets the base energy cost of building a new ripe seed
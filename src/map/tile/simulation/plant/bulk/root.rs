@@ -0,0 +1,90 @@
+use super::{Settings, TileData, TileNeighbors};
+
+/// Detailed implementation for a root, it can only grow in the ground where
+/// it draws water and nutrients instead of collecting light
+#[derive(Clone, Debug)]
+pub struct Root {}
+
+impl Root {
+    /// Gets the transparency of a root, no light passes through it
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The settings for this map
+    pub fn get_transparency(&self, _map_settings: &Settings) -> f64 {
+        return 0.0;
+    }
+
+    /// Gets the energy cost of building energy storage for a root
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The general map settings
+    ///
+    /// capacity: The storage capacity
+    pub fn get_energy_cost_storage_energy(&self, map_settings: &Settings, capacity: f64) -> f64 {
+        return map_settings.energy.storage.energy.root * capacity;
+    }
+
+    /// Gets the energy cost factor of running a root
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The general map settings
+    pub fn get_energy_cost_factor_run(&self, map_settings: &Settings) -> f64 {
+        return map_settings.energy.running.bulk.root;
+    }
+
+    /// Gets the base energy cost of building a new root
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The general map settings
+    pub fn get_energy_cost_build_base(&self, map_settings: &Settings) -> f64 {
+        return map_settings.energy.base.bulk.root;
+    }
+
+    /// Gets the energy gained by this root this round, a root collects no
+    /// light so all of its energy comes from the extracted nutrients
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The general map settings
+    ///
+    /// tile: The data of the tile this plant is located on
+    ///
+    /// neighbors: All neighbor tiles to this tile
+    pub fn get_energy_gain(
+        &self,
+        _map_settings: &Settings,
+        _tile: &TileData,
+        _neighbors: &TileNeighbors,
+    ) -> f64 {
+        return 0.0;
+    }
+
+    /// Gets the water drawn from the ground by this root this round
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The general map settings
+    ///
+    /// tile: The data of the tile this plant is located on
+    pub fn get_water_uptake(&self, map_settings: &Settings, tile: &TileData) -> f64 {
+        if !tile.ground {
+            return 0.0;
+        }
+
+        return map_settings.water.root_uptake;
+    }
+
+    /// Gets the maximum nutrients this root extracts from the ground each
+    /// round
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The general map settings
+    pub fn get_nutrient_extraction(&self, map_settings: &Settings) -> f64 {
+        return map_settings.nutrients.root_extraction;
+    }
+}
@@ -1,12 +1,11 @@
-// Log: #52361e
-// Leaf: #1b6623
-// SugarBulb: #93b5ae
-// Seed: #f2bb07
-// RipeSeed: #b30c1a
-
 // Log: #52361e
 // Branch: #78583c
-use super::{Neighbor, NeighborDirection, Settings, TileData, TileNeighbors};
+use rand::{Rng, rngs::StdRng};
+
+use super::{
+    BridgeInfo, EnergyBalance, Genealogy, Neighbor, NeighborDirection, PlantEvent, PlantInfo,
+    Settings, TileData, TileNeighbors,
+};
 
 mod state;
 pub use state::State;
@@ -14,13 +13,18 @@ pub use state::State;
 mod spread;
 use spread::Spread;
 
+mod pollen;
+pub use pollen::Pollen;
+
 mod bridge;
-use bridge::BridgeSet;
+use bridge::{Bridge, BridgeSet, BridgeType};
 
 mod bulk;
 use bulk::Bulk;
 
 mod program;
+pub use program::GenomeError;
+use program::{ApplyData, Decision, Program};
 
 /// A single plant tile
 #[derive(Clone, Debug)]
@@ -36,6 +40,9 @@ pub struct Plant {
     cum_age: usize,
     /// If the plant is currently alive
     alive: bool,
+    /// If true then this plant tile is the root of its plant, grown from a
+    /// landed seed, and stays alive without a mother
+    root: bool,
     /// The energy in this plant tile
     energy: f64,
     /// The maximum amount of energy allowed
@@ -44,12 +51,373 @@ pub struct Plant {
     /// this tile, if there are more energy then each neighbor can take up to
     /// 1/N of any extra energy where N is the number of bridges connected
     energy_reserve: f64,
+    /// The energy received through the bridge in every direction during the
+    /// last step in the order of NeighborDirection::collection(), negative if
+    /// energy was sent to the neighbor
+    transfers: [f64; 6],
     /// Set if it attempts to spread to a neighboring tile, the tile it will
     /// spread to and the energy allocated for creating the new plant
     spread: Spread,
+    /// The genome of the plant deciding its actions
+    program: Program,
+    /// The id of the lineage in the genealogy of the map, None until the map
+    /// has registered a newly created seed
+    lineage: Option<usize>,
+    /// The id of the lineage of the mother plant of the seed this plant grew
+    /// from, None for plants which were placed directly on the map
+    parent_lineage: Option<usize>,
 }
 
 impl Plant {
+    /// Constructs a new plant tile placed directly on the map with the
+    /// starter genome, it starts a plant of its own filled with energy
+    ///
+    /// # Parameters
+    ///
+    /// bulk_id: The type id of the bulk, see Bulk::id
+    ///
+    /// energy_capacity: The maximum amount of energy allowed
+    pub fn new_placed(bulk_id: usize, energy_capacity: f64) -> Self {
+        return Self::new_root(
+            Bulk::from_id(bulk_id),
+            energy_capacity,
+            Program::new_starter(),
+        );
+    }
+
+    /// Constructs a new plant tile placed directly on the map with a genome
+    /// read from the genome text format, it starts a plant of its own filled
+    /// with energy
+    ///
+    /// # Parameters
+    ///
+    /// text: The genome text, see Plant::genome_to_string
+    ///
+    /// bulk_id: The type id of the bulk, see Bulk::id
+    ///
+    /// energy_capacity: The maximum amount of energy allowed
+    ///
+    /// # Errors
+    ///
+    /// See GenomeError for a description of the different errors which may occur
+    pub fn from_genome_str(
+        text: &str,
+        bulk_id: usize,
+        energy_capacity: f64,
+    ) -> Result<Self, GenomeError> {
+        let program = Program::from_genome_str(text)?;
+
+        return Ok(Self::new_root(
+            Bulk::from_id(bulk_id),
+            energy_capacity,
+            program,
+        ));
+    }
+
+    /// Constructs a new sugar bulb with a random genome appearing
+    /// spontaneously on the map, like a landed seed it is the root of a new
+    /// plant
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The settings for the map
+    ///
+    /// rng: The random number generator to draw the genome from
+    pub fn new_spawned(map_settings: &Settings, rng: &mut StdRng) -> Self {
+        let genesis = &map_settings.genesis;
+
+        return Self::new_root(
+            Bulk::SugarBulb(bulk::SugarBulb {}),
+            genesis.energy_capacity,
+            Program::new_random(genesis.program_length, rng),
+        );
+    }
+
+    /// Constructs a new plant tile without a mother filled with energy, it is
+    /// the root of its plant unless it is a ripe seed which has not landed
+    ///
+    /// # Parameters
+    ///
+    /// bulk: The bulk of the plant
+    ///
+    /// energy_capacity: The maximum amount of energy allowed
+    ///
+    /// program: The genome of the plant
+    fn new_root(bulk: Bulk, energy_capacity: f64, program: Program) -> Self {
+        let root = !matches!(bulk, Bulk::RipeSeed(_));
+
+        return Self {
+            bulk,
+            bridges: BridgeSet::new(),
+            age: 0,
+            cum_age: 0,
+            alive: true,
+            root,
+            energy: energy_capacity,
+            energy_capacity,
+            energy_reserve: 0.0,
+            transfers: [0.0; 6],
+            spread: Spread::Nothing,
+            program,
+            lineage: None,
+            parent_lineage: None,
+        };
+    }
+
+    /// Checks if the plant is currently alive
+    pub fn is_alive(&self) -> bool {
+        return self.alive;
+    }
+
+    /// Retrieves the id of the lineage of the plant, None if it has not been
+    /// registered yet
+    pub fn get_lineage(&self) -> Option<usize> {
+        return self.lineage;
+    }
+
+    /// Gets the distance between the genome of this plant and the genome of
+    /// another plant, see Program::get_distance
+    ///
+    /// # Parameters
+    ///
+    /// other: The plant to compare with
+    pub fn get_genome_distance(&self, other: &Self) -> f64 {
+        return self.program.get_distance(&other.program);
+    }
+
+    /// Checks if the bulk of this plant can only exist in ground tiles
+    pub fn requires_ground(&self) -> bool {
+        return self.bulk.requires_ground();
+    }
+
+    /// Gets a grain of pollen carrying the genome of this plant if it is a
+    /// living flower which has not been fertilized
+    pub fn get_pollen(&self) -> Option<Pollen> {
+        if !self.alive {
+            return None;
+        }
+
+        return match &self.bulk {
+            Bulk::Flower(flower) if flower.get_offspring().is_none() => Some(Pollen {
+                program: self.program.clone(),
+                lineage: self.lineage,
+            }),
+            _ => None,
+        };
+    }
+
+    /// Fertilizes this plant tile if it is a living flower which has not been
+    /// fertilized yet, the genome of the offspring is a mutated crossover of
+    /// the genome of this plant and the pollen. Returns false if the pollen
+    /// was not taken, pollen from the same lineage is never taken
+    ///
+    /// # Parameters
+    ///
+    /// pollen: The pollen to fertilize with
+    ///
+    /// map_settings: The settings for the map
+    ///
+    /// rng: The random number generator to draw the crossover and mutations
+    /// from
+    pub fn fertilize(
+        &mut self,
+        pollen: &Pollen,
+        map_settings: &Settings,
+        rng: &mut StdRng,
+    ) -> bool {
+        if !self.alive || pollen.lineage == self.lineage {
+            return false;
+        }
+
+        return match &mut self.bulk {
+            Bulk::Flower(flower) if flower.get_offspring().is_none() => {
+                flower.fertilize(
+                    self.program
+                        .crossover(&pollen.program, rng)
+                        .mutate(map_settings, rng),
+                );
+                true
+            }
+            _ => false,
+        };
+    }
+
+    /// Checks if this is a living thorn protecting itself and its neighbors
+    /// from grazing
+    pub fn is_thorn(&self) -> bool {
+        return self.alive && self.bulk.is_thorn();
+    }
+
+    /// Checks if this is a living leaf
+    pub fn is_leaf(&self) -> bool {
+        return self.alive && self.bulk.is_leaf();
+    }
+
+    /// Kills this plant tile, it is removed in the next step and its energy
+    /// is lost
+    pub fn kill(&mut self) {
+        self.alive = false;
+    }
+
+    /// Gets the energy herbivores can graze from this plant tile, None if it
+    /// is dead
+    pub fn get_forage(&self) -> Option<f64> {
+        if !self.alive {
+            return None;
+        }
+
+        return Some(self.energy);
+    }
+
+    /// Lets herbivores eat a fraction of the energy of this plant tile
+    ///
+    /// # Parameters
+    ///
+    /// damage: The fraction of the energy to eat
+    pub fn graze(&mut self, damage: f64) {
+        if self.alive {
+            self.energy -= damage * self.energy.max(0.0);
+        }
+    }
+
+    /// Registers this plant tile as the start of a new lineage if it does not
+    /// belong to one yet
+    ///
+    /// # Parameters
+    ///
+    /// genealogy: The genealogy of the map to register the lineage in
+    ///
+    /// time: The current simulation step
+    pub fn register_lineage(&mut self, genealogy: &mut Genealogy, time: usize) {
+        if self.lineage.is_none() {
+            self.lineage =
+                Some(genealogy.register(self.parent_lineage, time, self.program.get_hash()));
+        }
+    }
+
+    /// Writes the genome of this plant tile in the genome text format
+    ///
+    /// # Parameters
+    ///
+    /// simplify: True if operators which can never be evaluated are left out
+    pub fn genome_to_string(&self, simplify: bool) -> String {
+        return if simplify {
+            self.program.simplify().to_genome_string()
+        } else {
+            self.program.to_genome_string()
+        };
+    }
+
+    /// Makes sure all indices of the genome of this plant tile are in range
+    ///
+    /// # Errors
+    ///
+    /// See Program::validate for the errors which may occur
+    pub fn validate_genome(&self) -> Result<(), GenomeError> {
+        return self.program.validate();
+    }
+
+    /// Retrieves the id of the bulk type of this plant tile
+    pub fn get_bulk_id(&self) -> usize {
+        return self.bulk.id();
+    }
+
+    /// Gets the type id and energy capacity of the bridges in all directions
+    /// in the order of NeighborDirection::collection(), None for directions
+    /// without a bridge
+    pub fn get_bridge_data(&self) -> [Option<(usize, f64)>; 6] {
+        return NeighborDirection::collection().map(|dir| {
+            self.bridges
+                .get(&dir)
+                .as_ref()
+                .map(|bridge| (bridge.bridge.id(), bridge.energy_capacity))
+        });
+    }
+
+    /// Gets the energy received through the bridge in every direction during
+    /// the last step in the order of NeighborDirection::collection(),
+    /// negative if energy was sent to the neighbor
+    pub fn get_transfer_data(&self) -> [f64; 6] {
+        return self.transfers;
+    }
+
+    /// Gets the total energy moved through the bridges in all directions
+    /// since they were built in the order of NeighborDirection::collection(),
+    /// None for directions without a bridge
+    pub fn get_bridge_throughputs(&self) -> [Option<f64>; 6] {
+        return NeighborDirection::collection().map(|dir| {
+            self.bridges
+                .get(&dir)
+                .as_ref()
+                .map(|bridge| bridge.throughput)
+        });
+    }
+
+    /// Retrieves the age of this plant tile in simulation steps
+    pub fn get_age(&self) -> usize {
+        return self.age;
+    }
+
+    /// Retrieves the age of the entire plant in simulation steps
+    pub fn get_cum_age(&self) -> usize {
+        return self.cum_age;
+    }
+
+    /// Retrieves the energy stored in this plant tile
+    pub fn get_energy(&self) -> f64 {
+        return self.energy;
+    }
+
+    /// Retrieves the energy stored in this plant tile including the energy
+    /// set aside for spreading
+    pub fn get_stored_energy(&self) -> f64 {
+        return self.energy + self.spread.get_energy();
+    }
+
+    /// Gets a snapshot of the state of this plant for inspection
+    ///
+    /// # Parameters
+    ///
+    /// building: True if the plant is still being built
+    pub fn get_info(&self, building: bool) -> PlantInfo {
+        return PlantInfo {
+            bulk: self.bulk.get_name(),
+            building,
+            alive: self.alive,
+            root: self.root,
+            energy: self.energy,
+            energy_capacity: self.energy_capacity,
+            age: self.age,
+            cum_age: self.cum_age,
+            lineage: self.lineage,
+            species: None,
+            bridges: self.get_bridge_info(),
+        };
+    }
+
+    /// Gets a snapshot of all bridges connecting this plant tile to its
+    /// neighbors for inspection
+    pub fn get_bridge_info(&self) -> Vec<BridgeInfo> {
+        return NeighborDirection::collection()
+            .iter()
+            .filter_map(|dir| {
+                self.bridges.get(dir).as_ref().map(|bridge| BridgeInfo {
+                    direction: dir.get_name(),
+                    bridge: bridge.bridge.get_name(),
+                    exiting: bridge.exiting,
+                    energy_capacity: bridge.energy_capacity,
+                    water_capacity: bridge.water_capacity,
+                    throughput: bridge.throughput,
+                })
+            })
+            .collect();
+    }
+
+    /// Retrieves the name of the bulk type of this plant tile
+    pub fn get_bulk_name(&self) -> &'static str {
+        return self.bulk.get_name();
+    }
+
     /// Gets the transparency of this plant
     ///
     /// # Parameters
@@ -106,7 +474,23 @@ impl Plant {
                 .bridges
                 .iter()
                 .map(|bridge| 0.5 * bridge.get_energy_cost_run(map_settings))
-                .sum::<f64>();
+                .sum::<f64>()
+            + self.get_program_energy_cost_run(map_settings);
+    }
+
+    /// Gets the running energy cost of the program of this plant
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The general map settings
+    fn get_program_energy_cost_run(&self, map_settings: &Settings) -> f64 {
+        let settings = &map_settings.energy.running.program;
+        let mut cost = settings.operator * self.program.get_operator_count() as f64;
+        if settings.active_operator != 0.0 {
+            cost += settings.active_operator * self.program.get_active_operator_count() as f64;
+        }
+
+        return cost;
     }
 
     /// Gets the energy gained by this plant this round
@@ -127,43 +511,83 @@ impl Plant {
         return self.bulk.get_energy_gain(map_settings, tile, neighbors);
     }
 
-    /// Gets the energy transfered to or from this plant tile with its
-    /// neighbors, gains energy if positive, looses energy if negative
+    /// Gets the energy transfered to or from this plant tile through the
+    /// bridge in every direction in the order of
+    /// NeighborDirection::collection(), gains energy if positive, looses
+    /// energy if negative. Energy only moves while both ends have the bridge
+    /// such that both ends agree on the transfer
     ///
     /// # Parameters
     ///
     /// neighbors: All neighbor tiles to this tile
-    fn get_energy_transfer(&self, neighbors: &TileNeighbors) -> f64 {
-        return NeighborDirection::collection()
+    fn get_energy_transfers(&self, neighbors: &TileNeighbors) -> [f64; 6] {
+        return NeighborDirection::collection().map(|dir| {
+            if let Some(bridge) = self.bridges.get(&dir) {
+                if let Neighbor::Tile(tile) = neighbors.get(&dir) {
+                    if let State::Occupied(plant) = &tile.plant {
+                        if plant.alive && plant.bridges.get(&dir.opposite()).is_some() {
+                            let self_energy = ((self.energy - self.energy_reserve) / 6.0).max(0.0);
+                            let self_capacity =
+                                (self.energy_capacity - self.energy_reserve) / 6.0 - self_energy;
+                            let neighbor_energy =
+                                ((plant.energy - plant.energy_reserve) / 6.0).max(0.0);
+                            let neighbor_capacity = (plant.energy_capacity - plant.energy_reserve)
+                                / 6.0
+                                - neighbor_energy;
+
+                            return (neighbor_energy - self_energy).clamp(
+                                if bridge.energy_transfer.can_transmit() {
+                                    -(bridge.energy_capacity.min(neighbor_capacity))
+                                } else {
+                                    0.0
+                                },
+                                if bridge.energy_transfer.can_receive() {
+                                    bridge.energy_capacity.min(self_capacity)
+                                } else {
+                                    0.0
+                                },
+                            );
+                        }
+                    }
+                }
+            }
+            return 0.0;
+        });
+    }
+
+    /// Gets the water level of the tile of this plant for the next step, water
+    /// diffuses through the bridges to other living plant tiles limited by
+    /// the water capacity of the bridges
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The general map settings
+    ///
+    /// tile: The data of the tile this plant is located on
+    ///
+    /// neighbors: All neighbor tiles to this tile
+    fn get_water_next(
+        &self,
+        map_settings: &Settings,
+        tile: &TileData,
+        neighbors: &TileNeighbors,
+    ) -> f64 {
+        if !self.alive {
+            return 0.0;
+        }
+
+        let transfer = NeighborDirection::collection()
             .iter()
             .filter_map(|dir| {
                 if let Some(bridge) = self.bridges.get(dir) {
-                    if let Neighbor::Tile(tile) = neighbors.get(dir) {
-                        if let State::Occupied(plant) = &tile.plant {
-                            if plant.alive {
-                                let self_energy =
-                                    ((self.energy - self.energy_reserve) / 6.0).max(0.0);
-                                let self_capacity = (self.energy_capacity - self.energy_reserve)
-                                    / 6.0
-                                    - self_energy;
-                                let neighbor_energy =
-                                    ((plant.energy - plant.energy_reserve) / 6.0).max(0.0);
-                                let neighbor_capacity =
-                                    (plant.energy_capacity - plant.energy_reserve) / 6.0
-                                        - neighbor_energy;
-
-                                return Some((neighbor_energy - self_energy).clamp(
-                                    if bridge.energy_transfer.can_transmit() {
-                                        -(bridge.energy_capacity.min(neighbor_capacity))
-                                    } else {
-                                        0.0
-                                    },
-                                    if bridge.energy_transfer.can_receive() {
-                                        bridge.energy_capacity.min(self_capacity)
-                                    } else {
-                                        0.0
-                                    },
-                                ));
+                    if let Neighbor::Tile(neighbor) = neighbors.get(dir) {
+                        if let State::Occupied(plant) = &neighbor.plant {
+                            if plant.alive && plant.bridges.get(&dir.opposite()).is_some() {
+                                return Some(
+                                    (map_settings.water.diffusion
+                                        * (neighbor.data.water - tile.water))
+                                        .clamp(-bridge.water_capacity, bridge.water_capacity),
+                                );
                             }
                         }
                     }
@@ -171,6 +595,32 @@ impl Plant {
                 return None;
             })
             .sum::<f64>();
+        let usage = self.bulk.get_water_usage(map_settings, tile, neighbors);
+        let uptake = self.bulk.get_water_uptake(map_settings, tile);
+
+        return (tile.water * (1.0 - map_settings.water.evaporation) + transfer + uptake - usage)
+            .max(0.0);
+    }
+
+    /// Gets the nutrients extracted from the tile of this plant this step, a
+    /// living plant rooted in the ground extracts up to the extraction rate of
+    /// its bulk
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The general map settings
+    ///
+    /// tile: The data of the tile this plant is located on
+    fn get_nutrient_extraction(&self, map_settings: &Settings, tile: &TileData) -> f64 {
+        if !self.alive || !tile.ground {
+            return 0.0;
+        }
+
+        return self
+            .bulk
+            .get_nutrient_extraction(map_settings)
+            .min(tile.nutrients)
+            .max(0.0);
     }
 
     /// Forwards the state of this plant to the next simulation step
@@ -182,22 +632,29 @@ impl Plant {
     /// tile: The tile data for the tile of this plant
     ///
     /// neighbors: References to all the neighbors of this tile
+    ///
+    /// balance: The energy balance of the step to add the changes of this
+    /// plant to
     fn forward(
         &self,
         map_settings: &Settings,
         tile: &TileData,
         neighbors: &TileNeighbors,
+        balance: &mut EnergyBalance,
     ) -> Option<Self> {
         // Kill it if it was assigned to die
         if !self.alive {
+            balance.died += self.get_stored_energy();
             return None;
         }
 
         // Setup initial bridges
         let mut bridges = self.bridges.clone();
 
-        // Remove unused bridges
+        // Remove unused bridges and upgrade the branches the other end has
+        // upgraded
         Self::remove_bridges(&mut bridges, neighbors);
+        Self::follow_upgrades(&mut bridges, neighbors);
 
         // Handle ongoing spreading
         let (spread, energy) = match &self.spread {
@@ -210,32 +667,196 @@ impl Plant {
         };
 
         // Calculate all changes in energy
-        let cost_energy = self.get_energy_cost_run(map_settings);
-        let gain_energy = self.get_energy_gain(map_settings, tile, neighbors);
-        let transfer_energy = self.get_energy_transfer(neighbors);
+        let cost_energy = self.get_energy_cost_run(map_settings)
+            * map_settings
+                .temperature
+                .get_cost_multiplier(tile.temperature);
+        let gain_energy = self.get_energy_gain(map_settings, tile, neighbors)
+            + map_settings.nutrients.energy * self.get_nutrient_extraction(map_settings, tile);
+        let transfers = self.get_energy_transfers(neighbors);
+        let transfer_energy = transfers.iter().sum::<f64>();
+        NeighborDirection::collection()
+            .iter()
+            .zip(transfers.iter())
+            .for_each(|(dir, transfer)| {
+                if let Some(bridge) = bridges.get_mut(dir) {
+                    bridge.throughput += transfer.abs();
+                }
+            });
 
         // Get total energy
-        let new_energy =
-            (energy + gain_energy + transfer_energy - cost_energy).min(self.energy_capacity);
+        let total_energy = energy + gain_energy + transfer_energy - cost_energy;
+        let mut new_energy = total_energy.min(self.energy_capacity);
+        balance.gained += gain_energy;
+        balance.spent += cost_energy;
+        balance.wasted += total_energy - new_energy;
+        balance.transferred += transfer_energy;
+
+        // Run the program of the plant
+        let decision = self.program.run(
+            map_settings,
+            &ApplyData {
+                plant: self,
+                tile,
+                neighbors,
+                new_energy,
+            },
+        );
+
+        // Change the energy storage if the plant can pay for it
+        let (energy_capacity, energy_reserve, remodel_energy) =
+            self.get_storage_change(map_settings, &decision, new_energy);
+        new_energy -= remodel_energy;
+        balance.spent += remodel_energy;
+
+        // Upgrade or prune a bridge if the plant can pay for it
+        let bridge_energy =
+            Self::remodel_bridges(map_settings, &mut bridges, &decision, new_energy);
+        new_energy -= bridge_energy;
+        balance.spent += bridge_energy;
+
+        // Start spreading if it is not already spreading and the tile is free
+        let spread = match (spread, decision.spread) {
+            (Spread::Nothing, Some((plant, spread_energy, dir))) => {
+                let free = if let Neighbor::Tile(tile) = neighbors.get(&dir) {
+                    matches!(tile.plant, State::Nothing)
+                } else {
+                    false
+                };
+                if free {
+                    let spread_energy = spread_energy.min(new_energy.max(0.0));
+                    new_energy -= spread_energy;
+                    Spread::Trying(Box::new((plant, spread_energy, dir)))
+                } else {
+                    Spread::Nothing
+                }
+            }
+            (spread, _) => spread,
+        };
 
-        // Check if it is still alive
-        let new_alive = bridges.iter().any(|bridge| !bridge.exiting) && new_energy >= 0.0;
+        // A full seed ripens and detaches from its mother while a ripe seed
+        // lies dormant when it has landed and turns into the rooted sugar
+        // bulb of a new plant when it decides to start growing or has waited
+        // for the full dormancy, a fertilized flower turns into a seed of the
+        // next generation
+        let (bulk, root) = match &self.bulk {
+            Bulk::Seed(_)
+                if matches!(spread, Spread::Nothing) && new_energy >= self.energy_capacity =>
+            {
+                bridges = BridgeSet::new();
+                (Bulk::RipeSeed(bulk::RipeSeed::new()), self.root)
+            }
+            Bulk::RipeSeed(seed)
+                if decision.grow || seed.is_dormant() || self.is_landed(tile, neighbors) =>
+            {
+                match seed.wait(map_settings) {
+                    Some(seed) if !decision.grow => (Bulk::RipeSeed(seed), self.root),
+                    _ => (Bulk::SugarBulb(bulk::SugarBulb {}), true),
+                }
+            }
+            Bulk::Flower(flower) if flower.get_offspring().is_some() => {
+                (Bulk::Seed(bulk::Seed {}), self.root)
+            }
+            bulk => (bulk.clone(), self.root),
+        };
+
+        // The seed of a fertilized flower carries the mixed genome and starts a
+        // new lineage which is registered by the map
+        let offspring = match &self.bulk {
+            Bulk::Flower(flower) => flower.get_offspring(),
+            _ => None,
+        };
+        let (program, cum_age, lineage, parent_lineage) = match offspring {
+            Some(offspring) => (offspring.clone(), 0, None, self.lineage),
+            None => (
+                self.program.clone(),
+                self.cum_age + 1,
+                self.lineage,
+                self.parent_lineage,
+            ),
+        };
+
+        // Check if it is still alive, all plant tiles except roots and ripe
+        // seeds need a mother
+        let supported = root
+            || matches!(bulk, Bulk::RipeSeed(_))
+            || bridges.iter().any(|bridge| !bridge.exiting);
+        let new_alive = !decision.kill && supported && new_energy >= 0.0;
 
         // Construct the new plant
         return Some(Self {
-            bulk: self.bulk.clone(),
+            bulk,
             bridges,
             age: self.age + 1,
-            cum_age: self.cum_age + 1,
+            cum_age,
             alive: new_alive,
+            root,
             energy: new_energy,
-            energy_capacity: self.energy_capacity,
-            energy_reserve: self.energy_reserve,
+            energy_capacity,
+            energy_reserve,
+            transfers,
             spread,
+            program,
+            lineage,
+            parent_lineage,
         });
     }
 
-    /// Removes any bridge connected to a tile which is not occupied with an alive plant
+    /// Gets the energy capacity and reserve after the changes decided on by
+    /// the program together with the energy they cost. The capacity only
+    /// grows within the limits of the settings and pays for the extra storage
+    /// of its bulk, the reserve pays for the distance it is moved. A change
+    /// which cannot be paid for with the energy of the plant is skipped
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The settings for the map
+    ///
+    /// decision: The decision of the program of the plant
+    ///
+    /// energy: The energy available to pay with
+    fn get_storage_change(
+        &self,
+        map_settings: &Settings,
+        decision: &Decision,
+        energy: f64,
+    ) -> (f64, f64, f64) {
+        let settings = &map_settings.remodel;
+        let mut energy_capacity = self.energy_capacity;
+        let mut energy_reserve = self.energy_reserve;
+        let mut cost = 0.0;
+
+        if let Some(growth) = decision.capacity_growth {
+            let new_capacity = (self.energy_capacity + growth.min(settings.capacity_growth))
+                .min(settings.capacity_max)
+                .max(self.energy_capacity);
+            let growth_cost = self
+                .bulk
+                .get_energy_cost_storage_energy(map_settings, new_capacity)
+                - self
+                    .bulk
+                    .get_energy_cost_storage_energy(map_settings, self.energy_capacity);
+            if growth_cost <= energy - cost {
+                energy_capacity = new_capacity;
+                cost += growth_cost;
+            }
+        }
+
+        if let Some(fraction) = decision.reserve {
+            let new_reserve = fraction * energy_capacity;
+            let reserve_cost = settings.reserve_cost * (new_reserve - self.energy_reserve).abs();
+            if reserve_cost <= energy - cost {
+                energy_reserve = new_reserve;
+                cost += reserve_cost;
+            }
+        }
+
+        return (energy_capacity, energy_reserve, cost);
+    }
+
+    /// Removes any bridge connected to a tile which is not occupied with an
+    /// alive plant or where the plant does not have a bridge back, like a
+    /// seed which has detached
     ///
     /// # Parameters
     ///
@@ -246,7 +867,7 @@ impl Plant {
         NeighborDirection::collection().iter().for_each(|dir| {
             if let Neighbor::Tile(tile) = neighbors.get(dir) {
                 if let State::Occupied(plant) = &tile.plant {
-                    if plant.alive {
+                    if plant.alive && plant.bridges.get(&dir.opposite()).is_some() {
                         return;
                     }
                 }
@@ -256,14 +877,123 @@ impl Plant {
         });
     }
 
+    /// Converts every branch into a log if the bridge back from the other end
+    /// is a log, such that both ends agree the step after one end has been
+    /// upgraded
+    ///
+    /// # Parameters
+    ///
+    /// bridges: The bridges to modify
+    ///
+    /// neighbors: All of the neighboring tiles
+    fn follow_upgrades(bridges: &mut BridgeSet, neighbors: &TileNeighbors) {
+        NeighborDirection::collection().iter().for_each(|dir| {
+            if let (Some(bridge), Neighbor::Tile(tile)) = (bridges.get_mut(dir), neighbors.get(dir))
+            {
+                if let State::Occupied(plant) = &tile.plant {
+                    if let Some(other) = plant.bridges.get(&dir.opposite()) {
+                        if let (BridgeType::Branch(_), BridgeType::Log(_)) =
+                            (&bridge.bridge, &other.bridge)
+                        {
+                            bridge.bridge = other.bridge.clone();
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    /// Upgrades and prunes the bridges decided on by the program and returns
+    /// the energy spent. A branch is converted into a log by paying the
+    /// difference in build cost, it is skipped if the plant cannot pay for
+    /// it. Pruning is free and the other end removes its bridge in the next
+    /// step
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The settings for the map
+    ///
+    /// bridges: The bridges to modify
+    ///
+    /// decision: The decision of the program of the plant
+    ///
+    /// energy: The energy available to pay with
+    fn remodel_bridges(
+        map_settings: &Settings,
+        bridges: &mut BridgeSet,
+        decision: &Decision,
+        energy: f64,
+    ) -> f64 {
+        if let Some(dir) = decision.prune {
+            *bridges.get_mut(&dir) = None;
+        }
+
+        let Some(dir) = decision.upgrade else {
+            return 0.0;
+        };
+        let Some(bridge) = bridges.get_mut(&dir) else {
+            return 0.0;
+        };
+        if !matches!(bridge.bridge, BridgeType::Branch(_)) {
+            return 0.0;
+        }
+
+        let upgraded = Bridge {
+            bridge: BridgeType::Log(bridge::Log {}),
+            ..bridge.clone()
+        };
+        let cost = (upgraded.get_energy_cost_build(map_settings)
+            - bridge.get_energy_cost_build(map_settings))
+        .max(0.0);
+        if cost > energy {
+            return 0.0;
+        }
+        *bridge = upgraded;
+
+        return cost;
+    }
+
+    /// Checks if this is a ripe seed which is free to fall, it must not have
+    /// landed, be connected to any other plant tile or be in the middle of
+    /// spreading
+    fn is_falling(&self) -> bool {
+        return self.alive
+            && matches!(&self.bulk, Bulk::RipeSeed(seed) if !seed.is_dormant())
+            && self.bridges.count() == 0
+            && matches!(self.spread, Spread::Nothing);
+    }
+
+    /// Checks if a falling plant has landed, either by reaching the ground or
+    /// by having no free tile below it
+    ///
+    /// # Parameters
+    ///
+    /// tile: The data of the tile this plant is located on
+    ///
+    /// neighbors: All neighbor tiles to this tile
+    fn is_landed(&self, tile: &TileData, neighbors: &TileNeighbors) -> bool {
+        if tile.ground {
+            return true;
+        }
+
+        return [&neighbors.down_left, &neighbors.down_right].iter().all(
+            |neighbor| match neighbor {
+                Neighbor::Tile(tile) => !matches!(tile.plant, State::Nothing),
+                Neighbor::Empty | Neighbor::SunTile(_) => true,
+            },
+        );
+    }
+
     /// Resolves a spread action after waiting, returning the new energy of this
-    /// plant and sets the new bridge if it is spreading
+    /// plant and sets the new bridge if it is spreading. The energy used is
+    /// handed over to the new plant tile whenever it is being built, even if
+    /// it has no bridge back to this plant
     ///
     /// # Parameters
     ///
     /// bridges: The bridges for the plant after removing dead connections
     ///
-    /// direction: The direction to spread in
+    /// direction: The direction to spread in as seen from this plant
     ///
     /// energy: The energy used to spread
     ///
@@ -279,23 +1009,70 @@ impl Plant {
     ) -> f64 {
         if let Neighbor::Tile(tile) = neighbors.get(direction) {
             if let State::Building((plant, _, build_dir)) = &tile.plant {
-                if build_dir == direction {
+                if *build_dir == direction.opposite() {
                     if let Some(bridge) = plant.bridges.get(&direction.opposite()).as_ref() {
                         *bridges.get_mut(direction) = Some(bridge.get_opposite());
-                        return self_energy;
                     }
+                    return self_energy;
                 }
             }
         }
         return self_energy + energy;
     }
 
-    /// Returns a mutated version of itself
+    /// Returns a mutated version of itself, only seeds mutate since they
+    /// start a new plant while all other offspring are part of the same plant
     ///
     /// # Parameters
     ///
     /// map_settings: The settings for the map
-    fn mutate(&self, _map_settings: &Settings) -> Self {
-        return self.clone();
+    ///
+    /// rng: The random number generator to draw the mutations from
+    fn mutate(&self, map_settings: &Settings, rng: &mut StdRng) -> Self {
+        if !matches!(self.bulk, Bulk::Seed(_)) {
+            return self.clone();
+        }
+
+        let jitter = map_settings.mutation.parameter_jitter;
+        let mut bridges = self.bridges.clone();
+        NeighborDirection::collection().iter().for_each(|dir| {
+            if let Some(bridge) = bridges.get_mut(dir) {
+                *bridge = bridge.mutate(jitter, rng);
+            }
+        });
+
+        return Self {
+            bulk: self.bulk.mutate(jitter, rng),
+            bridges,
+            age: self.age,
+            cum_age: self.cum_age,
+            alive: self.alive,
+            root: self.root,
+            energy: self.energy,
+            energy_capacity: apply_jitter(self.energy_capacity, jitter, rng),
+            energy_reserve: apply_jitter(self.energy_reserve, jitter, rng),
+            transfers: self.transfers,
+            spread: self.spread.clone(),
+            program: self.program.mutate(map_settings, rng),
+            lineage: self.lineage,
+            parent_lineage: self.parent_lineage,
+        };
     }
 }
+
+/// Randomly scales a non-negative parameter by up to the given relative amount
+///
+/// # Parameters
+///
+/// value: The value to scale
+///
+/// jitter: The maximum relative change
+///
+/// rng: The random number generator to draw from
+fn apply_jitter(value: f64, jitter: f64, rng: &mut StdRng) -> f64 {
+    if jitter <= 0.0 {
+        return value;
+    }
+
+    return (value * (1.0 + rng.random_range(-jitter..=jitter))).max(0.0);
+}
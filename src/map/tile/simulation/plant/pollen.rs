@@ -0,0 +1,12 @@
+use super::Program;
+
+/// A grain of pollen emitted by a flower, it carries the genome of its plant
+/// to be mixed with the genome of the flower it fertilizes
+#[derive(Clone, Debug)]
+pub struct Pollen {
+    /// The genome of the plant which emitted the pollen
+    pub(super) program: Program,
+    /// The id of the lineage of the plant which emitted the pollen, flowers
+    /// of the same lineage are not fertilized
+    pub(super) lineage: Option<usize>,
+}
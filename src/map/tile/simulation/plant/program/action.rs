@@ -1,7 +1,9 @@
-use super::{ApplyData, Arithmetic, NeighborDirection, Plant, TileData, TileNeighbors};
+use super::{
+    ApplyData, Arithmetic, BridgeSet, Decision, Logic, NeighborDirection, Plant, Spread, bulk::Bulk,
+};
 
 /// Plant action logic to handle spreading and internal production management
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Action {
     /// Do nothing
     None,
@@ -16,24 +18,79 @@ pub enum Action {
     /// Attempts to spread the plant defined by bulk of .0 and bridge of .1 to
     /// the tile in the direction of .2
     Spread(usize, usize, NeighborDirection),
-    /// Only applicable if plant type is a grounded RipeSeed, starts the initial
-    /// growing process
+    /// Only applicable if plant type is a RipeSeed, stops falling or lying
+    /// dormant and starts the initial growing process where it is, a landed
+    /// seed starts growing by itself after the dormancy duration
     Grow,
+    /// Moves the energy reserve to the fraction .0 of the energy capacity,
+    /// paying for the distance moved
+    SetReserve(usize),
+    /// Grows the energy capacity by .0, paying for the extra storage
+    GrowCapacity(usize),
+    /// Converts the branch in the direction of .0 into a log, paying the
+    /// difference in build cost, the other end follows in the next step
+    UpgradeBridge(NeighborDirection),
+    /// Removes the bridge in the direction of .0, the other end is removed
+    /// in the next step
+    PruneBridge(NeighborDirection),
 }
 
 impl Action {
-    /// The number of different logic operators
-    pub const COUNT: usize = 1;
+    /// The number of different action operators
+    pub const COUNT: usize = 11;
 
     /// Gets a unique id for this specific action type smaller than COUNT
     pub fn get_id(&self) -> usize {
-        todo!()
+        return match self {
+            Self::None => 0,
+            Self::If(_, _) => 1,
+            Self::IfElse(_, _, _) => 2,
+            Self::Both(_, _) => 3,
+            Self::Kill => 4,
+            Self::Spread(_, _, _) => 5,
+            Self::Grow => 6,
+            Self::SetReserve(_) => 7,
+            Self::GrowCapacity(_) => 8,
+            Self::UpgradeBridge(_) => 9,
+            Self::PruneBridge(_) => 10,
+        };
     }
 
     /// Gets the three indices used in the action or if less are used then the
     /// value of the rest is 0
-    pub fn get_indices(&self) -> (usize, usize) {
-        todo!()
+    pub fn get_indices(&self) -> (usize, usize, usize) {
+        return match self {
+            &Self::None => (0, 0, 0),
+            &Self::If(index1, index2) => (index1, index2, 0),
+            &Self::IfElse(index1, index2, index3) => (index1, index2, index3),
+            &Self::Both(index1, index2) => (index1, index2, 0),
+            &Self::Kill => (0, 0, 0),
+            &Self::Spread(index1, index2, dir) => (index1, index2, dir.index()),
+            &Self::Grow => (0, 0, 0),
+            &Self::SetReserve(index) => (index, 0, 0),
+            &Self::GrowCapacity(index) => (index, 0, 0),
+            &Self::UpgradeBridge(dir) => (dir.index(), 0, 0),
+            &Self::PruneBridge(dir) => (dir.index(), 0, 0),
+        };
+    }
+
+    /// Gets the number of different values each of the three indices of an
+    /// action type can take, an index holding a direction takes one of the
+    /// directions and any other index one of index_count
+    ///
+    /// # Parameters
+    ///
+    /// id: The unique id for the action type
+    ///
+    /// index_count: The number of values of an index which is not a direction
+    pub fn get_index_counts(id: usize, index_count: usize) -> (usize, usize, usize) {
+        return match Self::from_id(id, (0, 0, 0)) {
+            Self::Spread(_, _, _) => (index_count, index_count, NeighborDirection::COUNT),
+            Self::UpgradeBridge(_) | Self::PruneBridge(_) => {
+                (NeighborDirection::COUNT, index_count, index_count)
+            }
+            _ => (index_count, index_count, index_count),
+        };
     }
 
     /// Constructs a new action from its unique type id and the three indices,
@@ -45,18 +102,183 @@ impl Action {
     ///
     /// indices: The three indices used to get the values to operate on
     pub fn from_id(id: usize, indices: (usize, usize, usize)) -> Self {
-        todo!()
+        return match id {
+            0 => Self::None,
+            1 => Self::If(indices.0, indices.1),
+            2 => Self::IfElse(indices.0, indices.1, indices.2),
+            3 => Self::Both(indices.0, indices.1),
+            4 => Self::Kill,
+            5 => Self::Spread(
+                indices.0,
+                indices.1,
+                NeighborDirection::from_index(indices.2),
+            ),
+            6 => Self::Grow,
+            7 => Self::SetReserve(indices.0),
+            8 => Self::GrowCapacity(indices.0),
+            9 => Self::UpgradeBridge(NeighborDirection::from_index(indices.0)),
+            10 => Self::PruneBridge(NeighborDirection::from_index(indices.0)),
+            _ => Self::None,
+        };
+    }
+
+    /// Applies the action operator, the first spread, the first change of
+    /// the reserve and capacity and the first bridge to upgrade and to prune
+    /// decided on are kept
+    ///
+    /// # Parameters
+    ///
+    /// data: All data required for the apply operation
+    ///
+    /// remaining count: The remaining number of operators to evaluate before
+    /// returning default values
+    ///
+    /// decision: The decision to update with the result of the action
+    pub fn apply(&self, data: &ApplyData, remain_count: &mut usize, decision: &mut Decision) {
+        if *remain_count == 0 {
+            return;
+        }
+        *remain_count -= 1;
+
+        match self {
+            &Self::None => (),
+            &Self::If(condition, action) => {
+                if Logic::apply_index(data, condition, remain_count) {
+                    Self::apply_index(data, action, remain_count, decision);
+                }
+            }
+            &Self::IfElse(condition, action_true, action_false) => {
+                if Logic::apply_index(data, condition, remain_count) {
+                    Self::apply_index(data, action_true, remain_count, decision);
+                } else {
+                    Self::apply_index(data, action_false, remain_count, decision);
+                }
+            }
+            &Self::Both(action1, action2) => {
+                Self::apply_index(data, action1, remain_count, decision);
+                Self::apply_index(data, action2, remain_count, decision);
+            }
+            &Self::Kill => decision.kill = true,
+            &Self::Spread(bulk, bridge, dir) => {
+                if decision.spread.is_none() {
+                    decision.spread = Self::get_offspring(data, bulk, bridge, dir, remain_count);
+                }
+            }
+            &Self::Grow => {
+                if let Bulk::RipeSeed(_) = data.plant.bulk {
+                    decision.grow = true;
+                }
+            }
+            &Self::SetReserve(fraction) => {
+                if decision.reserve.is_none() {
+                    let fraction =
+                        Arithmetic::apply_index(data, fraction, remain_count).clamp(0.0, 1.0);
+                    decision.reserve = Some(fraction);
+                }
+            }
+            &Self::GrowCapacity(growth) => {
+                if decision.capacity_growth.is_none() {
+                    let growth = Arithmetic::apply_index(data, growth, remain_count).max(0.0);
+                    decision.capacity_growth = Some(growth);
+                }
+            }
+            &Self::UpgradeBridge(dir) => {
+                if decision.upgrade.is_none() {
+                    decision.upgrade = Some(dir);
+                }
+            }
+            &Self::PruneBridge(dir) => {
+                if decision.prune.is_none() {
+                    decision.prune = Some(dir);
+                }
+            }
+        }
     }
 
-    /// Applies the action operator
+    /// Applies the action operator at the given index of the program of the
+    /// plant, does nothing if there is no operator at the index
     ///
     /// # Parameters
     ///
     /// data: All data required for the apply operation
     ///
+    /// index: The index of the operator
+    ///
     /// remaining count: The remaining number of operators to evaluate before
     /// returning default values
-    pub fn apply(&self, data: &ApplyData, remain_count: &mut usize) -> bool {
-        todo!()
+    ///
+    /// decision: The decision to update with the result of the action
+    pub fn apply_index(
+        data: &ApplyData,
+        index: usize,
+        remain_count: &mut usize,
+        decision: &mut Decision,
+    ) {
+        if let Some(operator) = data.plant.program.action.get(index) {
+            operator.apply(data, remain_count, decision);
+        }
+    }
+
+    /// Constructs the offspring for a spread action, returns None if the
+    /// spread operators do not exist
+    ///
+    /// # Parameters
+    ///
+    /// data: All data required for the apply operation
+    ///
+    /// bulk: The index of the spread bulk operator
+    ///
+    /// bridge: The index of the spread bridge operator
+    ///
+    /// dir: The direction to spread in
+    ///
+    /// remaining count: The remaining number of operators to evaluate before
+    /// returning default values
+    fn get_offspring(
+        data: &ApplyData,
+        bulk: usize,
+        bridge: usize,
+        dir: NeighborDirection,
+        remain_count: &mut usize,
+    ) -> Option<(Plant, f64, NeighborDirection)> {
+        let program = &data.plant.program;
+        let (bulk, energy_capacity, energy_reserve, energy) =
+            program.spread_bulk.get(bulk)?.apply(data, remain_count);
+        let bridge = program.spread_bridge.get(bridge)?.apply(data, remain_count);
+
+        // The offspring connects back to this plant
+        let mut bridges = BridgeSet::new();
+        *bridges.get_mut(&dir.opposite()) = Some(bridge.get_opposite());
+
+        // Seeds start a new plant and a new lineage which is registered by
+        // the map once the seed has been built
+        let (cum_age, lineage, parent_lineage) = if let Bulk::Seed(_) = bulk {
+            (0, None, data.plant.lineage)
+        } else {
+            (
+                data.plant.cum_age,
+                data.plant.lineage,
+                data.plant.parent_lineage,
+            )
+        };
+
+        let plant = Plant {
+            bulk,
+            bridges,
+            age: 0,
+            cum_age,
+            alive: true,
+            root: false,
+            energy: 0.0,
+            energy_capacity,
+            energy_reserve,
+            transfers: [0.0; 6],
+            spread: Spread::Nothing,
+            program: program.clone(),
+            lineage,
+            parent_lineage,
+        };
+
+        return Some((plant, energy, dir));
     }
 }
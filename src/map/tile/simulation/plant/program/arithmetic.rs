@@ -1,7 +1,7 @@
-use super::{ApplyData, NeighborDirection, Plant, TileData, TileNeighbors};
+use super::{ApplyData, Neighbor, NeighborDirection, Plant};
 
 /// Plant action logic to calculate float operations
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Arithmetic {
     /// Always has the value 0.0
     Zero,
@@ -69,11 +69,17 @@ pub enum Arithmetic {
     PlantEnergyShare,
     /// Gets the change in shared energy since the last simulation step
     PlantEnergyShareChange,
+    /// Gets the water level of this tile
+    TileWater,
+    /// Gets the gradient of the water level in the specified direction
+    TileWaterGradient(NeighborDirection),
+    /// Gets the nutrient concentration of this tile
+    TileNutrients,
 }
 
 impl Arithmetic {
     /// The number of different arithmetic operators
-    pub const COUNT: usize = 33;
+    pub const COUNT: usize = 36;
 
     /// Gets a unique id for this specific arithmetic operator type smaller than
     /// COUNT
@@ -112,6 +118,9 @@ impl Arithmetic {
             Self::PlantEnergySelfChange => 30,
             Self::PlantEnergyShare => 31,
             Self::PlantEnergyShareChange => 32,
+            Self::TileWater => 33,
+            Self::TileWaterGradient(_) => 34,
+            Self::TileNutrients => 35,
         };
     }
 
@@ -139,29 +148,9 @@ impl Arithmetic {
             &Self::MaxOne(index) => (index, 0),
             &Self::Mean(index1, index2) => (index1, index2),
             &Self::TileLight => (0, 0),
-            &Self::TileLightGradient(dir) => (
-                match dir {
-                    NeighborDirection::Right => 0,
-                    NeighborDirection::UpRight => 1,
-                    NeighborDirection::UpLeft => 2,
-                    NeighborDirection::Left => 3,
-                    NeighborDirection::DownLeft => 4,
-                    NeighborDirection::DownRight => 5,
-                },
-                0,
-            ),
+            &Self::TileLightGradient(dir) => (dir.index(), 0),
             &Self::TileTransparency => (0, 0),
-            &Self::TileTransparencyGradient(dir) => (
-                match dir {
-                    NeighborDirection::Right => 0,
-                    NeighborDirection::UpRight => 1,
-                    NeighborDirection::UpLeft => 2,
-                    NeighborDirection::Left => 3,
-                    NeighborDirection::DownLeft => 4,
-                    NeighborDirection::DownRight => 5,
-                },
-                0,
-            ),
+            &Self::TileTransparencyGradient(dir) => (dir.index(), 0),
             &Self::PlantAge => (0, 0),
             &Self::PlantCumAge => (0, 0),
             &Self::PlantEnergyCapacity => (0, 0),
@@ -172,6 +161,27 @@ impl Arithmetic {
             &Self::PlantEnergySelfChange => (0, 0),
             &Self::PlantEnergyShare => (0, 0),
             &Self::PlantEnergyShareChange => (0, 0),
+            &Self::TileWater => (0, 0),
+            &Self::TileWaterGradient(dir) => (dir.index(), 0),
+            &Self::TileNutrients => (0, 0),
+        };
+    }
+
+    /// Gets the number of different values each of the two indices of an
+    /// arithmetic operator type can take, an index holding a direction takes
+    /// one of the directions and any other index one of index_count
+    ///
+    /// # Parameters
+    ///
+    /// id: The unique id for the operator type
+    ///
+    /// index_count: The number of values of an index which is not a direction
+    pub fn get_index_counts(id: usize, index_count: usize) -> (usize, usize) {
+        return match Self::from_id(id, (0, 0)) {
+            Self::TileLightGradient(_)
+            | Self::TileTransparencyGradient(_)
+            | Self::TileWaterGradient(_) => (NeighborDirection::COUNT, index_count),
+            _ => (index_count, index_count),
         };
     }
 
@@ -205,23 +215,9 @@ impl Arithmetic {
             17 => Self::MaxOne(indices.0),
             18 => Self::Mean(indices.0, indices.1),
             19 => Self::TileLight,
-            20 => Self::TileLightGradient(match indices.0 {
-                0 => NeighborDirection::Right,
-                1 => NeighborDirection::UpRight,
-                2 => NeighborDirection::UpLeft,
-                3 => NeighborDirection::Left,
-                4 => NeighborDirection::DownLeft,
-                _ => NeighborDirection::DownRight,
-            }),
+            20 => Self::TileLightGradient(NeighborDirection::from_index(indices.0)),
             21 => Self::TileTransparency,
-            22 => Self::TileTransparencyGradient(match indices.0 {
-                0 => NeighborDirection::Right,
-                1 => NeighborDirection::UpRight,
-                2 => NeighborDirection::UpLeft,
-                3 => NeighborDirection::Left,
-                4 => NeighborDirection::DownLeft,
-                _ => NeighborDirection::DownRight,
-            }),
+            22 => Self::TileTransparencyGradient(NeighborDirection::from_index(indices.0)),
             23 => Self::PlantAge,
             24 => Self::PlantCumAge,
             25 => Self::PlantEnergyCapacity,
@@ -232,19 +228,143 @@ impl Arithmetic {
             30 => Self::PlantEnergySelfChange,
             31 => Self::PlantEnergyShare,
             32 => Self::PlantEnergyShareChange,
+            33 => Self::TileWater,
+            34 => Self::TileWaterGradient(NeighborDirection::from_index(indices.0)),
+            35 => Self::TileNutrients,
             _ => Self::Zero,
         };
     }
 
-    /// Applies the arithmetic operator
+    /// Applies the arithmetic operator, any result which is not finite is
+    /// replaced by 0.0
+    ///
+    /// # Parameters
+    ///
+    /// data: All data required for the apply operation
+    ///
+    /// remaining count: The remaining number of operators to evaluate before
+    /// returning default values
+    pub fn apply(&self, data: &ApplyData, remain_count: &mut usize) -> f64 {
+        if *remain_count == 0 {
+            return 0.0;
+        }
+        *remain_count -= 1;
+
+        let plant = data.plant;
+        let value = match self {
+            &Self::Zero => 0.0,
+            &Self::One => 1.0,
+            &Self::Double(index) => 2.0 * Self::apply_index(data, index, remain_count),
+            &Self::Half(index) => 0.5 * Self::apply_index(data, index, remain_count),
+            &Self::Increment(index) => Self::apply_index(data, index, remain_count) + 1.0,
+            &Self::Decrement(index) => Self::apply_index(data, index, remain_count) - 1.0,
+            &Self::Add(index1, index2) => {
+                Self::apply_index(data, index1, remain_count)
+                    + Self::apply_index(data, index2, remain_count)
+            }
+            &Self::Sub(index1, index2) => {
+                Self::apply_index(data, index1, remain_count)
+                    - Self::apply_index(data, index2, remain_count)
+            }
+            &Self::Mul(index1, index2) => {
+                Self::apply_index(data, index1, remain_count)
+                    * Self::apply_index(data, index2, remain_count)
+            }
+            &Self::Div(index1, index2) => {
+                Self::apply_index(data, index1, remain_count)
+                    / Self::apply_index(data, index2, remain_count)
+            }
+            &Self::Mod(index1, index2) => Self::apply_index(data, index1, remain_count)
+                .rem_euclid(Self::apply_index(data, index2, remain_count)),
+            &Self::Neg(index) => -Self::apply_index(data, index, remain_count),
+            &Self::Min(index1, index2) => Self::apply_index(data, index1, remain_count)
+                .min(Self::apply_index(data, index2, remain_count)),
+            &Self::MinZero(index) => Self::apply_index(data, index, remain_count).min(0.0),
+            &Self::MinOne(index) => Self::apply_index(data, index, remain_count).min(1.0),
+            &Self::Max(index1, index2) => Self::apply_index(data, index1, remain_count)
+                .max(Self::apply_index(data, index2, remain_count)),
+            &Self::MaxZero(index) => Self::apply_index(data, index, remain_count).max(0.0),
+            &Self::MaxOne(index) => Self::apply_index(data, index, remain_count).max(1.0),
+            &Self::Mean(index1, index2) => {
+                0.5 * (Self::apply_index(data, index1, remain_count)
+                    + Self::apply_index(data, index2, remain_count))
+            }
+            &Self::TileLight => data.tile.light,
+            &Self::TileLightGradient(dir) => match data.neighbors.get(&dir) {
+                Neighbor::Empty => 0.0,
+                Neighbor::Tile(tile) => tile.data.light - data.tile.light,
+                Neighbor::SunTile(tile) => tile.intensity - data.tile.light,
+            },
+            &Self::TileTransparency => data.tile.transparency,
+            &Self::TileTransparencyGradient(dir) => match data.neighbors.get(&dir) {
+                Neighbor::Empty => 0.0,
+                Neighbor::Tile(tile) => tile.data.transparency - data.tile.transparency,
+                Neighbor::SunTile(_) => 1.0 - data.tile.transparency,
+            },
+            &Self::PlantAge => plant.age as f64,
+            &Self::PlantCumAge => plant.cum_age as f64,
+            &Self::PlantEnergyCapacity => plant.energy_capacity,
+            &Self::PlantEnergyReserve => plant.energy_reserve,
+            &Self::PlantEnergy => data.new_energy,
+            &Self::PlantEnergyChange => data.new_energy - plant.energy,
+            &Self::PlantEnergySelf => Self::get_energy_self(plant, data.new_energy),
+            &Self::PlantEnergySelfChange => {
+                Self::get_energy_self(plant, data.new_energy)
+                    - Self::get_energy_self(plant, plant.energy)
+            }
+            &Self::PlantEnergyShare => Self::get_energy_share(plant, data.new_energy),
+            &Self::PlantEnergyShareChange => {
+                Self::get_energy_share(plant, data.new_energy)
+                    - Self::get_energy_share(plant, plant.energy)
+            }
+            &Self::TileWater => data.tile.water,
+            &Self::TileWaterGradient(dir) => match data.neighbors.get(&dir) {
+                Neighbor::Tile(tile) => tile.data.water - data.tile.water,
+                Neighbor::Empty | Neighbor::SunTile(_) => 0.0,
+            },
+            &Self::TileNutrients => data.tile.nutrients,
+        };
+
+        return if value.is_finite() { value } else { 0.0 };
+    }
+
+    /// Applies the arithmetic operator at the given index of the program of
+    /// the plant, returns 0.0 if there is no operator at the index
     ///
     /// # Parameters
     ///
     /// data: All data required for the apply operation
     ///
+    /// index: The index of the operator
+    ///
     /// remaining count: The remaining number of operators to evaluate before
     /// returning default values
-    pub fn apply(&self, data: &ApplyData, remain_count: &mut usize) -> bool {
-        todo!()
+    pub fn apply_index(data: &ApplyData, index: usize, remain_count: &mut usize) -> f64 {
+        return match data.plant.program.arithmetic.get(index) {
+            Some(operator) => operator.apply(data, remain_count),
+            None => 0.0,
+        };
+    }
+
+    /// Gets the energy the plant reserves for itself
+    ///
+    /// # Parameters
+    ///
+    /// plant: The plant to get the energy for
+    ///
+    /// energy: The energy of the plant
+    fn get_energy_self(plant: &Plant, energy: f64) -> f64 {
+        return energy.min(plant.energy_reserve);
+    }
+
+    /// Gets the energy the plant allocates for sharing with each neighbor
+    ///
+    /// # Parameters
+    ///
+    /// plant: The plant to get the energy for
+    ///
+    /// energy: The energy of the plant
+    fn get_energy_share(plant: &Plant, energy: f64) -> f64 {
+        return ((energy - Self::get_energy_self(plant, energy)) / 6.0).max(0.0);
     }
 }
@@ -0,0 +1,274 @@
+use thiserror::Error;
+
+use super::{Action, Arithmetic, Logic, Program, SpreadBridge, SpreadBulk};
+
+/// The names of the sections of a genome in the order they are written
+const SECTIONS: [&str; 5] = [
+    "arithmetic",
+    "logic",
+    "action",
+    "spread_bulk",
+    "spread_bridge",
+];
+
+/// An operator as read from the genome text, its type id and all indices
+type RawOperator = (usize, Vec<usize>);
+
+impl Program {
+    /// Writes the program in the genome text format, every operator list is
+    /// written on its own line as the name of the list followed by a colon
+    /// and all operators separated by spaces, each operator is written as
+    /// "id:index,index,..." where the id is the type id of the operator.
+    /// Offspring bridges are written as "bridge:energy_capacity,water_capacity,energy_transfer"
+    pub fn to_genome_string(&self) -> String {
+        let arithmetic = self
+            .arithmetic
+            .iter()
+            .map(|operator| {
+                let (index1, index2) = operator.get_indices();
+                format!("{}:{},{}", operator.get_id(), index1, index2)
+            })
+            .collect::<Vec<_>>();
+        let logic = self
+            .logic
+            .iter()
+            .map(|operator| {
+                let (index1, index2) = operator.get_indices();
+                format!("{}:{},{}", operator.get_id(), index1, index2)
+            })
+            .collect::<Vec<_>>();
+        let action = self
+            .action
+            .iter()
+            .map(|operator| {
+                let (index1, index2, index3) = operator.get_indices();
+                format!("{}:{},{},{}", operator.get_id(), index1, index2, index3)
+            })
+            .collect::<Vec<_>>();
+        let spread_bulk = self
+            .spread_bulk
+            .iter()
+            .map(|operator| {
+                let (index1, index2, index3, index4) = operator.get_indices();
+                format!(
+                    "{}:{},{},{},{}",
+                    operator.bulk, index1, index2, index3, index4
+                )
+            })
+            .collect::<Vec<_>>();
+        let spread_bridge = self
+            .spread_bridge
+            .iter()
+            .map(|operator| {
+                format!(
+                    "{}:{},{},{}",
+                    operator.bridge,
+                    operator.energy_capacity,
+                    operator.water_capacity,
+                    operator.energy_transfer
+                )
+            })
+            .collect::<Vec<_>>();
+
+        return SECTIONS
+            .iter()
+            .zip([arithmetic, logic, action, spread_bulk, spread_bridge])
+            .map(|(name, operators)| format!("{}: {}\n", name, operators.join(" ")))
+            .collect();
+    }
+
+    /// Reads a program from the genome text format, see
+    /// Program::to_genome_string, empty lines and lines starting with # are
+    /// ignored and sections which are left out have no operators
+    ///
+    /// # Parameters
+    ///
+    /// text: The genome text
+    ///
+    /// # Errors
+    ///
+    /// See GenomeError for a description of the different errors which may occur
+    pub fn from_genome_str(text: &str) -> Result<Self, GenomeError> {
+        let mut sections: [Option<Vec<RawOperator>>; 5] = Default::default();
+        for line in text
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        {
+            let (name, operators) = line
+                .split_once(':')
+                .ok_or_else(|| GenomeError::Line(String::from(line)))?;
+            let name = name.trim();
+            let section = SECTIONS
+                .iter()
+                .position(|section| *section == name)
+                .ok_or_else(|| GenomeError::UnknownSection(String::from(name)))?;
+            if sections[section].is_some() {
+                return Err(GenomeError::DuplicateSection(SECTIONS[section]));
+            }
+            sections[section] = Some(
+                operators
+                    .split_whitespace()
+                    .map(|operator| parse_operator(SECTIONS[section], operator))
+                    .collect::<Result<_, _>>()?,
+            );
+        }
+        let [arithmetic, logic, action, spread_bulk, spread_bridge] =
+            sections.map(|section| section.unwrap_or_default());
+
+        let arithmetic = arithmetic
+            .into_iter()
+            .map(|(id, indices)| {
+                check_operator("arithmetic", id, Arithmetic::COUNT, &indices, 2)?;
+                return Ok(Arithmetic::from_id(id, (indices[0], indices[1])));
+            })
+            .collect::<Result<_, _>>()?;
+        let logic = logic
+            .into_iter()
+            .map(|(id, indices)| {
+                check_operator("logic", id, Logic::COUNT, &indices, 2)?;
+                return Ok(Logic::from_id(id, (indices[0], indices[1])));
+            })
+            .collect::<Result<_, _>>()?;
+        let action = action
+            .into_iter()
+            .map(|(id, indices)| {
+                check_operator("action", id, Action::COUNT, &indices, 3)?;
+                return Ok(Action::from_id(id, (indices[0], indices[1], indices[2])));
+            })
+            .collect::<Result<_, _>>()?;
+        let spread_bulk = spread_bulk
+            .into_iter()
+            .map(|(id, indices)| {
+                check_operator("spread_bulk", id, SpreadBulk::BULK_COUNT, &indices, 4)?;
+                return Ok(SpreadBulk::from_id(
+                    id,
+                    (indices[0], indices[1], indices[2], indices[3]),
+                ));
+            })
+            .collect::<Result<_, _>>()?;
+        let spread_bridge = spread_bridge
+            .into_iter()
+            .map(|(id, indices)| {
+                check_operator("spread_bridge", id, SpreadBridge::BRIDGE_COUNT, &indices, 3)?;
+                check_operator(
+                    "spread_bridge",
+                    indices[2],
+                    SpreadBridge::TRANSFER_COUNT,
+                    &indices,
+                    3,
+                )?;
+                return Ok(SpreadBridge::new(id, indices[0], indices[1], indices[2]));
+            })
+            .collect::<Result<_, _>>()?;
+
+        return Ok(Self::new(
+            arithmetic,
+            logic,
+            action,
+            spread_bulk,
+            spread_bridge,
+        ));
+    }
+}
+
+/// Parses a single operator of the form "id:index,index,..." into its id and
+/// indices
+///
+/// # Parameters
+///
+/// section: The name of the section the operator is in
+///
+/// operator: The text of the operator
+fn parse_operator(section: &'static str, operator: &str) -> Result<RawOperator, GenomeError> {
+    let invalid = || GenomeError::Operator {
+        section,
+        operator: String::from(operator),
+    };
+    let (id, indices) = operator.split_once(':').ok_or_else(invalid)?;
+    let id = id.parse::<usize>().map_err(|_| invalid())?;
+    let indices = indices
+        .split(',')
+        .map(|index| index.parse::<usize>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| invalid())?;
+
+    return Ok((id, indices));
+}
+
+/// Makes sure an operator has a known type id and the right number of
+/// indices
+///
+/// # Parameters
+///
+/// section: The name of the section the operator is in
+///
+/// id: The type id to check
+///
+/// count: The number of different type ids
+///
+/// indices: The indices of the operator
+///
+/// index_count: The number of indices the operator must have
+fn check_operator(
+    section: &'static str,
+    id: usize,
+    count: usize,
+    indices: &[usize],
+    index_count: usize,
+) -> Result<(), GenomeError> {
+    if id >= count || indices.len() != index_count {
+        return Err(GenomeError::Operator {
+            section,
+            operator: format!(
+                "{}:{}",
+                id,
+                indices
+                    .iter()
+                    .map(|index| index.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+        });
+    }
+
+    return Ok(());
+}
+
+/// The error types for when reading a genome
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum GenomeError {
+    /// A line is not of the form "section: operators"
+    #[error("Expected a line of the form \"section: operators\" but received \"{}\"", .0)]
+    Line(String),
+    /// A section name is not known
+    #[error("Unknown genome section \"{}\"", .0)]
+    UnknownSection(String),
+    /// A section is given more than once
+    #[error("The genome section \"{}\" is given more than once", .0)]
+    DuplicateSection(&'static str),
+    /// An operator could not be parsed, has an unknown type id or the wrong
+    /// number of indices
+    #[error("Invalid operator \"{}\" in the genome section \"{}\"", .operator, .section)]
+    Operator {
+        /// The name of the section of the operator
+        section: &'static str,
+        /// The text of the operator
+        operator: String,
+    },
+    /// An operator refers to an index outside of the list it refers to
+    #[error(
+        "The operator {} in the genome section \"{}\" refers to the index {} outside of the genome section \"{}\"",
+        .operator, .section, .index, .target
+    )]
+    Index {
+        /// The name of the section of the operator
+        section: &'static str,
+        /// The index of the operator in its section
+        operator: usize,
+        /// The index out of range
+        index: usize,
+        /// The name of the section the index refers to
+        target: &'static str,
+    },
+}
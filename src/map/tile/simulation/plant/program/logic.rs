@@ -1,7 +1,7 @@
-use super::{ApplyData, Arithmetic, NeighborDirection, Plant, TileData, TileNeighbors};
+use super::{ApplyData, Arithmetic, Neighbor, NeighborDirection, State};
 
 /// Plant action logic to calculate boolean operations
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Logic {
     /// Always false
     False,
@@ -135,17 +135,23 @@ impl Logic {
             &Self::IsNotPositiveRound(index) => (index, 0),
             &Self::IsNegative(index) => (index, 0),
             &Self::IsNegativeRound(index) => (index, 0),
-            &Self::TileFree(dir) => (
-                match dir {
-                    NeighborDirection::Right => 0,
-                    NeighborDirection::UpRight => 1,
-                    NeighborDirection::UpLeft => 2,
-                    NeighborDirection::Left => 3,
-                    NeighborDirection::DownLeft => 4,
-                    NeighborDirection::DownRight => 5,
-                },
-                0,
-            ),
+            &Self::TileFree(dir) => (dir.index(), 0),
+        };
+    }
+
+    /// Gets the number of different values each of the two indices of a
+    /// logic operator type can take, an index holding a direction takes one
+    /// of the directions and any other index one of index_count
+    ///
+    /// # Parameters
+    ///
+    /// id: The unique id for the operator type
+    ///
+    /// index_count: The number of values of an index which is not a direction
+    pub fn get_index_counts(id: usize, index_count: usize) -> (usize, usize) {
+        return match Self::from_id(id, (0, 0)) {
+            Self::TileFree(_) => (NeighborDirection::COUNT, index_count),
+            _ => (index_count, index_count),
         };
     }
 
@@ -187,14 +193,7 @@ impl Logic {
             25 => Self::IsNotPositiveRound(indices.0),
             26 => Self::IsNegative(indices.0),
             27 => Self::IsNegativeRound(indices.0),
-            28 => Self::TileFree(match indices.0 {
-                0 => NeighborDirection::Right,
-                1 => NeighborDirection::UpRight,
-                2 => NeighborDirection::UpLeft,
-                3 => NeighborDirection::Left,
-                4 => NeighborDirection::DownLeft,
-                _ => NeighborDirection::DownRight,
-            }),
+            28 => Self::TileFree(NeighborDirection::from_index(indices.0)),
             _ => Self::False,
         };
     }
@@ -208,6 +207,98 @@ impl Logic {
     /// remaining count: The remaining number of operators to evaluate before
     /// returning default values
     pub fn apply(&self, data: &ApplyData, remain_count: &mut usize) -> bool {
-        todo!()
+        if *remain_count == 0 {
+            return false;
+        }
+        *remain_count -= 1;
+
+        let logic = |index: usize, remain_count: &mut usize| -> bool {
+            return Self::apply_index(data, index, remain_count);
+        };
+        let arithmetic = |index: usize, remain_count: &mut usize| -> f64 {
+            return Arithmetic::apply_index(data, index, remain_count);
+        };
+        let round = |index: usize, remain_count: &mut usize| -> f64 {
+            return Arithmetic::apply_index(data, index, remain_count).round();
+        };
+
+        return match self {
+            &Self::False => false,
+            &Self::True => true,
+            &Self::And(index1, index2) => {
+                logic(index1, remain_count) && logic(index2, remain_count)
+            }
+            &Self::Or(index1, index2) => logic(index1, remain_count) || logic(index2, remain_count),
+            &Self::Xor(index1, index2) => logic(index1, remain_count) ^ logic(index2, remain_count),
+            &Self::Not(index) => !logic(index, remain_count),
+            &Self::Equal(index1, index2) => {
+                arithmetic(index1, remain_count) == arithmetic(index2, remain_count)
+            }
+            &Self::EqualRound(index1, index2) => {
+                round(index1, remain_count) == round(index2, remain_count)
+            }
+            &Self::NotEqual(index1, index2) => {
+                arithmetic(index1, remain_count) != arithmetic(index2, remain_count)
+            }
+            &Self::NotEqualRound(index1, index2) => {
+                round(index1, remain_count) != round(index2, remain_count)
+            }
+            &Self::Greater(index1, index2) => {
+                arithmetic(index1, remain_count) > arithmetic(index2, remain_count)
+            }
+            &Self::GreaterRound(index1, index2) => {
+                round(index1, remain_count) > round(index2, remain_count)
+            }
+            &Self::GreaterOrEqual(index1, index2) => {
+                arithmetic(index1, remain_count) >= arithmetic(index2, remain_count)
+            }
+            &Self::GreaterOrEqualRound(index1, index2) => {
+                round(index1, remain_count) >= round(index2, remain_count)
+            }
+            &Self::Less(index1, index2) => {
+                arithmetic(index1, remain_count) < arithmetic(index2, remain_count)
+            }
+            &Self::LessRound(index1, index2) => {
+                round(index1, remain_count) < round(index2, remain_count)
+            }
+            &Self::LessOrEqual(index1, index2) => {
+                arithmetic(index1, remain_count) <= arithmetic(index2, remain_count)
+            }
+            &Self::LessOrEqualRound(index1, index2) => {
+                round(index1, remain_count) <= round(index2, remain_count)
+            }
+            &Self::IsPositive(index) => arithmetic(index, remain_count) > 0.0,
+            &Self::IsPositiveRound(index) => round(index, remain_count) > 0.0,
+            &Self::IsNotNegative(index) => arithmetic(index, remain_count) >= 0.0,
+            &Self::IsNotNegativeRound(index) => round(index, remain_count) >= 0.0,
+            &Self::IsZero(index) => arithmetic(index, remain_count) == 0.0,
+            &Self::IsZeroRound(index) => round(index, remain_count) == 0.0,
+            &Self::IsNotPositive(index) => arithmetic(index, remain_count) <= 0.0,
+            &Self::IsNotPositiveRound(index) => round(index, remain_count) <= 0.0,
+            &Self::IsNegative(index) => arithmetic(index, remain_count) < 0.0,
+            &Self::IsNegativeRound(index) => round(index, remain_count) < 0.0,
+            &Self::TileFree(dir) => match data.neighbors.get(&dir) {
+                Neighbor::Tile(tile) => matches!(tile.plant, State::Nothing),
+                Neighbor::Empty | Neighbor::SunTile(_) => false,
+            },
+        };
+    }
+
+    /// Applies the logic operator at the given index of the program of the
+    /// plant, returns false if there is no operator at the index
+    ///
+    /// # Parameters
+    ///
+    /// data: All data required for the apply operation
+    ///
+    /// index: The index of the operator
+    ///
+    /// remaining count: The remaining number of operators to evaluate before
+    /// returning default values
+    pub fn apply_index(data: &ApplyData, index: usize, remain_count: &mut usize) -> bool {
+        return match data.plant.program.logic.get(index) {
+            Some(operator) => operator.apply(data, remain_count),
+            None => false,
+        };
     }
 }
@@ -1,4 +1,11 @@
-use super::{NeighborDirection, Plant, TileData, TileNeighbors};
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use rand::{Rng, rngs::StdRng};
+
+use super::{
+    BridgeSet, Neighbor, NeighborDirection, Plant, Settings, Spread, State, TileData,
+    TileNeighbors, bridge, bulk,
+};
 
 mod arithmetic;
 pub use arithmetic::Arithmetic;
@@ -15,6 +22,14 @@ pub use spread_bulk::SpreadBulk;
 mod spread_bridge;
 pub use spread_bridge::SpreadBridge;
 
+mod genome;
+pub use genome::GenomeError;
+
+mod simplify;
+
+#[cfg(test)]
+mod tests;
+
 /// All data required to apply an operator
 #[derive(Clone, Copy, Debug)]
 pub struct ApplyData<'a> {
@@ -27,3 +42,495 @@ pub struct ApplyData<'a> {
     /// The energy of the plant in the new simulation step
     pub new_energy: f64,
 }
+
+/// The result of running the program of a plant for a single step
+#[derive(Clone, Debug)]
+pub struct Decision {
+    /// True if the plant should die
+    pub kill: bool,
+    /// True if a ripe seed should start growing
+    pub grow: bool,
+    /// The offspring to spread given by the non-mutated plant, the energy
+    /// to allocate and the direction to spread in
+    pub spread: Option<(Plant, f64, NeighborDirection)>,
+    /// The fraction of the energy capacity to move the energy reserve to
+    pub reserve: Option<f64>,
+    /// The amount to grow the energy capacity by before the limits of the
+    /// settings are applied
+    pub capacity_growth: Option<f64>,
+    /// The direction of the branch to convert into a log
+    pub upgrade: Option<NeighborDirection>,
+    /// The direction of the bridge to remove
+    pub prune: Option<NeighborDirection>,
+}
+
+impl Decision {
+    /// Constructs a new decision to do nothing
+    pub fn new() -> Self {
+        return Self {
+            kill: false,
+            grow: false,
+            spread: None,
+            reserve: None,
+            capacity_growth: None,
+            upgrade: None,
+            prune: None,
+        };
+    }
+}
+
+/// The genome of a plant, all operators of a kind are stored in a list and
+/// refer to each other by their index in these lists
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Program {
+    /// All arithmetic operators
+    arithmetic: Vec<Arithmetic>,
+    /// All logic operators
+    logic: Vec<Logic>,
+    /// All action operators, the first is the entry point
+    action: Vec<Action>,
+    /// All descriptions of offspring bulks
+    spread_bulk: Vec<SpreadBulk>,
+    /// All descriptions of offspring bridges
+    spread_bridge: Vec<SpreadBridge>,
+}
+
+impl Program {
+    /// Constructs a new program
+    ///
+    /// # Parameters
+    ///
+    /// arithmetic: All arithmetic operators
+    ///
+    /// logic: All logic operators
+    ///
+    /// action: All action operators, the first is the entry point
+    ///
+    /// spread_bulk: All descriptions of offspring bulks
+    ///
+    /// spread_bridge: All descriptions of offspring bridges
+    pub fn new(
+        arithmetic: Vec<Arithmetic>,
+        logic: Vec<Logic>,
+        action: Vec<Action>,
+        spread_bulk: Vec<SpreadBulk>,
+        spread_bridge: Vec<SpreadBridge>,
+    ) -> Self {
+        return Self {
+            arithmetic,
+            logic,
+            action,
+            spread_bulk,
+            spread_bridge,
+        };
+    }
+
+    /// Constructs the simple genome given to plants placed by hand, once it
+    /// has enough energy it grows leaves towards the brighter of the two
+    /// upper neighbors and when it has grown old enough it spreads seeds
+    pub fn new_starter() -> Self {
+        let arithmetic = vec![
+            Arithmetic::One,
+            Arithmetic::Double(0),
+            Arithmetic::Double(1),
+            Arithmetic::Double(2),
+            Arithmetic::Double(3),
+            Arithmetic::Half(0),
+            Arithmetic::PlantEnergy,
+            Arithmetic::PlantAge,
+            Arithmetic::TileLightGradient(NeighborDirection::UpLeft),
+            Arithmetic::TileLightGradient(NeighborDirection::UpRight),
+        ];
+        let logic = vec![
+            Logic::Greater(6, 3),
+            Logic::Greater(7, 4),
+            Logic::Greater(8, 9),
+        ];
+        let action = vec![
+            Action::If(0, 1),
+            Action::IfElse(1, 2, 3),
+            Action::Spread(1, 0, NeighborDirection::Right),
+            Action::IfElse(2, 4, 5),
+            Action::Spread(0, 0, NeighborDirection::UpLeft),
+            Action::Spread(0, 0, NeighborDirection::UpRight),
+        ];
+        let spread_bulk = vec![
+            SpreadBulk::new(2, 3, 5, 5, 2),
+            SpreadBulk::new(3, 4, 5, 5, 3),
+        ];
+        let spread_bridge = vec![SpreadBridge::new(1, 3, 3, 2)];
+
+        return Self::new(arithmetic, logic, action, spread_bulk, spread_bridge);
+    }
+
+    /// Constructs a program of random operators with random indices, all
+    /// indices point into the lists and all directions are equally likely
+    ///
+    /// # Parameters
+    ///
+    /// length: The number of operators of each kind, at least 1
+    ///
+    /// rng: The random number generator to draw from
+    pub fn new_random(length: usize, rng: &mut StdRng) -> Self {
+        let length = length.max(1);
+        let arithmetic = (0..length)
+            .map(|_| {
+                let id = rng.random_range(0..Arithmetic::COUNT);
+                let counts = Arithmetic::get_index_counts(id, length);
+                Arithmetic::from_id(
+                    id,
+                    (rng.random_range(0..counts.0), rng.random_range(0..counts.1)),
+                )
+            })
+            .collect();
+        let logic = (0..length)
+            .map(|_| {
+                let id = rng.random_range(0..Logic::COUNT);
+                let counts = Logic::get_index_counts(id, length);
+                Logic::from_id(
+                    id,
+                    (rng.random_range(0..counts.0), rng.random_range(0..counts.1)),
+                )
+            })
+            .collect();
+        let action = (0..length)
+            .map(|_| {
+                let id = rng.random_range(0..Action::COUNT);
+                let counts = Action::get_index_counts(id, length);
+                Action::from_id(
+                    id,
+                    (
+                        rng.random_range(0..counts.0),
+                        rng.random_range(0..counts.1),
+                        rng.random_range(0..counts.2),
+                    ),
+                )
+            })
+            .collect();
+        let spread_bulk = (0..length)
+            .map(|_| {
+                let id = rng.random_range(0..SpreadBulk::BULK_COUNT);
+                SpreadBulk::from_id(
+                    id,
+                    (
+                        rng.random_range(0..length),
+                        rng.random_range(0..length),
+                        rng.random_range(0..length),
+                        rng.random_range(0..length),
+                    ),
+                )
+            })
+            .collect();
+        let spread_bridge = (0..length)
+            .map(|_| {
+                SpreadBridge::new(
+                    rng.random_range(0..SpreadBridge::BRIDGE_COUNT),
+                    rng.random_range(0..length),
+                    rng.random_range(0..length),
+                    rng.random_range(0..SpreadBridge::TRANSFER_COUNT),
+                )
+            })
+            .collect();
+
+        return Self::new(arithmetic, logic, action, spread_bulk, spread_bridge);
+    }
+
+    /// Computes a hash of the program, programs which are equal have equal
+    /// hashes
+    pub fn get_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+
+        return hasher.finish();
+    }
+
+    /// Gets the distance between this genome and another as the fraction of
+    /// operator positions where they differ, a position only one of them has
+    /// counts as a difference. The distance is 0 for equal genomes and 1 for
+    /// genomes without any operator in common
+    ///
+    /// # Parameters
+    ///
+    /// other: The genome to compare with
+    pub fn get_distance(&self, other: &Self) -> f64 {
+        let (differences, positions) = [
+            Self::count_differences(&self.arithmetic, &other.arithmetic),
+            Self::count_differences(&self.logic, &other.logic),
+            Self::count_differences(&self.action, &other.action),
+            Self::count_differences(&self.spread_bulk, &other.spread_bulk),
+            Self::count_differences(&self.spread_bridge, &other.spread_bridge),
+        ]
+        .iter()
+        .fold((0, 0), |(differences, positions), (count, length)| {
+            (differences + count, positions + length)
+        });
+        if positions == 0 {
+            return 0.0;
+        }
+
+        return differences as f64 / positions as f64;
+    }
+
+    /// Counts the positions where two lists of operators differ and returns
+    /// it together with the length of the longest list
+    ///
+    /// # Parameters
+    ///
+    /// operators1: The first list of operators
+    ///
+    /// operators2: The second list of operators
+    fn count_differences<T: PartialEq>(operators1: &[T], operators2: &[T]) -> (usize, usize) {
+        let length = operators1.len().max(operators2.len());
+        let equal = operators1
+            .iter()
+            .zip(operators2.iter())
+            .filter(|(operator1, operator2)| operator1 == operator2)
+            .count();
+
+        return (length - equal, length);
+    }
+
+    /// Runs the program starting from the first action, at most the
+    /// operator limit of the settings are evaluated
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The settings for the map
+    ///
+    /// data: All data required for the apply operation
+    pub fn run(&self, map_settings: &Settings, data: &ApplyData) -> Decision {
+        let mut decision = Decision::new();
+        let mut remain_count = map_settings.program.operator_limit;
+        Action::apply_index(data, 0, &mut remain_count, &mut decision);
+
+        return decision;
+    }
+
+    /// Returns a mutated version of this program, every operator may be
+    /// swapped for another type keeping its indices and may have one of its
+    /// indices replaced. Unreachable operators are removed afterwards if set
+    /// in the mutation settings
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The settings for the map
+    ///
+    /// rng: The random number generator to draw the mutations from
+    pub fn mutate(&self, map_settings: &Settings, rng: &mut StdRng) -> Self {
+        let settings = &map_settings.mutation;
+
+        // Indices may point into any of the lists so draw them from the
+        // longest one, indices out of range are handled when applying.
+        // Directions are drawn from the directions only
+        let index_count = self
+            .arithmetic
+            .len()
+            .max(self.logic.len())
+            .max(self.action.len())
+            .max(self.spread_bulk.len())
+            .max(self.spread_bridge.len())
+            .max(1);
+
+        let arithmetic = self
+            .arithmetic
+            .iter()
+            .map(|operator| {
+                let id = Self::mutate_id(
+                    operator.get_id(),
+                    Arithmetic::COUNT,
+                    settings.swap_rate,
+                    rng,
+                );
+                let indices = operator.get_indices();
+                let counts = Arithmetic::get_index_counts(id, index_count);
+                let indices =
+                    match Self::mutate_index(&[counts.0, counts.1], settings.point_rate, rng) {
+                        Some((0, index)) => (index, indices.1),
+                        Some((_, index)) => (indices.0, index),
+                        None => indices,
+                    };
+                Arithmetic::from_id(id, indices)
+            })
+            .collect();
+
+        let logic = self
+            .logic
+            .iter()
+            .map(|operator| {
+                let id = Self::mutate_id(operator.get_id(), Logic::COUNT, settings.swap_rate, rng);
+                let indices = operator.get_indices();
+                let counts = Logic::get_index_counts(id, index_count);
+                let indices =
+                    match Self::mutate_index(&[counts.0, counts.1], settings.point_rate, rng) {
+                        Some((0, index)) => (index, indices.1),
+                        Some((_, index)) => (indices.0, index),
+                        None => indices,
+                    };
+                Logic::from_id(id, indices)
+            })
+            .collect();
+
+        let action = self
+            .action
+            .iter()
+            .map(|operator| {
+                let id = Self::mutate_id(operator.get_id(), Action::COUNT, settings.swap_rate, rng);
+                let indices = operator.get_indices();
+                let counts = Action::get_index_counts(id, index_count);
+                let counts = [counts.0, counts.1, counts.2];
+                let indices = match Self::mutate_index(&counts, settings.point_rate, rng) {
+                    Some((0, index)) => (index, indices.1, indices.2),
+                    Some((1, index)) => (indices.0, index, indices.2),
+                    Some((_, index)) => (indices.0, indices.1, index),
+                    None => indices,
+                };
+                Action::from_id(id, indices)
+            })
+            .collect();
+
+        let spread_bulk = self
+            .spread_bulk
+            .iter()
+            .map(|operator| {
+                let id = Self::mutate_id(
+                    operator.bulk,
+                    SpreadBulk::BULK_COUNT,
+                    settings.swap_rate,
+                    rng,
+                );
+                let indices = operator.get_indices();
+                let indices = match Self::mutate_index(&[index_count; 4], settings.point_rate, rng)
+                {
+                    Some((0, index)) => (index, indices.1, indices.2, indices.3),
+                    Some((1, index)) => (indices.0, index, indices.2, indices.3),
+                    Some((2, index)) => (indices.0, indices.1, index, indices.3),
+                    Some((_, index)) => (indices.0, indices.1, indices.2, index),
+                    None => indices,
+                };
+                SpreadBulk::from_id(id, indices)
+            })
+            .collect();
+
+        let spread_bridge = self
+            .spread_bridge
+            .iter()
+            .map(|operator| {
+                let bridge = Self::mutate_id(
+                    operator.bridge,
+                    SpreadBridge::BRIDGE_COUNT,
+                    settings.swap_rate,
+                    rng,
+                );
+                let energy_transfer = Self::mutate_id(
+                    operator.energy_transfer,
+                    SpreadBridge::TRANSFER_COUNT,
+                    settings.swap_rate,
+                    rng,
+                );
+                let indices = operator.get_indices();
+                let indices = match Self::mutate_index(&[index_count; 2], settings.point_rate, rng)
+                {
+                    Some((0, index)) => (index, indices.1),
+                    Some((_, index)) => (indices.0, index),
+                    None => indices,
+                };
+                SpreadBridge::new(bridge, indices.0, indices.1, energy_transfer)
+            })
+            .collect();
+
+        let program = Self {
+            arithmetic,
+            logic,
+            action,
+            spread_bulk,
+            spread_bridge,
+        };
+
+        return if settings.simplify {
+            program.simplify()
+        } else {
+            program
+        };
+    }
+
+    /// Combines this program with the program of another parent, every
+    /// operator is taken from either parent with equal probability such that
+    /// about half of the genome of each parent is passed on. The lists keep
+    /// the lengths of this program and operators missing in the other parent
+    /// are taken from this one
+    ///
+    /// # Parameters
+    ///
+    /// other: The program of the other parent
+    ///
+    /// rng: The random number generator to draw the choices from
+    pub fn crossover(&self, other: &Self, rng: &mut StdRng) -> Self {
+        return Self {
+            arithmetic: Self::crossover_list(&self.arithmetic, &other.arithmetic, rng),
+            logic: Self::crossover_list(&self.logic, &other.logic, rng),
+            action: Self::crossover_list(&self.action, &other.action, rng),
+            spread_bulk: Self::crossover_list(&self.spread_bulk, &other.spread_bulk, rng),
+            spread_bridge: Self::crossover_list(&self.spread_bridge, &other.spread_bridge, rng),
+        };
+    }
+
+    /// Combines two lists of operators by taking every operator from either
+    /// list with equal probability
+    ///
+    /// # Parameters
+    ///
+    /// first: The list giving the length of the result
+    ///
+    /// second: The other list
+    ///
+    /// rng: The random number generator to draw the choices from
+    fn crossover_list<T: Clone>(first: &[T], second: &[T], rng: &mut StdRng) -> Vec<T> {
+        return first
+            .iter()
+            .enumerate()
+            .map(|(index, operator)| match second.get(index) {
+                Some(other) if rng.random_bool(0.5) => other.clone(),
+                _ => operator.clone(),
+            })
+            .collect();
+    }
+
+    /// Draws a possibly swapped operator type id
+    ///
+    /// # Parameters
+    ///
+    /// id: The current type id
+    ///
+    /// count: The number of different operator types
+    ///
+    /// rate: The probability of swapping the type
+    ///
+    /// rng: The random number generator to draw from
+    fn mutate_id(id: usize, count: usize, rate: f64, rng: &mut StdRng) -> usize {
+        return if rng.random_bool(rate.clamp(0.0, 1.0)) {
+            rng.random_range(0..count)
+        } else {
+            id
+        };
+    }
+
+    /// Draws a possible point mutation, returns which of the indices to
+    /// replace and its new value
+    ///
+    /// # Parameters
+    ///
+    /// counts: The number of different values of every index of the
+    /// operator, the new value is smaller than this
+    ///
+    /// rate: The probability of a point mutation
+    ///
+    /// rng: The random number generator to draw from
+    fn mutate_index(counts: &[usize], rate: f64, rng: &mut StdRng) -> Option<(usize, usize)> {
+        if !rng.random_bool(rate.clamp(0.0, 1.0)) {
+            return None;
+        }
+
+        let slot = rng.random_range(0..counts.len());
+        return Some((slot, rng.random_range(0..counts[slot])));
+    }
+}
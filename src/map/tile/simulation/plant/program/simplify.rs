@@ -0,0 +1,447 @@
+use super::{Action, Arithmetic, GenomeError, Logic, Program, SpreadBridge, SpreadBulk};
+
+/// The lists of operators of a program
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Section {
+    Arithmetic,
+    Logic,
+    Action,
+    SpreadBulk,
+    SpreadBridge,
+}
+
+impl Section {
+    /// The number of different sections
+    const COUNT: usize = 5;
+
+    /// Gets the unique id of the section smaller than COUNT
+    fn id(&self) -> usize {
+        return match self {
+            Self::Arithmetic => 0,
+            Self::Logic => 1,
+            Self::Action => 2,
+            Self::SpreadBulk => 3,
+            Self::SpreadBridge => 4,
+        };
+    }
+
+    /// Gets the name of the section as used in the genome text format
+    fn get_name(&self) -> &'static str {
+        return match self {
+            Self::Arithmetic => "arithmetic",
+            Self::Logic => "logic",
+            Self::Action => "action",
+            Self::SpreadBulk => "spread_bulk",
+            Self::SpreadBridge => "spread_bridge",
+        };
+    }
+}
+
+impl Program {
+    /// Makes sure every index of every operator points to an operator in the
+    /// list it refers to and all type ids are known. Indices out of range are
+    /// allowed when running a program, they give default values, so this is
+    /// only a check of the quality of a genome
+    ///
+    /// # Errors
+    ///
+    /// Returns GenomeError::Index for the first index out of range and
+    /// GenomeError::Operator for the first unknown type id
+    pub fn validate(&self) -> Result<(), GenomeError> {
+        let lengths = self.get_lengths();
+        for section in [
+            Section::Arithmetic,
+            Section::Logic,
+            Section::Action,
+            Section::SpreadBulk,
+            Section::SpreadBridge,
+        ] {
+            for operator in 0..lengths[section.id()] {
+                if let Some((target, index)) = self
+                    .get_references(section, operator)
+                    .into_iter()
+                    .find(|(target, index)| *index >= lengths[target.id()])
+                {
+                    return Err(GenomeError::Index {
+                        section: section.get_name(),
+                        operator,
+                        index,
+                        target: target.get_name(),
+                    });
+                }
+            }
+        }
+
+        let unknown = |section: Section, operator: String| GenomeError::Operator {
+            section: section.get_name(),
+            operator,
+        };
+        if let Some(operator) = self
+            .spread_bulk
+            .iter()
+            .find(|operator| operator.bulk >= SpreadBulk::BULK_COUNT)
+        {
+            return Err(unknown(Section::SpreadBulk, format!("{:?}", operator)));
+        }
+        if let Some(operator) = self.spread_bridge.iter().find(|operator| {
+            operator.bridge >= SpreadBridge::BRIDGE_COUNT
+                || operator.energy_transfer >= SpreadBridge::TRANSFER_COUNT
+        }) {
+            return Err(unknown(Section::SpreadBridge, format!("{:?}", operator)));
+        }
+
+        return Ok(());
+    }
+
+    /// Returns a copy of the program without the operators which can never be
+    /// evaluated since they are not reachable from the entry action. The
+    /// remaining operators keep their order and their indices are updated,
+    /// indices out of range stay out of range, so the simplified program
+    /// makes the same decisions using the same number of operators
+    pub fn simplify(&self) -> Self {
+        let reachable = self.get_reachable();
+
+        // Find the new index of every reachable operator, indices out of range
+        // are moved to the first index out of range of the new list
+        let new_lengths = reachable
+            .each_ref()
+            .map(|reachable| reachable.iter().filter(|reachable| **reachable).count());
+        let new_indices = reachable.each_ref().map(|reachable| {
+            return reachable
+                .iter()
+                .scan(0, |next, reachable| {
+                    let index = *next;
+                    if *reachable {
+                        *next += 1;
+                    }
+                    return Some(index);
+                })
+                .collect::<Vec<_>>();
+        });
+        let remap = |section: Section, index: usize| -> usize {
+            return new_indices[section.id()]
+                .get(index)
+                .copied()
+                .unwrap_or(new_lengths[section.id()]);
+        };
+        let keep = |section: Section, index: usize| reachable[section.id()][index];
+
+        let arithmetic = self
+            .arithmetic
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| keep(Section::Arithmetic, *index))
+            .map(|(_, operator)| {
+                let (index1, index2) = operator.get_indices();
+                let references = get_arithmetic_references(operator);
+                Arithmetic::from_id(
+                    operator.get_id(),
+                    (
+                        remap_slot(&references, 0, index1, &remap),
+                        remap_slot(&references, 1, index2, &remap),
+                    ),
+                )
+            })
+            .collect();
+        let logic = self
+            .logic
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| keep(Section::Logic, *index))
+            .map(|(_, operator)| {
+                let (index1, index2) = operator.get_indices();
+                let references = get_logic_references(operator);
+                Logic::from_id(
+                    operator.get_id(),
+                    (
+                        remap_slot(&references, 0, index1, &remap),
+                        remap_slot(&references, 1, index2, &remap),
+                    ),
+                )
+            })
+            .collect();
+        let action = self
+            .action
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| keep(Section::Action, *index))
+            .map(|(_, operator)| {
+                let (index1, index2, index3) = operator.get_indices();
+                let references = get_action_references(operator);
+                Action::from_id(
+                    operator.get_id(),
+                    (
+                        remap_slot(&references, 0, index1, &remap),
+                        remap_slot(&references, 1, index2, &remap),
+                        remap_slot(&references, 2, index3, &remap),
+                    ),
+                )
+            })
+            .collect();
+        let spread_bulk = self
+            .spread_bulk
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| keep(Section::SpreadBulk, *index))
+            .map(|(_, operator)| {
+                let (index1, index2, index3, index4) = operator.get_indices();
+                SpreadBulk::from_id(
+                    operator.bulk,
+                    (
+                        remap(Section::Arithmetic, index1),
+                        remap(Section::Arithmetic, index2),
+                        remap(Section::Arithmetic, index3),
+                        remap(Section::Arithmetic, index4),
+                    ),
+                )
+            })
+            .collect();
+        let spread_bridge = self
+            .spread_bridge
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| keep(Section::SpreadBridge, *index))
+            .map(|(_, operator)| {
+                let (index1, index2) = operator.get_indices();
+                SpreadBridge::new(
+                    operator.bridge,
+                    remap(Section::Arithmetic, index1),
+                    remap(Section::Arithmetic, index2),
+                    operator.energy_transfer,
+                )
+            })
+            .collect();
+
+        return Self::new(arithmetic, logic, action, spread_bulk, spread_bridge);
+    }
+
+    /// Gets the total number of operators of the program
+    pub fn get_operator_count(&self) -> usize {
+        return self.get_lengths().iter().sum();
+    }
+
+    /// Gets the number of operators which are reachable from the entry action
+    pub fn get_active_operator_count(&self) -> usize {
+        return self
+            .get_reachable()
+            .iter()
+            .map(|reachable| reachable.iter().filter(|reachable| **reachable).count())
+            .sum();
+    }
+
+    /// Finds all operators reachable from the entry action, gives for every
+    /// section if each of its operators is reachable
+    fn get_reachable(&self) -> [Vec<bool>; Section::COUNT] {
+        let lengths = self.get_lengths();
+        let mut reachable = lengths.map(|length| vec![false; length]);
+        let mut stack = vec![(Section::Action, 0)];
+        while let Some((section, index)) = stack.pop() {
+            if index >= lengths[section.id()] || reachable[section.id()][index] {
+                continue;
+            }
+            reachable[section.id()][index] = true;
+            stack.extend(self.get_references(section, index));
+        }
+
+        return reachable;
+    }
+
+    /// Gets the number of operators in every list in the order of the
+    /// section ids
+    fn get_lengths(&self) -> [usize; Section::COUNT] {
+        return [
+            self.arithmetic.len(),
+            self.logic.len(),
+            self.action.len(),
+            self.spread_bulk.len(),
+            self.spread_bridge.len(),
+        ];
+    }
+
+    /// Gets all operators an operator may evaluate as the section and index
+    /// of each of them
+    ///
+    /// # Parameters
+    ///
+    /// section: The section of the operator
+    ///
+    /// index: The index of the operator, it must be in range
+    fn get_references(&self, section: Section, index: usize) -> Vec<(Section, usize)> {
+        return match section {
+            Section::Arithmetic => {
+                let operator = &self.arithmetic[index];
+                let (index1, index2) = operator.get_indices();
+                zip_references(&get_arithmetic_references(operator), &[index1, index2])
+            }
+            Section::Logic => {
+                let operator = &self.logic[index];
+                let (index1, index2) = operator.get_indices();
+                zip_references(&get_logic_references(operator), &[index1, index2])
+            }
+            Section::Action => {
+                let operator = &self.action[index];
+                let (index1, index2, index3) = operator.get_indices();
+                zip_references(&get_action_references(operator), &[index1, index2, index3])
+            }
+            Section::SpreadBulk => {
+                let (index1, index2, index3, index4) = self.spread_bulk[index].get_indices();
+                [index1, index2, index3, index4]
+                    .into_iter()
+                    .map(|index| (Section::Arithmetic, index))
+                    .collect()
+            }
+            Section::SpreadBridge => {
+                let (index1, index2) = self.spread_bridge[index].get_indices();
+                vec![(Section::Arithmetic, index1), (Section::Arithmetic, index2)]
+            }
+        };
+    }
+}
+
+/// Gets the sections the leading indices of an arithmetic operator refer to,
+/// the remaining indices are not references
+///
+/// # Parameters
+///
+/// operator: The operator to get the references of
+fn get_arithmetic_references(operator: &Arithmetic) -> Vec<Section> {
+    return match operator {
+        Arithmetic::Double(_)
+        | Arithmetic::Half(_)
+        | Arithmetic::Increment(_)
+        | Arithmetic::Decrement(_)
+        | Arithmetic::Neg(_)
+        | Arithmetic::MinZero(_)
+        | Arithmetic::MinOne(_)
+        | Arithmetic::MaxZero(_)
+        | Arithmetic::MaxOne(_) => vec![Section::Arithmetic],
+        Arithmetic::Add(_, _)
+        | Arithmetic::Sub(_, _)
+        | Arithmetic::Mul(_, _)
+        | Arithmetic::Div(_, _)
+        | Arithmetic::Mod(_, _)
+        | Arithmetic::Min(_, _)
+        | Arithmetic::Max(_, _)
+        | Arithmetic::Mean(_, _) => vec![Section::Arithmetic, Section::Arithmetic],
+        Arithmetic::Zero
+        | Arithmetic::One
+        | Arithmetic::TileLight
+        | Arithmetic::TileLightGradient(_)
+        | Arithmetic::TileTransparency
+        | Arithmetic::TileTransparencyGradient(_)
+        | Arithmetic::PlantAge
+        | Arithmetic::PlantCumAge
+        | Arithmetic::PlantEnergyCapacity
+        | Arithmetic::PlantEnergyReserve
+        | Arithmetic::PlantEnergy
+        | Arithmetic::PlantEnergyChange
+        | Arithmetic::PlantEnergySelf
+        | Arithmetic::PlantEnergySelfChange
+        | Arithmetic::PlantEnergyShare
+        | Arithmetic::PlantEnergyShareChange
+        | Arithmetic::TileWater
+        | Arithmetic::TileWaterGradient(_)
+        | Arithmetic::TileNutrients => Vec::new(),
+    };
+}
+
+/// Gets the sections the leading indices of a logic operator refer to, the
+/// remaining indices are not references
+///
+/// # Parameters
+///
+/// operator: The operator to get the references of
+fn get_logic_references(operator: &Logic) -> Vec<Section> {
+    return match operator {
+        Logic::False | Logic::True | Logic::TileFree(_) => Vec::new(),
+        Logic::And(_, _) | Logic::Or(_, _) | Logic::Xor(_, _) => {
+            vec![Section::Logic, Section::Logic]
+        }
+        Logic::Not(_) => vec![Section::Logic],
+        Logic::IsPositive(_)
+        | Logic::IsPositiveRound(_)
+        | Logic::IsNotNegative(_)
+        | Logic::IsNotNegativeRound(_)
+        | Logic::IsZero(_)
+        | Logic::IsZeroRound(_)
+        | Logic::IsNotPositive(_)
+        | Logic::IsNotPositiveRound(_)
+        | Logic::IsNegative(_)
+        | Logic::IsNegativeRound(_) => vec![Section::Arithmetic],
+        Logic::Equal(_, _)
+        | Logic::EqualRound(_, _)
+        | Logic::NotEqual(_, _)
+        | Logic::NotEqualRound(_, _)
+        | Logic::Greater(_, _)
+        | Logic::GreaterRound(_, _)
+        | Logic::GreaterOrEqual(_, _)
+        | Logic::GreaterOrEqualRound(_, _)
+        | Logic::Less(_, _)
+        | Logic::LessRound(_, _)
+        | Logic::LessOrEqual(_, _)
+        | Logic::LessOrEqualRound(_, _) => vec![Section::Arithmetic, Section::Arithmetic],
+    };
+}
+
+/// Gets the sections the leading indices of an action operator refer to,
+/// the remaining indices are not references
+///
+/// # Parameters
+///
+/// operator: The operator to get the references of
+fn get_action_references(operator: &Action) -> Vec<Section> {
+    return match operator {
+        Action::None
+        | Action::Kill
+        | Action::Grow
+        | Action::UpgradeBridge(_)
+        | Action::PruneBridge(_) => Vec::new(),
+        Action::If(_, _) => vec![Section::Logic, Section::Action],
+        Action::IfElse(_, _, _) => vec![Section::Logic, Section::Action, Section::Action],
+        Action::Both(_, _) => vec![Section::Action, Section::Action],
+        Action::Spread(_, _, _) => vec![Section::SpreadBulk, Section::SpreadBridge],
+        Action::SetReserve(_) | Action::GrowCapacity(_) => vec![Section::Arithmetic],
+    };
+}
+
+/// Pairs the sections of the references of an operator with its indices
+///
+/// # Parameters
+///
+/// references: The sections the leading indices refer to
+///
+/// indices: All indices of the operator
+fn zip_references(references: &[Section], indices: &[usize]) -> Vec<(Section, usize)> {
+    return references
+        .iter()
+        .copied()
+        .zip(indices.iter().copied())
+        .collect();
+}
+
+/// Updates an index of an operator if it is a reference, other indices like
+/// directions are kept
+///
+/// # Parameters
+///
+/// references: The sections the leading indices refer to
+///
+/// slot: The position of the index in the operator
+///
+/// index: The index to update
+///
+/// remap: Gives the new index of an operator from its section and old index
+fn remap_slot(
+    references: &[Section],
+    slot: usize,
+    index: usize,
+    remap: &impl Fn(Section, usize) -> usize,
+) -> usize {
+    return match references.get(slot) {
+        Some(section) => remap(*section, index),
+        None => index,
+    };
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,275 @@
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use super::super::{
+    Decision, NeighborDirection,
+    tests::{LEAF, SIZE, UNLIMITED, with_data},
+};
+use super::*;
+
+/// Runs a program for a leaf on every tile of the test map, returns the
+/// decisions together with the number of operators left
+///
+/// # Parameters
+///
+/// program: The program to run
+fn decide_everywhere(program: &Program) -> Vec<(Decision, usize)> {
+    return (0..SIZE.w * SIZE.h)
+        .map(|index| {
+            with_data(program.clone(), LEAF, index, |data| {
+                let mut decision = Decision::new();
+                let mut remain_count = UNLIMITED;
+                Action::apply_index(data, 0, &mut remain_count, &mut decision);
+                return (decision, remain_count);
+            })
+        })
+        .collect();
+}
+
+/// Checks that two decisions are the same, the genome of the offspring is
+/// not compared as it is the genome of the deciding program
+///
+/// # Parameters
+///
+/// decision: The decision to check
+///
+/// expected: The expected decision
+fn assert_same_decision(decision: &Decision, expected: &Decision) {
+    assert_eq!(decision.kill, expected.kill);
+    assert_eq!(decision.grow, expected.grow);
+    assert_eq!(decision.reserve, expected.reserve);
+    assert_eq!(decision.capacity_growth, expected.capacity_growth);
+    assert_eq!(decision.upgrade, expected.upgrade);
+    assert_eq!(decision.prune, expected.prune);
+    assert_eq!(
+        decision.spread.as_ref().map(|(plant, energy, dir)| (
+            plant.bulk.id(),
+            plant.energy_capacity,
+            *energy,
+            *dir
+        )),
+        expected.spread.as_ref().map(|(plant, energy, dir)| (
+            plant.bulk.id(),
+            plant.energy_capacity,
+            *energy,
+            *dir
+        )),
+    );
+}
+
+/// Constructs random programs where some lists are cut short such that
+/// indices out of range are common
+fn random_programs() -> Vec<Program> {
+    let mut rng = StdRng::seed_from_u64(5);
+    return (0..200)
+        .map(|_| {
+            let length = rng.random_range(1..12);
+            let mut program = Program::new_random(length, &mut rng);
+            program.logic.truncate(rng.random_range(0..=length));
+            program.action.truncate(rng.random_range(1..=length));
+            program.spread_bulk.truncate(rng.random_range(0..=length));
+            return program;
+        })
+        .collect();
+}
+
+#[test]
+fn simplify_keeps_decisions() {
+    for program in random_programs() {
+        let simplified = program.simplify();
+        for ((decision, remain_count), (expected, expected_count)) in decide_everywhere(&simplified)
+            .iter()
+            .zip(decide_everywhere(&program).iter())
+        {
+            assert_same_decision(decision, expected);
+            assert_eq!(remain_count, expected_count, "{:?}", program);
+        }
+    }
+}
+
+#[test]
+fn simplify_does_not_grow() {
+    for program in random_programs() {
+        let simplified = program.simplify();
+        assert!(simplified.get_operator_count() <= program.get_operator_count());
+        assert_eq!(
+            simplified.get_operator_count(),
+            program.get_active_operator_count()
+        );
+        assert_eq!(simplified.simplify(), simplified);
+    }
+}
+
+#[test]
+fn simplify_removes_unreachable() {
+    let program = Program::new(
+        vec![
+            Arithmetic::PlantAge,
+            Arithmetic::Double(0),
+            Arithmetic::TileLightGradient(NeighborDirection::Left),
+            Arithmetic::Half(2),
+        ],
+        vec![Logic::True, Logic::IsPositive(3)],
+        vec![Action::If(1, 2), Action::Kill, Action::SetReserve(3)],
+        vec![SpreadBulk::new(0, 0, 0, 0, 0)],
+        vec![SpreadBridge::new(0, 1, 1, 0)],
+    );
+    let simplified = program.simplify();
+
+    assert_eq!(program.get_active_operator_count(), 5);
+    assert_eq!(
+        simplified,
+        Program::new(
+            vec![
+                Arithmetic::TileLightGradient(NeighborDirection::Left),
+                Arithmetic::Half(0),
+            ],
+            vec![Logic::IsPositive(1)],
+            vec![Action::If(0, 1), Action::SetReserve(1)],
+            Vec::new(),
+            Vec::new(),
+        )
+    );
+}
+
+#[test]
+fn simplify_keeps_out_of_range() {
+    let program = Program::new(
+        vec![
+            Arithmetic::One,
+            Arithmetic::Increment(7),
+            Arithmetic::Add(1, 9),
+        ],
+        vec![Logic::TileFree(NeighborDirection::DownLeft)],
+        vec![
+            Action::Both(1, 2),
+            Action::GrowCapacity(2),
+            Action::IfElse(3, 5, 0),
+            Action::Spread(4, 0, NeighborDirection::UpLeft),
+        ],
+        Vec::new(),
+        vec![SpreadBridge::new(0, 0, 0, 0)],
+    );
+    let simplified = program.simplify();
+
+    // The unreachable operators are removed and every index out of range
+    // points just past the end of its list, the directions are kept
+    assert_eq!(
+        simplified,
+        Program::new(
+            vec![Arithmetic::Increment(2), Arithmetic::Add(0, 2)],
+            Vec::new(),
+            vec![
+                Action::Both(1, 2),
+                Action::GrowCapacity(1),
+                Action::IfElse(0, 3, 0),
+            ],
+            Vec::new(),
+            Vec::new(),
+        )
+    );
+    assert!(simplified.validate().is_err());
+}
+
+#[test]
+fn validate_valid() {
+    assert_eq!(Program::new_starter().validate(), Ok(()));
+    assert_eq!(Program::new_starter().simplify().validate(), Ok(()));
+
+    let mut rng = StdRng::seed_from_u64(3);
+    for length in 1..10 {
+        assert_eq!(Program::new_random(length, &mut rng).validate(), Ok(()));
+    }
+}
+
+#[test]
+fn validate_index() {
+    let starter = Program::new_starter;
+    let index = |section, operator, index, target| -> Result<(), GenomeError> {
+        return Err(GenomeError::Index {
+            section,
+            operator,
+            index,
+            target,
+        });
+    };
+
+    let mut program = starter();
+    program.arithmetic[2] = Arithmetic::Sub(0, 10);
+    assert_eq!(program.validate(), index("arithmetic", 2, 10, "arithmetic"));
+
+    let mut program = starter();
+    program.logic[1] = Logic::Not(3);
+    assert_eq!(program.validate(), index("logic", 1, 3, "logic"));
+
+    let mut program = starter();
+    program.logic[0] = Logic::Less(1, 10);
+    assert_eq!(program.validate(), index("logic", 0, 10, "arithmetic"));
+
+    let mut program = starter();
+    program.action[3] = Action::IfElse(3, 0, 0);
+    assert_eq!(program.validate(), index("action", 3, 3, "logic"));
+
+    let mut program = starter();
+    program.action[1] = Action::Both(0, 6);
+    assert_eq!(program.validate(), index("action", 1, 6, "action"));
+
+    let mut program = starter();
+    program.action[4] = Action::Spread(0, 1, NeighborDirection::Left);
+    assert_eq!(program.validate(), index("action", 4, 1, "spread_bridge"));
+
+    let mut program = starter();
+    program.action[5] = Action::Spread(2, 0, NeighborDirection::Left);
+    assert_eq!(program.validate(), index("action", 5, 2, "spread_bulk"));
+
+    let mut program = starter();
+    program.spread_bulk[1] = SpreadBulk::new(1, 0, 0, 10, 0);
+    assert_eq!(
+        program.validate(),
+        index("spread_bulk", 1, 10, "arithmetic")
+    );
+
+    let mut program = starter();
+    program.spread_bridge[0] = SpreadBridge::new(0, 0, 10, 0);
+    assert_eq!(
+        program.validate(),
+        index("spread_bridge", 0, 10, "arithmetic")
+    );
+
+    // Directions are not indices
+    let mut program = starter();
+    program.action[0] = Action::PruneBridge(NeighborDirection::DownRight);
+    assert_eq!(program.validate(), Ok(()));
+}
+
+#[test]
+fn validate_operator() {
+    let mut program = Program::new_starter();
+    program.spread_bulk[0].bulk = SpreadBulk::BULK_COUNT;
+    assert!(matches!(
+        program.validate(),
+        Err(GenomeError::Operator {
+            section: "spread_bulk",
+            ..
+        })
+    ));
+
+    let mut program = Program::new_starter();
+    program.spread_bridge[0].bridge = SpreadBridge::BRIDGE_COUNT;
+    assert!(matches!(
+        program.validate(),
+        Err(GenomeError::Operator {
+            section: "spread_bridge",
+            ..
+        })
+    ));
+
+    let mut program = Program::new_starter();
+    program.spread_bridge[0].energy_transfer = SpreadBridge::TRANSFER_COUNT;
+    assert!(matches!(
+        program.validate(),
+        Err(GenomeError::Operator {
+            section: "spread_bridge",
+            ..
+        })
+    ));
+}
@@ -0,0 +1,88 @@
+use super::{
+    ApplyData, Arithmetic,
+    bridge::{Branch, Bridge, BridgeType, Log, TransferMode},
+};
+
+/// Describes the bridge connecting an offspring to its mother when
+/// spreading, as seen from the mother
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SpreadBridge {
+    /// The type id of the bridge, 0 is a log and anything else is a branch
+    pub bridge: usize,
+    /// The index of the arithmetic operator giving the energy capacity
+    pub energy_capacity: usize,
+    /// The index of the arithmetic operator giving the water capacity
+    pub water_capacity: usize,
+    /// The id of the transfer mode, 0 is out, 1 is in, 2 is open and
+    /// anything else is closed
+    pub energy_transfer: usize,
+}
+
+impl SpreadBridge {
+    /// The number of bridge types
+    pub const BRIDGE_COUNT: usize = 2;
+    /// The number of transfer modes
+    pub const TRANSFER_COUNT: usize = 4;
+
+    /// Constructs a new spread bridge operator
+    ///
+    /// # Parameters
+    ///
+    /// bridge: The type id of the bridge
+    ///
+    /// energy_capacity: The index of the arithmetic operator giving the energy capacity
+    ///
+    /// water_capacity: The index of the arithmetic operator giving the water capacity
+    ///
+    /// energy_transfer: The id of the transfer mode
+    pub fn new(
+        bridge: usize,
+        energy_capacity: usize,
+        water_capacity: usize,
+        energy_transfer: usize,
+    ) -> Self {
+        return Self {
+            bridge,
+            energy_capacity,
+            water_capacity,
+            energy_transfer,
+        };
+    }
+
+    /// Gets the two arithmetic indices used in the operator
+    pub fn get_indices(&self) -> (usize, usize) {
+        return (self.energy_capacity, self.water_capacity);
+    }
+
+    /// Evaluates the operator, returns the bridge as seen from the mother
+    ///
+    /// # Parameters
+    ///
+    /// data: All data required for the apply operation
+    ///
+    /// remaining count: The remaining number of operators to evaluate before
+    /// returning default values
+    pub fn apply(&self, data: &ApplyData, remain_count: &mut usize) -> Bridge {
+        let energy_capacity =
+            Arithmetic::apply_index(data, self.energy_capacity, remain_count).max(0.0);
+        let water_capacity =
+            Arithmetic::apply_index(data, self.water_capacity, remain_count).max(0.0);
+
+        return Bridge {
+            bridge: match self.bridge {
+                0 => BridgeType::Log(Log {}),
+                _ => BridgeType::Branch(Branch {}),
+            },
+            exiting: true,
+            energy_capacity,
+            water_capacity,
+            energy_transfer: match self.energy_transfer {
+                0 => TransferMode::Out,
+                1 => TransferMode::In,
+                2 => TransferMode::Open,
+                _ => TransferMode::Closed,
+            },
+            throughput: 0.0,
+        };
+    }
+}
@@ -0,0 +1,124 @@
+use super::{
+    ApplyData, Arithmetic,
+    bulk::{Bulk, Flower, Leaf, Log, Root, Seed, SugarBulb, Thorn},
+};
+
+/// Describes the bulk of an offspring when spreading, all values are given
+/// by the arithmetic operators at the stored indices
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SpreadBulk {
+    /// The type id of the bulk, see SpreadBulk::get_bulk
+    pub bulk: usize,
+    /// The index of the arithmetic operator giving the energy capacity
+    pub energy_capacity: usize,
+    /// The index of the arithmetic operator giving the energy reserve as a
+    /// fraction of the energy capacity
+    pub energy_reserve: usize,
+    /// The index of the arithmetic operator giving the absorption of a leaf,
+    /// ignored for other bulk types
+    pub absorption: usize,
+    /// The index of the arithmetic operator giving the energy to allocate for
+    /// building the offspring
+    pub energy: usize,
+}
+
+impl SpreadBulk {
+    /// The number of bulk types which can be spread to, ripe seeds are only
+    /// created by a seed ripening
+    pub const BULK_COUNT: usize = 7;
+
+    /// Constructs a new spread bulk operator
+    ///
+    /// # Parameters
+    ///
+    /// bulk: The type id of the bulk
+    ///
+    /// energy_capacity: The index of the arithmetic operator giving the energy capacity
+    ///
+    /// energy_reserve: The index of the arithmetic operator giving the energy reserve
+    ///
+    /// absorption: The index of the arithmetic operator giving the absorption of a leaf
+    ///
+    /// energy: The index of the arithmetic operator giving the energy to allocate
+    pub fn new(
+        bulk: usize,
+        energy_capacity: usize,
+        energy_reserve: usize,
+        absorption: usize,
+        energy: usize,
+    ) -> Self {
+        return Self {
+            bulk,
+            energy_capacity,
+            energy_reserve,
+            absorption,
+            energy,
+        };
+    }
+
+    /// Gets the four arithmetic indices used in the operator
+    pub fn get_indices(&self) -> (usize, usize, usize, usize) {
+        return (
+            self.energy_capacity,
+            self.energy_reserve,
+            self.absorption,
+            self.energy,
+        );
+    }
+
+    /// Constructs a new spread bulk operator from its bulk type id and the
+    /// four arithmetic indices
+    ///
+    /// # Parameters
+    ///
+    /// bulk: The type id of the bulk
+    ///
+    /// indices: The four arithmetic indices
+    pub fn from_id(bulk: usize, indices: (usize, usize, usize, usize)) -> Self {
+        return Self::new(bulk, indices.0, indices.1, indices.2, indices.3);
+    }
+
+    /// Evaluates the operator, returns the bulk, the energy capacity, the
+    /// energy reserve and the energy to allocate
+    ///
+    /// # Parameters
+    ///
+    /// data: All data required for the apply operation
+    ///
+    /// remaining count: The remaining number of operators to evaluate before
+    /// returning default values
+    pub fn apply(&self, data: &ApplyData, remain_count: &mut usize) -> (Bulk, f64, f64, f64) {
+        let energy_capacity =
+            Arithmetic::apply_index(data, self.energy_capacity, remain_count).max(0.0);
+        let energy_reserve = energy_capacity
+            * Arithmetic::apply_index(data, self.energy_reserve, remain_count).clamp(0.0, 1.0);
+        let absorption = Arithmetic::apply_index(data, self.absorption, remain_count);
+        let energy = Arithmetic::apply_index(data, self.energy, remain_count).max(0.0);
+
+        return (
+            Self::get_bulk(self.bulk, absorption),
+            energy_capacity,
+            energy_reserve,
+            energy,
+        );
+    }
+
+    /// Constructs the bulk from its type id
+    ///
+    /// # Parameters
+    ///
+    /// id: The type id of the bulk
+    ///
+    /// absorption: The absorption used if the bulk is a leaf
+    fn get_bulk(id: usize, absorption: f64) -> Bulk {
+        return match id {
+            0 => Bulk::Log(Log {}),
+            1 => Bulk::SugarBulb(SugarBulb {}),
+            2 => Bulk::Leaf(Leaf::new(absorption)),
+            3 => Bulk::Seed(Seed {}),
+            4 => Bulk::Root(Root {}),
+            5 => Bulk::Flower(Flower::new()),
+            _ => Bulk::Thorn(Thorn {}),
+        };
+    }
+}
@@ -0,0 +1,735 @@
+use super::*;
+use crate::{
+    map::{
+        settings::wrap::Wrap,
+        sun,
+        tile::{Tile, TilePos},
+    },
+    types,
+};
+
+/// The size of the map the operators are applied on
+pub(super) const SIZE: types::ISize = types::ISize { w: 3, h: 3 };
+
+/// The index of the center tile, its row is shifted right
+pub(super) const CENTER: usize = 4;
+
+/// The index of the tile in the middle of the top row, it is lit by the sun
+pub(super) const TOP: usize = 1;
+
+/// The index of the tile on the left edge of the middle row
+pub(super) const EDGE: usize = 3;
+
+/// The index of the only tile with a plant, right of the center
+const OCCUPIED: usize = 5;
+
+/// The bulk id of a leaf
+pub(super) const LEAF: usize = 2;
+
+/// The bulk id of a ripe seed
+const RIPE_SEED: usize = 4;
+
+/// The energy of the plant in the new step, the old energy is 4
+const NEW_ENERGY: f64 = 5.0;
+
+/// Enough operators to never run out
+pub(super) const UNLIMITED: usize = 1000;
+
+/// Constructs the tiles of the map, every tile has different light,
+/// transparency and water
+fn new_tiles() -> Vec<Tile> {
+    return (0..SIZE.w * SIZE.h)
+        .map(|index| {
+            let mut tile = Tile::new();
+            tile.data.light = 0.1 * index as f64;
+            tile.data.transparency = 1.0 - 0.05 * index as f64;
+            tile.data.water = 0.2 * index as f64;
+            tile.data.nutrients = 0.3;
+            if index == OCCUPIED {
+                tile.plant = State::Occupied(Plant::new_placed(LEAF, 1.0));
+            }
+            return tile;
+        })
+        .collect();
+}
+
+/// Constructs the plant running the program, it is 3 steps old, its plant 7
+/// steps old and it has 4 energy of a capacity of 10 and a reserve of 2
+///
+/// # Parameters
+///
+/// program: The program of the plant
+///
+/// bulk_id: The type id of the bulk
+fn new_plant(program: Program, bulk_id: usize) -> Plant {
+    let mut plant = Plant::new_root(bulk::Bulk::from_id(bulk_id), 10.0, program);
+    plant.age = 3;
+    plant.cum_age = 7;
+    plant.energy = 4.0;
+    plant.energy_reserve = 2.0;
+
+    return plant;
+}
+
+/// Runs a function with the data of a plant on a tile of the map
+///
+/// # Parameters
+///
+/// program: The program of the plant
+///
+/// bulk_id: The type id of the bulk
+///
+/// index: The index of the tile of the plant
+///
+/// f: The function to run
+pub(super) fn with_data<T>(
+    program: Program,
+    bulk_id: usize,
+    index: usize,
+    f: impl FnOnce(&ApplyData) -> T,
+) -> T {
+    let tiles = new_tiles();
+    let sun = vec![sun::Tile::new(1.0, 0.0); SIZE.w];
+    let priorities = vec![0; SIZE.w * SIZE.h];
+    let pos = TilePos::from_index(index, &SIZE);
+    let neighbors = TileNeighbors::new(&tiles, &sun, &SIZE, &Wrap::None, &pos, &priorities);
+    let plant = new_plant(program, bulk_id);
+    let data = ApplyData {
+        plant: &plant,
+        tile: &tiles[index].data,
+        neighbors: &neighbors,
+        new_energy: NEW_ENERGY,
+    };
+
+    return f(&data);
+}
+
+/// The arithmetic operators the tested operators take their operands from
+pub(super) fn operands() -> Vec<Arithmetic> {
+    return vec![
+        // 0: 3
+        Arithmetic::PlantAge,
+        // 1: 7
+        Arithmetic::PlantCumAge,
+        // 2: 0
+        Arithmetic::Zero,
+        // 3: -3
+        Arithmetic::Neg(0),
+        // 4: 0.4
+        Arithmetic::TileLight,
+        // 5: -0.4
+        Arithmetic::Neg(4),
+    ];
+}
+
+/// Applies a single arithmetic operator placed after operands()
+///
+/// # Parameters
+///
+/// operator: The operator to apply
+///
+/// index: The index of the tile of the plant
+///
+/// remain_count: The number of operators which may be evaluated
+fn arithmetic_at(operator: Arithmetic, index: usize, remain_count: &mut usize) -> f64 {
+    let mut arithmetic = operands();
+    let operator_index = arithmetic.len();
+    arithmetic.push(operator);
+    let program = Program::new(arithmetic, Vec::new(), Vec::new(), Vec::new(), Vec::new());
+
+    return with_data(program, LEAF, index, |data| {
+        Arithmetic::apply_index(data, operator_index, remain_count)
+    });
+}
+
+/// Applies a single arithmetic operator on the center tile without a limit
+///
+/// # Parameters
+///
+/// operator: The operator to apply
+fn arithmetic(operator: Arithmetic) -> f64 {
+    let mut count = UNLIMITED;
+    return arithmetic_at(operator, CENTER, &mut count);
+}
+
+/// Applies a single logic operator, the logic operators True and False are
+/// found at index 1 and 2 and the arithmetic operators are operands()
+///
+/// # Parameters
+///
+/// operator: The operator to apply
+///
+/// index: The index of the tile of the plant
+///
+/// remain_count: The number of operators which may be evaluated
+fn logic_at(operator: Logic, index: usize, remain_count: &mut usize) -> bool {
+    let logic = vec![operator, Logic::True, Logic::False];
+    let program = Program::new(operands(), logic, Vec::new(), Vec::new(), Vec::new());
+
+    return with_data(program, LEAF, index, |data| {
+        Logic::apply_index(data, 0, remain_count)
+    });
+}
+
+/// Applies a single logic operator on the center tile without a limit
+///
+/// # Parameters
+///
+/// operator: The operator to apply
+fn logic(operator: Logic) -> bool {
+    let mut count = UNLIMITED;
+    return logic_at(operator, CENTER, &mut count);
+}
+
+/// Applies the first action of a program with the logic operators True and
+/// False at index 0 and 1, the arithmetic operators operands() and a single
+/// spread bulk and bridge
+///
+/// # Parameters
+///
+/// action: The actions of the program
+///
+/// bulk_id: The type id of the bulk of the plant
+///
+/// remain_count: The number of operators which may be evaluated
+fn decide_with(action: Vec<Action>, bulk_id: usize, remain_count: &mut usize) -> Decision {
+    let program = Program::new(
+        operands(),
+        vec![Logic::True, Logic::False],
+        action,
+        vec![SpreadBulk::new(1, 1, 4, 4, 0)],
+        vec![SpreadBridge::new(0, 0, 0, 0)],
+    );
+
+    return with_data(program, bulk_id, CENTER, |data| {
+        let mut decision = Decision::new();
+        Action::apply_index(data, 0, remain_count, &mut decision);
+        return decision;
+    });
+}
+
+/// Applies the first action of a program for a leaf without a limit
+///
+/// # Parameters
+///
+/// action: The actions of the program
+fn decide(action: Vec<Action>) -> Decision {
+    let mut count = UNLIMITED;
+    return decide_with(action, LEAF, &mut count);
+}
+
+/// Checks that two values are equal up to rounding errors
+///
+/// # Parameters
+///
+/// value: The computed value
+///
+/// expected: The expected value
+fn assert_close(value: f64, expected: f64) {
+    assert!(
+        (value - expected).abs() < 1e-12,
+        "Expected {} but received {}",
+        expected,
+        value
+    );
+}
+
+/// Checks that a decision does nothing
+///
+/// # Parameters
+///
+/// decision: The decision to check
+fn assert_nothing(decision: &Decision) {
+    assert!(!decision.kill);
+    assert!(!decision.grow);
+    assert!(decision.spread.is_none());
+    assert!(decision.reserve.is_none());
+    assert!(decision.capacity_growth.is_none());
+    assert!(decision.upgrade.is_none());
+    assert!(decision.prune.is_none());
+}
+
+/// The tile index of the neighbor of the center tile in every direction
+fn center_neighbors() -> [(NeighborDirection, usize); 6] {
+    return [
+        (NeighborDirection::Right, 5),
+        (NeighborDirection::UpRight, 2),
+        (NeighborDirection::UpLeft, 1),
+        (NeighborDirection::Left, 3),
+        (NeighborDirection::DownLeft, 7),
+        (NeighborDirection::DownRight, 8),
+    ];
+}
+
+#[test]
+fn ids_round_trip() {
+    for id in 0..Arithmetic::COUNT {
+        let operator = Arithmetic::from_id(id, (1, 2));
+        assert_eq!(operator.get_id(), id);
+        assert_eq!(
+            Arithmetic::from_id(id, operator.get_indices()),
+            operator,
+            "{:?}",
+            operator
+        );
+    }
+    for id in 0..Logic::COUNT {
+        let operator = Logic::from_id(id, (1, 2));
+        assert_eq!(operator.get_id(), id);
+        assert_eq!(
+            Logic::from_id(id, operator.get_indices()),
+            operator,
+            "{:?}",
+            operator
+        );
+    }
+    for id in 0..Action::COUNT {
+        let operator = Action::from_id(id, (1, 2, 3));
+        assert_eq!(operator.get_id(), id);
+        assert_eq!(
+            Action::from_id(id, operator.get_indices()),
+            operator,
+            "{:?}",
+            operator
+        );
+    }
+    assert_eq!(
+        Arithmetic::from_id(Arithmetic::COUNT, (1, 2)),
+        Arithmetic::Zero
+    );
+    assert_eq!(Logic::from_id(Logic::COUNT, (1, 2)), Logic::False);
+    assert_eq!(Action::from_id(Action::COUNT, (1, 2, 3)), Action::None);
+}
+
+#[test]
+fn directions_round_trip() {
+    for dir in NeighborDirection::collection() {
+        let id = Arithmetic::TileLightGradient(*dir).get_id();
+        let operator = Arithmetic::from_id(id, (dir.index(), 0));
+        assert_eq!(operator, Arithmetic::TileLightGradient(*dir));
+        assert_eq!(
+            Logic::from_id(Logic::TileFree(*dir).get_id(), (dir.index(), 0)),
+            Logic::TileFree(*dir)
+        );
+        assert_eq!(
+            Action::from_id(Action::Spread(0, 0, *dir).get_id(), (1, 2, dir.index())),
+            Action::Spread(1, 2, *dir)
+        );
+        assert_eq!(
+            Action::from_id(Action::PruneBridge(*dir).get_id(), (dir.index(), 0, 0)),
+            Action::PruneBridge(*dir)
+        );
+    }
+}
+
+#[test]
+fn arithmetic_constants() {
+    assert_eq!(arithmetic(Arithmetic::Zero), 0.0);
+    assert_eq!(arithmetic(Arithmetic::One), 1.0);
+}
+
+#[test]
+fn arithmetic_single_operand() {
+    assert_eq!(arithmetic(Arithmetic::Double(0)), 6.0);
+    assert_eq!(arithmetic(Arithmetic::Half(0)), 1.5);
+    assert_eq!(arithmetic(Arithmetic::Increment(0)), 4.0);
+    assert_eq!(arithmetic(Arithmetic::Decrement(0)), 2.0);
+    assert_eq!(arithmetic(Arithmetic::Neg(0)), -3.0);
+    assert_eq!(arithmetic(Arithmetic::MinZero(0)), 0.0);
+    assert_eq!(arithmetic(Arithmetic::MinZero(3)), -3.0);
+    assert_eq!(arithmetic(Arithmetic::MinOne(0)), 1.0);
+    assert_eq!(arithmetic(Arithmetic::MinOne(3)), -3.0);
+    assert_eq!(arithmetic(Arithmetic::MaxZero(0)), 3.0);
+    assert_eq!(arithmetic(Arithmetic::MaxZero(3)), 0.0);
+    assert_eq!(arithmetic(Arithmetic::MaxOne(0)), 3.0);
+    assert_eq!(arithmetic(Arithmetic::MaxOne(3)), 1.0);
+}
+
+#[test]
+fn arithmetic_two_operands() {
+    assert_eq!(arithmetic(Arithmetic::Add(0, 1)), 10.0);
+    assert_eq!(arithmetic(Arithmetic::Sub(0, 1)), -4.0);
+    assert_eq!(arithmetic(Arithmetic::Mul(0, 1)), 21.0);
+    assert_close(arithmetic(Arithmetic::Div(0, 1)), 3.0 / 7.0);
+    assert_eq!(arithmetic(Arithmetic::Mod(1, 0)), 1.0);
+    assert_eq!(arithmetic(Arithmetic::Mod(3, 1)), 4.0);
+    assert_eq!(arithmetic(Arithmetic::Min(0, 1)), 3.0);
+    assert_eq!(arithmetic(Arithmetic::Max(0, 1)), 7.0);
+    assert_eq!(arithmetic(Arithmetic::Mean(0, 1)), 5.0);
+}
+
+#[test]
+fn arithmetic_tile() {
+    assert_close(arithmetic(Arithmetic::TileLight), 0.4);
+    assert_close(arithmetic(Arithmetic::TileTransparency), 0.8);
+    assert_close(arithmetic(Arithmetic::TileWater), 0.8);
+    assert_close(arithmetic(Arithmetic::TileNutrients), 0.3);
+
+    for (dir, index) in center_neighbors() {
+        let index = index as f64;
+        assert_close(
+            arithmetic(Arithmetic::TileLightGradient(dir)),
+            0.1 * index - 0.4,
+        );
+        assert_close(
+            arithmetic(Arithmetic::TileTransparencyGradient(dir)),
+            0.2 - 0.05 * index,
+        );
+        assert_close(
+            arithmetic(Arithmetic::TileWaterGradient(dir)),
+            0.2 * index - 0.8,
+        );
+    }
+}
+
+#[test]
+fn arithmetic_tile_edges() {
+    let at = |operator, index| {
+        let mut count = UNLIMITED;
+        return arithmetic_at(operator, index, &mut count);
+    };
+
+    // Above the top row is the sun
+    let up = NeighborDirection::UpLeft;
+    assert_close(at(Arithmetic::TileLightGradient(up), TOP), 1.0 - 0.1);
+    assert_close(at(Arithmetic::TileTransparencyGradient(up), TOP), 0.05);
+    assert_eq!(at(Arithmetic::TileWaterGradient(up), TOP), 0.0);
+
+    // Beyond the other edges there is nothing
+    let left = NeighborDirection::Left;
+    assert_eq!(at(Arithmetic::TileLightGradient(left), EDGE), 0.0);
+    assert_eq!(at(Arithmetic::TileTransparencyGradient(left), EDGE), 0.0);
+    assert_eq!(at(Arithmetic::TileWaterGradient(left), EDGE), 0.0);
+}
+
+#[test]
+fn arithmetic_plant() {
+    assert_eq!(arithmetic(Arithmetic::PlantAge), 3.0);
+    assert_eq!(arithmetic(Arithmetic::PlantCumAge), 7.0);
+    assert_eq!(arithmetic(Arithmetic::PlantEnergyCapacity), 10.0);
+    assert_eq!(arithmetic(Arithmetic::PlantEnergyReserve), 2.0);
+    assert_eq!(arithmetic(Arithmetic::PlantEnergy), 5.0);
+    assert_eq!(arithmetic(Arithmetic::PlantEnergyChange), 1.0);
+    assert_eq!(arithmetic(Arithmetic::PlantEnergySelf), 2.0);
+    assert_eq!(arithmetic(Arithmetic::PlantEnergySelfChange), 0.0);
+    assert_eq!(arithmetic(Arithmetic::PlantEnergyShare), 0.5);
+    assert_close(
+        arithmetic(Arithmetic::PlantEnergyShareChange),
+        0.5 - 2.0 / 6.0,
+    );
+}
+
+#[test]
+fn arithmetic_not_finite() {
+    assert_eq!(arithmetic(Arithmetic::Div(0, 2)), 0.0);
+    assert_eq!(arithmetic(Arithmetic::Div(2, 2)), 0.0);
+    assert_eq!(arithmetic(Arithmetic::Mod(0, 2)), 0.0);
+}
+
+#[test]
+fn arithmetic_out_of_range() {
+    assert_eq!(arithmetic(Arithmetic::Double(99)), 0.0);
+    assert_eq!(arithmetic(Arithmetic::Increment(99)), 1.0);
+    assert_eq!(arithmetic(Arithmetic::Sub(99, 0)), -3.0);
+    assert_eq!(arithmetic(Arithmetic::Add(0, usize::MAX)), 3.0);
+}
+
+#[test]
+fn arithmetic_remain_count() {
+    let mut count = 0;
+    assert_eq!(arithmetic_at(Arithmetic::One, CENTER, &mut count), 0.0);
+    assert_eq!(count, 0);
+
+    // The operator itself is evaluated but its operand is not
+    let mut count = 1;
+    assert_eq!(
+        arithmetic_at(Arithmetic::Increment(0), CENTER, &mut count),
+        1.0
+    );
+    assert_eq!(count, 0);
+
+    // Only the first operand fits
+    let mut count = 2;
+    assert_eq!(
+        arithmetic_at(Arithmetic::Add(0, 1), CENTER, &mut count),
+        3.0
+    );
+    assert_eq!(count, 0);
+
+    let mut count = 10;
+    assert_eq!(
+        arithmetic_at(Arithmetic::Add(0, 1), CENTER, &mut count),
+        10.0
+    );
+    assert_eq!(count, 7);
+}
+
+#[test]
+fn logic_constants() {
+    assert!(!logic(Logic::False));
+    assert!(logic(Logic::True));
+}
+
+#[test]
+fn logic_boolean() {
+    assert!(logic(Logic::And(1, 1)));
+    assert!(!logic(Logic::And(1, 2)));
+    assert!(logic(Logic::Or(1, 2)));
+    assert!(!logic(Logic::Or(2, 2)));
+    assert!(logic(Logic::Xor(1, 2)));
+    assert!(!logic(Logic::Xor(1, 1)));
+    assert!(!logic(Logic::Not(1)));
+    assert!(logic(Logic::Not(2)));
+}
+
+#[test]
+fn logic_compare() {
+    // Index 4 is 0.4 and index 2 is 0, they are equal when rounded
+    assert!(!logic(Logic::Equal(4, 2)));
+    assert!(logic(Logic::Equal(0, 0)));
+    assert!(logic(Logic::EqualRound(4, 2)));
+    assert!(logic(Logic::NotEqual(4, 2)));
+    assert!(!logic(Logic::NotEqualRound(4, 2)));
+    assert!(logic(Logic::NotEqualRound(0, 1)));
+    assert!(logic(Logic::Greater(1, 0)));
+    assert!(!logic(Logic::Greater(0, 1)));
+    assert!(logic(Logic::Greater(4, 2)));
+    assert!(!logic(Logic::GreaterRound(4, 2)));
+    assert!(logic(Logic::GreaterOrEqual(0, 0)));
+    assert!(!logic(Logic::GreaterOrEqual(2, 4)));
+    assert!(logic(Logic::GreaterOrEqualRound(2, 4)));
+    assert!(logic(Logic::Less(0, 1)));
+    assert!(logic(Logic::Less(2, 4)));
+    assert!(!logic(Logic::LessRound(2, 4)));
+    assert!(logic(Logic::LessOrEqual(0, 0)));
+    assert!(!logic(Logic::LessOrEqual(4, 2)));
+    assert!(logic(Logic::LessOrEqualRound(4, 2)));
+}
+
+#[test]
+fn logic_sign() {
+    // Index 4 is 0.4, index 5 is -0.4 and both are 0 when rounded
+    assert!(logic(Logic::IsPositive(4)));
+    assert!(!logic(Logic::IsPositiveRound(4)));
+    assert!(!logic(Logic::IsNotNegative(5)));
+    assert!(logic(Logic::IsNotNegativeRound(5)));
+    assert!(!logic(Logic::IsZero(4)));
+    assert!(logic(Logic::IsZero(2)));
+    assert!(logic(Logic::IsZeroRound(4)));
+    assert!(!logic(Logic::IsNotPositive(4)));
+    assert!(logic(Logic::IsNotPositiveRound(4)));
+    assert!(logic(Logic::IsNegative(5)));
+    assert!(!logic(Logic::IsNegativeRound(5)));
+    assert!(logic(Logic::IsNegativeRound(3)));
+}
+
+#[test]
+fn logic_tile_free() {
+    for (dir, index) in center_neighbors() {
+        assert_eq!(logic(Logic::TileFree(dir)), index != OCCUPIED, "{:?}", dir);
+    }
+
+    // Neither the sun nor the outside of the map is free
+    let at = |operator, index| {
+        let mut count = UNLIMITED;
+        return logic_at(operator, index, &mut count);
+    };
+    assert!(!at(Logic::TileFree(NeighborDirection::UpLeft), TOP));
+    assert!(!at(Logic::TileFree(NeighborDirection::Left), EDGE));
+}
+
+#[test]
+fn logic_out_of_range() {
+    assert!(logic(Logic::Not(99)));
+    assert!(!logic(Logic::Or(99, 2)));
+    assert!(logic(Logic::IsZero(99)));
+    assert!(logic(Logic::Less(99, 0)));
+}
+
+#[test]
+fn logic_remain_count() {
+    let mut count = 0;
+    assert!(!logic_at(Logic::True, CENTER, &mut count));
+
+    // The operand is not evaluated and gives false
+    let mut count = 1;
+    assert!(logic_at(Logic::Not(1), CENTER, &mut count));
+    assert_eq!(count, 0);
+
+    // The arithmetic operands count as well
+    let mut count = 2;
+    assert!(logic_at(Logic::Greater(0, 1), CENTER, &mut count));
+    assert_eq!(count, 0);
+
+    let mut count = 10;
+    assert!(logic_at(Logic::And(1, 1), CENTER, &mut count));
+    assert_eq!(count, 7);
+}
+
+#[test]
+fn action_none() {
+    assert_nothing(&decide(vec![Action::None]));
+    assert_nothing(&decide(Vec::new()));
+}
+
+#[test]
+fn action_control() {
+    assert!(decide(vec![Action::If(0, 1), Action::Kill]).kill);
+    assert!(!decide(vec![Action::If(1, 1), Action::Kill]).kill);
+
+    let if_else = |condition| {
+        decide(vec![
+            Action::IfElse(condition, 1, 2),
+            Action::Kill,
+            Action::GrowCapacity(0),
+        ])
+    };
+    let decision = if_else(0);
+    assert!(decision.kill);
+    assert!(decision.capacity_growth.is_none());
+    let decision = if_else(1);
+    assert!(!decision.kill);
+    assert_eq!(decision.capacity_growth, Some(3.0));
+
+    let decision = decide(vec![
+        Action::Both(1, 2),
+        Action::Kill,
+        Action::SetReserve(4),
+    ]);
+    assert!(decision.kill);
+    assert_close(decision.reserve.unwrap(), 0.4);
+}
+
+#[test]
+fn action_kill_and_grow() {
+    assert!(decide(vec![Action::Kill]).kill);
+
+    // Only ripe seeds can start growing
+    assert!(!decide(vec![Action::Grow]).grow);
+    let mut count = UNLIMITED;
+    assert!(decide_with(vec![Action::Grow], RIPE_SEED, &mut count).grow);
+}
+
+#[test]
+fn action_spread() {
+    let decision = decide(vec![Action::Spread(0, 0, NeighborDirection::UpLeft)]);
+    let (plant, energy, dir) = decision.spread.unwrap();
+    assert_eq!(dir, NeighborDirection::UpLeft);
+    assert_eq!(energy, 3.0);
+    assert!(matches!(plant.bulk, bulk::Bulk::SugarBulb(_)));
+    assert_eq!(plant.energy_capacity, 7.0);
+    assert_close(plant.energy_reserve, 7.0 * 0.4);
+    assert!(plant.bridges.get(&NeighborDirection::DownRight).is_some());
+    assert_eq!(plant.cum_age, 7);
+
+    // The first spread is kept
+    let decision = decide(vec![
+        Action::Both(1, 2),
+        Action::Spread(0, 0, NeighborDirection::Left),
+        Action::Spread(0, 0, NeighborDirection::Right),
+    ]);
+    assert_eq!(decision.spread.unwrap().2, NeighborDirection::Left);
+
+    // Without a spread bulk or bridge nothing is spread
+    assert!(
+        decide(vec![Action::Spread(1, 0, NeighborDirection::Left)])
+            .spread
+            .is_none()
+    );
+    assert!(
+        decide(vec![Action::Spread(0, 1, NeighborDirection::Left)])
+            .spread
+            .is_none()
+    );
+}
+
+#[test]
+fn action_energy() {
+    // The reserve is a fraction of the capacity
+    assert_close(decide(vec![Action::SetReserve(4)]).reserve.unwrap(), 0.4);
+    assert_eq!(decide(vec![Action::SetReserve(1)]).reserve, Some(1.0));
+    assert_eq!(decide(vec![Action::SetReserve(3)]).reserve, Some(0.0));
+
+    assert_eq!(
+        decide(vec![Action::GrowCapacity(1)]).capacity_growth,
+        Some(7.0)
+    );
+    assert_eq!(
+        decide(vec![Action::GrowCapacity(3)]).capacity_growth,
+        Some(0.0)
+    );
+
+    // The first decisions are kept
+    let decision = decide(vec![
+        Action::Both(1, 2),
+        Action::Both(3, 4),
+        Action::Both(5, 6),
+        Action::SetReserve(4),
+        Action::GrowCapacity(0),
+        Action::SetReserve(2),
+        Action::GrowCapacity(1),
+    ]);
+    assert_close(decision.reserve.unwrap(), 0.4);
+    assert_eq!(decision.capacity_growth, Some(3.0));
+}
+
+#[test]
+fn action_bridges() {
+    let decision = decide(vec![
+        Action::Both(1, 4),
+        Action::Both(2, 3),
+        Action::UpgradeBridge(NeighborDirection::UpRight),
+        Action::PruneBridge(NeighborDirection::DownLeft),
+        Action::Both(5, 6),
+        Action::UpgradeBridge(NeighborDirection::Left),
+        Action::PruneBridge(NeighborDirection::Right),
+    ]);
+    assert_eq!(decision.upgrade, Some(NeighborDirection::UpRight));
+    assert_eq!(decision.prune, Some(NeighborDirection::DownLeft));
+}
+
+#[test]
+fn action_out_of_range() {
+    assert_nothing(&decide(vec![Action::Both(7, 99)]));
+    assert_nothing(&decide(vec![Action::If(99, 1), Action::Kill]));
+    assert!(decide(vec![Action::IfElse(99, 2, 1), Action::Kill]).kill);
+}
+
+#[test]
+fn action_remain_count() {
+    let actions = || vec![Action::Both(1, 2), Action::Kill, Action::Grow];
+
+    let mut count = 0;
+    assert_nothing(&decide_with(actions(), RIPE_SEED, &mut count));
+
+    // Only the first two actions fit
+    let mut count = 2;
+    let decision = decide_with(actions(), RIPE_SEED, &mut count);
+    assert!(decision.kill);
+    assert!(!decision.grow);
+    assert_eq!(count, 0);
+
+    let mut count = 10;
+    let decision = decide_with(actions(), RIPE_SEED, &mut count);
+    assert!(decision.kill);
+    assert!(decision.grow);
+    assert_eq!(count, 7);
+}
+
+#[test]
+fn run_operator_limit() {
+    let settings = Settings::new();
+    let run = |action| {
+        let program = Program::new(operands(), Vec::new(), action, Vec::new(), Vec::new());
+        return with_data(program.clone(), LEAF, CENTER, |data| {
+            program.run(&settings, data)
+        });
+    };
+
+    // The first action calls itself forever so the second is never reached
+    let decision = run(vec![Action::Both(0, 1), Action::GrowCapacity(0)]);
+    assert!(decision.capacity_growth.is_none());
+
+    let decision = run(vec![Action::Both(1, 0), Action::GrowCapacity(0)]);
+    assert_eq!(decision.capacity_growth, Some(3.0));
+}
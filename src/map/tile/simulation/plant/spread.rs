@@ -5,8 +5,20 @@ use super::{NeighborDirection, Plant};
 pub enum Spread {
     /// The plant is not attempting to spread
     Nothing,
-    /// The plant has announced its intensions of spreading, holds the direction, the non-mutated offspring and the energy used
+    /// The plant has announced its intensions of spreading, holds the non-mutated offspring, the energy used and the direction from this plant
     Trying(Box<(Plant, f64, NeighborDirection)>),
     /// The plant it waiting to see if the spread was successful, holds the direction and the energy used
     Waiting(Box<(f64, NeighborDirection)>),
 }
+
+impl Spread {
+    /// Gets the energy set aside for spreading which is still held by the
+    /// mother plant
+    pub fn get_energy(&self) -> f64 {
+        return match self {
+            Self::Nothing => 0.0,
+            Self::Trying(value) => value.1,
+            Self::Waiting(value) => value.0,
+        };
+    }
+}
@@ -1,4 +1,9 @@
-use super::{Neighbor, NeighborDirection, Plant, Settings, Spread, TileData, TileNeighbors};
+use rand::rngs::StdRng;
+
+use super::{
+    Bulk, EnergyBalance, Genealogy, Neighbor, NeighborDirection, Plant, PlantEvent, Pollen,
+    Settings, Spread, TileData, TileNeighbors,
+};
 
 /// The state of plant growth in a tile
 #[derive(Clone, Debug)]
@@ -29,6 +34,185 @@ impl State {
         };
     }
 
+    /// Gets the water level of the tile of this plant for the next step, only
+    /// living plants hold water
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The settings for the map
+    ///
+    /// tile: The tile data of the tile of this plant
+    ///
+    /// neighbors: References to all the neighbors of this tile
+    pub fn get_water_next(
+        &self,
+        map_settings: &Settings,
+        tile: &TileData,
+        neighbors: &TileNeighbors,
+    ) -> f64 {
+        return match self {
+            Self::Nothing | Self::Building(_) => 0.0,
+            Self::Occupied(plant) => plant.get_water_next(map_settings, tile, neighbors),
+        };
+    }
+
+    /// Gets the nutrients extracted from the tile by the plant this step, only
+    /// living plants extract nutrients
+    ///
+    /// # Parameters
+    ///
+    /// map_settings: The settings for the map
+    ///
+    /// tile: The tile data of the tile of this plant
+    pub fn get_nutrient_extraction(&self, map_settings: &Settings, tile: &TileData) -> f64 {
+        return match self {
+            Self::Nothing | Self::Building(_) => 0.0,
+            Self::Occupied(plant) => plant.get_nutrient_extraction(map_settings, tile),
+        };
+    }
+
+    /// Gets the change of the plant since an earlier state of the same tile,
+    /// plants moving between tiles are not seen as a change
+    ///
+    /// # Parameters
+    ///
+    /// previous: The earlier state of the tile
+    pub fn get_event(&self, previous: &Self) -> Option<PlantEvent> {
+        return match (previous, self) {
+            (Self::Building(_), Self::Occupied(plant)) if plant.alive => Some(PlantEvent::Born),
+            (Self::Occupied(previous), Self::Occupied(plant)) if previous.alive && !plant.alive => {
+                Some(PlantEvent::Died)
+            }
+            (Self::Occupied(previous), Self::Occupied(plant))
+                if matches!(previous.bulk, Bulk::Seed(_))
+                    && matches!(plant.bulk, Bulk::RipeSeed(_)) =>
+            {
+                Some(PlantEvent::SeedRipened)
+            }
+            _ => None,
+        };
+    }
+
+    /// Checks if there is a dead plant which is removed in the next step
+    pub fn is_dead(&self) -> bool {
+        return match self {
+            Self::Nothing | Self::Building(_) => false,
+            Self::Occupied(plant) => !plant.is_alive(),
+        };
+    }
+
+    /// Checks if this is a ripe seed which is free to fall
+    pub fn is_falling(&self) -> bool {
+        return match self {
+            Self::Nothing | Self::Building(_) => false,
+            Self::Occupied(plant) => plant.is_falling(),
+        };
+    }
+
+    /// Gets a grain of pollen from the plant if it is a flower able to emit
+    /// pollen
+    pub fn get_pollen(&self) -> Option<Pollen> {
+        return match self {
+            Self::Nothing | Self::Building(_) => None,
+            Self::Occupied(plant) => plant.get_pollen(),
+        };
+    }
+
+    /// Fertilizes the plant with pollen if it is a flower able to receive it,
+    /// returns false if the pollen was not taken
+    ///
+    /// # Parameters
+    ///
+    /// pollen: The pollen to fertilize with
+    ///
+    /// map_settings: The settings for the map
+    ///
+    /// rng: The random number generator to draw the genome of the offspring
+    /// from
+    pub fn fertilize(
+        &mut self,
+        pollen: &Pollen,
+        map_settings: &Settings,
+        rng: &mut StdRng,
+    ) -> bool {
+        return match self {
+            Self::Nothing | Self::Building(_) => false,
+            Self::Occupied(plant) => plant.fertilize(pollen, map_settings, rng),
+        };
+    }
+
+    /// Checks if the plant is a living thorn
+    pub fn is_thorn(&self) -> bool {
+        return match self {
+            Self::Nothing | Self::Building(_) => false,
+            Self::Occupied(plant) => plant.is_thorn(),
+        };
+    }
+
+    /// Checks if the plant is a living leaf
+    pub fn is_leaf(&self) -> bool {
+        return match self {
+            Self::Nothing | Self::Building(_) => false,
+            Self::Occupied(plant) => plant.is_leaf(),
+        };
+    }
+
+    /// Kills the plant if it is fully grown and alive, returns true if it
+    /// was killed
+    pub fn kill(&mut self) -> bool {
+        return match self {
+            Self::Occupied(plant) if plant.is_alive() => {
+                plant.kill();
+                true
+            }
+            _ => false,
+        };
+    }
+
+    /// Gets the energy stored in the plant including the energy set aside for
+    /// spreading, a plant being built holds no energy since its energy is
+    /// still held by the mother plant until the plant is built
+    pub fn get_stored_energy(&self) -> f64 {
+        return match self {
+            Self::Nothing | Self::Building(_) => 0.0,
+            Self::Occupied(plant) => plant.get_stored_energy(),
+        };
+    }
+
+    /// Gets the energy herbivores can graze from the plant, None if there is
+    /// no living plant
+    pub fn get_forage(&self) -> Option<f64> {
+        return match self {
+            Self::Nothing | Self::Building(_) => None,
+            Self::Occupied(plant) => plant.get_forage(),
+        };
+    }
+
+    /// Lets herbivores eat a fraction of the energy of the plant
+    ///
+    /// # Parameters
+    ///
+    /// damage: The fraction of the energy to eat
+    pub fn graze(&mut self, damage: f64) {
+        if let Self::Occupied(plant) = self {
+            plant.graze(damage);
+        }
+    }
+
+    /// Registers the plant as the start of a new lineage if it is fully grown
+    /// and does not belong to one yet
+    ///
+    /// # Parameters
+    ///
+    /// genealogy: The genealogy of the map to register the lineage in
+    ///
+    /// time: The current simulation step
+    pub fn register_lineage(&mut self, genealogy: &mut Genealogy, time: usize) {
+        if let Self::Occupied(plant) = self {
+            plant.register_lineage(genealogy, time);
+        }
+    }
+
     /// Forwards the state to the next simulation step
     ///
     /// # Parameters
@@ -38,16 +222,23 @@ impl State {
     /// tile: The tile data of the tile of this plant
     ///
     /// neighbors: References to all the neighbors of this tile
+    ///
+    /// rng: The random number generator of this tile for this step
+    ///
+    /// balance: The energy balance of the step to add the changes of the
+    /// plant to
     pub fn forward(
         &self,
         map_settings: &Settings,
         tile: &TileData,
         neighbors: &TileNeighbors,
+        rng: &mut StdRng,
+        balance: &mut EnergyBalance,
     ) -> Self {
         return match self {
-            Self::Nothing => Self::try_spread(map_settings, neighbors),
-            Self::Building(values) => Self::try_build(map_settings, values, neighbors),
-            Self::Occupied(plant) => match plant.forward(map_settings, tile, neighbors) {
+            Self::Nothing => Self::try_spread(map_settings, tile, neighbors, rng),
+            Self::Building(values) => Self::try_build(map_settings, values, neighbors, balance),
+            Self::Occupied(plant) => match plant.forward(map_settings, tile, neighbors, balance) {
                 Some(plant) => Self::Occupied(plant),
                 None => Self::Nothing,
             },
@@ -55,31 +246,46 @@ impl State {
     }
 
     /// See if any neighbors are trying to spread and mutates any attempt at
-    /// spreading
+    /// spreading, if several neighbors compete the one with the lowest update
+    /// priority wins with ties broken by direction. Offspring which can only
+    /// exist in the ground are ignored outside of the ground
     ///
     /// # Parameters
     ///
     /// map_settings: The settings for the map
     ///
+    /// tile: The tile data of the tile to spread to
+    ///
     /// neighbors: References to all the neighbors of this tile
-    fn try_spread(map_settings: &Settings, neighbors: &TileNeighbors) -> Self {
+    ///
+    /// rng: The random number generator to draw the mutations from
+    fn try_spread(
+        map_settings: &Settings,
+        tile: &TileData,
+        neighbors: &TileNeighbors,
+        rng: &mut StdRng,
+    ) -> Self {
         return if let Some((plant, energy, dir)) = NeighborDirection::collection()
             .iter()
             .filter_map(|dir| {
-                if let Neighbor::Tile(tile) = neighbors.get(dir) {
-                    if let State::Occupied(plant) = &tile.plant {
+                if let Neighbor::Tile(neighbor) = neighbors.get(dir) {
+                    if let State::Occupied(plant) = &neighbor.plant {
                         if let Spread::Trying(spread) = &plant.spread {
-                            if &spread.2 == dir {
-                                return Some(spread.as_ref());
+                            if spread.2 == dir.opposite()
+                                && !tile.barren
+                                && (tile.ground || !spread.0.requires_ground())
+                            {
+                                return Some((neighbors.get_priority(dir), spread.as_ref()));
                             }
                         }
                     }
                 }
                 return None;
             })
-            .min_by_key(|value| value.2.id())
+            .min_by_key(|(priority, value)| (*priority, value.2.id()))
+            .map(|(_, value)| value)
         {
-            Self::Building((plant.mutate(map_settings), *energy, *dir))
+            Self::Building((plant.mutate(map_settings, rng), *energy, dir.opposite()))
         } else {
             Self::Nothing
         };
@@ -95,10 +301,13 @@ impl State {
     /// input: The tile building input
     ///
     /// neighbors: All neighbor tiles
+    ///
+    /// balance: The energy balance of the step to add the building costs to
     fn try_build(
         map_settings: &Settings,
         input: &(Plant, f64, NeighborDirection),
         neighbors: &TileNeighbors,
+        balance: &mut EnergyBalance,
     ) -> Self {
         if let Neighbor::Tile(tile) = neighbors.get(&input.2) {
             if let State::Occupied(plant) = &tile.plant {
@@ -112,9 +321,12 @@ impl State {
                         );
                     let plant_energy = input.1 - cost_energy;
                     if plant_energy < 0.0 {
+                        balance.wasted += input.1;
                         return Self::Nothing;
                     }
                     new_plant.energy = plant_energy.min(new_plant.energy_capacity);
+                    balance.spent += cost_energy;
+                    balance.wasted += plant_energy - new_plant.energy;
 
                     return Self::Occupied(new_plant);
                 }
@@ -0,0 +1,136 @@
+use crate::types;
+
+use super::{BridgeInfo, TileInfo, species::SpeciesSet, tile::Tile};
+
+/// A read-only view of a single tile of the map, it borrows the map such that
+/// tools can look at the tiles without copying them or being able to change
+/// them
+#[derive(Clone, Copy, Debug)]
+pub struct TileView<'a> {
+    /// The position of the tile
+    pos: types::Index,
+    /// The tile to view
+    tile: &'a Tile,
+    /// The species of all clustered lineages
+    species: &'a SpeciesSet,
+}
+
+impl<'a> TileView<'a> {
+    /// Constructs a new view of a tile
+    ///
+    /// # Parameters
+    ///
+    /// pos: The position of the tile
+    ///
+    /// tile: The tile to view
+    ///
+    /// species: The species of all clustered lineages
+    pub(super) fn new(pos: types::Index, tile: &'a Tile, species: &'a SpeciesSet) -> Self {
+        return Self { pos, tile, species };
+    }
+
+    /// Retrieves the position of the tile
+    pub fn get_pos(&self) -> types::Index {
+        return self.pos;
+    }
+
+    /// Retrieves the light level of the tile
+    pub fn get_light(&self) -> f64 {
+        return self.tile.get_light();
+    }
+
+    /// Retrieves the part of the light level which is diffuse light scattered
+    /// by the sky
+    pub fn get_diffuse(&self) -> f64 {
+        return self.tile.get_diffuse();
+    }
+
+    /// Retrieves the light transparency of the tile
+    pub fn get_transparency(&self) -> f64 {
+        return self.tile.get_transparency();
+    }
+
+    /// Retrieves the water level of the tile
+    pub fn get_water(&self) -> f64 {
+        return self.tile.get_water();
+    }
+
+    /// Retrieves the nutrient concentration of the tile, always 0 if it is
+    /// not ground
+    pub fn get_nutrients(&self) -> f64 {
+        return self.tile.get_nutrients();
+    }
+
+    /// Retrieves the temperature of the tile
+    pub fn get_temperature(&self) -> f64 {
+        return self.tile.get_temperature();
+    }
+
+    /// Checks if the tile is part of the ground
+    pub fn is_ground(&self) -> bool {
+        return self.tile.is_ground();
+    }
+
+    /// Checks if there is a plant on the tile, even one still being built
+    pub fn has_plant(&self) -> bool {
+        return !self.tile.is_free();
+    }
+
+    /// Retrieves the name of the bulk type of the plant on the tile, None if
+    /// there is no fully grown plant
+    pub fn get_bulk(&self) -> Option<&'static str> {
+        return self.tile.get_plant_bulk();
+    }
+
+    /// Retrieves the energy of the plant on the tile, None if there is no
+    /// fully grown plant
+    pub fn get_energy(&self) -> Option<f64> {
+        return self.tile.get_plant_energy();
+    }
+
+    /// Retrieves the age of the plant tile and the age of the entire plant,
+    /// None if there is no living plant
+    pub fn get_age(&self) -> Option<(usize, usize)> {
+        return self.tile.get_plant_age();
+    }
+
+    /// Retrieves the id of the lineage of the plant, None if there is no
+    /// living plant or it has not been registered yet
+    pub fn get_lineage(&self) -> Option<usize> {
+        return self.tile.get_plant_lineage();
+    }
+
+    /// Retrieves the id of the species of the plant, None if there is no
+    /// living plant or its lineage has not been clustered yet
+    pub fn get_species(&self) -> Option<usize> {
+        return self
+            .get_lineage()
+            .and_then(|lineage| self.species.get(lineage));
+    }
+
+    /// Gets all bridges connecting the plant to its neighbors, empty if there
+    /// is no fully grown plant
+    pub fn get_bridges(&self) -> Vec<BridgeInfo> {
+        return self.tile.get_plant_bridge_info();
+    }
+
+    /// Writes the genome of the plant in the genome text format, None if
+    /// there is no fully grown plant
+    ///
+    /// # Parameters
+    ///
+    /// simplify: True if operators which can never be evaluated are left out
+    pub fn get_genome(&self, simplify: bool) -> Option<String> {
+        return self.tile.get_plant_genome(simplify);
+    }
+
+    /// Gets a full snapshot of the state of the tile
+    pub fn get_info(&self) -> TileInfo {
+        let mut info = self.tile.get_info(&self.pos);
+        if let Some(plant) = &mut info.plant {
+            plant.species = plant.lineage.and_then(|lineage| self.species.get(lineage));
+        }
+
+        return info;
+    }
+}
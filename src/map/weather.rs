@@ -0,0 +1,106 @@
+use rand::Rng;
+
+use super::{SimRng, settings::weather::Settings, sun};
+
+/// The clouds above the map, the cloud cover is a periodic 1D Perlin noise
+/// over the columns which drifts sideways with time
+#[derive(Clone, Debug, PartialEq)]
+pub struct Weather {
+    /// The cloud coverage of every column from 0 for a clear sky to 1 for a
+    /// full cloud
+    coverage: Vec<f64>,
+}
+
+impl Weather {
+    /// Constructs a new clear sky
+    ///
+    /// # Parameters
+    ///
+    /// width: The number of columns of the map
+    pub fn new(width: usize) -> Self {
+        return Self {
+            coverage: vec![0.0; width],
+        };
+    }
+
+    /// Moves the clouds to their position at a given time step
+    ///
+    /// # Parameters
+    ///
+    /// settings: The weather settings
+    ///
+    /// time: The time step
+    ///
+    /// rng: The random number generator of the simulation
+    pub fn update(&mut self, settings: &Settings, time: usize, rng: &SimRng) {
+        if settings.density <= 0.0 {
+            self.coverage.fill(0.0);
+            return;
+        }
+
+        // The noise is periodic over the width of the map such that the
+        // clouds wrap around like the map does
+        let width = self.coverage.len() as f64;
+        let cells = (width / settings.scale).round().max(1.0) as usize;
+        let gradients = (0..cells)
+            .map(|cell| {
+                rng.get(0, SimRng::STREAM_WEATHER, cell)
+                    .random_range(-1.0..=1.0)
+            })
+            .collect::<Vec<f64>>();
+
+        let offset = settings.speed * time as f64;
+        for (column, coverage) in self.coverage.iter_mut().enumerate() {
+            let position = ((column as f64 - offset) / width).rem_euclid(1.0) * cells as f64;
+            let noise = perlin(&gradients, position);
+
+            // The noise lies between -0.5 and 0.5, the density shifts it such
+            // that a density of 0 is always clear and 1 is always covered
+            *coverage = (noise + 2.0 * settings.density - 0.5).clamp(0.0, 1.0);
+        }
+    }
+
+    /// Shades the sun by the clouds
+    ///
+    /// # Parameters
+    ///
+    /// settings: The weather settings
+    ///
+    /// sun_tiles: The sun tiles for every column to shade
+    pub fn shade(&self, settings: &Settings, sun_tiles: &mut [sun::Tile]) {
+        for (tile, coverage) in sun_tiles.iter_mut().zip(self.coverage.iter()) {
+            tile.darken(1.0 - settings.opacity * coverage);
+        }
+    }
+
+    /// Retrieves the cloud coverage of a column
+    ///
+    /// # Parameters
+    ///
+    /// column: The column to get the coverage of
+    pub fn get_coverage(&self, column: usize) -> f64 {
+        return self.coverage[column];
+    }
+}
+
+/// Evaluates periodic 1D Perlin noise with one gradient per lattice point,
+/// the result lies between -0.5 and 0.5
+///
+/// # Parameters
+///
+/// gradients: The gradients at every lattice point
+///
+/// position: The position to evaluate at in units of lattice cells
+fn perlin(gradients: &[f64], position: f64) -> f64 {
+    let cell = position.floor();
+    let fraction = position - cell;
+    let index = cell as usize % gradients.len();
+    let next = (index + 1) % gradients.len();
+
+    // Blend the contributions of the surrounding lattice points smoothly
+    let left = gradients[index] * fraction;
+    let right = gradients[next] * (fraction - 1.0);
+    let fade = fraction * fraction * fraction * (fraction * (fraction * 6.0 - 15.0) + 10.0);
+
+    return left + fade * (right - left);
+}
@@ -13,6 +13,11 @@ pub struct RenderState {
     surface: wgpu::Surface<'static>,
     /// The configurations of the surface
     config: wgpu::SurfaceConfiguration,
+    /// The number of samples per pixel for anti-aliasing
+    sample_count: u32,
+    /// The multisampled color target which is resolved to the surface, None
+    /// if anti-aliasing is disabled
+    msaa_view: Option<wgpu::TextureView>,
 }
 
 impl RenderState {
@@ -22,13 +27,16 @@ impl RenderState {
     ///
     /// window: The window to use for the render state
     ///
+    /// sample_count: The requested number of samples per pixel for
+    /// anti-aliasing, it is lowered to the nearest count supported by the gpu
+    ///
     /// # Errors
     ///
     /// See NewRenderStateError for a description of the different errors which may occur
-    pub async fn new(window: &Arc<Window>) -> Result<Self, NewRenderStateError> {
+    pub async fn new(window: &Arc<Window>, sample_count: u32) -> Result<Self, NewRenderStateError> {
         // Get the size of the window
         let size = window.inner_size();
-        if size.width <= 0 || size.height <= 0 {
+        if size.width == 0 || size.height == 0 {
             return Err(NewRenderStateError::InvalidSize(size));
         }
 
@@ -56,7 +64,8 @@ impl RenderState {
         let (device, queue) = adapter
             .request_device(&wgpu::DeviceDescriptor {
                 label: Some("Request Device"),
-                required_features: wgpu::Features::empty(),
+                required_features: adapter.features()
+                    & wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES,
                 required_limits: wgpu::Limits::default(),
                 memory_hints: wgpu::MemoryHints::Performance,
                 trace: wgpu::Trace::Off,
@@ -87,12 +96,41 @@ impl RenderState {
         };
         surface.configure(&device, &config);
 
-        Ok(Self {
+        // Find the largest supported sample count not above the requested
+        // one, without adapter specific features only the guaranteed counts
+        // may be used
+        let format_features = if device
+            .features()
+            .contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES)
+        {
+            adapter.get_texture_format_features(surface_format)
+        } else {
+            surface_format.guaranteed_format_features(device.features())
+        };
+        let supported_counts = format_features.flags.supported_sample_counts();
+        let supported_count = supported_counts
+            .into_iter()
+            .filter(|count| *count <= sample_count)
+            .max()
+            .unwrap_or(1);
+        if supported_count != sample_count {
+            eprintln!(
+                "A sample count of {} is not supported, using {} instead",
+                sample_count, supported_count
+            );
+        }
+
+        let mut object = Self {
             device,
             queue,
             surface,
             config,
-        })
+            sample_count: supported_count,
+            msaa_view: None,
+        };
+        object.msaa_view = object.create_msaa_view(size.width, size.height);
+
+        Ok(object)
     }
 
     /// Called when the window has been resized
@@ -104,6 +142,7 @@ impl RenderState {
         self.config.width = new_size.width;
         self.config.height = new_size.height;
         self.surface.configure(&self.device, &self.config);
+        self.msaa_view = self.create_msaa_view(new_size.width, new_size.height);
     }
 
     /// Get a reference to the device
@@ -116,6 +155,18 @@ impl RenderState {
         &self.queue
     }
 
+    /// Gets the texture to render the next frame to, if the surface was lost
+    /// or is outdated then the surface is configured again such that the next
+    /// frame may succeed
+    pub fn get_current_texture(&self) -> Result<wgpu::SurfaceTexture, wgpu::SurfaceError> {
+        let texture = self.surface.get_current_texture();
+        if let Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) = &texture {
+            self.surface.configure(&self.device, &self.config);
+        }
+
+        texture
+    }
+
     /// Get a reference to the surface
     pub fn get_surface(&self) -> &wgpu::Surface<'_> {
         &self.surface
@@ -125,6 +176,73 @@ impl RenderState {
     pub fn get_config(&self) -> &wgpu::SurfaceConfiguration {
         &self.config
     }
+
+    /// Get the number of samples per pixel for anti-aliasing
+    pub fn get_sample_count(&self) -> u32 {
+        self.sample_count
+    }
+
+    /// Get a reference to the multisampled color target matching the size of
+    /// the surface, None if anti-aliasing is disabled
+    pub fn get_msaa_view(&self) -> Option<&wgpu::TextureView> {
+        self.msaa_view.as_ref()
+    }
+
+    /// Creates a multisampled color target to render into before resolving
+    /// to a texture of the same size, None if anti-aliasing is disabled
+    ///
+    /// # Parameters
+    ///
+    /// width: The width of the texture in pixels
+    ///
+    /// height: The height of the texture in pixels
+    pub fn create_msaa_view(&self, width: u32, height: u32) -> Option<wgpu::TextureView> {
+        if self.sample_count <= 1 {
+            return None;
+        }
+
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Texture: Multisampled"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: self.sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.config.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+
+        Some(texture.create_view(&wgpu::TextureViewDescriptor::default()))
+    }
+
+    /// Creates an offscreen texture to render into and copy back from, it has
+    /// the same format as the surface to be compatible with all pipelines
+    ///
+    /// # Parameters
+    ///
+    /// width: The width of the texture in pixels
+    ///
+    /// height: The height of the texture in pixels
+    pub fn create_capture_texture(&self, width: u32, height: u32) -> wgpu::Texture {
+        self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Texture: Capture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.config.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        })
+    }
 }
 
 /// The error types for when creating a new RenderState
@@ -0,0 +1,262 @@
+use std::{
+    fmt::{self, Write},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::map;
+
+mod recorder;
+pub use recorder::RunRecorder;
+
+mod stats_export;
+pub use stats_export::{StatsExporter, StatsFormat};
+
+mod stop;
+pub use stop::{StopAction, StopConditions};
+
+mod phylogeny;
+pub use phylogeny::{Phylogeny, TreeFormat};
+
+/// The reason a run was terminated
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EndReason {
+    /// The run reached its maximum number of steps
+    StepLimit,
+    /// All plants died out
+    Extinction,
+    /// The population grew above its limit
+    PopulationLimit,
+    /// The user closed the application
+    UserQuit,
+    /// The window could no longer be rendered
+    RenderError,
+}
+
+impl fmt::Display for EndReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(
+            f,
+            "{}",
+            match self {
+                Self::StepLimit => "The step limit was reached",
+                Self::Extinction => "All plants died out",
+                Self::PopulationLimit => "The population grew above its limit",
+                Self::UserQuit => "The run was stopped by the user",
+                Self::RenderError => "The window could no longer be rendered",
+            }
+        );
+    }
+}
+
+/// Something noteworthy which happened during a run
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Event {
+    /// The time step of the event
+    pub time: usize,
+    /// A human readable description of the event
+    pub description: String,
+}
+
+impl Event {
+    /// Constructs a new event
+    ///
+    /// # Parameters
+    ///
+    /// time: The time step of the event
+    ///
+    /// description: A human readable description of the event
+    pub fn new(time: usize, description: String) -> Self {
+        return Self { time, description };
+    }
+}
+
+/// A human readable summary of a finished run
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    /// The reason the run was terminated
+    reason: EndReason,
+    /// The seed of the simulation
+    seed: u64,
+    /// The statistics at the start of the run
+    start: map::Statistics,
+    /// The statistics at the end of the run
+    end: map::Statistics,
+    /// The statistics at the step with the highest population
+    peak: map::Statistics,
+    /// The statistics of every species at the end of the run
+    species: Vec<map::SpeciesStatistics>,
+    /// All events of the run sorted by time, including user annotations
+    events: Vec<Event>,
+    /// All thumbnails to embed given by a caption and a path relative to the
+    /// report
+    thumbnails: Vec<(String, PathBuf)>,
+}
+
+impl Report {
+    /// Constructs a new report for a finished run
+    ///
+    /// # Parameters
+    ///
+    /// reason: The reason the run was terminated
+    ///
+    /// recorder: The recorder which followed the run
+    ///
+    /// map: The map at the end of the run
+    pub fn new<S: map::sun::Intensity>(
+        reason: EndReason,
+        recorder: &RunRecorder,
+        map: &map::Map<S>,
+    ) -> Self {
+        let mut events = recorder.get_events().to_vec();
+        events.extend(map.get_annotations().get_all().iter().map(|annotation| {
+            Event::new(
+                annotation.time,
+                format!(
+                    "Note at tile ({}, {}): {}",
+                    annotation.pos.x, annotation.pos.y, annotation.text
+                ),
+            )
+        }));
+        events.extend(
+            map.get_catastrophes()
+                .get_history()
+                .iter()
+                .map(|(time, catastrophe)| Event::new(*time, catastrophe.to_string())),
+        );
+        events.sort_by_key(|event| event.time);
+
+        return Self {
+            reason,
+            seed: map.get_settings().seed,
+            start: *recorder.get_start(),
+            end: map.get_statistics(),
+            peak: *recorder.get_peak(),
+            species: map.get_species_statistics(),
+            events,
+            thumbnails: Vec::new(),
+        };
+    }
+
+    /// Adds a thumbnail to embed and returns the updated report
+    ///
+    /// # Parameters
+    ///
+    /// caption: The caption of the thumbnail
+    ///
+    /// path: The path of the thumbnail relative to the report
+    pub fn with_thumbnail(mut self, caption: &str, path: &Path) -> Self {
+        self.thumbnails
+            .push((caption.to_string(), path.to_path_buf()));
+
+        return self;
+    }
+
+    /// Writes the report as Markdown
+    pub fn to_markdown(&self) -> String {
+        // Writing to a string never fails
+        let mut text = String::new();
+        _ = self.write_markdown(&mut text);
+
+        return text;
+    }
+
+    /// Writes the report as Markdown to a file
+    ///
+    /// # Parameters
+    ///
+    /// path: The path of the file
+    ///
+    /// # Errors
+    ///
+    /// Returns the io error if the file could not be written
+    pub fn save(&self, path: &Path) -> Result<(), io::Error> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+
+        return fs::write(path, self.to_markdown());
+    }
+
+    /// Writes the report as Markdown
+    ///
+    /// # Parameters
+    ///
+    /// text: The text to write to
+    fn write_markdown(&self, text: &mut String) -> fmt::Result {
+        writeln!(text, "# Run report")?;
+        writeln!(text)?;
+        writeln!(
+            text,
+            "{} after {} steps (step {} to {}), the simulation seed was {}.",
+            self.reason,
+            self.end.time - self.start.time,
+            self.start.time,
+            self.end.time,
+            self.seed
+        )?;
+
+        writeln!(text)?;
+        writeln!(text, "## Final statistics")?;
+        writeln!(text)?;
+        writeln!(text, "| Statistic | Value |")?;
+        writeln!(text, "| --- | --- |")?;
+        writeln!(text, "| Step | {} |", self.end.time)?;
+        writeln!(text, "| Population | {} |", self.end.population)?;
+        writeln!(text, "| Energy | {:.3} |", self.end.energy)?;
+        writeln!(
+            text,
+            "| Peak population | {} at step {} |",
+            self.peak.population, self.peak.time
+        )?;
+
+        writeln!(text)?;
+        writeln!(text, "## Species")?;
+        writeln!(text)?;
+        if self.species.is_empty() {
+            writeln!(text, "No plants are left.")?;
+        } else {
+            writeln!(text, "| Species | Population | Energy |")?;
+            writeln!(text, "| --- | --- | --- |")?;
+            for species in self.species.iter() {
+                let name = match species.species {
+                    Some(id) => id.to_string(),
+                    None => "Without species".to_string(),
+                };
+                writeln!(
+                    text,
+                    "| {} | {} | {:.3} |",
+                    name, species.population, species.energy
+                )?;
+            }
+        }
+
+        writeln!(text)?;
+        writeln!(text, "## Timeline")?;
+        writeln!(text)?;
+        writeln!(text, "| Step | Event |")?;
+        writeln!(text, "| --- | --- |")?;
+        for event in self.events.iter() {
+            writeln!(
+                text,
+                "| {} | {} |",
+                event.time,
+                event.description.replace('|', "\\|")
+            )?;
+        }
+
+        if !self.thumbnails.is_empty() {
+            writeln!(text)?;
+            writeln!(text, "## Thumbnails")?;
+            for (caption, path) in self.thumbnails.iter() {
+                writeln!(text)?;
+                writeln!(text, "![{}]({})", caption, path.display())?;
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests;
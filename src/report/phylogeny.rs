@@ -0,0 +1,237 @@
+use std::{
+    fmt::{self, Write},
+    fs, io,
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::map;
+
+/// The file formats the ancestry tree can be exported as
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TreeFormat {
+    /// The Newick format read by most phylogenetics tools, every founder
+    /// starts a tree of its own on a separate line
+    Newick,
+    /// A GraphViz graph which can be drawn with dot
+    Dot,
+}
+
+impl TreeFormat {
+    /// Gets the file extension used for the format
+    pub fn get_extension(&self) -> &'static str {
+        return match self {
+            Self::Newick => "nwk",
+            Self::Dot => "dot",
+        };
+    }
+}
+
+/// The ancestry tree of the lineages of a map pruned to the lineages which
+/// are still alive and their ancestors, every lineage is named L followed by
+/// its id and the branch lengths are the number of steps between the birth
+/// of a lineage and the birth of its parent
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Phylogeny {
+    /// All lineages kept in the tree in the order of their id
+    lineages: Vec<map::Lineage>,
+    /// The ids of all lineages which are alive
+    living: Vec<usize>,
+    /// The index into the lineages of the parent of every kept lineage, None
+    /// for founders
+    parents: Vec<Option<usize>>,
+    /// The indices into the lineages of the children of every kept lineage
+    children: Vec<Vec<usize>>,
+    /// The indices into the lineages of the founders of all trees
+    roots: Vec<usize>,
+}
+
+impl Phylogeny {
+    /// Constructs the ancestry tree of the lineages alive on a map
+    ///
+    /// # Parameters
+    ///
+    /// map: The map to get the lineages from
+    pub fn new<S: map::sun::Intensity>(map: &map::Map<S>) -> Self {
+        let genealogy = map.get_genealogy();
+        let living = map.get_living_lineages();
+
+        // Keep every living lineage and all of its ancestors, the walk stops
+        // at the first lineage which is already kept
+        let mut kept = vec![false; genealogy.len()];
+        for &id in living.iter() {
+            let mut next = genealogy.get(id);
+            while let Some(lineage) = next {
+                if kept[lineage.id] {
+                    break;
+                }
+                kept[lineage.id] = true;
+                next = lineage.parent.and_then(|parent| genealogy.get(parent));
+            }
+        }
+
+        let lineages = genealogy
+            .get_all()
+            .iter()
+            .filter(|lineage| kept[lineage.id])
+            .copied()
+            .collect::<Vec<_>>();
+        let mut indices = vec![None; genealogy.len()];
+        lineages
+            .iter()
+            .enumerate()
+            .for_each(|(index, lineage)| indices[lineage.id] = Some(index));
+
+        let parents = lineages
+            .iter()
+            .map(|lineage| lineage.parent.and_then(|parent| indices[parent]))
+            .collect::<Vec<_>>();
+        let mut children = vec![Vec::new(); lineages.len()];
+        let mut roots = Vec::new();
+        for (index, parent) in parents.iter().enumerate() {
+            match parent {
+                Some(parent) => children[*parent].push(index),
+                None => roots.push(index),
+            }
+        }
+
+        return Self {
+            lineages,
+            living,
+            parents,
+            children,
+            roots,
+        };
+    }
+
+    /// Writes the tree to a file, overwriting any existing file
+    ///
+    /// # Parameters
+    ///
+    /// path: The path of the file
+    ///
+    /// format: The format to write the tree in
+    ///
+    /// # Errors
+    ///
+    /// Returns the io error if the file could not be written
+    pub fn save(&self, path: &Path, format: TreeFormat) -> Result<(), io::Error> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+
+        return fs::write(path, self.to_string(format));
+    }
+
+    /// Writes the tree as text in a format
+    ///
+    /// # Parameters
+    ///
+    /// format: The format to write the tree in
+    pub fn to_string(&self, format: TreeFormat) -> String {
+        let mut text = String::new();
+        match format {
+            TreeFormat::Newick => self.write_newick(&mut text),
+            TreeFormat::Dot => self.write_dot(&mut text),
+        }
+        .expect("Writing to a String never fails");
+
+        return text;
+    }
+
+    /// Writes every tree in the Newick format, the trees are written without
+    /// recursion since a long run can have very long chains of descendants
+    ///
+    /// # Parameters
+    ///
+    /// text: The text to write to
+    fn write_newick(&self, text: &mut String) -> fmt::Result {
+        for &root in self.roots.iter() {
+            let mut stack = vec![NewickStep::Open(root)];
+            while let Some(step) = stack.pop() {
+                match step {
+                    NewickStep::Open(index) => {
+                        // The children are written before the name of their
+                        // parent
+                        let children = &self.children[index];
+                        stack.push(NewickStep::Close(index));
+                        if !children.is_empty() {
+                            write!(text, "(")?;
+                        }
+                        for (order, &child) in children.iter().enumerate().rev() {
+                            stack.push(NewickStep::Open(child));
+                            if order > 0 {
+                                stack.push(NewickStep::Separate);
+                            }
+                        }
+                    }
+                    NewickStep::Separate => write!(text, ",")?,
+                    NewickStep::Close(index) => {
+                        if !self.children[index].is_empty() {
+                            write!(text, ")")?;
+                        }
+                        let lineage = &self.lineages[index];
+                        write!(text, "L{}", lineage.id)?;
+                        if let Some(parent) = self.parents[index] {
+                            write!(
+                                text,
+                                ":{}",
+                                lineage.birth_time - self.lineages[parent].birth_time
+                            )?;
+                        }
+                    }
+                }
+            }
+            writeln!(text, ";")?;
+        }
+
+        return Ok(());
+    }
+
+    /// Writes the trees as a GraphViz graph, living lineages are filled
+    ///
+    /// # Parameters
+    ///
+    /// text: The text to write to
+    fn write_dot(&self, text: &mut String) -> fmt::Result {
+        writeln!(text, "digraph phylogeny {{")?;
+        writeln!(text, "    rankdir=LR;")?;
+        writeln!(text, "    node [shape=box];")?;
+        for lineage in self.lineages.iter() {
+            write!(
+                text,
+                "    L{} [label=\"L{}\\nborn {}\"",
+                lineage.id, lineage.id, lineage.birth_time
+            )?;
+            if self.living.binary_search(&lineage.id).is_ok() {
+                write!(text, ", style=filled")?;
+            }
+            writeln!(text, "];")?;
+        }
+        for (index, children) in self.children.iter().enumerate() {
+            for &child in children.iter() {
+                writeln!(
+                    text,
+                    "    L{} -> L{};",
+                    self.lineages[index].id, self.lineages[child].id
+                )?;
+            }
+        }
+        writeln!(text, "}}")?;
+
+        return Ok(());
+    }
+}
+
+/// A step of writing a tree in the Newick format
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum NewickStep {
+    /// Opens the list of children of the lineage at the index
+    Open(usize),
+    /// Writes the separator between two children
+    Separate,
+    /// Closes the list of children of the lineage at the index and writes
+    /// its name
+    Close(usize),
+}
@@ -0,0 +1,87 @@
+use crate::map;
+
+use super::Event;
+
+/// Follows a run step by step and records the key events needed for the
+/// summary report
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunRecorder {
+    /// The statistics when the recording started
+    start: map::Statistics,
+    /// The statistics of the most recent step
+    last: map::Statistics,
+    /// The statistics at the step with the highest population
+    peak: map::Statistics,
+    /// All recorded events in the order they occured
+    events: Vec<Event>,
+}
+
+impl RunRecorder {
+    /// Constructs a new recorder starting at the given state
+    ///
+    /// # Parameters
+    ///
+    /// statistics: The statistics of the map when the run starts
+    pub fn new(statistics: map::Statistics) -> Self {
+        return Self {
+            start: statistics,
+            last: statistics,
+            peak: statistics,
+            events: vec![Event::new(statistics.time, "Run started".to_string())],
+        };
+    }
+
+    /// Records the statistics of a new step
+    ///
+    /// # Parameters
+    ///
+    /// statistics: The statistics of the map after the step
+    pub fn record(&mut self, statistics: map::Statistics) {
+        if self.last.population == 0 && statistics.population > 0 {
+            self.events.push(Event::new(
+                statistics.time,
+                format!(
+                    "Plants appeared with a population of {}",
+                    statistics.population
+                ),
+            ));
+        }
+        if self.last.population > 0 && statistics.population == 0 {
+            self.events.push(Event::new(
+                statistics.time,
+                "All plants died out".to_string(),
+            ));
+        }
+        if statistics.population > self.peak.population {
+            self.peak = statistics;
+        }
+
+        self.last = statistics;
+    }
+
+    /// Checks if the plants have died out after having been alive at some
+    /// point during the run
+    pub fn is_extinct(&self) -> bool {
+        return self.last.population == 0 && self.peak.population > 0;
+    }
+
+    /// Retrieves the statistics when the recording started
+    pub fn get_start(&self) -> &map::Statistics {
+        return &self.start;
+    }
+
+    /// Retrieves the statistics of the most recent step
+    pub fn get_last(&self) -> &map::Statistics {
+        return &self.last;
+    }
+
+    /// Retrieves the statistics at the step with the highest population
+    pub fn get_peak(&self) -> &map::Statistics {
+        return &self.peak;
+    }
+
+    /// Retrieves all recorded events in the order they occured
+    pub fn get_events(&self) -> &[Event] {
+        return &self.events;
+    }
+}
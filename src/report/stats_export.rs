@@ -0,0 +1,133 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::map;
+
+/// The file formats statistics can be exported as
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatsFormat {
+    /// Comma separated values with a header row
+    Csv,
+    /// One JSON object per line
+    JsonLines,
+}
+
+impl StatsFormat {
+    /// Finds the format from the extension of a file, .json and .jsonl are
+    /// exported as JSON lines and anything else as CSV
+    ///
+    /// # Parameters
+    ///
+    /// path: The path of the file
+    pub fn from_path(path: &Path) -> Self {
+        return match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") | Some("jsonl") => Self::JsonLines,
+            _ => Self::Csv,
+        };
+    }
+}
+
+/// Streams the statistics of every step of a run to a file such that the run
+/// can be analyzed with external tools
+#[derive(Debug)]
+pub struct StatsExporter {
+    /// The buffered file to write to
+    writer: BufWriter<File>,
+    /// The format of the file
+    format: StatsFormat,
+    /// The number of rows between each flush of the file
+    flush_interval: usize,
+    /// The number of rows written since the last flush
+    unflushed: usize,
+}
+
+impl StatsExporter {
+    /// Creates the file, overwriting any existing file, and writes the header
+    ///
+    /// # Parameters
+    ///
+    /// path: The path of the file, the format is given by its extension
+    ///
+    /// flush_interval: The number of rows between each flush of the file
+    ///
+    /// # Errors
+    ///
+    /// Returns the io error if the file could not be created
+    pub fn create(path: &Path, flush_interval: usize) -> Result<Self, io::Error> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+
+        let mut exporter = Self {
+            writer: BufWriter::new(File::create(path)?),
+            format: StatsFormat::from_path(path),
+            flush_interval,
+            unflushed: 0,
+        };
+
+        if let StatsFormat::Csv = exporter.format {
+            let header = map::Statistics::new(0)
+                .get_fields()
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>()
+                .join(",");
+            writeln!(exporter.writer, "{}", header)?;
+        }
+
+        return Ok(exporter);
+    }
+
+    /// Writes the statistics of a single step, the file is flushed once the
+    /// flush interval is reached
+    ///
+    /// # Parameters
+    ///
+    /// statistics: The statistics to write
+    ///
+    /// # Errors
+    ///
+    /// Returns the io error if the file could not be written
+    pub fn write(&mut self, statistics: &map::Statistics) -> Result<(), io::Error> {
+        let fields = statistics.get_fields();
+        match self.format {
+            StatsFormat::Csv => {
+                let row = fields
+                    .iter()
+                    .map(|(_, value)| value.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",");
+                writeln!(self.writer, "{}", row)?;
+            }
+            StatsFormat::JsonLines => {
+                let row = fields
+                    .iter()
+                    .map(|(name, value)| format!("\"{}\":{}", name, value))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                writeln!(self.writer, "{{{}}}", row)?;
+            }
+        }
+
+        self.unflushed += 1;
+        if self.unflushed >= self.flush_interval {
+            self.flush()?;
+        }
+
+        return Ok(());
+    }
+
+    /// Writes all buffered rows to the file
+    ///
+    /// # Errors
+    ///
+    /// Returns the io error if the file could not be written
+    pub fn flush(&mut self) -> Result<(), io::Error> {
+        self.unflushed = 0;
+
+        return self.writer.flush();
+    }
+}
@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+
+use crate::map;
+
+use super::{EndReason, RunRecorder};
+
+/// What happens when one of the stop conditions of a run is met
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StopAction {
+    /// The report is written and the application closes
+    End,
+    /// The report is written and the simulation is paused such that it can
+    /// be continued, the condition which was met is not checked again. Runs
+    /// without a window always end
+    Pause,
+}
+
+/// The conditions for stopping a run, they are checked after every step
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StopConditions {
+    /// The time step at which the run stops, None for no limit
+    pub step_limit: Option<usize>,
+    /// If true then the run stops when all plants have died out
+    pub extinction: bool,
+    /// The run stops when the population grows above this, None for no limit
+    pub population_limit: Option<usize>,
+}
+
+impl StopConditions {
+    /// Constructs a new set of conditions which never stop the run
+    pub fn new() -> Self {
+        return Self {
+            step_limit: None,
+            extinction: false,
+            population_limit: None,
+        };
+    }
+
+    /// Sets the step limit and returns the updated conditions
+    ///
+    /// # Parameters
+    ///
+    /// step_limit: The time step at which the run stops, None for no limit
+    pub fn with_step_limit(mut self, step_limit: Option<usize>) -> Self {
+        self.step_limit = step_limit;
+
+        return self;
+    }
+
+    /// Sets whether to stop on extinction and returns the updated conditions
+    ///
+    /// # Parameters
+    ///
+    /// extinction: If true then the run stops when all plants have died out
+    pub fn with_extinction(mut self, extinction: bool) -> Self {
+        self.extinction = extinction;
+
+        return self;
+    }
+
+    /// Sets the population limit and returns the updated conditions
+    ///
+    /// # Parameters
+    ///
+    /// population_limit: The population above which the run stops, None for
+    /// no limit
+    pub fn with_population_limit(mut self, population_limit: Option<usize>) -> Self {
+        self.population_limit = population_limit;
+
+        return self;
+    }
+
+    /// Finds the first condition which has been met, None if the run should
+    /// continue
+    ///
+    /// # Parameters
+    ///
+    /// recorder: The recorder following the run
+    ///
+    /// statistics: The statistics of the current step
+    pub fn check(&self, recorder: &RunRecorder, statistics: &map::Statistics) -> Option<EndReason> {
+        if self.extinction && recorder.is_extinct() {
+            return Some(EndReason::Extinction);
+        }
+        if self
+            .population_limit
+            .is_some_and(|limit| statistics.population > limit)
+        {
+            return Some(EndReason::PopulationLimit);
+        }
+        if self
+            .step_limit
+            .is_some_and(|limit| statistics.time >= limit)
+        {
+            return Some(EndReason::StepLimit);
+        }
+
+        return None;
+    }
+
+    /// Stops checking the condition which caused a reason, used when the run
+    /// continues after having been paused
+    ///
+    /// # Parameters
+    ///
+    /// reason: The reason the run was stopped
+    pub fn disarm(&mut self, reason: EndReason) {
+        match reason {
+            EndReason::StepLimit => self.step_limit = None,
+            EndReason::Extinction => self.extinction = false,
+            EndReason::PopulationLimit => self.population_limit = None,
+            EndReason::UserQuit | EndReason::RenderError => (),
+        }
+    }
+}
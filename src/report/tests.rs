@@ -0,0 +1,100 @@
+use crate::map::{self, settings, testing::new_map};
+
+use super::*;
+
+/// Runs a map for a number of steps and writes the report of the run
+///
+/// # Parameters
+///
+/// map: The map to run
+///
+/// steps: The number of steps to run
+fn run_report(map: &mut map::Map<map::sun::IntensityConstant>, steps: usize) -> Report {
+    let mut recorder = RunRecorder::new(map.get_statistics());
+    for _ in 0..steps {
+        map.step();
+        recorder.record(map.get_statistics());
+    }
+
+    return Report::new(EndReason::StepLimit, &recorder, map);
+}
+
+#[test]
+fn species_statistics() {
+    let mut map = new_map(
+        settings::Settings::new().with_species(settings::species::Settings::new().with_interval(1)),
+    );
+    map.step();
+    let statistics = map.get_statistics();
+    let species = map.get_species_statistics();
+
+    assert_eq!(
+        species
+            .iter()
+            .filter(|species| species.species.is_some())
+            .count(),
+        statistics.species
+    );
+    assert_eq!(
+        species
+            .iter()
+            .map(|species| species.population)
+            .sum::<usize>(),
+        statistics.population
+    );
+    let energy = species.iter().map(|species| species.energy).sum::<f64>();
+    assert!((energy - statistics.energy).abs() < 1e-9);
+}
+
+#[test]
+fn species_table() {
+    let mut map = new_map(
+        settings::Settings::new().with_species(settings::species::Settings::new().with_interval(1)),
+    );
+    let report = run_report(&mut map, 3);
+    let markdown = report.to_markdown();
+    let species = map.get_species_statistics();
+
+    assert!(!species.is_empty());
+    for species in species {
+        let id = species
+            .species
+            .expect("Every plant is clustered after a step");
+        let row = format!(
+            "| {} | {} | {:.3} |",
+            id, species.population, species.energy
+        );
+        assert!(markdown.contains(&row), "{} missing in\n{}", row, markdown);
+    }
+    assert!(!markdown.contains("All plants |"));
+}
+
+#[test]
+fn species_table_unclustered() {
+    // Without clustering no plant has a species
+    let mut map = new_map(
+        settings::Settings::new().with_species(settings::species::Settings::new().with_interval(0)),
+    );
+    let report = run_report(&mut map, 1);
+    let statistics = map.get_statistics();
+
+    assert!(report.to_markdown().contains(&format!(
+        "| Without species | {} | {:.3} |",
+        statistics.population, statistics.energy
+    )));
+}
+
+#[test]
+fn species_table_extinct() {
+    let mut map = new_map(settings::Settings::new());
+    for id in 0..map::EditBulk::COUNT {
+        map.remove_plant(&map::testing::get_plant_pos(map::EditBulk::from_id(id)));
+    }
+    let report = run_report(&mut map, 1);
+
+    assert!(
+        report
+            .to_markdown()
+            .contains("## Species\n\nNo plants are left.\n")
+    );
+}
@@ -0,0 +1,110 @@
+use crate::{config, map, report};
+
+/// Runs a simulation without a window until one of its stop conditions is
+/// met, the run is recorded such that a report can be written of it. This is
+/// the simulation other crates embed, stepping it themselves or running it
+/// to the end in one go
+#[derive(Clone, Debug)]
+pub struct Runner<S: map::sun::Intensity> {
+    /// The map being simulated
+    map: map::Map<S>,
+    /// The conditions ending the run
+    stop_conditions: report::StopConditions,
+    /// The recorder following the run
+    recorder: report::RunRecorder,
+}
+
+impl<S: map::sun::Intensity> Runner<S> {
+    /// Constructs a new runner starting from the current state of a map
+    ///
+    /// # Parameters
+    ///
+    /// map: The map to simulate
+    ///
+    /// stop_conditions: The conditions ending the run, there must be a step
+    /// limit for the run to be guaranteed to end
+    pub fn new(map: map::Map<S>, stop_conditions: report::StopConditions) -> Self {
+        let recorder = report::RunRecorder::new(map.get_statistics());
+
+        return Self {
+            map,
+            stop_conditions,
+            recorder,
+        };
+    }
+
+    /// Checks the stop conditions and takes a single step if none of them
+    /// are met, returns the reason the run ended or None if it continues
+    pub fn step(&mut self) -> Option<report::EndReason> {
+        if let Some(reason) = self
+            .stop_conditions
+            .check(&self.recorder, &self.map.get_statistics())
+        {
+            return Some(reason);
+        }
+
+        self.map.step();
+        self.recorder.record(self.map.get_statistics());
+
+        return None;
+    }
+
+    /// Takes steps until one of the stop conditions is met and returns the
+    /// reason the run ended
+    pub fn run(&mut self) -> report::EndReason {
+        loop {
+            if let Some(reason) = self.step() {
+                return reason;
+            }
+        }
+    }
+
+    /// Retrieves the map being simulated
+    pub fn get_map(&self) -> &map::Map<S> {
+        return &self.map;
+    }
+
+    /// Retrieves the map being simulated to edit it between steps
+    pub fn get_map_mut(&mut self) -> &mut map::Map<S> {
+        return &mut self.map;
+    }
+
+    /// Retrieves the conditions ending the run
+    pub fn get_stop_conditions(&self) -> &report::StopConditions {
+        return &self.stop_conditions;
+    }
+
+    /// Retrieves the recorder following the run
+    pub fn get_recorder(&self) -> &report::RunRecorder {
+        return &self.recorder;
+    }
+
+    /// Writes the summary report of the run so far
+    ///
+    /// # Parameters
+    ///
+    /// reason: The reason the run ended
+    pub fn get_report(&self, reason: report::EndReason) -> report::Report {
+        return report::Report::new(reason, &self.recorder, &self.map);
+    }
+
+    /// Ends the runner and retrieves the simulated map
+    pub fn into_map(self) -> map::Map<S> {
+        return self.map;
+    }
+}
+
+impl Runner<map::presets::WorldIntensity> {
+    /// Constructs a new runner of the map described by a config, the stop
+    /// conditions are those of the viewer settings of the config
+    ///
+    /// # Parameters
+    ///
+    /// config: The config to construct the map from
+    pub fn from_config(config: &config::Config) -> Self {
+        let sun = config.map.sun.get_world_intensity();
+        let map = map::Map::new(config.map.get_size(), config.map.settings, sun);
+
+        return Self::new(map, config.viewer.get_stop_conditions());
+    }
+}
@@ -0,0 +1,294 @@
+use std::fmt;
+
+use crate::map;
+
+use super::ParseScenarioError;
+
+/// A condition which must hold at a specific time step of a scenario, or at
+/// any step up to it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Assertion {
+    /// The time step to evaluate the condition at, or the last step at which
+    /// the condition may first hold
+    pub time: usize,
+    /// When the condition must hold
+    pub timing: Timing,
+    /// The condition to evaluate
+    pub condition: Condition,
+}
+
+impl Assertion {
+    /// Constructs a new assertion which must hold at the given time step
+    ///
+    /// # Parameters
+    ///
+    /// time: The time step to evaluate the condition at
+    ///
+    /// condition: The condition to evaluate
+    pub fn new(time: usize, condition: Condition) -> Self {
+        return Self {
+            time,
+            timing: Timing::At,
+            condition,
+        };
+    }
+
+    /// Sets when the condition must hold and returns the updated assertion
+    ///
+    /// # Parameters
+    ///
+    /// timing: When the condition must hold
+    pub fn with_timing(mut self, timing: Timing) -> Self {
+        self.timing = timing;
+
+        return self;
+    }
+
+    /// Checks if the condition of the assertion holds for a map
+    ///
+    /// # Parameters
+    ///
+    /// map: The map at the time of the check
+    ///
+    /// statistics: The statistics of the map
+    pub fn check<S: map::sun::Intensity>(
+        &self,
+        map: &map::Map<S>,
+        statistics: &map::Statistics,
+    ) -> bool {
+        return self.condition.check(map, statistics);
+    }
+
+    /// Parses an assertion of the form "at step <time> <condition>" or
+    /// "by step <time> <condition>"
+    ///
+    /// # Parameters
+    ///
+    /// line: The line number used for error reporting
+    ///
+    /// words: The words of the assertion
+    pub(super) fn parse(line: usize, words: &[&str]) -> Result<Self, ParseScenarioError> {
+        let timing = match words.first() {
+            Some(&"at") => Some(Timing::At),
+            Some(&"by") => Some(Timing::By),
+            _ => None,
+        };
+        let timing = match timing {
+            Some(timing) if words.len() >= 3 && words[1] == "step" => timing,
+            _ => {
+                return Err(ParseScenarioError::InvalidAssertion {
+                    line,
+                    text: words.join(" "),
+                });
+            }
+        };
+
+        let time = parse_number(line, words[2])?;
+        let condition = Condition::parse(line, &words[3..])?;
+
+        return Ok(Self {
+            time,
+            timing,
+            condition,
+        });
+    }
+}
+
+impl fmt::Display for Assertion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{} step {} {}", self.timing, self.time, self.condition);
+    }
+}
+
+/// When the condition of an assertion must hold
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Timing {
+    /// The condition must hold at the time step of the assertion
+    At,
+    /// The condition must hold at any step up to and including the time
+    /// step of the assertion, it passes at the first step where it holds
+    By,
+}
+
+impl fmt::Display for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(
+            f,
+            "{}",
+            match self {
+                Self::At => "at",
+                Self::By => "by",
+            }
+        );
+    }
+}
+
+/// A condition on the state of the map
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Condition {
+    /// Compares the number of plant tiles to a value
+    Population(Comparison, usize),
+    /// Compares the number of species at the last clustering to a value
+    Species(Comparison, usize),
+    /// The species with the given id has appeared and died out at the last
+    /// clustering
+    SpeciesExtinct(usize),
+    /// There are no plant tiles left
+    Extinct,
+}
+
+impl Condition {
+    /// Checks if the condition holds for a map
+    ///
+    /// # Parameters
+    ///
+    /// map: The map to check
+    ///
+    /// statistics: The statistics of the map
+    pub fn check<S: map::sun::Intensity>(
+        &self,
+        map: &map::Map<S>,
+        statistics: &map::Statistics,
+    ) -> bool {
+        return match self {
+            Self::Population(comparison, value) => comparison.check(statistics.population, *value),
+            Self::Species(comparison, value) => comparison.check(statistics.species, *value),
+            Self::SpeciesExtinct(species) => map.is_species_extinct(*species),
+            Self::Extinct => statistics.population == 0,
+        };
+    }
+
+    /// Parses a condition of the form "population <comparison> <value>",
+    /// "species <comparison> <value>", "species <id> extinct" or "extinct"
+    ///
+    /// # Parameters
+    ///
+    /// line: The line number used for error reporting
+    ///
+    /// words: The words of the condition
+    fn parse(line: usize, words: &[&str]) -> Result<Self, ParseScenarioError> {
+        return match words {
+            ["extinct"] => Ok(Self::Extinct),
+            ["species", species, "extinct"] => {
+                Ok(Self::SpeciesExtinct(parse_number(line, species)?))
+            }
+            ["population", comparison, value] => Ok(Self::Population(
+                Comparison::parse(line, comparison)?,
+                parse_number(line, value)?,
+            )),
+            ["species", comparison, value] => Ok(Self::Species(
+                Comparison::parse(line, comparison)?,
+                parse_number(line, value)?,
+            )),
+            _ => Err(ParseScenarioError::InvalidAssertion {
+                line,
+                text: words.join(" "),
+            }),
+        };
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Self::Population(comparison, value) => write!(f, "population {} {}", comparison, value),
+            Self::Species(comparison, value) => write!(f, "species {} {}", comparison, value),
+            Self::SpeciesExtinct(species) => write!(f, "species {} extinct", species),
+            Self::Extinct => write!(f, "extinct"),
+        };
+    }
+}
+
+/// The way to compare two values
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    /// The value must be greater
+    Greater,
+    /// The value must be greater or equal
+    GreaterOrEqual,
+    /// The value must be less
+    Less,
+    /// The value must be less or equal
+    LessOrEqual,
+    /// The values must be equal
+    Equal,
+    /// The values must not be equal
+    NotEqual,
+}
+
+impl Comparison {
+    /// Compares two values
+    ///
+    /// # Parameters
+    ///
+    /// lhs: The measured value
+    ///
+    /// rhs: The value to compare with
+    pub fn check(&self, lhs: usize, rhs: usize) -> bool {
+        return match self {
+            Self::Greater => lhs > rhs,
+            Self::GreaterOrEqual => lhs >= rhs,
+            Self::Less => lhs < rhs,
+            Self::LessOrEqual => lhs <= rhs,
+            Self::Equal => lhs == rhs,
+            Self::NotEqual => lhs != rhs,
+        };
+    }
+
+    /// Retrieves the symbol of the comparison
+    pub fn symbol(&self) -> &'static str {
+        return match self {
+            Self::Greater => ">",
+            Self::GreaterOrEqual => ">=",
+            Self::Less => "<",
+            Self::LessOrEqual => "<=",
+            Self::Equal => "==",
+            Self::NotEqual => "!=",
+        };
+    }
+
+    /// Parses a comparison from its symbol
+    ///
+    /// # Parameters
+    ///
+    /// line: The line number used for error reporting
+    ///
+    /// symbol: The symbol to parse
+    pub(super) fn parse(line: usize, symbol: &str) -> Result<Self, ParseScenarioError> {
+        return match symbol {
+            ">" => Ok(Self::Greater),
+            ">=" => Ok(Self::GreaterOrEqual),
+            "<" => Ok(Self::Less),
+            "<=" => Ok(Self::LessOrEqual),
+            "==" => Ok(Self::Equal),
+            "!=" => Ok(Self::NotEqual),
+            _ => Err(ParseScenarioError::InvalidComparison {
+                line,
+                symbol: symbol.to_string(),
+            }),
+        };
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.symbol());
+    }
+}
+
+/// Parses a non-negative integer
+///
+/// # Parameters
+///
+/// line: The line number used for error reporting
+///
+/// value: The text to parse
+pub(super) fn parse_number(line: usize, value: &str) -> Result<usize, ParseScenarioError> {
+    return value
+        .replace('_', "")
+        .parse()
+        .map_err(|_| ParseScenarioError::InvalidNumber {
+            line,
+            value: value.to_string(),
+        });
+}
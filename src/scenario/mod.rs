@@ -0,0 +1,255 @@
+// A scenario file is a plain text file with one command per line, empty lines
+// and lines starting with # are ignored:
+//
+// steps <count>
+// assert <timing> step <time> population <comparison> <value>
+// assert <timing> step <time> species <comparison> <value>
+// assert <timing> step <time> species <id> extinct
+// assert <timing> step <time> extinct
+//
+// timing: at if the condition must hold at the step, by if it must hold at
+// any step up to and including it
+// comparison: One of >, >=, <, <=, == and !=
+//
+// The species are updated at every clustering of the lineages, see the
+// interval of the species settings. A species which has not appeared yet is
+// not extinct
+
+use std::{error, fmt, fs, io, path::Path};
+
+use thiserror::Error;
+
+use crate::map;
+
+mod assertion;
+use assertion::parse_number;
+pub use assertion::{Assertion, Comparison, Condition, Timing};
+
+#[cfg(test)]
+mod tests;
+
+/// A headless run of the simulation with assertions on its behaviour
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scenario {
+    /// The number of simulation steps to run
+    pub steps: usize,
+    /// All assertions to evaluate during the run
+    pub assertions: Vec<Assertion>,
+}
+
+impl Scenario {
+    /// Constructs a new scenario without any assertions
+    ///
+    /// # Parameters
+    ///
+    /// steps: The number of simulation steps to run
+    pub fn new(steps: usize) -> Self {
+        return Self {
+            steps,
+            assertions: Vec::new(),
+        };
+    }
+
+    /// Adds an assertion and returns the updated scenario
+    ///
+    /// # Parameters
+    ///
+    /// assertion: The assertion to add
+    pub fn with_assertion(mut self, assertion: Assertion) -> Self {
+        self.assertions.push(assertion);
+
+        return self;
+    }
+
+    /// Loads a scenario from a scenario file
+    ///
+    /// # Parameters
+    ///
+    /// path: The path of the scenario file
+    ///
+    /// # Errors
+    ///
+    /// See LoadScenarioError for a description of the different errors which may occur
+    pub fn load(path: &Path) -> Result<Self, LoadScenarioError> {
+        let text = fs::read_to_string(path)?;
+
+        return Ok(Self::parse(&text)?);
+    }
+
+    /// Parses a scenario from the content of a scenario file
+    ///
+    /// # Parameters
+    ///
+    /// text: The content of the scenario file
+    ///
+    /// # Errors
+    ///
+    /// See ParseScenarioError for a description of the different errors which may occur
+    pub fn parse(text: &str) -> Result<Self, ParseScenarioError> {
+        let mut steps = None;
+        let mut assertions = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let words = line.split_whitespace().collect::<Vec<_>>();
+
+            match words.as_slice() {
+                [] => (),
+                [comment, ..] if comment.starts_with('#') => (),
+                ["steps", count] => steps = Some(parse_number(line_number, count)?),
+                ["assert", assertion @ ..] => {
+                    assertions.push(Assertion::parse(line_number, assertion)?)
+                }
+                [command, ..] => {
+                    return Err(ParseScenarioError::UnknownCommand {
+                        line: line_number,
+                        command: command.to_string(),
+                    });
+                }
+            };
+        }
+
+        // Default to running until the last assertion
+        let steps = match steps {
+            Some(value) => value,
+            None => assertions
+                .iter()
+                .map(|assertion| assertion.time)
+                .max()
+                .ok_or(ParseScenarioError::Empty)?,
+        };
+
+        return Ok(Self { steps, assertions });
+    }
+
+    /// Runs the scenario on a map, stepping it until all steps are done and
+    /// evaluating all assertions on the way
+    ///
+    /// # Parameters
+    ///
+    /// map: The map to run the scenario on
+    ///
+    /// # Errors
+    ///
+    /// Returns all the assertions which did not hold
+    pub fn run<S: map::sun::Intensity>(&self, map: &mut map::Map<S>) -> Result<(), ScenarioError> {
+        // Sort the assertions by time to evaluate them in order
+        let mut assertions = self.assertions.iter().collect::<Vec<_>>();
+        assertions.sort_by_key(|assertion| assertion.time);
+
+        let end_time = map.get_time() + self.steps;
+        let mut failures = Vec::new();
+        loop {
+            // Remove the assertions which are decided at this step, an
+            // assertion by a step passes as soon as it holds
+            let statistics = map.get_statistics();
+            assertions.retain(|assertion| {
+                let due = assertion.time <= statistics.time;
+                let decided = match assertion.timing {
+                    Timing::At => due,
+                    Timing::By => due || assertion.check(map, &statistics),
+                };
+                if decided && !assertion.check(map, &statistics) {
+                    failures.push(AssertionFailure {
+                        assertion: **assertion,
+                        statistics,
+                    });
+                }
+                return !decided;
+            });
+
+            if map.get_time() >= end_time {
+                break;
+            }
+            map.step();
+        }
+
+        // Any assertions after the end of the run can never hold
+        failures.extend(assertions.into_iter().map(|assertion| AssertionFailure {
+            assertion: *assertion,
+            statistics: map.get_statistics(),
+        }));
+
+        return if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(ScenarioError(failures))
+        };
+    }
+}
+
+/// An assertion which did not hold
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AssertionFailure {
+    /// The assertion which failed
+    pub assertion: Assertion,
+    /// The statistics of the map when the assertion was evaluated
+    pub statistics: map::Statistics,
+}
+
+/// The error when one or more assertions of a scenario did not hold
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenarioError(pub Vec<AssertionFailure>);
+
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} assertion(s) failed", self.0.len())?;
+        for failure in self.0.iter() {
+            write!(
+                f,
+                "\n  {} (evaluated at step {} with population {} and {} species)",
+                failure.assertion,
+                failure.statistics.time,
+                failure.statistics.population,
+                failure.statistics.species
+            )?;
+        }
+
+        return Ok(());
+    }
+}
+
+impl error::Error for ScenarioError {}
+
+/// The error types for when parsing a scenario file
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseScenarioError {
+    /// The line does not start with a known command
+    #[error("Line {line}: Unknown command \"{command}\"")]
+    UnknownCommand { line: usize, command: String },
+    /// A number could not be parsed
+    #[error("Line {line}: Invalid number \"{value}\"")]
+    InvalidNumber { line: usize, value: String },
+    /// A comparison symbol could not be parsed
+    #[error("Line {line}: Invalid comparison \"{symbol}\"")]
+    InvalidComparison { line: usize, symbol: String },
+    /// An assertion could not be parsed
+    #[error("Line {line}: Invalid assertion \"{text}\"")]
+    InvalidAssertion { line: usize, text: String },
+    /// There is neither a step count nor any assertions
+    #[error("The scenario has neither a step count nor any assertions")]
+    Empty,
+}
+
+/// The error types for when loading a scenario file
+#[derive(Error, Debug)]
+pub enum LoadScenarioError {
+    /// The file could not be read
+    #[error("Unable to read scenario file: {:?}", .0)]
+    Io(io::Error),
+    /// The content of the file is invalid
+    #[error("Unable to parse scenario file: {}", .0)]
+    Parse(ParseScenarioError),
+}
+
+impl From<io::Error> for LoadScenarioError {
+    fn from(value: io::Error) -> Self {
+        return Self::Io(value);
+    }
+}
+
+impl From<ParseScenarioError> for LoadScenarioError {
+    fn from(value: ParseScenarioError) -> Self {
+        return Self::Parse(value);
+    }
+}
@@ -0,0 +1,304 @@
+use crate::map;
+
+use super::*;
+
+/// Constructs the test map where the lineages are clustered into species
+/// every step
+fn new_map() -> map::Map<map::sun::IntensityConstant> {
+    return map::testing::new_map(
+        map::settings::Settings::new()
+            .with_species(map::settings::species::Settings::new().with_interval(1)),
+    );
+}
+
+/// Constructs the statistics of a map with the given population and species
+///
+/// # Parameters
+///
+/// population: The number of plant tiles
+///
+/// species: The number of species
+fn statistics(population: usize, species: usize) -> map::Statistics {
+    let mut statistics = map::Statistics::new(0);
+    statistics.population = population;
+    statistics.species = species;
+
+    return statistics;
+}
+
+#[test]
+fn parse() {
+    let scenario = Scenario::parse(
+        "# Plants must survive\n\
+         \n\
+         steps 1_000\n\
+         assert at step 10 population >= 5\n\
+         \x20 assert at step 500 species != 0\n\
+         assert at step 1000 extinct\n\
+         assert by step 900 species 3 extinct\n",
+    );
+
+    assert_eq!(
+        scenario,
+        Ok(Scenario::new(1000)
+            .with_assertion(Assertion::new(
+                10,
+                Condition::Population(Comparison::GreaterOrEqual, 5)
+            ))
+            .with_assertion(Assertion::new(
+                500,
+                Condition::Species(Comparison::NotEqual, 0)
+            ))
+            .with_assertion(Assertion::new(1000, Condition::Extinct))
+            .with_assertion(
+                Assertion::new(900, Condition::SpeciesExtinct(3)).with_timing(Timing::By)
+            ))
+    );
+}
+
+#[test]
+fn parse_steps_default() {
+    let scenario = Scenario::parse("assert at step 30 extinct\nassert at step 20 extinct");
+
+    assert_eq!(scenario.map(|scenario| scenario.steps), Ok(30));
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(
+        Scenario::parse("steps 10\nrun 10"),
+        Err(ParseScenarioError::UnknownCommand {
+            line: 2,
+            command: "run".to_string(),
+        })
+    );
+    assert_eq!(
+        Scenario::parse("steps ten"),
+        Err(ParseScenarioError::InvalidNumber {
+            line: 1,
+            value: "ten".to_string(),
+        })
+    );
+    assert_eq!(
+        Scenario::parse("assert at step 5 population > -1"),
+        Err(ParseScenarioError::InvalidNumber {
+            line: 1,
+            value: "-1".to_string(),
+        })
+    );
+    assert_eq!(
+        Scenario::parse("assert at step 5 species => 1"),
+        Err(ParseScenarioError::InvalidComparison {
+            line: 1,
+            symbol: "=>".to_string(),
+        })
+    );
+    assert_eq!(
+        Scenario::parse("assert on step 5 extinct"),
+        Err(ParseScenarioError::InvalidAssertion {
+            line: 1,
+            text: "on step 5 extinct".to_string(),
+        })
+    );
+    assert_eq!(
+        Scenario::parse("assert before step 5 extinct"),
+        Err(ParseScenarioError::InvalidAssertion {
+            line: 1,
+            text: "before step 5 extinct".to_string(),
+        })
+    );
+    assert_eq!(
+        Scenario::parse("assert by step 5 species x extinct"),
+        Err(ParseScenarioError::InvalidNumber {
+            line: 1,
+            value: "x".to_string(),
+        })
+    );
+    assert_eq!(
+        Scenario::parse("\nassert at step 5 energy > 1"),
+        Err(ParseScenarioError::InvalidAssertion {
+            line: 2,
+            text: "energy > 1".to_string(),
+        })
+    );
+    assert_eq!(
+        Scenario::parse("# Nothing to do\n"),
+        Err(ParseScenarioError::Empty)
+    );
+}
+
+#[test]
+fn display_round_trip() {
+    let assertions = [
+        Assertion::new(3, Condition::Population(Comparison::Less, 7)),
+        Assertion::new(4, Condition::Species(Comparison::Equal, 2)),
+        Assertion::new(5, Condition::Extinct),
+        Assertion::new(6, Condition::SpeciesExtinct(1)).with_timing(Timing::By),
+    ];
+    for assertion in assertions {
+        let text = format!("assert {}", assertion);
+        assert_eq!(
+            Scenario::parse(&text),
+            Ok(Scenario::new(assertion.time).with_assertion(assertion))
+        );
+    }
+}
+
+#[test]
+fn comparison() {
+    let cases = [
+        (Comparison::Greater, [false, false, true]),
+        (Comparison::GreaterOrEqual, [false, true, true]),
+        (Comparison::Less, [true, false, false]),
+        (Comparison::LessOrEqual, [true, true, false]),
+        (Comparison::Equal, [false, true, false]),
+        (Comparison::NotEqual, [true, false, true]),
+    ];
+    for (comparison, expected) in cases {
+        assert_eq!([1, 2, 3].map(|lhs| comparison.check(lhs, 2)), expected);
+        assert_eq!(Comparison::parse(0, comparison.symbol()), Ok(comparison));
+    }
+}
+
+#[test]
+fn condition() {
+    let map = new_map();
+
+    let condition = Condition::Population(Comparison::Greater, 3);
+    assert!(condition.check(&map, &statistics(4, 0)));
+    assert!(!condition.check(&map, &statistics(3, 5)));
+
+    let condition = Condition::Species(Comparison::LessOrEqual, 2);
+    assert!(condition.check(&map, &statistics(10, 2)));
+    assert!(!condition.check(&map, &statistics(0, 3)));
+
+    assert!(Condition::Extinct.check(&map, &statistics(0, 1)));
+    assert!(!Condition::Extinct.check(&map, &statistics(1, 0)));
+}
+
+#[test]
+fn condition_species_extinct() {
+    let mut map = new_map();
+    map.step();
+    let species = map
+        .get_species_statistics()
+        .iter()
+        .filter_map(|species| species.species)
+        .collect::<Vec<_>>();
+    let statistics = map.get_statistics();
+    assert!(!species.is_empty());
+
+    // Living species and species which have not appeared are not extinct
+    for id in species.iter() {
+        assert!(!Condition::SpeciesExtinct(*id).check(&map, &statistics));
+    }
+    let unknown = species.iter().max().unwrap() + 1;
+    assert!(!Condition::SpeciesExtinct(unknown).check(&map, &statistics));
+
+    // The species die out at the next clustering after the plants are gone
+    for id in 0..map::EditBulk::COUNT {
+        map.remove_plant(&map::testing::get_plant_pos(map::EditBulk::from_id(id)));
+    }
+    map.step();
+    let statistics = map.get_statistics();
+    for id in species.iter() {
+        assert!(Condition::SpeciesExtinct(*id).check(&map, &statistics));
+    }
+    assert!(!Condition::SpeciesExtinct(unknown).check(&map, &statistics));
+}
+
+#[test]
+fn run_passes() {
+    let mut map = new_map();
+    let scenario = Scenario::new(3)
+        .with_assertion(Assertion::new(
+            0,
+            Condition::Population(Comparison::Greater, 0),
+        ))
+        .with_assertion(Assertion::new(0, Condition::Species(Comparison::Equal, 0)))
+        .with_assertion(Assertion::new(
+            1,
+            Condition::Species(Comparison::GreaterOrEqual, 1),
+        ));
+
+    assert_eq!(scenario.run(&mut map), Ok(()));
+    assert_eq!(map.get_time(), 3);
+}
+
+#[test]
+fn run_fails() {
+    let mut map = new_map();
+    let scenario = Scenario::new(2)
+        .with_assertion(Assertion::new(1, Condition::Extinct))
+        .with_assertion(Assertion::new(
+            2,
+            Condition::Population(Comparison::Greater, 0),
+        ))
+        .with_assertion(Assertion::new(
+            5,
+            Condition::Population(Comparison::Greater, 0),
+        ));
+
+    // The assertion after the end of the run fails as well
+    let failures = match scenario.run(&mut map) {
+        Ok(()) => panic!("The scenario must fail"),
+        Err(ScenarioError(failures)) => failures,
+    };
+    assert_eq!(
+        failures
+            .iter()
+            .map(|failure| (failure.assertion.time, failure.statistics.time))
+            .collect::<Vec<_>>(),
+        vec![(1, 1), (5, 2)]
+    );
+    assert_eq!(map.get_time(), 2);
+}
+
+#[test]
+fn run_by_step() {
+    // The assertion by a step passes at the first step where it holds
+    let mut map = new_map();
+    let scenario = Scenario::new(4).with_assertion(
+        Assertion::new(2, Condition::Species(Comparison::Greater, 0)).with_timing(Timing::By),
+    );
+    assert_eq!(scenario.run(&mut map), Ok(()));
+
+    // It fails at its step if it has never held
+    let mut map = new_map();
+    let scenario = Scenario::new(4)
+        .with_assertion(Assertion::new(3, Condition::Extinct).with_timing(Timing::By))
+        .with_assertion(Assertion::new(6, Condition::Extinct).with_timing(Timing::By));
+    let failures = match scenario.run(&mut map) {
+        Ok(()) => panic!("The scenario must fail"),
+        Err(ScenarioError(failures)) => failures,
+    };
+    assert_eq!(
+        failures
+            .iter()
+            .map(|failure| (failure.assertion.time, failure.statistics.time))
+            .collect::<Vec<_>>(),
+        vec![(3, 3), (6, 4)]
+    );
+}
+
+#[test]
+fn run_species_extinct() {
+    let mut map = new_map();
+    map.step();
+    let species = map
+        .get_species_statistics()
+        .iter()
+        .filter_map(|species| species.species)
+        .collect::<Vec<_>>();
+    for id in 0..map::EditBulk::COUNT {
+        map.remove_plant(&map::testing::get_plant_pos(map::EditBulk::from_id(id)));
+    }
+
+    let mut scenario = Scenario::new(3);
+    for id in species.iter() {
+        scenario = scenario.with_assertion(
+            Assertion::new(3, Condition::SpeciesExtinct(*id)).with_timing(Timing::By),
+        );
+    }
+    assert_eq!(scenario.run(&mut map), Ok(()));
+}
@@ -0,0 +1,343 @@
+// A script is a Rhai program driving a headless run, next to the Rhai
+// standard library it has the following functions:
+//
+// step() / step(count): Steps the simulation once or a number of times
+// time(), width(), height(): The current time step and the size of the map
+// stats(): The statistics of the map as an object map
+// tile(x, y): The state of a tile as an object map, () outside the map
+// place_plant(x, y, bulk): Places a plant with the starter genome
+// remove_plant(x, y): Removes the plant of a tile
+// get_setting(path) / set_setting(path, value): Reads or changes a simulation
+// setting by its path in the config, like "water.source"
+
+use std::{cell::RefCell, fs, io, path::Path, rc::Rc};
+
+use rhai::{Array, Dynamic, Engine, EvalAltResult, INT, ImmutableString};
+use thiserror::Error;
+
+use crate::{map, types};
+
+/// An experiment written in Rhai which is run on a map without opening a
+/// window
+#[derive(Clone, Debug)]
+pub struct Script {
+    /// The compiled script
+    ast: rhai::AST,
+}
+
+impl Script {
+    /// Loads and compiles a script file
+    ///
+    /// # Parameters
+    ///
+    /// path: The path of the script file
+    ///
+    /// # Errors
+    ///
+    /// See ScriptError for a description of the different errors which may occur
+    pub fn load(path: &Path) -> Result<Self, ScriptError> {
+        let text = fs::read_to_string(path)?;
+
+        return Self::parse(&text);
+    }
+
+    /// Compiles a script from its source code
+    ///
+    /// # Parameters
+    ///
+    /// text: The source code of the script
+    ///
+    /// # Errors
+    ///
+    /// See ScriptError for a description of the different errors which may occur
+    pub fn parse(text: &str) -> Result<Self, ScriptError> {
+        let ast = Engine::new().compile(text)?;
+
+        return Ok(Self { ast });
+    }
+
+    /// Runs the script on a map and returns the map afterwards
+    ///
+    /// # Parameters
+    ///
+    /// map: The map to run the script on
+    ///
+    /// # Errors
+    ///
+    /// See ScriptError for a description of the different errors which may occur
+    pub fn run<S: map::sun::Intensity + 'static>(
+        &self,
+        map: map::Map<S>,
+    ) -> Result<map::Map<S>, ScriptError> {
+        let map = Rc::new(RefCell::new(map));
+        let result = {
+            let engine = new_engine(&map);
+            engine.run_ast(&self.ast)
+        };
+        result?;
+
+        // All references to the map were held by the engine which is dropped
+        return match Rc::try_unwrap(map) {
+            Ok(map) => Ok(map.into_inner()),
+            Err(_) => panic!("The script engine kept a reference to the map"),
+        };
+    }
+}
+
+/// Constructs a script engine with all map functions registered
+///
+/// # Parameters
+///
+/// map: The map the functions act on
+fn new_engine<S: map::sun::Intensity + 'static>(map: &Rc<RefCell<map::Map<S>>>) -> Engine {
+    let mut engine = Engine::new();
+
+    // Stepping
+    let handle = map.clone();
+    engine.register_fn("step", move || handle.borrow_mut().step());
+    let handle = map.clone();
+    engine.register_fn("step", move |count: INT| {
+        handle.borrow_mut().step_n(count.max(0) as usize)
+    });
+
+    // Queries
+    let handle = map.clone();
+    engine.register_fn("time", move || handle.borrow().get_time() as INT);
+    let handle = map.clone();
+    engine.register_fn("width", move || handle.borrow().get_size().w as INT);
+    let handle = map.clone();
+    engine.register_fn("height", move || handle.borrow().get_size().h as INT);
+    let handle = map.clone();
+    engine.register_fn("stats", move || {
+        handle
+            .borrow()
+            .get_statistics()
+            .get_fields()
+            .into_iter()
+            .map(|(name, value)| (name.into(), Dynamic::from_float(value)))
+            .collect::<rhai::Map>()
+    });
+    let handle = map.clone();
+    engine.register_fn("tile", move |x: INT, y: INT| {
+        match handle.borrow().inspect_tile(&get_index(x, y)) {
+            Some(info) => tile_to_dynamic(&info),
+            None => Dynamic::UNIT,
+        }
+    });
+
+    // Editing
+    let handle = map.clone();
+    engine.register_fn(
+        "place_plant",
+        move |x: INT, y: INT, bulk: &str| -> Result<bool, Box<EvalAltResult>> {
+            let bulk = parse_bulk(bulk)?;
+            return Ok(handle.borrow_mut().place_plant(&get_index(x, y), bulk));
+        },
+    );
+    let handle = map.clone();
+    engine.register_fn("remove_plant", move |x: INT, y: INT| {
+        handle.borrow_mut().remove_plant(&get_index(x, y))
+    });
+
+    // Settings
+    let handle = map.clone();
+    engine.register_fn(
+        "get_setting",
+        move |path: &str| -> Result<Dynamic, Box<EvalAltResult>> {
+            let settings = settings_to_toml(handle.borrow().get_settings())?;
+            let value = path
+                .split('.')
+                .try_fold(&settings, |value, key| value.get(key))
+                .ok_or_else(|| format!("Unknown setting \"{}\"", path))?;
+
+            return Ok(toml_to_dynamic(value));
+        },
+    );
+    let handle = map.clone();
+    engine.register_fn(
+        "set_setting",
+        move |path: &str, value: Dynamic| -> Result<(), Box<EvalAltResult>> {
+            let mut settings = settings_to_toml(handle.borrow().get_settings())?;
+            let target = path
+                .split('.')
+                .try_fold(&mut settings, |value, key| value.get_mut(key))
+                .ok_or_else(|| format!("Unknown setting \"{}\"", path))?;
+            *target = dynamic_to_toml(&value, target)
+                .ok_or_else(|| format!("Invalid value for setting \"{}\": {}", path, value))?;
+
+            let settings = settings
+                .try_into::<map::settings::Settings>()
+                .map_err(|error| format!("Invalid value for setting \"{}\": {}", path, error))?;
+            handle.borrow_mut().set_settings(settings);
+
+            return Ok(());
+        },
+    );
+
+    return engine;
+}
+
+/// Constructs a tile position from script integers
+///
+/// # Parameters
+///
+/// x: The column of the tile
+///
+/// y: The row of the tile
+fn get_index(x: INT, y: INT) -> types::Index {
+    return types::Index::new(x as isize, y as isize);
+}
+
+/// Finds the placeable bulk type with a given name, the case and any spaces
+/// or underscores are ignored
+///
+/// # Parameters
+///
+/// name: The name of the bulk type
+fn parse_bulk(name: &str) -> Result<map::EditBulk, Box<EvalAltResult>> {
+    let simplify = |name: &str| {
+        name.chars()
+            .filter(|character| *character != ' ' && *character != '_')
+            .collect::<String>()
+            .to_lowercase()
+    };
+    let name_simple = simplify(name);
+
+    return (0..map::EditBulk::COUNT)
+        .map(map::EditBulk::from_id)
+        .find(|bulk| simplify(bulk.get_name()) == name_simple)
+        .ok_or_else(|| format!("Unknown bulk type \"{}\"", name).into());
+}
+
+/// Converts the snapshot of a tile to a script object map
+///
+/// # Parameters
+///
+/// info: The snapshot of the tile
+fn tile_to_dynamic(info: &map::TileInfo) -> Dynamic {
+    let mut tile = rhai::Map::new();
+    tile.insert("x".into(), (info.pos.x as INT).into());
+    tile.insert("y".into(), (info.pos.y as INT).into());
+    tile.insert("light".into(), info.light.into());
+    tile.insert("diffuse".into(), info.diffuse.into());
+    tile.insert("transparency".into(), info.transparency.into());
+    tile.insert("water".into(), info.water.into());
+    tile.insert("ground".into(), info.ground.into());
+    tile.insert("terrain".into(), info.terrain.into());
+    tile.insert("barren".into(), info.barren.into());
+    tile.insert("nutrients".into(), info.nutrients.into());
+    tile.insert("temperature".into(), info.temperature.into());
+    tile.insert("detritus".into(), info.detritus.into());
+    tile.insert("fertility".into(), info.fertility.into());
+
+    let plant = match &info.plant {
+        Some(info) => {
+            let mut plant = rhai::Map::new();
+            plant.insert("bulk".into(), info.bulk.into());
+            plant.insert("building".into(), info.building.into());
+            plant.insert("alive".into(), info.alive.into());
+            plant.insert("root".into(), info.root.into());
+            plant.insert("energy".into(), info.energy.into());
+            plant.insert("energy_capacity".into(), info.energy_capacity.into());
+            plant.insert("age".into(), (info.age as INT).into());
+            plant.insert("cum_age".into(), (info.cum_age as INT).into());
+            plant.insert(
+                "lineage".into(),
+                info.lineage
+                    .map_or(Dynamic::UNIT, |lineage| (lineage as INT).into()),
+            );
+            plant.into()
+        }
+        None => Dynamic::UNIT,
+    };
+    tile.insert("plant".into(), plant);
+
+    return tile.into();
+}
+
+/// Converts the simulation settings to a TOML value to look up settings by
+/// their path in the config
+///
+/// # Parameters
+///
+/// settings: The settings to convert
+fn settings_to_toml(settings: &map::settings::Settings) -> Result<toml::Value, Box<EvalAltResult>> {
+    return toml::Value::try_from(settings)
+        .map_err(|error| format!("Unable to read the settings: {}", error).into());
+}
+
+/// Converts a TOML value to a script value
+///
+/// # Parameters
+///
+/// value: The value to convert
+fn toml_to_dynamic(value: &toml::Value) -> Dynamic {
+    return match value {
+        toml::Value::String(value) => ImmutableString::from(value.as_str()).into(),
+        toml::Value::Integer(value) => (*value as INT).into(),
+        toml::Value::Float(value) => (*value).into(),
+        toml::Value::Boolean(value) => (*value).into(),
+        toml::Value::Datetime(value) => value.to_string().into(),
+        toml::Value::Array(values) => values.iter().map(toml_to_dynamic).collect::<Array>().into(),
+        toml::Value::Table(values) => values
+            .iter()
+            .map(|(key, value)| (key.into(), toml_to_dynamic(value)))
+            .collect::<rhai::Map>()
+            .into(),
+    };
+}
+
+/// Converts a script value to a TOML value of the same type as the value it
+/// replaces, integers are accepted for decimal settings. Returns None if the
+/// value cannot be converted
+///
+/// # Parameters
+///
+/// value: The value to convert
+///
+/// previous: The value to replace
+fn dynamic_to_toml(value: &Dynamic, previous: &toml::Value) -> Option<toml::Value> {
+    return match previous {
+        toml::Value::Float(_) => value
+            .as_float()
+            .ok()
+            .or_else(|| value.as_int().ok().map(|value| value as f64))
+            .map(toml::Value::Float),
+        toml::Value::Integer(_) => value.as_int().ok().map(toml::Value::Integer),
+        toml::Value::Boolean(_) => value.as_bool().ok().map(toml::Value::Boolean),
+        toml::Value::String(_) => value.clone().into_string().ok().map(toml::Value::String),
+        toml::Value::Datetime(_) | toml::Value::Array(_) | toml::Value::Table(_) => None,
+    };
+}
+
+/// The error types for when loading or running a script
+#[derive(Error, Debug)]
+pub enum ScriptError {
+    /// The file could not be read
+    #[error("Unable to read script file: {:?}", .0)]
+    Io(io::Error),
+    /// The script could not be compiled
+    #[error("Unable to parse script: {}", .0)]
+    Parse(rhai::ParseError),
+    /// The script failed while running
+    #[error("Script failed: {}", .0)]
+    Eval(Box<EvalAltResult>),
+}
+
+impl From<io::Error> for ScriptError {
+    fn from(value: io::Error) -> Self {
+        return Self::Io(value);
+    }
+}
+
+impl From<rhai::ParseError> for ScriptError {
+    fn from(value: rhai::ParseError) -> Self {
+        return Self::Parse(value);
+    }
+}
+
+impl From<Box<EvalAltResult>> for ScriptError {
+    fn from(value: Box<EvalAltResult>) -> Self {
+        return Self::Eval(value);
+    }
+}
@@ -0,0 +1,228 @@
+// The server streams the map of a headless run to web viewers over
+// websockets, it is only available if built with the server feature. Every
+// step is sent as a single binary message with all numbers in little endian:
+//
+// u64: The time step
+// u32, u32: The width and height of the map
+// Then for every tile, row by row starting from the top:
+//   u8: Flags, 1 if the tile is ground, 2 if there is a plant on the tile and
+//       4 if the plant is fully grown and alive
+//   f32: The light level
+//   f32: The energy of the plant, 0 if there is no fully grown plant
+//   u32: The lineage of the plant, u32::MAX if there is none
+
+use std::{
+    io,
+    net::ToSocketAddrs,
+    sync::{Arc, Mutex, mpsc::SyncSender, mpsc::TrySendError},
+};
+#[cfg(feature = "server")]
+use std::{net::TcpListener, sync::mpsc, thread};
+
+use thiserror::Error;
+
+use crate::map;
+
+/// The number of frames waiting to be sent to a single viewer before new
+/// frames are dropped for it, such that a slow viewer never halts the run
+#[cfg(feature = "server")]
+const CLIENT_QUEUE_LENGTH: usize = 4;
+
+/// The flags marking the state of a tile in a frame
+const FLAG_GROUND: u8 = 1;
+const FLAG_OCCUPIED: u8 = 2;
+const FLAG_ALIVE: u8 = 4;
+
+/// The number of bytes in the header of a frame and for every tile
+const HEADER_SIZE: usize = 16;
+const TILE_SIZE: usize = 13;
+
+/// The queues of the frames for all connected viewers, shared with the
+/// thread accepting new viewers
+type Clients = Arc<Mutex<Vec<SyncSender<Arc<[u8]>>>>>;
+
+/// Streams the map of every step to all connected web viewers, viewers are
+/// accepted on a background thread and every viewer is sent its frames from
+/// its own thread
+#[derive(Debug)]
+pub struct MapServer {
+    /// The queues of the frames for all connected viewers
+    clients: Clients,
+}
+
+impl MapServer {
+    /// Starts listening for viewers on an address
+    ///
+    /// # Parameters
+    ///
+    /// address: The address to listen on, like 0.0.0.0:9001
+    ///
+    /// # Errors
+    ///
+    /// See ServerError for a description of the different errors which may occur
+    pub fn bind(address: impl ToSocketAddrs) -> Result<Self, ServerError> {
+        let clients = Arc::new(Mutex::new(Vec::new()));
+        listen(address, Arc::clone(&clients))?;
+
+        return Ok(Self { clients });
+    }
+
+    /// Sends the current state of the map to all connected viewers, viewers
+    /// which have disconnected are forgotten
+    ///
+    /// # Parameters
+    ///
+    /// map: The map to send
+    pub fn send<S: map::sun::Intensity>(&self, map: &map::Map<S>) {
+        let mut clients = match self.clients.lock() {
+            Ok(clients) => clients,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if clients.is_empty() {
+            return;
+        }
+
+        let frame: Arc<[u8]> = encode_frame(map).into();
+        clients.retain(|client| match client.try_send(Arc::clone(&frame)) {
+            Ok(()) | Err(TrySendError::Full(_)) => true,
+            Err(TrySendError::Disconnected(_)) => false,
+        });
+    }
+}
+
+/// Encodes the current state of the map as a single frame, see the top of
+/// this file for the layout
+///
+/// # Parameters
+///
+/// map: The map to encode
+pub fn encode_frame<S: map::sun::Intensity>(map: &map::Map<S>) -> Vec<u8> {
+    let size = map.get_size();
+    let tiles = map.get_tile_summaries();
+
+    let mut frame = Vec::with_capacity(HEADER_SIZE + TILE_SIZE * tiles.len());
+    frame.extend_from_slice(&(map.get_time() as u64).to_le_bytes());
+    frame.extend_from_slice(&(size.w as u32).to_le_bytes());
+    frame.extend_from_slice(&(size.h as u32).to_le_bytes());
+    for tile in tiles.iter() {
+        let mut flags = 0;
+        if tile.ground {
+            flags |= FLAG_GROUND;
+        }
+        if tile.occupied {
+            flags |= FLAG_OCCUPIED;
+        }
+        if tile.lineage.is_some() {
+            flags |= FLAG_ALIVE;
+        }
+        frame.push(flags);
+        frame.extend_from_slice(&(tile.light as f32).to_le_bytes());
+        frame.extend_from_slice(&(tile.energy.unwrap_or(0.0) as f32).to_le_bytes());
+        frame.extend_from_slice(
+            &tile
+                .lineage
+                .map_or(u32::MAX, |lineage| lineage as u32)
+                .to_le_bytes(),
+        );
+    }
+
+    return frame;
+}
+
+/// Starts accepting viewers on a background thread
+///
+/// # Parameters
+///
+/// address: The address to listen on
+///
+/// clients: The queues of the frames to add the new viewers to
+///
+/// # Errors
+///
+/// See ServerError for a description of the different errors which may occur
+#[cfg(feature = "server")]
+fn listen(address: impl ToSocketAddrs, clients: Clients) -> Result<(), ServerError> {
+    let listener = TcpListener::bind(address)?;
+    println!("Streaming the map to viewers on {}", listener.local_addr()?);
+    thread::spawn(move || accept(listener, clients));
+
+    return Ok(());
+}
+
+/// Fails as the application was built without the server feature
+///
+/// # Parameters
+///
+/// address: The address which would have been listened on
+///
+/// clients: The queues of the frames which would have been added to
+///
+/// # Errors
+///
+/// Always returns ServerError::Disabled
+#[cfg(not(feature = "server"))]
+fn listen(_address: impl ToSocketAddrs, _clients: Clients) -> Result<(), ServerError> {
+    return Err(ServerError::Disabled);
+}
+
+/// Accepts viewers until the listener fails, the websocket handshake and the
+/// sending of frames is done on a new thread for every viewer
+///
+/// # Parameters
+///
+/// listener: The listener to accept viewers from
+///
+/// clients: The queues of the frames to add the new viewers to
+#[cfg(feature = "server")]
+fn accept(listener: TcpListener, clients: Clients) {
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(error) => {
+                eprintln!("Unable to accept viewer: {:?}", error);
+                continue;
+            }
+        };
+
+        let (sender, receiver) = mpsc::sync_channel::<Arc<[u8]>>(CLIENT_QUEUE_LENGTH);
+        match clients.lock() {
+            Ok(mut clients) => clients.push(sender),
+            Err(poisoned) => poisoned.into_inner().push(sender),
+        }
+        thread::spawn(move || {
+            let mut socket = match tungstenite::accept(stream) {
+                Ok(socket) => socket,
+                Err(error) => {
+                    eprintln!("Unable to connect viewer: {}", error);
+                    return;
+                }
+            };
+            // Dropping the receiver tells the server the viewer is gone
+            for frame in receiver.iter() {
+                if socket
+                    .send(tungstenite::Message::binary(frame.to_vec()))
+                    .is_err()
+                {
+                    return;
+                }
+            }
+        });
+    }
+}
+
+/// The error types for when starting the server
+#[derive(Error, Debug)]
+pub enum ServerError {
+    /// The address could not be listened on
+    #[error("Unable to listen for viewers: {:?}", .0)]
+    Io(io::Error),
+    /// The application was built without the server feature
+    #[error("The application was built without the server feature")]
+    Disabled,
+}
+
+impl From<io::Error> for ServerError {
+    fn from(value: io::Error) -> Self {
+        return Self::Io(value);
+    }
+}
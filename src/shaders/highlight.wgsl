@@ -0,0 +1,210 @@
+// Structs
+// The structure to input for the vertex shader
+struct VertexInput {
+    // The position for the vertex in world coordinates
+    @location(0) pos: vec2<f32>,
+}
+
+// The instance input for the vertex shader
+struct InstanceInput {
+    // The index of the highlighted tile
+    @location(1) tile: u32,
+    // The color for the highlight
+    @location(2) color_value: f32,
+}
+
+// The stucture to output for the vertex shader
+struct VertexOutput {
+    // The position of the vertex in screen coordinates
+    @builtin(position) clip_position: vec4<f32>,
+    // The value to display
+    @location(0) color_value: f32,
+};
+
+// A transformation in 2D
+struct Transform2D {
+    // The transformation matrix
+    transform: mat4x4<f32>,
+};
+
+// All information to do with the color map
+struct ColorMap {
+    // The full list of colors for the color map
+    colors: array<vec4<f32>, 256>,
+    // All flags for the uniform, must be this big due to sizing in wgsl
+    //
+    // 0: If set then it is continuous
+    //
+    // 1: If set then the stops are interpolated instead of the colors
+    //
+    // The second value is the number of stops
+    //
+    // The third value is the id of the scaling of the values
+    flags: vec4<u32>,
+    // The colors of the stops
+    stop_colors: array<vec4<f32>, 8>,
+    // The positions of the stops packed 4 to a vector due to sizing in wgsl
+    stop_positions: array<vec4<f32>, 2>,
+    // The parameter of the scaling as the first value, the rest is padding
+    scaling: vec4<f32>,
+}
+
+// All information on the layout of the grid
+struct GridLayout {
+    // The number of columns
+    n_columns: u32,
+    // If not 0 then every tile is moved by a multiple of the map width to be
+    // as close as possible to the wrap center
+    wrap: u32,
+    // The x-coordinate in the grid to wrap all tiles around
+    wrap_center: f32,
+    // How much wider the columns at the edges are than the average column, 0
+    // for equally wide columns
+    stretch: f32,
+}
+
+// Uniforms
+// The transform to apply to each vertex
+@group(0) @binding(0)
+var<uniform> transform: Transform2D;
+
+// The number of columns in the grid
+@group(0) @binding(1)
+var<uniform> grid_layout: GridLayout;
+
+// The information for the color map
+@group(0) @binding(2)
+var<uniform> color_map: ColorMap;
+
+const sqrt_3: f32 = 1.73205080756887729352744634150587236694280525381038062805580697945193301690;
+const pi: f32 = 3.14159265358979323846264338327950288419716939937510582097494459230781640628;
+
+// Moves a position in the grid by a multiple of the map width such that it is
+// as close as possible to the wrap center, this way a single copy of the map
+// covers the view across the wrapping edge
+fn wrap_grid_pos(grid_pos: vec2<f32>) -> vec2<f32> {
+    if (grid_layout.wrap == 0u) {
+        return grid_pos;
+    }
+
+    let width = f32(grid_layout.n_columns);
+    let shift = round((grid_pos.x - grid_layout.wrap_center) / width) * width;
+    return vec2<f32>(grid_pos.x - shift, grid_pos.y);
+}
+
+// Moves a position from the grid, where every column is one wide, to world
+// coordinates by stretching the columns along a cosine which is widest at the
+// edges of the map, the width of the map is kept such that wrapping works the
+// same way
+fn warp_pos(pos: vec2<f32>) -> vec2<f32> {
+    if (grid_layout.stretch == 0.0) {
+        return pos;
+    }
+
+    let width = f32(grid_layout.n_columns);
+    let phase = 2.0 * pi * (pos.x + 0.5) / width;
+    return vec2<f32>(pos.x + grid_layout.stretch * width / (2.0 * pi) * sin(phase), pos.y);
+}
+
+// Gets the position of a stop of the color map
+fn get_stop_position(index: u32) -> f32 {
+    return color_map.stop_positions[index / 4u][index % 4u];
+}
+
+// Interpolates between the two stops of the color map surrounding a value,
+// values outside the stops get the color of the nearest stop
+fn get_stop_color(value: f32) -> vec4<f32> {
+    let count = color_map.flags.y;
+    if (value <= get_stop_position(0u)) {
+        return color_map.stop_colors[0];
+    }
+
+    for (var index = 1u; index < count; index++) {
+        let position = get_stop_position(index);
+        if (value <= position) {
+            let previous = get_stop_position(index - 1u);
+            let ratio = (value - previous) / max(position - previous, 1e-6);
+            return mix(color_map.stop_colors[index - 1u], color_map.stop_colors[index], ratio);
+        }
+    }
+
+    return color_map.stop_colors[count - 1u];
+}
+
+// Applies the scaling of the color map to a value from 0 to 1 such that small
+// values can be spread out
+fn scale_value(value: f32) -> f32 {
+    let parameter = color_map.scaling.x;
+    switch color_map.flags.z {
+        // Logarithmic
+        case 1u: {
+            return log(1.0 + parameter * value) / log(1.0 + parameter);
+        }
+        // Gamma and exponent
+        case 2u, 3u: {
+            return pow(value, parameter);
+        }
+        default: {
+            return value;
+        }
+    }
+}
+
+// Vertex shader
+@vertex
+fn vs_main(
+    model: VertexInput,
+    instance: InstanceInput,
+) -> VertexOutput {
+    // Get the position in the grid
+    let column = instance.tile % grid_layout.n_columns;
+    let row = instance.tile / grid_layout.n_columns;
+    let grid_pos = wrap_grid_pos(vec2<f32>(f32(column) + 0.5 * f32(row % 2u), -0.5 * sqrt_3 * f32(row)));
+
+    // Get the position on the screen
+    let screen_pos = transform.transform * vec4<f32>(warp_pos(model.pos + grid_pos), 0.0, 1.0);
+
+    // Create the output
+    var out: VertexOutput;
+    out.clip_position = screen_pos;
+    out.color_value = instance.color_value;
+    return out;
+}
+
+// Fragment shader
+@fragment
+fn fs_main(
+    in: VertexOutput
+) -> @location(0) vec4<f32> {
+    // Check if the color map is continuous
+    let continuous = (color_map.flags.x & 1u) != 0u;
+
+    // Clamp the value to avoid overflow and scale it for continuous color maps
+    var value = clamp(in.color_value, 0.0, 1.0);
+    if (continuous) {
+        value = clamp(scale_value(value), 0.0, 1.0);
+    }
+
+    // Handle color maps defined by stops
+    if (continuous && (color_map.flags.x & 2u) != 0u) {
+        return get_stop_color(value);
+    }
+
+    let color_value = value * 255.0;
+
+    // Handle non-continuous color maps by snapping
+    if (!continuous) {
+        let color_index = u32(color_value + 0.5);
+        return color_map.colors[color_index];
+    }
+
+    // Handle continuous color maps
+    let color_index = u32(color_value);
+    let color_ratio = color_value - f32(color_index);
+
+    // Handle the max value differently
+    if (color_index == 255u) {
+        return color_map.colors[color_index];
+    }
+    return color_ratio * color_map.colors[color_index + 1u] + (1.0 - color_ratio) * color_map.colors[color_index];
+}
\ No newline at end of file
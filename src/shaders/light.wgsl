@@ -0,0 +1,54 @@
+// The size of the map
+struct Uniforms {
+    width: u32,
+    height: u32,
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: Uniforms;
+
+// The light of every tile in the current step
+@group(0) @binding(1)
+var<storage, read> light: array<f32>;
+
+// The transparency of every tile in the current step
+@group(0) @binding(2)
+var<storage, read> transparency: array<f32>;
+
+// The intensity of the sun above every column
+@group(0) @binding(3)
+var<storage, read> sun: array<f32>;
+
+// The light of every tile in the next step
+@group(0) @binding(4)
+var<storage, read_write> light_next: array<f32>;
+
+@compute @workgroup_size(64)
+fn cs_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let index = id.x;
+    let width = uniforms.width;
+    if index >= width * uniforms.height {
+        return;
+    }
+    let x = index % width;
+    let y = index / width;
+
+    // Find the columns of the up-right and up-left neighbors, odd rows are
+    // shifted half a tile to the right and the map wraps in x
+    var x_right = x;
+    var x_left = x;
+    if y % 2u == 0u {
+        x_left = select(x - 1u, width - 1u, x == 0u);
+    } else {
+        x_right = select(x + 1u, 0u, x == width - 1u);
+    }
+
+    // The top row is lit directly by the sun
+    if y == 0u {
+        light_next[index] = 0.5 * (sun[x_right] + sun[x_left]);
+        return;
+    }
+    let right = (y - 1u) * width + x_right;
+    let left = (y - 1u) * width + x_left;
+    light_next[index] = 0.5 * (light[right] * transparency[right] + light[left] * transparency[left]);
+}
@@ -0,0 +1,327 @@
+// A sweep runs the simulation headless many times while varying settings of
+// the config, either over a grid of values or at random points, and writes
+// the final statistics of every run as a row of a CSV file. A parameter is
+// given by its dotted path in the config file, like "map.sun.tilt", see
+// sweep.example.toml for the format of a sweep file
+
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use itertools::Itertools;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{config, runner};
+
+/// How the points of a sweep are chosen
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Sampling {
+    /// Every combination of the values of all parameters
+    Grid,
+    /// Points drawn uniformly between the smallest and largest value of
+    /// every parameter
+    Random,
+}
+
+/// A single setting varied by a sweep
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Parameter {
+    /// The dotted path of the value in the config
+    pub path: String,
+    /// The values to use, random sampling draws between the smallest and
+    /// largest value
+    pub values: Vec<f64>,
+}
+
+/// All settings of a sweep
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "Sweep::new")]
+pub struct Sweep {
+    /// How the points are chosen
+    pub sampling: Sampling,
+    /// The number of points to draw for random sampling
+    pub samples: usize,
+    /// The seed for drawing random points
+    pub seed: u64,
+    /// The number of runs for each point, every run uses the seed of the
+    /// config plus the index of the run
+    pub repeats: usize,
+    /// The number of steps after which every run ends
+    pub steps: usize,
+    /// The CSV file to write the results to
+    pub output: PathBuf,
+    /// The settings to vary
+    pub parameters: Vec<Parameter>,
+}
+
+impl Sweep {
+    /// Constructs a new sweep with a single run and no parameters
+    pub fn new() -> Self {
+        return Self {
+            sampling: Sampling::Grid,
+            samples: 10,
+            seed: 0,
+            repeats: 1,
+            steps: 1000,
+            output: PathBuf::from("exports/sweep.csv"),
+            parameters: Vec::new(),
+        };
+    }
+
+    /// Parses a sweep from a TOML text and validates it
+    ///
+    /// # Parameters
+    ///
+    /// text: The TOML text
+    ///
+    /// # Errors
+    ///
+    /// See SweepError for a description of the different errors which may occur
+    pub fn parse(text: &str) -> Result<Self, SweepError> {
+        let sweep: Self = toml::from_str(text)?;
+        sweep.validate()?;
+
+        return Ok(sweep);
+    }
+
+    /// Loads a sweep from a TOML file and validates it
+    ///
+    /// # Parameters
+    ///
+    /// path: The path of the file
+    ///
+    /// # Errors
+    ///
+    /// See SweepError for a description of the different errors which may occur
+    pub fn load(path: &Path) -> Result<Self, SweepError> {
+        let text = fs::read_to_string(path)?;
+
+        return Self::parse(&text);
+    }
+
+    /// Makes sure the sweep describes at least a single run
+    ///
+    /// # Errors
+    ///
+    /// Returns SweepError::Invalid describing the first invalid value
+    pub fn validate(&self) -> Result<(), SweepError> {
+        if self.repeats == 0 {
+            return Err(SweepError::Invalid(String::from(
+                "repeats must be larger than 0",
+            )));
+        }
+        if self.sampling == Sampling::Random && self.samples == 0 {
+            return Err(SweepError::Invalid(String::from(
+                "samples must be larger than 0",
+            )));
+        }
+        if let Some(parameter) = self
+            .parameters
+            .iter()
+            .find(|parameter| parameter.values.is_empty())
+        {
+            return Err(SweepError::Invalid(format!(
+                "The parameter {} has no values",
+                parameter.path
+            )));
+        }
+
+        return Ok(());
+    }
+
+    /// Finds the values of all parameters at every point of the sweep
+    pub fn get_points(&self) -> Vec<Vec<f64>> {
+        if self.parameters.is_empty() {
+            return vec![Vec::new()];
+        }
+
+        return match self.sampling {
+            Sampling::Grid => self
+                .parameters
+                .iter()
+                .map(|parameter| parameter.values.iter().copied())
+                .multi_cartesian_product()
+                .collect(),
+            Sampling::Random => {
+                let mut rng = StdRng::seed_from_u64(self.seed);
+                (0..self.samples)
+                    .map(|_| {
+                        self.parameters
+                            .iter()
+                            .map(|parameter| {
+                                let min = parameter.values.iter().copied().fold(f64::MAX, f64::min);
+                                let max = parameter.values.iter().copied().fold(f64::MIN, f64::max);
+                                if min < max {
+                                    rng.random_range(min..max)
+                                } else {
+                                    min
+                                }
+                            })
+                            .collect()
+                    })
+                    .collect()
+            }
+        };
+    }
+
+    /// Runs the simulation for every point and repeat, writing a row of
+    /// results for each run, and returns the number of runs
+    ///
+    /// # Parameters
+    ///
+    /// config: The config to vary, presets and the gpu light are not used
+    ///
+    /// # Errors
+    ///
+    /// See SweepError for a description of the different errors which may occur
+    pub fn run(&self, config: &config::Config) -> Result<usize, SweepError> {
+        let base = toml::Value::try_from(config).map_err(|error| {
+            SweepError::Invalid(format!("Unable to read the config: {}", error))
+        })?;
+
+        // Make sure every parameter exists before running anything
+        for parameter in self.parameters.iter() {
+            set_value(&mut base.clone(), &parameter.path, parameter.values[0])?;
+        }
+
+        if let Some(directory) = self.output.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        let mut writer = BufWriter::new(File::create(&self.output)?);
+        let columns = self
+            .parameters
+            .iter()
+            .map(|parameter| parameter.path.as_str())
+            .chain([
+                "seed",
+                "reason",
+                "time",
+                "population",
+                "energy",
+                "peak_population",
+                "peak_time",
+            ])
+            .collect::<Vec<_>>();
+        writeln!(writer, "run,{}", columns.join(","))?;
+
+        let points = self.get_points();
+        let count = points.len() * self.repeats;
+        for (index, (values, repeat)) in
+            points.iter().cartesian_product(0..self.repeats).enumerate()
+        {
+            // Apply the point to the config
+            let mut value = base.clone();
+            for (parameter, x) in self.parameters.iter().zip(values.iter()) {
+                set_value(&mut value, &parameter.path, *x)?;
+            }
+            let mut config: config::Config = value.try_into().map_err(config::ConfigError::from)?;
+            config.map.settings.seed += repeat as u64;
+            config.viewer.step_limit = Some(self.steps);
+            config.validate()?;
+
+            let mut runner = runner::Runner::from_config(&config);
+            let reason = runner.run();
+            let recorder = runner.get_recorder();
+            let end = recorder.get_last();
+            let peak = recorder.get_peak();
+            println!(
+                "Run {}/{} ended at step {} with a population of {}: {}",
+                index + 1,
+                count,
+                end.time,
+                end.population,
+                reason
+            );
+
+            let row = values
+                .iter()
+                .map(|x| x.to_string())
+                .chain([
+                    config.map.settings.seed.to_string(),
+                    format!("\"{}\"", reason),
+                    end.time.to_string(),
+                    end.population.to_string(),
+                    end.energy.to_string(),
+                    peak.population.to_string(),
+                    peak.time.to_string(),
+                ])
+                .collect::<Vec<_>>();
+            writeln!(writer, "{},{}", index, row.join(","))?;
+            writer.flush()?;
+        }
+
+        return Ok(count);
+    }
+}
+
+/// Sets a number in a TOML value at a dotted path, integers are rounded
+///
+/// # Parameters
+///
+/// value: The TOML value to change
+///
+/// path: The dotted path of the number
+///
+/// x: The number to set
+///
+/// # Errors
+///
+/// Returns SweepError::Invalid if the path does not lead to a number
+fn set_value(value: &mut toml::Value, path: &str, x: f64) -> Result<(), SweepError> {
+    let target = path
+        .split('.')
+        .try_fold(value, |value, key| value.get_mut(key))
+        .ok_or_else(|| SweepError::Invalid(format!("Unknown setting \"{}\"", path)))?;
+
+    *target = match target {
+        toml::Value::Float(_) => toml::Value::Float(x),
+        toml::Value::Integer(_) => toml::Value::Integer(x.round() as i64),
+        _ => {
+            return Err(SweepError::Invalid(format!(
+                "The setting \"{}\" is not a number",
+                path
+            )));
+        }
+    };
+
+    return Ok(());
+}
+
+/// The error types for when loading or running a sweep
+#[derive(Error, Debug)]
+pub enum SweepError {
+    /// The sweep file could not be read or the results could not be written
+    #[error("Unable to access sweep file: {:?}", .0)]
+    Io(io::Error),
+    /// The content of the sweep file is not valid TOML or has the wrong types
+    #[error("Unable to parse sweep: {}", .0)]
+    Parse(toml::de::Error),
+    /// A value of the sweep is invalid
+    #[error("Invalid sweep: {}", .0)]
+    Invalid(String),
+    /// A point of the sweep gives an invalid config
+    #[error("{}", .0)]
+    Config(config::ConfigError),
+}
+
+impl From<io::Error> for SweepError {
+    fn from(value: io::Error) -> Self {
+        return Self::Io(value);
+    }
+}
+
+impl From<toml::de::Error> for SweepError {
+    fn from(value: toml::de::Error) -> Self {
+        return Self::Parse(value);
+    }
+}
+
+impl From<config::ConfigError> for SweepError {
+    fn from(value: config::ConfigError) -> Self {
+        return Self::Config(value);
+    }
+}
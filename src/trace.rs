@@ -0,0 +1,53 @@
+//! Sets up the logging of the application and the recording of the time
+//! spent in the phases of the simulation and the rendering
+
+use std::path::Path;
+
+use tracing_subscriber::{EnvFilter, Layer, filter::LevelFilter, prelude::*};
+
+/// Keeps the trace file open, the file is completed when it is dropped so it
+/// must be kept until the application ends
+pub struct TraceGuard {
+    /// Writes the end of the trace file when dropped
+    _chrome: Option<tracing_chrome::FlushGuard>,
+}
+
+/// Starts printing log messages to the terminal and optionally recording
+/// spans to a trace file which can be opened in chrome://tracing or Perfetto.
+/// The log messages are filtered by the RUST_LOG environment variable and
+/// only errors are printed if it is not set
+///
+/// # Parameters
+///
+/// trace_out: The file to record the spans to, None to not record spans
+///
+/// trace_level: The most detailed level of spans to record, the spans for
+/// every tile are only recorded at the trace level
+pub fn init(trace_out: Option<&Path>, trace_level: LevelFilter) -> TraceGuard {
+    let log_layer = tracing_subscriber::fmt::layer().with_filter(
+        EnvFilter::builder()
+            .with_default_directive(LevelFilter::ERROR.into())
+            .from_env_lossy(),
+    );
+
+    let (chrome_layer, chrome) = match trace_out {
+        Some(path) => {
+            let (layer, guard) = tracing_chrome::ChromeLayerBuilder::new()
+                .file(path)
+                .include_args(true)
+                .build();
+            (Some(layer.with_filter(trace_level)), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    if let Err(error) = tracing_subscriber::registry()
+        .with(log_layer)
+        .with(chrome_layer)
+        .try_init()
+    {
+        eprintln!("Unable to setup logging: {}", error);
+    }
+
+    return TraceGuard { _chrome: chrome };
+}
@@ -1,9 +1,10 @@
 use std::fmt::Debug;
 
 use dyn_clone::DynClone;
+use serde::{Deserialize, Serialize};
 
 /// Describes a single RGBA color
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Color {
     /// The red component
     r: f64,
@@ -31,6 +32,52 @@ impl Color {
         return Self { r, g, b, a };
     }
 
+    /// Constructs a new opaque color from a hex code like 0x52361e
+    ///
+    /// # Parameters
+    ///
+    /// hex: The red, green and blue components as the lower 24 bits
+    pub const fn from_hex(hex: u32) -> Self {
+        return Self {
+            r: ((hex >> 16) & 0xff) as f64 / 255.0,
+            g: ((hex >> 8) & 0xff) as f64 / 255.0,
+            b: (hex & 0xff) as f64 / 255.0,
+            a: 1.0,
+        };
+    }
+
+    /// Constructs a new opaque color from a hue, saturation and value
+    ///
+    /// # Parameters
+    ///
+    /// hue: The hue from 0 to 1 going from red through green and blue back
+    /// to red
+    ///
+    /// saturation: The saturation from 0 for gray to 1 for a pure color
+    ///
+    /// value: The brightness from 0 for black to 1
+    pub fn from_hsv(hue: f64, saturation: f64, value: f64) -> Self {
+        let sector = hue.rem_euclid(1.0) * 6.0;
+        let chroma = value * saturation;
+        let second = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+        let (r, g, b) = match sector as usize {
+            0 => (chroma, second, 0.0),
+            1 => (second, chroma, 0.0),
+            2 => (0.0, chroma, second),
+            3 => (0.0, second, chroma),
+            4 => (second, 0.0, chroma),
+            _ => (chroma, 0.0, second),
+        };
+        let offset = value - chroma;
+
+        return Self {
+            r: r + offset,
+            g: g + offset,
+            b: b + offset,
+            a: 1.0,
+        };
+    }
+
     /// Retrieves the red component of the color
     pub const fn get_r(&self) -> f64 {
         return self.r;
@@ -56,6 +103,8 @@ impl Color {
         return [self.r as f32, self.g as f32, self.b as f32, self.a as f32];
     }
 
+    /// Converts the color into the color type of wgpu
+    #[cfg(feature = "viewer")]
     pub const fn get_wgpu(&self) -> wgpu::Color {
         return wgpu::Color {
             r: self.r,
@@ -77,6 +126,13 @@ pub trait ColorMap: DynClone + Debug {
     /// Retrieves all the colors for the map
     fn get_colors(&self) -> [Color; 256];
 
+    /// Retrieves the stops the shader interpolates between in order of
+    /// increasing position, at most ColorMapGradient::MAX_STOPS, if empty
+    /// then the 256 colors are used instead
+    fn get_stops(&self) -> Vec<ColorStop> {
+        return Vec::new();
+    }
+
     /// Retrieves all the colors for the map as shader compatible data
     fn get_data(&self) -> UniformColorMap {
         let colors = self
@@ -86,9 +142,31 @@ pub trait ColorMap: DynClone + Debug {
             .collect::<Vec<_>>()
             .try_into()
             .unwrap();
-        let flags = [(self.get_continuous() as u32) & 1, 0, 0, 0];
 
-        return UniformColorMap { colors, flags };
+        // Pack the stops, the positions are packed 4 to a vector
+        let stops = self.get_stops();
+        let mut stop_colors = [[0.0; 4]; ColorMapGradient::MAX_STOPS];
+        let mut stop_positions = [[0.0; 4]; ColorMapGradient::MAX_STOPS / 4];
+        for (index, stop) in stops.iter().take(ColorMapGradient::MAX_STOPS).enumerate() {
+            stop_colors[index] = stop.color.get_data();
+            stop_positions[index / 4][index % 4] = stop.position as f32;
+        }
+        let stop_count = stops.len().min(ColorMapGradient::MAX_STOPS) as u32;
+
+        let flags = [
+            ((self.get_continuous() as u32) & 1) | (((stop_count > 0) as u32) << 1),
+            stop_count,
+            ColorScaling::Linear.id() as u32,
+            0,
+        ];
+
+        return UniformColorMap {
+            colors,
+            flags,
+            stop_colors,
+            stop_positions,
+            scaling: [0.0; 4],
+        };
     }
 }
 
@@ -103,11 +181,124 @@ pub struct UniformColorMap {
     /// All flags for the uniform, must be this big due to sizing in wgsl
     ///
     /// 0: If set then it is continuous
+    ///
+    /// 1: If set then the stops are interpolated instead of the colors
+    ///
+    /// The second value is the number of stops
+    ///
+    /// The third value is the id of the scaling of the values
     pub flags: [u32; 4],
+    /// The colors of the stops
+    pub stop_colors: [[f32; 4]; ColorMapGradient::MAX_STOPS],
+    /// The positions of the stops packed 4 to a vector due to sizing in wgsl
+    pub stop_positions: [[f32; 4]; ColorMapGradient::MAX_STOPS / 4],
+    /// The parameter of the scaling as the first value, the rest is padding
+    pub scaling: [f32; 4],
+}
+
+impl UniformColorMap {
+    /// Sets the scaling applied to the values before looking up the color
+    /// and returns the uniform
+    ///
+    /// # Parameters
+    ///
+    /// scaling: The scaling to set
+    ///
+    /// exponent: The exponent used by the exponent scaling
+    pub fn with_scaling(mut self, scaling: ColorScaling, exponent: f64) -> Self {
+        self.flags[2] = scaling.id() as u32;
+        self.scaling[0] = scaling.get_parameter(exponent) as f32;
+
+        return self;
+    }
+}
+
+/// The transform applied to the values of a continuous color map before the
+/// color is looked up, values stay within 0 to 1 but small values can be
+/// spread out to make dark regions distinguishable
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColorScaling {
+    /// Use the values as they are
+    Linear,
+    /// Scale the values logarithmically such that values down to around
+    /// 1 / LOG_RANGE are distinguishable from 0
+    Logarithmic,
+    /// Raise the values to the power of 1 / GAMMA
+    Gamma,
+    /// Raise the values to a custom power
+    Exponent,
+}
+
+impl ColorScaling {
+    pub const COUNT: usize = 4;
+    /// The ratio between the largest value and the smallest value which is
+    /// distinguishable from 0 for the logarithmic scaling
+    pub const LOG_RANGE: f64 = 1000.0;
+    /// The gamma of the gamma scaling
+    pub const GAMMA: f64 = 2.2;
+
+    /// The id to the scaling in a list of all scalings
+    pub fn id(&self) -> usize {
+        return match self {
+            Self::Linear => 0,
+            Self::Logarithmic => 1,
+            Self::Gamma => 2,
+            Self::Exponent => 3,
+        };
+    }
+
+    /// Constructs a new scaling from an id
+    ///
+    /// # Parameters
+    ///
+    /// id: The id to construct from
+    pub fn from_id(id: usize) -> Self {
+        return match id.clamp(0, Self::COUNT - 1) {
+            0 => Self::Linear,
+            1 => Self::Logarithmic,
+            2 => Self::Gamma,
+            3 => Self::Exponent,
+            _ => panic!("ColorScaling::from_id has not been updated"),
+        };
+    }
+
+    /// Gets the name of the scaling for displaying
+    pub fn get_name(&self) -> &'static str {
+        return match self {
+            Self::Linear => "Linear",
+            Self::Logarithmic => "Logarithmic",
+            Self::Gamma => "Gamma",
+            Self::Exponent => "Exponent",
+        };
+    }
+
+    /// Gets the next scaling
+    pub fn next(&self) -> Self {
+        return Self::from_id((self.id() + 1) % Self::COUNT);
+    }
+
+    /// Gets the previous scaling
+    pub fn prev(&self) -> Self {
+        return Self::from_id((self.id() + (Self::COUNT - 1)) % Self::COUNT);
+    }
+
+    /// Gets the parameter used by the shader for this scaling
+    ///
+    /// # Parameters
+    ///
+    /// exponent: The exponent used by the exponent scaling
+    pub fn get_parameter(&self, exponent: f64) -> f64 {
+        return match self {
+            Self::Linear => 0.0,
+            Self::Logarithmic => Self::LOG_RANGE,
+            Self::Gamma => 1.0 / Self::GAMMA,
+            Self::Exponent => exponent,
+        };
+    }
 }
 
 /// A color map with linear spacing in RGBA space between two colors
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ColorMapLinearRGBA {
     /// The color when it is the least saturated
     pub empty: Color,
@@ -183,3 +374,113 @@ impl ColorMap for ColorMapDiscrete {
             .unwrap();
     }
 }
+
+/// A single color of a gradient color map
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColorStop {
+    /// The value from 0 to 1 where the color is reached
+    pub position: f64,
+    /// The color at the position
+    pub color: Color,
+}
+
+impl ColorStop {
+    /// Constructs a new color stop
+    ///
+    /// # Parameters
+    ///
+    /// position: The value from 0 to 1 where the color is reached
+    ///
+    /// color: The color at the position
+    pub const fn new(position: f64, color: Color) -> Self {
+        return Self { position, color };
+    }
+}
+
+/// A continuous color map interpolating linearly in RGBA space between any
+/// number of stops, values before the first stop or after the last stop get
+/// the color of that stop
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColorMapGradient {
+    /// The stops to interpolate between
+    pub stops: Vec<ColorStop>,
+}
+
+impl ColorMapGradient {
+    /// The largest number of stops interpolated in the shader, with more
+    /// stops the 256 colors are used instead, must be a multiple of 4
+    pub const MAX_STOPS: usize = 8;
+
+    /// Constructs a new gradient color map
+    ///
+    /// # Parameters
+    ///
+    /// stops: The stops to interpolate between, they are sorted by position
+    pub fn new(mut stops: Vec<ColorStop>) -> Self {
+        stops.sort_by(|stop_1, stop_2| stop_1.position.total_cmp(&stop_2.position));
+
+        return Self { stops };
+    }
+
+    /// Gets the stops sorted by position
+    fn get_sorted_stops(&self) -> Vec<ColorStop> {
+        let mut stops = self.stops.clone();
+        stops.sort_by(|stop_1, stop_2| stop_1.position.total_cmp(&stop_2.position));
+
+        return stops;
+    }
+
+    /// Gets the color at a value by interpolating between the surrounding
+    /// stops, it is transparent if there are no stops
+    ///
+    /// # Parameters
+    ///
+    /// value: The value to get the color for
+    pub fn get_color(&self, value: f64) -> Color {
+        let stops = self.get_sorted_stops();
+        let (first, last) = match (stops.first(), stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Color::new(0.0, 0.0, 0.0, 0.0),
+        };
+        if value <= first.position {
+            return first.color;
+        }
+
+        return stops
+            .windows(2)
+            .find(|pair| value <= pair[1].position)
+            .map(|pair| {
+                let width = pair[1].position - pair[0].position;
+                let ratio = if width > 0.0 {
+                    (value - pair[0].position) / width
+                } else {
+                    1.0
+                };
+                Color {
+                    r: ratio * pair[1].color.r + (1.0 - ratio) * pair[0].color.r,
+                    g: ratio * pair[1].color.g + (1.0 - ratio) * pair[0].color.g,
+                    b: ratio * pair[1].color.b + (1.0 - ratio) * pair[0].color.b,
+                    a: ratio * pair[1].color.a + (1.0 - ratio) * pair[0].color.a,
+                }
+            })
+            .unwrap_or(last.color);
+    }
+}
+
+impl ColorMap for ColorMapGradient {
+    fn get_colors(&self) -> [Color; 256] {
+        return (0..256)
+            .map(|index| self.get_color(index as f64 / 255.0))
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+    }
+
+    fn get_stops(&self) -> Vec<ColorStop> {
+        if self.stops.len() > Self::MAX_STOPS {
+            return Vec::new();
+        }
+
+        return self.get_sorted_stops();
+    }
+}
@@ -1,9 +1,11 @@
 use std::ops::Add;
 
+use serde::{Deserialize, Serialize};
+
 use super::Point;
 
 /// A 2D index
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Index {
     /// The x-index
     pub x: isize,
@@ -20,4 +20,7 @@ mod transform2d;
 pub use transform2d::{Transform2D, UniformTransform2D};
 
 mod color;
-pub use color::{Color, ColorMap, ColorMapDiscrete, ColorMapLinearRGBA, UniformColorMap};
+pub use color::{
+    Color, ColorMap, ColorMapDiscrete, ColorMapGradient, ColorMapLinearRGBA, ColorScaling,
+    ColorStop, UniformColorMap,
+};
@@ -0,0 +1,169 @@
+use wasm_bindgen::{JsCast, prelude::*};
+
+use crate::{application, config, map};
+
+/// The sun of a map shown in a web page with both a yearly and daily cycle,
+/// it is set up from JavaScript before the simulation is started
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WebSun {
+    /// The settings of the sun
+    settings: config::map::SunSettings,
+}
+
+#[wasm_bindgen]
+impl WebSun {
+    /// Constructs the default sun
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        return Self {
+            settings: config::map::SunSettings::new(),
+        };
+    }
+
+    /// The tilt of the planet axis in degrees
+    #[wasm_bindgen(getter)]
+    pub fn tilt(&self) -> f64 {
+        return self.settings.tilt;
+    }
+
+    /// Sets the tilt of the planet axis in degrees
+    ///
+    /// # Parameters
+    ///
+    /// tilt: The new tilt
+    #[wasm_bindgen(setter)]
+    pub fn set_tilt(&mut self, tilt: f64) {
+        self.settings.tilt = tilt;
+    }
+
+    /// The latitude of the map in degrees
+    #[wasm_bindgen(getter)]
+    pub fn latitude(&self) -> f64 {
+        return self.settings.latitude;
+    }
+
+    /// Sets the latitude of the map in degrees
+    ///
+    /// # Parameters
+    ///
+    /// latitude: The new latitude
+    #[wasm_bindgen(setter)]
+    pub fn set_latitude(&mut self, latitude: f64) {
+        self.settings.latitude = latitude;
+    }
+
+    /// The number of steps in a year
+    #[wasm_bindgen(getter)]
+    pub fn year(&self) -> f64 {
+        return self.settings.year;
+    }
+
+    /// Sets the number of steps in a year
+    ///
+    /// # Parameters
+    ///
+    /// year: The new number of steps
+    #[wasm_bindgen(setter)]
+    pub fn set_year(&mut self, year: f64) {
+        self.settings.year = year;
+    }
+
+    /// The number of steps in a day
+    #[wasm_bindgen(getter)]
+    pub fn day(&self) -> f64 {
+        return self.settings.day;
+    }
+
+    /// Sets the number of steps in a day
+    ///
+    /// # Parameters
+    ///
+    /// day: The new number of steps
+    #[wasm_bindgen(setter)]
+    pub fn set_day(&mut self, day: f64) {
+        self.settings.day = day;
+    }
+
+    /// The intensity of the sun
+    #[wasm_bindgen(getter)]
+    pub fn intensity(&self) -> f64 {
+        return self.settings.intensity;
+    }
+
+    /// Sets the intensity of the sun
+    ///
+    /// # Parameters
+    ///
+    /// intensity: The new intensity
+    #[wasm_bindgen(setter)]
+    pub fn set_intensity(&mut self, intensity: f64) {
+        self.settings.intensity = intensity;
+    }
+}
+
+impl WebSun {
+    /// Constructs the sun intensity with both a yearly and daily cycle
+    pub fn get_intensity(&self) -> map::presets::IntensityPlanet {
+        return self.settings.get_intensity();
+    }
+}
+
+/// Starts the simulation in a web page, the page keeps running it after this
+/// returns
+///
+/// # Parameters
+///
+/// canvas_id: The id of the canvas to draw onto, if it is empty a new canvas
+/// is added to the page
+///
+/// config: The config as TOML like config.example.toml, the default config is
+/// used if it is empty. Presets are not available as a page has no files
+///
+/// sun: The sun lighting the map, it replaces the sun of the config
+///
+/// # Errors
+///
+/// Returns the error message if the config is invalid, the canvas could not
+/// be found or the event loop could not be created
+#[wasm_bindgen]
+pub fn start(canvas_id: &str, config: &str, sun: &WebSun) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let mut config = if config.is_empty() {
+        config::Config::new()
+    } else {
+        config::Config::parse(config).map_err(|error| JsValue::from_str(&error.to_string()))?
+    };
+    config.map.sun = sun.settings;
+
+    let map = map::Map::new(
+        config.map.get_size(),
+        config.map.settings,
+        sun.get_intensity(),
+    );
+    let presets = map::settings::preset::PresetLibrary::new(&config.viewer.preset_directory);
+    let mut main_loop = application::MainLoop::from_config(map, &config, presets);
+    if !canvas_id.is_empty() {
+        main_loop = main_loop.with_canvas(get_canvas(canvas_id)?);
+    }
+
+    return application::run_web(main_loop).map_err(|error| JsValue::from_str(&error.to_string()));
+}
+
+/// Finds a canvas of the web page
+///
+/// # Parameters
+///
+/// id: The id of the canvas
+///
+/// # Errors
+///
+/// Returns the error message if there is no canvas with the id
+fn get_canvas(id: &str) -> Result<web_sys::HtmlCanvasElement, JsValue> {
+    return web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(id))
+        .and_then(|element| element.dyn_into::<web_sys::HtmlCanvasElement>().ok())
+        .ok_or_else(|| JsValue::from_str(&format!("There is no canvas with the id {}", id)));
+}
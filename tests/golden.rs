@@ -0,0 +1,157 @@
+//! Runs the simulation with fixed seeds and settings and compares a hash of
+//! the final state of every tile against a stored golden value, such that
+//! changes to the simulation core which alter its behavior are noticed. If a
+//! change of behavior is intended the golden values are replaced by the
+//! hashes printed by the failing tests. The same run is also repeated and
+//! compared tile by tile, such that behavior depending on anything but the
+//! seed is noticed
+
+use plant_growth_simulation::{config, map, types};
+
+/// The size of the simulated maps
+const SIZE: types::ISize = types::ISize { w: 48, h: 32 };
+
+/// The number of steps simulated before the state is hashed
+const STEPS: usize = 200;
+
+/// The sun intensity of the default settings
+type Intensity =
+    map::sun::IntensityYearDay<map::sun::IntensityYearPlanet, map::sun::IntensityDayPlanet>;
+
+/// Constructs a map where plants of every bulk type are placed in a fixed
+/// pattern and simulates it
+///
+/// # Parameters
+///
+/// settings: The simulation settings
+fn run(settings: map::settings::Settings) -> map::Map<Intensity> {
+    let sun = config::map::Settings::new().sun.get_intensity();
+    let mut map = map::Map::new(SIZE, settings, sun);
+    for y in 0..SIZE.h {
+        for x in 0..SIZE.w {
+            if (7 * x + 3 * y) % 11 == 0 {
+                let bulk = map::EditBulk::from_id((x + y) % map::EditBulk::COUNT);
+                map.place_plant(&types::Index::new(x as isize, y as isize), bulk);
+            }
+        }
+    }
+
+    for _ in 0..STEPS {
+        map.step();
+    }
+
+    return map;
+}
+
+/// The number of decimals the values of the state are rounded to before
+/// hashing, such that the last bits of the floating point math, which may
+/// differ between platforms, do not change the hash
+const DECIMALS: i32 = 6;
+
+/// Rounds a value of the state to the decimals which are hashed
+///
+/// # Parameters
+///
+/// value: The value to round
+fn quantize(value: f64) -> f64 {
+    let scale = 10f64.powi(DECIMALS);
+
+    return (value * scale).round() / scale;
+}
+
+/// Hashes the state of every tile with FNV-1a, the state is written as the
+/// snapshot of the map with rounded values followed by the genome of every
+/// plant in the genome text format
+///
+/// # Parameters
+///
+/// map: The map to hash
+fn hash(map: &map::Map<Intensity>) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let mut feed = |text: &str| {
+        for byte in text.bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    };
+
+    let mut snapshot = map.get_snapshot();
+    for tile in snapshot.tiles.iter_mut() {
+        tile.light = quantize(tile.light);
+        tile.energy = tile.energy.map(quantize);
+    }
+    feed(&toml::to_string(&snapshot).expect("A snapshot can always be serialized"));
+
+    for y in 0..SIZE.h {
+        for x in 0..SIZE.w {
+            if let Some(genome) = map.get_genome(&types::Index::new(x as isize, y as isize), false)
+            {
+                feed(&genome);
+            }
+        }
+    }
+
+    return hash;
+}
+
+/// Runs the simulation and checks the hash of the final state
+///
+/// # Parameters
+///
+/// settings: The simulation settings
+///
+/// golden: The hash of the final state of the original simulation
+fn check(settings: map::settings::Settings, golden: u64) {
+    let map = run(settings);
+    let hash = hash(&map);
+    assert!(
+        map.get_statistics().population > 0,
+        "All plants died so the run covers too little of the simulation"
+    );
+    assert_eq!(
+        hash, golden,
+        "The final state has changed, the new hash is {:#018x}",
+        hash
+    );
+}
+
+/// The default settings covering the plants, their bridges and the light
+/// falling straight down
+#[test]
+fn golden_default() {
+    check(
+        map::settings::Settings::new().with_seed(1),
+        0x973dd6b3603e3a38,
+    );
+}
+
+/// Diffuse and reflected light falling at the angle of the sun onto raised
+/// terrain
+#[test]
+fn golden_light() {
+    let settings = map::settings::Settings::new()
+        .with_seed(2)
+        .with_sky(
+            map::settings::sky::Settings::new()
+                .with_intensity(0.3)
+                .with_angled(true),
+        )
+        .with_transparency(map::settings::transparency::Settings::new().with_albedo(0.2))
+        .with_terrain(map::settings::terrain::Settings::new().with_height(6));
+    check(settings, 0x0105214c8e14aacf);
+}
+
+/// Runs the simulation twice with the same settings and lists every tile
+/// which differs between the runs
+#[test]
+fn deterministic() {
+    let settings = map::settings::Settings::new().with_seed(3);
+    let first = run(settings).get_snapshot();
+    let second = run(settings).get_snapshot();
+
+    let diff = first
+        .diff(&second, 0.0)
+        .expect("Both runs have the same size");
+    assert!(diff.is_empty(), "The runs differ:\n{}", diff.report(20));
+    assert_eq!(first, second);
+}